@@ -1,6 +1,7 @@
 //! Codeberg adapter implementing `ForgePort` trait
 //!
-//! Uses the Gitea/Forgejo API v1 (Codeberg runs Forgejo).
+//! Uses the Gitea/Forgejo API v1 (Codeberg runs Forgejo), so the same adapter
+//! also serves self-hosted Gitea/Forgejo instances as [`GiteaAdapter`].
 
 use async_trait::async_trait;
 use reqwest::{Client, header, Response};
@@ -9,8 +10,11 @@ use std::sync::Arc;
 use tokio::task::JoinSet;
 
 use crate::auth::AuthProvider;
-use crate::types::{Forge, OwnerType, Repo, Visibility};
-use super::{ForgeError, ForgePort, ForgeResult, ListResult};
+use crate::scheduler::LimitedSend;
+use crate::types::repo::normalize_topics;
+use crate::types::{Forge, OwnerType, Repo, Visibility, WebhookEvent};
+use super::rate_limit::RateLimitHeaders;
+use super::{BranchProtection, CheckState, DeployKey, ForgeError, ForgePort, ForgeResult, ForkInfo, Issue, IssuePort, IssueState, ListResult, MergeSettings, NewDeployKey, NewIssue, NewPullRequest, NewWebhook, PullRequest, PullRequestState, StorageUsage, UpstreamStatus, Webhook};
 
 /// Codeberg API base URL
 const CODEBERG_API_URL: &str = "https://codeberg.org/api/v1";
@@ -22,9 +26,188 @@ struct CodebergRepo {
     description: Option<String>,
     private: bool,
     #[serde(default)]
+    topics: Vec<String>,
+    #[serde(default)]
     archived: bool,
     #[serde(default)]
     default_branch: Option<String>,
+    /// The token's permissions (omitted for unauthenticated requests)
+    #[serde(default)]
+    permissions: Option<CodebergPermissions>,
+}
+
+/// Fork fields of a single-repo response
+#[derive(Debug, Deserialize)]
+struct CodebergForkFields {
+    #[serde(default)]
+    fork: bool,
+    #[serde(default)]
+    parent: Option<CodebergParent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodebergParent {
+    full_name: String,
+    #[serde(default)]
+    archived: bool,
+}
+
+/// Size field of a single-repo response (KiB)
+#[derive(Debug, Deserialize)]
+struct CodebergSize {
+    #[serde(default)]
+    size: u64,
+}
+
+/// Merge settings of a single-repo response
+#[derive(Debug, Deserialize)]
+#[allow(clippy::struct_field_names)] // mirrors the API's field names
+struct CodebergMergeFields {
+    allow_merge_commits: Option<bool>,
+    allow_squash_merge: Option<bool>,
+    allow_rebase: Option<bool>,
+    #[serde(default)]
+    allow_rebase_explicit: bool,
+}
+
+/// Pull request list entry
+#[derive(Debug, Deserialize)]
+struct CodebergPull {
+    number: u64,
+    title: String,
+    html_url: String,
+    user: CodebergLogin,
+    head: CodebergPullHead,
+    base: CodebergPullHead,
+    #[serde(default)]
+    draft: bool,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodebergLogin {
+    login: String,
+}
+
+/// Issue list entry
+#[derive(Debug, Deserialize)]
+struct CodebergIssue {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    labels: Vec<CodebergLabel>,
+    state: String,
+    html_url: String,
+}
+
+impl From<CodebergIssue> for Issue {
+    fn from(issue: CodebergIssue) -> Self {
+        Self {
+            number: issue.number,
+            title: issue.title,
+            body: issue.body.unwrap_or_default(),
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            state: if issue.state == "closed" { IssueState::Closed } else { IssueState::Open },
+            url: issue.html_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CodebergPullHead {
+    #[serde(rename = "ref")]
+    ref_name: String,
+    sha: String,
+}
+
+/// Combined commit status (Woodpecker and Forgejo Actions both report here)
+#[derive(Debug, Deserialize)]
+struct CodebergCombinedStatus {
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    total_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodebergPermissionFields {
+    #[serde(default)]
+    permissions: Option<CodebergPermissions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodebergPermissions {
+    push: bool,
+    #[serde(default)]
+    admin: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodebergForkOwner {
+    owner: CodebergLogin,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodebergLabel {
+    id: u64,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodebergPullState {
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    head: CodebergPullHead,
+}
+
+/// Branch protection response
+#[derive(Debug, Deserialize)]
+struct CodebergProtection {
+    #[serde(default)]
+    required_approvals: u32,
+    #[serde(default)]
+    enable_status_check: bool,
+    #[serde(default)]
+    status_check_contexts: Vec<String>,
+    /// Missing before Gitea 1.22, where protected branches refuse force-pushes
+    #[serde(default)]
+    enable_force_push: bool,
+}
+
+/// Repository webhook response from API
+#[derive(Debug, Deserialize)]
+struct CodebergHook {
+    id: u64,
+    active: bool,
+    #[serde(default)]
+    events: Vec<String>,
+    config: CodebergHookConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodebergHookConfig {
+    #[serde(default)]
+    url: String,
+}
+
+/// Repository deploy key response from API
+#[derive(Debug, Deserialize)]
+struct CodebergDeployKey {
+    id: u64,
+    #[serde(default)]
+    title: String,
+    key: String,
+    #[serde(default)]
+    read_only: bool,
+}
+
+impl From<CodebergDeployKey> for DeployKey {
+    fn from(k: CodebergDeployKey) -> Self {
+        Self { id: k.id, title: k.title, key: k.key, read_only: k.read_only }
+    }
 }
 
 /// Request body for creating a repository
@@ -55,6 +238,12 @@ struct RenameRepoRequest {
     name: String,
 }
 
+/// Request body for transferring a repository to another owner
+#[derive(Debug, Serialize)]
+struct TransferRepoRequest {
+    new_owner: String,
+}
+
 /// Codeberg adapter for `ForgePort` trait
 pub struct CodebergAdapter {
     client: Client,
@@ -62,8 +251,16 @@ pub struct CodebergAdapter {
     api_url: String,
     org: String,
     owner_type: Option<OwnerType>,
+    /// `Forge::Codeberg`, or the `Forge::Gitea` host this adapter talks to
+    forge: Forge,
+    /// Forge name used in error messages
+    label: String,
 }
 
+/// Adapter for a self-hosted Gitea/Forgejo instance: the Codeberg adapter
+/// pointed at another host (see [`CodebergAdapter::gitea`])
+pub type GiteaAdapter = CodebergAdapter;
+
 impl CodebergAdapter {
     /// Create a new `CodebergAdapter` with the given auth provider
     pub fn new(auth: Arc<dyn AuthProvider>, org: impl Into<String>) -> ForgeResult<Self> {
@@ -77,7 +274,24 @@ impl CodebergAdapter {
             .build()
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
-        Ok(Self { client, auth, api_url, org: org.into(), owner_type: None })
+        Ok(Self {
+            client,
+            auth,
+            api_url,
+            org: org.into(),
+            owner_type: None,
+            forge: Forge::Codeberg,
+            label: "Codeberg".to_string(),
+        })
+    }
+
+    /// Create an adapter for the Gitea/Forgejo instance at `host`, using
+    /// `https://{host}/api/v1` and the token at `{host}/{org}/token`
+    pub fn gitea(auth: Arc<dyn AuthProvider>, org: impl Into<String>, host: &str) -> ForgeResult<Self> {
+        let mut adapter = Self::with_api_url(auth, org, format!("https://{host}/api/v1"))?;
+        adapter.forge = Forge::Gitea { host: host.to_string() };
+        adapter.label = host.to_string();
+        Ok(adapter)
     }
 
     /// Set the owner type for this adapter (user vs org)
@@ -88,21 +302,39 @@ impl CodebergAdapter {
 
     /// Get authorization headers with token from auth provider
     async fn auth_headers(&self) -> ForgeResult<header::HeaderMap> {
-        // Construct secret path: codeberg/{org}/token
-        let secret_path = format!("codeberg/{}/token", self.org);
-        let token = self.auth.get_secret(&secret_path).await
-            .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })?
+        let token = self.token().await?
             .ok_or_else(|| ForgeError::AuthenticationFailed {
-                message: format!("No Codeberg token found for org: {}", self.org),
+                message: format!("No {} token found for org: {}", self.label, self.org),
             })?;
+        Self::headers(Some(&token))
+    }
+
+    /// The org's stored token, if any
+    async fn token(&self) -> ForgeResult<Option<String>> {
+        // Construct secret path: codeberg/{org}/token ({host}/{org}/token when self-hosted)
+        let secret_path = format!("{}/{}/token", self.forge, self.org);
+        self.auth.get_secret(&secret_path).await
+            .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })
+    }
+
+    /// Headers for read-only requests: authenticated when a token is
+    /// stored, anonymous otherwise (public repos only). A failing auth
+    /// provider is an error, not a reason to go anonymous
+    async fn read_headers(&self) -> ForgeResult<header::HeaderMap> {
+        let token = self.token().await?;
+        Self::headers(token.as_deref())
+    }
 
+    fn headers(token: Option<&str>) -> ForgeResult<header::HeaderMap> {
         let mut headers = header::HeaderMap::new();
         // Gitea/Forgejo uses "token" instead of "Bearer"
-        headers.insert(
-            header::AUTHORIZATION,
-            header::HeaderValue::from_str(&format!("token {token}"))
-                .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })?,
-        );
+        if let Some(token) = token {
+            headers.insert(
+                header::AUTHORIZATION,
+                header::HeaderValue::from_str(&format!("token {token}"))
+                    .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })?,
+            );
+        }
         headers.insert(
             header::ACCEPT,
             header::HeaderValue::from_static("application/json"),
@@ -111,6 +343,48 @@ impl CodebergAdapter {
         Ok(headers)
     }
 
+    /// Map a non-success response to a `ForgeError`, passing successes through
+    async fn expect_success(&self, response: Response, name: &str) -> ForgeResult<Response> {
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "{} API error {status}: {body}", self.label
+            )));
+        }
+        Ok(response)
+    }
+
+    fn combined_status_state(status: &CodebergCombinedStatus) -> CheckState {
+        if status.total_count == 0 {
+            return CheckState::Unknown;
+        }
+        match status.state.as_str() {
+            "success" | "warning" => CheckState::Success,
+            "pending" => CheckState::Pending,
+            "" => CheckState::Unknown,
+            _ => CheckState::Failure,
+        }
+    }
+
+    /// Fork relationship from a repo's `fork`/`parent` fields: `None` for a
+    /// non-fork, `Deleted` when the parent is gone
+    fn to_fork_info(fields: CodebergForkFields) -> Option<ForkInfo> {
+        if !fields.fork {
+            return None;
+        }
+        Some(match fields.parent {
+            Some(parent) => ForkInfo {
+                status: if parent.archived { UpstreamStatus::Archived } else { UpstreamStatus::Active },
+                upstream: Some(parent.full_name),
+            },
+            None => ForkInfo { upstream: None, status: UpstreamStatus::Deleted },
+        })
+    }
+
     /// Convert Codeberg API response to our Repo type
     fn to_repo(cb_repo: CodebergRepo) -> Repo {
         Repo {
@@ -125,10 +399,19 @@ impl CodebergAdapter {
             mirrors: Vec::new(),
             protected: cb_repo.archived,
             staged_for_deletion: false,
+            archived: cb_repo.archived,
             default_branch: cb_repo.default_branch,
+            read_only: cb_repo.permissions.is_some_and(|p| !p.admin),
+            topics: normalize_topics(&cb_repo.topics),
+            visibility_on: std::collections::BTreeMap::new(),
         }
     }
 
+    /// `to_repo` with this adapter's forge as origin
+    fn repo_from(&self, cb_repo: CodebergRepo) -> Repo {
+        Repo { origin: self.forge.clone(), ..Self::to_repo(cb_repo) }
+    }
+
     /// Parse X-Total-Count header from Codeberg/Gitea response to compute total pages.
     /// Returns the total number of pages (`ceil(total_count` / `per_page`)).
     fn parse_total_pages(response: &Response, per_page: u32) -> Option<u32> {
@@ -153,7 +436,7 @@ impl CodebergAdapter {
         let first_repos: Vec<CodebergRepo> = first_response.json().await
             .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
 
-        let mut all_repos: Vec<Repo> = first_repos.into_iter().map(Self::to_repo).collect();
+        let mut all_repos: Vec<Repo> = first_repos.into_iter().map(|r| self.repo_from(r)).collect();
 
         // If there's only one page or we couldn't determine total, we're done
         let total_pages = match total_pages {
@@ -162,7 +445,7 @@ impl CodebergAdapter {
         };
 
         // Fetch remaining pages in parallel (pages 2..=total_pages)
-        let headers = self.auth_headers().await?;
+        let headers = self.read_headers().await?;
         let mut join_set = JoinSet::new();
 
         let separator = if base_url.contains('?') { '&' } else { '?' };
@@ -170,11 +453,13 @@ impl CodebergAdapter {
             let client = self.client.clone();
             let hdrs = headers.clone();
             let url = format!("{base_url}{separator}page={page}");
+            let forge = self.forge.clone();
+            let label = self.label.clone();
 
             join_set.spawn(async move {
                 let response = client.get(&url)
                     .headers(hdrs)
-                    .send()
+                    .send_limited(forge)
                     .await
                     .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -182,7 +467,7 @@ impl CodebergAdapter {
                     let status = response.status();
                     let body = response.text().await.unwrap_or_default();
                     return Err(ForgeError::ApiError(format!(
-                        "Codeberg API error {status}: {body}"
+                        "{label} API error {status}: {body}"
                     )));
                 }
 
@@ -197,7 +482,7 @@ impl CodebergAdapter {
                 if let Some(result) = join_set.join_next().await {
                     let repos = result
                         .map_err(|e| ForgeError::ApiError(format!("Task join error: {e}")))??;
-                    all_repos.extend(repos.into_iter().map(Self::to_repo));
+                    all_repos.extend(repos.into_iter().map(|r| self.repo_from(r)));
                 }
             }
         }
@@ -206,27 +491,48 @@ impl CodebergAdapter {
         while let Some(result) = join_set.join_next().await {
             let repos = result
                 .map_err(|e| ForgeError::ApiError(format!("Task join error: {e}")))??;
-            all_repos.extend(repos.into_iter().map(Self::to_repo));
+            all_repos.extend(repos.into_iter().map(|r| self.repo_from(r)));
         }
 
+        // Pages finish in any order; sort so every run lists the same way
+        all_repos.sort_by(|a, b| a.name.cmp(&b.name));
+
         Ok(all_repos)
     }
 }
 
+/// Rate limit state from a Gitea/Forgejo response. Instances that enable
+/// limits answer 429 with `Retry-After`; some proxies add `X-RateLimit-*`.
+pub(crate) fn rate_limit_headers(headers: &header::HeaderMap, now: i64) -> RateLimitHeaders {
+    RateLimitHeaders::parse(headers, "x-ratelimit-remaining", "x-ratelimit-reset", now)
+}
+
 #[async_trait]
 impl ForgePort for CodebergAdapter {
+    fn forge(&self) -> Option<Forge> {
+        Some(self.forge.clone())
+    }
+
+    fn issues(&self) -> Option<&dyn IssuePort> {
+        Some(self)
+    }
+
+    async fn has_token(&self) -> bool {
+        matches!(self.token().await, Ok(Some(_)))
+    }
+
     async fn list_repos(&self, org: &str) -> ForgeResult<Vec<Repo>> {
         // If we know this is a user account, skip the org endpoint entirely
         if self.owner_type == Some(OwnerType::User) {
             return self.list_user_repos(org).await;
         }
 
-        let headers = self.auth_headers().await?;
+        let headers = self.read_headers().await?;
         let base_url = format!("{}/orgs/{}/repos?limit=100", self.api_url, org);
 
         let response = self.client.get(&base_url)
             .headers(headers)
-            .send()
+            .send_limited(self.forge.clone())
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -239,7 +545,7 @@ impl ForgePort for CodebergAdapter {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             return Err(ForgeError::ApiError(format!(
-                "Codeberg API error {status}: {body}"
+                "{} API error {status}: {body}", self.label
             )));
         }
 
@@ -247,12 +553,12 @@ impl ForgePort for CodebergAdapter {
     }
 
     async fn get_repo(&self, org: &str, name: &str) -> ForgeResult<Repo> {
-        let headers = self.auth_headers().await?;
+        let headers = self.read_headers().await?;
         let url = format!("{}/repos/{}/{}", self.api_url, org, name);
 
         let response = self.client.get(&url)
             .headers(headers)
-            .send()
+            .send_limited(self.forge.clone())
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -264,20 +570,440 @@ impl ForgePort for CodebergAdapter {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             return Err(ForgeError::ApiError(format!(
-                "Codeberg API error {status}: {body}"
+                "{} API error {status}: {body}", self.label
             )));
         }
 
         let cb_repo: CodebergRepo = response.json().await
             .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
 
-        Ok(Self::to_repo(cb_repo))
+        Ok(self.repo_from(cb_repo))
+    }
+
+    async fn fork_info(&self, org: &str, name: &str) -> ForgeResult<Option<ForkInfo>> {
+        let headers = self.auth_headers().await?;
+        let url = format!("{}/repos/{}/{}", self.api_url, org, name);
+
+        let response = self.client.get(&url)
+            .headers(headers)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "{} API error {status}: {body}", self.label
+            )));
+        }
+
+        let fields: CodebergForkFields = response.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(Self::to_fork_info(fields))
+    }
+
+    async fn storage_usage(&self, org: &str, name: &str) -> ForgeResult<Option<StorageUsage>> {
+        let headers = self.auth_headers().await?;
+        let url = format!("{}/repos/{}/{}", self.api_url, org, name);
+
+        let response = self.client.get(&url)
+            .headers(headers)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "{} API error {status}: {body}", self.label
+            )));
+        }
+
+        let size: CodebergSize = response.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(Some(StorageUsage {
+            repo_bytes: size.size * 1024,
+            ..StorageUsage::default()
+        }))
+    }
+
+    async fn merge_settings(&self, org: &str, name: &str) -> ForgeResult<Option<MergeSettings>> {
+        let headers = self.auth_headers().await?;
+        let url = format!("{}/repos/{}/{}", self.api_url, org, name);
+
+        let response = self.client.get(&url)
+            .headers(headers)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "{} API error {status}: {body}", self.label
+            )));
+        }
+
+        let fields: CodebergMergeFields = response.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(match (fields.allow_merge_commits, fields.allow_squash_merge, fields.allow_rebase) {
+            (Some(merge_commit), Some(squash), Some(rebase)) => Some(MergeSettings {
+                merge_commit,
+                squash,
+                rebase: rebase || fields.allow_rebase_explicit,
+            }),
+            _ => None,
+        })
+    }
+
+    async fn list_pull_requests(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<PullRequest>>> {
+        let headers = self.auth_headers().await?;
+        let mut pulls = Vec::new();
+        for page in 1.. {
+            let url = format!("{}/repos/{}/{}/pulls?state=open&limit=50&page={page}", self.api_url, org, name);
+            let response = self.client.get(&url)
+                .headers(headers.clone())
+                .send_limited(self.forge.clone())
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+            let batch: Vec<CodebergPull> = self.expect_success(response, name).await?.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            let last = batch.len() < 50;
+            pulls.extend(batch.into_iter().map(|p| PullRequest {
+                number: p.number,
+                title: p.title,
+                author: p.user.login,
+                head_branch: p.head.ref_name,
+                head_sha: p.head.sha,
+                base_branch: p.base.ref_name,
+                draft: p.draft,
+                url: p.html_url,
+                created_at: p.created_at,
+            }));
+            if last {
+                break;
+            }
+        }
+        Ok(Some(pulls))
+    }
+
+    async fn branch_protection(&self, org: &str, name: &str, branch: &str) -> ForgeResult<Option<BranchProtection>> {
+        let url = format!("{}/repos/{}/{}/branch_protections/{}", self.api_url, org, name, urlencoding::encode(branch));
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        // 404: no rule for the branch
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Some(BranchProtection::default()));
+        }
+        let protection: CodebergProtection = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        let mut status_checks = if protection.enable_status_check { protection.status_check_contexts } else { Vec::new() };
+        status_checks.sort();
+        Ok(Some(BranchProtection {
+            required_reviews: protection.required_approvals,
+            status_checks,
+            block_force_push: !protection.enable_force_push,
+        }))
+    }
+
+    async fn set_branch_protection(&self, org: &str, name: &str, branch: &str, rule: &BranchProtection) -> ForgeResult<bool> {
+        let headers = self.auth_headers().await?;
+        let rule_url = format!("{}/repos/{}/{}/branch_protections/{}", self.api_url, org, name, urlencoding::encode(branch));
+        let existing = self.client.get(&rule_url)
+            .headers(headers.clone())
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        let body = serde_json::json!({
+            "rule_name": branch,
+            "branch_name": branch,
+            "enable_push": true,
+            "required_approvals": rule.required_reviews,
+            "enable_status_check": !rule.status_checks.is_empty(),
+            "status_check_contexts": rule.status_checks,
+            "enable_force_push": !rule.block_force_push,
+        });
+        let builder = if existing.status() == reqwest::StatusCode::NOT_FOUND {
+            self.client.post(format!("{}/repos/{}/{}/branch_protections", self.api_url, org, name))
+        } else {
+            self.expect_success(existing, name).await?;
+            self.client.patch(&rule_url)
+        };
+        let response = builder
+            .headers(headers)
+            .json(&body)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        self.expect_success(response, name).await?;
+        Ok(true)
+    }
+
+    async fn list_webhooks(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<Webhook>>> {
+        let url = format!("{}/repos/{}/{}/hooks?limit=50", self.api_url, org, name);
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let hooks: Vec<CodebergHook> = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(Some(hooks.into_iter().map(|h| Webhook {
+            id: h.id,
+            url: h.config.url,
+            events: h.events.iter().filter_map(|e| WebhookEvent::parse(e)).collect(),
+            active: h.active,
+        }).collect()))
+    }
+
+    async fn create_webhook(&self, org: &str, name: &str, hook: &NewWebhook) -> ForgeResult<Option<u64>> {
+        let url = format!("{}/repos/{}/{}/hooks", self.api_url, org, name);
+        let events: Vec<&str> = hook.events.iter().map(|e| e.as_str()).collect();
+        let mut config = serde_json::json!({ "url": hook.url, "content_type": "json" });
+        if let Some(secret) = &hook.secret {
+            config["secret"] = serde_json::json!(secret);
+        }
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({
+                "type": "gitea",
+                "active": true,
+                "events": events,
+                "config": config,
+            }))
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let created: CodebergHook = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(created.id))
+    }
+
+    async fn delete_webhook(&self, org: &str, name: &str, id: u64) -> ForgeResult<bool> {
+        let url = format!("{}/repos/{}/{}/hooks/{id}", self.api_url, org, name);
+        let response = self.client.delete(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        self.expect_success(response, name).await?;
+        Ok(true)
+    }
+
+    async fn list_deploy_keys(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<DeployKey>>> {
+        let url = format!("{}/repos/{}/{}/keys?limit=50", self.api_url, org, name);
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let keys: Vec<CodebergDeployKey> = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(keys.into_iter().map(DeployKey::from).collect()))
+    }
+
+    async fn add_deploy_key(&self, org: &str, name: &str, key: &NewDeployKey) -> ForgeResult<Option<u64>> {
+        let url = format!("{}/repos/{}/{}/keys", self.api_url, org, name);
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({
+                "title": key.title,
+                "key": key.key,
+                "read_only": key.read_only,
+            }))
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let created: CodebergDeployKey = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(created.id))
+    }
+
+    async fn remove_deploy_key(&self, org: &str, name: &str, id: u64) -> ForgeResult<bool> {
+        let url = format!("{}/repos/{}/{}/keys/{id}", self.api_url, org, name);
+        let response = self.client.delete(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        self.expect_success(response, name).await?;
+        Ok(true)
+    }
+
+    async fn check_state(&self, org: &str, name: &str, git_ref: &str) -> ForgeResult<Option<CheckState>> {
+        let url = format!(
+            "{}/repos/{}/{}/commits/{}/status",
+            self.api_url,
+            org,
+            name,
+            urlencoding::encode(git_ref)
+        );
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let status: CodebergCombinedStatus = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(Some(Self::combined_status_state(&status)))
+    }
+
+    async fn approve_pull_request(&self, org: &str, name: &str, number: u64) -> ForgeResult<bool> {
+        let url = format!("{}/repos/{}/{}/pulls/{number}/reviews", self.api_url, org, name);
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({ "event": "APPROVED" }))
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        self.expect_success(response, name).await?;
+        Ok(true)
+    }
+
+    async fn merge_pull_request(&self, org: &str, name: &str, number: u64) -> ForgeResult<bool> {
+        let url = format!("{}/repos/{}/{}/pulls/{number}/merge", self.api_url, org, name);
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({ "Do": "merge" }))
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        self.expect_success(response, name).await?;
+        Ok(true)
+    }
+
+    async fn pull_request_state(&self, org: &str, name: &str, head_branch: &str) -> ForgeResult<Option<PullRequestState>> {
+        // Gitea has no head filter on the list endpoint; newest first
+        let url = format!("{}/repos/{}/{}/pulls?state=all&sort=recentupdate&limit=50", self.api_url, org, name);
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let pulls: Vec<CodebergPullState> = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(pulls.iter().find(|p| p.head.ref_name == head_branch).map(|p| {
+            if p.state == "open" {
+                PullRequestState::Open
+            } else if p.merged {
+                PullRequestState::Merged
+            } else {
+                PullRequestState::Closed
+            }
+        }))
+    }
+
+    async fn can_push(&self, org: &str, name: &str) -> ForgeResult<Option<bool>> {
+        let url = format!("{}/repos/{}/{}", self.api_url, org, name);
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let fields: CodebergPermissionFields = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(fields.permissions.map(|p| p.push))
+    }
+
+    async fn create_fork(&self, org: &str, name: &str) -> ForgeResult<Option<String>> {
+        let headers = self.auth_headers().await?;
+        let url = format!("{}/repos/{}/{}/forks", self.api_url, org, name);
+        let response = self.client.post(&url)
+            .headers(headers.clone())
+            .json(&serde_json::json!({}))
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        // 409: the user already has a fork, which lives under their login
+        if response.status() == reqwest::StatusCode::CONFLICT {
+            let url = format!("{}/user", self.api_url);
+            let response = self.client.get(&url)
+                .headers(headers)
+                .send_limited(self.forge.clone())
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+            let user: CodebergLogin = self.expect_success(response, name).await?.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            return Ok(Some(user.login));
+        }
+        let fork: CodebergForkOwner = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(fork.owner.login))
+    }
+
+    async fn create_pull_request(&self, org: &str, name: &str, pr: &NewPullRequest) -> ForgeResult<Option<String>> {
+        if let Some(existing) = self.list_pull_requests(org, name).await?
+            .and_then(|open| open.into_iter().find(|p| p.head_branch == pr.head))
+        {
+            if existing.title != pr.title {
+                let url = format!("{}/repos/{}/{}/pulls/{}", self.api_url, org, name, existing.number);
+                let response = self.client.patch(&url)
+                    .headers(self.auth_headers().await?)
+                    .json(&serde_json::json!({ "title": pr.title }))
+                    .send_limited(self.forge.clone())
+                    .await
+                    .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+                self.expect_success(response, name).await?;
+            }
+            return Ok(Some(existing.url));
+        }
+
+        let headers = self.auth_headers().await?;
+        let label_ids = self.label_ids(org, name, &pr.labels, &headers).await?;
+
+        let url = format!("{}/repos/{}/{}/pulls", self.api_url, org, name);
+        let response = self.client.post(&url)
+            .headers(headers)
+            .json(&serde_json::json!({
+                "title": pr.title,
+                "body": pr.body,
+                "head": pr.head_owner.as_ref().map_or_else(|| pr.head.clone(), |owner| format!("{owner}:{}", pr.head)),
+                "base": pr.base,
+                "labels": label_ids,
+            }))
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let created: CodebergPull = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(Some(created.html_url))
     }
 
     async fn create_repo(&self, org: &str, repo: &Repo) -> ForgeResult<()> {
         // If we know this is a user account, go directly to user endpoint
         if self.owner_type == Some(OwnerType::User) {
-            return self.create_user_repo(repo).await;
+            self.create_user_repo(repo).await?;
+            return self.replace_topics(org, &repo.name, &repo.topics).await;
         }
 
         let headers = self.auth_headers().await?;
@@ -293,13 +1019,14 @@ impl ForgePort for CodebergAdapter {
         let response = self.client.post(&url)
             .headers(headers.clone())
             .json(&request)
-            .send()
+            .send_limited(self.forge.clone())
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
         // If org create fails with 404, try user create (only when owner_type is None/unknown)
         if response.status() == reqwest::StatusCode::NOT_FOUND {
-            return self.create_user_repo(repo).await;
+            self.create_user_repo(repo).await?;
+            return self.replace_topics(org, &repo.name, &repo.topics).await;
         }
 
         if response.status() == reqwest::StatusCode::CONFLICT {
@@ -311,18 +1038,18 @@ impl ForgePort for CodebergAdapter {
             if body.contains("already exists") || body.contains("conflict") {
                 return Err(ForgeError::RepoAlreadyExists { name: repo.name.clone() });
             }
-            return Err(ForgeError::ApiError(format!("Codeberg API error: {body}")));
+            return Err(ForgeError::ApiError(format!("{} API error: {body}", self.label)));
         }
 
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             return Err(ForgeError::ApiError(format!(
-                "Codeberg API error {status}: {body}"
+                "{} API error {status}: {body}", self.label
             )));
         }
 
-        Ok(())
+        self.replace_topics(org, &repo.name, &repo.topics).await
     }
 
     async fn update_repo(&self, org: &str, repo: &Repo) -> ForgeResult<()> {
@@ -338,7 +1065,7 @@ impl ForgePort for CodebergAdapter {
         let response = self.client.patch(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(self.forge.clone())
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -350,11 +1077,11 @@ impl ForgePort for CodebergAdapter {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             return Err(ForgeError::ApiError(format!(
-                "Codeberg API error {status}: {body}"
+                "{} API error {status}: {body}", self.label
             )));
         }
 
-        Ok(())
+        self.replace_topics(org, &repo.name, &repo.topics).await
     }
 
     async fn set_archived(&self, org: &str, name: &str, archived: bool) -> ForgeResult<()> {
@@ -370,7 +1097,7 @@ impl ForgePort for CodebergAdapter {
         let response = self.client.patch(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(self.forge.clone())
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -382,7 +1109,7 @@ impl ForgePort for CodebergAdapter {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             return Err(ForgeError::ApiError(format!(
-                "Codeberg API error {status}: {body}"
+                "{} API error {status}: {body}", self.label
             )));
         }
 
@@ -395,7 +1122,7 @@ impl ForgePort for CodebergAdapter {
 
         let response = self.client.delete(&url)
             .headers(headers)
-            .send()
+            .send_limited(self.forge.clone())
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -408,7 +1135,7 @@ impl ForgePort for CodebergAdapter {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             return Err(ForgeError::ApiError(format!(
-                "Codeberg API error {status}: {body}"
+                "{} API error {status}: {body}", self.label
             )));
         }
 
@@ -424,7 +1151,7 @@ impl ForgePort for CodebergAdapter {
         let response = self.client.patch(&url)
             .headers(headers)
             .json(&body)
-            .send()
+            .send_limited(self.forge.clone())
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -436,7 +1163,7 @@ impl ForgePort for CodebergAdapter {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             return Err(ForgeError::ApiError(format!(
-                "Codeberg API error {status}: {body}"
+                "{} API error {status}: {body}", self.label
             )));
         }
 
@@ -454,7 +1181,7 @@ impl ForgePort for CodebergAdapter {
         let response = self.client.patch(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(self.forge.clone())
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -466,13 +1193,31 @@ impl ForgePort for CodebergAdapter {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             return Err(ForgeError::ApiError(format!(
-                "Codeberg API error {status}: {body}"
+                "{} API error {status}: {body}", self.label
             )));
         }
 
         Ok(())
     }
 
+    async fn transfer_repo(&self, org: &str, name: &str, new_org: &str) -> ForgeResult<()> {
+        let headers = self.auth_headers().await?;
+        let url = format!("{}/repos/{}/{}/transfer", self.api_url, org, name);
+
+        let request = TransferRepoRequest {
+            new_owner: new_org.to_string(),
+        };
+
+        let response = self.client.post(&url)
+            .headers(headers)
+            .json(&request)
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        self.expect_success(response, name).await?;
+        Ok(())
+    }
+
     async fn list_repos_incremental(
         &self, org: &str, etag: Option<String>,
     ) -> ForgeResult<ListResult> {
@@ -486,7 +1231,7 @@ impl ForgePort for CodebergAdapter {
             });
         }
 
-        let mut headers = self.auth_headers().await?;
+        let mut headers = self.read_headers().await?;
         if let Some(ref etag_value) = etag {
             headers.insert(
                 header::IF_NONE_MATCH,
@@ -499,7 +1244,7 @@ impl ForgePort for CodebergAdapter {
 
         let response = self.client.get(&url)
             .headers(headers)
-            .send()
+            .send_limited(self.forge.clone())
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -526,7 +1271,7 @@ impl ForgePort for CodebergAdapter {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             return Err(ForgeError::ApiError(format!(
-                "Codeberg API error {status}: {body}"
+                "{} API error {status}: {body}", self.label
             )));
         }
 
@@ -546,15 +1291,92 @@ impl ForgePort for CodebergAdapter {
     }
 }
 
+#[async_trait]
+impl IssuePort for CodebergAdapter {
+    async fn list_issues(&self, org: &str, name: &str, state: Option<IssueState>) -> ForgeResult<Vec<Issue>> {
+        let headers = self.auth_headers().await?;
+        let state = state.map_or("all", IssueState::as_str);
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "{}/repos/{}/{}/issues?state={state}&type=issues&limit=50&page={page}",
+                self.api_url, org, name
+            );
+            let response = self.client.get(&url)
+                .headers(headers.clone())
+                .send_limited(self.forge.clone())
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+            let batch: Vec<CodebergIssue> = self.expect_success(response, name).await?.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            let last = batch.len() < 50;
+            issues.extend(batch.into_iter().map(Issue::from));
+            if last {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    async fn create_issue(&self, org: &str, name: &str, issue: &NewIssue) -> ForgeResult<Issue> {
+        let headers = self.auth_headers().await?;
+        let label_ids = self.label_ids(org, name, &issue.labels, &headers).await?;
+
+        let url = format!("{}/repos/{}/{}/issues", self.api_url, org, name);
+        let response = self.client.post(&url)
+            .headers(headers)
+            .json(&serde_json::json!({
+                "title": issue.title,
+                "body": issue.body,
+                "labels": label_ids,
+                "closed": issue.state == IssueState::Closed,
+            }))
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let created: CodebergIssue = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(created.into())
+    }
+}
+
 impl CodebergAdapter {
+    /// Ids of the repo's labels named in `names`. Gitea takes label ids, not
+    /// names; names that don't exist are skipped.
+    async fn label_ids(
+        &self,
+        org: &str,
+        name: &str,
+        names: &[String],
+        headers: &header::HeaderMap,
+    ) -> ForgeResult<Vec<u64>> {
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+        let url = format!("{}/repos/{}/{}/labels?limit=50", self.api_url, org, name);
+        let response = self.client.get(&url)
+            .headers(headers.clone())
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let labels: Vec<CodebergLabel> = self.expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(labels
+            .into_iter()
+            .filter(|l| names.contains(&l.name))
+            .map(|l| l.id)
+            .collect())
+    }
+
     /// List repos for a user (fallback when org doesn't exist)
     async fn list_user_repos(&self, username: &str) -> ForgeResult<Vec<Repo>> {
-        let headers = self.auth_headers().await?;
+        let headers = self.read_headers().await?;
         let base_url = format!("{}/users/{}/repos?limit=100", self.api_url, username);
 
         let response = self.client.get(&base_url)
             .headers(headers)
-            .send()
+            .send_limited(self.forge.clone())
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -562,13 +1384,29 @@ impl CodebergAdapter {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             return Err(ForgeError::ApiError(format!(
-                "Codeberg API error {status}: {body}"
+                "{} API error {status}: {body}", self.label
             )));
         }
 
         self.fetch_all_pages(response, &base_url).await
     }
 
+    /// Replace the repo's topics; an empty list leaves them alone
+    async fn replace_topics(&self, org: &str, name: &str, topics: &[String]) -> ForgeResult<()> {
+        if topics.is_empty() {
+            return Ok(());
+        }
+        let url = format!("{}/repos/{}/{}/topics", self.api_url, org, name);
+        let response = self.client.put(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({ "topics": topics }))
+            .send_limited(self.forge.clone())
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        self.expect_success(response, name).await?;
+        Ok(())
+    }
+
     /// Create repo under authenticated user (fallback when org doesn't exist)
     async fn create_user_repo(&self, repo: &Repo) -> ForgeResult<()> {
         let headers = self.auth_headers().await?;
@@ -584,7 +1422,7 @@ impl CodebergAdapter {
         let response = self.client.post(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(self.forge.clone())
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -597,14 +1435,14 @@ impl CodebergAdapter {
             if body.contains("already exists") || body.contains("conflict") {
                 return Err(ForgeError::RepoAlreadyExists { name: repo.name.clone() });
             }
-            return Err(ForgeError::ApiError(format!("Codeberg API error: {body}")));
+            return Err(ForgeError::ApiError(format!("{} API error: {body}", self.label)));
         }
 
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
             return Err(ForgeError::ApiError(format!(
-                "Codeberg API error {status}: {body}"
+                "{} API error {status}: {body}", self.label
             )));
         }
 
@@ -649,8 +1487,10 @@ mod tests {
             name: "test-repo".to_string(),
             description: Some("A test repo".to_string()),
             private: false,
+            topics: Vec::new(),
             archived: false,
             default_branch: None,
+            permissions: None,
         };
 
         let repo = CodebergAdapter::to_repo(cb_repo);
@@ -667,8 +1507,10 @@ mod tests {
             name: "private-repo".to_string(),
             description: None,
             private: true,
+            topics: Vec::new(),
             archived: true,
             default_branch: None,
+            permissions: None,
         };
 
         let repo = CodebergAdapter::to_repo(cb_repo);
@@ -676,6 +1518,38 @@ mod tests {
         assert!(repo.protected); // archived maps to protected
     }
 
+    /// Returns a token only for one secret path
+    struct KeyedAuthProvider(&'static str);
+
+    #[async_trait]
+    impl AuthProvider for KeyedAuthProvider {
+        async fn get_secret(&self, key: &str) -> anyhow::Result<Option<String>> {
+            Ok((key == self.0).then(|| "tok".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gitea_adapter_uses_host() {
+        let auth = Arc::new(KeyedAuthProvider("git.acme.dev/acme/token"));
+        let adapter = GiteaAdapter::gitea(auth, "acme", "git.acme.dev").unwrap();
+        assert_eq!(adapter.api_url, "https://git.acme.dev/api/v1");
+        assert!(adapter.auth_headers().await.is_ok());
+
+        let repo = adapter.repo_from(CodebergRepo {
+            name: "tool".to_string(),
+            description: None,
+            private: false,
+            topics: Vec::new(),
+            archived: false,
+            default_branch: None,
+            permissions: None,
+        });
+        assert_eq!(repo.origin, Forge::Gitea { host: "git.acme.dev".to_string() });
+
+        let codeberg = CodebergAdapter::new(Arc::new(KeyedAuthProvider("git.acme.dev/acme/token")), "acme").unwrap();
+        assert!(codeberg.auth_headers().await.is_err());
+    }
+
     /* Broken: CodebergAdapter::new requires 2 arguments, not 1
     #[tokio::test]
     async fn test_auth_headers_missing_token() {
@@ -4,9 +4,11 @@
 //! (GitHub, Codeberg, etc.) and the local forge (in-memory state).
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 
-use crate::types::Repo;
+use super::issue_port::IssuePort;
+use crate::types::{BranchProtectionConfig, Forge, Repo, WebhookEvent};
 
 /// Result of a conditional list operation (ETag-based)
 #[derive(Debug)]
@@ -19,6 +21,240 @@ pub struct ListResult {
     pub modified: bool,
 }
 
+/// State of a fork's upstream repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpstreamStatus {
+    Active,
+    Archived,
+    /// The forge still flags the repo as a fork but the parent is gone
+    Deleted,
+}
+
+impl UpstreamStatus {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Archived => "archived",
+            Self::Deleted => "deleted",
+        }
+    }
+}
+
+/// Fork relationship of a repository, as reported by the forge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForkInfo {
+    /// Upstream as `owner/name`, if the forge still reports one
+    pub upstream: Option<String>,
+    pub status: UpstreamStatus,
+}
+
+/// Storage a forge reports for one repository, in bytes.
+///
+/// `None` means the forge doesn't expose that figure.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageUsage {
+    pub repo_bytes: u64,
+    pub lfs_bytes: Option<u64>,
+    pub artifact_bytes: Option<u64>,
+    pub package_bytes: Option<u64>,
+}
+
+impl StorageUsage {
+    /// Sum of every reported figure
+    pub fn total_bytes(&self) -> u64 {
+        self.repo_bytes
+            + self.lfs_bytes.unwrap_or(0)
+            + self.artifact_bytes.unwrap_or(0)
+            + self.package_bytes.unwrap_or(0)
+    }
+}
+
+/// Merge strategies a forge allows for pull/merge requests on a repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeSettings {
+    pub merge_commit: bool,
+    pub squash: bool,
+    pub rebase: bool,
+}
+
+impl MergeSettings {
+    /// Only squash merges are allowed
+    pub const fn is_squash_only(&self) -> bool {
+        self.squash && !self.merge_commit && !self.rebase
+    }
+}
+
+impl std::fmt::Display for MergeSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let allowed: Vec<&str> = [
+            (self.merge_commit, "merge"),
+            (self.squash, "squash"),
+            (self.rebase, "rebase"),
+        ]
+        .iter()
+        .filter(|(on, _)| *on)
+        .map(|(_, name)| *name)
+        .collect();
+        if allowed.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", allowed.join(", "))
+        }
+    }
+}
+
+/// Combined CI state of a commit (checks, statuses, or pipelines)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckState {
+    Success,
+    Failure,
+    Pending,
+    /// No CI reported anything for the commit
+    Unknown,
+}
+
+impl CheckState {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::Pending => "pending",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// Fold several sources into one state: any failure fails, any pending
+    /// keeps it pending, and unknown sources are ignored.
+    pub fn combine(states: impl IntoIterator<Item = Self>) -> Self {
+        states.into_iter().fold(Self::Unknown, |acc, s| match (acc, s) {
+            (Self::Failure, _) | (_, Self::Failure) => Self::Failure,
+            (Self::Pending, _) | (_, Self::Pending) => Self::Pending,
+            (Self::Success, _) | (_, Self::Success) => Self::Success,
+            _ => Self::Unknown,
+        })
+    }
+}
+
+/// An open pull request (merge request on GitLab)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequest {
+    /// PR number (GitLab: project-scoped `iid`)
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub head_branch: String,
+    pub head_sha: String,
+    /// Branch the PR merges into
+    pub base_branch: String,
+    pub draft: bool,
+    pub url: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Lifecycle state of a pull request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullRequestState {
+    Open,
+    Merged,
+    /// Closed without merging
+    Closed,
+}
+
+/// A pull request to open from `head` into `base` on the same repository
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NewPullRequest {
+    pub title: String,
+    pub body: String,
+    pub head: String,
+    /// Owner of the fork `head` lives in, for PRs from a fork; `None` when
+    /// `head` is a branch of the target repo itself
+    pub head_owner: Option<String>,
+    pub base: String,
+    /// Label names; labels the forge doesn't know are skipped or created,
+    /// depending on the forge
+    pub labels: Vec<String>,
+}
+
+/// Prefix of approval rule names managed from the workspace ownership map.
+/// Rules without it are left alone.
+pub const APPROVAL_RULE_PREFIX: &str = "codeowners: ";
+
+/// A desired merge/pull request approval rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalRule {
+    /// Rule name, starting with [`APPROVAL_RULE_PREFIX`]
+    pub name: String,
+    /// Users (`alice`) or groups (`acme/platform`), without the leading `@`
+    pub approvers: Vec<String>,
+    pub approvals_required: u32,
+}
+
+/// What converging a single approval rule did (or would do)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalRuleChange {
+    pub rule: String,
+    /// `created`, `updated`, `deleted`, or `unchanged`
+    pub action: &'static str,
+}
+
+/// Protection rule on one branch. An unprotected branch reads as the
+/// default: no reviews, no checks, force-pushes allowed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BranchProtection {
+    pub required_reviews: u32,
+    /// CI contexts that must pass, sorted
+    pub status_checks: Vec<String>,
+    pub block_force_push: bool,
+}
+
+impl From<&BranchProtectionConfig> for BranchProtection {
+    fn from(config: &BranchProtectionConfig) -> Self {
+        let mut status_checks = config.status_checks.clone();
+        status_checks.sort();
+        Self {
+            required_reviews: config.required_reviews,
+            status_checks,
+            block_force_push: config.block_force_push,
+        }
+    }
+}
+
+/// A webhook as listed from a forge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    pub id: u64,
+    pub url: String,
+    /// Events it fires on; forge events hyperforge doesn't model are dropped
+    pub events: Vec<WebhookEvent>,
+    pub active: bool,
+}
+
+/// A webhook to create on a forge (JSON payloads, active)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewWebhook {
+    pub url: String,
+    pub events: Vec<WebhookEvent>,
+    pub secret: Option<String>,
+}
+
+/// A deploy key as listed from a forge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeployKey {
+    pub id: u64,
+    pub title: String,
+    /// Public key as `<type> <base64>`, without a comment
+    pub key: String,
+    pub read_only: bool,
+}
+
+/// A deploy key to add to a repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewDeployKey {
+    pub title: String,
+    pub key: String,
+    pub read_only: bool,
+}
+
 /// Errors that can occur when interacting with a forge
 #[derive(Debug, Error)]
 pub enum ForgeError {
@@ -62,6 +298,24 @@ pub type ForgeResult<T> = std::result::Result<T, ForgeError>;
 /// - `CodebergAdapter`: Codeberg API client
 #[async_trait]
 pub trait ForgePort: Send + Sync {
+    /// The forge this adapter talks to, whose metadata limits apply to
+    /// repos written through it: `None` for stores with no limits of their
+    /// own (`LocalForge`).
+    fn forge(&self) -> Option<Forge> {
+        None
+    }
+
+    /// The forge's issue tracker: `None` if this port has none
+    fn issues(&self) -> Option<&dyn IssuePort> {
+        None
+    }
+
+    /// Whether requests carry a stored token. Without one, listings and
+    /// lookups go out anonymously and only see public repos.
+    async fn has_token(&self) -> bool {
+        true
+    }
+
     /// List all repositories for the given organization
     async fn list_repos(&self, org: &str) -> ForgeResult<Vec<Repo>>;
 
@@ -80,12 +334,175 @@ pub trait ForgePort: Send + Sync {
     /// Rename a repository
     async fn rename_repo(&self, org: &str, old_name: &str, new_name: &str) -> ForgeResult<()>;
 
+    /// Move a repository to another org or user, keeping its name.
+    ///
+    /// Default implementation refuses (forges without transfers).
+    async fn transfer_repo(&self, org: &str, name: &str, new_org: &str) -> ForgeResult<()> {
+        let _ = (org, name, new_org);
+        Err(ForgeError::ApiError("this forge doesn't support repository transfers".to_string()))
+    }
+
     /// Set the default branch for a repository
     async fn set_default_branch(&self, org: &str, name: &str, branch: &str) -> ForgeResult<()>;
 
     /// Set the archived status of a repository
     async fn set_archived(&self, org: &str, name: &str, archived: bool) -> ForgeResult<()>;
 
+    /// Archive a repository: it stays readable but takes no pushes, issues
+    /// or settings changes
+    async fn archive_repo(&self, org: &str, name: &str) -> ForgeResult<()> {
+        self.set_archived(org, name, true).await
+    }
+
+    /// Make an archived repository writable again
+    async fn unarchive_repo(&self, org: &str, name: &str) -> ForgeResult<()> {
+        self.set_archived(org, name, false).await
+    }
+
+    /// Fork relationship of a repository: `None` if it isn't a fork.
+    ///
+    /// Default implementation reports no forks (forges that don't track them).
+    async fn fork_info(&self, org: &str, name: &str) -> ForgeResult<Option<ForkInfo>> {
+        let _ = (org, name);
+        Ok(None)
+    }
+
+    /// Storage used by a repository: `None` if the forge doesn't report it.
+    async fn storage_usage(&self, org: &str, name: &str) -> ForgeResult<Option<StorageUsage>> {
+        let _ = (org, name);
+        Ok(None)
+    }
+
+    /// Allowed merge strategies: `None` if the forge doesn't report them
+    /// (or the token lacks the access needed to see them).
+    async fn merge_settings(&self, org: &str, name: &str) -> ForgeResult<Option<MergeSettings>> {
+        let _ = (org, name);
+        Ok(None)
+    }
+
+    /// Converge the repository's managed approval rules to `rules`, deleting
+    /// managed rules that are no longer wanted: `None` if the forge has no
+    /// approval rules beyond CODEOWNERS itself.
+    async fn converge_approval_rules(
+        &self,
+        org: &str,
+        name: &str,
+        rules: &[ApprovalRule],
+        dry_run: bool,
+    ) -> ForgeResult<Option<Vec<ApprovalRuleChange>>> {
+        let _ = (org, name, rules, dry_run);
+        Ok(None)
+    }
+
+    /// Protection rule on `branch`: `None` if the forge doesn't support
+    /// branch protection.
+    async fn branch_protection(&self, org: &str, name: &str, branch: &str) -> ForgeResult<Option<BranchProtection>> {
+        let _ = (org, name, branch);
+        Ok(None)
+    }
+
+    /// Protect `branch` with exactly `rule`, replacing any existing rule.
+    /// Returns `false` if the forge doesn't support branch protection.
+    async fn set_branch_protection(&self, org: &str, name: &str, branch: &str, rule: &BranchProtection) -> ForgeResult<bool> {
+        let _ = (org, name, branch, rule);
+        Ok(false)
+    }
+
+    /// Webhooks on a repository: `None` if the forge doesn't expose them.
+    async fn list_webhooks(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<Webhook>>> {
+        let _ = (org, name);
+        Ok(None)
+    }
+
+    /// Create a webhook and return its id: `None` if the forge doesn't
+    /// support webhooks.
+    async fn create_webhook(&self, org: &str, name: &str, hook: &NewWebhook) -> ForgeResult<Option<u64>> {
+        let _ = (org, name, hook);
+        Ok(None)
+    }
+
+    /// Delete a webhook by id. Returns `false` if the forge doesn't support it.
+    async fn delete_webhook(&self, org: &str, name: &str, id: u64) -> ForgeResult<bool> {
+        let _ = (org, name, id);
+        Ok(false)
+    }
+
+    /// Deploy keys on a repository: `None` if the forge doesn't expose them.
+    async fn list_deploy_keys(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<DeployKey>>> {
+        let _ = (org, name);
+        Ok(None)
+    }
+
+    /// Add a deploy key and return its id: `None` if the forge doesn't
+    /// support deploy keys.
+    async fn add_deploy_key(&self, org: &str, name: &str, key: &NewDeployKey) -> ForgeResult<Option<u64>> {
+        let _ = (org, name, key);
+        Ok(None)
+    }
+
+    /// Remove a deploy key by id. Returns `false` if the forge doesn't support it.
+    async fn remove_deploy_key(&self, org: &str, name: &str, id: u64) -> ForgeResult<bool> {
+        let _ = (org, name, id);
+        Ok(false)
+    }
+
+    /// Every open pull request (merge request on GitLab), across all pages:
+    /// `None` if the forge doesn't expose them.
+    async fn list_pull_requests(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<PullRequest>>> {
+        let _ = (org, name);
+        Ok(None)
+    }
+
+    /// Combined CI state of `git_ref` (branch or commit sha): `None` if the
+    /// forge doesn't report CI results.
+    async fn check_state(&self, org: &str, name: &str, git_ref: &str) -> ForgeResult<Option<CheckState>> {
+        let _ = (org, name, git_ref);
+        Ok(None)
+    }
+
+    /// Approve a pull request. Returns `false` if the forge doesn't support it.
+    async fn approve_pull_request(&self, org: &str, name: &str, number: u64) -> ForgeResult<bool> {
+        let _ = (org, name, number);
+        Ok(false)
+    }
+
+    /// Merge a pull request with the repository's default merge method.
+    /// Returns `false` if the forge doesn't support it.
+    async fn merge_pull_request(&self, org: &str, name: &str, number: u64) -> ForgeResult<bool> {
+        let _ = (org, name, number);
+        Ok(false)
+    }
+
+    /// Whether the authenticated user can push to the repository: `None` if
+    /// the forge doesn't report permissions.
+    async fn can_push(&self, org: &str, name: &str) -> ForgeResult<Option<bool>> {
+        let _ = (org, name);
+        Ok(None)
+    }
+
+    /// Fork the repository into the authenticated user's namespace and return
+    /// the fork's owner. An existing fork is reused. `None` if the forge
+    /// doesn't support forking. The fork keeps the source repo's name.
+    async fn create_fork(&self, org: &str, name: &str) -> ForgeResult<Option<String>> {
+        let _ = (org, name);
+        Ok(None)
+    }
+
+    /// Open a pull request and return its URL. An already-open PR from the
+    /// same head branch is reused rather than duplicated, with its title
+    /// updated if it changed. `None` if the forge doesn't support pull requests.
+    async fn create_pull_request(&self, org: &str, name: &str, pr: &NewPullRequest) -> ForgeResult<Option<String>> {
+        let _ = (org, name, pr);
+        Ok(None)
+    }
+
+    /// State of the most recent PR from `head_branch`: `Ok(None)` if there is
+    /// none or the forge doesn't support pull requests.
+    async fn pull_request_state(&self, org: &str, name: &str, head_branch: &str) -> ForgeResult<Option<PullRequestState>> {
+        let _ = (org, name, head_branch);
+        Ok(None)
+    }
+
     /// Check if a repository exists
     async fn repo_exists(&self, org: &str, name: &str) -> ForgeResult<bool> {
         match self.get_repo(org, name).await {
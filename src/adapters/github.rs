@@ -8,9 +8,12 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::task::JoinSet;
 
-use crate::auth::AuthProvider;
-use crate::types::{Forge, OwnerType, Repo, Visibility};
-use super::{ForgeError, ForgePort, ForgeResult, ListResult};
+use crate::auth::{AuthProvider, GitHubAppAuth};
+use crate::scheduler::LimitedSend;
+use crate::types::repo::normalize_topics;
+use crate::types::{Forge, OwnerType, Repo, Visibility, WebhookEvent};
+use super::rate_limit::RateLimitHeaders;
+use super::{BranchProtection, CheckState, DeployKey, ForgeError, ForgePort, ForgeResult, ForkInfo, Issue, IssuePort, IssueState, ListResult, MergeSettings, NewDeployKey, NewIssue, NewPullRequest, NewWebhook, PullRequest, PullRequestState, StorageUsage, UpstreamStatus, Webhook};
 
 /// GitHub API base URL
 const GITHUB_API_URL: &str = "https://api.github.com";
@@ -22,12 +25,222 @@ struct GitHubRepo {
     description: Option<String>,
     private: bool,
     #[serde(default)]
+    topics: Vec<String>,
+    #[serde(default)]
     archived: bool,
     #[serde(default)]
     default_branch: Option<String>,
+    /// The token's permissions (omitted for unauthenticated requests)
+    #[serde(default)]
+    permissions: Option<GitHubPermissions>,
+}
+
+/// Fork fields of a single-repo response
+#[derive(Debug, Deserialize)]
+struct GitHubForkFields {
+    #[serde(default)]
+    fork: bool,
+    #[serde(default)]
+    parent: Option<GitHubParent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubParent {
+    full_name: String,
+    #[serde(default)]
+    archived: bool,
+}
+
+/// Size field of a single-repo response (KiB)
+#[derive(Debug, Deserialize)]
+struct GitHubSize {
+    #[serde(default)]
+    size: u64,
+}
+
+/// One page of Actions artifacts
+#[derive(Debug, Deserialize)]
+struct GitHubArtifactPage {
+    total_count: u64,
+    #[serde(default)]
+    artifacts: Vec<GitHubArtifact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubArtifact {
+    size_in_bytes: u64,
+}
+
+/// Merge settings of a single-repo response (only present with admin access)
+#[derive(Debug, Deserialize)]
+#[allow(clippy::struct_field_names)] // mirrors the API's field names
+struct GitHubMergeFields {
+    allow_merge_commit: Option<bool>,
+    allow_squash_merge: Option<bool>,
+    allow_rebase_merge: Option<bool>,
+}
+
+/// Pull request list entry
+#[derive(Debug, Deserialize)]
+struct GitHubPull {
+    number: u64,
+    title: String,
+    html_url: String,
+    user: GitHubLogin,
+    head: GitHubPullHead,
+    base: GitHubPullHead,
+    #[serde(default)]
+    draft: bool,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubLogin {
+    login: String,
+}
+
+/// Issue list entry (the issues API also returns pull requests)
+#[derive(Debug, Deserialize)]
+struct GitHubIssue {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    labels: Vec<GitHubLabel>,
+    state: String,
+    html_url: String,
+    #[serde(default)]
+    pull_request: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubLabel {
+    name: String,
+}
+
+impl From<GitHubIssue> for Issue {
+    fn from(issue: GitHubIssue) -> Self {
+        Self {
+            number: issue.number,
+            title: issue.title,
+            body: issue.body.unwrap_or_default(),
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            state: if issue.state == "closed" { IssueState::Closed } else { IssueState::Open },
+            url: issue.html_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubPullHead {
+    #[serde(rename = "ref")]
+    ref_name: String,
+    sha: String,
+}
+
+/// Check runs for a commit (GitHub Actions and other check apps)
+#[derive(Debug, Deserialize)]
+struct GitHubCheckRuns {
+    check_runs: Vec<GitHubCheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubCheckRun {
+    status: String,
+    conclusion: Option<String>,
+}
+
+/// Combined commit status (legacy status API)
+#[derive(Debug, Deserialize)]
+struct GitHubCombinedStatus {
+    state: String,
+    total_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubPullState {
+    state: String,
+    merged_at: Option<String>,
 }
 
 /// Request body for creating a repository
+#[derive(Debug, Deserialize)]
+struct GitHubPermissionFields {
+    #[serde(default)]
+    permissions: Option<GitHubPermissions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubPermissions {
+    push: bool,
+    #[serde(default)]
+    admin: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubForkOwner {
+    owner: GitHubLogin,
+}
+
+/// Branch protection response; every rule is optional
+#[derive(Debug, Deserialize)]
+struct GitHubProtection {
+    required_status_checks: Option<GitHubStatusChecks>,
+    required_pull_request_reviews: Option<GitHubReviews>,
+    allow_force_pushes: Option<GitHubEnabled>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubStatusChecks {
+    #[serde(default)]
+    contexts: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubReviews {
+    #[serde(default)]
+    required_approving_review_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubEnabled {
+    enabled: bool,
+}
+
+/// Repository webhook response from API
+#[derive(Debug, Deserialize)]
+struct GitHubHook {
+    id: u64,
+    active: bool,
+    #[serde(default)]
+    events: Vec<String>,
+    config: GitHubHookConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubHookConfig {
+    #[serde(default)]
+    url: String,
+}
+
+/// Repository deploy key response from API
+#[derive(Debug, Deserialize)]
+struct GitHubDeployKey {
+    id: u64,
+    #[serde(default)]
+    title: String,
+    key: String,
+    #[serde(default)]
+    read_only: bool,
+}
+
+impl From<GitHubDeployKey> for DeployKey {
+    fn from(k: GitHubDeployKey) -> Self {
+        Self { id: k.id, title: k.title, key: k.key, read_only: k.read_only }
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct CreateRepoRequest {
     name: String,
@@ -53,6 +266,12 @@ struct RenameRepoRequest {
     name: String,
 }
 
+/// Request body for transferring a repository to another owner
+#[derive(Debug, Serialize)]
+struct TransferRepoRequest {
+    new_owner: String,
+}
+
 /// GitHub adapter for `ForgePort` trait
 pub struct GitHubAdapter {
     client: Client,
@@ -75,6 +294,8 @@ impl GitHubAdapter {
             .build()
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
+        // Orgs without a PAT can authenticate as a GitHub App installation
+        let auth = Arc::new(GitHubAppAuth::new(auth, client.clone(), api_url.clone()));
         Ok(Self { client, auth, api_url, org: org.into(), owner_type: None })
     }
 
@@ -84,22 +305,40 @@ impl GitHubAdapter {
         self
     }
 
-    /// Get authorization headers with token from auth provider
-    async fn auth_headers(&self) -> ForgeResult<header::HeaderMap> {
+    /// The org's stored token, if any
+    async fn token(&self) -> ForgeResult<Option<String>> {
         // Construct secret path: github/{org}/token
         let secret_path = format!("github/{}/token", self.org);
-        let token = self.auth.get_secret(&secret_path).await
-            .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })?
+        self.auth.get_secret(&secret_path).await
+            .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })
+    }
+
+    /// Get authorization headers with token from auth provider
+    async fn auth_headers(&self) -> ForgeResult<header::HeaderMap> {
+        let token = self.token().await?
             .ok_or_else(|| ForgeError::AuthenticationFailed {
                 message: format!("No GitHub token found for org: {}", self.org),
             })?;
+        Self::headers(Some(&token))
+    }
+
+    /// Headers for read-only requests: authenticated when a token is
+    /// stored, anonymous otherwise (public repos only). A failing auth
+    /// provider is an error, not a reason to go anonymous
+    async fn read_headers(&self) -> ForgeResult<header::HeaderMap> {
+        let token = self.token().await?;
+        Self::headers(token.as_deref())
+    }
 
+    fn headers(token: Option<&str>) -> ForgeResult<header::HeaderMap> {
         let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::AUTHORIZATION,
-            header::HeaderValue::from_str(&format!("Bearer {token}"))
-                .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })?,
-        );
+        if let Some(token) = token {
+            headers.insert(
+                header::AUTHORIZATION,
+                header::HeaderValue::from_str(&format!("Bearer {token}"))
+                    .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })?,
+            );
+        }
         headers.insert(
             header::ACCEPT,
             header::HeaderValue::from_static("application/vnd.github+json"),
@@ -112,6 +351,58 @@ impl GitHubAdapter {
         Ok(headers)
     }
 
+    /// Map a non-success response to a `ForgeError`, passing successes through
+    async fn expect_success(response: Response, name: &str) -> ForgeResult<Response> {
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "GitHub API error {status}: {body}"
+            )));
+        }
+        Ok(response)
+    }
+
+    fn check_run_state(run: &GitHubCheckRun) -> CheckState {
+        if run.status != "completed" {
+            return CheckState::Pending;
+        }
+        match run.conclusion.as_deref() {
+            Some("success" | "neutral" | "skipped") => CheckState::Success,
+            Some(_) => CheckState::Failure,
+            None => CheckState::Pending,
+        }
+    }
+
+    fn combined_status_state(status: &GitHubCombinedStatus) -> CheckState {
+        if status.total_count == 0 {
+            return CheckState::Unknown;
+        }
+        match status.state.as_str() {
+            "success" => CheckState::Success,
+            "pending" => CheckState::Pending,
+            _ => CheckState::Failure,
+        }
+    }
+
+    /// Fork relationship from a repo's `fork`/`parent` fields: `None` for a
+    /// non-fork, `Deleted` when the parent is gone
+    fn to_fork_info(fields: GitHubForkFields) -> Option<ForkInfo> {
+        if !fields.fork {
+            return None;
+        }
+        Some(match fields.parent {
+            Some(parent) => ForkInfo {
+                status: if parent.archived { UpstreamStatus::Archived } else { UpstreamStatus::Active },
+                upstream: Some(parent.full_name),
+            },
+            None => ForkInfo { upstream: None, status: UpstreamStatus::Deleted },
+        })
+    }
+
     /// Convert GitHub API response to our Repo type
     fn to_repo(gh_repo: GitHubRepo) -> Repo {
         Repo {
@@ -126,7 +417,11 @@ impl GitHubAdapter {
             mirrors: Vec::new(),
             protected: gh_repo.archived,
             staged_for_deletion: false,
+            archived: gh_repo.archived,
             default_branch: gh_repo.default_branch,
+            read_only: gh_repo.permissions.is_some_and(|p| !p.admin),
+            topics: normalize_topics(&gh_repo.topics),
+            visibility_on: std::collections::BTreeMap::new(),
         }
     }
 
@@ -173,7 +468,7 @@ impl GitHubAdapter {
         };
 
         // Fetch remaining pages in parallel (pages 2..=last_page)
-        let headers = self.auth_headers().await?;
+        let headers = self.read_headers().await?;
         let mut join_set = JoinSet::new();
 
         // Limit concurrency to 10 pages at a time
@@ -186,7 +481,7 @@ impl GitHubAdapter {
             join_set.spawn(async move {
                 let response = client.get(&url)
                     .headers(hdrs)
-                    .send()
+                    .send_limited(Forge::GitHub)
                     .await
                     .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -221,24 +516,45 @@ impl GitHubAdapter {
             all_repos.extend(repos.into_iter().map(Self::to_repo));
         }
 
+        // Pages finish in any order; sort so every run lists the same way
+        all_repos.sort_by(|a, b| a.name.cmp(&b.name));
+
         Ok(all_repos)
     }
 }
 
+/// Rate limit state from a GitHub response: `x-ratelimit-remaining` and
+/// `x-ratelimit-reset` (unix seconds); secondary limits send `Retry-After`
+pub(crate) fn rate_limit_headers(headers: &header::HeaderMap, now: i64) -> RateLimitHeaders {
+    RateLimitHeaders::parse(headers, "x-ratelimit-remaining", "x-ratelimit-reset", now)
+}
+
 #[async_trait]
 impl ForgePort for GitHubAdapter {
+    fn forge(&self) -> Option<Forge> {
+        Some(Forge::GitHub)
+    }
+
+    fn issues(&self) -> Option<&dyn IssuePort> {
+        Some(self)
+    }
+
+    async fn has_token(&self) -> bool {
+        matches!(self.token().await, Ok(Some(_)))
+    }
+
     async fn list_repos(&self, org: &str) -> ForgeResult<Vec<Repo>> {
         // If we know this is a user account, skip the org endpoint entirely
         if self.owner_type == Some(OwnerType::User) {
             return self.list_user_repos(org).await;
         }
 
-        let headers = self.auth_headers().await?;
+        let headers = self.read_headers().await?;
         let base_url = format!("{}/orgs/{}/repos?per_page=100", self.api_url, org);
 
         let response = self.client.get(&base_url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -259,12 +575,12 @@ impl ForgePort for GitHubAdapter {
     }
 
     async fn get_repo(&self, org: &str, name: &str) -> ForgeResult<Repo> {
-        let headers = self.auth_headers().await?;
+        let headers = self.read_headers().await?;
         let url = format!("{}/repos/{}/{}", self.api_url, org, name);
 
         let response = self.client.get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -286,10 +602,451 @@ impl ForgePort for GitHubAdapter {
         Ok(Self::to_repo(gh_repo))
     }
 
+    async fn fork_info(&self, org: &str, name: &str) -> ForgeResult<Option<ForkInfo>> {
+        let headers = self.auth_headers().await?;
+        let url = format!("{}/repos/{}/{}", self.api_url, org, name);
+
+        let response = self.client.get(&url)
+            .headers(headers)
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "GitHub API error {status}: {body}"
+            )));
+        }
+
+        let fields: GitHubForkFields = response.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(Self::to_fork_info(fields))
+    }
+
+    async fn storage_usage(&self, org: &str, name: &str) -> ForgeResult<Option<StorageUsage>> {
+        /// Stop paging artifacts after this many pages (100 per page)
+        const MAX_ARTIFACT_PAGES: u64 = 10;
+
+        let headers = self.auth_headers().await?;
+        let url = format!("{}/repos/{}/{}", self.api_url, org, name);
+
+        let response = self.client.get(&url)
+            .headers(headers.clone())
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "GitHub API error {status}: {body}"
+            )));
+        }
+
+        let size: GitHubSize = response.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        // LFS usage isn't exposed per repo; artifacts are, paged
+        let mut artifact_bytes = 0u64;
+        let mut seen = 0u64;
+        for page in 1..=MAX_ARTIFACT_PAGES {
+            let url = format!(
+                "{}/repos/{}/{}/actions/artifacts?per_page=100&page={page}",
+                self.api_url, org, name
+            );
+            let response = self.client.get(&url)
+                .headers(headers.clone())
+                .send_limited(Forge::GitHub)
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+            if !response.status().is_success() {
+                break;
+            }
+            let artifacts: GitHubArtifactPage = response.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            seen += artifacts.artifacts.len() as u64;
+            artifact_bytes += artifacts.artifacts.iter().map(|a| a.size_in_bytes).sum::<u64>();
+            if artifacts.artifacts.is_empty() || seen >= artifacts.total_count {
+                break;
+            }
+        }
+
+        Ok(Some(StorageUsage {
+            repo_bytes: size.size * 1024,
+            lfs_bytes: None,
+            artifact_bytes: Some(artifact_bytes),
+            package_bytes: None,
+        }))
+    }
+
+    async fn merge_settings(&self, org: &str, name: &str) -> ForgeResult<Option<MergeSettings>> {
+        let headers = self.auth_headers().await?;
+        let url = format!("{}/repos/{}/{}", self.api_url, org, name);
+
+        let response = self.client.get(&url)
+            .headers(headers)
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "GitHub API error {status}: {body}"
+            )));
+        }
+
+        let fields: GitHubMergeFields = response.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(match (fields.allow_merge_commit, fields.allow_squash_merge, fields.allow_rebase_merge) {
+            (Some(merge_commit), Some(squash), Some(rebase)) => Some(MergeSettings { merge_commit, squash, rebase }),
+            _ => None,
+        })
+    }
+
+    async fn list_pull_requests(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<PullRequest>>> {
+        let headers = self.auth_headers().await?;
+        let mut pulls = Vec::new();
+        for page in 1.. {
+            let url = format!("{}/repos/{}/{}/pulls?state=open&per_page=100&page={page}", self.api_url, org, name);
+            let response = self.client.get(&url)
+                .headers(headers.clone())
+                .send_limited(Forge::GitHub)
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+            let batch: Vec<GitHubPull> = Self::expect_success(response, name).await?.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            let last = batch.len() < 100;
+            pulls.extend(batch.into_iter().map(|p| PullRequest {
+                number: p.number,
+                title: p.title,
+                author: p.user.login,
+                head_branch: p.head.ref_name,
+                head_sha: p.head.sha,
+                base_branch: p.base.ref_name,
+                draft: p.draft,
+                url: p.html_url,
+                created_at: p.created_at,
+            }));
+            if last {
+                break;
+            }
+        }
+        Ok(Some(pulls))
+    }
+
+    async fn branch_protection(&self, org: &str, name: &str, branch: &str) -> ForgeResult<Option<BranchProtection>> {
+        let url = format!("{}/repos/{}/{}/branches/{}/protection", self.api_url, org, name, urlencoding::encode(branch));
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        // 404 is "Branch not protected"
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Some(BranchProtection::default()));
+        }
+        let protection: GitHubProtection = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        let mut status_checks = protection.required_status_checks.map(|c| c.contexts).unwrap_or_default();
+        status_checks.sort();
+        Ok(Some(BranchProtection {
+            required_reviews: protection.required_pull_request_reviews.map_or(0, |r| r.required_approving_review_count),
+            status_checks,
+            block_force_push: !protection.allow_force_pushes.is_some_and(|f| f.enabled),
+        }))
+    }
+
+    async fn set_branch_protection(&self, org: &str, name: &str, branch: &str, rule: &BranchProtection) -> ForgeResult<bool> {
+        let url = format!("{}/repos/{}/{}/branches/{}/protection", self.api_url, org, name, urlencoding::encode(branch));
+        let checks = (!rule.status_checks.is_empty())
+            .then(|| serde_json::json!({ "strict": false, "contexts": rule.status_checks }));
+        let reviews = (rule.required_reviews > 0)
+            .then(|| serde_json::json!({ "required_approving_review_count": rule.required_reviews }));
+        let response = self.client.put(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({
+                "required_status_checks": checks,
+                "enforce_admins": null,
+                "required_pull_request_reviews": reviews,
+                "restrictions": null,
+                "allow_force_pushes": !rule.block_force_push,
+            }))
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        Self::expect_success(response, name).await?;
+        Ok(true)
+    }
+
+    async fn list_webhooks(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<Webhook>>> {
+        let url = format!("{}/repos/{}/{}/hooks?per_page=100", self.api_url, org, name);
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let hooks: Vec<GitHubHook> = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(Some(hooks.into_iter().map(|h| Webhook {
+            id: h.id,
+            url: h.config.url,
+            events: h.events.iter().filter_map(|e| WebhookEvent::parse(e)).collect(),
+            active: h.active,
+        }).collect()))
+    }
+
+    async fn create_webhook(&self, org: &str, name: &str, hook: &NewWebhook) -> ForgeResult<Option<u64>> {
+        let url = format!("{}/repos/{}/{}/hooks", self.api_url, org, name);
+        let events: Vec<&str> = hook.events.iter().map(|e| e.as_str()).collect();
+        let mut config = serde_json::json!({ "url": hook.url, "content_type": "json" });
+        if let Some(secret) = &hook.secret {
+            config["secret"] = serde_json::json!(secret);
+        }
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({
+                "name": "web",
+                "active": true,
+                "events": events,
+                "config": config,
+            }))
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let created: GitHubHook = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(created.id))
+    }
+
+    async fn delete_webhook(&self, org: &str, name: &str, id: u64) -> ForgeResult<bool> {
+        let url = format!("{}/repos/{}/{}/hooks/{id}", self.api_url, org, name);
+        let response = self.client.delete(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        Self::expect_success(response, name).await?;
+        Ok(true)
+    }
+
+    async fn list_deploy_keys(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<DeployKey>>> {
+        let url = format!("{}/repos/{}/{}/keys?per_page=100", self.api_url, org, name);
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let keys: Vec<GitHubDeployKey> = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(keys.into_iter().map(DeployKey::from).collect()))
+    }
+
+    async fn add_deploy_key(&self, org: &str, name: &str, key: &NewDeployKey) -> ForgeResult<Option<u64>> {
+        let url = format!("{}/repos/{}/{}/keys", self.api_url, org, name);
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({
+                "title": key.title,
+                "key": key.key,
+                "read_only": key.read_only,
+            }))
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let created: GitHubDeployKey = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(created.id))
+    }
+
+    async fn remove_deploy_key(&self, org: &str, name: &str, id: u64) -> ForgeResult<bool> {
+        let url = format!("{}/repos/{}/{}/keys/{id}", self.api_url, org, name);
+        let response = self.client.delete(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        Self::expect_success(response, name).await?;
+        Ok(true)
+    }
+
+    async fn check_state(&self, org: &str, name: &str, git_ref: &str) -> ForgeResult<Option<CheckState>> {
+        let headers = self.auth_headers().await?;
+        let git_ref = urlencoding::encode(git_ref);
+
+        let url = format!("{}/repos/{}/{}/commits/{}/check-runs?per_page=100", self.api_url, org, name, git_ref);
+        let response = self.client.get(&url)
+            .headers(headers.clone())
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let runs: GitHubCheckRuns = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        let url = format!("{}/repos/{}/{}/commits/{}/status", self.api_url, org, name, git_ref);
+        let response = self.client.get(&url)
+            .headers(headers)
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let status: GitHubCombinedStatus = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(Some(CheckState::combine(
+            runs.check_runs
+                .iter()
+                .map(Self::check_run_state)
+                .chain([Self::combined_status_state(&status)]),
+        )))
+    }
+
+    async fn approve_pull_request(&self, org: &str, name: &str, number: u64) -> ForgeResult<bool> {
+        let url = format!("{}/repos/{}/{}/pulls/{number}/reviews", self.api_url, org, name);
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({ "event": "APPROVE" }))
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        Self::expect_success(response, name).await?;
+        Ok(true)
+    }
+
+    async fn merge_pull_request(&self, org: &str, name: &str, number: u64) -> ForgeResult<bool> {
+        let url = format!("{}/repos/{}/{}/pulls/{number}/merge", self.api_url, org, name);
+        let response = self.client.put(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({}))
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        Self::expect_success(response, name).await?;
+        Ok(true)
+    }
+
+    async fn pull_request_state(&self, org: &str, name: &str, head_branch: &str) -> ForgeResult<Option<PullRequestState>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls?state=all&per_page=1&head={}",
+            self.api_url,
+            org,
+            name,
+            urlencoding::encode(&format!("{org}:{head_branch}"))
+        );
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let pulls: Vec<GitHubPullState> = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(pulls.first().map(|p| match (p.state.as_str(), &p.merged_at) {
+            ("open", _) => PullRequestState::Open,
+            (_, Some(_)) => PullRequestState::Merged,
+            _ => PullRequestState::Closed,
+        }))
+    }
+
+    async fn can_push(&self, org: &str, name: &str) -> ForgeResult<Option<bool>> {
+        let url = format!("{}/repos/{}/{}", self.api_url, org, name);
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let fields: GitHubPermissionFields = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(fields.permissions.map(|p| p.push))
+    }
+
+    async fn create_fork(&self, org: &str, name: &str) -> ForgeResult<Option<String>> {
+        // Returns the existing fork if there is one; forking itself is async
+        let url = format!("{}/repos/{}/{}/forks", self.api_url, org, name);
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({}))
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let fork: GitHubForkOwner = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(fork.owner.login))
+    }
+
+    async fn create_pull_request(&self, org: &str, name: &str, pr: &NewPullRequest) -> ForgeResult<Option<String>> {
+        if let Some(existing) = self.list_pull_requests(org, name).await?
+            .and_then(|open| open.into_iter().find(|p| p.head_branch == pr.head))
+        {
+            if existing.title != pr.title {
+                let url = format!("{}/repos/{}/{}/pulls/{}", self.api_url, org, name, existing.number);
+                let response = self.client.patch(&url)
+                    .headers(self.auth_headers().await?)
+                    .json(&serde_json::json!({ "title": pr.title }))
+                    .send_limited(Forge::GitHub)
+                    .await
+                    .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+                Self::expect_success(response, name).await?;
+            }
+            return Ok(Some(existing.url));
+        }
+
+        let headers = self.auth_headers().await?;
+        let url = format!("{}/repos/{}/{}/pulls", self.api_url, org, name);
+        let response = self.client.post(&url)
+            .headers(headers.clone())
+            .json(&serde_json::json!({
+                "title": pr.title,
+                "body": pr.body,
+                "head": pr.head_owner.as_ref().map_or_else(|| pr.head.clone(), |owner| format!("{owner}:{}", pr.head)),
+                "base": pr.base,
+            }))
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let created: GitHubPull = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        if !pr.labels.is_empty() {
+            let url = format!("{}/repos/{}/{}/issues/{}/labels", self.api_url, org, name, created.number);
+            let response = self.client.post(&url)
+                .headers(headers)
+                .json(&serde_json::json!({ "labels": pr.labels }))
+                .send_limited(Forge::GitHub)
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+            Self::expect_success(response, name).await?;
+        }
+
+        Ok(Some(created.html_url))
+    }
+
     async fn create_repo(&self, org: &str, repo: &Repo) -> ForgeResult<()> {
         // If we know this is a user account, go directly to user endpoint
         if self.owner_type == Some(OwnerType::User) {
-            return self.create_user_repo(repo).await;
+            self.create_user_repo(repo).await?;
+            return self.replace_topics(org, &repo.name, &repo.topics).await;
         }
 
         let headers = self.auth_headers().await?;
@@ -304,13 +1061,14 @@ impl ForgePort for GitHubAdapter {
         let response = self.client.post(&url)
             .headers(headers.clone())
             .json(&request)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
         // If org create fails with 404, try user create (only when owner_type is None/unknown)
         if response.status() == reqwest::StatusCode::NOT_FOUND {
-            return self.create_user_repo(repo).await;
+            self.create_user_repo(repo).await?;
+            return self.replace_topics(org, &repo.name, &repo.topics).await;
         }
 
         if response.status() == reqwest::StatusCode::UNPROCESSABLE_ENTITY {
@@ -329,7 +1087,7 @@ impl ForgePort for GitHubAdapter {
             )));
         }
 
-        Ok(())
+        self.replace_topics(org, &repo.name, &repo.topics).await
     }
 
     async fn update_repo(&self, org: &str, repo: &Repo) -> ForgeResult<()> {
@@ -345,7 +1103,7 @@ impl ForgePort for GitHubAdapter {
         let response = self.client.patch(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -361,7 +1119,7 @@ impl ForgePort for GitHubAdapter {
             )));
         }
 
-        Ok(())
+        self.replace_topics(org, &repo.name, &repo.topics).await
     }
 
     async fn set_archived(&self, org: &str, name: &str, archived: bool) -> ForgeResult<()> {
@@ -377,7 +1135,7 @@ impl ForgePort for GitHubAdapter {
         let response = self.client.patch(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -402,7 +1160,7 @@ impl ForgePort for GitHubAdapter {
 
         let response = self.client.delete(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -431,7 +1189,7 @@ impl ForgePort for GitHubAdapter {
         let response = self.client.patch(&url)
             .headers(headers)
             .json(&body)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -461,7 +1219,7 @@ impl ForgePort for GitHubAdapter {
         let response = self.client.patch(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -480,6 +1238,24 @@ impl ForgePort for GitHubAdapter {
         Ok(())
     }
 
+    async fn transfer_repo(&self, org: &str, name: &str, new_org: &str) -> ForgeResult<()> {
+        let headers = self.auth_headers().await?;
+        let url = format!("{}/repos/{}/{}/transfer", self.api_url, org, name);
+
+        let request = TransferRepoRequest {
+            new_owner: new_org.to_string(),
+        };
+
+        let response = self.client.post(&url)
+            .headers(headers)
+            .json(&request)
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        Self::expect_success(response, name).await?;
+        Ok(())
+    }
+
     async fn list_repos_incremental(
         &self, org: &str, etag: Option<String>,
     ) -> ForgeResult<ListResult> {
@@ -493,7 +1269,7 @@ impl ForgePort for GitHubAdapter {
             });
         }
 
-        let mut headers = self.auth_headers().await?;
+        let mut headers = self.read_headers().await?;
         if let Some(ref etag_value) = etag {
             headers.insert(
                 header::IF_NONE_MATCH,
@@ -506,7 +1282,7 @@ impl ForgePort for GitHubAdapter {
 
         let response = self.client.get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -552,17 +1328,82 @@ impl ForgePort for GitHubAdapter {
     }
 }
 
+#[async_trait]
+impl IssuePort for GitHubAdapter {
+    async fn list_issues(&self, org: &str, name: &str, state: Option<IssueState>) -> ForgeResult<Vec<Issue>> {
+        let headers = self.auth_headers().await?;
+        let state = state.map_or("all", IssueState::as_str);
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "{}/repos/{}/{}/issues?state={state}&per_page=100&page={page}",
+                self.api_url, org, name
+            );
+            let response = self.client.get(&url)
+                .headers(headers.clone())
+                .send_limited(Forge::GitHub)
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+            let batch: Vec<GitHubIssue> = Self::expect_success(response, name).await?.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            let last = batch.len() < 100;
+            issues.extend(batch.into_iter().filter(|i| i.pull_request.is_none()).map(Issue::from));
+            if last {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    async fn create_issue(&self, org: &str, name: &str, issue: &NewIssue) -> ForgeResult<Issue> {
+        let headers = self.auth_headers().await?;
+        // GitHub creates labels that don't exist yet
+        let url = format!("{}/repos/{}/{}/issues", self.api_url, org, name);
+        let response = self.client.post(&url)
+            .headers(headers.clone())
+            .json(&serde_json::json!({
+                "title": issue.title,
+                "body": issue.body,
+                "labels": issue.labels,
+            }))
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let created: GitHubIssue = Self::expect_success(response, name).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        if issue.state == IssueState::Closed {
+            let url = format!("{}/repos/{}/{}/issues/{}", self.api_url, org, name, created.number);
+            let response = self.client.patch(&url)
+                .headers(headers)
+                .json(&serde_json::json!({ "state": "closed" }))
+                .send_limited(Forge::GitHub)
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+            let closed: GitHubIssue = Self::expect_success(response, name).await?.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            return Ok(closed.into());
+        }
+
+        Ok(created.into())
+    }
+}
+
 impl GitHubAdapter {
     /// List repos for a user (fallback when org doesn't exist)
-    async fn list_user_repos(&self, _username: &str) -> ForgeResult<Vec<Repo>> {
-        let headers = self.auth_headers().await?;
+    async fn list_user_repos(&self, username: &str) -> ForgeResult<Vec<Repo>> {
+        let headers = self.read_headers().await?;
         // Use /user/repos (authenticated) instead of /users/{name}/repos (public)
-        // so that private repos are included in the listing.
-        let base_url = format!("{}/user/repos?per_page=100&affiliation=owner", self.api_url);
+        // so that private repos are included in the listing, unless anonymous
+        let base_url = if headers.contains_key(header::AUTHORIZATION) {
+            format!("{}/user/repos?per_page=100&affiliation=owner", self.api_url)
+        } else {
+            format!("{}/users/{}/repos?per_page=100&type=owner", self.api_url, username)
+        };
 
         let response = self.client.get(&base_url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -577,6 +1418,22 @@ impl GitHubAdapter {
         self.fetch_all_pages(response, &base_url).await
     }
 
+    /// Replace the repo's topics; an empty list leaves them alone
+    async fn replace_topics(&self, org: &str, name: &str, topics: &[String]) -> ForgeResult<()> {
+        if topics.is_empty() {
+            return Ok(());
+        }
+        let url = format!("{}/repos/{}/{}/topics", self.api_url, org, name);
+        let response = self.client.put(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({ "names": topics }))
+            .send_limited(Forge::GitHub)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        Self::expect_success(response, name).await?;
+        Ok(())
+    }
+
     /// Create repo under authenticated user (fallback when org doesn't exist)
     async fn create_user_repo(&self, repo: &Repo) -> ForgeResult<()> {
         let headers = self.auth_headers().await?;
@@ -591,7 +1448,7 @@ impl GitHubAdapter {
         let response = self.client.post(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -620,15 +1477,10 @@ mod tests {
     use super::*;
 
     /// Mock auth provider for testing.
-    ///
-    /// Referenced only by the commented-out `test_auth_headers_*` tests below.
-    /// Kept for when those tests are restored (tracked by HF-TESTS — see commit body).
-    #[allow(dead_code)]
     struct MockAuthProvider {
         token: Option<String>,
     }
 
-    #[allow(dead_code)]
     impl MockAuthProvider {
         fn with_token(token: &str) -> Self {
             Self { token: Some(token.to_string()) }
@@ -646,14 +1498,95 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_read_headers_fall_back_to_anonymous() {
+        let anonymous = GitHubAdapter::new(Arc::new(MockAuthProvider::without_token()), "acme").unwrap();
+        assert!(!anonymous.has_token().await);
+        assert!(matches!(anonymous.auth_headers().await, Err(ForgeError::AuthenticationFailed { .. })));
+        let headers = anonymous.read_headers().await.unwrap();
+        assert!(!headers.contains_key(header::AUTHORIZATION));
+        assert!(headers.contains_key(header::ACCEPT));
+
+        let authed = GitHubAdapter::new(Arc::new(MockAuthProvider::with_token("ghp_test123")), "acme").unwrap();
+        assert!(authed.has_token().await);
+        assert!(authed.read_headers().await.unwrap().contains_key(header::AUTHORIZATION));
+    }
+
+    /// Auth provider whose secret store can't be read
+    struct BrokenAuthProvider;
+
+    #[async_trait]
+    impl AuthProvider for BrokenAuthProvider {
+        async fn get_secret(&self, _key: &str) -> anyhow::Result<Option<String>> {
+            anyhow::bail!("secrets file unreadable")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_headers_propagate_auth_errors() {
+        let adapter = GitHubAdapter::new(Arc::new(BrokenAuthProvider), "acme").unwrap();
+        assert!(matches!(adapter.read_headers().await, Err(ForgeError::AuthenticationFailed { .. })));
+    }
+
+    #[test]
+    fn test_check_state_mapping() {
+        let run = |status: &str, conclusion: Option<&str>| GitHubCheckRun {
+            status: status.to_string(),
+            conclusion: conclusion.map(str::to_string),
+        };
+        assert_eq!(GitHubAdapter::check_run_state(&run("completed", Some("skipped"))), CheckState::Success);
+        assert_eq!(GitHubAdapter::check_run_state(&run("completed", Some("timed_out"))), CheckState::Failure);
+        assert_eq!(GitHubAdapter::check_run_state(&run("in_progress", None)), CheckState::Pending);
+
+        // No legacy statuses at all reads as "pending" from the API
+        let none = GitHubCombinedStatus { state: "pending".to_string(), total_count: 0 };
+        assert_eq!(GitHubAdapter::combined_status_state(&none), CheckState::Unknown);
+
+        assert_eq!(
+            CheckState::combine([CheckState::Success, CheckState::Unknown]),
+            CheckState::Success
+        );
+        assert_eq!(
+            CheckState::combine([CheckState::Success, CheckState::Pending, CheckState::Failure]),
+            CheckState::Failure
+        );
+        assert_eq!(CheckState::combine([]), CheckState::Unknown);
+    }
+
+    #[test]
+    fn test_to_fork_info() {
+        let not_fork: GitHubForkFields = serde_json::from_str(r#"{"fork": false}"#).unwrap();
+        assert_eq!(GitHubAdapter::to_fork_info(not_fork), None);
+
+        let archived: GitHubForkFields = serde_json::from_str(
+            r#"{"fork": true, "parent": {"full_name": "upstream/tool", "archived": true}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            GitHubAdapter::to_fork_info(archived),
+            Some(ForkInfo {
+                upstream: Some("upstream/tool".to_string()),
+                status: UpstreamStatus::Archived,
+            })
+        );
+
+        let orphaned: GitHubForkFields = serde_json::from_str(r#"{"fork": true}"#).unwrap();
+        assert_eq!(
+            GitHubAdapter::to_fork_info(orphaned).map(|f| f.status),
+            Some(UpstreamStatus::Deleted)
+        );
+    }
+
     #[test]
     fn test_to_repo_public() {
         let gh_repo = GitHubRepo {
             name: "test-repo".to_string(),
             description: Some("A test repo".to_string()),
             private: false,
+            topics: vec!["Rust".to_string(), "cli".to_string()],
             archived: false,
             default_branch: None,
+            permissions: None,
         };
 
         let repo = GitHubAdapter::to_repo(gh_repo);
@@ -662,6 +1595,7 @@ mod tests {
         assert_eq!(repo.visibility, Visibility::Public);
         assert_eq!(repo.origin, Forge::GitHub);
         assert!(!repo.protected);
+        assert_eq!(repo.topics, vec!["cli", "rust"]);
     }
 
     #[test]
@@ -670,8 +1604,10 @@ mod tests {
             name: "private-repo".to_string(),
             description: None,
             private: true,
+            topics: Vec::new(),
             archived: true,
             default_branch: None,
+            permissions: None,
         };
 
         let repo = GitHubAdapter::to_repo(gh_repo);
@@ -679,6 +1615,19 @@ mod tests {
         assert!(repo.protected); // archived maps to protected
     }
 
+    #[test]
+    fn test_to_repo_read_only_without_admin() {
+        let listed: GitHubRepo = serde_json::from_str(
+            r#"{"name":"shared","description":null,"private":false,"permissions":{"admin":false,"push":true}}"#,
+        ).unwrap();
+        assert!(GitHubAdapter::to_repo(listed).read_only);
+
+        let owned: GitHubRepo = serde_json::from_str(
+            r#"{"name":"mine","description":null,"private":false,"permissions":{"admin":true,"push":true}}"#,
+        ).unwrap();
+        assert!(!GitHubAdapter::to_repo(owned).read_only);
+    }
+
     /*
     #[tokio::test]
     async fn test_auth_headers_missing_token() {
@@ -9,8 +9,11 @@ use std::sync::Arc;
 use tokio::task::JoinSet;
 
 use crate::auth::AuthProvider;
-use crate::types::{Forge, OwnerType, Repo, Visibility};
-use super::{ForgeError, ForgePort, ForgeResult, ListResult};
+use crate::scheduler::LimitedSend;
+use crate::types::repo::normalize_topics;
+use crate::types::{Forge, OwnerType, Repo, Visibility, WebhookEvent};
+use super::rate_limit::RateLimitHeaders;
+use super::{ApprovalRule, ApprovalRuleChange, CheckState, DeployKey, ForgeError, ForgePort, ForgeResult, ForkInfo, Issue, IssuePort, IssueState, ListResult, MergeSettings, NewDeployKey, NewIssue, NewPullRequest, NewWebhook, PullRequest, PullRequestState, StorageUsage, UpstreamStatus, Webhook, APPROVAL_RULE_PREFIX};
 
 /// GitLab API base URL
 const GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
@@ -22,9 +25,245 @@ struct GitLabProject {
     description: Option<String>,
     visibility: String, // "public", "internal", "private"
     #[serde(default)]
+    topics: Vec<String>,
+    #[serde(default)]
     archived: bool,
     #[serde(default)]
     default_branch: Option<String>,
+    /// The token's access (omitted for unauthenticated requests)
+    #[serde(default)]
+    permissions: Option<GitLabPermissions>,
+}
+
+/// Fork fields of a single-project response
+#[derive(Debug, Deserialize)]
+struct GitLabForkFields {
+    #[serde(default)]
+    forked_from_project: Option<GitLabForkParent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabForkParent {
+    id: i64,
+    path_with_namespace: String,
+}
+
+/// Archive flag of a fork's parent project
+#[derive(Debug, Deserialize)]
+struct GitLabArchivedFlag {
+    #[serde(default)]
+    archived: bool,
+}
+
+/// Single-project response with `statistics=true`
+#[derive(Debug, Deserialize)]
+struct GitLabProjectStatistics {
+    #[serde(default)]
+    statistics: Option<GitLabStatistics>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(clippy::struct_field_names)] // mirrors the API's field names
+struct GitLabStatistics {
+    #[serde(default)]
+    repository_size: u64,
+    #[serde(default)]
+    lfs_objects_size: u64,
+    #[serde(default)]
+    job_artifacts_size: u64,
+    #[serde(default)]
+    packages_size: u64,
+}
+
+/// Merge settings of a single-project response
+#[derive(Debug, Deserialize)]
+struct GitLabMergeFields {
+    /// `merge`, `rebase_merge`, or `ff`
+    merge_method: Option<String>,
+    /// `never`, `always`, `default_on`, or `default_off`
+    squash_option: Option<String>,
+}
+
+/// Project-level approval rule response
+#[derive(Debug, Deserialize)]
+struct GitLabApprovalRule {
+    id: i64,
+    name: String,
+    approvals_required: u32,
+    #[serde(default)]
+    users: Vec<GitLabIdRef>,
+    #[serde(default)]
+    groups: Vec<GitLabIdRef>,
+}
+
+/// Any API object reduced to its id
+#[derive(Debug, Deserialize)]
+struct GitLabIdRef {
+    id: i64,
+}
+
+/// Request body for creating or updating an approval rule
+#[derive(Debug, Serialize)]
+struct ApprovalRuleRequest {
+    name: String,
+    approvals_required: u32,
+    user_ids: Vec<i64>,
+    group_ids: Vec<i64>,
+}
+
+impl ApprovalRuleRequest {
+    /// Whether `existing` already matches this request (ignoring id order)
+    fn matches(&self, existing: &GitLabApprovalRule) -> bool {
+        fn sorted(mut ids: Vec<i64>) -> Vec<i64> {
+            ids.sort_unstable();
+            ids
+        }
+        existing.approvals_required == self.approvals_required
+            && sorted(existing.users.iter().map(|u| u.id).collect()) == sorted(self.user_ids.clone())
+            && sorted(existing.groups.iter().map(|g| g.id).collect()) == sorted(self.group_ids.clone())
+    }
+}
+
+/// Merge request list entry
+#[derive(Debug, Deserialize)]
+struct GitLabMergeRequest {
+    iid: u64,
+    title: String,
+    web_url: String,
+    author: GitLabUsername,
+    source_branch: String,
+    target_branch: String,
+    sha: String,
+    #[serde(default)]
+    draft: bool,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabUsername {
+    username: String,
+}
+
+/// Issue list entry
+#[derive(Debug, Deserialize)]
+struct GitLabIssue {
+    iid: u64,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    labels: Vec<String>,
+    state: String, // "opened", "closed"
+    web_url: String,
+}
+
+impl From<GitLabIssue> for Issue {
+    fn from(issue: GitLabIssue) -> Self {
+        Self {
+            number: issue.iid,
+            title: issue.title,
+            body: issue.description.unwrap_or_default(),
+            labels: issue.labels,
+            state: if issue.state == "closed" { IssueState::Closed } else { IssueState::Open },
+            url: issue.web_url,
+        }
+    }
+}
+
+/// Single commit response, reduced to its latest pipeline
+#[derive(Debug, Deserialize)]
+struct GitLabCommitPipeline {
+    last_pipeline: Option<GitLabPipelineStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabPipelineStatus {
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabMergeRequestState {
+    state: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabPermissionFields {
+    id: u64,
+    #[serde(default)]
+    permissions: Option<GitLabPermissions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabPermissions {
+    project_access: Option<GitLabAccess>,
+    group_access: Option<GitLabAccess>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabAccess {
+    access_level: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabForkNamespace {
+    namespace: GitLabNamespacePath,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabNamespacePath {
+    full_path: String,
+}
+
+/// Lowest access level that may push (Developer)
+const DEVELOPER_ACCESS: u32 = 30;
+
+/// Lowest access level that may change project settings (Maintainer)
+const MAINTAINER_ACCESS: u32 = 40;
+
+/// Project hook; events are one flag each
+#[derive(Debug, Deserialize)]
+struct GitLabHook {
+    id: u64,
+    url: String,
+    #[serde(default)]
+    push_events: bool,
+    #[serde(default)]
+    merge_requests_events: bool,
+    #[serde(default)]
+    issues_events: bool,
+    #[serde(default)]
+    releases_events: bool,
+}
+
+impl GitLabHook {
+    fn events(&self) -> Vec<WebhookEvent> {
+        [
+            (self.push_events, WebhookEvent::Push),
+            (self.merge_requests_events, WebhookEvent::PullRequest),
+            (self.issues_events, WebhookEvent::Issues),
+            (self.releases_events, WebhookEvent::Release),
+        ]
+        .into_iter()
+        .filter_map(|(on, event)| on.then_some(event))
+        .collect()
+    }
+}
+
+/// Project deploy key; write access is `can_push`
+#[derive(Debug, Deserialize)]
+struct GitLabDeployKey {
+    id: u64,
+    #[serde(default)]
+    title: String,
+    key: String,
+    #[serde(default)]
+    can_push: bool,
+}
+
+impl From<GitLabDeployKey> for DeployKey {
+    fn from(k: GitLabDeployKey) -> Self {
+        Self { id: k.id, title: k.title, key: k.key, read_only: !k.can_push }
+    }
 }
 
 /// Request body for creating a project
@@ -35,6 +274,8 @@ struct CreateProjectRequest {
     description: Option<String>,
     visibility: String,
     namespace_id: Option<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    topics: Vec<String>,
 }
 
 /// Request body for updating a project
@@ -46,6 +287,9 @@ struct UpdateProjectRequest {
     visibility: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     archived: Option<bool>,
+    /// Replaces every topic; `None` leaves them alone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topics: Option<Vec<String>>,
 }
 
 /// Request body for renaming a project
@@ -55,6 +299,13 @@ struct RenameProjectRequest {
     path: String,
 }
 
+/// Request body for moving a project to another namespace
+#[derive(Debug, Serialize)]
+struct TransferProjectRequest {
+    /// Group or user path (an ID works too)
+    namespace: String,
+}
+
 /// GitLab group response
 #[derive(Debug, Deserialize)]
 struct GitLabGroup {
@@ -76,6 +327,19 @@ impl GitLabAdapter {
         Self::with_api_url(auth, org, GITLAB_API_URL.to_string())
     }
 
+    /// Create a new `GitLabAdapter` for a self-hosted instance, e.g.
+    /// `gitlab.mycompany.com` (API at `https://{host}/api/v4`). A host given
+    /// as a URL keeps its scheme, so `http://` instances work too.
+    pub fn with_host(auth: Arc<dyn AuthProvider>, org: impl Into<String>, host: &str) -> ForgeResult<Self> {
+        let host = host.trim_end_matches('/');
+        let base = if host.starts_with("https://") || host.starts_with("http://") {
+            host.to_string()
+        } else {
+            format!("https://{host}")
+        };
+        Self::with_api_url(auth, org, format!("{base}/api/v4"))
+    }
+
     /// Create a new `GitLabAdapter` with a custom API URL (for testing or self-hosted)
     pub fn with_api_url(auth: Arc<dyn AuthProvider>, org: impl Into<String>, api_url: String) -> ForgeResult<Self> {
         let client = Client::builder()
@@ -94,21 +358,39 @@ impl GitLabAdapter {
 
     /// Get authorization headers with token from auth provider
     async fn auth_headers(&self) -> ForgeResult<header::HeaderMap> {
-        // Construct secret path: gitlab/{org}/token
-        let secret_path = format!("gitlab/{}/token", self.org);
-        let token = self.auth.get_secret(&secret_path).await
-            .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })?
+        let token = self.token().await?
             .ok_or_else(|| ForgeError::AuthenticationFailed {
                 message: format!("No GitLab token found for org: {}", self.org),
             })?;
+        Self::headers(Some(&token))
+    }
 
+    /// The org's stored token, if any
+    async fn token(&self) -> ForgeResult<Option<String>> {
+        // Construct secret path: gitlab/{org}/token
+        let secret_path = format!("gitlab/{}/token", self.org);
+        self.auth.get_secret(&secret_path).await
+            .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })
+    }
+
+    /// Headers for read-only requests: authenticated when a token is
+    /// stored, anonymous otherwise (public projects only). A failing auth
+    /// provider is an error, not a reason to go anonymous
+    async fn read_headers(&self) -> ForgeResult<header::HeaderMap> {
+        let token = self.token().await?;
+        Self::headers(token.as_deref())
+    }
+
+    fn headers(token: Option<&str>) -> ForgeResult<header::HeaderMap> {
         let mut headers = header::HeaderMap::new();
         // GitLab supports both PRIVATE-TOKEN header and Bearer token
-        headers.insert(
-            "PRIVATE-TOKEN",
-            header::HeaderValue::from_str(&token)
-                .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })?,
-        );
+        if let Some(token) = token {
+            headers.insert(
+                "PRIVATE-TOKEN",
+                header::HeaderValue::from_str(token)
+                    .map_err(|e| ForgeError::AuthenticationFailed { message: e.to_string() })?,
+            );
+        }
         headers.insert(
             header::ACCEPT,
             header::HeaderValue::from_static("application/json"),
@@ -124,7 +406,7 @@ impl GitLabAdapter {
 
         let response = self.client.get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitLab)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -146,6 +428,65 @@ impl GitLabAdapter {
         Ok(group.id)
     }
 
+    /// Map a non-success response to a `ForgeError`, passing successes through
+    async fn expect_success(response: Response) -> ForgeResult<Response> {
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        Err(ForgeError::ApiError(format!(
+            "GitLab API error {status}: {body}"
+        )))
+    }
+
+    /// Project id and the caller's access levels
+    async fn permission_fields(&self, org: &str, name: &str) -> ForgeResult<GitLabPermissionFields> {
+        let project_path = format!("{org}/{name}");
+        let url = format!("{}/projects/{}", self.api_url, urlencoding::encode(&project_path));
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))
+    }
+
+    fn pipeline_state(status: &str) -> CheckState {
+        match status {
+            "success" => CheckState::Success,
+            "failed" | "canceled" => CheckState::Failure,
+            "skipped" | "manual" => CheckState::Unknown,
+            _ => CheckState::Pending,
+        }
+    }
+
+    /// Resolve an approver to `(user_ids, group_ids)` contributions.
+    /// Paths with a `/` are groups, bare names are users.
+    async fn resolve_approver(&self, approver: &str) -> ForgeResult<(Option<i64>, Option<i64>)> {
+        if approver.contains('/') {
+            let id = self.get_group_id(&urlencoding::encode(approver)).await?;
+            return Ok((None, Some(id)));
+        }
+
+        let headers = self.auth_headers().await?;
+        let url = format!("{}/users?username={}", self.api_url, urlencoding::encode(approver));
+        let response = self.client.get(&url)
+            .headers(headers)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let users: Vec<GitLabIdRef> = Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        let user = users.first()
+            .ok_or_else(|| ForgeError::ApiError(format!("User '{approver}' not found")))?;
+        Ok((Some(user.id), None))
+    }
+
     /// Convert GitLab API response to our Repo type
     fn to_repo(gl_project: GitLabProject) -> Repo {
         let visibility = match gl_project.visibility.as_str() {
@@ -162,7 +503,16 @@ impl GitLabAdapter {
             mirrors: Vec::new(),
             protected: gl_project.archived,
             staged_for_deletion: false,
+            archived: gl_project.archived,
             default_branch: gl_project.default_branch,
+            read_only: gl_project.permissions.is_some_and(|p| {
+                ![p.project_access, p.group_access]
+                    .iter()
+                    .flatten()
+                    .any(|a| a.access_level >= MAINTAINER_ACCESS)
+            }),
+            topics: normalize_topics(&gl_project.topics),
+            visibility_on: std::collections::BTreeMap::new(),
         }
     }
 
@@ -203,7 +553,7 @@ impl GitLabAdapter {
         };
 
         // Fetch remaining pages in parallel (pages 2..=total_pages)
-        let headers = self.auth_headers().await?;
+        let headers = self.read_headers().await?;
         let mut join_set = JoinSet::new();
 
         let separator = if base_url.contains('?') { '&' } else { '?' };
@@ -215,7 +565,7 @@ impl GitLabAdapter {
             join_set.spawn(async move {
                 let response = client.get(&url)
                     .headers(hdrs)
-                    .send()
+                    .send_limited(Forge::GitLab)
                     .await
                     .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -250,25 +600,46 @@ impl GitLabAdapter {
             all_repos.extend(projects.into_iter().map(Self::to_repo));
         }
 
+        // Pages finish in any order; sort so every run lists the same way
+        all_repos.sort_by(|a, b| a.name.cmp(&b.name));
+
         Ok(all_repos)
     }
 }
 
+/// Rate limit state from a GitLab response: `RateLimit-Remaining` and
+/// `RateLimit-Reset` (unix seconds), plus `Retry-After` on 429s
+pub(crate) fn rate_limit_headers(headers: &header::HeaderMap, now: i64) -> RateLimitHeaders {
+    RateLimitHeaders::parse(headers, "ratelimit-remaining", "ratelimit-reset", now)
+}
+
 #[async_trait]
 impl ForgePort for GitLabAdapter {
+    fn forge(&self) -> Option<Forge> {
+        Some(Forge::GitLab)
+    }
+
+    fn issues(&self) -> Option<&dyn IssuePort> {
+        Some(self)
+    }
+
+    async fn has_token(&self) -> bool {
+        matches!(self.token().await, Ok(Some(_)))
+    }
+
     async fn list_repos(&self, org: &str) -> ForgeResult<Vec<Repo>> {
         // If we know this is a user account, skip the group endpoint entirely
         if self.owner_type == Some(OwnerType::User) {
             return self.list_user_repos(org).await;
         }
 
-        let headers = self.auth_headers().await?;
+        let headers = self.read_headers().await?;
         // Try as group first
         let base_url = format!("{}/groups/{}/projects?per_page=100", self.api_url, org);
 
         let response = self.client.get(&base_url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitLab)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -289,7 +660,7 @@ impl ForgePort for GitLabAdapter {
     }
 
     async fn get_repo(&self, org: &str, name: &str) -> ForgeResult<Repo> {
-        let headers = self.auth_headers().await?;
+        let headers = self.read_headers().await?;
         // GitLab uses URL-encoded "namespace/project" as project ID
         let project_path = format!("{org}/{name}");
         let encoded_path = urlencoding::encode(&project_path);
@@ -297,7 +668,7 @@ impl ForgePort for GitLabAdapter {
 
         let response = self.client.get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitLab)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -319,6 +690,564 @@ impl ForgePort for GitLabAdapter {
         Ok(Self::to_repo(gl_project))
     }
 
+    async fn fork_info(&self, org: &str, name: &str) -> ForgeResult<Option<ForkInfo>> {
+        let headers = self.auth_headers().await?;
+        let project_path = format!("{org}/{name}");
+        let encoded_path = urlencoding::encode(&project_path);
+        let url = format!("{}/projects/{}", self.api_url, encoded_path);
+
+        let response = self.client.get(&url)
+            .headers(headers.clone())
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "GitLab API error {status}: {body}"
+            )));
+        }
+
+        let fields: GitLabForkFields = response.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        let Some(parent) = fields.forked_from_project else {
+            return Ok(None);
+        };
+
+        // forked_from_project doesn't carry the archive flag — look the parent up
+        let parent_url = format!("{}/projects/{}", self.api_url, parent.id);
+        let response = self.client.get(&parent_url)
+            .headers(headers)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        let status = if response.status() == reqwest::StatusCode::NOT_FOUND {
+            UpstreamStatus::Deleted
+        } else if response.status().is_success() {
+            let flag: GitLabArchivedFlag = response.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            if flag.archived { UpstreamStatus::Archived } else { UpstreamStatus::Active }
+        } else {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "GitLab API error {status}: {body}"
+            )));
+        };
+
+        Ok(Some(ForkInfo {
+            upstream: Some(parent.path_with_namespace),
+            status,
+        }))
+    }
+
+    async fn storage_usage(&self, org: &str, name: &str) -> ForgeResult<Option<StorageUsage>> {
+        let headers = self.auth_headers().await?;
+        let project_path = format!("{org}/{name}");
+        let encoded_path = urlencoding::encode(&project_path);
+        let url = format!("{}/projects/{}?statistics=true", self.api_url, encoded_path);
+
+        let response = self.client.get(&url)
+            .headers(headers)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "GitLab API error {status}: {body}"
+            )));
+        }
+
+        let project: GitLabProjectStatistics = response.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        // Statistics are only returned to members with at least Reporter access
+        Ok(project.statistics.map(|s| StorageUsage {
+            repo_bytes: s.repository_size,
+            lfs_bytes: Some(s.lfs_objects_size),
+            artifact_bytes: Some(s.job_artifacts_size),
+            package_bytes: Some(s.packages_size),
+        }))
+    }
+
+    async fn merge_settings(&self, org: &str, name: &str) -> ForgeResult<Option<MergeSettings>> {
+        let headers = self.auth_headers().await?;
+        let project_path = format!("{org}/{name}");
+        let encoded_path = urlencoding::encode(&project_path);
+        let url = format!("{}/projects/{}", self.api_url, encoded_path);
+
+        let response = self.client.get(&url)
+            .headers(headers)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::ApiError(format!(
+                "GitLab API error {status}: {body}"
+            )));
+        }
+
+        let fields: GitLabMergeFields = response.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        let (Some(method), Some(squash)) = (fields.merge_method, fields.squash_option) else {
+            return Ok(None);
+        };
+        // Only fast-forward merges avoid merge commits; with squash forced on
+        // every MR lands as a single commit
+        let fast_forward = method == "ff";
+        Ok(Some(MergeSettings {
+            merge_commit: !fast_forward,
+            squash: squash != "never",
+            rebase: fast_forward && squash != "always",
+        }))
+    }
+
+    async fn list_pull_requests(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<PullRequest>>> {
+        let headers = self.auth_headers().await?;
+        let project_path = format!("{org}/{name}");
+        let mut mrs = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "{}/projects/{}/merge_requests?state=opened&per_page=100&page={page}",
+                self.api_url,
+                urlencoding::encode(&project_path)
+            );
+            let response = self.client.get(&url)
+                .headers(headers.clone())
+                .send_limited(Forge::GitLab)
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Err(ForgeError::RepoNotFound { name: name.to_string() });
+            }
+            let batch: Vec<GitLabMergeRequest> = Self::expect_success(response).await?.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            let last = batch.len() < 100;
+            mrs.extend(batch.into_iter().map(|mr| PullRequest {
+                number: mr.iid,
+                title: mr.title,
+                author: mr.author.username,
+                head_branch: mr.source_branch,
+                head_sha: mr.sha,
+                base_branch: mr.target_branch,
+                draft: mr.draft,
+                url: mr.web_url,
+                created_at: mr.created_at,
+            }));
+            if last {
+                break;
+            }
+        }
+        Ok(Some(mrs))
+    }
+
+    async fn list_webhooks(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<Webhook>>> {
+        let project_path = format!("{org}/{name}");
+        let url = format!("{}/projects/{}/hooks?per_page=100", self.api_url, urlencoding::encode(&project_path));
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        let hooks: Vec<GitLabHook> = Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(Some(hooks.into_iter().map(|h| Webhook {
+            id: h.id,
+            events: h.events(),
+            url: h.url,
+            active: true,
+        }).collect()))
+    }
+
+    async fn create_webhook(&self, org: &str, name: &str, hook: &NewWebhook) -> ForgeResult<Option<u64>> {
+        let project_path = format!("{org}/{name}");
+        let url = format!("{}/projects/{}/hooks", self.api_url, urlencoding::encode(&project_path));
+        let mut body = serde_json::json!({
+            "url": hook.url,
+            "push_events": hook.events.contains(&WebhookEvent::Push),
+            "merge_requests_events": hook.events.contains(&WebhookEvent::PullRequest),
+            "issues_events": hook.events.contains(&WebhookEvent::Issues),
+            "releases_events": hook.events.contains(&WebhookEvent::Release),
+        });
+        if let Some(secret) = &hook.secret {
+            body["token"] = serde_json::json!(secret);
+        }
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .json(&body)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        let created: GitLabHook = Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(created.id))
+    }
+
+    async fn delete_webhook(&self, org: &str, name: &str, id: u64) -> ForgeResult<bool> {
+        let project_path = format!("{org}/{name}");
+        let url = format!("{}/projects/{}/hooks/{id}", self.api_url, urlencoding::encode(&project_path));
+        let response = self.client.delete(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        Self::expect_success(response).await?;
+        Ok(true)
+    }
+
+    async fn list_deploy_keys(&self, org: &str, name: &str) -> ForgeResult<Option<Vec<DeployKey>>> {
+        let project_path = format!("{org}/{name}");
+        let url = format!("{}/projects/{}/deploy_keys?per_page=100", self.api_url, urlencoding::encode(&project_path));
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        let keys: Vec<GitLabDeployKey> = Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(keys.into_iter().map(DeployKey::from).collect()))
+    }
+
+    async fn add_deploy_key(&self, org: &str, name: &str, key: &NewDeployKey) -> ForgeResult<Option<u64>> {
+        let project_path = format!("{org}/{name}");
+        let url = format!("{}/projects/{}/deploy_keys", self.api_url, urlencoding::encode(&project_path));
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .json(&serde_json::json!({
+                "title": key.title,
+                "key": key.key,
+                "can_push": !key.read_only,
+            }))
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        let created: GitLabDeployKey = Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(created.id))
+    }
+
+    async fn remove_deploy_key(&self, org: &str, name: &str, id: u64) -> ForgeResult<bool> {
+        let project_path = format!("{org}/{name}");
+        let url = format!("{}/projects/{}/deploy_keys/{id}", self.api_url, urlencoding::encode(&project_path));
+        let response = self.client.delete(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        Self::expect_success(response).await?;
+        Ok(true)
+    }
+
+    async fn check_state(&self, org: &str, name: &str, git_ref: &str) -> ForgeResult<Option<CheckState>> {
+        let project_path = format!("{org}/{name}");
+        let url = format!(
+            "{}/projects/{}/repository/commits/{}",
+            self.api_url,
+            urlencoding::encode(&project_path),
+            urlencoding::encode(git_ref)
+        );
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        let commit: GitLabCommitPipeline = Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(Some(commit.last_pipeline.map_or(CheckState::Unknown, |p| Self::pipeline_state(&p.status))))
+    }
+
+    async fn approve_pull_request(&self, org: &str, name: &str, number: u64) -> ForgeResult<bool> {
+        let project_path = format!("{org}/{name}");
+        let url = format!(
+            "{}/projects/{}/merge_requests/{number}/approve",
+            self.api_url,
+            urlencoding::encode(&project_path)
+        );
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        Self::expect_success(response).await?;
+        Ok(true)
+    }
+
+    async fn merge_pull_request(&self, org: &str, name: &str, number: u64) -> ForgeResult<bool> {
+        let project_path = format!("{org}/{name}");
+        let url = format!(
+            "{}/projects/{}/merge_requests/{number}/merge",
+            self.api_url,
+            urlencoding::encode(&project_path)
+        );
+        let response = self.client.put(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        Self::expect_success(response).await?;
+        Ok(true)
+    }
+
+    async fn pull_request_state(&self, org: &str, name: &str, head_branch: &str) -> ForgeResult<Option<PullRequestState>> {
+        let project_path = format!("{org}/{name}");
+        let url = format!(
+            "{}/projects/{}/merge_requests?state=all&per_page=1&source_branch={}",
+            self.api_url,
+            urlencoding::encode(&project_path),
+            urlencoding::encode(head_branch)
+        );
+        let response = self.client.get(&url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        let mrs: Vec<GitLabMergeRequestState> = Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(mrs.first().map(|mr| match mr.state.as_str() {
+            "opened" | "locked" => PullRequestState::Open,
+            "merged" => PullRequestState::Merged,
+            _ => PullRequestState::Closed,
+        }))
+    }
+
+    async fn can_push(&self, org: &str, name: &str) -> ForgeResult<Option<bool>> {
+        let fields = self.permission_fields(org, name).await?;
+        Ok(fields.permissions.map(|p| {
+            [p.project_access, p.group_access]
+                .iter()
+                .flatten()
+                .any(|a| a.access_level >= DEVELOPER_ACCESS)
+        }))
+    }
+
+    async fn create_fork(&self, org: &str, name: &str) -> ForgeResult<Option<String>> {
+        let headers = self.auth_headers().await?;
+        let project_path = format!("{org}/{name}");
+        let url = format!("{}/projects/{}/fork", self.api_url, urlencoding::encode(&project_path));
+        let response = self.client.post(&url)
+            .headers(headers.clone())
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        // 409: the user already has a fork, which lives under their username
+        if response.status() == reqwest::StatusCode::CONFLICT {
+            let url = format!("{}/user", self.api_url);
+            let response = self.client.get(&url)
+                .headers(headers)
+                .send_limited(Forge::GitLab)
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+            let user: GitLabUsername = Self::expect_success(response).await?.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            return Ok(Some(user.username));
+        }
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        let fork: GitLabForkNamespace = Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+        Ok(Some(fork.namespace.full_path))
+    }
+
+    async fn create_pull_request(&self, org: &str, name: &str, pr: &NewPullRequest) -> ForgeResult<Option<String>> {
+        if let Some(existing) = self.list_pull_requests(org, name).await?
+            .and_then(|open| open.into_iter().find(|p| p.head_branch == pr.head))
+        {
+            if existing.title != pr.title {
+                let url = format!(
+                    "{}/projects/{}/merge_requests/{}",
+                    self.api_url,
+                    urlencoding::encode(&format!("{org}/{name}")),
+                    existing.number
+                );
+                let response = self.client.put(&url)
+                    .headers(self.auth_headers().await?)
+                    .json(&serde_json::json!({ "title": pr.title }))
+                    .send_limited(Forge::GitLab)
+                    .await
+                    .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+                Self::expect_success(response).await?;
+            }
+            return Ok(Some(existing.url));
+        }
+
+        // MRs from a fork are created on the fork, targeting the source project
+        let mut body = serde_json::json!({
+            "title": pr.title,
+            "description": pr.body,
+            "source_branch": pr.head,
+            "target_branch": pr.base,
+            "labels": pr.labels.join(","),
+        });
+        let source_path = match &pr.head_owner {
+            Some(owner) => {
+                body["target_project_id"] = serde_json::json!(self.permission_fields(org, name).await?.id);
+                format!("{owner}/{name}")
+            }
+            None => format!("{org}/{name}"),
+        };
+        let url = format!(
+            "{}/projects/{}/merge_requests",
+            self.api_url,
+            urlencoding::encode(&source_path)
+        );
+        // GitLab creates labels that don't exist yet
+        let response = self.client.post(&url)
+            .headers(self.auth_headers().await?)
+            .json(&body)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let created: GitLabMergeRequest = Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        Ok(Some(created.web_url))
+    }
+
+    async fn converge_approval_rules(
+        &self,
+        org: &str,
+        name: &str,
+        rules: &[ApprovalRule],
+        dry_run: bool,
+    ) -> ForgeResult<Option<Vec<ApprovalRuleChange>>> {
+        let project_path = format!("{org}/{name}");
+        let base_url = format!(
+            "{}/projects/{}/approval_rules",
+            self.api_url,
+            urlencoding::encode(&project_path)
+        );
+
+        let response = self.client.get(&base_url)
+            .headers(self.auth_headers().await?)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        // Approval rules are a paid-tier feature; free projects answer 403
+        if response.status() == reqwest::StatusCode::FORBIDDEN {
+            return Ok(None);
+        }
+
+        let existing: Vec<GitLabApprovalRule> = Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        let mut changes = Vec::new();
+        for rule in rules {
+            let mut request = ApprovalRuleRequest {
+                name: rule.name.clone(),
+                approvals_required: rule.approvals_required,
+                user_ids: Vec::new(),
+                group_ids: Vec::new(),
+            };
+            for approver in &rule.approvers {
+                let (user, group) = self.resolve_approver(approver).await?;
+                request.user_ids.extend(user);
+                request.group_ids.extend(group);
+            }
+
+            let current = existing.iter().find(|r| r.name == rule.name);
+            let action = match current {
+                Some(r) if request.matches(r) => "unchanged",
+                Some(_) => "updated",
+                None => "created",
+            };
+            if !dry_run && action != "unchanged" {
+                let builder = match current {
+                    Some(r) => self.client.put(format!("{base_url}/{}", r.id)),
+                    None => self.client.post(&base_url),
+                };
+                let response = builder
+                    .headers(self.auth_headers().await?)
+                    .json(&request)
+                    .send_limited(Forge::GitLab)
+                    .await
+                    .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+                Self::expect_success(response).await?;
+            }
+            changes.push(ApprovalRuleChange { rule: rule.name.clone(), action });
+        }
+
+        let stale = existing.iter().filter(|r| {
+            r.name.starts_with(APPROVAL_RULE_PREFIX) && !rules.iter().any(|w| w.name == r.name)
+        });
+        for rule in stale {
+            if !dry_run {
+                let response = self.client.delete(format!("{base_url}/{}", rule.id))
+                    .headers(self.auth_headers().await?)
+                    .send_limited(Forge::GitLab)
+                    .await
+                    .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+                Self::expect_success(response).await?;
+            }
+            changes.push(ApprovalRuleChange { rule: rule.name.clone(), action: "deleted" });
+        }
+
+        Ok(Some(changes))
+    }
+
     async fn create_repo(&self, org: &str, repo: &Repo) -> ForgeResult<()> {
         let headers = self.auth_headers().await?;
 
@@ -337,12 +1266,13 @@ impl ForgePort for GitLabAdapter {
             description: repo.description.clone(),
             visibility: Self::to_gitlab_visibility(&repo.visibility),
             namespace_id,
+            topics: repo.topics.clone(),
         };
 
         let response = self.client.post(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(Forge::GitLab)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -375,12 +1305,13 @@ impl ForgePort for GitLabAdapter {
             description: repo.description.clone(),
             visibility: Some(Self::to_gitlab_visibility(&repo.visibility)),
             archived: None,
+            topics: (!repo.topics.is_empty()).then(|| repo.topics.clone()),
         };
 
         let response = self.client.put(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(Forge::GitLab)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -409,12 +1340,13 @@ impl ForgePort for GitLabAdapter {
             description: None,
             visibility: None,
             archived: Some(archived),
+            topics: None,
         };
 
         let response = self.client.put(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(Forge::GitLab)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -441,7 +1373,7 @@ impl ForgePort for GitLabAdapter {
 
         let response = self.client.delete(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitLab)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -473,7 +1405,7 @@ impl ForgePort for GitLabAdapter {
         let response = self.client.put(&url)
             .headers(headers)
             .json(&body)
-            .send()
+            .send_limited(Forge::GitLab)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -507,7 +1439,7 @@ impl ForgePort for GitLabAdapter {
         let response = self.client.put(&url)
             .headers(headers)
             .json(&request)
-            .send()
+            .send_limited(Forge::GitLab)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -526,6 +1458,30 @@ impl ForgePort for GitLabAdapter {
         Ok(())
     }
 
+    async fn transfer_repo(&self, org: &str, name: &str, new_org: &str) -> ForgeResult<()> {
+        let headers = self.auth_headers().await?;
+        let project_path = format!("{org}/{name}");
+        let encoded_path = urlencoding::encode(&project_path);
+        let url = format!("{}/projects/{}/transfer", self.api_url, encoded_path);
+
+        let request = TransferProjectRequest {
+            namespace: new_org.to_string(),
+        };
+
+        let response = self.client.put(&url)
+            .headers(headers)
+            .json(&request)
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ForgeError::RepoNotFound { name: name.to_string() });
+        }
+        Self::expect_success(response).await?;
+        Ok(())
+    }
+
     async fn list_repos_incremental(
         &self, org: &str, etag: Option<String>,
     ) -> ForgeResult<ListResult> {
@@ -539,7 +1495,7 @@ impl ForgePort for GitLabAdapter {
             });
         }
 
-        let mut headers = self.auth_headers().await?;
+        let mut headers = self.read_headers().await?;
         if let Some(ref etag_value) = etag {
             headers.insert(
                 header::IF_NONE_MATCH,
@@ -553,7 +1509,7 @@ impl ForgePort for GitLabAdapter {
 
         let response = self.client.get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitLab)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -600,15 +1556,87 @@ impl ForgePort for GitLabAdapter {
     }
 }
 
+#[async_trait]
+impl IssuePort for GitLabAdapter {
+    async fn list_issues(&self, org: &str, name: &str, state: Option<IssueState>) -> ForgeResult<Vec<Issue>> {
+        let headers = self.auth_headers().await?;
+        let project_path = format!("{org}/{name}");
+        let state = match state {
+            Some(IssueState::Open) => "&state=opened",
+            Some(IssueState::Closed) => "&state=closed",
+            None => "",
+        };
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "{}/projects/{}/issues?per_page=100&page={page}{state}",
+                self.api_url,
+                urlencoding::encode(&project_path)
+            );
+            let response = self.client.get(&url)
+                .headers(headers.clone())
+                .send_limited(Forge::GitLab)
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Err(ForgeError::RepoNotFound { name: name.to_string() });
+            }
+            let batch: Vec<GitLabIssue> = Self::expect_success(response).await?.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            let last = batch.len() < 100;
+            issues.extend(batch.into_iter().map(Issue::from));
+            if last {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    async fn create_issue(&self, org: &str, name: &str, issue: &NewIssue) -> ForgeResult<Issue> {
+        let headers = self.auth_headers().await?;
+        let project_path = format!("{org}/{name}");
+        let url = format!("{}/projects/{}/issues", self.api_url, urlencoding::encode(&project_path));
+        // GitLab creates labels that don't exist yet
+        let response = self.client.post(&url)
+            .headers(headers.clone())
+            .json(&serde_json::json!({
+                "title": issue.title,
+                "description": issue.body,
+                "labels": issue.labels.join(","),
+            }))
+            .send_limited(Forge::GitLab)
+            .await
+            .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+        let created: GitLabIssue = Self::expect_success(response).await?.json().await
+            .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+
+        if issue.state == IssueState::Closed {
+            let url = format!("{url}/{}", created.iid);
+            let response = self.client.put(&url)
+                .headers(headers)
+                .json(&serde_json::json!({ "state_event": "close" }))
+                .send_limited(Forge::GitLab)
+                .await
+                .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
+            let closed: GitLabIssue = Self::expect_success(response).await?.json().await
+                .map_err(|e| ForgeError::ApiError(format!("Failed to parse response: {e}")))?;
+            return Ok(closed.into());
+        }
+
+        Ok(created.into())
+    }
+}
+
 impl GitLabAdapter {
     /// List repos for a user (fallback when group doesn't exist)
     async fn list_user_repos(&self, username: &str) -> ForgeResult<Vec<Repo>> {
-        let headers = self.auth_headers().await?;
+        let headers = self.read_headers().await?;
         let base_url = format!("{}/users/{}/projects?per_page=100", self.api_url, username);
 
         let response = self.client.get(&base_url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitLab)
             .await
             .map_err(|e| ForgeError::NetworkError(e.to_string()))?;
 
@@ -661,8 +1689,10 @@ mod tests {
             name: "test-repo".to_string(),
             description: Some("A test repo".to_string()),
             visibility: "public".to_string(),
+            topics: Vec::new(),
             archived: false,
             default_branch: None,
+            permissions: None,
         };
 
         let repo = GitLabAdapter::to_repo(gl_project);
@@ -679,8 +1709,10 @@ mod tests {
             name: "private-repo".to_string(),
             description: None,
             visibility: "private".to_string(),
+            topics: Vec::new(),
             archived: true,
             default_branch: None,
+            permissions: None,
         };
 
         let repo = GitLabAdapter::to_repo(gl_project);
@@ -694,14 +1726,47 @@ mod tests {
             name: "internal-repo".to_string(),
             description: None,
             visibility: "internal".to_string(),
+            topics: Vec::new(),
             archived: false,
             default_branch: None,
+            permissions: None,
         };
 
         let repo = GitLabAdapter::to_repo(gl_project);
         assert_eq!(repo.visibility, Visibility::Public); // internal treated as public
     }
 
+    #[test]
+    fn test_approval_rule_matches_ignores_order() {
+        let existing = GitLabApprovalRule {
+            id: 7,
+            name: "codeowners: acme/platform".to_string(),
+            approvals_required: 1,
+            users: vec![GitLabIdRef { id: 3 }, GitLabIdRef { id: 1 }],
+            groups: vec![GitLabIdRef { id: 42 }],
+        };
+        let mut request = ApprovalRuleRequest {
+            name: existing.name.clone(),
+            approvals_required: 1,
+            user_ids: vec![1, 3],
+            group_ids: vec![42],
+        };
+        assert!(request.matches(&existing));
+
+        request.approvals_required = 2;
+        assert!(!request.matches(&existing));
+    }
+
+    #[test]
+    fn test_with_host_keeps_scheme() {
+        let auth = || Arc::new(MockAuthProvider::without_token());
+        let api_url = |host| GitLabAdapter::with_host(auth(), "acme", host).unwrap().api_url;
+
+        assert_eq!(api_url("gitlab.acme.dev"), "https://gitlab.acme.dev/api/v4");
+        assert_eq!(api_url("https://gitlab.acme.dev/"), "https://gitlab.acme.dev/api/v4");
+        assert_eq!(api_url("http://gitlab.internal:8080"), "http://gitlab.internal:8080/api/v4");
+    }
+
     /*
     #[tokio::test]
     async fn test_auth_headers_missing_token() {
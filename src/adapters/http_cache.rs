@@ -0,0 +1,247 @@
+//! Conditional-request cache for forge API reads
+//!
+//! Every GET that goes through [`LimitedSend`](crate::scheduler::LimitedSend)
+//! is looked up here by URL (and a digest of its `Authorization` header, so
+//! tokens never see each other's results). A response that carried an
+//! `ETag` or `Last-Modified` is kept under `~/.config/hyperforge/cache/http/`;
+//! the next request for the URL is sent with `If-None-Match` /
+//! `If-Modified-Since`, and a `304 Not Modified` is answered from the stored
+//! copy. Forges don't count 304s against authenticated rate limits, so
+//! repeated `diff` and `status` runs mostly cost nothing.
+//!
+//! Requests that already carry their own conditional headers (the Phase 5
+//! import) bypass the cache. `HYPERFORGE_HTTP_CACHE=0` turns it off.
+
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Method, Request, RequestBuilder, Response, ResponseBuilderExt, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::build_system::cross_compile::sha256_hex;
+
+/// Set to `0` to send every request unconditionally
+const CACHE_ENV: &str = "HYPERFORGE_HTTP_CACHE";
+
+/// Bodies larger than this aren't kept
+const MAX_BODY: usize = 4 * 1024 * 1024;
+
+/// A stored response and the validators to revalidate it with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    pub status: u16,
+    /// Response headers (pagination links, rate limits, ...)
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    pub stored_at: DateTime<Utc>,
+}
+
+impl CachedResponse {
+    /// `request` made conditional on this copy still being current
+    pub fn condition(&self, request: RequestBuilder) -> RequestBuilder {
+        let request = match &self.etag {
+            Some(etag) => request.header(IF_NONE_MATCH, etag),
+            None => request,
+        };
+        match &self.last_modified {
+            Some(modified) => request.header(IF_MODIFIED_SINCE, modified),
+            None => request,
+        }
+    }
+
+    /// The stored copy as a fresh response, at the URL it was fetched from
+    pub fn to_response(&self) -> Response {
+        let mut builder = http::Response::builder().status(self.status);
+        if let Ok(url) = Url::parse(&self.url) {
+            builder = builder.url(url);
+        }
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        builder.body(self.body.clone().into_bytes()).map_or_else(
+            |_| Response::from(http::Response::new(self.body.clone().into_bytes())),
+            Response::from,
+        )
+    }
+}
+
+/// The on-disk response cache
+#[derive(Debug)]
+pub struct ResponseCache {
+    /// `None` when caching is off
+    dir: Option<PathBuf>,
+}
+
+impl ResponseCache {
+    pub const fn new(dir: Option<PathBuf>) -> Self {
+        Self { dir }
+    }
+
+    /// The cache under ~/.config/hyperforge/cache/http
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<ResponseCache> = OnceLock::new();
+        GLOBAL.get_or_init(|| {
+            let enabled = std::env::var(CACHE_ENV).map_or(true, |v| v != "0");
+            Self::new(
+                dirs::home_dir()
+                    .filter(|_| enabled)
+                    .map(|home| home.join(".config").join("hyperforge").join("cache").join("http")),
+            )
+        })
+    }
+
+    /// The cache key for `request`, if it is one the cache handles
+    pub fn key(&self, request: &Request) -> Option<String> {
+        self.dir.as_ref()?;
+        let headers = request.headers();
+        if request.method() != Method::GET || headers.contains_key(IF_NONE_MATCH) || headers.contains_key(IF_MODIFIED_SINCE) {
+            return None;
+        }
+        let auth = headers.get(AUTHORIZATION).map(HeaderValue::as_bytes).unwrap_or_default();
+        let mut material = request.url().as_str().as_bytes().to_vec();
+        material.push(b'\n');
+        material.extend_from_slice(auth);
+        Some(sha256_hex(&material))
+    }
+
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        Some(self.dir.as_ref()?.join(format!("{key}.json")))
+    }
+
+    /// The stored response for `key`
+    pub fn load(&self, key: &str) -> Option<CachedResponse> {
+        let contents = std::fs::read_to_string(self.path(key)?).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    /// Keep `response` for `key` if it can be revalidated later, returning
+    /// it (rebuilt, since its body has been read) either way
+    pub async fn record(&self, key: &str, response: Response) -> reqwest::Result<Response> {
+        if response.status() != StatusCode::OK {
+            return Ok(response);
+        }
+        let header = |name: HeaderName| response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string);
+        let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+        if etag.is_none() && last_modified.is_none() {
+            return Ok(response);
+        }
+
+        let url = response.url().clone();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+        let entry = CachedResponse {
+            url: url.to_string(),
+            etag,
+            last_modified,
+            status: StatusCode::OK.as_u16(),
+            headers: header_pairs(&headers),
+            body: String::from_utf8_lossy(&body).into_owned(),
+            stored_at: Utc::now(),
+        };
+        // Only text bodies under the cap are kept; the rest pass through
+        if body.len() <= MAX_BODY && std::str::from_utf8(&body).is_ok() {
+            if let Err(e) = self.store(key, &entry) {
+                tracing::debug!("failed to cache {}: {e}", entry.url);
+            }
+        }
+
+        let mut rebuilt = http::Response::builder().status(StatusCode::OK).url(url).body(body.to_vec()).expect("status and URL are valid");
+        *rebuilt.headers_mut() = headers;
+        Ok(Response::from(rebuilt))
+    }
+
+    fn store(&self, key: &str, entry: &CachedResponse) -> Result<(), String> {
+        let path = self.path(key).ok_or("cache disabled")?;
+        write_private(&path, &serde_json::to_string(entry).map_err(|e| e.to_string())?)
+    }
+}
+
+fn header_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+/// Write `contents` to `path` readable only by the owner: cached bodies can
+/// describe private repos
+fn write_private(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    // A unique staging file (created 0600) per write, so concurrent stores
+    // of one key never interleave
+    let mut staged = tempfile::NamedTempFile::new_in(path.parent().unwrap_or_else(|| Path::new("."))).map_err(|e| e.to_string())?;
+    staged.write_all(contents.as_bytes()).map_err(|e| e.to_string())?;
+    staged.persist(path).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(builder: RequestBuilder) -> Request {
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn test_keys() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = ResponseCache::new(Some(temp.path().to_path_buf()));
+        let client = reqwest::Client::new();
+        let url = "https://api.github.com/orgs/acme/repos?page=1";
+
+        let anonymous = cache.key(&request(client.get(url))).unwrap();
+        let alice = cache.key(&request(client.get(url).bearer_auth("alice"))).unwrap();
+        let bob = cache.key(&request(client.get(url).bearer_auth("bob"))).unwrap();
+        assert_ne!(anonymous, alice);
+        assert_ne!(alice, bob);
+        assert_eq!(alice, cache.key(&request(client.get(url).bearer_auth("alice"))).unwrap());
+
+        // Writes and self-conditioned reads bypass the cache
+        assert!(cache.key(&request(client.post(url))).is_none());
+        assert!(cache.key(&request(client.get(url).header(IF_NONE_MATCH, "\"abc\""))).is_none());
+        assert!(ResponseCache::new(None).key(&request(client.get(url))).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = ResponseCache::new(Some(temp.path().to_path_buf()));
+
+        let response = http::Response::builder()
+            .status(200)
+            .header("etag", "W/\"v1\"")
+            .header("link", "<https://api.github.com/orgs/acme/repos?page=2>; rel=\"next\"")
+            .url(Url::parse("https://api.github.com/orgs/acme/repos?page=1").unwrap())
+            .body(br#"[{"name":"tool"}]"#.to_vec())
+            .unwrap();
+        let passed = cache.record("k", Response::from(response)).await.unwrap();
+        assert_eq!(passed.url().as_str(), "https://api.github.com/orgs/acme/repos?page=1");
+        assert_eq!(passed.text().await.unwrap(), r#"[{"name":"tool"}]"#);
+
+        let entry = cache.load("k").unwrap();
+        assert_eq!(entry.etag.as_deref(), Some("W/\"v1\""));
+        let conditioned = request(entry.condition(reqwest::Client::new().get("https://api.github.com/orgs/acme/repos")));
+        assert_eq!(conditioned.headers()[IF_NONE_MATCH], "W/\"v1\"");
+
+        let replayed = entry.to_response();
+        assert_eq!(replayed.status(), StatusCode::OK);
+        assert_eq!(replayed.url().as_str(), "https://api.github.com/orgs/acme/repos?page=1");
+        assert!(replayed.headers().contains_key("link"));
+        assert_eq!(replayed.text().await.unwrap(), r#"[{"name":"tool"}]"#);
+
+        // No validators, nothing kept
+        let plain = Response::from(http::Response::new(b"{}".to_vec()));
+        cache.record("plain", plain).await.unwrap();
+        assert!(cache.load("plain").is_none());
+    }
+}
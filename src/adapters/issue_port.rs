@@ -0,0 +1,56 @@
+//! `IssuePort` trait - issue tracker operations on a forge
+//!
+//! Kept apart from `ForgePort` because not every port has an issue tracker
+//! (`LocalForge` doesn't). Adapters that do expose it via `ForgePort::issues`.
+
+use async_trait::async_trait;
+
+use super::forge_port::ForgeResult;
+
+/// Whether an issue is open or closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueState {
+    Open,
+    Closed,
+}
+
+impl IssueState {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Closed => "closed",
+        }
+    }
+}
+
+/// An issue as listed from a forge (never a pull request)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+    pub state: IssueState,
+    pub url: String,
+}
+
+/// An issue to create on a forge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewIssue {
+    pub title: String,
+    pub body: String,
+    /// Label names; created if missing where the forge allows it
+    pub labels: Vec<String>,
+    /// Closed issues are created and then closed
+    pub state: IssueState,
+}
+
+/// Issue tracker operations
+#[async_trait]
+pub trait IssuePort: Send + Sync {
+    /// Issues on a repo, pull requests excluded. `None` lists every state.
+    async fn list_issues(&self, org: &str, name: &str, state: Option<IssueState>) -> ForgeResult<Vec<Issue>>;
+
+    /// Create an issue, returning it as the forge stored it
+    async fn create_issue(&self, org: &str, name: &str, issue: &NewIssue) -> ForgeResult<Issue>;
+}
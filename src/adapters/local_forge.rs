@@ -13,14 +13,53 @@ use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 
 use crate::adapters::forge_port::{ForgeError, ForgePort, ForgeResult};
+use crate::build_system::cross_compile::sha256_hex;
 use crate::types::{Forge, OwnerType, Repo};
-use crate::types::repo::RepoRecord;
+use crate::types::repo::{same_name, RepoRecord};
 
 /// Sync state tracked per remote forge
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForgeSyncState {
     pub last_synced: DateTime<Utc>,
     pub etag: Option<String>,
+    /// What `etag` was issued for
+    #[serde(flatten, default)]
+    pub scope: EtagScope,
+}
+
+/// Who a repo listing was made as
+///
+/// The same list URL answers differently for another token (private repos
+/// it can or can't see) or once the owner turns out to be a user rather than
+/// an org (another endpoint), so an etag is only replayed under the scope it
+/// was issued for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EtagScope {
+    /// Leading hex of the token's SHA-256; never the token itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_fingerprint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_type: Option<OwnerType>,
+}
+
+impl EtagScope {
+    pub fn new(token: Option<&str>, owner_type: Option<OwnerType>) -> Self {
+        Self {
+            token_fingerprint: token.map(|t| sha256_hex(t.as_bytes())[..16].to_string()),
+            owner_type,
+        }
+    }
+}
+
+/// A forge's stored etag, as checked against the current [`EtagScope`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoredEtag {
+    /// Nothing stored
+    Missing,
+    /// Issued under the current scope; safe to send
+    Current(String),
+    /// Issued under another token or owner type; it has been cleared
+    Reset,
 }
 
 /// In-memory forge state mirror with YAML persistence
@@ -28,8 +67,10 @@ pub struct ForgeSyncState {
 pub struct LocalForge {
     /// Organization name
     org: String,
-    /// Repository state (`repo_name` -> `RepoRecord`)
-    repos: Arc<RwLock<HashMap<String, RepoRecord>>>,
+    /// Repository state (`repo_name` -> `RepoRecord`). Records are shared so
+    /// snapshots of large orgs copy pointers, not records; writes go through
+    /// `Arc::make_mut`.
+    repos: Arc<RwLock<HashMap<String, Arc<RepoRecord>>>>,
     /// Per-forge sync state
     forges: Arc<RwLock<HashMap<Forge, ForgeSyncState>>>,
     /// Path to repos.yaml file
@@ -61,13 +102,22 @@ impl LocalForge {
         }
     }
 
+    /// Key of the record `name` refers to: the exact name, else a name that
+    /// differs only in case (forges are case-insensitive but case-preserving)
+    fn find_key(repos: &HashMap<String, Arc<RepoRecord>>, name: &str) -> Option<String> {
+        if repos.contains_key(name) {
+            return Some(name.to_string());
+        }
+        repos.keys().find(|k| same_name(k, name)).cloned()
+    }
+
     /// Acquire a read lock on the repos map
-    fn read_repos(&self) -> Result<std::sync::RwLockReadGuard<'_, HashMap<String, RepoRecord>>, ForgeError> {
+    fn read_repos(&self) -> Result<std::sync::RwLockReadGuard<'_, HashMap<String, Arc<RepoRecord>>>, ForgeError> {
         self.repos.read().map_err(|e| ForgeError::ApiError(format!("Lock poisoned: {e}")))
     }
 
     /// Acquire a write lock on the repos map
-    fn write_repos(&self) -> Result<std::sync::RwLockWriteGuard<'_, HashMap<String, RepoRecord>>, ForgeError> {
+    fn write_repos(&self) -> Result<std::sync::RwLockWriteGuard<'_, HashMap<String, Arc<RepoRecord>>>, ForgeError> {
         self.repos.write().map_err(|e| ForgeError::ApiError(format!("Lock poisoned: {e}")))
     }
 
@@ -105,14 +155,15 @@ impl LocalForge {
     pub fn add_repo(&self, repo: Repo) -> ForgeResult<()> {
         let mut repos = self.write_repos()?;
 
-        if let Some(existing) = repos.get(&repo.name) {
-            if !existing.dismissed {
+        if let Some(key) = Self::find_key(&repos, &repo.name) {
+            if !repos[&key].dismissed {
                 return Err(ForgeError::RepoAlreadyExists { name: repo.name.clone() });
             }
+            repos.remove(&key);
         }
 
         let record = RepoRecord::from_repo(&repo);
-        repos.insert(repo.name, record);
+        repos.insert(repo.name, Arc::new(record));
         Ok(())
     }
 
@@ -120,7 +171,8 @@ impl LocalForge {
     pub fn remove_repo(&self, name: &str) -> ForgeResult<()> {
         let mut repos = self.write_repos()?;
 
-        repos.remove(name)
+        Self::find_key(&repos, name)
+            .and_then(|key| repos.remove(&key))
             .ok_or_else(|| ForgeError::RepoNotFound { name: name.to_string() })?;
 
         Ok(())
@@ -130,7 +182,7 @@ impl LocalForge {
     pub fn all_repos(&self) -> ForgeResult<Vec<Repo>> {
         let repos = self.read_repos()?;
 
-        Ok(repos.values().map(super::super::types::repo::RepoRecord::to_repo).collect())
+        Ok(repos.values().map(|record| record.to_repo()).collect())
     }
 
     // --- RepoRecord API ---
@@ -148,25 +200,80 @@ impl LocalForge {
         Ok(())
     }
 
+    /// The etag stored for `forge`, cleared (and reported as
+    /// [`StoredEtag::Reset`]) when it was issued under a different scope: a
+    /// "not modified" answer to it could hide real changes
+    pub fn etag_for(&self, forge: &Forge, scope: &EtagScope) -> ForgeResult<StoredEtag> {
+        let mut states = self.write_forges()?;
+        let Some(state) = states.get_mut(forge) else {
+            return Ok(StoredEtag::Missing);
+        };
+        match state.etag.clone() {
+            None => Ok(StoredEtag::Missing),
+            Some(etag) if state.scope == *scope => Ok(StoredEtag::Current(etag)),
+            Some(_) => {
+                state.etag = None;
+                state.scope = scope.clone();
+                Ok(StoredEtag::Reset)
+            }
+        }
+    }
+
+    /// Drop sync state for forges not in `configured`, returning the dropped
+    /// forges in name order
+    pub fn prune_forge_states(&self, configured: &[Forge]) -> ForgeResult<Vec<Forge>> {
+        let mut states = self.write_forges()?;
+        let mut pruned: Vec<Forge> = states.keys().filter(|f| !configured.contains(f)).cloned().collect();
+        for forge in &pruned {
+            states.remove(forge);
+        }
+        pruned.sort_by_key(ToString::to_string);
+        Ok(pruned)
+    }
+
     /// Get a repo record by name
     pub fn get_record(&self, name: &str) -> ForgeResult<RepoRecord> {
         let repos = self.read_repos()?;
-        repos.get(name)
-            .cloned()
+        Self::find_key(&repos, name)
+            .map(|key| RepoRecord::clone(&repos[&key]))
             .ok_or_else(|| ForgeError::RepoNotFound { name: name.to_string() })
     }
 
-    /// Update a repo record
+    /// Update a repo record, replacing any record whose name differs only in
+    /// case
     pub fn update_record(&self, record: &RepoRecord) -> ForgeResult<()> {
         let mut repos = self.write_repos()?;
-        repos.insert(record.name.clone(), record.clone());
+        if let Some(key) = Self::find_key(&repos, &record.name) {
+            repos.remove(&key);
+        }
+        repos.insert(record.name.clone(), Arc::new(record.clone()));
         Ok(())
     }
 
+    /// Record whether the token can only read `name` on `forge`. Returns
+    /// whether the record changed.
+    pub fn set_read_only(&self, name: &str, forge: &Forge, read_only: bool) -> ForgeResult<bool> {
+        let mut repos = self.write_repos()?;
+        let record = Self::find_key(&repos, name)
+            .and_then(|key| repos.get_mut(&key))
+            .ok_or_else(|| ForgeError::RepoNotFound { name: name.to_string() })?;
+        if record.read_only_on.contains(forge) == read_only {
+            return Ok(false);
+        }
+        let record = Arc::make_mut(record);
+        if read_only {
+            record.read_only_on.insert(forge.clone());
+        } else {
+            record.read_only_on.remove(forge);
+        }
+        Ok(true)
+    }
+
     /// Add or merge a repo record
     pub fn upsert_record(&self, record: RepoRecord) -> ForgeResult<()> {
         let mut repos = self.write_repos()?;
-        if let Some(existing) = repos.get_mut(&record.name) {
+        if let Some(existing) = Self::find_key(&repos, &record.name).and_then(|key| repos.get_mut(&key)) {
+            let existing = Arc::make_mut(existing);
             // Merge present_on sets
             for forge in &record.present_on {
                 existing.present_on.insert(forge.clone());
@@ -175,19 +282,34 @@ impl LocalForge {
             if record.description.is_some() {
                 existing.description = record.description.clone();
             }
+            if !record.topics.is_empty() {
+                existing.topics = record.topics.clone();
+            }
             existing.visibility = record.visibility.clone();
         } else {
-            repos.insert(record.name.clone(), record);
+            repos.insert(record.name.clone(), Arc::new(record));
         }
         Ok(())
     }
 
-    /// Get all repo records
+    /// Get all repo records as owned copies
     pub fn all_records(&self) -> ForgeResult<Vec<RepoRecord>> {
+        let repos = self.read_repos()?;
+        Ok(repos.values().map(|record| RepoRecord::clone(record)).collect())
+    }
+
+    /// Snapshot of all repo records that shares them with the store; prefer
+    /// this over `all_records` for read-only passes over large orgs
+    pub fn records(&self) -> ForgeResult<Vec<Arc<RepoRecord>>> {
         let repos = self.read_repos()?;
         Ok(repos.values().cloned().collect())
     }
 
+    /// Number of repo records (including dismissed ones)
+    pub fn record_count(&self) -> ForgeResult<usize> {
+        Ok(self.read_repos()?.len())
+    }
+
     /// Load repositories from YAML file (supports migration from old format)
     pub async fn load_from_yaml(&self) -> ForgeResult<()> {
         let path = self.config_path.as_ref()
@@ -206,7 +328,7 @@ impl LocalForge {
             repos.clear();
             for (name, mut record) in config.repos {
                 record.name = name.clone();
-                repos.insert(name, record);
+                repos.insert(name, Arc::new(record));
             }
             // Load forge states
             let mut states = self.write_forges()?;
@@ -232,7 +354,7 @@ impl LocalForge {
                 let mut r = repo;
                 r.name = name.clone();
                 let record = RepoRecord::from_repo(&r);
-                repos.insert(name, record);
+                repos.insert(name, Arc::new(record));
             }
             return Ok(());
         }
@@ -245,25 +367,25 @@ impl LocalForge {
         let path = self.config_path.as_ref()
             .ok_or_else(|| ForgeError::ApiError("No config path set".to_string()))?;
 
-        // Clone data while holding lock, then release before async operations
-        let (yaml_repos, forge_states, owner_type) = {
+        // Snapshot shared records while holding lock, then release before async operations
+        let (records, forge_states, owner_type) = {
             let repos = self.read_repos()?;
             let states = self.read_forges()?;
             let ot = self.owner_type.read().map_err(|e| {
                 ForgeError::ApiError(format!("Lock poisoned: {e}"))
             })?;
 
-            let repo_map: HashMap<String, RepoRecord> = repos.iter()
-                .map(|(name, record)| (name.clone(), record.clone()))
+            let records: Vec<(String, Arc<RepoRecord>)> = repos.iter()
+                .map(|(name, record)| (name.clone(), Arc::clone(record)))
                 .collect();
             let state_map: HashMap<String, ForgeSyncState> = states.iter()
-                .map(|(forge, state)| (format!("{forge:?}").to_lowercase(), state.clone()))
+                .map(|(forge, state)| (forge.to_string(), state.clone()))
                 .collect();
-            (repo_map, state_map, ot.clone())
+            (records, state_map, ot.clone())
         }; // Locks are dropped here
 
-        let config = ReposYaml {
-            repos: yaml_repos,
+        let config = ReposYamlRef {
+            repos: records.iter().map(|(name, record)| (name.as_str(), &**record)).collect(),
             forge_states,
             owner_type,
         };
@@ -285,7 +407,7 @@ impl LocalForge {
 #[async_trait]
 impl ForgePort for LocalForge {
     async fn list_repos(&self, org: &str) -> ForgeResult<Vec<Repo>> {
-        if org != self.org {
+        if !same_name(org, &self.org) {
             return Ok(Vec::new());
         }
 
@@ -293,19 +415,19 @@ impl ForgePort for LocalForge {
     }
 
     async fn get_repo(&self, org: &str, name: &str) -> ForgeResult<Repo> {
-        if org != self.org {
+        if !same_name(org, &self.org) {
             return Err(ForgeError::RepoNotFound { name: name.to_string() });
         }
 
         let repos = self.read_repos()?;
 
-        repos.get(name)
-            .map(super::super::types::repo::RepoRecord::to_repo)
+        Self::find_key(&repos, name)
+            .map(|key| repos[&key].to_repo())
             .ok_or_else(|| ForgeError::RepoNotFound { name: name.to_string() })
     }
 
     async fn create_repo(&self, org: &str, repo: &Repo) -> ForgeResult<()> {
-        if org != self.org {
+        if !same_name(org, &self.org) {
             return Err(ForgeError::ApiError(format!(
                 "Organization mismatch: expected {}, got {}",
                 self.org, org
@@ -316,7 +438,7 @@ impl ForgePort for LocalForge {
     }
 
     async fn update_repo(&self, org: &str, repo: &Repo) -> ForgeResult<()> {
-        if org != self.org {
+        if !same_name(org, &self.org) {
             return Err(ForgeError::ApiError(format!(
                 "Organization mismatch: expected {}, got {}",
                 self.org, org
@@ -325,18 +447,19 @@ impl ForgePort for LocalForge {
 
         let mut repos = self.write_repos()?;
 
-        if !repos.contains_key(&repo.name) {
+        let Some(key) = Self::find_key(&repos, &repo.name) else {
             return Err(ForgeError::RepoNotFound { name: repo.name.clone() });
-        }
+        };
+        repos.remove(&key);
 
         // Update the existing record from the incoming Repo
         let record = RepoRecord::from_repo(repo);
-        repos.insert(repo.name.clone(), record);
+        repos.insert(repo.name.clone(), Arc::new(record));
         Ok(())
     }
 
     async fn delete_repo(&self, org: &str, name: &str) -> ForgeResult<()> {
-        if org != self.org {
+        if !same_name(org, &self.org) {
             return Err(ForgeError::ApiError(format!(
                 "Organization mismatch: expected {}, got {}",
                 self.org, org
@@ -345,7 +468,9 @@ impl ForgePort for LocalForge {
 
         let mut repos = self.write_repos()?;
 
-        let record = repos.get_mut(name)
+        let record = Self::find_key(&repos, name)
+            .and_then(|key| repos.get_mut(&key))
+            .map(Arc::make_mut)
             .ok_or_else(|| ForgeError::RepoNotFound { name: name.to_string() })?;
 
         record.dismissed = true;
@@ -357,8 +482,8 @@ impl ForgePort for LocalForge {
 
     async fn set_archived(&self, _org: &str, name: &str, archived: bool) -> ForgeResult<()> {
         let mut repos = self.write_repos()?;
-        if let Some(record) = repos.get_mut(name) {
-            record.protected = archived;
+        if let Some(record) = Self::find_key(&repos, name).and_then(|key| repos.get_mut(&key)).map(Arc::make_mut) {
+            record.archived = archived;
         }
         Ok(())
     }
@@ -366,14 +491,14 @@ impl ForgePort for LocalForge {
     async fn set_default_branch(&self, _org: &str, name: &str, branch: &str) -> ForgeResult<()> {
         // LocalForge now tracks default branch in RepoRecord
         let mut repos = self.write_repos()?;
-        if let Some(record) = repos.get_mut(name) {
+        if let Some(record) = Self::find_key(&repos, name).and_then(|key| repos.get_mut(&key)).map(Arc::make_mut) {
             record.default_branch = branch.to_string();
         }
         Ok(())
     }
 
     async fn rename_repo(&self, org: &str, old_name: &str, new_name: &str) -> ForgeResult<()> {
-        if org != self.org {
+        if !same_name(org, &self.org) {
             return Err(ForgeError::ApiError(format!(
                 "Organization mismatch: expected {}, got {}",
                 self.org, org
@@ -383,21 +508,25 @@ impl ForgePort for LocalForge {
         let mut repos = self.write_repos()?;
 
         // Get the existing record
-        let mut record = repos.remove(old_name).ok_or_else(|| {
+        let old_key = Self::find_key(&repos, old_name).ok_or_else(|| {
+            ForgeError::RepoNotFound { name: old_name.to_string() }
+        })?;
+        let mut record = repos.remove(&old_key).ok_or_else(|| {
             ForgeError::RepoNotFound { name: old_name.to_string() }
         })?;
 
-        // Check new name doesn't already exist
-        if repos.contains_key(new_name) {
+        // Check new name doesn't already exist (a case-only rename is fine)
+        if Self::find_key(&repos, new_name).is_some() {
             // Put the old one back
-            repos.insert(old_name.to_string(), record);
+            repos.insert(old_key, record);
             return Err(ForgeError::RepoAlreadyExists { name: new_name.to_string() });
         }
 
         // Track the previous name
-        record.previous_names.push(old_name.to_string());
+        let renamed = Arc::make_mut(&mut record);
+        renamed.previous_names.push(old_key);
         // Update the name and insert with new key
-        record.name = new_name.to_string();
+        renamed.name = new_name.to_string();
         repos.insert(new_name.to_string(), record);
 
         Ok(())
@@ -415,6 +544,17 @@ struct ReposYaml {
     owner_type: Option<OwnerType>,
 }
 
+/// Borrowed view of `ReposYaml` for saving without copying records; repos
+/// are written in name order
+#[derive(Serialize)]
+struct ReposYamlRef<'a> {
+    repos: std::collections::BTreeMap<&'a str, &'a RepoRecord>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    forge_states: HashMap<String, ForgeSyncState>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner_type: Option<OwnerType>,
+}
+
 /// Old YAML format for migration from pre-state-mirror versions
 #[derive(Debug, Deserialize)]
 struct OldReposYaml {
@@ -520,6 +660,26 @@ mod tests {
         assert!(matches!(result, Err(ForgeError::RepoAlreadyExists { .. })));
     }
 
+    #[tokio::test]
+    async fn test_local_forge_names_case_insensitive() {
+        let forge = LocalForge::new("TestOrg");
+        forge.create_repo("testorg", &Repo::new("MyRepo", Forge::GitHub)).await.unwrap();
+
+        assert!(forge.repo_exists("testorg", "myrepo").await.unwrap());
+        assert_eq!(forge.get_record("myrepo").unwrap().name, "MyRepo");
+        assert!(matches!(
+            forge.create_repo("TestOrg", &Repo::new("MYREPO", Forge::GitHub)).await,
+            Err(ForgeError::RepoAlreadyExists { .. })
+        ));
+
+        // A case-only rename is allowed and keeps one record
+        forge.rename_repo("TestOrg", "myrepo", "myrepo").await.unwrap();
+        let record = forge.get_record("MyRepo").unwrap();
+        assert_eq!(record.name, "myrepo");
+        assert_eq!(record.previous_names, vec!["MyRepo".to_string()]);
+        assert_eq!(forge.record_count().unwrap(), 1);
+    }
+
     #[tokio::test]
     async fn test_local_forge_wrong_org() {
         let forge = LocalForge::new("testorg");
@@ -705,6 +865,22 @@ mod tests {
         assert_eq!(all.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_set_read_only() {
+        let forge = LocalForge::new("testorg");
+        forge.add_repo(Repo::new("shared", Forge::GitHub).with_mirror(Forge::Codeberg)).unwrap();
+
+        assert!(forge.set_read_only("Shared", &Forge::GitHub, true).unwrap());
+        assert!(!forge.set_read_only("shared", &Forge::GitHub, true).unwrap(), "unchanged");
+        let record = forge.get_record("shared").unwrap();
+        assert!(record.read_only_on.contains(&Forge::GitHub));
+        assert!(!record.read_only_on.contains(&Forge::Codeberg));
+
+        assert!(forge.set_read_only("shared", &Forge::GitHub, false).unwrap());
+        assert!(forge.get_record("shared").unwrap().read_only_on.is_empty());
+        assert!(forge.set_read_only("missing", &Forge::GitHub, true).is_err());
+    }
+
     #[tokio::test]
     async fn test_upsert_record_merge() {
         let forge = LocalForge::new("testorg");
@@ -741,12 +917,15 @@ mod tests {
             default_branch: "main".to_string(),
             present_on,
             protected: false,
+            archived: false,
             managed: true,
             dismissed: false,
             deleted_from: Vec::new(),
             deleted_at: None,
             privatized_on: std::collections::HashSet::new(),
+            read_only_on: std::collections::HashSet::new(),
             previous_names: Vec::new(),
+            topics: Vec::new(),
             local_path: None,
             forges: Vec::new(),
             ssh: std::collections::HashMap::new(),
@@ -775,6 +954,7 @@ mod tests {
         forge.set_forge_state(Forge::GitHub, ForgeSyncState {
             last_synced: now,
             etag: Some("abc123".to_string()),
+            scope: EtagScope::default(),
         }).unwrap();
 
         let states = forge.forge_states().unwrap();
@@ -783,6 +963,39 @@ mod tests {
         assert_eq!(states[&Forge::GitHub].etag, Some("abc123".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_forge_state_gc() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("repos.yaml");
+        let forge = LocalForge::with_config_path("testorg", path.clone());
+        let alice = EtagScope::new(Some("ghp_alice"), Some(OwnerType::Org));
+        for f in [Forge::GitHub, Forge::Codeberg, Forge::GitLab] {
+            forge.set_forge_state(f, ForgeSyncState {
+                last_synced: Utc::now(),
+                etag: Some("\"v1\"".to_string()),
+                scope: alice.clone(),
+            }).unwrap();
+        }
+        forge.save_to_yaml().await.unwrap();
+        let yaml = std::fs::read_to_string(&path).unwrap();
+        assert!(!yaml.contains("ghp_alice"));
+
+        let reloaded = LocalForge::with_config_path("testorg", path);
+        reloaded.load_from_yaml().await.unwrap();
+
+        assert_eq!(reloaded.prune_forge_states(&[Forge::GitHub, Forge::Codeberg]).unwrap(), vec![Forge::GitLab]);
+        assert_eq!(reloaded.forge_states().unwrap().len(), 2);
+
+        assert_eq!(reloaded.etag_for(&Forge::GitHub, &alice).unwrap(), StoredEtag::Current("\"v1\"".to_string()));
+        let bob = EtagScope::new(Some("ghp_bob"), Some(OwnerType::Org));
+        assert_eq!(reloaded.etag_for(&Forge::GitHub, &bob).unwrap(), StoredEtag::Reset);
+        assert_eq!(reloaded.etag_for(&Forge::GitHub, &bob).unwrap(), StoredEtag::Missing);
+
+        let as_user = EtagScope::new(Some("ghp_alice"), Some(OwnerType::User));
+        assert_eq!(reloaded.etag_for(&Forge::Codeberg, &as_user).unwrap(), StoredEtag::Reset);
+        assert_eq!(reloaded.etag_for(&Forge::GitLab, &alice).unwrap(), StoredEtag::Missing);
+    }
+
     #[tokio::test]
     async fn test_rename_tracks_previous_names() {
         let forge = LocalForge::new("testorg");
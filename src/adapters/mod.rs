@@ -4,12 +4,19 @@ pub mod codeberg;
 pub mod forge_port;
 pub mod github;
 pub mod gitlab;
+pub mod http_cache;
+pub mod issue_port;
 pub mod local_forge;
+pub mod rate_limit;
 pub mod registry;
 pub mod releases;
+pub mod simulation;
 
-pub use codeberg::CodebergAdapter;
-pub use forge_port::{ForgeError, ForgePort, ForgeResult, ListResult};
+pub use codeberg::{CodebergAdapter, GiteaAdapter};
+pub use forge_port::{ApprovalRule, ApprovalRuleChange, BranchProtection, CheckState, DeployKey, ForgeError, ForgePort, ForgeResult, ForkInfo, ListResult, MergeSettings, NewDeployKey, NewPullRequest, NewWebhook, PullRequest, PullRequestState, StorageUsage, UpstreamStatus, Webhook, APPROVAL_RULE_PREFIX};
 pub use github::GitHubAdapter;
+pub use issue_port::{Issue, IssuePort, IssueState, NewIssue};
 pub use gitlab::GitLabAdapter;
-pub use local_forge::{ForgeSyncState, LocalForge};
+pub use local_forge::{EtagScope, ForgeSyncState, LocalForge, StoredEtag};
+pub use http_cache::ResponseCache;
+pub use rate_limit::RateLimiter;
@@ -0,0 +1,264 @@
+//! Rate limit handling shared by every forge adapter
+//!
+//! Each adapter knows which headers its forge uses to report rate limits
+//! (`rate_limit_headers` in `github`, `gitlab` and `codeberg`). Every API
+//! request goes through [`LimitedSend`](crate::scheduler::LimitedSend), which
+//! asks the process-wide [`RateLimiter`] to:
+//! - hold requests to a forge while it is paused
+//! - pause the forge and retry a request that was rate limited (429, or 403
+//!   with `Retry-After` or an exhausted quota), up to `MAX_RETRIES` times
+//! - pause the forge until the quota resets when a response reports none left
+//!
+//! Waits come from `Retry-After`, else the quota reset time, else
+//! `DEFAULT_BACKOFF` doubled per attempt. Waits longer than `MAX_WAIT` are
+//! not taken: the rate-limited response is returned and fails as before.
+//!
+//! Every pause is announced on a broadcast channel. [`with_notices`] turns
+//! the announcements into [`HyperforgeEvent::RateLimited`] events in a hub
+//! method's stream.
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+
+use crate::hub::HyperforgeEvent;
+use crate::types::Forge;
+
+/// Retries of one request before its rate-limited response is returned
+pub const MAX_RETRIES: u32 = 5;
+
+/// Wait when a forge says "limited" without saying for how long
+const DEFAULT_BACKOFF: Duration = Duration::from_mins(1);
+
+/// Longest pause taken; a quota resetting later fails the request instead
+const MAX_WAIT: Duration = Duration::from_mins(15);
+
+/// What a response's headers say about the forge's rate limit
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitHeaders {
+    /// Requests left in the current window
+    pub remaining: Option<u64>,
+    /// When the window resets (unix seconds)
+    pub reset_at: Option<i64>,
+    /// `Retry-After`, in seconds
+    pub retry_after: Option<u64>,
+}
+
+impl RateLimitHeaders {
+    /// Read the given remaining/reset header names plus `Retry-After`
+    pub fn parse(headers: &HeaderMap, remaining: &str, reset: &str, now: i64) -> Self {
+        let number = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<i64>().ok();
+        Self {
+            remaining: number(remaining).and_then(|n| u64::try_from(n).ok()),
+            reset_at: number(reset),
+            retry_after: headers
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| parse_retry_after(v, now)),
+        }
+    }
+
+    /// Whether a response with `status` and these headers was rate limited
+    pub fn is_limited(&self, status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS
+            || (status == StatusCode::FORBIDDEN && (self.remaining == Some(0) || self.retry_after.is_some()))
+    }
+
+    /// How long to wait before retrying a rate-limited request
+    pub fn backoff(&self, attempt: u32, now: i64) -> Duration {
+        if let Some(secs) = self.retry_after {
+            return Duration::from_secs(secs.max(1));
+        }
+        if let Some(wait) = self.until_reset(now) {
+            return wait;
+        }
+        DEFAULT_BACKOFF * 2u32.saturating_pow(attempt)
+    }
+
+    /// Time until the quota resets, when none is left
+    pub fn exhausted_for(&self, now: i64) -> Option<Duration> {
+        if self.remaining == Some(0) { self.until_reset(now) } else { None }
+    }
+
+    fn until_reset(&self, now: i64) -> Option<Duration> {
+        let secs = self.reset_at? - now;
+        // One extra second so the first request lands after the reset
+        Some(Duration::from_secs(u64::try_from(secs.max(0)).unwrap_or(0) + 1))
+    }
+}
+
+/// `Retry-After` as seconds from `now`: delta seconds or an HTTP date
+fn parse_retry_after(value: &str, now: i64) -> Option<u64> {
+    if let Ok(secs) = value.trim().parse::<u64>() {
+        return Some(secs);
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value.trim()).ok()?.timestamp();
+    u64::try_from((at - now).max(0)).ok()
+}
+
+/// The rate limit headers `forge` sends
+pub fn headers_for(forge: &Forge, headers: &HeaderMap, now: i64) -> RateLimitHeaders {
+    match forge {
+        Forge::GitHub => super::github::rate_limit_headers(headers, now),
+        Forge::GitLab => super::gitlab::rate_limit_headers(headers, now),
+        Forge::Codeberg | Forge::Gitea { .. } => super::codeberg::rate_limit_headers(headers, now),
+    }
+}
+
+/// A forge paused for rate limiting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitNotice {
+    pub forge: String,
+    pub retry_after: Duration,
+}
+
+/// Process-wide rate limit state per forge
+#[derive(Debug)]
+pub struct RateLimiter {
+    paused_until: Mutex<HashMap<Forge, Instant>>,
+    notices: broadcast::Sender<RateLimitNotice>,
+}
+
+impl RateLimiter {
+    fn new() -> Self {
+        Self { paused_until: Mutex::new(HashMap::new()), notices: broadcast::channel(64).0 }
+    }
+
+    /// The limiter every adapter shares
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<RateLimiter> = OnceLock::new();
+        GLOBAL.get_or_init(Self::new)
+    }
+
+    /// Announcements of every pause from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<RateLimitNotice> {
+        self.notices.subscribe()
+    }
+
+    /// Wait out `forge`'s pause, if any
+    pub async fn wait(&self, forge: &Forge) {
+        let until = self.paused_until.lock().ok().and_then(|paused| paused.get(forge).copied());
+        if let Some(until) = until {
+            tokio::time::sleep_until(until).await;
+        }
+    }
+
+    /// Pause `forge` for `wait`; `false` (and no pause) when `wait` is
+    /// longer than we are willing to sit out
+    pub fn pause(&self, forge: &Forge, wait: Duration) -> bool {
+        if wait > MAX_WAIT {
+            return false;
+        }
+        let until = Instant::now() + wait;
+        if let Ok(mut paused) = self.paused_until.lock() {
+            // Concurrent requests hitting the same limit don't re-announce it
+            if paused.get(forge).is_some_and(|existing| *existing >= until) {
+                return true;
+            }
+            paused.insert(forge.clone(), until);
+        }
+        tracing::warn!(forge = %forge, wait_secs = wait.as_secs(), "rate limited, pausing requests");
+        // No subscribers is fine
+        let _ = self.notices.send(RateLimitNotice { forge: forge.to_string(), retry_after: wait });
+        true
+    }
+}
+
+/// Pass `events` through, interleaving a [`HyperforgeEvent::RateLimited`]
+/// whenever a forge is paused while the stream runs
+pub fn with_notices<S>(events: S) -> impl Stream<Item = HyperforgeEvent> + Send + 'static
+where
+    S: Stream<Item = HyperforgeEvent> + Send + 'static,
+{
+    let mut notices = RateLimiter::global().subscribe();
+    stream! {
+        let mut events = Box::pin(events);
+        loop {
+            let next = tokio::select! {
+                biased;
+                Ok(notice) = notices.recv() => Err(notice),
+                event = events.next() => Ok(event),
+            };
+            match next {
+                Ok(Some(event)) => yield event,
+                Ok(None) => break,
+                Err(notice) => yield HyperforgeEvent::RateLimited {
+                    forge: notice.forge,
+                    retry_after: notice.retry_after.as_secs(),
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_limited_responses_and_waits() {
+        // GitHub secondary limit: 403 with Retry-After
+        let secondary = headers_for(&Forge::GitHub, &headers(&[("retry-after", "30")]), NOW);
+        assert!(secondary.is_limited(StatusCode::FORBIDDEN));
+        assert_eq!(secondary.backoff(0, NOW), Duration::from_secs(30));
+
+        // Primary limit: quota gone, wait for the reset
+        let primary = headers_for(
+            &Forge::GitHub,
+            &headers(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", "1700000120")]),
+            NOW,
+        );
+        assert!(primary.is_limited(StatusCode::FORBIDDEN));
+        assert_eq!(primary.backoff(0, NOW), Duration::from_secs(121));
+        assert_eq!(primary.exhausted_for(NOW), Some(Duration::from_secs(121)));
+
+        // GitLab's names, and a 429 without any hint backs off exponentially
+        let gitlab = headers_for(&Forge::GitLab, &headers(&[("ratelimit-remaining", "12")]), NOW);
+        assert_eq!(gitlab.remaining, Some(12));
+        assert!(gitlab.is_limited(StatusCode::TOO_MANY_REQUESTS));
+        assert_eq!(gitlab.backoff(2, NOW), Duration::from_mins(4));
+
+        // A plain permission error isn't a rate limit
+        let denied = headers_for(&Forge::Codeberg, &headers(&[]), NOW);
+        assert!(!denied.is_limited(StatusCode::FORBIDDEN));
+        assert_eq!(denied.exhausted_for(NOW), None);
+    }
+
+    #[test]
+    fn test_retry_after_http_date() {
+        assert_eq!(parse_retry_after("Tue, 14 Nov 2023 22:14:20 GMT", NOW), Some(60));
+        assert_eq!(parse_retry_after("soon", NOW), None);
+    }
+
+    #[tokio::test]
+    async fn test_pause_announces_and_refuses_long_waits() {
+        let limiter = RateLimiter::new();
+        let mut notices = limiter.subscribe();
+        let forge = Forge::Gitea { host: "git.acme.dev".to_string() };
+
+        assert!(!limiter.pause(&forge, Duration::from_hours(1)));
+        assert!(limiter.pause(&forge, Duration::from_millis(10)));
+        let notice = notices.recv().await.unwrap();
+        assert_eq!(notice.forge, forge.to_string());
+
+        limiter.wait(&forge).await;
+        limiter.wait(&Forge::GitHub).await;
+    }
+}
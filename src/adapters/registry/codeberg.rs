@@ -9,6 +9,8 @@ use reqwest::{header, Client};
 use std::sync::Arc;
 
 use crate::auth::AuthProvider;
+use crate::scheduler::LimitedSend;
+use crate::types::Forge;
 use super::{ImageTag, PackageInfo, RegistryError, RegistryPort, RegistryResult};
 
 /// Gitea package list response
@@ -71,7 +73,7 @@ impl RegistryPort for CodebergRegistryAdapter {
 
         let response = self.client.get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::Codeberg)
             .await
             .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
 
@@ -129,7 +131,7 @@ impl RegistryPort for CodebergRegistryAdapter {
 
         let response = self.client.get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::Codeberg)
             .await
             .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
 
@@ -172,7 +174,7 @@ impl RegistryPort for CodebergRegistryAdapter {
 
         let response = self.client.delete(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::Codeberg)
             .await
             .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
 
@@ -9,6 +9,8 @@ use reqwest::{header, Client};
 use std::sync::Arc;
 
 use crate::auth::AuthProvider;
+use crate::scheduler::LimitedSend;
+use crate::types::Forge;
 use super::{ImageTag, PackageInfo, RegistryError, RegistryPort, RegistryResult};
 
 /// GitHub packages list response.
@@ -113,7 +115,7 @@ impl RegistryPort for GitHubRegistryAdapter {
 
         let response = self.client.get(&url)
             .headers(headers.clone())
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
 
@@ -125,7 +127,7 @@ impl RegistryPort for GitHubRegistryAdapter {
             );
             self.client.get(&user_url)
                 .headers(headers)
-                .send()
+                .send_limited(Forge::GitHub)
                 .await
                 .map_err(|e| RegistryError::NetworkError(e.to_string()))?
         } else {
@@ -179,7 +181,7 @@ impl RegistryPort for GitHubRegistryAdapter {
 
         let response = self.client.get(&url)
             .headers(headers.clone())
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
 
@@ -191,7 +193,7 @@ impl RegistryPort for GitHubRegistryAdapter {
             );
             self.client.get(&user_url)
                 .headers(headers)
-                .send()
+                .send_limited(Forge::GitHub)
                 .await
                 .map_err(|e| RegistryError::NetworkError(e.to_string()))?
         } else {
@@ -271,7 +273,7 @@ impl RegistryPort for GitHubRegistryAdapter {
 
         let response = self.client.get(&url)
             .headers(headers.clone())
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
 
@@ -283,7 +285,7 @@ impl RegistryPort for GitHubRegistryAdapter {
             );
             self.client.get(&user_url)
                 .headers(headers.clone())
-                .send()
+                .send_limited(Forge::GitHub)
                 .await
                 .map_err(|e| RegistryError::NetworkError(e.to_string()))?
         } else {
@@ -306,7 +308,7 @@ impl RegistryPort for GitHubRegistryAdapter {
 
         let response = self.client.delete(&delete_url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| RegistryError::NetworkError(e.to_string()))?;
 
@@ -12,6 +12,8 @@ use reqwest::{header, multipart, Client};
 use std::sync::Arc;
 
 use crate::auth::AuthProvider;
+use crate::scheduler::LimitedSend;
+use crate::types::Forge;
 use super::{AssetInfo, ReleaseError, ReleaseInfo, ReleasePort, ReleaseResult};
 
 // --- Gitea API response types ---
@@ -187,7 +189,7 @@ impl ReleasePort for CodebergReleaseAdapter {
             .post(&url)
             .headers(headers)
             .json(&req_body)
-            .send()
+            .send_limited(Forge::Codeberg)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -239,7 +241,7 @@ impl ReleasePort for CodebergReleaseAdapter {
             .post(&url)
             .headers(headers)
             .multipart(form)
-            .send()
+            .send_limited(Forge::Codeberg)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -270,7 +272,7 @@ impl ReleasePort for CodebergReleaseAdapter {
             .client
             .get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::Codeberg)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -306,7 +308,7 @@ impl ReleasePort for CodebergReleaseAdapter {
             .client
             .get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::Codeberg)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -341,7 +343,7 @@ impl ReleasePort for CodebergReleaseAdapter {
             .client
             .delete(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::Codeberg)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -372,7 +374,7 @@ impl ReleasePort for CodebergReleaseAdapter {
             .client
             .get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::Codeberg)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -404,7 +406,7 @@ impl ReleasePort for CodebergReleaseAdapter {
             .client
             .delete(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::Codeberg)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
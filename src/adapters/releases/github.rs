@@ -9,6 +9,8 @@ use reqwest::{header, Client};
 use std::sync::Arc;
 
 use crate::auth::AuthProvider;
+use crate::scheduler::LimitedSend;
+use crate::types::Forge;
 use super::{AssetInfo, ReleaseError, ReleaseInfo, ReleasePort, ReleaseResult};
 
 // --- GitHub API response types ---
@@ -194,7 +196,7 @@ impl ReleasePort for GitHubReleaseAdapter {
             .post(&url)
             .headers(headers)
             .json(&req_body)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -243,7 +245,7 @@ impl ReleasePort for GitHubReleaseAdapter {
             .headers(headers)
             .header(header::CONTENT_TYPE, content_type_header)
             .body(data)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -274,7 +276,7 @@ impl ReleasePort for GitHubReleaseAdapter {
             .client
             .get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -310,7 +312,7 @@ impl ReleasePort for GitHubReleaseAdapter {
             .client
             .get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -345,7 +347,7 @@ impl ReleasePort for GitHubReleaseAdapter {
             .client
             .delete(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -376,7 +378,7 @@ impl ReleasePort for GitHubReleaseAdapter {
             .client
             .get(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -407,7 +409,7 @@ impl ReleasePort for GitHubReleaseAdapter {
             .client
             .delete(&url)
             .headers(headers)
-            .send()
+            .send_limited(Forge::GitHub)
             .await
             .map_err(|e| ReleaseError::NetworkError(e.to_string()))?;
 
@@ -0,0 +1,217 @@
+//! API-level dry runs
+//!
+//! Inside [`record`], every forge API write that goes through
+//! [`LimitedSend`](crate::scheduler::LimitedSend) is written down instead of
+//! sent: the forge, the method, the endpoint and a summary of the JSON
+//! payload. The adapter gets a synthetic success back (`201` for POST, `204`
+//! for DELETE, `200` otherwise, with an empty JSON object as the body) and
+//! carries on as if the write had happened. Reads still go to the forge, so
+//! the adapters decide what to write from the real remote state and the
+//! recording lists exactly the calls a real run would make.
+//!
+//! The recording belongs to the task that started it; [`carry`] hands it to
+//! tasks spawned from there (the batch runner does this for every item).
+
+use reqwest::{Method, Request, Response, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use crate::types::Forge;
+
+/// Payload fields whose values are never shown
+const REDACTED_KEYS: [&str; 5] = ["secret", "token", "password", "key", "config"];
+
+/// Longest string value shown in a payload summary
+const MAX_VALUE: usize = 60;
+
+/// A write that was recorded instead of sent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ApiCall {
+    pub forge: String,
+    pub method: String,
+    /// Path and query, without the host
+    pub endpoint: String,
+    /// `field=value` pairs of a JSON body, secrets redacted
+    pub payload: Option<String>,
+}
+
+type Recording = Arc<Mutex<Vec<ApiCall>>>;
+
+tokio::task_local! {
+    static RECORDING: Recording;
+}
+
+/// Run `future` with API writes recorded rather than sent; returns its
+/// output and the writes in the order they were made
+pub async fn record<F: Future>(future: F) -> (F::Output, Vec<ApiCall>) {
+    let recording = Recording::default();
+    let output = RECORDING.scope(recording.clone(), future).await;
+    let calls = std::mem::take(&mut *recording.lock().unwrap_or_else(std::sync::PoisonError::into_inner));
+    (output, calls)
+}
+
+/// `future` under the current task's recording, if there is one. Call it in
+/// the spawning task, before handing the future to `spawn`.
+pub fn carry<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    let recording = RECORDING.try_with(Clone::clone).ok();
+    async move {
+        match recording {
+            Some(recording) => RECORDING.scope(recording, future).await,
+            None => future.await,
+        }
+    }
+}
+
+/// Whether writes made from this task are being recorded
+pub fn is_recording() -> bool {
+    RECORDING.try_with(|_| ()).is_ok()
+}
+
+/// Record `request` if it's a write made under [`record`], returning the
+/// synthetic response to hand back in place of the forge's
+pub fn intercept(request: &Request, forge: &Forge) -> Option<Response> {
+    if matches!(*request.method(), Method::GET | Method::HEAD) {
+        return None;
+    }
+    RECORDING
+        .try_with(|recording| {
+            let url = request.url();
+            let endpoint = url.query().map_or_else(|| url.path().to_string(), |query| format!("{}?{query}", url.path()));
+            let call = ApiCall {
+                forge: forge.to_string(),
+                method: request.method().to_string(),
+                endpoint,
+                payload: request.body().and_then(reqwest::Body::as_bytes).and_then(summarize),
+            };
+            recording.lock().unwrap_or_else(std::sync::PoisonError::into_inner).push(call);
+        })
+        .ok()?;
+    Some(synthetic(request.method()))
+}
+
+/// Call counts per forge, then per HTTP method
+pub fn tally(calls: &[ApiCall]) -> BTreeMap<String, BTreeMap<String, usize>> {
+    let mut counts: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+    for call in calls {
+        *counts.entry(call.forge.clone()).or_default().entry(call.method.clone()).or_default() += 1;
+    }
+    counts
+}
+
+fn synthetic(method: &Method) -> Response {
+    let (status, body) = match *method {
+        Method::POST => (StatusCode::CREATED, "{}"),
+        Method::DELETE => (StatusCode::NO_CONTENT, ""),
+        _ => (StatusCode::OK, "{}"),
+    };
+    let response = http::Response::builder()
+        .status(status)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body.as_bytes().to_vec())
+        .unwrap_or_else(|_| http::Response::new(Vec::new()));
+    Response::from(response)
+}
+
+/// `field=value` pairs of a JSON object body, in key order; the size of
+/// anything else
+fn summarize(body: &[u8]) -> Option<String> {
+    if body.is_empty() {
+        return None;
+    }
+    let Ok(Value::Object(fields)) = serde_json::from_slice::<Value>(body) else {
+        return Some(format!("<{} bytes>", body.len()));
+    };
+    let mut keys: Vec<&String> = fields.keys().collect();
+    keys.sort();
+    let pairs: Vec<String> = keys
+        .into_iter()
+        .map(|key| {
+            let value = if REDACTED_KEYS.iter().any(|secret| key.to_lowercase().contains(secret)) {
+                "***".to_string()
+            } else {
+                show(&fields[key])
+            };
+            format!("{key}={value}")
+        })
+        .collect();
+    Some(pairs.join(" "))
+}
+
+fn show(value: &Value) -> String {
+    match value {
+        Value::String(s) if s.chars().count() > MAX_VALUE => {
+            format!("{:?}", format!("{}...", s.chars().take(MAX_VALUE).collect::<String>()))
+        }
+        Value::Array(items) => format!("[{} items]", items.len()),
+        Value::Object(fields) => format!("{{{} fields}}", fields.len()),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_writes_are_recorded_not_sent() {
+        let client = reqwest::Client::new();
+        let write = client
+            .post("https://api.github.com/orgs/acme/repos")
+            .json(&serde_json::json!({ "name": "web", "private": true, "description": "Site" }))
+            .build()
+            .unwrap();
+        let read = client.get("https://api.github.com/repos/acme/web").build().unwrap();
+
+        // Outside a recording nothing is intercepted
+        assert!(intercept(&write, &Forge::GitHub).is_none());
+
+        let ((), calls) = record(async {
+            let response = intercept(&write, &Forge::GitHub).unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            assert!(intercept(&read, &Forge::GitHub).is_none());
+
+            // Spawned work is recorded into the same list
+            let hook = client
+                .delete("https://codeberg.org/api/v1/repos/acme/web/hooks/7")
+                .build()
+                .unwrap();
+            tokio::spawn(carry(async move { assert!(intercept(&hook, &Forge::Codeberg).is_some()) }))
+                .await
+                .unwrap();
+        })
+        .await;
+
+        assert_eq!(
+            calls,
+            vec![
+                ApiCall {
+                    forge: "github".to_string(),
+                    method: "POST".to_string(),
+                    endpoint: "/orgs/acme/repos".to_string(),
+                    payload: Some("description=\"Site\" name=\"web\" private=true".to_string()),
+                },
+                ApiCall {
+                    forge: "codeberg".to_string(),
+                    method: "DELETE".to_string(),
+                    endpoint: "/api/v1/repos/acme/web/hooks/7".to_string(),
+                    payload: None,
+                },
+            ]
+        );
+        assert_eq!(tally(&calls)["github"]["POST"], 1);
+    }
+
+    #[test]
+    fn test_summary_redacts_secrets() {
+        let body = serde_json::json!({ "title": "ci", "key": "ssh-ed25519 AAAA", "config": { "secret": "s" }, "events": ["push"] });
+        assert_eq!(
+            summarize(body.to_string().as_bytes()).unwrap(),
+            "config=*** events=[1 items] key=*** title=\"ci\""
+        );
+        assert_eq!(summarize(b"not json").unwrap(), "<8 bytes>");
+        assert_eq!(summarize(b""), None);
+    }
+}
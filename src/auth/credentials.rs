@@ -216,11 +216,9 @@ pub fn credentials_for_channels(
 /// Returns the primary forge token (not packages/GHCR tokens — those are
 /// channel-driven via `credentials_for_channels`).
 pub fn credentials_for_forge(forge: &Forge, org: &str) -> Vec<ResolvedCredential> {
-    let prefix = match forge {
-        Forge::GitHub => "github/",
-        Forge::Codeberg => "codeberg/",
-        Forge::GitLab => "gitlab/",
-    };
+    // Self-hosted forges have no catalogue entries; their token lives at
+    // `{host}/{org}/token`
+    let prefix = format!("{forge}/");
 
     // The primary token pattern for each forge is `<forge>/{org}/token`.
     // The `{{org}}` escape yields a literal `{org}` placeholder for the
@@ -245,7 +243,7 @@ pub fn credentials_for_forge(forge: &Forge, org: &str) -> Vec<ResolvedCredential
 
 use crate::auth_hub::storage::YamlStorage;
 use crate::auth_hub::types::SecretPath;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
 use std::collections::HashSet;
 
 /// Result of a pre-flight credential check for a single credential.
@@ -274,15 +272,17 @@ pub async fn preflight_check(
     let storage = match YamlStorage::default_location() {
         Ok(s) => s,
         Err(e) => {
-            return vec![HyperforgeEvent::Error {
-                message: format!("Pre-flight: failed to initialize secrets storage: {e}"),
-            }];
+            return vec![HyperforgeEvent::error(
+                HyperforgeError::Auth,
+                format!("Pre-flight: failed to initialize secrets storage: {e}"),
+            )];
         }
     };
     if let Err(e) = storage.load().await {
-        return vec![HyperforgeEvent::Error {
-            message: format!("Pre-flight: failed to load secrets: {e}"),
-        }];
+        return vec![HyperforgeEvent::error(
+            HyperforgeError::Auth,
+            format!("Pre-flight: failed to load secrets: {e}"),
+        )];
     }
 
     // Collect all required credentials, deduplicating by key_path
@@ -360,9 +360,10 @@ pub async fn preflight_check(
         "\nAborting. Run `auth setup --org {org}` to configure missing credentials.",
     ));
 
-    events.push(HyperforgeEvent::Error {
-        message: detail_lines,
-    });
+    events.push(HyperforgeEvent::error(
+        HyperforgeError::Auth,
+        detail_lines,
+    ));
 
     events
 }
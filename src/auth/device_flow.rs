@@ -0,0 +1,177 @@
+//! OAuth device flow (RFC 8628)
+//!
+//! `auth.login` asks the forge for a user code, the user approves it in a
+//! browser, and the token endpoint is polled until the forge hands out an
+//! access token. GitHub and GitLab have built-in endpoints; other forges
+//! (Codeberg and Forgejo instances that enable the grant) need `device_url`
+//! and `token_url` under `[oauth.<forge>]`.
+
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::config::OAuthAppConfig;
+
+const DEVICE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Where and what to ask for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceFlowEndpoints {
+    pub device_url: String,
+    pub token_url: String,
+    pub scope: String,
+}
+
+impl DeviceFlowEndpoints {
+    /// Endpoints for `forge`: the app's overrides, else the forge's
+    /// built-in ones. `None` if the forge has neither.
+    pub fn resolve(forge: &str, app: &OAuthAppConfig) -> Option<Self> {
+        let (device_url, token_url, scope) = match forge {
+            "github" => ("https://github.com/login/device/code", "https://github.com/login/oauth/access_token", "repo read:org"),
+            "gitlab" => ("https://gitlab.com/oauth/authorize_device", "https://gitlab.com/oauth/token", "api"),
+            _ => ("", "", ""),
+        };
+        let device_url = app.device_url.clone().unwrap_or_else(|| device_url.to_string());
+        let token_url = app.token_url.clone().unwrap_or_else(|| token_url.to_string());
+        if device_url.is_empty() || token_url.is_empty() {
+            return None;
+        }
+        Some(Self { device_url, token_url, scope: app.scope.clone().unwrap_or_else(|| scope.to_string()) })
+    }
+}
+
+/// The forge's answer to a device authorization request
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    /// Seconds until `device_code` expires
+    pub expires_in: u64,
+    /// Minimum seconds between polls
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+}
+
+const fn default_interval() -> u64 {
+    5
+}
+
+/// One poll of the token endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PollOutcome {
+    Token(String),
+    /// Not approved yet
+    Pending,
+    /// Polling too fast: wait five seconds longer from now on
+    SlowDown,
+    /// Denied, expired, or rejected for another reason
+    Failed(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+impl PollOutcome {
+    /// Interpret a token endpoint response body. Forges answer pending
+    /// polls with 200 (GitHub) or 400 (GitLab), so the body decides.
+    pub fn from_body(body: &str) -> Self {
+        let Ok(response) = serde_json::from_str::<TokenResponse>(body) else {
+            return Self::Failed(format!("unexpected token response: {body}"));
+        };
+        match (response.access_token, response.error.as_deref()) {
+            (Some(token), _) => Self::Token(token),
+            (None, Some("authorization_pending")) => Self::Pending,
+            (None, Some("slow_down")) => Self::SlowDown,
+            (None, Some(error)) => Self::Failed(response.error_description.unwrap_or_else(|| error.to_string())),
+            (None, None) => Self::Failed("token response had neither a token nor an error".to_string()),
+        }
+    }
+}
+
+/// A device flow against one forge's endpoints
+pub struct DeviceFlow {
+    client: Client,
+    endpoints: DeviceFlowEndpoints,
+    client_id: String,
+}
+
+impl DeviceFlow {
+    pub fn new(endpoints: DeviceFlowEndpoints, client_id: impl Into<String>) -> anyhow::Result<Self> {
+        let client = Client::builder().user_agent("hyperforge/2.0").build()?;
+        Ok(Self { client, endpoints, client_id: client_id.into() })
+    }
+
+    /// Request a device and user code
+    pub async fn start(&self) -> anyhow::Result<DeviceAuthorization> {
+        let response = self
+            .client
+            .post(&self.endpoints.device_url)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .form(&[("client_id", self.client_id.as_str()), ("scope", self.endpoints.scope.as_str())])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("device authorization failed ({status}): {body}");
+        }
+        Ok(response.json().await?)
+    }
+
+    /// Ask once whether the user approved `device_code`
+    pub async fn poll(&self, device_code: &str) -> anyhow::Result<PollOutcome> {
+        let body = self
+            .client
+            .post(&self.endpoints.token_url)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .form(&[("client_id", self.client_id.as_str()), ("device_code", device_code), ("grant_type", DEVICE_GRANT)])
+            .send()
+            .await?
+            .text()
+            .await?;
+        Ok(PollOutcome::from_body(&body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_endpoints() {
+        let app = OAuthAppConfig { client_id: "Iv1.abc".to_string(), ..OAuthAppConfig::default() };
+        let github = DeviceFlowEndpoints::resolve("github", &app).unwrap();
+        assert_eq!(github.device_url, "https://github.com/login/device/code");
+        assert_eq!(github.scope, "repo read:org");
+
+        // Codeberg has no built-in endpoints
+        assert_eq!(DeviceFlowEndpoints::resolve("codeberg", &app), None);
+        let app = OAuthAppConfig {
+            device_url: Some("https://codeberg.org/login/oauth/device".to_string()),
+            token_url: Some("https://codeberg.org/login/oauth/access_token".to_string()),
+            scope: Some("write:repository".to_string()),
+            ..app
+        };
+        let codeberg = DeviceFlowEndpoints::resolve("codeberg", &app).unwrap();
+        assert_eq!(codeberg.scope, "write:repository");
+    }
+
+    #[test]
+    fn test_poll_outcome_from_body() {
+        assert_eq!(
+            PollOutcome::from_body(r#"{"access_token":"gho_x","token_type":"bearer","scope":"repo"}"#),
+            PollOutcome::Token("gho_x".to_string())
+        );
+        assert_eq!(PollOutcome::from_body(r#"{"error":"authorization_pending"}"#), PollOutcome::Pending);
+        assert_eq!(PollOutcome::from_body(r#"{"error":"slow_down","interval":10}"#), PollOutcome::SlowDown);
+        assert_eq!(
+            PollOutcome::from_body(r#"{"error":"access_denied","error_description":"The user denied the request"}"#),
+            PollOutcome::Failed("The user denied the request".to_string())
+        );
+        assert!(matches!(PollOutcome::from_body("<html>"), PollOutcome::Failed(_)));
+    }
+}
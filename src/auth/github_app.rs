@@ -0,0 +1,258 @@
+//! GitHub App authentication
+//!
+//! An org can authenticate as a GitHub App installation instead of with a
+//! PAT. The app's credentials live in the secret store next to the token:
+//! - `github/<org>/app_id`
+//! - `github/<org>/app_private_key` - the PEM from the app's settings page
+//! - `github/<org>/app_installation_id` - optional, looked up from the org
+//!   (or user) when absent
+//!
+//! `GitHubAppAuth` wraps the regular provider and answers
+//! `github/<org>/token` with an installation token when the org has no PAT
+//! of its own (an org's app beats a forge-wide `github/token`).
+//! Installation tokens live an hour; they are cached per process and minted
+//! again once less than `REFRESH_MARGIN` remains. Adapters look the token up
+//! for every request, so a long `sync` rolls over to a fresh token mid-run.
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::Mutex;
+
+use super::AuthProvider;
+
+/// Mint a new installation token once the cached one is this close to expiry
+const REFRESH_MARGIN: TimeDelta = TimeDelta::minutes(5);
+
+/// Installation tokens by `<api_url>|<org>`, shared by every adapter
+fn installation_tokens() -> &'static Mutex<HashMap<String, InstallationToken>> {
+    static TOKENS: OnceLock<Mutex<HashMap<String, InstallationToken>>> = OnceLock::new();
+    TOKENS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// A minted installation token
+#[derive(Debug, Clone, Deserialize)]
+struct InstallationToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+impl InstallationToken {
+    /// Still usable for at least `REFRESH_MARGIN`
+    fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at - now > REFRESH_MARGIN
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Installation {
+    id: u64,
+}
+
+/// The org of a `github/<org>/token` path
+fn token_org(key: &str) -> Option<&str> {
+    key.strip_prefix("github/")?
+        .strip_suffix("/token")
+        .filter(|org| !org.is_empty() && !org.contains('/'))
+}
+
+/// Unpadded base64url, as JWTs use
+fn base64url(bytes: &[u8]) -> String {
+    openssl::base64::encode_block(bytes)
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+/// An RS256 JWT identifying the app, valid for nine minutes from `now`
+/// (GitHub caps app JWTs at ten). `iat` is backdated a minute for clock drift.
+pub fn app_jwt(app_id: &str, private_key_pem: &str, now: DateTime<Utc>) -> anyhow::Result<String> {
+    let header = serde_json::json!({ "alg": "RS256", "typ": "JWT" });
+    let claims = serde_json::json!({
+        "iat": (now - TimeDelta::minutes(1)).timestamp(),
+        "exp": (now + TimeDelta::minutes(9)).timestamp(),
+        "iss": app_id,
+    });
+    let signing_input = format!(
+        "{}.{}",
+        base64url(header.to_string().as_bytes()),
+        base64url(claims.to_string().as_bytes())
+    );
+
+    let key = PKey::private_key_from_pem(private_key_pem.as_bytes())
+        .map_err(|e| anyhow::anyhow!("Invalid GitHub App private key: {e}"))?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+    signer.update(signing_input.as_bytes())?;
+    let signature = signer.sign_to_vec()?;
+
+    Ok(format!("{signing_input}.{}", base64url(&signature)))
+}
+
+/// Auth provider that falls back to GitHub App installation tokens
+pub struct GitHubAppAuth {
+    inner: Arc<dyn AuthProvider>,
+    client: Client,
+    api_url: String,
+}
+
+impl GitHubAppAuth {
+    /// Wrap `inner`, minting tokens against the API at `api_url`
+    pub fn new(inner: Arc<dyn AuthProvider>, client: Client, api_url: impl Into<String>) -> Self {
+        Self { inner, client, api_url: api_url.into() }
+    }
+
+    /// A fresh installation token for `org`, or `None` if no app is configured
+    async fn installation_token(&self, org: &str) -> anyhow::Result<Option<String>> {
+        let cache_key = format!("{}|{org}", self.api_url);
+        // Held while minting so concurrent requests share one new token
+        let mut tokens = installation_tokens().lock().await;
+        if let Some(cached) = tokens.get(&cache_key).filter(|t| t.is_fresh(Utc::now())) {
+            return Ok(Some(cached.token.clone()));
+        }
+
+        let app_id = self.inner.get_secret(&format!("github/{org}/app_id")).await?;
+        let private_key = self.inner.get_secret(&format!("github/{org}/app_private_key")).await?;
+        let (Some(app_id), Some(private_key)) = (app_id, private_key) else {
+            return Ok(None);
+        };
+
+        let jwt = app_jwt(app_id.trim(), &private_key, Utc::now())?;
+        let installation_id = match self.inner.get_secret(&format!("github/{org}/app_installation_id")).await? {
+            Some(id) => id.trim().to_string(),
+            None => self.find_installation(org, &jwt).await?.to_string(),
+        };
+
+        let url = format!("{}/app/installations/{installation_id}/access_tokens", self.api_url);
+        let response = Self::request(self.client.post(url), &jwt).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "GitHub App installation {installation_id} for {org}: token request failed ({})",
+                response.status()
+            );
+        }
+        let token: InstallationToken = response.json().await?;
+        tracing::debug!(org, expires_at = %token.expires_at, "minted GitHub App installation token");
+
+        let secret = token.token.clone();
+        tokens.insert(cache_key, token);
+        drop(tokens);
+        Ok(Some(secret))
+    }
+
+    /// The app's installation on `org`, trying the org then the user endpoint
+    async fn find_installation(&self, org: &str, jwt: &str) -> anyhow::Result<u64> {
+        for owner in ["orgs", "users"] {
+            let url = format!("{}/{owner}/{org}/installation", self.api_url);
+            let response = Self::request(self.client.get(url), jwt).send().await?;
+            match response.status() {
+                StatusCode::NOT_FOUND => {}
+                status if status.is_success() => return Ok(response.json::<Installation>().await?.id),
+                status => anyhow::bail!("GitHub App installation lookup for {org} failed ({status})"),
+            }
+        }
+        anyhow::bail!("GitHub App is not installed on {org}")
+    }
+
+    /// Authenticate `builder` as the app itself
+    fn request(builder: reqwest::RequestBuilder, jwt: &str) -> reqwest::RequestBuilder {
+        builder
+            .bearer_auth(jwt)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    }
+}
+
+#[async_trait]
+impl AuthProvider for GitHubAppAuth {
+    async fn get_secret(&self, key: &str) -> anyhow::Result<Option<String>> {
+        let Some(org) = token_org(key) else {
+            return self.inner.get_secret(key).await;
+        };
+        // The org's own PAT wins, then its app, then a forge-wide PAT
+        if let Some(secret) = self.inner.get_scoped_secret(key).await? {
+            return Ok(Some(secret));
+        }
+        if let Some(token) = self.installation_token(org).await? {
+            return Ok(Some(token));
+        }
+        self.inner.get_secret(key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::rsa::Rsa;
+    use openssl::sign::Verifier;
+
+    struct MapProvider(HashMap<String, String>);
+
+    #[async_trait]
+    impl AuthProvider for MapProvider {
+        async fn get_secret(&self, key: &str) -> anyhow::Result<Option<String>> {
+            Ok(self.0.get(key).cloned())
+        }
+    }
+
+    fn base64url_decode(s: &str) -> Vec<u8> {
+        let mut padded = s.replace('-', "+").replace('_', "/");
+        while !padded.len().is_multiple_of(4) {
+            padded.push('=');
+        }
+        openssl::base64::decode_block(&padded).unwrap()
+    }
+
+    #[test]
+    fn test_app_jwt_is_signed_rs256() {
+        let rsa = Rsa::generate(2048).unwrap();
+        let pem = String::from_utf8(rsa.private_key_to_pem().unwrap()).unwrap();
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+        let jwt = app_jwt("12345", &pem, now).unwrap();
+        let parts: Vec<&str> = jwt.split('.').collect();
+        assert_eq!(parts.len(), 3);
+        assert!(!jwt.contains('='));
+
+        let header: serde_json::Value = serde_json::from_slice(&base64url_decode(parts[0])).unwrap();
+        assert_eq!(header["alg"], "RS256");
+        let claims: serde_json::Value = serde_json::from_slice(&base64url_decode(parts[1])).unwrap();
+        assert_eq!(claims["iss"], "12345");
+        assert_eq!(claims["iat"], 1_700_000_000 - 60);
+        assert_eq!(claims["exp"], 1_700_000_000 + 540);
+
+        let public = PKey::from_rsa(Rsa::public_key_from_pem(&rsa.public_key_to_pem().unwrap()).unwrap()).unwrap();
+        let mut verifier = Verifier::new(MessageDigest::sha256(), &public).unwrap();
+        verifier.update(format!("{}.{}", parts[0], parts[1]).as_bytes()).unwrap();
+        assert!(verifier.verify(&base64url_decode(parts[2])).unwrap());
+
+        assert!(app_jwt("12345", "not a key", now).is_err());
+    }
+
+    #[test]
+    fn test_installation_token_refreshes_before_expiry() {
+        let now = Utc::now();
+        let token = |minutes| InstallationToken { token: "ghs_x".to_string(), expires_at: now + TimeDelta::minutes(minutes) };
+        assert!(token(60).is_fresh(now));
+        assert!(!token(4).is_fresh(now));
+        assert!(!token(-1).is_fresh(now));
+    }
+
+    #[tokio::test]
+    async fn test_pat_wins_and_missing_app_falls_through() {
+        assert_eq!(token_org("github/acme/token"), Some("acme"));
+        assert_eq!(token_org("github/acme/packages_token"), None);
+        assert_eq!(token_org("codeberg/acme/token"), None);
+
+        let inner = MapProvider(HashMap::from([("github/acme/token".to_string(), "ghp_pat".to_string())]));
+        // Unroutable URL: any attempt to mint would fail the test
+        let auth = GitHubAppAuth::new(Arc::new(inner), Client::new(), "http://127.0.0.1:1");
+        assert_eq!(auth.get_secret("github/acme/token").await.unwrap().as_deref(), Some("ghp_pat"));
+        assert_eq!(auth.get_secret("github/other/token").await.unwrap(), None);
+    }
+}
@@ -1,42 +1,64 @@
 //! Keychain bridge for secure secret storage
 //!
-//! This module provides access to the macOS Keychain using the `security` CLI command.
-//! Secrets are stored with a service name format of `hyperforge:<org>:<forge>`.
+//! `KeychainBridge` stores secrets in the OS credential store, chosen when
+//! the bridge is created from the platform hyperforge runs on:
+//! - macOS: the login Keychain, through the `security` CLI
+//! - Linux: the Secret Service (GNOME Keyring, `KWallet`), through
+//!   `secret-tool` from libsecret
+//! - Windows: Credential Manager, through `PowerShell` calling the
+//!   `CredRead`/`CredWrite` Win32 API
+//!
+//! Secrets are stored with a service name format of `hyperforge:<org>:<forge>`
+//! (or `hyperforge:<path>` for [`KeychainBridge::global`]). Secret values are
+//! passed on stdin wherever the tool allows it.
 
+use async_trait::async_trait;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 
-/// Bridge to the macOS Keychain for secure secret storage
-pub struct KeychainBridge {
-    service_prefix: String,
+/// A platform credential store, addressed by service and account
+#[async_trait]
+trait SecretStore: Send + Sync {
+    /// Short name for messages
+    fn name(&self) -> &'static str;
+
+    /// `Ok(None)` if the entry doesn't exist
+    async fn get(&self, service: &str, account: &str) -> Result<Option<String>, String>;
+
+    /// Create or replace an entry
+    async fn set(&self, service: &str, account: &str, value: &str) -> Result<(), String>;
+
+    /// Remove an entry; removing a missing entry is not an error
+    async fn delete(&self, service: &str, account: &str) -> Result<(), String>;
 }
 
-impl KeychainBridge {
-    /// Create a new `KeychainBridge` for the given organization
-    pub fn new(org_name: &str) -> Self {
-        Self {
-            service_prefix: format!("hyperforge:{org_name}"),
-        }
+/// Run `program` with `args`, feeding `stdin` if given
+async fn run(program: &str, args: &[&str], stdin: Option<&str>) -> Result<std::process::Output, String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run {program}: {e}"))?;
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(input.as_bytes()).await.map_err(|e| e.to_string())?;
     }
+    child.wait_with_output().await.map_err(|e| e.to_string())
+}
 
-    /// Get the full service name for a given key
-    /// Format: hyperforge:<org>:<key> (e.g., hyperforge:hypermemetic:github)
-    fn service_name(&self, key: &str) -> String {
-        format!("{}:{}", self.service_prefix, key)
-    }
+/// macOS Keychain via `security`
+struct MacKeychain;
 
-    /// Get a secret value from the keychain
-    ///
-    /// Uses `security find-generic-password` to retrieve the value.
-    /// Returns `Ok(None)` if the secret doesn't exist.
-    pub async fn get(&self, key: &str) -> Result<Option<String>, String> {
-        let service = self.service_name(key);
-        let account = std::env::var("USER").unwrap_or_else(|_| "hyperforge".to_string());
+#[async_trait]
+impl SecretStore for MacKeychain {
+    fn name(&self) -> &'static str {
+        "macOS Keychain"
+    }
 
-        let output = Command::new("security")
-            .args(["find-generic-password", "-a", &account, "-s", &service, "-w"])
-            .output()
-            .await
-            .map_err(|e| e.to_string())?;
+    async fn get(&self, service: &str, account: &str) -> Result<Option<String>, String> {
+        let output = run("security", &["find-generic-password", "-a", account, "-s", service, "-w"], None).await?;
 
         if output.status.success() {
             let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -47,35 +69,25 @@ impl KeychainBridge {
         }
     }
 
-    /// Set a secret value in the keychain
-    ///
-    /// Uses `security add-generic-password` to store the value.
-    /// Deletes any existing entry first to avoid duplicates.
-    pub async fn set(&self, key: &str, value: &str) -> Result<(), String> {
-        let service = self.service_name(key);
-        let account = std::env::var("USER").unwrap_or_else(|_| "hyperforge".to_string());
-
+    async fn set(&self, service: &str, account: &str, value: &str) -> Result<(), String> {
         // Delete existing entry if present (ignore errors)
-        let _ = Command::new("security")
-            .args(["delete-generic-password", "-s", &service])
-            .output()
-            .await;
-
-        // Add new entry
-        let output = Command::new("security")
-            .args([
+        let _ = run("security", &["delete-generic-password", "-s", service], None).await;
+
+        let output = run(
+            "security",
+            &[
                 "add-generic-password",
                 "-s",
-                &service,
+                service,
                 "-a",
-                &account,
+                account,
                 "-w",
                 value,
                 "-U", // Update if exists (shouldn't happen after delete, but safety)
-            ])
-            .output()
-            .await
-            .map_err(|e| e.to_string())?;
+            ],
+            None,
+        )
+        .await?;
 
         if output.status.success() {
             Ok(())
@@ -84,22 +96,8 @@ impl KeychainBridge {
         }
     }
 
-    /// Check if a secret exists in the keychain
-    pub async fn exists(&self, key: &str) -> Result<bool, String> {
-        Ok(self.get(key).await?.is_some())
-    }
-
-    /// Delete a secret from the keychain
-    ///
-    /// Uses `security delete-generic-password` to remove the entry.
-    pub async fn delete(&self, key: &str) -> Result<(), String> {
-        let service = self.service_name(key);
-
-        let output = Command::new("security")
-            .args(["delete-generic-password", "-s", &service])
-            .output()
-            .await
-            .map_err(|e| e.to_string())?;
+    async fn delete(&self, service: &str, _account: &str) -> Result<(), String> {
+        let output = run("security", &["delete-generic-password", "-s", service], None).await?;
 
         if output.status.success() {
             Ok(())
@@ -115,10 +113,263 @@ impl KeychainBridge {
     }
 }
 
+/// Secret Service (freedesktop.org) via libsecret's `secret-tool`
+struct SecretService;
+
+#[async_trait]
+impl SecretStore for SecretService {
+    fn name(&self) -> &'static str {
+        "Secret Service"
+    }
+
+    async fn get(&self, service: &str, account: &str) -> Result<Option<String>, String> {
+        let output = run("secret-tool", &["lookup", "service", service, "account", account], None).await?;
+
+        // lookup exits 1 with no output when nothing matches
+        if output.status.success() {
+            Ok(Some(String::from_utf8_lossy(&output.stdout).trim_end_matches('\n').to_string()))
+        } else if output.stderr.is_empty() {
+            Ok(None)
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        }
+    }
+
+    async fn set(&self, service: &str, account: &str, value: &str) -> Result<(), String> {
+        let label = format!("--label={service}");
+        let output = run("secret-tool", &["store", &label, "service", service, "account", account], Some(value)).await?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        }
+    }
+
+    async fn delete(&self, service: &str, account: &str) -> Result<(), String> {
+        let output = run("secret-tool", &["clear", "service", service, "account", account], None).await?;
+
+        // clear exits non-zero without a message when nothing matched
+        if output.status.success() || output.stderr.is_empty() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        }
+    }
+}
+
+/// Credential Manager helpers, loaded into `PowerShell` with `Add-Type`.
+/// Entries are generic credentials (`CRED_TYPE_GENERIC`) persisted for the
+/// local machine; the target name is read from `$env:HF_TARGET` and values
+/// travel on stdin/stdout.
+const WINCRED_SCRIPT: &str = r#"
+$ErrorActionPreference = 'Stop'
+Add-Type -TypeDefinition @'
+using System;
+using System.Runtime.InteropServices;
+using System.Text;
+public static class HfCred {
+    [StructLayout(LayoutKind.Sequential, CharSet = CharSet.Unicode)]
+    struct CREDENTIAL {
+        public int Flags; public int Type; public string TargetName; public string Comment;
+        public System.Runtime.InteropServices.ComTypes.FILETIME LastWritten;
+        public int CredentialBlobSize; public IntPtr CredentialBlob; public int Persist;
+        public int AttributeCount; public IntPtr Attributes; public string TargetAlias; public string UserName;
+    }
+    [DllImport("advapi32.dll", SetLastError = true, CharSet = CharSet.Unicode)]
+    static extern bool CredReadW(string target, int type, int flags, out IntPtr cred);
+    [DllImport("advapi32.dll", SetLastError = true, CharSet = CharSet.Unicode)]
+    static extern bool CredWriteW(ref CREDENTIAL cred, int flags);
+    [DllImport("advapi32.dll", SetLastError = true, CharSet = CharSet.Unicode)]
+    static extern bool CredDeleteW(string target, int type, int flags);
+    [DllImport("advapi32.dll")]
+    static extern void CredFree(IntPtr cred);
+    public static string Read(string target) {
+        IntPtr p;
+        if (!CredReadW(target, 1, 0, out p)) { return null; }
+        try {
+            var c = (CREDENTIAL)Marshal.PtrToStructure(p, typeof(CREDENTIAL));
+            return Marshal.PtrToStringUni(c.CredentialBlob, c.CredentialBlobSize / 2);
+        } finally { CredFree(p); }
+    }
+    public static void Write(string target, string user, string secret) {
+        var bytes = Encoding.Unicode.GetBytes(secret);
+        var c = new CREDENTIAL { Type = 1, TargetName = target, UserName = user, Persist = 2,
+            CredentialBlobSize = bytes.Length, CredentialBlob = Marshal.AllocHGlobal(bytes.Length) };
+        try {
+            Marshal.Copy(bytes, 0, c.CredentialBlob, bytes.Length);
+            if (!CredWriteW(ref c, 0)) { throw new System.ComponentModel.Win32Exception(); }
+        } finally { Marshal.FreeHGlobal(c.CredentialBlob); }
+    }
+    public static void Delete(string target) {
+        // ERROR_NOT_FOUND (1168) means there was nothing to delete
+        if (!CredDeleteW(target, 1, 0) && Marshal.GetLastWin32Error() != 1168) {
+            throw new System.ComponentModel.Win32Exception();
+        }
+    }
+}
+'@
+"#;
+
+/// `-EncodedCommand` argument: base64 of the UTF-16LE script
+fn powershell_encoded(script: &str) -> String {
+    let bytes: Vec<u8> = script.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    openssl::base64::encode_block(&bytes)
+}
+
+/// Windows Credential Manager via `PowerShell`
+struct WindowsCredentialManager;
+
+impl WindowsCredentialManager {
+    async fn powershell(action: &str, target: &str, stdin: Option<&str>) -> Result<std::process::Output, String> {
+        let script = powershell_encoded(&format!("{WINCRED_SCRIPT}\n$env:HF_TARGET = '{}'\n{action}", target.replace('\'', "''")));
+        run("powershell", &["-NoProfile", "-NonInteractive", "-EncodedCommand", &script], stdin).await
+    }
+}
+
+#[async_trait]
+impl SecretStore for WindowsCredentialManager {
+    fn name(&self) -> &'static str {
+        "Windows Credential Manager"
+    }
+
+    async fn get(&self, service: &str, _account: &str) -> Result<Option<String>, String> {
+        let output = Self::powershell(
+            "$v = [HfCred]::Read($env:HF_TARGET); if ($null -eq $v) { exit 3 }; [Console]::Out.Write($v)",
+            service,
+            None,
+        )
+        .await?;
+
+        match output.status.code() {
+            Some(0) => Ok(Some(String::from_utf8_lossy(&output.stdout).to_string())),
+            Some(3) => Ok(None),
+            _ => Err(String::from_utf8_lossy(&output.stderr).to_string()),
+        }
+    }
+
+    async fn set(&self, service: &str, account: &str, value: &str) -> Result<(), String> {
+        let action = format!(
+            "[HfCred]::Write($env:HF_TARGET, '{}', [Console]::In.ReadToEnd())",
+            account.replace('\'', "''")
+        );
+        let output = Self::powershell(&action, service, Some(value)).await?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        }
+    }
+
+    async fn delete(&self, service: &str, _account: &str) -> Result<(), String> {
+        let output = Self::powershell("[HfCred]::Delete($env:HF_TARGET)", service, None).await?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).to_string())
+        }
+    }
+}
+
+static MAC_KEYCHAIN: MacKeychain = MacKeychain;
+static SECRET_SERVICE: SecretService = SecretService;
+static WINDOWS_CREDENTIALS: WindowsCredentialManager = WindowsCredentialManager;
+
+/// The credential store for an `std::env::consts::OS` value; other Unixes
+/// get the Secret Service, which is what their desktops ship
+fn store_for(os: &str) -> &'static dyn SecretStore {
+    match os {
+        "macos" | "ios" => &MAC_KEYCHAIN,
+        "windows" => &WINDOWS_CREDENTIALS,
+        _ => &SECRET_SERVICE,
+    }
+}
+
+/// Bridge to the OS credential store for secure secret storage
+pub struct KeychainBridge {
+    service_prefix: String,
+    store: &'static dyn SecretStore,
+}
+
+impl KeychainBridge {
+    /// Create a new `KeychainBridge` for the given organization
+    pub fn new(org_name: &str) -> Self {
+        Self {
+            service_prefix: format!("hyperforge:{org_name}"),
+            store: store_for(std::env::consts::OS),
+        }
+    }
+
+    /// Bridge for secrets addressed by their full path, stored as
+    /// `hyperforge:<path>` (keychain-backed secret namespaces)
+    pub fn global() -> Self {
+        Self {
+            service_prefix: "hyperforge".to_string(),
+            store: store_for(std::env::consts::OS),
+        }
+    }
+
+    /// Name of the credential store in use, e.g. `"Secret Service"`
+    pub fn backend(&self) -> &'static str {
+        self.store.name()
+    }
+
+    /// Get the full service name for a given key
+    /// Format: hyperforge:<org>:<key> (e.g., hyperforge:hypermemetic:github)
+    fn service_name(&self, key: &str) -> String {
+        format!("{}:{}", self.service_prefix, key)
+    }
+
+    fn account() -> String {
+        std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "hyperforge".to_string())
+    }
+
+    /// Get a secret value from the credential store.
+    /// Returns `Ok(None)` if the secret doesn't exist.
+    pub async fn get(&self, key: &str) -> Result<Option<String>, String> {
+        self.store.get(&self.service_name(key), &Self::account()).await
+    }
+
+    /// Set a secret value in the credential store, replacing any existing
+    /// entry
+    pub async fn set(&self, key: &str, value: &str) -> Result<(), String> {
+        self.store.set(&self.service_name(key), &Self::account(), value).await
+    }
+
+    /// Check if a secret exists in the credential store
+    pub async fn exists(&self, key: &str) -> Result<bool, String> {
+        Ok(self.get(key).await?.is_some())
+    }
+
+    /// Delete a secret from the credential store; deleting a missing
+    /// secret succeeds
+    pub async fn delete(&self, key: &str) -> Result<(), String> {
+        self.store.delete(&self.service_name(key), &Self::account()).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_store_for_platform() {
+        assert_eq!(store_for("macos").name(), "macOS Keychain");
+        assert_eq!(store_for("linux").name(), "Secret Service");
+        assert_eq!(store_for("freebsd").name(), "Secret Service");
+        assert_eq!(store_for("windows").name(), "Windows Credential Manager");
+    }
+
+    #[test]
+    fn test_powershell_encoded_is_utf16le_base64() {
+        // "ls" as UTF-16LE is 6c 00 73 00
+        assert_eq!(powershell_encoded("ls"), "bABzAA==");
+    }
+
     #[test]
     fn test_service_name_format() {
         let bridge = KeychainBridge::new("myorg");
@@ -127,7 +378,7 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "requires macOS with keychain access"]
+    #[ignore = "requires an OS credential store (Keychain, Secret Service or Credential Manager)"]
     async fn test_set_and_get() {
         let bridge = KeychainBridge::new("test-org");
         let key = "test-token";
@@ -148,7 +399,7 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "requires macOS with keychain access"]
+    #[ignore = "requires an OS credential store (Keychain, Secret Service or Credential Manager)"]
     async fn test_get_nonexistent() {
         let bridge = KeychainBridge::new("test-org");
         let result = bridge.get("nonexistent-key").await.unwrap();
@@ -156,7 +407,7 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "requires macOS with keychain access"]
+    #[ignore = "requires an OS credential store (Keychain, Secret Service or Credential Manager)"]
     async fn test_delete_nonexistent() {
         let bridge = KeychainBridge::new("test-org");
         // Should not error even if key doesn't exist
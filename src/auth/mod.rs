@@ -3,11 +3,17 @@
 //! Provides secure access to forge tokens via macOS Keychain or YAML storage.
 
 pub mod credentials;
+pub mod device_flow;
+pub mod github_app;
 pub mod keychain;
+pub mod secret_env;
+pub mod session;
 pub mod yaml_provider;
 
 use async_trait::async_trait;
+pub use github_app::GitHubAppAuth;
 pub use keychain::KeychainBridge;
+pub use session::SecretSession;
 pub use yaml_provider::YamlAuthProvider;
 
 /// Trait for secret providers
@@ -16,6 +22,12 @@ pub trait AuthProvider: Send + Sync {
     /// Get a secret by key
     /// For forge tokens, key format: "<forge>" (e.g., "github", "codeberg")
     async fn get_secret(&self, key: &str) -> anyhow::Result<Option<String>>;
+
+    /// Get a secret stored for exactly `key`, skipping the forge-wide
+    /// fallback `get_secret` applies to org-scoped tokens
+    async fn get_scoped_secret(&self, key: &str) -> anyhow::Result<Option<String>> {
+        self.get_secret(key).await
+    }
 }
 
 /// Keychain-based auth provider for forge tokens
@@ -0,0 +1,115 @@
+//! Secret references in validation env vars
+//!
+//! A runner env value of the form `secret:<path>` (e.g.
+//! `CARGO_TOKEN = "secret:registry/crates-io/token"`) is looked up in the
+//! secrets store when the step starts, so credentials never live in repo
+//! config. Resolved values are handed to the process environment only —
+//! never to argv, where `ps` would show them — and are redacted from any
+//! output that ends up in events.
+
+use crate::auth_hub::storage::YamlStorage;
+use crate::auth_hub::types::SecretPath;
+
+/// Prefix marking an env value as a secret reference
+pub const SECRET_PREFIX: &str = "secret:";
+
+/// Placeholder that replaces secret values in captured output
+const REDACTED: &str = "***";
+
+/// The secret path an env value refers to, if it is a reference
+pub fn secret_ref(value: &str) -> Option<&str> {
+    value.strip_prefix(SECRET_PREFIX).map(str::trim).filter(|p| !p.is_empty())
+}
+
+/// Whether any of the env values is a secret reference
+pub fn has_secret_refs<'a>(mut values: impl Iterator<Item = &'a String>) -> bool {
+    values.any(|v| secret_ref(v).is_some())
+}
+
+/// Open the secrets store, for resolving references
+pub async fn load_store() -> Result<YamlStorage, String> {
+    let storage = YamlStorage::default_location()
+        .map_err(|e| format!("Failed to initialize secrets storage: {e}"))?;
+    storage
+        .load()
+        .await
+        .map_err(|e| format!("Failed to load secrets: {e}"))?;
+    Ok(storage)
+}
+
+/// Resolved env vars plus the secret values among them
+pub type ResolvedEnv = (Vec<(String, String)>, Vec<String>);
+
+/// Env with secret references replaced by their values, plus the values
+/// themselves for redaction. A missing secret is an error naming the env var
+/// and path.
+pub fn resolve_env<'a>(
+    env: impl IntoIterator<Item = (&'a String, &'a String)>,
+    storage: Option<&YamlStorage>,
+) -> Result<ResolvedEnv, String> {
+    let mut resolved = Vec::new();
+    let mut secrets = Vec::new();
+    for (key, value) in env {
+        let Some(path) = secret_ref(value) else {
+            resolved.push((key.clone(), value.clone()));
+            continue;
+        };
+        let storage = storage.ok_or_else(|| format!("{key}: secrets store not loaded"))?;
+        let secret = storage
+            .get(&SecretPath::new(path))
+            .ok()
+            .filter(|s| !s.value.is_empty())
+            .ok_or_else(|| format!("{key}: secret '{path}' is not set"))?;
+        secrets.push(secret.value.clone());
+        resolved.push((key.clone(), secret.value));
+    }
+    Ok((resolved, secrets))
+}
+
+/// Replace every occurrence of a secret value in `text`
+pub fn redact(text: &str, secrets: &[String]) -> String {
+    let mut out = text.to_string();
+    for secret in secrets.iter().filter(|s| !s.is_empty()) {
+        out = out.replace(secret.as_str(), REDACTED);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_ref() {
+        assert_eq!(secret_ref("secret:registry/crates-io/token"), Some("registry/crates-io/token"));
+        assert_eq!(secret_ref("plain"), None);
+        assert_eq!(secret_ref("secret:"), None);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_env_and_redact() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let storage = YamlStorage::new(temp.path().join("secrets.yaml"));
+        storage
+            .set(crate::auth_hub::types::Secret::new(
+                SecretPath::new("registry/crates-io/token"),
+                "tok-123".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let env = [
+            ("CARGO_TOKEN".to_string(), "secret:registry/crates-io/token".to_string()),
+            ("RUST_LOG".to_string(), "debug".to_string()),
+        ];
+        let (resolved, secrets) = resolve_env(env.iter().map(|(k, v)| (k, v)), Some(&storage)).unwrap();
+        assert_eq!(resolved[0].1, "tok-123");
+        assert_eq!(resolved[1].1, "debug");
+        assert_eq!(redact("auth failed for tok-123", &secrets), "auth failed for ***");
+
+        let missing = [("X".to_string(), "secret:nope".to_string())];
+        let err = resolve_env(missing.iter().map(|(k, v)| (k, v)), Some(&storage)).unwrap_err();
+        assert!(err.contains("nope") && err.starts_with("X:"));
+        assert!(resolve_env(env.iter().map(|(k, v)| (k, v)), None).is_err());
+    }
+}
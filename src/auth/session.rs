@@ -0,0 +1,88 @@
+//! Session-scoped secret cache
+//!
+//! A multi-phase run such as `workspace sync` asks for the same tokens
+//! hundreds of times, and every keychain read can prompt on macOS. While a
+//! [`SecretSession`] is alive, secrets that `YamlAuthProvider` resolves are
+//! kept in memory and served from there. Values sit in zeroizing buffers;
+//! when the last session ends the cache is cleared and every buffer is
+//! wiped. Outside a session nothing is cached.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use zeroize::Zeroizing;
+
+#[derive(Default)]
+struct SessionState {
+    /// Live sessions; the cache is only filled while this is non-zero
+    sessions: usize,
+    secrets: HashMap<String, Zeroizing<String>>,
+}
+
+fn state() -> MutexGuard<'static, SessionState> {
+    static STATE: OnceLock<Mutex<SessionState>> = OnceLock::new();
+    STATE
+        .get_or_init(|| Mutex::new(SessionState::default()))
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Keeps resolved secrets cached until dropped. Sessions nest: the cache is
+/// wiped when the last one ends.
+#[must_use = "the cache is wiped as soon as the session is dropped"]
+pub struct SecretSession {
+    _private: (),
+}
+
+impl SecretSession {
+    pub fn begin() -> Self {
+        state().sessions += 1;
+        Self { _private: () }
+    }
+}
+
+impl Drop for SecretSession {
+    fn drop(&mut self) {
+        let mut state = state();
+        state.sessions -= 1;
+        if state.sessions == 0 {
+            // Dropping the `Zeroizing` buffers wipes them
+            state.secrets.clear();
+        }
+    }
+}
+
+/// A secret cached by the current session
+pub fn cached(key: &str) -> Option<String> {
+    state().secrets.get(key).map(|value| value.as_str().to_owned())
+}
+
+/// Cache a resolved secret if a session is running
+pub fn remember(key: &str, value: &str) {
+    let mut state = state();
+    if state.sessions > 0 {
+        state.secrets.insert(key.to_string(), Zeroizing::new(value.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_lives_as_long_as_sessions() {
+        remember("session-test/token", "outside");
+        assert_eq!(cached("session-test/token"), None);
+
+        let outer = SecretSession::begin();
+        remember("session-test/token", "ghp_xxx");
+        {
+            let _inner = SecretSession::begin();
+            assert_eq!(cached("session-test/token").as_deref(), Some("ghp_xxx"));
+        }
+        // The outer session still holds it
+        assert_eq!(cached("session-test/token").as_deref(), Some("ghp_xxx"));
+
+        drop(outer);
+        assert_eq!(cached("session-test/token"), None);
+    }
+}
@@ -1,18 +1,30 @@
 //! YAML-file auth provider
 //!
-//! Reads secrets directly from ~/.config/hyperforge/secrets.yaml.
+//! Reads secrets directly from ~/.config/hyperforge/secrets.yaml, or from
+//! the keychain for paths a `[secrets.namespaces]` rule routes there.
+//! A missing `<forge>/<org>/token` falls back to the forge-wide
+//! `<forge>/token`, then to env vars like `GITHUB_TOKEN`. Inside a
+//! [`SecretSession`](super::SecretSession) resolved secrets are cached.
 
 use async_trait::async_trait;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
 
-use super::AuthProvider;
+use super::{session, AuthProvider, KeychainBridge};
+use crate::auth_hub::{encryption, env};
+use crate::auth_hub::namespaces::{Namespaces, SecretBackend};
+use crate::auth_hub::types::SecretPath;
 
 /// A single secret entry in the YAML file
 #[derive(Debug, Deserialize)]
 struct SecretEntry {
+    #[serde(default)]
     value: String,
+    /// Backend a migrated secret is pinned to
+    #[serde(default)]
+    backend: Option<SecretBackend>,
 }
 
 /// Top-level secrets file structure
@@ -24,6 +36,7 @@ struct SecretsFile {
 /// Auth provider that reads secrets directly from YAML on disk
 pub struct YamlAuthProvider {
     secrets_path: PathBuf,
+    namespaces: Namespaces,
 }
 
 impl YamlAuthProvider {
@@ -31,24 +44,44 @@ impl YamlAuthProvider {
     pub fn new() -> anyhow::Result<Self> {
         let home = dirs::home_dir()
             .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
-        Ok(Self {
-            secrets_path: home.join(".config/hyperforge/secrets.yaml"),
-        })
+        Ok(Self::in_dir(&home.join(".config/hyperforge")))
     }
 
-    /// Create with a custom secrets file path
+    /// Read secrets.yaml and the secret namespaces from `config_dir`
+    pub fn in_dir(config_dir: &Path) -> Self {
+        Self {
+            secrets_path: config_dir.join("secrets.yaml"),
+            namespaces: Namespaces::load(config_dir),
+        }
+    }
+
+    /// Create with a custom secrets file path (no namespaces)
     pub const fn with_path(path: PathBuf) -> Self {
         Self {
             secrets_path: path,
+            namespaces: Namespaces::empty(),
         }
     }
 }
 
-#[async_trait]
-impl AuthProvider for YamlAuthProvider {
-    async fn get_secret(&self, key: &str) -> anyhow::Result<Option<String>> {
+impl YamlAuthProvider {
+    /// `key` from its backend, then (if `forge_wide`) the forge-wide token,
+    /// then its env var
+    async fn resolve(&self, key: &str, forge_wide: bool) -> anyhow::Result<Option<String>> {
+        let cache_key = format!("{}#{key}{}", self.secrets_path.display(), if forge_wide { "" } else { "#scoped" });
+        if let Some(value) = session::cached(&cache_key) {
+            return Ok(Some(value));
+        }
+        let routed = self.namespaces.route(key).0;
+        let wide = SecretPath::new(key).forge_wide().filter(|_| forge_wide);
+        let wide_routed = wide.as_ref().map(|wide| self.namespaces.route(wide.as_str()).0);
+
         let content = match std::fs::read_to_string(&self.secrets_path) {
             Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound
+                && (routed == SecretBackend::Keychain
+                    || wide_routed == Some(SecretBackend::Keychain)
+                    || env::env_var_for(key).is_some()) => String::new(),
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
                 return Err(anyhow::anyhow!(
                     "Secrets file not found at {}. Run the secrets hub to configure tokens.",
@@ -58,9 +91,75 @@ impl AuthProvider for YamlAuthProvider {
             Err(e) => return Err(e.into()),
         };
 
-        let file: SecretsFile = serde_yaml::from_str(&content)
+        let content = Zeroizing::new(content);
+        let content = Zeroizing::new(
+            encryption::read_plaintext(&content, encryption::passphrase_from_env().as_deref())
+                .map_err(|e| anyhow::anyhow!("Failed to read {}: {e}", self.secrets_path.display()))?,
+        );
+        let file: Option<SecretsFile> = serde_yaml::from_str(&content)
             .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", self.secrets_path.display(), e))?;
 
-        Ok(file.secrets.get(key).map(|entry| entry.value.clone()))
+        // An org-scoped token falls back to the forge-wide one
+        let mut found = self.lookup(file.as_ref(), key).await?;
+        if found.is_none() {
+            if let Some(wide) = &wide {
+                found = self.lookup(file.as_ref(), wide.as_str()).await?;
+            }
+        }
+
+        // CI-style env vars stand in for missing tokens
+        let found = found.or_else(|| env::lookup(key).map(|(_, value)| value));
+        if let Some(value) = &found {
+            session::remember(&cache_key, value);
+        }
+        Ok(found)
+    }
+
+    /// `key` from the backend holding it, without fallbacks
+    async fn lookup(&self, file: Option<&SecretsFile>, key: &str) -> anyhow::Result<Option<String>> {
+        let entry = file.and_then(|f| f.secrets.get(key));
+        let routed = self.namespaces.route(key).0;
+
+        // A migrated secret's pin beats the namespaces
+        if entry.and_then(|e| e.backend).unwrap_or(routed) == SecretBackend::Keychain {
+            KeychainBridge::global().get(key).await
+                .map_err(|e| anyhow::anyhow!("Failed to get secret from keychain: {e}"))
+        } else {
+            Ok(entry.map(|entry| entry.value.clone()))
+        }
+    }
+}
+
+#[async_trait]
+impl AuthProvider for YamlAuthProvider {
+    async fn get_secret(&self, key: &str) -> anyhow::Result<Option<String>> {
+        self.resolve(key, true).await
+    }
+
+    async fn get_scoped_secret(&self, key: &str) -> anyhow::Result<Option<String>> {
+        self.resolve(key, false).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_forge_wide_token_fallback() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("secrets.yaml");
+        std::fs::write(
+            &path,
+            "secrets:\n  github/token:\n    value: ghp_wide\n  github/acme/token:\n    value: ghp_acme\n",
+        )
+        .unwrap();
+        let provider = YamlAuthProvider::with_path(path);
+
+        assert_eq!(provider.get_secret("github/acme/token").await.unwrap().as_deref(), Some("ghp_acme"));
+        assert_eq!(provider.get_secret("github/other/token").await.unwrap().as_deref(), Some("ghp_wide"));
+        assert_eq!(provider.get_secret("github/other/app_id").await.unwrap(), None);
+        // Scoped lookups skip the forge-wide token (GITHUB_TOKEN may still answer)
+        assert_ne!(provider.get_scoped_secret("github/other/token").await.unwrap().as_deref(), Some("ghp_wide"));
     }
 }
@@ -0,0 +1,204 @@
+//! Encryption at rest for secrets.yaml
+//!
+//! With `[secrets] storage = "encrypted"` in ~/.config/hyperforge/config.toml
+//! the secrets file holds an envelope instead of plaintext YAML:
+//!
+//! ```yaml
+//! format: chacha20-poly1305
+//! kdf: scrypt
+//! salt: <base64>
+//! nonce: <base64>
+//! ciphertext: <base64, Poly1305 tag appended>
+//! ```
+//!
+//! The key is derived from the passphrase in `HYPERFORGE_SECRETS_PASSPHRASE`
+//! with scrypt. Every save draws a fresh nonce and keeps the salt, so the
+//! derived key is computed once per process and cached.
+
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+/// Env var holding the passphrase
+pub const PASSPHRASE_ENV: &str = "HYPERFORGE_SECRETS_PASSPHRASE";
+
+const FORMAT: &str = "chacha20-poly1305";
+const KDF: &str = "scrypt";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+/// scrypt cost: N = 2^15, r = 8, p = 1 (32 MiB)
+const SCRYPT_N: u64 = 1 << 15;
+const SCRYPT_R: u64 = 8;
+const SCRYPT_P: u64 = 1;
+const SCRYPT_MAXMEM: u64 = 64 * 1024 * 1024;
+
+/// How secrets.yaml is written, under `[secrets] storage`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageFormat {
+    #[default]
+    Plaintext,
+    Encrypted,
+}
+
+impl StorageFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "plaintext" => Some(Self::Plaintext),
+            "encrypted" => Some(Self::Encrypted),
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Plaintext => "plaintext",
+            Self::Encrypted => "encrypted",
+        }
+    }
+}
+
+/// The passphrase from `HYPERFORGE_SECRETS_PASSPHRASE`, if set
+pub fn passphrase_from_env() -> Option<String> {
+    std::env::var(PASSPHRASE_ENV).ok().filter(|p| !p.is_empty())
+}
+
+/// An encrypted secrets file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    format: String,
+    kdf: String,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Derived keys by `(sha256(passphrase), salt)`
+type KeyCache = Mutex<HashMap<(Vec<u8>, Vec<u8>), [u8; 32]>>;
+
+fn derived_keys() -> &'static KeyCache {
+    static KEYS: OnceLock<KeyCache> = OnceLock::new();
+    KEYS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+    let cache_key = (Sha256::digest(passphrase.as_bytes()).to_vec(), salt.to_vec());
+    if let Some(key) = derived_keys().lock().map_err(|e| e.to_string())?.get(&cache_key) {
+        return Ok(*key);
+    }
+
+    let mut key = [0u8; 32];
+    openssl::pkcs5::scrypt(passphrase.as_bytes(), salt, SCRYPT_N, SCRYPT_R, SCRYPT_P, SCRYPT_MAXMEM, &mut key)
+        .map_err(|e| format!("key derivation failed: {e}"))?;
+    derived_keys().lock().map_err(|e| e.to_string())?.insert(cache_key, key);
+    Ok(key)
+}
+
+fn decode(field: &str, value: &str) -> Result<Vec<u8>, String> {
+    openssl::base64::decode_block(value).map_err(|e| format!("invalid {field}: {e}"))
+}
+
+impl Envelope {
+    /// Parse `content` as an envelope; `None` for anything else, such as
+    /// plaintext secrets YAML
+    pub fn parse(content: &str) -> Option<Self> {
+        serde_yaml::from_str::<Self>(content).ok().filter(|e| e.format == FORMAT)
+    }
+
+    /// Encrypt `plaintext`, reusing `salt` if given
+    pub fn seal(plaintext: &str, passphrase: &str, salt: Option<&[u8]>) -> Result<Self, String> {
+        let salt = if let Some(salt) = salt {
+            salt.to_vec()
+        } else {
+            let mut salt = vec![0u8; SALT_LEN];
+            openssl::rand::rand_bytes(&mut salt).map_err(|e| e.to_string())?;
+            salt
+        };
+        let mut nonce = [0u8; NONCE_LEN];
+        openssl::rand::rand_bytes(&mut nonce).map_err(|e| e.to_string())?;
+
+        let key = derive_key(passphrase, &salt)?;
+        let mut tag = [0u8; TAG_LEN];
+        let mut ciphertext = encrypt_aead(Cipher::chacha20_poly1305(), &key, Some(&nonce), &[], plaintext.as_bytes(), &mut tag)
+            .map_err(|e| format!("encryption failed: {e}"))?;
+        ciphertext.extend_from_slice(&tag);
+
+        Ok(Self {
+            format: FORMAT.to_string(),
+            kdf: KDF.to_string(),
+            salt: openssl::base64::encode_block(&salt),
+            nonce: openssl::base64::encode_block(&nonce),
+            ciphertext: openssl::base64::encode_block(&ciphertext),
+        })
+    }
+
+    /// Decrypt with `passphrase`; fails on a wrong passphrase or tampering
+    pub fn open(&self, passphrase: &str) -> Result<String, String> {
+        if self.kdf != KDF {
+            return Err(format!("unsupported kdf: {}", self.kdf));
+        }
+        let salt = self.salt()?;
+        let nonce = decode("nonce", &self.nonce)?;
+        let data = decode("ciphertext", &self.ciphertext)?;
+        if data.len() < TAG_LEN {
+            return Err("ciphertext is truncated".to_string());
+        }
+        let (ciphertext, tag) = data.split_at(data.len() - TAG_LEN);
+
+        let key = derive_key(passphrase, &salt)?;
+        let plaintext = decrypt_aead(Cipher::chacha20_poly1305(), &key, Some(&nonce), &[], ciphertext, tag)
+            .map_err(|_| "wrong passphrase or corrupted secrets file".to_string())?;
+        String::from_utf8(plaintext).map_err(|e| e.to_string())
+    }
+
+    /// The scrypt salt
+    pub fn salt(&self) -> Result<Vec<u8>, String> {
+        decode("salt", &self.salt)
+    }
+
+    pub fn to_yaml(&self) -> Result<String, String> {
+        serde_yaml::to_string(self).map_err(|e| e.to_string())
+    }
+}
+
+/// Plaintext secrets YAML from a secrets file's `content`, decrypting it
+/// with `passphrase` if it is an envelope
+pub fn read_plaintext(content: &str, passphrase: Option<&str>) -> Result<String, String> {
+    match Envelope::parse(content) {
+        Some(envelope) => {
+            let passphrase = passphrase.ok_or_else(|| format!("the secrets file is encrypted: set {PASSPHRASE_ENV}"))?;
+            envelope.open(passphrase)
+        }
+        None => Ok(content.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let plaintext = "secrets:\n  github/alice/token:\n    value: ghp_xxx\n";
+        let envelope = Envelope::seal(plaintext, "correct horse", None).unwrap();
+        let yaml = envelope.to_yaml().unwrap();
+        assert!(!yaml.contains("ghp_xxx"));
+
+        let parsed = Envelope::parse(&yaml).unwrap();
+        assert_eq!(parsed.open("correct horse").unwrap(), plaintext);
+        assert!(parsed.open("wrong").is_err());
+
+        // Same salt, fresh nonce
+        let resealed = Envelope::seal(plaintext, "correct horse", Some(&parsed.salt().unwrap())).unwrap();
+        assert_eq!(resealed.salt, parsed.salt);
+        assert_ne!(resealed.nonce, parsed.nonce);
+
+        assert!(Envelope::parse(plaintext).is_none());
+        assert_eq!(read_plaintext(plaintext, None).unwrap(), plaintext);
+        assert!(read_plaintext(&yaml, None).is_err());
+        assert_eq!(read_plaintext(&yaml, Some("correct horse")).unwrap(), plaintext);
+    }
+}
@@ -0,0 +1,47 @@
+//! Environment variable fallback for missing secrets
+//!
+//! CI runners usually expose tokens under well-known names. A path missing
+//! from its backend is read from the variable its convention names:
+//!
+//! | Path                                          | Variable               |
+//! |-----------------------------------------------|------------------------|
+//! | `github/<org>/token`                          | `GITHUB_TOKEN`         |
+//! | `gitlab/<org>/token`                          | `GITLAB_TOKEN`         |
+//! | `codeberg/<org>/token`                        | `CODEBERG_TOKEN`       |
+//! | `crates-io/token`, `registry/crates-io/token` | `CARGO_REGISTRY_TOKEN` |
+
+/// The variable a secret path falls back to, if any
+pub fn env_var_for(path: &str) -> Option<&'static str> {
+    if matches!(path, "crates-io/token" | "registry/crates-io/token") {
+        return Some("CARGO_REGISTRY_TOKEN");
+    }
+    match path.split('/').collect::<Vec<_>>().as_slice() {
+        ["github", org, "token"] if !org.is_empty() => Some("GITHUB_TOKEN"),
+        ["gitlab", org, "token"] if !org.is_empty() => Some("GITLAB_TOKEN"),
+        ["codeberg", org, "token"] if !org.is_empty() => Some("CODEBERG_TOKEN"),
+        _ => None,
+    }
+}
+
+/// The fallback variable for `path` and its value, when set and non-empty
+pub fn lookup(path: &str) -> Option<(&'static str, String)> {
+    let var = env_var_for(path)?;
+    std::env::var(var).ok().filter(|v| !v.trim().is_empty()).map(|v| (var, v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_var_for() {
+        assert_eq!(env_var_for("github/acme/token"), Some("GITHUB_TOKEN"));
+        assert_eq!(env_var_for("gitlab/acme/token"), Some("GITLAB_TOKEN"));
+        assert_eq!(env_var_for("codeberg/acme/token"), Some("CODEBERG_TOKEN"));
+        assert_eq!(env_var_for("crates-io/token"), Some("CARGO_REGISTRY_TOKEN"));
+        assert_eq!(env_var_for("registry/crates-io/token"), Some("CARGO_REGISTRY_TOKEN"));
+        assert_eq!(env_var_for("github/acme/packages_token"), None);
+        assert_eq!(env_var_for("github//token"), None);
+        assert_eq!(env_var_for("git.acme.dev/acme/token"), None);
+    }
+}
@@ -1,19 +1,41 @@
 //! Auth Hub - Simple secret management plugin
 //!
-//! Provides basic secret storage using YAML files.
-//! Secrets are stored at ~/.config/hyperforge/secrets.yaml
-
+//! Provides basic secret storage using YAML files, optionally encrypted at
+//! rest (see [`encryption`]).
+//! Secrets are stored at ~/.config/hyperforge/secrets.yaml, except under
+//! `[secrets.namespaces]` routed elsewhere (see [`namespaces`]). Every get
+//! emits a `secret_access` event naming the backend it was served from.
+//! Token paths missing everywhere fall back to CI-style env vars such as
+//! `GITHUB_TOKEN` (see [`env`]).
+//!
+//! `migrate_secrets` moves secrets between backends. A migrated secret stays
+//! listed in secrets.yaml, pinned to its new backend with its original
+//! timestamps (and no value once it lives in the keychain), so it can be
+//! listed, exported and migrated back.
+
+pub mod encryption;
+pub mod env;
+pub mod namespaces;
 pub mod storage;
 pub mod types;
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use async_stream::stream;
 use futures::stream::Stream;
+use plexus_core::plexus::Activation;
 use serde::{Deserialize, Serialize};
 
+use crate::auth::device_flow::{DeviceFlow, DeviceFlowEndpoints, PollOutcome};
+use crate::auth::KeychainBridge;
+use crate::config::UserConfig;
+use crate::help::{self, ParamHelp};
+use crate::hubs::HyperforgeState;
+use encryption::{passphrase_from_env, StorageFormat, PASSPHRASE_ENV};
+use namespaces::{Namespaces, SecretBackend};
 use storage::{StorageError, YamlStorage};
-use types::{Secret, SecretPath};
+use types::{Secret, SecretInfo, SecretPath};
 
 /// Auth hub events
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
@@ -34,6 +56,57 @@ pub enum AuthEvent {
         updated_at: Option<String>,
     },
 
+    /// Audit record of a get, emitted whether or not the secret exists
+    SecretAccess {
+        path: String,
+        backend: String, // "file", "keychain", "env"
+        /// `[secrets.namespaces]` pattern that routed the path, if any
+        namespace: Option<String>,
+        found: bool,
+        /// The forge-wide path (`github/token`) that answered for an
+        /// org-scoped one with no value of its own
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        resolved_from: Option<String>,
+        accessed_at: String,
+    },
+
+    /// A `[secrets.namespaces]` rule
+    Namespace { pattern: String, backend: String },
+
+    /// Device flow started: the user approves `user_code` at `verification_uri`
+    DeviceCode {
+        forge: String,
+        user_code: String,
+        verification_uri: String,
+        /// Seconds the code stays valid
+        expires_in: u64,
+    },
+
+    /// A secret moved (or, in a dry run, to be moved) between backends
+    SecretMigrated {
+        path: String,
+        from: String,
+        to: String,
+        dry_run: bool,
+    },
+
+    /// An exported secret; `value` is `None` when redacted
+    SecretExport {
+        path: String,
+        backend: String,
+        value: Option<String>,
+        created_at: Option<String>,
+        updated_at: Option<String>,
+    },
+
+    /// Usage of one method, from `help`
+    MethodHelp {
+        method: String,
+        description: String,
+        params: Vec<ParamHelp>,
+        example: String,
+    },
+
     /// Success message
     Success { message: String },
 
@@ -41,41 +114,156 @@ pub enum AuthEvent {
     Error { message: String },
 }
 
-/// Auth Hub - manages secrets in YAML storage
+/// The backend holding `path`: its migration pin, else the namespace route,
+/// along with the namespace pattern that matched
+fn route(storage: &YamlStorage, namespaces: &Namespaces, path: &SecretPath) -> (SecretBackend, Option<String>) {
+    let (routed, namespace) = namespaces.route(path.as_str());
+    let pinned = storage.pinned_backend(path).ok().flatten();
+    (pinned.unwrap_or(routed), namespace.map(str::to_string))
+}
+
+/// Read `path` from the backend holding it, with that backend and the
+/// namespace that routed there
+async fn lookup(
+    storage: &YamlStorage,
+    keychain: &KeychainBridge,
+    namespaces: &Namespaces,
+    path: &SecretPath,
+) -> (SecretBackend, Option<String>, Result<Option<Secret>, String>) {
+    let (backend, namespace) = route(storage, namespaces, path);
+    let result = match backend {
+        SecretBackend::File => match storage.get(path) {
+            Ok(secret) => Ok(Some(secret)),
+            Err(StorageError::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        },
+        SecretBackend::Keychain => keychain.get(path.as_str()).await.map(|value| {
+            // Migrated secrets keep their timestamps in secrets.yaml
+            let (created_at, updated_at) = storage
+                .get(path)
+                .ok()
+                .filter(|_| matches!(storage.pinned_backend(path), Ok(Some(_))))
+                .map(|s| (s.created_at, s.updated_at))
+                .unwrap_or_default();
+            value.map(|v| Secret::with_timestamps(path.clone(), v, created_at, updated_at))
+        }),
+    };
+    (backend, namespace, result)
+}
+
+/// Store `value` at `path` in the backend holding it
+async fn store(
+    storage: &YamlStorage,
+    keychain: &KeychainBridge,
+    namespaces: &Namespaces,
+    path: SecretPath,
+    value: String,
+) -> Result<SecretBackend, String> {
+    let (backend, _) = route(storage, namespaces, &path);
+    match backend {
+        SecretBackend::File => storage.set(Secret::new(path, value)).await.map_err(|e| e.to_string())?,
+        SecretBackend::Keychain => {
+            keychain.set(path.as_str(), &value).await?;
+            // A migrated secret's entry records the update
+            if storage.pinned_backend(&path).ok().flatten().is_some() {
+                storage.set(Secret::new(path, "")).await.map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    Ok(backend)
+}
+
+/// Whether a secrets.yaml entry is the one `get_secret` serves: pinned, or
+/// not shadowed by a keychain namespace
+fn is_served(namespaces: &Namespaces, info: &SecretInfo) -> bool {
+    info.backend.is_some() || namespaces.route(info.path.as_str()).0 == SecretBackend::File
+}
+
+/// Auth Hub - manages secrets in YAML storage and namespaced backends
 #[derive(Clone)]
 pub struct AuthHub {
     storage: Arc<YamlStorage>,
+    keychain: Arc<KeychainBridge>,
+    namespaces: Arc<Namespaces>,
+    /// Where config.toml and secrets.yaml live
+    config_dir: PathBuf,
 }
 
 impl AuthHub {
-    /// Create a new auth hub with default storage location
-    pub async fn new() -> Result<Self, StorageError> {
-        let storage = YamlStorage::default_location()?;
+    /// Create an auth hub over secrets.yaml in `config_dir`, with the secret
+    /// namespaces of its config.toml
+    pub async fn new(config_dir: &Path) -> Result<Self, StorageError> {
+        let mut storage = YamlStorage::new(config_dir.join("secrets.yaml"));
+        if UserConfig::load(config_dir).secrets.storage == StorageFormat::Encrypted {
+            let passphrase = passphrase_from_env().ok_or_else(|| {
+                StorageError::Encryption(format!("[secrets] storage = \"encrypted\" needs {PASSPHRASE_ENV}"))
+            })?;
+            storage = storage.with_passphrase(passphrase);
+        }
 
         // Load existing secrets
         storage.load().await?;
 
         Ok(Self {
             storage: Arc::new(storage),
+            keychain: Arc::new(KeychainBridge::global()),
+            namespaces: Arc::new(Namespaces::load(config_dir)),
+            config_dir: config_dir.to_path_buf(),
         })
     }
 
-    /// Create auth hub with custom storage location
+    /// Create auth hub with custom storage location (no namespaces)
     pub async fn with_storage(storage: YamlStorage) -> Result<Self, StorageError> {
         storage.load().await?;
         Ok(Self {
             storage: Arc::new(storage),
+            keychain: Arc::new(KeychainBridge::global()),
+            namespaces: Arc::new(Namespaces::empty()),
+            config_dir: HyperforgeState::default_config_dir(),
         })
     }
+
+    /// Route secret paths through `namespaces`
+    #[must_use]
+    pub fn with_namespaces(mut self, namespaces: Namespaces) -> Self {
+        self.namespaces = Arc::new(namespaces);
+        self
+    }
 }
 
 #[plexus_macros::activation(
     namespace = "auth",
     version = "1.0.0",
-    description = "Simple secret management with YAML storage",
+    description = "Secret management with YAML storage and per-namespace backends",
     crate_path = "plexus_core"
 )]
 impl AuthHub {
+    /// This hub's method catalog
+    #[plexus_macros::method(
+        description = "List this hub's methods: what each does, its parameters (type, required or optional, description) and an example invocation",
+        params(
+            method = "Only describe this method (optional)"
+        )
+    )]
+    pub async fn help(
+        &self,
+        method: Option<String>,
+    ) -> impl Stream<Item = AuthEvent> + Send + 'static {
+        let events: Vec<AuthEvent> = match help::catalog(&self.plugin_schema(), "synapse secrets auth", method.as_deref()) {
+            Ok(entries) => entries
+                .into_iter()
+                .map(|entry| AuthEvent::MethodHelp {
+                    method: entry.method,
+                    description: entry.description,
+                    params: entry.params,
+                    example: entry.example,
+                })
+                .collect(),
+            Err(message) => vec![AuthEvent::Error { message }],
+        };
+        futures::stream::iter(events)
+    }
+
     /// Get a secret by path
     #[plexus_macros::method(
         description = "Get a secret by path",
@@ -88,12 +276,41 @@ impl AuthHub {
         secret_key: String,
     ) -> impl Stream<Item = AuthEvent> + Send + 'static {
         let storage = self.storage.clone();
+        let keychain = self.keychain.clone();
+        let namespaces = self.namespaces.clone();
 
         stream! {
             let secret_path = SecretPath::new(secret_key);
-
-            match storage.get(&secret_path) {
-                Ok(secret) => {
+            let (backend, namespace, result) = lookup(&storage, &keychain, &namespaces, &secret_path).await;
+
+            // An org-scoped token falls back to the forge-wide one
+            let wide = if matches!(result, Ok(None)) { secret_path.forge_wide() } else { None };
+            let (backend, result, resolved_from) = match wide {
+                Some(wide) => match lookup(&storage, &keychain, &namespaces, &wide).await {
+                    (wide_backend, _, found @ Ok(Some(_))) => (wide_backend, found, Some(wide.to_string())),
+                    _ => (backend, result, None),
+                },
+                None => (backend, result, None),
+            };
+
+            // Missing secrets fall back to well-known env vars (see [`env`])
+            let fallback = if matches!(result, Ok(None)) { env::lookup(secret_path.as_str()) } else { None };
+            let (served_by, result) = match fallback {
+                Some((_, value)) => ("env", Ok(Some(Secret::with_timestamps(secret_path.clone(), value, None, None)))),
+                None => (backend.as_str(), result),
+            };
+
+            yield AuthEvent::SecretAccess {
+                path: secret_path.to_string(),
+                backend: served_by.to_string(),
+                namespace,
+                found: matches!(result, Ok(Some(_))),
+                resolved_from,
+                accessed_at: chrono::Utc::now().to_rfc3339(),
+            };
+
+            match result {
+                Ok(Some(secret)) => {
                     yield AuthEvent::Secret {
                         path: secret.path.to_string(),
                         value: secret.value,
@@ -101,6 +318,11 @@ impl AuthHub {
                         updated_at: secret.updated_at.map(|d| d.to_rfc3339()),
                     };
                 }
+                Ok(None) => {
+                    yield AuthEvent::Error {
+                        message: format!("Failed to get secret: Secret not found: {secret_path} ({})", backend.as_str()),
+                    };
+                }
                 Err(e) => {
                     yield AuthEvent::Error {
                         message: format!("Failed to get secret: {e}"),
@@ -124,14 +346,14 @@ impl AuthHub {
         value: String,
     ) -> impl Stream<Item = AuthEvent> + Send + 'static {
         let storage = self.storage.clone();
+        let keychain = self.keychain.clone();
+        let namespaces = self.namespaces.clone();
 
         stream! {
-            let secret = Secret::new(secret_key.clone(), value);
-
-            match storage.set(secret).await {
-                Ok(()) => {
+            match store(&storage, &keychain, &namespaces, SecretPath::new(secret_key.clone()), value).await {
+                Ok(backend) => {
                     yield AuthEvent::Success {
-                        message: format!("Secret set: {secret_key}"),
+                        message: format!("Secret set: {secret_key} ({})", backend.as_str()),
                     };
                 }
                 Err(e) => {
@@ -145,7 +367,7 @@ impl AuthHub {
 
     /// List secrets matching a prefix
     #[plexus_macros::method(
-        description = "List secrets matching a prefix",
+        description = "List secrets in secrets.yaml matching a prefix, including ones migrated to the keychain (other keychain secrets can't be enumerated)",
         params(
             prefix = "Prefix to filter by (empty string for all secrets)"
         )
@@ -155,11 +377,14 @@ impl AuthHub {
         prefix: String,
     ) -> impl Stream<Item = AuthEvent> + Send + 'static {
         let storage = self.storage.clone();
+        let namespaces = self.namespaces.clone();
 
         stream! {
             match storage.list(&prefix) {
                 Ok(secrets) => {
-                    for info in secrets {
+                    // Entries shadowed by a keychain namespace aren't the ones
+                    // `get_secret` returns
+                    for info in secrets.into_iter().filter(|i| is_served(&namespaces, i)) {
                         yield AuthEvent::SecretInfo {
                             path: info.path.to_string(),
                             created_at: info.created_at.map(|d| d.to_rfc3339()),
@@ -188,11 +413,23 @@ impl AuthHub {
         secret_key: String,
     ) -> impl Stream<Item = AuthEvent> + Send + 'static {
         let storage = self.storage.clone();
+        let keychain = self.keychain.clone();
+        let namespaces = self.namespaces.clone();
 
         stream! {
             let secret_path = SecretPath::new(secret_key.clone());
 
-            match storage.delete(&secret_path).await {
+            let result = match route(&storage, &namespaces, &secret_path).0 {
+                SecretBackend::File => storage.delete(&secret_path).await.map_err(|e| e.to_string()),
+                SecretBackend::Keychain => match keychain.delete(&secret_key).await {
+                    Ok(()) if storage.exists(&secret_path).unwrap_or(false) => {
+                        storage.delete(&secret_path).await.map_err(|e| e.to_string())
+                    }
+                    other => other,
+                },
+            };
+
+            match result {
                 Ok(()) => {
                     yield AuthEvent::Success {
                         message: format!("Secret deleted: {secret_key}"),
@@ -206,6 +443,317 @@ impl AuthHub {
             }
         }
     }
+
+    /// Log in to a forge with the OAuth device flow
+    #[plexus_macros::method(
+        description = "Log in to a forge with the OAuth device flow and store the token at <forge>/<org>/token. Needs an [oauth.<forge>] client_id in ~/.config/hyperforge/config.toml.",
+        params(
+            forge = "Forge to log in to: github, gitlab or codeberg",
+            org = "Org the token is stored for"
+        )
+    )]
+    pub async fn login(
+        &self,
+        forge: String,
+        org: String,
+    ) -> impl Stream<Item = AuthEvent> + Send + 'static {
+        let storage = self.storage.clone();
+        let keychain = self.keychain.clone();
+        let namespaces = self.namespaces.clone();
+        let config_dir = self.config_dir.clone();
+
+        stream! {
+            let Some(app) = UserConfig::load(&config_dir).oauth.remove(&forge) else {
+                yield AuthEvent::Error {
+                    message: format!("No OAuth app for {forge}: set [oauth.{forge}] client_id in ~/.config/hyperforge/config.toml"),
+                };
+                return;
+            };
+            let Some(endpoints) = DeviceFlowEndpoints::resolve(&forge, &app) else {
+                yield AuthEvent::Error {
+                    message: format!("{forge} has no built-in device flow endpoints: set device_url and token_url under [oauth.{forge}]"),
+                };
+                return;
+            };
+
+            let flow = match DeviceFlow::new(endpoints, app.client_id) {
+                Ok(flow) => flow,
+                Err(e) => {
+                    yield AuthEvent::Error { message: format!("Login failed: {e}") };
+                    return;
+                }
+            };
+            let authorization = match flow.start().await {
+                Ok(authorization) => authorization,
+                Err(e) => {
+                    yield AuthEvent::Error { message: format!("Login to {forge} failed: {e}") };
+                    return;
+                }
+            };
+
+            yield AuthEvent::DeviceCode {
+                forge: forge.clone(),
+                user_code: authorization.user_code.clone(),
+                verification_uri: authorization.verification_uri.clone(),
+                expires_in: authorization.expires_in,
+            };
+
+            let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(authorization.expires_in);
+            let mut interval = authorization.interval;
+            let token = loop {
+                tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+                if tokio::time::Instant::now() >= deadline {
+                    break Err("the code expired before it was approved".to_string());
+                }
+                match flow.poll(&authorization.device_code).await {
+                    Ok(PollOutcome::Token(token)) => break Ok(token),
+                    Ok(PollOutcome::Pending) => {}
+                    Ok(PollOutcome::SlowDown) => interval += 5,
+                    Ok(PollOutcome::Failed(reason)) => break Err(reason),
+                    Err(e) => break Err(e.to_string()),
+                }
+            };
+
+            let token = match token {
+                Ok(token) => token,
+                Err(reason) => {
+                    yield AuthEvent::Error { message: format!("Login to {forge} failed: {reason}") };
+                    return;
+                }
+            };
+
+            let path = SecretPath::from_parts(&forge, &org, "token");
+            match store(&storage, &keychain, &namespaces, path.clone(), token).await {
+                Ok(backend) => {
+                    yield AuthEvent::Success {
+                        message: format!("Logged in to {forge}; token stored at {path} ({})", backend.as_str()),
+                    };
+                }
+                Err(e) => {
+                    yield AuthEvent::Error { message: format!("Logged in to {forge} but could not store the token: {e}") };
+                }
+            }
+        }
+    }
+
+    /// Move secrets between backends
+    #[plexus_macros::method(
+        description = "Move the secrets listed in secrets.yaml from one backend to another, keeping their paths and timestamps. Migrated secrets are pinned to their new backend regardless of [secrets.namespaces].",
+        params(
+            from = "Backend to move out of: file or keychain",
+            to = "Backend to move into: file or keychain",
+            prefix = "Only secrets whose path starts with this (optional, default all)",
+            dry_run = "List what would move without moving anything (optional, default false)"
+        )
+    )]
+    pub async fn migrate_secrets(
+        &self,
+        from: String,
+        to: String,
+        prefix: Option<String>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = AuthEvent> + Send + 'static {
+        let storage = self.storage.clone();
+        let keychain = self.keychain.clone();
+        let namespaces = self.namespaces.clone();
+        let dry_run = dry_run.unwrap_or(false);
+
+        stream! {
+            let (Some(source), Some(target)) = (SecretBackend::parse(&from), SecretBackend::parse(&to)) else {
+                yield AuthEvent::Error {
+                    message: format!("Unknown backend in '{from}' -> '{to}': expected file or keychain"),
+                };
+                return;
+            };
+            if source == target {
+                yield AuthEvent::Error { message: format!("Secrets are already in {from}") };
+                return;
+            }
+
+            let mut secrets = match storage.list(prefix.as_deref().unwrap_or("")) {
+                Ok(secrets) => secrets,
+                Err(e) => {
+                    yield AuthEvent::Error { message: format!("Failed to list secrets: {e}") };
+                    return;
+                }
+            };
+            secrets.sort_by(|a, b| a.path.as_str().cmp(b.path.as_str()));
+
+            let mut moved = 0usize;
+            for info in secrets.into_iter().filter(|i| is_served(&namespaces, i)) {
+                let path = info.path.to_string();
+                if info.backend.unwrap_or_else(|| namespaces.route(&path).0) != source {
+                    continue;
+                }
+
+                if !dry_run {
+                    let value = match source {
+                        SecretBackend::File => storage.get(&info.path).map(|s| Some(s.value)).map_err(|e| e.to_string()),
+                        SecretBackend::Keychain => keychain.get(&path).await,
+                    };
+                    let value = match value {
+                        Ok(Some(value)) => value,
+                        Ok(None) => {
+                            yield AuthEvent::Error { message: format!("Failed to migrate {path}: not found in {from}") };
+                            continue;
+                        }
+                        Err(e) => {
+                            yield AuthEvent::Error { message: format!("Failed to migrate {path}: {e}") };
+                            continue;
+                        }
+                    };
+
+                    // Write the new copy before dropping the old one
+                    if target == SecretBackend::Keychain {
+                        if let Err(e) = keychain.set(&path, &value).await {
+                            yield AuthEvent::Error { message: format!("Failed to migrate {path}: {e}") };
+                            continue;
+                        }
+                    }
+                    let secret = Secret::with_timestamps(info.path.clone(), value, info.created_at, info.updated_at);
+                    if let Err(e) = storage.restore(secret, target).await {
+                        yield AuthEvent::Error { message: format!("Failed to migrate {path}: {e}") };
+                        continue;
+                    }
+                    if source == SecretBackend::Keychain {
+                        if let Err(e) = keychain.delete(&path).await {
+                            yield AuthEvent::Error { message: format!("Migrated {path} but could not remove it from the keychain: {e}") };
+                        }
+                    }
+                }
+
+                moved += 1;
+                yield AuthEvent::SecretMigrated {
+                    path,
+                    from: source.as_str().to_string(),
+                    to: target.as_str().to_string(),
+                    dry_run,
+                };
+            }
+
+            yield AuthEvent::Success {
+                message: format!("{} {moved} secret(s) from {from} to {to}", if dry_run { "Would migrate" } else { "Migrated" }),
+            };
+        }
+    }
+
+    /// Export secrets
+    #[plexus_macros::method(
+        description = "Export the secrets listed in secrets.yaml matching a prefix, with their backend and timestamps",
+        params(
+            prefix = "Prefix to filter by (empty string for all secrets)",
+            redacted = "Leave out the values (optional, default false)"
+        )
+    )]
+    pub async fn export_secrets(
+        &self,
+        prefix: String,
+        redacted: Option<bool>,
+    ) -> impl Stream<Item = AuthEvent> + Send + 'static {
+        let storage = self.storage.clone();
+        let keychain = self.keychain.clone();
+        let namespaces = self.namespaces.clone();
+        let redacted = redacted.unwrap_or(false);
+
+        stream! {
+            let mut secrets = match storage.list(&prefix) {
+                Ok(secrets) => secrets,
+                Err(e) => {
+                    yield AuthEvent::Error { message: format!("Failed to export secrets: {e}") };
+                    return;
+                }
+            };
+            secrets.sort_by(|a, b| a.path.as_str().cmp(b.path.as_str()));
+
+            for info in secrets.into_iter().filter(|i| is_served(&namespaces, i)) {
+                let backend = info.backend.unwrap_or_else(|| namespaces.route(info.path.as_str()).0);
+                let value = if redacted {
+                    None
+                } else {
+                    match backend {
+                        SecretBackend::File => storage.get(&info.path).ok().map(|s| s.value),
+                        SecretBackend::Keychain => keychain.get(info.path.as_str()).await.ok().flatten(),
+                    }
+                };
+
+                yield AuthEvent::SecretExport {
+                    path: info.path.to_string(),
+                    backend: backend.as_str().to_string(),
+                    value,
+                    created_at: info.created_at.map(|d| d.to_rfc3339()),
+                    updated_at: info.updated_at.map(|d| d.to_rfc3339()),
+                };
+            }
+        }
+    }
+
+    /// Rewrite secrets.yaml encrypted or as plaintext
+    #[plexus_macros::method(
+        description = "Rewrite secrets.yaml encrypted (ChaCha20-Poly1305, key derived with scrypt from HYPERFORGE_SECRETS_PASSPHRASE) or as plaintext, and record the choice as [secrets] storage in config.toml",
+        params(
+            to = "Storage format: encrypted or plaintext"
+        )
+    )]
+    pub async fn migrate_storage(&self, to: String) -> impl Stream<Item = AuthEvent> + Send + 'static {
+        let storage = self.storage.clone();
+        let config_dir = self.config_dir.clone();
+
+        stream! {
+            let Some(format) = StorageFormat::parse(&to) else {
+                yield AuthEvent::Error { message: format!("Unknown storage format '{to}': expected encrypted or plaintext") };
+                return;
+            };
+            let passphrase = match format {
+                StorageFormat::Encrypted => {
+                    let Some(passphrase) = passphrase_from_env() else {
+                        yield AuthEvent::Error { message: format!("Encrypting secrets.yaml needs {PASSPHRASE_ENV}") };
+                        return;
+                    };
+                    Some(passphrase)
+                }
+                StorageFormat::Plaintext => None,
+            };
+
+            let result = match storage.set_passphrase(passphrase) {
+                Ok(()) => storage.save().await.map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = result {
+                yield AuthEvent::Error { message: format!("Failed to rewrite secrets.yaml: {e}") };
+                return;
+            }
+
+            match UserConfig::set_secret_storage(&config_dir, format) {
+                Ok(()) => {
+                    yield AuthEvent::Success {
+                        message: format!("secrets.yaml is now {}; [secrets] storage = \"{}\" set in config.toml", format.as_str(), format.as_str()),
+                    };
+                }
+                Err(e) => {
+                    yield AuthEvent::Error {
+                        message: format!("secrets.yaml is now {} but config.toml could not be updated: {e}", format.as_str()),
+                    };
+                }
+            }
+        }
+    }
+
+    /// List secret namespaces
+    #[plexus_macros::method(
+        description = "List the [secrets.namespaces] rules routing secret paths to backends, most specific first; unmatched paths use secrets.yaml"
+    )]
+    pub async fn list_namespaces(&self) -> impl Stream<Item = AuthEvent> + Send + 'static {
+        let namespaces = self.namespaces.clone();
+
+        stream! {
+            for (pattern, backend) in namespaces.rules() {
+                yield AuthEvent::Namespace {
+                    pattern: pattern.clone(),
+                    backend: backend.as_str().to_string(),
+                };
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -216,11 +764,9 @@ mod tests {
 
     /// Test hub factory.
     ///
-    /// Referenced only by the commented-out `test_set_and_get_secret`,
+    /// Also meant for the commented-out `test_set_and_get_secret`,
     /// `test_list_secrets`, and `test_delete_secret` tests below (pending a
-    /// stream-Unpin fix in `AuthHub`). Kept for when those tests are restored
-    /// (tracked by HF-TESTS — see commit body).
-    #[allow(dead_code)]
+    /// stream-Unpin fix in `AuthHub`).
     async fn create_test_hub() -> (AuthHub, TempDir) {
         let temp = TempDir::new().unwrap();
         let storage = YamlStorage::new(temp.path().join("secrets.yaml"));
@@ -228,6 +774,102 @@ mod tests {
         (hub, temp)
     }
 
+    #[tokio::test]
+    async fn test_get_secret_emits_access_event() {
+        use futures::StreamExt;
+
+        let (hub, _temp) = create_test_hub().await;
+        let hub = hub.with_namespaces(Namespaces::new(&std::collections::BTreeMap::from([(
+            "registry/*".to_string(),
+            SecretBackend::File,
+        )])));
+        hub.storage.set(Secret::new("registry/npm", "npm_xxx")).await.unwrap();
+
+        let events: Vec<AuthEvent> = hub.get_secret("registry/npm".to_string()).await.collect().await;
+        match events.as_slice() {
+            [AuthEvent::SecretAccess { path, backend, namespace, found, .. }, AuthEvent::Secret { value, .. }] => {
+                assert_eq!(path, "registry/npm");
+                assert_eq!(backend, "file");
+                assert_eq!(namespace.as_deref(), Some("registry/*"));
+                assert!(*found);
+                assert_eq!(value, "npm_xxx");
+            }
+            other => panic!("unexpected events: {other:?}"),
+        }
+
+        // Misses are audited too (a path with no env var fallback)
+        let events: Vec<AuthEvent> = hub.get_secret("github/alice/packages_token".to_string()).await.collect().await;
+        assert!(matches!(
+            events.as_slice(),
+            [AuthEvent::SecretAccess { found: false, namespace: None, .. }, AuthEvent::Error { .. }]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_export_and_dry_run_migrate() {
+        use futures::StreamExt;
+
+        let (hub, _temp) = create_test_hub().await;
+        hub.storage.set(Secret::new("github/alice/token", "ghp_xxx")).await.unwrap();
+        hub.storage.set(Secret::new("codeberg/alice/token", "cb_zzz")).await.unwrap();
+
+        let events: Vec<AuthEvent> = hub.export_secrets(String::new(), Some(true)).await.collect().await;
+        let exported: Vec<_> = events
+            .iter()
+            .map(|e| match e {
+                AuthEvent::SecretExport { path, backend, value, created_at, .. } => {
+                    assert!(created_at.is_some());
+                    (path.as_str(), backend.as_str(), value.clone())
+                }
+                other => panic!("unexpected event: {other:?}"),
+            })
+            .collect();
+        assert_eq!(exported, vec![("codeberg/alice/token", "file", None), ("github/alice/token", "file", None)]);
+
+        let events: Vec<AuthEvent> = hub.export_secrets("github/".to_string(), None).await.collect().await;
+        assert!(matches!(events.as_slice(), [AuthEvent::SecretExport { value: Some(v), .. }] if v == "ghp_xxx"));
+
+        // Dry runs touch nothing, so they work without a keychain
+        let events: Vec<AuthEvent> = hub
+            .migrate_secrets("file".to_string(), "keychain".to_string(), Some("github/".to_string()), Some(true))
+            .await
+            .collect()
+            .await;
+        assert!(matches!(
+            events.as_slice(),
+            [AuthEvent::SecretMigrated { path, dry_run: true, .. }, AuthEvent::Success { .. }] if path == "github/alice/token"
+        ));
+        assert_eq!(hub.storage.pinned_backend(&SecretPath::new("github/alice/token")).unwrap(), None);
+
+        let events: Vec<AuthEvent> = hub.migrate_secrets("file".to_string(), "file".to_string(), None, None).await.collect().await;
+        assert!(matches!(events.as_slice(), [AuthEvent::Error { .. }]));
+    }
+
+    #[tokio::test]
+    async fn test_org_token_falls_back_to_forge_wide() {
+        use futures::StreamExt;
+
+        let (hub, _temp) = create_test_hub().await;
+        hub.storage.set(Secret::new("github/token", "ghp_wide")).await.unwrap();
+
+        let events: Vec<AuthEvent> = hub.get_secret("github/alice/token".to_string()).await.collect().await;
+        match events.as_slice() {
+            [AuthEvent::SecretAccess { found: true, resolved_from, .. }, AuthEvent::Secret { value, .. }] => {
+                assert_eq!(resolved_from.as_deref(), Some("github/token"));
+                assert_eq!(value, "ghp_wide");
+            }
+            other => panic!("unexpected events: {other:?}"),
+        }
+
+        // The org's own token wins once set
+        hub.storage.set(Secret::new("github/alice/token", "ghp_alice")).await.unwrap();
+        let events: Vec<AuthEvent> = hub.get_secret("github/alice/token".to_string()).await.collect().await;
+        assert!(matches!(
+            events.as_slice(),
+            [AuthEvent::SecretAccess { resolved_from: None, .. }, AuthEvent::Secret { value, .. }] if value == "ghp_alice"
+        ));
+    }
+
     /* Commented out: stream does not implement Unpin, cannot use .next().await directly
     #[tokio::test]
     async fn test_set_and_get_secret() {
@@ -0,0 +1,135 @@
+//! Secret namespaces - route secret paths to storage backends
+//!
+//! `[secrets.namespaces]` in ~/.config/hyperforge/config.toml maps path
+//! patterns to the backend holding them:
+//!
+//! ```toml
+//! [secrets.namespaces]
+//! "github/*" = "keychain"
+//! "registry/*" = "file"
+//! ```
+//!
+//! A pattern ending in `*` matches every path with that prefix, any other
+//! pattern only itself. The longest matching pattern wins; paths nothing
+//! matches stay in secrets.yaml.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::UserConfig;
+
+/// Where a namespace's secrets live
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretBackend {
+    /// ~/.config/hyperforge/secrets.yaml
+    #[default]
+    File,
+    /// The OS keychain, one entry per path (`hyperforge:<path>`)
+    Keychain,
+}
+
+impl SecretBackend {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "file" => Some(Self::File),
+            "keychain" => Some(Self::Keychain),
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::File => "file",
+            Self::Keychain => "keychain",
+        }
+    }
+}
+
+/// Routing table from secret paths to backends
+#[derive(Debug, Clone, Default)]
+pub struct Namespaces {
+    /// `(pattern, backend)`, most specific first
+    rules: Vec<(String, SecretBackend)>,
+}
+
+impl Namespaces {
+    /// No namespaces: every path is file-backed
+    pub const fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Build from `[secrets.namespaces]`
+    pub fn new(namespaces: &BTreeMap<String, SecretBackend>) -> Self {
+        let mut rules: Vec<(String, SecretBackend)> = namespaces.iter().map(|(p, b)| (p.clone(), *b)).collect();
+        // Exact patterns beat globs of the same length
+        rules.sort_by_key(|(pattern, _)| (std::cmp::Reverse(pattern.trim_end_matches('*').len()), pattern.ends_with('*')));
+        Self { rules }
+    }
+
+    /// Namespaces from the user config in `config_dir`
+    pub fn load(config_dir: &Path) -> Self {
+        Self::new(&UserConfig::load(config_dir).secrets.namespaces)
+    }
+
+    /// The backend holding `path`, and the pattern that routed it there
+    pub fn route(&self, path: &str) -> (SecretBackend, Option<&str>) {
+        self.rules
+            .iter()
+            .find(|(pattern, _)| match pattern.strip_suffix('*') {
+                Some(prefix) => path.starts_with(prefix),
+                None => path == pattern,
+            })
+            .map_or((SecretBackend::File, None), |(pattern, backend)| (*backend, Some(pattern.as_str())))
+    }
+
+    /// Every `(pattern, backend)` rule, most specific first
+    pub fn rules(&self) -> &[(String, SecretBackend)] {
+        &self.rules
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_longest_match_wins() {
+        let namespaces = Namespaces::new(&BTreeMap::from([
+            ("github/*".to_string(), SecretBackend::Keychain),
+            ("github/acme/*".to_string(), SecretBackend::File),
+            ("cargo/token".to_string(), SecretBackend::Keychain),
+        ]));
+
+        assert_eq!(namespaces.route("github/alice/token"), (SecretBackend::Keychain, Some("github/*")));
+        assert_eq!(namespaces.route("github/acme/token"), (SecretBackend::File, Some("github/acme/*")));
+        assert_eq!(namespaces.route("cargo/token"), (SecretBackend::Keychain, Some("cargo/token")));
+        assert_eq!(namespaces.route("cargo/token2"), (SecretBackend::File, None));
+        assert_eq!(Namespaces::empty().route("github/alice/token"), (SecretBackend::File, None));
+    }
+
+    #[test]
+    fn test_user_config_namespaces() {
+        let config: UserConfig = toml::from_str(
+            "[secrets.namespaces]\n\"github/*\" = \"keychain\"\n\"registry/*\" = \"file\"\n",
+        )
+        .unwrap();
+        let namespaces = Namespaces::new(&config.secrets.namespaces);
+        assert_eq!(namespaces.route("github/alice/token").0, SecretBackend::Keychain);
+        assert_eq!(namespaces.route("registry/npm").0, SecretBackend::File);
+    }
+
+    #[test]
+    fn test_load_reads_given_config_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("config.toml"),
+            "[secrets.namespaces]\n\"github/*\" = \"keychain\"\n",
+        )
+        .unwrap();
+        assert_eq!(Namespaces::load(dir.path()).route("github/alice/token").0, SecretBackend::Keychain);
+        let empty = tempfile::tempdir().unwrap();
+        assert_eq!(Namespaces::load(empty.path()).route("github/alice/token"), (SecretBackend::File, None));
+    }
+}
@@ -7,6 +7,8 @@ use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use thiserror::Error;
 
+use super::encryption::{read_plaintext, Envelope, StorageFormat};
+use super::namespaces::SecretBackend;
 use super::types::{Secret, SecretInfo, SecretPath};
 
 #[derive(Debug, Error)]
@@ -22,6 +24,9 @@ pub enum StorageError {
 
     #[error("Lock poisoned: {0}")]
     LockPoisoned(String),
+
+    #[error("Encryption error: {0}")]
+    Encryption(String),
 }
 
 pub type StorageResult<T> = Result<T, StorageError>;
@@ -35,13 +40,28 @@ struct StorageYaml {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct SecretEntry {
+    /// Empty for entries whose value is pinned to the keychain
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     value: String,
+    /// Set by `migrate_secrets`: the backend holding the value, whatever
+    /// `[secrets.namespaces]` says. Unpinned entries follow the namespaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    backend: Option<SecretBackend>,
     #[serde(skip_serializing_if = "Option::is_none")]
     created_at: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     updated_at: Option<DateTime<Utc>>,
 }
 
+/// How the file is encrypted, if at all
+#[derive(Debug, Clone, Default)]
+struct Encryption {
+    /// Set: the file is written encrypted with this passphrase
+    passphrase: Option<String>,
+    /// scrypt salt of the file on disk, reused across saves
+    salt: Option<Vec<u8>>,
+}
+
 /// YAML-based secret storage
 #[derive(Clone)]
 pub struct YamlStorage {
@@ -49,6 +69,7 @@ pub struct YamlStorage {
     file_path: PathBuf,
     /// In-memory cache of secrets
     secrets: Arc<RwLock<HashMap<String, SecretEntry>>>,
+    encryption: Arc<RwLock<Encryption>>,
 }
 
 impl YamlStorage {
@@ -57,9 +78,35 @@ impl YamlStorage {
         Self {
             file_path,
             secrets: Arc::new(RwLock::new(HashMap::new())),
+            encryption: Arc::new(RwLock::new(Encryption::default())),
         }
     }
 
+    /// Read and write the file encrypted with `passphrase`
+    #[must_use]
+    pub fn with_passphrase(mut self, passphrase: impl Into<String>) -> Self {
+        self.encryption = Arc::new(RwLock::new(Encryption { passphrase: Some(passphrase.into()), salt: None }));
+        self
+    }
+
+    /// Switch how the next save writes the file: encrypted with
+    /// `passphrase`, or plaintext for `None`
+    pub fn set_passphrase(&self, passphrase: Option<String>) -> StorageResult<()> {
+        let mut encryption = self.encryption.write().map_err(|e| {
+            StorageError::LockPoisoned(e.to_string())
+        })?;
+        *encryption = Encryption { passphrase, salt: None };
+        Ok(())
+    }
+
+    /// How saves write the file
+    pub fn format(&self) -> StorageResult<StorageFormat> {
+        let encryption = self.encryption.read().map_err(|e| {
+            StorageError::LockPoisoned(e.to_string())
+        })?;
+        Ok(if encryption.passphrase.is_some() { StorageFormat::Encrypted } else { StorageFormat::Plaintext })
+    }
+
     /// Create storage at default location (~/.config/hyperforge/secrets.yaml)
     pub fn default_location() -> StorageResult<Self> {
         let config_dir = dirs::home_dir()
@@ -80,13 +127,32 @@ impl YamlStorage {
         }
 
         let content = tokio::fs::read_to_string(&self.file_path).await?;
-        let storage: StorageYaml = serde_yaml::from_str(&content)?;
+        let envelope = Envelope::parse(&content);
+        let plaintext = {
+            let mut encryption = self.encryption.write().map_err(|e| {
+                StorageError::LockPoisoned(e.to_string())
+            })?;
+            let plaintext = read_plaintext(&content, encryption.passphrase.as_deref()).map_err(StorageError::Encryption)?;
+            if let Some(envelope) = &envelope {
+                encryption.salt = Some(envelope.salt().map_err(StorageError::Encryption)?);
+            }
+            plaintext
+        };
+        let storage: StorageYaml = serde_yaml::from_str(&plaintext)?;
 
-        let mut secrets = self.secrets.write().map_err(|e| {
-            StorageError::LockPoisoned(e.to_string())
-        })?;
+        {
+            let mut secrets = self.secrets.write().map_err(|e| {
+                StorageError::LockPoisoned(e.to_string())
+            })?;
+
+            *secrets = storage.secrets;
+        }
 
-        *secrets = storage.secrets;
+        // Encryption configured over a plaintext file: rewrite it encrypted
+        if envelope.is_none() && self.format()? == StorageFormat::Encrypted {
+            tracing::info!(path = %self.file_path.display(), "encrypting plaintext secrets file");
+            self.save().await?;
+        }
 
         Ok(())
     }
@@ -105,7 +171,17 @@ impl YamlStorage {
             secrets: secrets_clone,
         };
 
-        let yaml = serde_yaml::to_string(&storage)?;
+        let mut yaml = serde_yaml::to_string(&storage)?;
+        {
+            let mut encryption = self.encryption.write().map_err(|e| {
+                StorageError::LockPoisoned(e.to_string())
+            })?;
+            if let Some(passphrase) = encryption.passphrase.clone() {
+                let envelope = Envelope::seal(&yaml, &passphrase, encryption.salt.as_deref()).map_err(StorageError::Encryption)?;
+                encryption.salt = Some(envelope.salt().map_err(StorageError::Encryption)?);
+                yaml = envelope.to_yaml().map_err(StorageError::Encryption)?;
+            }
+        }
 
         // Ensure parent directory exists
         if let Some(parent) = self.file_path.parent() {
@@ -141,17 +217,20 @@ impl YamlStorage {
     pub async fn set(&self, secret: Secret) -> StorageResult<()> {
         let now = Utc::now();
 
-        let entry = SecretEntry {
-            value: secret.value,
-            created_at: secret.created_at.or(Some(now)),
-            updated_at: Some(now),
-        };
-
         {
             let mut secrets = self.secrets.write().map_err(|e| {
                 StorageError::LockPoisoned(e.to_string())
             })?;
 
+            // Keep an existing entry's pin and creation time
+            let existing = secrets.get(secret.path.as_str());
+            let entry = SecretEntry {
+                value: secret.value,
+                backend: existing.and_then(|e| e.backend),
+                created_at: existing.and_then(|e| e.created_at).or(secret.created_at).or(Some(now)),
+                updated_at: Some(now),
+            };
+
             secrets.insert(secret.path.to_string(), entry);
         }
 
@@ -161,6 +240,28 @@ impl YamlStorage {
         Ok(())
     }
 
+    /// Write an entry exactly as given, timestamps included, pinned to
+    /// `backend`. Keychain-pinned entries keep no value, only the path and
+    /// timestamps.
+    pub async fn restore(&self, secret: Secret, backend: SecretBackend) -> StorageResult<()> {
+        let entry = SecretEntry {
+            value: if backend == SecretBackend::File { secret.value } else { String::new() },
+            backend: Some(backend),
+            created_at: secret.created_at,
+            updated_at: secret.updated_at,
+        };
+
+        {
+            let mut secrets = self.secrets.write().map_err(|e| {
+                StorageError::LockPoisoned(e.to_string())
+            })?;
+
+            secrets.insert(secret.path.to_string(), entry);
+        }
+
+        self.save().await
+    }
+
     /// Delete a secret
     pub async fn delete(&self, path: &SecretPath) -> StorageResult<()> {
         {
@@ -193,6 +294,7 @@ impl YamlStorage {
             if path_str.starts_with(prefix) {
                 result.push(SecretInfo {
                     path: SecretPath::new(path_str.clone()),
+                    backend: entry.backend,
                     created_at: entry.created_at,
                     updated_at: entry.updated_at,
                 });
@@ -210,6 +312,15 @@ impl YamlStorage {
 
         Ok(secrets.contains_key(path.as_str()))
     }
+
+    /// The backend an entry is pinned to, if it exists and is pinned
+    pub fn pinned_backend(&self, path: &SecretPath) -> StorageResult<Option<SecretBackend>> {
+        let secrets = self.secrets.read().map_err(|e| {
+            StorageError::LockPoisoned(e.to_string())
+        })?;
+
+        Ok(secrets.get(path.as_str()).and_then(|e| e.backend))
+    }
 }
 
 #[cfg(test)]
@@ -292,4 +403,54 @@ mod tests {
         let alice_secrets = storage.list("").unwrap();
         assert_eq!(alice_secrets.len(), 3);
     }
+
+    #[tokio::test]
+    async fn test_storage_encrypts_plaintext_file_on_load() {
+        let temp = TempDir::new().unwrap();
+        let file_path = temp.path().join("secrets.yaml");
+        YamlStorage::new(file_path.clone()).set(Secret::new("github/alice/token", "ghp_xxx")).await.unwrap();
+
+        let storage = YamlStorage::new(file_path.clone()).with_passphrase("correct horse");
+        storage.load().await.unwrap();
+        assert!(!std::fs::read_to_string(&file_path).unwrap().contains("ghp_xxx"));
+
+        // Readable with the passphrase only
+        let reopened = YamlStorage::new(file_path.clone()).with_passphrase("correct horse");
+        reopened.load().await.unwrap();
+        assert_eq!(reopened.get(&SecretPath::new("github/alice/token")).unwrap().value, "ghp_xxx");
+        assert!(YamlStorage::new(file_path.clone()).load().await.is_err());
+        assert!(YamlStorage::new(file_path.clone()).with_passphrase("wrong").load().await.is_err());
+
+        // And back to plaintext
+        reopened.set_passphrase(None).unwrap();
+        reopened.save().await.unwrap();
+        assert!(std::fs::read_to_string(&file_path).unwrap().contains("ghp_xxx"));
+    }
+
+    #[tokio::test]
+    async fn test_storage_restore_keeps_timestamps_and_pin() {
+        let temp = TempDir::new().unwrap();
+        let file_path = temp.path().join("secrets.yaml");
+        let storage = YamlStorage::new(file_path.clone());
+
+        let created = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let updated = DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let secret = Secret::with_timestamps("github/alice/token", "ghp_xxx", Some(created), Some(updated));
+        storage.restore(secret, SecretBackend::Keychain).await.unwrap();
+
+        // Only the path, pin and timestamps reach the file
+        let content = std::fs::read_to_string(&file_path).unwrap();
+        assert!(!content.contains("ghp_xxx"));
+        let storage2 = YamlStorage::new(file_path);
+        storage2.load().await.unwrap();
+        let path = SecretPath::new("github/alice/token");
+        assert_eq!(storage2.pinned_backend(&path).unwrap(), Some(SecretBackend::Keychain));
+        let info = &storage2.list("github/").unwrap()[0];
+        assert_eq!((info.created_at, info.updated_at), (Some(created), Some(updated)));
+
+        // A later set keeps the pin and the creation time
+        storage2.set(Secret::new("github/alice/token", "")).await.unwrap();
+        assert_eq!(storage2.pinned_backend(&path).unwrap(), Some(SecretBackend::Keychain));
+        assert_eq!(storage2.list("github/").unwrap()[0].created_at, Some(created));
+    }
 }
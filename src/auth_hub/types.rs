@@ -3,6 +3,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::namespaces::SecretBackend;
+
 /// Hierarchical secret path: forge/org/key or registry/token
 ///
 /// Examples:
@@ -45,6 +47,17 @@ impl SecretPath {
     pub fn segments(&self) -> Vec<&str> {
         self.path.split('/').collect()
     }
+
+    /// The forge-wide path an org-scoped token falls back to:
+    /// `<forge>/<org>/token` → `<forge>/token` (likewise `*_token` names)
+    pub fn forge_wide(&self) -> Option<Self> {
+        match self.segments().as_slice() {
+            [forge, org, name] if !forge.is_empty() && !org.is_empty() && name.ends_with("token") => {
+                Some(Self::new(format!("{forge}/{name}")))
+            }
+            _ => None,
+        }
+    }
 }
 
 impl From<String> for SecretPath {
@@ -106,6 +119,8 @@ impl Secret {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecretInfo {
     pub path: SecretPath,
+    /// Backend the entry is pinned to by a migration, if any
+    pub backend: Option<SecretBackend>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
@@ -114,6 +129,7 @@ impl From<&Secret> for SecretInfo {
     fn from(secret: &Secret) -> Self {
         Self {
             path: secret.path.clone(),
+            backend: None,
             created_at: secret.created_at,
             updated_at: secret.updated_at,
         }
@@ -124,6 +140,16 @@ impl From<&Secret> for SecretInfo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_forge_wide_fallback() {
+        assert_eq!(SecretPath::new("github/alice/token").forge_wide().unwrap().as_str(), "github/token");
+        assert_eq!(SecretPath::new("github/alice/packages_token").forge_wide().unwrap().as_str(), "github/packages_token");
+        assert_eq!(SecretPath::new("git.acme.dev/ops/token").forge_wide().unwrap().as_str(), "git.acme.dev/token");
+        assert!(SecretPath::new("github/token").forge_wide().is_none());
+        assert!(SecretPath::new("github/alice/app_id").forge_wide().is_none());
+        assert!(SecretPath::new("github//token").forge_wide().is_none());
+    }
+
     #[test]
     fn test_secret_path_from_parts() {
         let path = SecretPath::from_parts("github", "alice", "token");
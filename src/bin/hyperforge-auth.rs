@@ -11,6 +11,7 @@ use clap::Parser;
 use plexus_core::plexus::DynamicHub;
 use plexus_transport::TransportServer;
 use hyperforge::auth_hub::AuthHub;
+use hyperforge::hubs::HyperforgeState;
 use std::sync::Arc;
 use tracing::{info, error};
 
@@ -36,10 +37,11 @@ async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
     info!("Starting Hyperforge Auth Hub");
-    info!("Storage: ~/.config/hyperforge/secrets.yaml");
+    let config_dir = HyperforgeState::default_config_dir();
+    info!("Storage: {}", config_dir.join("secrets.yaml").display());
 
     // Create auth hub
-    let auth_hub = match AuthHub::new().await {
+    let auth_hub = match AuthHub::new(&config_dir).await {
         Ok(hub) => hub,
         Err(e) => {
             error!("Failed to create auth hub: {}", e);
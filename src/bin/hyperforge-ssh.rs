@@ -1,8 +1,10 @@
 //! hyperforge-ssh — SSH wrapper that selects the correct identity key per forge
 //!
 //! Git invokes: `hyperforge-ssh <hostname> <git-upload-pack 'org/repo.git'>`
-//! We map hostname -> forge, walk up to find .hyperforge/config.toml -> read org,
-//! then look up the SSH key from ~/.config/hyperforge/orgs/{org}.toml.
+//! We map hostname -> forge, take the org from the repo path (so a second
+//! account's remote, e.g. `github:acme`, gets acme's key), else walk up to
+//! find .hyperforge/config.toml -> read org, then look up the SSH key from
+//! ~/.config/hyperforge/orgs/{org}.toml.
 //! Falls back to plain `ssh` if anything goes wrong.
 
 use std::env;
@@ -35,7 +37,16 @@ fn find_ssh_key(ssh_args: &[&str]) -> Option<String> {
     let hostname = ssh_args.first()?;
 
     // Map hostname to forge name
-    let forge_name = hostname_to_forge(hostname)?;
+    let forge_name = hostname_to_forge(hostname);
+
+    let orgs_dir = dirs::home_dir()?.join(".config").join("hyperforge").join("orgs");
+
+    // The org being accessed, when it has a key of its own
+    if let Some(org) = ssh_args.last().and_then(|cmd| org_from_command(cmd)) {
+        if let Some(key) = read_ssh_key_from_org_config(&orgs_dir.join(format!("{org}.toml")), &forge_name) {
+            return Some(key);
+        }
+    }
 
     // Walk up from CWD to find .hyperforge/config.toml
     let cwd = env::current_dir().ok()?;
@@ -45,31 +56,35 @@ fn find_ssh_key(ssh_args: &[&str]) -> Option<String> {
     let org = read_org_from_config(&config)?;
 
     // Read SSH key from org config: ~/.config/hyperforge/orgs/{org}.toml
-    let org_config_path = dirs::home_dir()?
-        .join(".config")
-        .join("hyperforge")
-        .join("orgs")
-        .join(format!("{org}.toml"));
+    read_ssh_key_from_org_config(&orgs_dir.join(format!("{org}.toml")), &forge_name)
+}
 
-    read_ssh_key_from_org_config(&org_config_path, &forge_name)
+/// Org from git's remote command, e.g. `git-upload-pack '/acme/tool.git'`
+fn org_from_command(command: &str) -> Option<&str> {
+    let (_, path) = command.split_once(' ')?;
+    let path = path.trim().trim_matches('\'').trim_start_matches('/');
+    let (org, _) = path.split_once('/')?;
+    (!org.is_empty()).then_some(org)
 }
 
 /// Map SSH hostname to forge name
-fn hostname_to_forge(hostname: &str) -> Option<String> {
+fn hostname_to_forge(hostname: &str) -> String {
     match hostname {
-        "github.com" => Some("github".to_string()),
-        "codeberg.org" => Some("codeberg".to_string()),
-        "gitlab.com" => Some("gitlab".to_string()),
+        "github.com" => "github".to_string(),
+        "codeberg.org" => "codeberg".to_string(),
+        "gitlab.com" => "gitlab".to_string(),
         _ => {
             // Check for custom hostnames containing forge names
             if hostname.contains("github") {
-                Some("github".to_string())
+                "github".to_string()
             } else if hostname.contains("codeberg") {
-                Some("codeberg".to_string())
+                "codeberg".to_string()
             } else if hostname.contains("gitlab") {
-                Some("gitlab".to_string())
+                "gitlab".to_string()
             } else {
-                None
+                // Self-hosted Gitea/Forgejo forges are named by hostname
+                let host = hostname.rsplit_once('@').map_or(hostname, |(_, h)| h);
+                host.to_lowercase()
             }
         }
     }
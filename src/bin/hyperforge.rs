@@ -1,9 +1,13 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use plexus_core::plexus::DynamicHub;
 use plexus_transport::TransportServer;
 use hyperforge::HyperforgeHub;
 use hyperforge::auth_hub::AuthHub;
 use hyperforge::registry::{RegistryClient, RegistryConfig};
+use hyperforge::output::{write_ndjson, OutputFormat};
+use hyperforge::render::{render_lines, ColorChoice, Renderer};
+use std::io::IsTerminal;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// CLI arguments for hyperforge standalone server
@@ -38,6 +42,34 @@ struct Args {
     /// Name to register as in the registry
     #[arg(long, default_value = "lforge")]
     registry_name: String,
+
+    /// Render newline-delimited JSON events from stdin for a terminal instead
+    /// of starting the server
+    #[arg(long)]
+    render: bool,
+
+    /// Colorize rendered output: auto, always, or never (auto honors `NO_COLOR`)
+    #[arg(long, default_value = "auto")]
+    color: ColorChoice,
+
+    /// With --render: `text` for a terminal, or `json` to pass through only
+    /// typed events as NDJSON (no `info` narration)
+    #[arg(long, default_value = "text")]
+    format: OutputFormat,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Interactive shell over one workspace, with history, tab completion of
+    /// methods and repo names, and a remembered org and filter
+    Shell {
+        /// Workspace directory
+        #[arg(long, default_value = ".")]
+        path: PathBuf,
+    },
 }
 
 #[tokio::main]
@@ -45,6 +77,27 @@ async fn main() -> anyhow::Result<()> {
     // Parse CLI arguments
     let args = Args::parse();
 
+    if let Some(Command::Shell { path }) = &args.command {
+        let no_color = std::env::var("NO_COLOR").ok();
+        let color = args
+            .color
+            .enabled(std::io::stdout().is_terminal(), no_color.as_deref());
+        return hyperforge::shell::run(path, color).await.map_err(anyhow::Error::msg);
+    }
+
+    if args.render && args.format == OutputFormat::Json {
+        write_ndjson(std::io::stdin().lock(), std::io::stdout().lock())?;
+        return Ok(());
+    }
+    if args.render {
+        let no_color = std::env::var("NO_COLOR").ok();
+        let color = args
+            .color
+            .enabled(std::io::stdout().is_terminal(), no_color.as_deref());
+        render_lines(std::io::stdin().lock(), std::io::stdout().lock(), &mut Renderer::new(color))?;
+        return Ok(());
+    }
+
     // Initialize tracing with filtering
     // Logging targets (filterable via RUST_LOG):
     //   hyperforge                       — top-level startup/shutdown
@@ -81,10 +134,16 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("Starting hyperforge at {}", chrono::Utc::now());
 
+    let hub = HyperforgeHub::new();
+    let config_dir = hub.config_dir().to_path_buf();
+
+    // Passive "new version available" check (`[upgrade] check_on_startup`)
+    hyperforge::services::upgrade::spawn_startup_check(config_dir.clone());
+
     // Create lforge hub (DynamicHub with "lforge" namespace, hyperforge activation registered)
     let lforge = Arc::new(
         DynamicHub::new("lforge")
-            .register(HyperforgeHub::new())
+            .register(hub)
     );
 
     // Log activation info
@@ -127,7 +186,7 @@ async fn main() -> anyhow::Result<()> {
     // Auto-start secrets sidecar in-process on port + 1
     let secrets_handle = if !args.no_secrets && !args.stdio {
         let secrets_port = args.port + 1;
-        match AuthHub::new().await {
+        match AuthHub::new(&config_dir).await {
             Ok(auth_hub) => {
                 let secrets = Arc::new(
                     DynamicHub::new("secrets")
@@ -0,0 +1,205 @@
+//! Benchmark baselines for `workspace bench`
+//!
+//! Each repo's `[ci.bench].command` output is parsed into named timings
+//! (libtest `bench:` lines or criterion `time: [..]` lines, in nanoseconds;
+//! a command with neither is timed as a whole). Runs are stored per repo in
+//! `<workspace>/.hyperforge/bench/<repo>.toml`, keyed by commit, and each run
+//! is compared against the latest baseline from a different commit.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const BENCH_DIR: &str = "bench";
+
+/// Baselines kept per repo
+pub const MAX_RUNS: usize = 20;
+
+/// Metric recorded for a command that prints no recognizable benchmarks
+pub const WALL_CLOCK: &str = "wall_clock";
+
+/// One benchmark run of a repo
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchRun {
+    pub commit: String,
+    pub run_at: DateTime<Utc>,
+    /// Benchmark name → nanoseconds (lower is better)
+    pub results: BTreeMap<String, f64>,
+}
+
+/// Stored runs for one repo, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BenchHistory {
+    #[serde(default)]
+    pub runs: Vec<BenchRun>,
+}
+
+/// A benchmark compared against its baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchDelta {
+    pub name: String,
+    pub baseline_ns: Option<f64>,
+    pub current_ns: f64,
+    pub change_pct: Option<f64>,
+    pub regressed: bool,
+}
+
+impl BenchHistory {
+    pub fn path(workspace_root: &Path, repo_name: &str) -> PathBuf {
+        workspace_root
+            .join(crate::config::CONFIG_DIR)
+            .join(BENCH_DIR)
+            .join(format!("{repo_name}.toml"))
+    }
+
+    pub fn load(workspace_root: &Path, repo_name: &str) -> Result<Self, String> {
+        let path = Self::path(workspace_root, repo_name);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        toml::from_str(&content).map_err(|e| format!("Failed to parse {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, workspace_root: &Path, repo_name: &str) -> Result<(), String> {
+        let path = Self::path(workspace_root, repo_name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize bench history: {e}"))?;
+        std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    /// Latest run from a commit other than `commit`
+    pub fn baseline_for(&self, commit: &str) -> Option<&BenchRun> {
+        self.runs.iter().rev().find(|r| r.commit != commit)
+    }
+
+    /// Record a run, replacing any earlier run of the same commit and keeping
+    /// the newest [`MAX_RUNS`].
+    pub fn record(&mut self, run: BenchRun) {
+        self.runs.retain(|r| r.commit != run.commit);
+        self.runs.push(run);
+        let excess = self.runs.len().saturating_sub(MAX_RUNS);
+        self.runs.drain(..excess);
+    }
+}
+
+/// Nanoseconds for a value with a time unit (`ns`, `µs`/`us`, `ms`, `s`)
+fn to_ns(value: &str, unit: &str) -> Option<f64> {
+    let value: f64 = value.replace(',', "").parse().ok()?;
+    let scale = match unit.trim_end_matches("/iter") {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    Some(value * scale)
+}
+
+/// Parse libtest (`test name ... bench: 1,234 ns/iter (+/- 5)`) and criterion
+/// (`name  time: [1.1 ms 1.2 ms 1.3 ms]`, name possibly on the line before)
+/// output. Criterion's middle estimate is used.
+pub fn parse_results(output: &str) -> BTreeMap<String, f64> {
+    let mut results = BTreeMap::new();
+    let mut last_line = "";
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some((head, tail)) = trimmed.split_once(" bench:") {
+            let name = head.trim_start_matches("test ").trim_end_matches("...").trim();
+            let mut parts = tail.split_whitespace();
+            if let (Some(v), Some(unit)) = (parts.next(), parts.next()) {
+                if let Some(ns) = to_ns(v, unit) {
+                    results.insert(name.to_string(), ns);
+                }
+            }
+        } else if let Some((head, tail)) = trimmed.split_once("time:") {
+            let name = if head.trim().is_empty() { last_line } else { head.trim() };
+            let values: Vec<&str> = tail.trim().trim_matches(['[', ']']).split_whitespace().collect();
+            if let (false, [_, _, v, unit, ..]) = (name.is_empty(), values.as_slice()) {
+                if let Some(ns) = to_ns(v, unit) {
+                    results.insert(name.to_string(), ns);
+                }
+            }
+        }
+        if !trimmed.is_empty() {
+            last_line = trimmed;
+        }
+    }
+    results
+}
+
+/// Compare a run against its baseline; a benchmark regresses when it got
+/// slower by more than `threshold_pct`.
+pub fn compare(baseline: Option<&BenchRun>, current: &BenchRun, threshold_pct: f64) -> Vec<BenchDelta> {
+    current
+        .results
+        .iter()
+        .map(|(name, &current_ns)| {
+            let baseline_ns = baseline.and_then(|b| b.results.get(name).copied());
+            let change_pct = baseline_ns
+                .filter(|b| *b > 0.0)
+                .map(|b| (current_ns - b) * 100.0 / b);
+            BenchDelta {
+                name: name.clone(),
+                baseline_ns,
+                current_ns,
+                change_pct,
+                regressed: change_pct.is_some_and(|c| c > threshold_pct),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(commit: &str, results: &[(&str, f64)]) -> BenchRun {
+        BenchRun {
+            commit: commit.to_string(),
+            run_at: Utc::now(),
+            results: results.iter().map(|(n, v)| ((*n).to_string(), *v)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_libtest_and_criterion() {
+        let output = "\
+test parse_small ... bench:       1,234 ns/iter (+/- 56)
+test parse_large ... bench:   2.50 µs/iter (+/- 0.1)
+encode/short            time:   [1.0000 ms 1.2000 ms 1.4000 ms]
+decode/long
+                        time:   [10.0 us 12.0 us 14.0 us]
+";
+        let results = parse_results(output);
+        assert_eq!(results.len(), 4);
+        assert!((results["parse_small"] - 1234.0).abs() < 1e-6);
+        assert!((results["parse_large"] - 2500.0).abs() < 1e-6);
+        assert!((results["encode/short"] - 1.2e6).abs() < 1e-6);
+        assert!((results["decode/long"] - 12_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_compare_and_record() {
+        let mut history = BenchHistory::default();
+        history.record(run("aaa", &[("encode", 100.0), ("decode", 100.0)]));
+        let current = run("bbb", &[("encode", 125.0), ("decode", 105.0), ("new", 1.0)]);
+
+        let deltas = compare(history.baseline_for("bbb"), &current, 10.0);
+        let regressed: Vec<&str> = deltas.iter().filter(|d| d.regressed).map(|d| d.name.as_str()).collect();
+        assert_eq!(regressed, vec!["encode"]);
+        assert!(deltas.iter().any(|d| d.name == "new" && d.baseline_ns.is_none()));
+
+        // Re-running a commit replaces its run instead of becoming its own baseline
+        history.record(current.clone());
+        history.record(current);
+        assert_eq!(history.runs.len(), 2);
+        assert_eq!(history.baseline_for("bbb").unwrap().commit, "aaa");
+    }
+}
@@ -183,6 +183,7 @@ fn parse_dep_list(
                 is_path_dep: false,
                 path: None,
                 is_dev: false,
+                is_optional: false,
             });
         }
     }
@@ -5,12 +5,16 @@
 
 use std::path::Path;
 
+use super::generated::{self, Drift, DriftPolicy, Reconcile};
+
 /// Report from cabal project generation
 #[derive(Debug, Clone)]
 pub struct CabalProjectReport {
     pub packages: Vec<String>,
     pub content: String,
     pub action: FileAction,
+    /// Set when cabal.project was edited by hand since it was last generated
+    pub drift: Option<Drift>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -18,6 +22,8 @@ pub enum FileAction {
     Created,
     Updated,
     Unchanged,
+    Merged,
+    Conflict,
 }
 
 /// Information about a Haskell package in the workspace
@@ -32,6 +38,16 @@ pub fn generate_cabal_project(
     workspace_root: &Path,
     packages: &[CabalPackageInfo],
     dry_run: bool,
+) -> Result<CabalProjectReport, String> {
+    generate_cabal_project_with(workspace_root, packages, dry_run, DriftPolicy::default())
+}
+
+/// Like [`generate_cabal_project`], with an explicit policy for hand-edited files.
+pub fn generate_cabal_project_with(
+    workspace_root: &Path,
+    packages: &[CabalPackageInfo],
+    dry_run: bool,
+    policy: DriftPolicy,
 ) -> Result<CabalProjectReport, String> {
     if packages.is_empty() {
         return Err("No Haskell packages found in workspace".to_string());
@@ -49,38 +65,23 @@ pub fn generate_cabal_project(
         content.push_str(&format!("  {pkg}/\n"));
     }
 
-    let project_path = workspace_root.join("cabal.project");
-
-    let action = if !dry_run {
-        if project_path.exists() {
-            let existing = std::fs::read_to_string(&project_path).unwrap_or_default();
-            if existing == content {
-                FileAction::Unchanged
-            } else {
-                std::fs::write(&project_path, &content)
-                    .map_err(|e| format!("Failed to write cabal.project: {e}"))?;
-                FileAction::Updated
-            }
-        } else {
-            std::fs::write(&project_path, &content)
-                .map_err(|e| format!("Failed to write cabal.project: {e}"))?;
-            FileAction::Created
-        }
-    } else if project_path.exists() {
-        let existing = std::fs::read_to_string(&project_path).unwrap_or_default();
-        if existing == content {
-            FileAction::Unchanged
-        } else {
-            FileAction::Updated
-        }
-    } else {
-        FileAction::Created
+    let reconciled = generated::reconcile(workspace_root, "cabal.project", &content, policy)?;
+    if !dry_run {
+        generated::apply(workspace_root, "cabal.project", &reconciled, &content)?;
+    }
+    let action = match reconciled.action {
+        Reconcile::Created => FileAction::Created,
+        Reconcile::Updated => FileAction::Updated,
+        Reconcile::Unchanged => FileAction::Unchanged,
+        Reconcile::Merged => FileAction::Merged,
+        Reconcile::Conflict => FileAction::Conflict,
     };
 
     Ok(CabalProjectReport {
         packages: pkg_paths,
         content,
         action,
+        drift: reconciled.drift,
     })
 }
 
@@ -112,4 +113,37 @@ mod tests {
         assert!(report.content.contains("  synapse/"));
         assert_eq!(report.action, FileAction::Created);
     }
+
+    #[test]
+    fn test_hand_edited_project_is_not_overwritten() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut packages = vec![CabalPackageInfo {
+            name: "synapse".to_string(),
+            path: "synapse".to_string(),
+        }];
+        generate_cabal_project(tmp.path(), &packages, false).unwrap();
+
+        let project_path = tmp.path().join("cabal.project");
+        let edited = format!(
+            "{}\nallow-newer: all\n",
+            std::fs::read_to_string(&project_path).unwrap()
+        );
+        std::fs::write(&project_path, &edited).unwrap();
+
+        packages.push(CabalPackageInfo {
+            name: "plexus-protocol".to_string(),
+            path: "plexus-protocol".to_string(),
+        });
+        let report = generate_cabal_project(tmp.path(), &packages, false).unwrap();
+        assert_eq!(report.action, FileAction::Conflict);
+        assert!(report.drift.is_some());
+        assert_eq!(std::fs::read_to_string(&project_path).unwrap(), edited);
+
+        let report =
+            generate_cabal_project_with(tmp.path(), &packages, false, DriftPolicy::Merge).unwrap();
+        assert_eq!(report.action, FileAction::Merged);
+        let merged = std::fs::read_to_string(&project_path).unwrap();
+        assert!(merged.contains("plexus-protocol/"));
+        assert!(merged.contains("allow-newer: all"));
+    }
 }
@@ -191,6 +191,7 @@ fn parse_dep_table(table: &toml::map::Map<String, toml::Value>, is_dev: bool) ->
                     is_path_dep: false,
                     path: None,
                     is_dev,
+                    is_optional: false,
                 });
             }
             // Table form: dep = { version = "1.0", path = "../dep", ... }
@@ -198,6 +199,7 @@ fn parse_dep_table(table: &toml::map::Map<String, toml::Value>, is_dev: bool) ->
                 let version = t.get("version").and_then(|v| v.as_str()).map(std::string::ToString::to_string);
                 let dep_path = t.get("path").and_then(|v| v.as_str()).map(std::string::ToString::to_string);
                 let is_path = dep_path.is_some();
+                let is_optional = t.get("optional").and_then(toml::Value::as_bool).unwrap_or(false);
 
                 deps.push(DepRef {
                     name: name.clone(),
@@ -205,6 +207,7 @@ fn parse_dep_table(table: &toml::map::Map<String, toml::Value>, is_dev: bool) ->
                     is_path_dep: is_path,
                     path: dep_path,
                     is_dev,
+                    is_optional,
                 });
             }
             _ => {}
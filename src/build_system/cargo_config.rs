@@ -6,6 +6,7 @@
 
 use std::path::Path;
 
+use super::generated::{self, Drift, DriftPolicy, Reconcile};
 use super::DepRef;
 
 /// Report from cargo config generation
@@ -15,6 +16,8 @@ pub struct CargoConfigReport {
     pub content: String,
     pub action: FileAction,
     pub cleanup: Vec<(String, FileAction)>, // (relative description, action taken)
+    /// Set when .cargo/config.toml was edited by hand since it was last generated
+    pub drift: Option<Drift>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,6 +26,8 @@ pub enum FileAction {
     Updated,
     Unchanged,
     Removed,
+    Merged,
+    Conflict,
 }
 
 /// Information about a Rust crate in the workspace
@@ -44,6 +49,16 @@ pub fn generate_cargo_config(
     workspace_root: &Path,
     crates: &[CrateInfo],
     dry_run: bool,
+) -> Result<CargoConfigReport, String> {
+    generate_cargo_config_with(workspace_root, crates, dry_run, DriftPolicy::default())
+}
+
+/// Like [`generate_cargo_config`], with an explicit policy for hand-edited files.
+pub fn generate_cargo_config_with(
+    workspace_root: &Path,
+    crates: &[CrateInfo],
+    dry_run: bool,
+    policy: DriftPolicy,
 ) -> Result<CargoConfigReport, String> {
     if crates.is_empty() {
         return Err("No Rust crates found in workspace".to_string());
@@ -70,8 +85,7 @@ pub fn generate_cargo_config(
     patches.sort_by(|a, b| a.0.cmp(&b.0));
 
     // Build the [patch.crates-io] content, merging into existing config if present
-    let config_dir = workspace_root.join(".cargo");
-    let config_path = config_dir.join("config.toml");
+    let config_path = workspace_root.join(".cargo").join("config.toml");
 
     let mut doc = if config_path.exists() {
         let existing = std::fs::read_to_string(&config_path)
@@ -105,33 +119,16 @@ pub fn generate_cargo_config(
     let content = doc.to_string();
 
     // Determine action for .cargo/config.toml
-    let action = if !dry_run {
-        std::fs::create_dir_all(&config_dir)
-            .map_err(|e| format!("Failed to create .cargo/ directory: {e}"))?;
-
-        if config_path.exists() {
-            let existing = std::fs::read_to_string(&config_path).unwrap_or_default();
-            if existing == content {
-                FileAction::Unchanged
-            } else {
-                std::fs::write(&config_path, &content)
-                    .map_err(|e| format!("Failed to write .cargo/config.toml: {e}"))?;
-                FileAction::Updated
-            }
-        } else {
-            std::fs::write(&config_path, &content)
-                .map_err(|e| format!("Failed to write .cargo/config.toml: {e}"))?;
-            FileAction::Created
-        }
-    } else if config_path.exists() {
-        let existing = std::fs::read_to_string(&config_path).unwrap_or_default();
-        if existing == content {
-            FileAction::Unchanged
-        } else {
-            FileAction::Updated
-        }
-    } else {
-        FileAction::Created
+    let reconciled = generated::reconcile(workspace_root, ".cargo/config.toml", &content, policy)?;
+    if !dry_run {
+        generated::apply(workspace_root, ".cargo/config.toml", &reconciled, &content)?;
+    }
+    let action = match reconciled.action {
+        Reconcile::Created => FileAction::Created,
+        Reconcile::Updated => FileAction::Updated,
+        Reconcile::Unchanged => FileAction::Unchanged,
+        Reconcile::Merged => FileAction::Merged,
+        Reconcile::Conflict => FileAction::Conflict,
     };
 
     // Cleanup old workspace artifacts
@@ -171,6 +168,7 @@ pub fn generate_cargo_config(
         content,
         action,
         cleanup,
+        drift: reconciled.drift,
     })
 }
 
@@ -233,6 +231,7 @@ mod tests {
                     is_path_dep: false,
                     path: None,
                     is_dev: false,
+                    is_optional: false,
                 }],
             },
         ];
@@ -265,6 +264,7 @@ mod tests {
                     is_path_dep: true,
                     path: Some("../crate-a".to_string()),
                     is_dev: false,
+                    is_optional: false,
                 }],
             },
         ];
@@ -294,6 +294,7 @@ mod tests {
                     is_path_dep: false,
                     path: None,
                     is_dev: false,
+                    is_optional: false,
                 }],
             },
         ];
@@ -366,6 +367,7 @@ mod tests {
                     is_path_dep: false,
                     path: None,
                     is_dev: false,
+                    is_optional: false,
                 }],
             },
         ];
@@ -0,0 +1,149 @@
+//! Coverage collection for `build validate --coverage`
+//!
+//! Each repo's coverage command runs in the validation container with a
+//! writable `/coverage` mount backed by
+//! `<workspace>/.hyperforge/coverage/<repo>/`, and must leave an LCOV report
+//! at `/coverage/lcov.info`. Reports are summarized per repo and concatenated
+//! into a workspace-wide `<workspace>/.hyperforge/coverage/lcov.info`.
+
+use std::path::{Path, PathBuf};
+
+use super::BuildSystemKind;
+
+const COVERAGE_DIR: &str = "coverage";
+const LCOV_FILE: &str = "lcov.info";
+
+/// Where the coverage dir is mounted inside the validation container
+pub const CONTAINER_DIR: &str = "/coverage";
+
+/// Line totals from an LCOV report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LcovSummary {
+    pub lines_found: u64,
+    pub lines_hit: u64,
+}
+
+impl LcovSummary {
+    /// Line coverage percentage; an empty report counts as fully covered
+    #[allow(clippy::cast_precision_loss)]
+    pub fn line_pct(self) -> f64 {
+        if self.lines_found == 0 {
+            return 100.0;
+        }
+        self.lines_hit as f64 * 100.0 / self.lines_found as f64
+    }
+
+    pub const fn merge(self, other: Self) -> Self {
+        Self {
+            lines_found: self.lines_found + other.lines_found,
+            lines_hit: self.lines_hit + other.lines_hit,
+        }
+    }
+}
+
+/// Sum the `LF`/`LH` records of an LCOV report
+pub fn parse_lcov(content: &str) -> LcovSummary {
+    let mut summary = LcovSummary::default();
+    for line in content.lines() {
+        if let Some(n) = line.strip_prefix("LF:") {
+            summary.lines_found += n.trim().parse::<u64>().unwrap_or(0);
+        } else if let Some(n) = line.strip_prefix("LH:") {
+            summary.lines_hit += n.trim().parse::<u64>().unwrap_or(0);
+        }
+    }
+    summary
+}
+
+/// Default coverage command for a repo's build system, writing LCOV to
+/// [`CONTAINER_DIR`]: cargo-llvm-cov for Cargo, jest (istanbul) for Node.
+pub fn default_command(build_systems: &[BuildSystemKind]) -> Option<Vec<String>> {
+    let to_vec = |args: &[&str]| args.iter().map(|a| (*a).to_string()).collect();
+    match build_systems.iter().find(|bs| **bs != BuildSystemKind::Unknown)? {
+        BuildSystemKind::Cargo => Some(to_vec(&[
+            "cargo",
+            "llvm-cov",
+            "--lcov",
+            "--output-path",
+            "/coverage/lcov.info",
+        ])),
+        BuildSystemKind::Node => Some(to_vec(&[
+            "npx",
+            "jest",
+            "--coverage",
+            "--coverageReporters=lcov",
+            "--coverageDirectory=/coverage",
+        ])),
+        BuildSystemKind::Cabal | BuildSystemKind::Unknown => None,
+    }
+}
+
+/// Host directory mounted at [`CONTAINER_DIR`] for one repo
+pub fn repo_dir(workspace_root: &Path, repo_name: &str) -> PathBuf {
+    workspace_root
+        .join(crate::config::CONFIG_DIR)
+        .join(COVERAGE_DIR)
+        .join(repo_name)
+}
+
+/// Empty the repo's coverage dir so a failed run can't report stale numbers
+pub fn reset_repo_dir(workspace_root: &Path, repo_name: &str) -> Result<PathBuf, String> {
+    let dir = repo_dir(workspace_root, repo_name);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to clear {}: {e}", dir.display()))?;
+    }
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+    Ok(dir)
+}
+
+/// The LCOV report a repo's coverage run left behind
+pub fn load_repo_report(workspace_root: &Path, repo_name: &str) -> Option<String> {
+    std::fs::read_to_string(repo_dir(workspace_root, repo_name).join(LCOV_FILE)).ok()
+}
+
+/// Write the concatenated workspace report, returning its path
+pub fn write_workspace_report(workspace_root: &Path, reports: &[String]) -> Result<PathBuf, String> {
+    let path = workspace_root
+        .join(crate::config::CONFIG_DIR)
+        .join(COVERAGE_DIR)
+        .join(LCOV_FILE);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    let mut content = String::new();
+    for report in reports {
+        content.push_str(report);
+        if !report.ends_with('\n') {
+            content.push('\n');
+        }
+    }
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = "TN:\nSF:/workspace/core/src/lib.rs\nDA:1,1\nDA:2,0\nLF:4\nLH:3\nend_of_record\n\
+                          SF:/workspace/core/src/main.rs\nLF:6\nLH:2\nend_of_record\n";
+
+    #[test]
+    fn test_parse_lcov_sums_records() {
+        let summary = parse_lcov(REPORT);
+        assert_eq!(summary, LcovSummary { lines_found: 10, lines_hit: 5 });
+        assert!((summary.line_pct() - 50.0).abs() < f64::EPSILON);
+        assert!((LcovSummary::default().line_pct() - 100.0).abs() < f64::EPSILON);
+
+        let merged = summary.merge(LcovSummary { lines_found: 10, lines_hit: 10 });
+        assert!((merged.line_pct() - 75.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_workspace_report_concatenates() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = write_workspace_report(temp.path(), &[REPORT.to_string(), "LF:2\nLH:2".to_string()]).unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        assert_eq!(parse_lcov(&content), LcovSummary { lines_found: 12, lines_hit: 7 });
+    }
+}
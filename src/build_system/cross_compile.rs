@@ -105,6 +105,83 @@ pub const APPLE_TARGETS: &[&str] = &[
     "aarch64-apple-darwin",
 ];
 
+/// The release matrix: (OS, arch, triple) for linux/macos/windows on
+/// `x86_64` and `aarch64`.
+///
+/// Windows uses the GNU ABIs, which `cross` can build from a Linux host.
+pub const RELEASE_MATRIX: &[(&str, &str, &str)] = &[
+    ("linux", "x86_64", "x86_64-unknown-linux-gnu"),
+    ("linux", "aarch64", "aarch64-unknown-linux-gnu"),
+    ("macos", "x86_64", "x86_64-apple-darwin"),
+    ("macos", "aarch64", "aarch64-apple-darwin"),
+    ("windows", "x86_64", "x86_64-pc-windows-gnu"),
+    ("windows", "aarch64", "aarch64-pc-windows-gnullvm"),
+];
+
+/// Name of the checksum file attached to releases alongside the archives
+pub const CHECKSUMS_FILE: &str = "SHA256SUMS";
+
+/// Expand target entries into triples.
+///
+/// An entry is a full triple, `all` (the whole [`RELEASE_MATRIX`]), an OS
+/// (`linux`, `macos`, `windows`), or `os/arch` (`linux/aarch64`).
+/// Duplicates are dropped, order is kept.
+pub fn expand_targets<S: AsRef<str>>(entries: &[S]) -> Vec<TargetTriple> {
+    let mut triples: Vec<TargetTriple> = Vec::new();
+    for entry in entries {
+        let entry = entry.as_ref().trim();
+        let (os, arch) = entry.split_once('/').map_or((entry, None), |(o, a)| (o, Some(a)));
+        let matched: Vec<&str> = RELEASE_MATRIX
+            .iter()
+            .filter(|(o, a, _)| (os == "all" || *o == os) && arch.is_none_or(|arch| *a == arch))
+            .map(|(_, _, triple)| *triple)
+            .collect();
+        let expanded = if matched.is_empty() { vec![entry] } else { matched };
+        for triple in expanded {
+            if !triple.is_empty() && !triples.iter().any(|t| t.triple == triple) {
+                triples.push(TargetTriple::new(triple));
+            }
+        }
+    }
+    triples
+}
+
+/// How a Rust target is built on this host
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Builder {
+    /// Plain `cargo build --target`
+    Cargo,
+    /// `cross build`, compiling inside a target-specific container
+    Cross,
+}
+
+/// Pick the builder for `target`, or explain why this host can't build it.
+///
+/// Apple targets need the macOS SDK and MSVC targets the Windows SDK, so
+/// those only build on their own OS (where every arch builds with cargo).
+/// Everything else that isn't native goes through `cross`.
+pub fn builder_for(target: &TargetTriple) -> Result<Builder, String> {
+    let host = host_triple();
+    if target.is_native() {
+        return Ok(Builder::Cargo);
+    }
+    if target.triple.contains("apple") {
+        return if host.contains("apple") {
+            Ok(Builder::Cargo)
+        } else {
+            Err(format!("{target} needs a macOS host"))
+        };
+    }
+    if target.triple.ends_with("windows-msvc") {
+        return if host.contains("windows") {
+            Ok(Builder::Cargo)
+        } else {
+            Err(format!("{target} needs a Windows host (use the -pc-windows-gnu target instead)"))
+        };
+    }
+    Ok(Builder::Cross)
+}
+
 /// Result of compiling for a single target
 #[derive(Debug, Clone)]
 pub struct CompileResult {
@@ -169,11 +246,11 @@ async fn compile_rust(
     target: &TargetTriple,
     binary_names: &[String],
 ) -> Result<Vec<PathBuf>, String> {
-    let (program, mut args) = if target.is_native() {
-        ("cargo", vec!["build", "--release", "--target", &target.triple])
-    } else {
-        ("cross", vec!["build", "--release", "--target", &target.triple])
+    let program = match builder_for(target)? {
+        Builder::Cargo => "cargo",
+        Builder::Cross => "cross",
     };
+    let mut args = vec!["build", "--release", "--target", &target.triple];
 
     // Add --bin flags for each binary
     for name in binary_names {
@@ -306,6 +383,38 @@ pub fn package_binaries(
     Ok(archive_path)
 }
 
+/// Hex sha256 of a file's contents
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+    Ok(sha256_hex(&bytes))
+}
+
+/// Hex sha256 of `bytes`
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Write a `sha256sum`-compatible [`CHECKSUMS_FILE`] covering `archives`
+/// into `output_dir`, returning its path.
+pub fn write_checksums(archives: &[PathBuf], output_dir: &Path) -> Result<PathBuf, String> {
+    let mut lines = Vec::new();
+    for archive in archives {
+        let name = archive
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| "invalid archive filename".to_string())?;
+        lines.push(format!("{}  {name}\n", sha256_file(archive)?));
+    }
+    lines.sort_by(|a, b| a[64..].cmp(&b[64..]));
+
+    let path = output_dir.join(CHECKSUMS_FILE);
+    std::fs::write(&path, lines.concat())
+        .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    Ok(path)
+}
+
 fn create_tar_gz(
     binary_paths: &[PathBuf],
     stem: &str,
@@ -588,6 +697,52 @@ mod tests {
         assert_eq!(entries[0], "tool-aarch64-apple-darwin-v0.1.0/tool");
     }
 
+    #[test]
+    fn test_expand_targets_matrix() {
+        let all = expand_targets(&["all"]);
+        assert_eq!(all.len(), RELEASE_MATRIX.len());
+
+        let picked = expand_targets(&["linux", "windows/x86_64", "x86_64-unknown-linux-gnu", "riscv64gc-unknown-linux-gnu"]);
+        let triples: Vec<&str> = picked.iter().map(|t| t.triple.as_str()).collect();
+        assert_eq!(
+            triples,
+            [
+                "x86_64-unknown-linux-gnu",
+                "aarch64-unknown-linux-gnu",
+                "x86_64-pc-windows-gnu",
+                "riscv64gc-unknown-linux-gnu",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_builder_for() {
+        assert_eq!(builder_for(&TargetTriple::new(host_triple())), Ok(Builder::Cargo));
+        if host_triple().contains("linux") {
+            assert_eq!(builder_for(&TargetTriple::new("riscv64gc-unknown-linux-gnu")), Ok(Builder::Cross));
+            assert!(builder_for(&TargetTriple::new("aarch64-apple-darwin")).is_err());
+            assert!(builder_for(&TargetTriple::new("x86_64-pc-windows-msvc")).is_err());
+        }
+    }
+
+    #[test]
+    fn test_write_checksums() {
+        let tmp = TempDir::new().unwrap();
+        let b = tmp.path().join("b.tar.gz");
+        let a = tmp.path().join("a.tar.gz");
+        std::fs::write(&b, b"bbb").unwrap();
+        std::fs::write(&a, b"").unwrap();
+
+        let path = write_checksums(&[b, a], tmp.path()).unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(
+            lines[0],
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855  a.tar.gz"
+        );
+        assert!(lines[1].ends_with("  b.tar.gz"));
+    }
+
     #[test]
     fn test_package_binaries_missing_file_fails() {
         let tmp = TempDir::new().unwrap();
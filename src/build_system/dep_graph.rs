@@ -3,7 +3,7 @@
 //! Cross-language dependency graph from enriched workspace context.
 //! Supports topological ordering, build tiers, reverse deps, and cycle detection.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::DepRef;
 
@@ -31,6 +31,10 @@ pub struct DepEdge {
     pub version_req: Option<String>,
     /// Whether this is a path dependency
     pub is_path_dep: bool,
+    /// Whether this is a dev/test-only dependency
+    pub is_dev: bool,
+    /// Whether this dependency is optional (feature-gated)
+    pub is_optional: bool,
 }
 
 /// A version mismatch between pinned and local versions
@@ -54,6 +58,63 @@ impl std::fmt::Display for CycleError {
     }
 }
 
+/// How an edge on a dependency cycle can be broken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EdgeKind {
+    /// `[dev-dependencies]` / devDependencies — only needed for tests
+    Dev,
+    /// Optional dependency, only pulled in behind a feature
+    Optional,
+    /// Regular build dependency
+    Normal,
+}
+
+impl EdgeKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dev => "dev",
+            Self::Optional => "optional",
+            Self::Normal => "normal",
+        }
+    }
+
+    const fn of(edge: &DepEdge) -> Self {
+        if edge.is_dev {
+            Self::Dev
+        } else if edge.is_optional {
+            Self::Optional
+        } else {
+            Self::Normal
+        }
+    }
+}
+
+/// An edge suggested for removal to make the graph acyclic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleBreak {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+    /// Number of enumerated cycles that pass through this edge
+    pub cycles_broken: usize,
+    pub suggestion: String,
+}
+
+/// Full diagnosis of the cycles in a dependency graph
+#[derive(Debug, Clone, Default)]
+pub struct CycleDiagnosis {
+    /// Each cycle as a closed path of package names (`a -> b -> a`)
+    pub cycles: Vec<Vec<String>>,
+    /// Edges to break, cheapest kind first, that leave the graph acyclic
+    pub breaks: Vec<CycleBreak>,
+    /// True if cycle enumeration hit [`MAX_CYCLES`] and more cycles exist
+    pub truncated: bool,
+}
+
+/// Upper bound on cycles enumerated per diagnosis (cycle counts grow
+/// exponentially in dense strongly-connected components).
+pub const MAX_CYCLES: usize = 50;
+
 /// Cross-language workspace dependency graph
 #[derive(Debug, Clone)]
 pub struct DepGraph {
@@ -88,6 +149,8 @@ impl DepGraph {
                         to: to_idx,
                         version_req: dep.version_req.clone(),
                         is_path_dep: dep.is_path_dep,
+                        is_dev: dep.is_dev,
+                        is_optional: dep.is_optional,
                     });
                 }
             }
@@ -126,12 +189,22 @@ impl DepGraph {
         }
 
         if order.len() != n {
-            // Find a cycle for error reporting
-            let in_cycle: Vec<String> = (0..n)
-                .filter(|&i| in_degree[i] > 0)
-                .map(|i| self.nodes[i].name.clone())
-                .collect();
-            return Err(CycleError { cycle: in_cycle });
+            // Report a concrete cycle path rather than every blocked node
+            let cycle = self
+                .find_cycles(&[], 1)
+                .0
+                .into_iter()
+                .next()
+                .map_or_else(
+                    || {
+                        (0..n)
+                            .filter(|&i| in_degree[i] > 0)
+                            .map(|i| self.nodes[i].name.clone())
+                            .collect()
+                    },
+                    |c| self.cycle_names(&c),
+                );
+            return Err(CycleError { cycle });
         }
 
         Ok(order)
@@ -189,6 +262,21 @@ impl DepGraph {
             .collect()
     }
 
+    /// Nodes affected by changes to `changed`: the changed nodes plus every
+    /// transitive dependent, in index order. `build validate --changed_since`
+    /// tests exactly these.
+    pub fn affected(&self, changed: &[usize]) -> Vec<usize> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack: Vec<usize> = changed.to_vec();
+        while let Some(idx) = stack.pop() {
+            if std::mem::replace(&mut seen[idx], true) {
+                continue;
+            }
+            stack.extend(self.reverse_deps(idx));
+        }
+        (0..self.nodes.len()).filter(|&i| seen[i]).collect()
+    }
+
     /// Detect version mismatches between pinned versions and local versions.
     pub fn version_mismatches(&self) -> Vec<VersionMismatch> {
         let mut mismatches = Vec::new();
@@ -216,12 +304,284 @@ impl DepGraph {
         mismatches
     }
 
+    /// Diagnose every dependency cycle: list the cycle paths and pick a
+    /// small set of edges that, once removed, make the graph acyclic.
+    ///
+    /// Breaks are chosen greedily — the edge on the most remaining cycles,
+    /// preferring dev-only, then optional, then normal edges. Minimum
+    /// feedback arc set is NP-hard, so this is a suggestion, not an optimum.
+    pub fn diagnose_cycles(&self) -> CycleDiagnosis {
+        let (cycles, truncated) = self.find_cycles(&[], MAX_CYCLES);
+        if cycles.is_empty() {
+            return CycleDiagnosis::default();
+        }
+
+        let mut removed: Vec<usize> = Vec::new();
+        let mut breaks = Vec::new();
+        let mut remaining = cycles.clone();
+
+        loop {
+            if remaining.is_empty() {
+                // Enumeration may have been capped; look again without the removed edges
+                let (more, _) = self.find_cycles(&removed, MAX_CYCLES);
+                if more.is_empty() {
+                    break;
+                }
+                remaining = more;
+            }
+
+            let mut counts: HashMap<usize, usize> = HashMap::new();
+            for cycle in &remaining {
+                for &e in cycle {
+                    *counts.entry(e).or_insert(0) += 1;
+                }
+            }
+            let Some((&edge_idx, &count)) = counts.iter().min_by(|(a_idx, a_n), (b_idx, b_n)| {
+                let (a, b) = (&self.edges[**a_idx], &self.edges[**b_idx]);
+                EdgeKind::of(a)
+                    .cmp(&EdgeKind::of(b))
+                    .then(b_n.cmp(a_n))
+                    .then(self.nodes[a.from].name.cmp(&self.nodes[b.from].name))
+                    .then(self.nodes[a.to].name.cmp(&self.nodes[b.to].name))
+            }) else {
+                break;
+            };
+
+            let edge = &self.edges[edge_idx];
+            let kind = EdgeKind::of(edge);
+            let (from, to) = (&self.nodes[edge.from].name, &self.nodes[edge.to].name);
+            let suggestion = match kind {
+                EdgeKind::Dev => format!(
+                    "{from} -> {to} is a dev-dependency; exclude dev deps from build ordering or move the tests that need {to} into {to} itself"
+                ),
+                EdgeKind::Optional => format!(
+                    "{from} -> {to} is optional; keep it out of default features so ordinary builds don't follow it"
+                ),
+                EdgeKind::Normal => format!(
+                    "move {to} to [dev-dependencies] of {from} if only tests use it, or extract the shared code into a new package both depend on"
+                ),
+            };
+            breaks.push(CycleBreak {
+                from: from.clone(),
+                to: to.clone(),
+                kind,
+                cycles_broken: count,
+                suggestion,
+            });
+
+            removed.push(edge_idx);
+            remaining.retain(|c| !c.contains(&edge_idx));
+        }
+
+        CycleDiagnosis {
+            cycles: cycles.iter().map(|c| self.cycle_names(c)).collect(),
+            breaks,
+            truncated,
+        }
+    }
+
+    /// Enumerate elementary cycles as lists of edge indices, ignoring edges in
+    /// `skip`. Each cycle starts at its lowest node index so it's found once.
+    /// Returns the cycles and whether enumeration stopped at `limit`.
+    ///
+    /// Johnson's algorithm, confined to strongly connected components: nodes
+    /// outside every cycle are never searched, and blocking keeps the search
+    /// from re-walking paths that can't close back to the start. Cycles that
+    /// differ only by parallel edges are reported once.
+    fn find_cycles(&self, skip: &[usize], limit: usize) -> (Vec<Vec<usize>>, bool) {
+        let n = self.nodes.len();
+        let mut out_edges: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for (i, edge) in self.edges.iter().enumerate() {
+            if !skip.contains(&i) {
+                out_edges[edge.from].push(i);
+            }
+        }
+
+        let mut search = CircuitSearch {
+            graph: self,
+            component: self.components(&out_edges),
+            out_edges,
+            start: 0,
+            blocked: vec![false; n],
+            blocked_by: vec![Vec::new(); n],
+            path: Vec::new(),
+            seen: HashSet::new(),
+            cycles: Vec::new(),
+            limit,
+        };
+        for start in 0..n {
+            search.start = start;
+            search.blocked.fill(false);
+            search.blocked_by.iter_mut().for_each(Vec::clear);
+            search.circuit(start);
+            if search.cycles.len() >= limit {
+                return (search.cycles, true);
+            }
+        }
+        (search.cycles, false)
+    }
+
+    /// Strongly connected component id of every node (Tarjan), over
+    /// `out_edges`.
+    fn components(&self, out_edges: &[Vec<usize>]) -> Vec<usize> {
+        let n = self.nodes.len();
+        let mut index = vec![usize::MAX; n];
+        let mut lowlink = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack: Vec<usize> = Vec::new();
+        let mut component = vec![usize::MAX; n];
+        let (mut next_index, mut next_component) = (0, 0);
+
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            index[root] = next_index;
+            lowlink[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            // Explicit DFS stack: (node, next out-edge position)
+            let mut calls: Vec<(usize, usize)> = vec![(root, 0)];
+
+            while let Some(&mut (node, ref mut pos)) = calls.last_mut() {
+                if let Some(&edge_idx) = out_edges[node].get(*pos) {
+                    *pos += 1;
+                    let next = self.edges[edge_idx].to;
+                    if index[next] == usize::MAX {
+                        index[next] = next_index;
+                        lowlink[next] = next_index;
+                        next_index += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        calls.push((next, 0));
+                    } else if on_stack[next] {
+                        lowlink[node] = lowlink[node].min(index[next]);
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[node]);
+                }
+                if lowlink[node] == index[node] {
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component[member] = next_component;
+                        if member == node {
+                            break;
+                        }
+                    }
+                    next_component += 1;
+                }
+            }
+        }
+        component
+    }
+
+    /// Closed path of package names for a cycle of edge indices.
+    fn cycle_names(&self, cycle: &[usize]) -> Vec<String> {
+        let mut names: Vec<String> = cycle
+            .iter()
+            .map(|&e| self.nodes[self.edges[e].from].name.clone())
+            .collect();
+        if let Some(&first) = cycle.first() {
+            names.push(self.nodes[self.edges[first].from].name.clone());
+        }
+        names
+    }
+
     /// Look up node index by name.
     pub fn node_index(&self, name: &str) -> Option<usize> {
         self.name_to_idx.get(name).copied()
     }
 }
 
+/// State of one [`DepGraph::find_cycles`] run: Johnson's circuit search from
+/// `start` through nodes above it in its component.
+struct CircuitSearch<'a> {
+    graph: &'a DepGraph,
+    out_edges: Vec<Vec<usize>>,
+    component: Vec<usize>,
+    start: usize,
+    blocked: Vec<bool>,
+    /// Nodes to unblock once the key node is unblocked
+    blocked_by: Vec<Vec<usize>>,
+    path: Vec<usize>,
+    /// Node sequences already reported, rotated to their lowest node
+    seen: HashSet<Vec<usize>>,
+    cycles: Vec<Vec<usize>>,
+    limit: usize,
+}
+
+impl CircuitSearch<'_> {
+    /// Whether `node` can lie on a cycle through `start` that starts there
+    fn in_scope(&self, node: usize) -> bool {
+        node >= self.start && self.component[node] == self.component[self.start]
+    }
+
+    /// Search on from `node`; true when some cycle closed through it
+    fn circuit(&mut self, node: usize) -> bool {
+        let mut closed = false;
+        self.blocked[node] = true;
+
+        for i in 0..self.out_edges[node].len() {
+            if self.cycles.len() >= self.limit {
+                return true;
+            }
+            let edge_idx = self.out_edges[node][i];
+            let next = self.graph.edges[edge_idx].to;
+            if !self.in_scope(next) {
+                continue;
+            }
+            if next == self.start {
+                self.path.push(edge_idx);
+                self.record();
+                self.path.pop();
+                closed = true;
+            } else if !self.blocked[next] {
+                self.path.push(edge_idx);
+                closed |= self.circuit(next);
+                self.path.pop();
+            }
+        }
+
+        if closed {
+            self.unblock(node);
+        } else {
+            for i in 0..self.out_edges[node].len() {
+                let next = self.graph.edges[self.out_edges[node][i]].to;
+                if self.in_scope(next) && !self.blocked_by[next].contains(&node) {
+                    self.blocked_by[next].push(node);
+                }
+            }
+        }
+        closed
+    }
+
+    fn unblock(&mut self, node: usize) {
+        self.blocked[node] = false;
+        for waiting in std::mem::take(&mut self.blocked_by[node]) {
+            if self.blocked[waiting] {
+                self.unblock(waiting);
+            }
+        }
+    }
+
+    /// Keep the cycle on `path` unless one through the same nodes (over
+    /// parallel edges) was already kept
+    fn record(&mut self) {
+        let mut nodes: Vec<usize> = self.path.iter().map(|&e| self.graph.edges[e].from).collect();
+        if let Some(lowest) = nodes.iter().enumerate().min_by_key(|&(_, &n)| n).map(|(i, _)| i) {
+            nodes.rotate_left(lowest);
+        }
+        if self.seen.insert(nodes) {
+            self.cycles.push(self.path.clone());
+        }
+    }
+}
+
 /// Simple version compatibility check.
 /// Returns true if the requirement could plausibly match the local version.
 /// This is a heuristic — it checks if the major version matches for semver.
@@ -295,6 +655,7 @@ mod tests {
                 is_path_dep: false,
                 path: None,
                 is_dev: false,
+                is_optional: false,
             }]),
             (2, vec![
                 DepRef {
@@ -303,6 +664,7 @@ mod tests {
                     is_path_dep: false,
                     path: None,
                     is_dev: false,
+                    is_optional: false,
                 },
                 DepRef {
                     name: "macros".to_string(),
@@ -310,6 +672,7 @@ mod tests {
                     is_path_dep: false,
                     path: None,
                     is_dev: false,
+                    is_optional: false,
                 },
             ]),
         ];
@@ -373,6 +736,7 @@ mod tests {
             is_path_dep: false,
             path: None,
             is_dev: false,
+            is_optional: false,
         }])];
 
         let graph = DepGraph::build(nodes, &deps);
@@ -407,6 +771,7 @@ mod tests {
                 is_path_dep: false,
                 path: None,
                 is_dev: false,
+                is_optional: false,
             }]),
             (1, vec![DepRef {
                 name: "a".to_string(),
@@ -414,6 +779,7 @@ mod tests {
                 is_path_dep: false,
                 path: None,
                 is_dev: false,
+                is_optional: false,
             }]),
         ];
 
@@ -421,6 +787,126 @@ mod tests {
         assert!(graph.topo_order().is_err());
     }
 
+    fn dep(name: &str, is_dev: bool, is_optional: bool) -> DepRef {
+        DepRef {
+            name: name.to_string(),
+            version_req: None,
+            is_path_dep: false,
+            path: None,
+            is_dev,
+            is_optional,
+        }
+    }
+
+    fn named_nodes(names: &[&str]) -> Vec<DepNode> {
+        names
+            .iter()
+            .map(|n| DepNode {
+                name: (*n).to_string(),
+                version: None,
+                build_system: "cargo".to_string(),
+                path: (*n).to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cycle_error_reports_path() {
+        // a -> b -> c -> a, plus d -> a (downstream of the cycle, not on it)
+        let deps = vec![
+            (0, vec![dep("b", false, false)]),
+            (1, vec![dep("c", false, false)]),
+            (2, vec![dep("a", false, false)]),
+            (3, vec![dep("a", false, false)]),
+        ];
+        let graph = DepGraph::build(named_nodes(&["a", "b", "c", "d"]), &deps);
+        let err = graph.topo_order().unwrap_err();
+        assert_eq!(err.cycle, vec!["a", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_diagnose_prefers_dev_edge() {
+        // a -> b (normal), b -> a (dev)
+        let deps = vec![
+            (0, vec![dep("b", false, false)]),
+            (1, vec![dep("a", true, false)]),
+        ];
+        let graph = DepGraph::build(named_nodes(&["a", "b"]), &deps);
+        let diagnosis = graph.diagnose_cycles();
+
+        assert_eq!(diagnosis.cycles, vec![vec!["a", "b", "a"]]);
+        assert_eq!(diagnosis.breaks.len(), 1);
+        assert_eq!(diagnosis.breaks[0].from, "b");
+        assert_eq!(diagnosis.breaks[0].to, "a");
+        assert_eq!(diagnosis.breaks[0].kind, EdgeKind::Dev);
+        assert!(!diagnosis.truncated);
+    }
+
+    #[test]
+    fn test_diagnose_shared_edge_breaks_both_cycles() {
+        // a -> b -> a and a -> b -> c -> a share a -> b
+        let deps = vec![
+            (0, vec![dep("b", false, false)]),
+            (1, vec![dep("a", false, false), dep("c", false, false)]),
+            (2, vec![dep("a", false, false)]),
+        ];
+        let graph = DepGraph::build(named_nodes(&["a", "b", "c"]), &deps);
+        let diagnosis = graph.diagnose_cycles();
+
+        assert_eq!(diagnosis.cycles.len(), 2);
+        assert_eq!(diagnosis.breaks.len(), 1);
+        assert_eq!(diagnosis.breaks[0].from, "a");
+        assert_eq!(diagnosis.breaks[0].to, "b");
+        assert_eq!(diagnosis.breaks[0].cycles_broken, 2);
+    }
+
+    #[test]
+    fn test_diagnose_reports_parallel_edge_cycles_once() {
+        // a -> b twice (normal and dev), b -> a, b -> c -> a
+        let deps = vec![
+            (0, vec![dep("b", false, false), dep("b", true, false)]),
+            (1, vec![dep("a", false, false), dep("c", false, false)]),
+            (2, vec![dep("a", false, false)]),
+        ];
+        let graph = DepGraph::build(named_nodes(&["a", "b", "c"]), &deps);
+        let diagnosis = graph.diagnose_cycles();
+
+        assert_eq!(diagnosis.cycles, vec![vec!["a", "b", "a"], vec!["a", "b", "c", "a"]]);
+    }
+
+    #[test]
+    fn test_diagnose_dense_graph_is_capped() {
+        // Every package depends on every other: millions of elementary cycles
+        let names = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l"];
+        let deps: Vec<_> = (0..names.len())
+            .map(|i| {
+                let others = names.iter().enumerate().filter(|&(j, _)| j != i);
+                (i, others.map(|(_, n)| dep(n, false, false)).collect())
+            })
+            .collect();
+        let graph = DepGraph::build(named_nodes(&names), &deps);
+        let diagnosis = graph.diagnose_cycles();
+
+        assert!(diagnosis.truncated);
+        assert_eq!(diagnosis.cycles.len(), MAX_CYCLES);
+    }
+
+    #[test]
+    fn test_affected_is_transitive() {
+        let graph = make_test_graph();
+        assert_eq!(graph.affected(&[0]), vec![0, 1, 2]); // core → macros → hyperforge
+        assert_eq!(graph.affected(&[1]), vec![1, 2]);
+        assert_eq!(graph.affected(&[2]), vec![2]);
+    }
+
+    #[test]
+    fn test_diagnose_acyclic_is_empty() {
+        let graph = make_test_graph();
+        let diagnosis = graph.diagnose_cycles();
+        assert!(diagnosis.cycles.is_empty());
+        assert!(diagnosis.breaks.is_empty());
+    }
+
     #[test]
     fn test_versions_compatible() {
         assert!(versions_compatible("1.0", "1.5.0"));
@@ -0,0 +1,411 @@
+//! Drift detection for generated workspace files
+//!
+//! `workspace unify` records a sha256 of every file it writes in
+//! `<workspace>/.hyperforge/generated.toml`, plus a copy of the generated
+//! content under `.hyperforge/generated/` to serve as a merge base. On later
+//! runs a file whose hash no longer matches has been edited by hand; unify
+//! then refuses to overwrite it unless told to force or three-way merge.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const MANIFEST_FILE: &str = "generated.toml";
+const BASE_DIR: &str = "generated";
+
+/// How to handle a generated file that was edited since it was last written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DriftPolicy {
+    /// Leave the edited file alone and report a conflict
+    #[default]
+    Refuse,
+    /// Overwrite manual edits with freshly generated content
+    Force,
+    /// Three-way merge manual edits with the new content (base = last generated)
+    Merge,
+}
+
+/// Outcome of reconciling new content with what's on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reconcile {
+    Created,
+    Updated,
+    Unchanged,
+    /// Manual edits were merged with the new content
+    Merged,
+    /// Manual edits conflict with the new content; nothing written
+    Conflict,
+}
+
+/// A detected manual edit to a generated file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Drift {
+    pub recorded_hash: String,
+    pub current_hash: String,
+}
+
+/// Result of [`reconcile`]: what to write (if anything) and why.
+#[derive(Debug, Clone)]
+pub struct Reconciled {
+    pub action: Reconcile,
+    /// Text to write to the target file, `None` if it stays as-is
+    pub write: Option<String>,
+    /// Set when the on-disk file was edited since it was last generated
+    pub drift: Option<Drift>,
+}
+
+/// Recorded hashes of generated files, keyed by path relative to the workspace root
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GeneratedManifest {
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+impl GeneratedManifest {
+    fn path(workspace_root: &Path) -> PathBuf {
+        workspace_root
+            .join(crate::config::CONFIG_DIR)
+            .join(MANIFEST_FILE)
+    }
+
+    /// Load the manifest. Returns an empty manifest if none has been written
+    /// yet; a manifest that exists but doesn't parse is an error, since
+    /// treating it as empty would silently adopt every hand edit.
+    pub fn load(workspace_root: &Path) -> Result<Self, String> {
+        let path = Self::path(workspace_root);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Failed to read {}: {e}", path.display())),
+        };
+        toml::from_str(&content).map_err(|e| format!("Failed to parse {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, workspace_root: &Path) -> Result<(), String> {
+        let path = Self::path(workspace_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize generated file manifest: {e}"))?;
+        // Write-then-rename so a concurrent reader never sees a partial file
+        let tmp = path.with_extension(format!("toml.{}", uuid::Uuid::new_v4()));
+        std::fs::write(&tmp, content)
+            .map_err(|e| format!("Failed to write {}: {e}", tmp.display()))?;
+        std::fs::rename(&tmp, &path).map_err(|e| {
+            let _ = std::fs::remove_file(&tmp);
+            format!("Failed to write {}: {e}", path.display())
+        })
+    }
+
+    /// Record `entries` (`(rel_path, hash)`, as returned by [`write`]) in one
+    /// read-modify-write. Batch callers collect entries from their workers and
+    /// record them once afterwards, so parallel repos never race on the file.
+    pub fn record(workspace_root: &Path, entries: &[ManifestEntry]) -> Result<(), String> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut manifest = Self::load(workspace_root)?;
+        manifest.files.extend(entries.iter().cloned());
+        manifest.save(workspace_root)
+    }
+}
+
+/// A pending manifest update: `(path relative to the workspace root, content hash)`
+pub type ManifestEntry = (String, String);
+
+/// sha256 hex digest of file content
+pub fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+fn base_path(workspace_root: &Path, rel_path: &str) -> PathBuf {
+    workspace_root
+        .join(crate::config::CONFIG_DIR)
+        .join(BASE_DIR)
+        .join(rel_path)
+}
+
+/// Decide what to do with freshly generated `content` for `rel_path`.
+///
+/// Files with no recorded hash (written before drift tracking existed, or by
+/// hand) are adopted as-is, matching the old overwrite behavior. A file is
+/// only in conflict when both the user and the generator changed it.
+pub fn reconcile(
+    workspace_root: &Path,
+    rel_path: &str,
+    content: &str,
+    policy: DriftPolicy,
+) -> Result<Reconciled, String> {
+    let target = workspace_root.join(rel_path);
+    let Ok(existing) = std::fs::read_to_string(&target) else {
+        return Ok(Reconciled {
+            action: Reconcile::Created,
+            write: Some(content.to_string()),
+            drift: None,
+        });
+    };
+
+    if existing == content {
+        return Ok(Reconciled {
+            action: Reconcile::Unchanged,
+            write: None,
+            drift: None,
+        });
+    }
+
+    let manifest = GeneratedManifest::load(workspace_root)?;
+    let current_hash = content_hash(&existing);
+    let drift = match manifest.files.get(rel_path) {
+        Some(recorded) if *recorded != current_hash => Some(Drift {
+            recorded_hash: recorded.clone(),
+            current_hash,
+        }),
+        _ => None,
+    };
+
+    let Some(drift) = drift else {
+        return Ok(Reconciled {
+            action: Reconcile::Updated,
+            write: Some(content.to_string()),
+            drift: None,
+        });
+    };
+
+    // Generator output hasn't moved since last run — only the user changed
+    // the file, so keep their edits unless forced.
+    let generator_unchanged = content_hash(content) == drift.recorded_hash;
+
+    match policy {
+        DriftPolicy::Refuse | DriftPolicy::Merge if generator_unchanged => Ok(Reconciled {
+            action: Reconcile::Unchanged,
+            write: None,
+            drift: Some(drift),
+        }),
+        DriftPolicy::Refuse => Ok(Reconciled {
+            action: Reconcile::Conflict,
+            write: None,
+            drift: Some(drift),
+        }),
+        DriftPolicy::Force => Ok(Reconciled {
+            action: Reconcile::Updated,
+            write: Some(content.to_string()),
+            drift: Some(drift),
+        }),
+        DriftPolicy::Merge => {
+            let Ok(base) = std::fs::read_to_string(base_path(workspace_root, rel_path)) else {
+                return Ok(Reconciled {
+                    action: Reconcile::Conflict,
+                    write: None,
+                    drift: Some(drift),
+                });
+            };
+            match three_way_merge(&existing, &base, content)? {
+                Some(merged) => Ok(Reconciled {
+                    action: Reconcile::Merged,
+                    write: Some(merged),
+                    drift: Some(drift),
+                }),
+                None => Ok(Reconciled {
+                    action: Reconcile::Conflict,
+                    write: None,
+                    drift: Some(drift),
+                }),
+            }
+        }
+    }
+}
+
+/// Write the reconciled file and its merge base, returning the manifest entry
+/// to record (`None` for a conflict, which leaves everything untouched).
+///
+/// The hash recorded is always that of the *generated* content, so a merged
+/// file keeps showing as drifted and user edits survive later runs.
+pub fn write(
+    workspace_root: &Path,
+    rel_path: &str,
+    reconciled: &Reconciled,
+    generated: &str,
+) -> Result<Option<ManifestEntry>, String> {
+    if reconciled.action == Reconcile::Conflict {
+        return Ok(None);
+    }
+
+    if let Some(text) = &reconciled.write {
+        let target = workspace_root.join(rel_path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        std::fs::write(&target, text).map_err(|e| format!("Failed to write {rel_path}: {e}"))?;
+    }
+
+    let base = base_path(workspace_root, rel_path);
+    if let Some(parent) = base.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    std::fs::write(&base, generated)
+        .map_err(|e| format!("Failed to record merge base for {rel_path}: {e}"))?;
+
+    Ok(Some((rel_path.to_string(), content_hash(generated))))
+}
+
+/// [`write`] the reconciled file and record it in the manifest straight away.
+/// Only for sequential callers; parallel batches use `write` + [`GeneratedManifest::record`].
+pub fn apply(
+    workspace_root: &Path,
+    rel_path: &str,
+    reconciled: &Reconciled,
+    generated: &str,
+) -> Result<(), String> {
+    let entry = write(workspace_root, rel_path, reconciled, generated)?;
+    GeneratedManifest::record(workspace_root, entry.as_slice())
+}
+
+/// Three-way merge via `git merge-file`. Returns `None` if the merge has conflicts.
+fn three_way_merge(current: &str, base: &str, new: &str) -> Result<Option<String>, String> {
+    let dir = std::env::temp_dir().join(format!("hyperforge-merge-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create merge dir: {e}"))?;
+
+    let result = (|| {
+        let paths = [dir.join("current"), dir.join("base"), dir.join("new")];
+        for (path, text) in paths.iter().zip([current, base, new]) {
+            std::fs::write(path, text).map_err(|e| format!("Failed to stage merge input: {e}"))?;
+        }
+        let output = Command::new("git")
+            .arg("merge-file")
+            .arg("-p")
+            .args(&paths)
+            .output()
+            .map_err(|e| format!("Failed to run git merge-file: {e}"))?;
+
+        // Exit code is the number of conflicts; negative/signal means failure
+        match output.status.code() {
+            Some(0) => Ok(Some(String::from_utf8_lossy(&output.stdout).to_string())),
+            Some(n) if n > 0 => Ok(None),
+            _ => Err(format!(
+                "git merge-file failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )),
+        }
+    })();
+
+    let _ = std::fs::remove_dir_all(&dir);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const REL: &str = "cabal.project";
+
+    fn write_generated(root: &Path, content: &str) {
+        let r = reconcile(root, REL, content, DriftPolicy::Refuse).unwrap();
+        apply(root, REL, &r, content).unwrap();
+    }
+
+    #[test]
+    fn test_untracked_file_is_adopted() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join(REL), "old\n").unwrap();
+
+        let r = reconcile(tmp.path(), REL, "new\n", DriftPolicy::Refuse).unwrap();
+        assert_eq!(r.action, Reconcile::Updated);
+        assert!(r.drift.is_none());
+    }
+
+    #[test]
+    fn test_manual_edit_conflicts() {
+        let tmp = TempDir::new().unwrap();
+        write_generated(tmp.path(), "a\nb\n");
+        std::fs::write(tmp.path().join(REL), "a\nb\nmine\n").unwrap();
+
+        let r = reconcile(tmp.path(), REL, "a\nc\n", DriftPolicy::Refuse).unwrap();
+        assert_eq!(r.action, Reconcile::Conflict);
+        assert!(r.write.is_none());
+        assert!(r.drift.is_some());
+    }
+
+    #[test]
+    fn test_manual_edit_kept_when_generator_unchanged() {
+        let tmp = TempDir::new().unwrap();
+        write_generated(tmp.path(), "a\nb\n");
+        std::fs::write(tmp.path().join(REL), "a\nb\nmine\n").unwrap();
+
+        let r = reconcile(tmp.path(), REL, "a\nb\n", DriftPolicy::Refuse).unwrap();
+        assert_eq!(r.action, Reconcile::Unchanged);
+    }
+
+    #[test]
+    fn test_force_overwrites() {
+        let tmp = TempDir::new().unwrap();
+        write_generated(tmp.path(), "a\nb\n");
+        std::fs::write(tmp.path().join(REL), "a\nb\nmine\n").unwrap();
+
+        let r = reconcile(tmp.path(), REL, "a\nc\n", DriftPolicy::Force).unwrap();
+        assert_eq!(r.action, Reconcile::Updated);
+        apply(tmp.path(), REL, &r, "a\nc\n").unwrap();
+        assert_eq!(std::fs::read_to_string(tmp.path().join(REL)).unwrap(), "a\nc\n");
+    }
+
+    #[test]
+    fn test_force_overwrites_user_edit_when_generator_unchanged() {
+        let tmp = TempDir::new().unwrap();
+        write_generated(tmp.path(), "a\nb\n");
+        std::fs::write(tmp.path().join(REL), "a\nb\nmine\n").unwrap();
+
+        let kept = reconcile(tmp.path(), REL, "a\nb\n", DriftPolicy::Refuse).unwrap();
+        assert_eq!(kept.action, Reconcile::Unchanged);
+
+        let r = reconcile(tmp.path(), REL, "a\nb\n", DriftPolicy::Force).unwrap();
+        assert_eq!(r.action, Reconcile::Updated);
+        apply(tmp.path(), REL, &r, "a\nb\n").unwrap();
+        assert_eq!(std::fs::read_to_string(tmp.path().join(REL)).unwrap(), "a\nb\n");
+    }
+
+    #[test]
+    fn test_merge_keeps_user_edits() {
+        let tmp = TempDir::new().unwrap();
+        write_generated(tmp.path(), "header\n\none\n\nfooter\n");
+        std::fs::write(tmp.path().join(REL), "header\n\none\n\nfooter\nmine\n").unwrap();
+
+        let new = "header\n\ntwo\n\nfooter\n";
+        let r = reconcile(tmp.path(), REL, new, DriftPolicy::Merge).unwrap();
+        assert_eq!(r.action, Reconcile::Merged);
+        assert_eq!(r.write.as_deref(), Some("header\n\ntwo\n\nfooter\nmine\n"));
+    }
+
+    #[test]
+    fn test_corrupt_manifest_is_an_error() {
+        let tmp = TempDir::new().unwrap();
+        write_generated(tmp.path(), "a\n");
+        std::fs::write(GeneratedManifest::path(tmp.path()), "files = [").unwrap();
+        std::fs::write(tmp.path().join(REL), "mine\n").unwrap();
+
+        assert!(GeneratedManifest::load(tmp.path()).is_err());
+        assert!(reconcile(tmp.path(), REL, "b\n", DriftPolicy::Refuse).is_err());
+    }
+
+    #[test]
+    fn test_record_batches_entries() {
+        let tmp = TempDir::new().unwrap();
+        let entries: Vec<ManifestEntry> = ["a/x", "b/x"]
+            .iter()
+            .map(|rel| {
+                let r = reconcile(tmp.path(), rel, "x\n", DriftPolicy::Refuse).unwrap();
+                write(tmp.path(), rel, &r, "x\n").unwrap().unwrap()
+            })
+            .collect();
+        GeneratedManifest::record(tmp.path(), &entries).unwrap();
+
+        let manifest = GeneratedManifest::load(tmp.path()).unwrap();
+        assert_eq!(manifest.files.len(), 2);
+        assert_eq!(manifest.files["a/x"], content_hash("x\n"));
+    }
+}
@@ -0,0 +1,307 @@
+//! Dependency graph export (DOT, Mermaid, JSON)
+//!
+//! Renders a [`DepGraph`] — nodes, edges, build tiers, and version
+//! mismatches — into standard graph formats for documentation and
+//! visualization pipelines.
+
+use serde::Serialize;
+
+use super::dep_graph::DepGraph;
+
+/// Graph export formats accepted by `analyze --format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    Mermaid,
+    Json,
+}
+
+impl GraphFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "dot" => Some(Self::Dot),
+            "mermaid" => Some(Self::Mermaid),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::Mermaid => "mermaid",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Render `graph` in the requested format.
+pub fn render(graph: &DepGraph, format: GraphFormat) -> String {
+    match format {
+        GraphFormat::Dot => to_dot(graph),
+        GraphFormat::Mermaid => to_mermaid(graph),
+        GraphFormat::Json => to_json(graph),
+    }
+}
+
+/// Indices of edges whose pinned requirement doesn't match the local version.
+fn mismatched_edges(graph: &DepGraph) -> Vec<usize> {
+    let mismatches = graph.version_mismatches();
+    graph
+        .edges
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| {
+            mismatches.iter().any(|m| {
+                m.repo_name == graph.nodes[e.from].name && m.dependency == graph.nodes[e.to].name
+            })
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn node_label(graph: &DepGraph, idx: usize) -> String {
+    let node = &graph.nodes[idx];
+    match &node.version {
+        Some(v) => format!("{} {v}", node.name),
+        None => node.name.clone(),
+    }
+}
+
+/// Graphviz DOT. Edges point from dependent to dependency; each build tier
+/// is a `rank=same` group and mismatched pins are drawn in red.
+pub fn to_dot(graph: &DepGraph) -> String {
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+    let mismatched = mismatched_edges(graph);
+
+    let mut out = String::from("digraph workspace {\n  rankdir=LR;\n  node [shape=box];\n");
+    for (i, node) in graph.nodes.iter().enumerate() {
+        out.push_str(&format!(
+            "  n{i} [label={}, tooltip={}];\n",
+            quote(&node_label(graph, i)),
+            quote(&format!("{} ({})", node.path, node.build_system))
+        ));
+    }
+    for (i, edge) in graph.edges.iter().enumerate() {
+        let mut attrs = Vec::new();
+        if let Some(req) = &edge.version_req {
+            attrs.push(format!("label={}", quote(req)));
+        }
+        if edge.is_path_dep {
+            attrs.push("style=dashed".to_string());
+        }
+        if mismatched.contains(&i) {
+            attrs.push("color=red".to_string());
+        }
+        let attrs = if attrs.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", attrs.join(", "))
+        };
+        out.push_str(&format!("  n{} -> n{}{attrs};\n", edge.from, edge.to));
+    }
+    if let Ok(tiers) = graph.build_tiers() {
+        for tier in tiers {
+            let members: Vec<String> = tier.iter().map(|i| format!("n{i}")).collect();
+            out.push_str(&format!("  {{ rank=same; {}; }}\n", members.join("; ")));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Mermaid flowchart. Mismatched pins are styled red via `linkStyle`.
+pub fn to_mermaid(graph: &DepGraph) -> String {
+    let escape = |s: &str| s.replace('"', "#quot;");
+    let mismatched = mismatched_edges(graph);
+
+    let mut out = String::from("graph LR\n");
+    for i in 0..graph.nodes.len() {
+        out.push_str(&format!("  n{i}[\"{}\"]\n", escape(&node_label(graph, i))));
+    }
+    for edge in &graph.edges {
+        let arrow = if edge.is_path_dep { "-.->" } else { "-->" };
+        match &edge.version_req {
+            Some(req) => out.push_str(&format!(
+                "  n{} {arrow}|\"{}\"| n{}\n",
+                edge.from,
+                escape(req),
+                edge.to
+            )),
+            None => out.push_str(&format!("  n{} {arrow} n{}\n", edge.from, edge.to)),
+        }
+    }
+    if !mismatched.is_empty() {
+        let ids: Vec<String> = mismatched.iter().map(ToString::to_string).collect();
+        out.push_str(&format!("  linkStyle {} stroke:red\n", ids.join(",")));
+    }
+    out
+}
+
+#[derive(Serialize)]
+struct JsonNode<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'a str>,
+    build_system: &'a str,
+    path: &'a str,
+}
+
+#[derive(Serialize)]
+struct JsonEdge<'a> {
+    from: &'a str,
+    to: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version_req: Option<&'a str>,
+    is_path_dep: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    is_dev: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    is_optional: bool,
+}
+
+#[derive(Serialize)]
+struct JsonMismatch<'a> {
+    repo: &'a str,
+    dependency: &'a str,
+    pinned_version: &'a str,
+    local_version: &'a str,
+}
+
+#[derive(Serialize)]
+struct JsonGraph<'a> {
+    nodes: Vec<JsonNode<'a>>,
+    edges: Vec<JsonEdge<'a>>,
+    /// Package names per build tier; `None` when the graph has a cycle
+    tiers: Option<Vec<Vec<&'a str>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cycle: Option<Vec<String>>,
+    mismatches: Vec<JsonMismatch<'a>>,
+}
+
+/// JSON document with nodes, edges (by name), tiers, and mismatches.
+pub fn to_json(graph: &DepGraph) -> String {
+    let mismatches = graph.version_mismatches();
+    let (tiers, cycle) = match graph.build_tiers() {
+        Ok(tiers) => (
+            Some(
+                tiers
+                    .iter()
+                    .map(|t| t.iter().map(|&i| graph.nodes[i].name.as_str()).collect())
+                    .collect(),
+            ),
+            None,
+        ),
+        Err(e) => (None, Some(e.cycle)),
+    };
+
+    let doc = JsonGraph {
+        nodes: graph
+            .nodes
+            .iter()
+            .map(|n| JsonNode {
+                name: &n.name,
+                version: n.version.as_deref(),
+                build_system: &n.build_system,
+                path: &n.path,
+            })
+            .collect(),
+        edges: graph
+            .edges
+            .iter()
+            .map(|e| JsonEdge {
+                from: &graph.nodes[e.from].name,
+                to: &graph.nodes[e.to].name,
+                version_req: e.version_req.as_deref(),
+                is_path_dep: e.is_path_dep,
+                is_dev: e.is_dev,
+                is_optional: e.is_optional,
+            })
+            .collect(),
+        tiers,
+        cycle,
+        mismatches: mismatches
+            .iter()
+            .map(|m| JsonMismatch {
+                repo: &m.repo_name,
+                dependency: &m.dependency,
+                pinned_version: &m.pinned_version,
+                local_version: &m.local_version,
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&doc).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_system::dep_graph::DepNode;
+    use crate::build_system::DepRef;
+
+    fn sample_graph() -> DepGraph {
+        let nodes = vec![
+            DepNode {
+                name: "core".to_string(),
+                version: Some("2.0.0".to_string()),
+                build_system: "cargo".to_string(),
+                path: "core".to_string(),
+            },
+            DepNode {
+                name: "app".to_string(),
+                version: Some("0.1.0".to_string()),
+                build_system: "cargo".to_string(),
+                path: "app".to_string(),
+            },
+        ];
+        let deps = vec![(
+            1,
+            vec![DepRef {
+                name: "core".to_string(),
+                version_req: Some("1.0".to_string()),
+                is_path_dep: false,
+                path: None,
+                is_dev: false,
+                is_optional: false,
+            }],
+        )];
+        DepGraph::build(nodes, &deps)
+    }
+
+    #[test]
+    fn test_format_parse() {
+        assert_eq!(GraphFormat::parse("dot"), Some(GraphFormat::Dot));
+        assert_eq!(GraphFormat::parse("mermaid"), Some(GraphFormat::Mermaid));
+        assert_eq!(GraphFormat::parse("json"), Some(GraphFormat::Json));
+        assert_eq!(GraphFormat::parse("svg"), None);
+    }
+
+    #[test]
+    fn test_dot_output() {
+        let dot = to_dot(&sample_graph());
+        assert!(dot.starts_with("digraph workspace {"));
+        assert!(dot.contains("n0 [label=\"core 2.0.0\""));
+        assert!(dot.contains("n1 -> n0 [label=\"1.0\", color=red];"));
+        assert!(dot.contains("{ rank=same; n0; }"));
+    }
+
+    #[test]
+    fn test_mermaid_output() {
+        let mermaid = to_mermaid(&sample_graph());
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("n1 -->|\"1.0\"| n0"));
+        assert!(mermaid.contains("linkStyle 0 stroke:red"));
+    }
+
+    #[test]
+    fn test_json_output() {
+        let json: serde_json::Value = serde_json::from_str(&to_json(&sample_graph())).unwrap();
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 2);
+        assert_eq!(json["edges"][0]["from"], "app");
+        assert_eq!(json["edges"][0]["to"], "core");
+        assert_eq!(json["tiers"], serde_json::json!([["core"], ["app"]]));
+        assert_eq!(json["mismatches"][0]["dependency"], "core");
+        assert!(json.get("cycle").is_none());
+    }
+}
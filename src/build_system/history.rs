@@ -0,0 +1,280 @@
+//! Run history for validation steps
+//!
+//! Every `validate` and `run` appends its step outcomes to
+//! `<workspace>/.hyperforge/history/steps.jsonl`, one JSON record per line.
+//! The history feeds `validate_report`: steps that alternate between pass and
+//! fail are flagged as flaky, and steps whose duration keeps growing are
+//! flagged as trending up. Only the most recent runs of each step are kept,
+//! and `[artifacts]` can encrypt them and prune old ones (see
+//! [`crate::services::artifacts`]).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::services::ArtifactPolicy;
+
+const HISTORY_DIR: &str = "history";
+const STEPS_FILE: &str = "steps.jsonl";
+
+/// Runs kept per `(repo, step)` when the history is compacted
+pub const MAX_RUNS_PER_STEP: usize = 50;
+
+/// A trend is reported when recent runs are this much slower than the baseline
+const TREND_THRESHOLD_PCT: i64 = 20;
+
+/// Outcome of one validation step in one run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepRecord {
+    pub run_at: DateTime<Utc>,
+    pub repo: String,
+    pub step: String,
+    pub passed: bool,
+    pub duration_ms: u64,
+}
+
+/// A step that flips between pass and fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlakyStep {
+    pub repo: String,
+    pub step: String,
+    pub runs: usize,
+    /// Pass→fail or fail→pass transitions within the window
+    pub flips: usize,
+    pub pass_rate_pct: u8,
+}
+
+/// A step whose recent runs are slower than its earlier ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DurationTrend {
+    pub repo: String,
+    pub step: String,
+    pub baseline_ms: u64,
+    pub recent_ms: u64,
+    pub change_pct: i64,
+}
+
+fn history_path(workspace_root: &Path) -> PathBuf {
+    workspace_root
+        .join(crate::config::CONFIG_DIR)
+        .join(HISTORY_DIR)
+        .join(STEPS_FILE)
+}
+
+/// Load all recorded steps, oldest first. Unparseable lines, and lines that
+/// don't decrypt under `policy`, are skipped.
+pub fn load(workspace_root: &Path, policy: &ArtifactPolicy) -> Vec<StepRecord> {
+    std::fs::read_to_string(history_path(workspace_root))
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| policy.open_line(line).ok())
+                .filter_map(|line| serde_json::from_str(&line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Append one run's step outcomes, sealed per `policy`. The file is
+/// rewritten instead once any step has more than [`MAX_RUNS_PER_STEP`]
+/// records or some records are past the retention period.
+pub fn append(workspace_root: &Path, records: &[StepRecord], policy: &ArtifactPolicy) -> Result<(), String> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    let path = history_path(workspace_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+
+    let now = Utc::now();
+    let mut all = load(workspace_root, policy);
+    all.extend(records.iter().cloned());
+    let expired = all.iter().any(|r| policy.is_expired(r.run_at, now));
+    let compact = group(&all).values().any(|runs| runs.len() > MAX_RUNS_PER_STEP);
+
+    if compact || expired {
+        let mut kept: Vec<StepRecord> = group(&all)
+            .into_values()
+            .flat_map(|runs| {
+                let skip = runs.len().saturating_sub(MAX_RUNS_PER_STEP);
+                runs.into_iter().skip(skip).cloned().collect::<Vec<_>>()
+            })
+            .filter(|r| !policy.is_expired(r.run_at, now))
+            .collect();
+        kept.sort_by_key(|r| r.run_at);
+        write_all(&path, &kept, policy)
+    } else {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+        for record in records {
+            writeln!(file, "{}", seal(record, policy)?).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+fn seal(record: &StepRecord, policy: &ArtifactPolicy) -> Result<String, String> {
+    let line = serde_json::to_string(record)
+        .map_err(|e| format!("Failed to serialize step record: {e}"))?;
+    policy.seal_line(&line)
+}
+
+fn write_all(path: &Path, records: &[StepRecord], policy: &ArtifactPolicy) -> Result<(), String> {
+    let mut content = String::new();
+    for record in records {
+        content.push_str(&seal(record, policy)?);
+        content.push('\n');
+    }
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// Records grouped by `(repo, step)`, each group oldest first
+fn group(records: &[StepRecord]) -> BTreeMap<(&str, &str), Vec<&StepRecord>> {
+    let mut groups: BTreeMap<(&str, &str), Vec<&StepRecord>> = BTreeMap::new();
+    for record in records {
+        groups
+            .entry((record.repo.as_str(), record.step.as_str()))
+            .or_default()
+            .push(record);
+    }
+    for runs in groups.values_mut() {
+        runs.sort_by_key(|r| r.run_at);
+    }
+    groups
+}
+
+/// Steps that flipped between pass and fail at least twice in their last
+/// `window` runs.
+///
+/// A single regression (pass, pass, fail, fail) is one flip and isn't flaky.
+pub fn flaky_steps(records: &[StepRecord], window: usize) -> Vec<FlakyStep> {
+    group(records)
+        .into_iter()
+        .filter_map(|((repo, step), runs)| {
+            let recent = &runs[runs.len().saturating_sub(window)..];
+            let flips = recent.windows(2).filter(|w| w[0].passed != w[1].passed).count();
+            if flips < 2 {
+                return None;
+            }
+            let passes = recent.iter().filter(|r| r.passed).count();
+            Some(FlakyStep {
+                repo: repo.to_string(),
+                step: step.to_string(),
+                runs: recent.len(),
+                flips,
+                pass_rate_pct: u8::try_from(passes * 100 / recent.len()).unwrap_or(100),
+            })
+        })
+        .collect()
+}
+
+fn mean_ms(runs: &[&StepRecord]) -> u64 {
+    if runs.is_empty() {
+        return 0;
+    }
+    runs.iter().map(|r| r.duration_ms).sum::<u64>() / runs.len() as u64
+}
+
+/// Steps whose passing runs got slower.
+///
+/// The newer half of the last `window` passing runs is compared against the
+/// older half. Needs at least four passing runs; failures are ignored since
+/// they often stop early.
+pub fn duration_trends(records: &[StepRecord], window: usize) -> Vec<DurationTrend> {
+    group(records)
+        .into_iter()
+        .filter_map(|((repo, step), runs)| {
+            let passing: Vec<&StepRecord> = runs.into_iter().filter(|r| r.passed).collect();
+            let recent = &passing[passing.len().saturating_sub(window)..];
+            if recent.len() < 4 {
+                return None;
+            }
+            let (older, newer) = recent.split_at(recent.len() / 2);
+            let baseline_ms = mean_ms(older);
+            let recent_ms = mean_ms(newer);
+            if baseline_ms == 0 {
+                return None;
+            }
+            let change_pct = i64::try_from(recent_ms).unwrap_or(i64::MAX)
+                .saturating_sub(i64::try_from(baseline_ms).unwrap_or(i64::MAX))
+                .saturating_mul(100)
+                / i64::try_from(baseline_ms).unwrap_or(i64::MAX);
+            (change_pct >= TREND_THRESHOLD_PCT).then(|| DurationTrend {
+                repo: repo.to_string(),
+                step: step.to_string(),
+                baseline_ms,
+                recent_ms,
+                change_pct,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs(repo: &str, step: &str, outcomes: &[(bool, u64)]) -> Vec<StepRecord> {
+        let start = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        outcomes
+            .iter()
+            .enumerate()
+            .map(|(i, &(passed, duration_ms))| StepRecord {
+                run_at: start + chrono::Duration::hours(i64::try_from(i).unwrap()),
+                repo: repo.to_string(),
+                step: step.to_string(),
+                passed,
+                duration_ms,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_flaky_needs_alternation() {
+        let mut records = runs("api", "test", &[(true, 1), (false, 1), (true, 1), (false, 1)]);
+        records.extend(runs("web", "test", &[(true, 1), (true, 1), (false, 1), (false, 1)]));
+
+        let flaky = flaky_steps(&records, 10);
+        assert_eq!(flaky.len(), 1);
+        assert_eq!(flaky[0].repo, "api");
+        assert_eq!(flaky[0].flips, 3);
+        assert_eq!(flaky[0].pass_rate_pct, 50);
+    }
+
+    #[test]
+    fn test_duration_trend_ignores_failures() {
+        let records = runs(
+            "api",
+            "build",
+            &[(true, 100), (true, 100), (false, 5), (true, 150), (true, 150)],
+        );
+        let trends = duration_trends(&records, 10);
+        assert_eq!(trends.len(), 1);
+        assert_eq!(trends[0].baseline_ms, 100);
+        assert_eq!(trends[0].recent_ms, 150);
+        assert_eq!(trends[0].change_pct, 50);
+
+        let steady = runs("web", "build", &[(true, 100), (true, 105), (true, 100), (true, 102)]);
+        assert!(duration_trends(&steady, 10).is_empty());
+    }
+
+    #[test]
+    fn test_append_compacts_per_step() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let outcomes = vec![(true, 10); MAX_RUNS_PER_STEP + 5];
+        let policy = ArtifactPolicy::plaintext();
+        append(temp.path(), &runs("api", "build", &outcomes), &policy).unwrap();
+        append(temp.path(), &runs("web", "build", &[(true, 10)]), &policy).unwrap();
+
+        let loaded = load(temp.path(), &policy);
+        assert_eq!(loaded.iter().filter(|r| r.repo == "api").count(), MAX_RUNS_PER_STEP);
+        assert_eq!(loaded.iter().filter(|r| r.repo == "web").count(), 1);
+    }
+}
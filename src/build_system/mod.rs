@@ -4,14 +4,21 @@
 //! parses manifests for dependency information, and generates native
 //! workspace files for unified builds.
 
+pub mod bench;
 pub mod cabal;
 pub mod cabal_project;
 pub mod cargo;
 pub mod cargo_config;
+pub mod coverage;
 pub mod cross_compile;
 pub mod dep_graph;
+pub mod generated;
+pub mod graph_export;
+pub mod history;
 pub mod node;
+pub mod node_workspace;
 pub mod publish;
+pub mod signing;
 pub mod validate;
 pub mod version;
 
@@ -54,6 +61,9 @@ pub struct DepRef {
     /// Whether this is a dev/test-only dependency
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub is_dev: bool,
+    /// Whether this dependency is optional (only pulled in behind a feature)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_optional: bool,
 }
 
 /// A binary/executable target detected from a build manifest
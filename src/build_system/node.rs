@@ -65,6 +65,7 @@ pub fn parse_node_deps(path: &Path) -> Vec<DepRef> {
                     is_path_dep: is_path,
                     path,
                     is_dev,
+                    is_optional: false,
                 });
             }
         }
@@ -0,0 +1,283 @@
+//! Node workspace file generator (pnpm / yarn)
+//!
+//! Generates `pnpm-workspace.yaml` — or the `workspaces` field of a root
+//! `package.json` for yarn — listing every Node package in the workspace,
+//! plus `file:` overrides so sibling packages resolve locally instead of
+//! from the registry. Parallels the Cargo patch and cabal.project generation.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use super::generated::{self, Drift, DriftPolicy, Reconcile};
+use super::DepRef;
+
+/// Report from Node workspace generation
+#[derive(Debug, Clone)]
+pub struct NodeWorkspaceReport {
+    pub manager: NodeWorkspaceManager,
+    pub packages: Vec<String>,
+    pub overrides: Vec<(String, String)>, // (package_name, file: spec)
+    pub content: String,
+    pub action: FileAction,
+    /// Set when the workspace file was edited by hand since it was last generated
+    pub drift: Option<Drift>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileAction {
+    Created,
+    Updated,
+    Unchanged,
+    Merged,
+    Conflict,
+}
+
+/// Which package manager's workspace format to generate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeWorkspaceManager {
+    Pnpm,
+    Yarn,
+}
+
+impl NodeWorkspaceManager {
+    /// Pick the workspace format already in use at the root.
+    ///
+    /// An existing `pnpm-workspace.yaml` means pnpm; a root `package.json`,
+    /// `yarn.lock` or `.yarnrc.yml` means yarn. Otherwise default to pnpm.
+    pub fn detect(workspace_root: &Path) -> Self {
+        if workspace_root.join("pnpm-workspace.yaml").exists() {
+            Self::Pnpm
+        } else if ["package.json", "yarn.lock", ".yarnrc.yml"]
+            .iter()
+            .any(|f| workspace_root.join(f).exists())
+        {
+            Self::Yarn
+        } else {
+            Self::Pnpm
+        }
+    }
+
+    /// Workspace file written for this manager, relative to the root
+    pub const fn file_name(self) -> &'static str {
+        match self {
+            Self::Pnpm => "pnpm-workspace.yaml",
+            Self::Yarn => "package.json",
+        }
+    }
+}
+
+impl std::fmt::Display for NodeWorkspaceManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pnpm => write!(f, "pnpm"),
+            Self::Yarn => write!(f, "yarn"),
+        }
+    }
+}
+
+/// Information about a Node package in the workspace
+#[derive(Debug, Clone)]
+pub struct NodePackageInfo {
+    pub name: String,
+    pub path: String, // relative to workspace root
+    pub dependencies: Vec<DepRef>,
+}
+
+/// Generate the Node workspace file from detected Node packages.
+pub fn generate_node_workspace(
+    workspace_root: &Path,
+    packages: &[NodePackageInfo],
+    dry_run: bool,
+    policy: DriftPolicy,
+) -> Result<NodeWorkspaceReport, String> {
+    if packages.is_empty() {
+        return Err("No Node packages found in workspace".to_string());
+    }
+
+    let manager = NodeWorkspaceManager::detect(workspace_root);
+
+    let mut pkg_paths: Vec<String> = packages.iter().map(|p| p.path.clone()).collect();
+    pkg_paths.sort();
+
+    // Override every sibling that another sibling pulls from the registry
+    let names: HashSet<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+    let mut overrides: BTreeMap<String, String> = BTreeMap::new();
+    for pkg in packages {
+        for dep in &pkg.dependencies {
+            if dep.is_path_dep || !names.contains(dep.name.as_str()) {
+                continue;
+            }
+            if let Some(target) = packages.iter().find(|p| p.name == dep.name) {
+                overrides
+                    .entry(dep.name.clone())
+                    .or_insert_with(|| format!("file:./{}", target.path));
+            }
+        }
+    }
+    let overrides: Vec<(String, String)> = overrides.into_iter().collect();
+
+    let content = match manager {
+        NodeWorkspaceManager::Pnpm => render_pnpm_workspace(&pkg_paths, &overrides),
+        NodeWorkspaceManager::Yarn => {
+            let existing = std::fs::read_to_string(workspace_root.join("package.json")).ok();
+            render_yarn_root(existing.as_deref(), &pkg_paths, &overrides)?
+        }
+    };
+
+    let rel_path = manager.file_name();
+    let reconciled = generated::reconcile(workspace_root, rel_path, &content, policy)?;
+    if !dry_run {
+        generated::apply(workspace_root, rel_path, &reconciled, &content)?;
+    }
+    let action = match reconciled.action {
+        Reconcile::Created => FileAction::Created,
+        Reconcile::Updated => FileAction::Updated,
+        Reconcile::Unchanged => FileAction::Unchanged,
+        Reconcile::Merged => FileAction::Merged,
+        Reconcile::Conflict => FileAction::Conflict,
+    };
+
+    Ok(NodeWorkspaceReport {
+        manager,
+        packages: pkg_paths,
+        overrides,
+        content,
+        action,
+        drift: reconciled.drift,
+    })
+}
+
+/// Render `pnpm-workspace.yaml`. Strings are emitted JSON-quoted, which is
+/// valid YAML and keeps scoped names like `@scope/pkg` safe.
+fn render_pnpm_workspace(pkg_paths: &[String], overrides: &[(String, String)]) -> String {
+    let quote = |s: &str| serde_json::to_string(s).unwrap_or_default();
+
+    let mut content = String::new();
+    content.push_str("# Generated by hyperforge workspace unify\n");
+    content.push_str("# Do not edit manually; re-run `workspace unify` to regenerate.\n\n");
+    content.push_str("packages:\n");
+    for path in pkg_paths {
+        content.push_str(&format!("  - {}\n", quote(path)));
+    }
+    if !overrides.is_empty() {
+        content.push_str("\noverrides:\n");
+        for (name, spec) in overrides {
+            content.push_str(&format!("  {}: {}\n", quote(name), quote(spec)));
+        }
+    }
+    content
+}
+
+/// Merge `workspaces` and `resolutions` into the root `package.json`,
+/// keeping every other field the user has there.
+fn render_yarn_root(
+    existing: Option<&str>,
+    pkg_paths: &[String],
+    overrides: &[(String, String)],
+) -> Result<String, String> {
+    let mut doc: serde_json::Value = match existing {
+        Some(text) => serde_json::from_str(text)
+            .map_err(|e| format!("Failed to parse root package.json: {e}"))?,
+        None => serde_json::json!({ "private": true }),
+    };
+    let obj = doc
+        .as_object_mut()
+        .ok_or_else(|| "Root package.json is not a JSON object".to_string())?;
+
+    obj.insert("workspaces".to_string(), serde_json::json!(pkg_paths));
+    if overrides.is_empty() {
+        obj.remove("resolutions");
+    } else {
+        let resolutions: serde_json::Map<String, serde_json::Value> = overrides
+            .iter()
+            .map(|(name, spec)| (name.clone(), serde_json::Value::from(spec.as_str())))
+            .collect();
+        obj.insert("resolutions".to_string(), serde_json::Value::Object(resolutions));
+    }
+
+    let mut text = serde_json::to_string_pretty(&doc)
+        .map_err(|e| format!("Failed to serialize root package.json: {e}"))?;
+    text.push('\n');
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_packages() -> Vec<NodePackageInfo> {
+        vec![
+            NodePackageInfo {
+                name: "@acme/ui".to_string(),
+                path: "ui".to_string(),
+                dependencies: vec![],
+            },
+            NodePackageInfo {
+                name: "web".to_string(),
+                path: "web".to_string(),
+                dependencies: vec![
+                    DepRef {
+                        name: "@acme/ui".to_string(),
+                        version_req: Some("^1.0.0".to_string()),
+                        is_path_dep: false,
+                        path: None,
+                        is_dev: false,
+                        is_optional: false,
+                    },
+                    DepRef {
+                        name: "react".to_string(),
+                        version_req: Some("^18.0.0".to_string()),
+                        is_path_dep: false,
+                        path: None,
+                        is_dev: false,
+                        is_optional: false,
+                    },
+                ],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_generate_pnpm_workspace() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let report =
+            generate_node_workspace(tmp.path(), &sample_packages(), true, DriftPolicy::Refuse)
+                .unwrap();
+
+        assert_eq!(report.manager, NodeWorkspaceManager::Pnpm);
+        assert_eq!(report.packages, vec!["ui", "web"]);
+        assert_eq!(
+            report.overrides,
+            vec![("@acme/ui".to_string(), "file:./ui".to_string())]
+        );
+        assert!(report.content.contains("packages:\n  - \"ui\"\n  - \"web\"\n"));
+        assert!(report.content.contains("\"@acme/ui\": \"file:./ui\""));
+        assert_eq!(report.action, FileAction::Created);
+        assert!(!tmp.path().join("pnpm-workspace.yaml").exists());
+
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&report.content).unwrap();
+        assert_eq!(parsed["overrides"]["@acme/ui"], "file:./ui");
+    }
+
+    #[test]
+    fn test_generate_yarn_workspace_preserves_fields() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("package.json"),
+            r#"{"name": "root", "private": true, "scripts": {"build": "tsc -b"}}"#,
+        )
+        .unwrap();
+
+        let report =
+            generate_node_workspace(tmp.path(), &sample_packages(), false, DriftPolicy::Refuse)
+                .unwrap();
+        assert_eq!(report.manager, NodeWorkspaceManager::Yarn);
+        assert_eq!(report.action, FileAction::Updated);
+
+        let written = std::fs::read_to_string(tmp.path().join("package.json")).unwrap();
+        let doc: serde_json::Value = serde_json::from_str(&written).unwrap();
+        assert_eq!(doc["scripts"]["build"], "tsc -b");
+        assert_eq!(doc["workspaces"], serde_json::json!(["ui", "web"]));
+        assert_eq!(doc["resolutions"]["@acme/ui"], "file:./ui");
+    }
+}
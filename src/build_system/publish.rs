@@ -105,8 +105,19 @@ pub async fn build_publish_plan(
             _ => super::BuildSystemKind::Unknown,
         };
 
-        // Get registry client
-        let registry = if let Some(r) = package::registry_for(&build_system) { r } else {
+        let pkg_path = workspace_root.join(&node.path);
+
+        // Get registry client: the public registry, else the first forge
+        // package registry the repo publishes to
+        let registry: Box<dyn package::RegistryClient> = if let Some(r) = package::registry_for(&build_system) {
+            r
+        } else if let Some(r) = package::forge_registries_for(&pkg_path, &build_system)
+            .await
+            .into_iter()
+            .find_map(|(_, client)| client.ok())
+        {
+            Box::new(r)
+        } else {
             excluded.push((
                 node.name.clone(),
                 format!("no registry for build system '{}'", node.build_system),
@@ -119,8 +130,6 @@ pub async fn build_publish_plan(
             continue;
         };
 
-        let pkg_path = workspace_root.join(&node.path);
-
         // Query registry for published version
         let published = match registry.published_version(&node.name).await {
             Ok(pv) => pv,
@@ -251,6 +260,7 @@ mod tests {
                     is_path_dep: true,
                     path: Some("../b".to_string()),
                     is_dev: false,
+                    is_optional: false,
                 }],
             ),
             (
@@ -261,6 +271,7 @@ mod tests {
                     is_path_dep: true,
                     path: Some("../c".to_string()),
                     is_dev: false,
+                    is_optional: false,
                 }],
             ),
         ];
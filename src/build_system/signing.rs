@@ -0,0 +1,342 @@
+//! Release artifact signing and SLSA provenance
+//!
+//! With `[dist.signing]` configured, every release asset is signed with
+//! cosign (`<asset>.sig`) or minisign (`<asset>.minisig`), and an in-toto
+//! statement carrying an SLSA v1 provenance predicate over the assets'
+//! digests is written alongside them (and signed too). Private keys and
+//! their passwords are read from the secrets store by path; the key only
+//! touches disk as an owner-only temp file for the duration of the run.
+
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+
+use super::cross_compile::sha256_file;
+use crate::types::config::{SigningConfig, SigningTool};
+
+impl SigningTool {
+    /// Extension appended to an artifact's name for its signature
+    pub const fn signature_extension(self) -> &'static str {
+        match self {
+            Self::Cosign => ".sig",
+            Self::Minisign => ".minisig",
+        }
+    }
+
+    /// The tool that produced a signature file, by extension
+    pub fn for_signature(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        [Self::Cosign, Self::Minisign]
+            .into_iter()
+            .find(|t| name.ends_with(t.signature_extension()))
+    }
+
+    pub const fn program(self) -> &'static str {
+        match self {
+            Self::Cosign => "cosign",
+            Self::Minisign => "minisign",
+        }
+    }
+}
+
+/// in-toto statement type
+const STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+/// SLSA provenance predicate type
+pub const PROVENANCE_PREDICATE: &str = "https://slsa.dev/provenance/v1";
+/// Build type recorded in provenance for `build release`
+const BUILD_TYPE: &str = "https://github.com/juggernautlabs/hyperforge/release/v1";
+
+/// A signer holding its private key in an owner-only temp file, removed on drop
+pub struct Signer {
+    tool: SigningTool,
+    key_file: PathBuf,
+    password: Option<String>,
+}
+
+impl Signer {
+    /// Resolve the key (and password) from the secrets store
+    pub async fn from_config(config: &SigningConfig) -> Result<Self, String> {
+        let store = crate::auth::secret_env::load_store().await?;
+        let lookup = |path: &str| {
+            store
+                .get(&crate::auth_hub::types::SecretPath::new(path))
+                .ok()
+                .map(|s| s.value)
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| format!("signing secret '{path}' is not set"))
+        };
+        let key = lookup(&config.key)?;
+        let password = config.password.as_deref().map(lookup).transpose()?;
+
+        let key_file = std::env::temp_dir().join(format!("hyperforge-signing-{}.key", uuid::Uuid::new_v4()));
+        write_private(&key_file, &key)?;
+        Ok(Self { tool: config.tool, key_file, password })
+    }
+
+    pub const fn tool(&self) -> SigningTool {
+        self.tool
+    }
+
+    /// Sign `artifact`, writing the signature next to it; returns its path
+    pub async fn sign(&self, artifact: &Path) -> Result<PathBuf, String> {
+        let signature = signature_path(artifact, self.tool);
+        let password = self.password.clone().unwrap_or_default();
+        let mut cmd = tokio::process::Command::new(self.tool.program());
+        match self.tool {
+            SigningTool::Cosign => {
+                cmd.arg("sign-blob")
+                    .arg("--yes")
+                    .arg("--key")
+                    .arg(&self.key_file)
+                    .arg("--output-signature")
+                    .arg(&signature)
+                    .arg(artifact)
+                    .env("COSIGN_PASSWORD", &password);
+            }
+            SigningTool::Minisign => {
+                cmd.arg("-S")
+                    .arg("-s")
+                    .arg(&self.key_file)
+                    .arg("-m")
+                    .arg(artifact)
+                    .arg("-x")
+                    .arg(&signature);
+            }
+        }
+        cmd.stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped());
+
+        let mut child = cmd.spawn().map_err(|e| format!("failed to spawn {}: {e}", self.tool))?;
+        // minisign reads the key password from stdin
+        if let Some(mut stdin) = child.stdin.take() {
+            if self.tool == SigningTool::Minisign && !password.is_empty() {
+                use tokio::io::AsyncWriteExt;
+                let _ = stdin.write_all(format!("{password}\n").as_bytes()).await;
+            }
+        }
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| format!("{} failed: {e}", self.tool))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stderr = if password.is_empty() { stderr.to_string() } else { stderr.replace(&password, "***") };
+            return Err(format!("{} failed to sign {}: {}", self.tool, artifact.display(), stderr.trim()));
+        }
+        Ok(signature)
+    }
+}
+
+impl Drop for Signer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.key_file);
+    }
+}
+
+fn write_private(path: &Path, content: &str) -> Result<(), String> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to create {}: {e}", path.display()))?;
+    std::io::Write::write_all(&mut file, content.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// Where `tool` puts the signature of `artifact`
+pub fn signature_path(artifact: &Path, tool: SigningTool) -> PathBuf {
+    let mut name = artifact.file_name().unwrap_or_default().to_os_string();
+    name.push(tool.signature_extension());
+    artifact.with_file_name(name)
+}
+
+/// What a release was built from, for provenance
+pub struct BuildSource<'a> {
+    /// Repo web URL
+    pub repository: &'a str,
+    pub tag: &'a str,
+    pub commit: &'a str,
+    pub started_on: DateTime<Utc>,
+}
+
+/// An in-toto statement with an SLSA v1 provenance predicate whose
+/// subjects are `artifacts`, identified by file name and sha256.
+pub fn provenance_statement(artifacts: &[PathBuf], source: &BuildSource<'_>) -> Result<serde_json::Value, String> {
+    let mut subjects = Vec::new();
+    for artifact in artifacts {
+        let name = artifact.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        subjects.push(serde_json::json!({
+            "name": name,
+            "digest": { "sha256": sha256_file(artifact)? },
+        }));
+    }
+    Ok(serde_json::json!({
+        "_type": STATEMENT_TYPE,
+        "subject": subjects,
+        "predicateType": PROVENANCE_PREDICATE,
+        "predicate": {
+            "buildDefinition": {
+                "buildType": BUILD_TYPE,
+                "externalParameters": {
+                    "repository": source.repository,
+                    "ref": format!("refs/tags/{}", source.tag),
+                },
+                "resolvedDependencies": [{
+                    "uri": format!("git+{}@refs/tags/{}", source.repository, source.tag),
+                    "digest": { "gitCommit": source.commit },
+                }],
+            },
+            "runDetails": {
+                "builder": {
+                    "id": format!("https://github.com/juggernautlabs/hyperforge@v{}", env!("CARGO_PKG_VERSION")),
+                },
+                "metadata": {
+                    "invocationId": uuid::Uuid::new_v4().to_string(),
+                    "startedOn": source.started_on.to_rfc3339(),
+                    "finishedOn": Utc::now().to_rfc3339(),
+                },
+            },
+        },
+    }))
+}
+
+/// Write a provenance statement as `<stem>.intoto.json` in `dir`
+pub fn write_provenance(dir: &Path, stem: &str, statement: &serde_json::Value) -> Result<PathBuf, String> {
+    let path = dir.join(format!("{stem}.intoto.json"));
+    let content = serde_json::to_string_pretty(statement)
+        .map_err(|e| format!("Failed to serialize provenance: {e}"))?;
+    std::fs::write(&path, content + "\n").map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    Ok(path)
+}
+
+/// Check a signature with the signer's public key
+pub async fn verify_signature(
+    tool: SigningTool,
+    public_key: &Path,
+    artifact: &Path,
+    signature: &Path,
+) -> Result<(), String> {
+    let mut cmd = tokio::process::Command::new(tool.program());
+    match tool {
+        SigningTool::Cosign => {
+            cmd.arg("verify-blob")
+                .arg("--key")
+                .arg(public_key)
+                .arg("--signature")
+                .arg(signature)
+                .arg(artifact);
+        }
+        SigningTool::Minisign => {
+            cmd.arg("-V").arg("-p").arg(public_key).arg("-m").arg(artifact).arg("-x").arg(signature);
+        }
+    }
+    let output = cmd.output().await.map_err(|e| format!("failed to spawn {tool}: {e}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Provenance check result for one artifact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubjectCheck {
+    /// Listed with a matching sha256
+    Match,
+    /// Listed, but the file's digest differs
+    Mismatch { expected: String, actual: String },
+    /// Not a subject of the statement
+    Missing,
+}
+
+/// Check artifacts against the subjects of a provenance statement
+pub fn verify_provenance(statement: &serde_json::Value, artifacts: &[PathBuf]) -> Result<Vec<(PathBuf, SubjectCheck)>, String> {
+    if statement.get("predicateType").and_then(serde_json::Value::as_str) != Some(PROVENANCE_PREDICATE) {
+        return Err(format!("not an SLSA provenance statement (expected predicateType {PROVENANCE_PREDICATE})"));
+    }
+    let subjects: Vec<(&str, &str)> = statement
+        .get("subject")
+        .and_then(serde_json::Value::as_array)
+        .map(|subjects| {
+            subjects
+                .iter()
+                .filter_map(|s| {
+                    let name = s.get("name")?.as_str()?;
+                    let digest = s.get("digest")?.get("sha256")?.as_str()?;
+                    Some((name, digest))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut results = Vec::new();
+    for artifact in artifacts {
+        let name = artifact.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let check = match subjects.iter().find(|(n, _)| *n == name) {
+            None => SubjectCheck::Missing,
+            Some((_, expected)) => {
+                let actual = sha256_file(artifact)?;
+                if actual == *expected {
+                    SubjectCheck::Match
+                } else {
+                    SubjectCheck::Mismatch { expected: (*expected).to_string(), actual }
+                }
+            }
+        };
+        results.push((artifact.clone(), check));
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_paths() {
+        let artifact = Path::new("/dist/tool-x86_64-unknown-linux-gnu-v1.0.0.tar.gz");
+        let sig = signature_path(artifact, SigningTool::Cosign);
+        assert_eq!(sig, Path::new("/dist/tool-x86_64-unknown-linux-gnu-v1.0.0.tar.gz.sig"));
+        assert_eq!(SigningTool::for_signature(&sig), Some(SigningTool::Cosign));
+        assert_eq!(
+            SigningTool::for_signature(&signature_path(artifact, SigningTool::Minisign)),
+            Some(SigningTool::Minisign)
+        );
+        assert_eq!(SigningTool::for_signature(artifact), None);
+    }
+
+    #[test]
+    fn test_provenance_round_trip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let a = temp.path().join("a.tar.gz");
+        let b = temp.path().join("b.tar.gz");
+        std::fs::write(&a, b"a").unwrap();
+        std::fs::write(&b, b"b").unwrap();
+
+        let source = BuildSource {
+            repository: "https://github.com/acme/tool",
+            tag: "v1.0.0",
+            commit: "abc123",
+            started_on: Utc::now(),
+        };
+        let statement = provenance_statement(std::slice::from_ref(&a), &source).unwrap();
+        assert_eq!(statement["predicate"]["buildDefinition"]["resolvedDependencies"][0]["digest"]["gitCommit"], "abc123");
+        let path = write_provenance(temp.path(), "tool-v1.0.0", &statement).unwrap();
+        let statement: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+        let results = verify_provenance(&statement, &[a.clone(), b]).unwrap();
+        assert_eq!(results[0].1, SubjectCheck::Match);
+        assert_eq!(results[1].1, SubjectCheck::Missing);
+
+        std::fs::write(&a, b"tampered").unwrap();
+        let results = verify_provenance(&statement, &[a]).unwrap();
+        assert!(matches!(results[0].1, SubjectCheck::Mismatch { .. }));
+
+        assert!(verify_provenance(&serde_json::json!({}), &[]).is_err());
+    }
+}
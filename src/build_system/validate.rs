@@ -3,11 +3,13 @@
 //! Runs builds and tests in Docker containers using the dependency graph
 //! for ordering and `CiConfig` for per-repo overrides.
 
+use std::collections::HashSet;
 use std::path::Path;
 use std::process::Command;
 use std::time::Instant;
 
 use super::dep_graph::DepGraph;
+use crate::scheduler::{Resource, Scheduler};
 
 /// CI configuration for a specific repo
 #[derive(Debug, Clone)]
@@ -18,7 +20,15 @@ pub struct RepoCiConfig {
     pub dockerfile: Option<String>,
     pub skip: bool,
     pub timeout_secs: u64,
+    /// Parallel containers to split the test step across
+    pub test_shards: u32,
+    /// Command writing LCOV to `/coverage/lcov.info`; empty means no coverage
+    pub coverage_command: Vec<String>,
+    /// Fail when line coverage is below this percentage
+    pub min_line_pct: Option<f64>,
     pub env: Vec<(String, String)>,
+    /// Resolved secret values in `env`, redacted from step output
+    pub secrets: Vec<String>,
 }
 
 impl Default for RepoCiConfig {
@@ -30,7 +40,11 @@ impl Default for RepoCiConfig {
             dockerfile: None,
             skip: false,
             timeout_secs: 300,
+            test_shards: 1,
+            coverage_command: Vec::new(),
+            min_line_pct: None,
             env: Vec::new(),
+            secrets: Vec::new(),
         }
     }
 }
@@ -82,6 +96,16 @@ pub struct ValidationPlan {
     pub run_tests: bool,
     /// Docker image to use (if not per-repo)
     pub default_image: String,
+    /// Only test repos affected by changes since this git ref; set with
+    /// [`ValidationPlan::narrow_to_changes`]
+    pub changed_since: Option<String>,
+    /// Repos whose own tree changed since `changed_since`
+    pub changed: HashSet<String>,
+    /// Repos tested under `changed_since`: the changed ones and every repo
+    /// depending on them
+    pub affected: HashSet<String>,
+    /// Run each repo's coverage command after its tests
+    pub coverage: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -133,9 +157,45 @@ pub fn build_validation_plan(
         steps,
         run_tests,
         default_image: "rust:latest".to_string(),
+        changed_since: None,
+        changed: HashSet::new(),
+        affected: HashSet::new(),
+        coverage: false,
     })
 }
 
+impl ValidationPlan {
+    /// Test only what changes since `since` can break: the repos in
+    /// `changed` and, through the dependency graph, every repo that depends
+    /// on one of them, even when its own tree is untouched.
+    pub fn narrow_to_changes(&mut self, graph: &DepGraph, since: String, changed: &[String]) {
+        let indices: Vec<usize> = graph
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| changed.contains(&node.name))
+            .map(|(i, _)| i)
+            .collect();
+        self.affected = graph.affected(&indices).into_iter().map(|i| graph.nodes[i].name.clone()).collect();
+        self.changed = changed.iter().cloned().collect();
+        self.changed_since = Some(since);
+    }
+}
+
+/// Repos with changes since `since`. A repo git can't diff (unknown ref,
+/// not a repo) counts as changed.
+pub fn changed_repos(graph: &DepGraph, workspace_root: &Path, since: &str) -> Vec<String> {
+    graph
+        .nodes
+        .iter()
+        .filter(|node| {
+            crate::git::Git::changed_files(&workspace_root.join(&node.path), since)
+                .map_or(true, |files| !files.is_empty())
+        })
+        .map(|node| node.name.clone())
+        .collect()
+}
+
 /// Execute a validation plan using Docker.
 ///
 /// Returns step results as they complete. The workspace is bind-mounted
@@ -159,6 +219,21 @@ pub fn execute_validation(
             continue;
         }
 
+        // Per-repo image from `ci.dockerfile`, built once per dockerfile content
+        let image = match &step.ci_config.dockerfile {
+            Some(dockerfile) => match ensure_dockerfile_image(workspace_root, step, dockerfile, dry_run) {
+                Ok((tag, built)) => {
+                    results.extend(built);
+                    tag
+                }
+                Err(failed) => {
+                    results.push(failed);
+                    continue;
+                }
+            },
+            None => plan.default_image.clone(),
+        };
+
         // Build step
         let build_result = if dry_run {
             ValidateStepResult {
@@ -177,8 +252,9 @@ pub fn execute_validation(
                 workspace_root,
                 &step.repo_path,
                 &step.ci_config.build_command,
-                &step.ci_config.env,
-                &plan.default_image,
+                &step.ci_config,
+                &[],
+                &image,
                 step.ci_config.timeout_secs,
                 "build",
                 &step.repo_name,
@@ -190,43 +266,311 @@ pub fn execute_validation(
 
         // Test step (only if build passed and tests requested)
         if plan.run_tests && build_passed {
-            let test_result = if dry_run {
-                ValidateStepResult {
-                    repo_name: step.repo_name.clone(),
-                    step: "test".to_string(),
-                    status: StepStatus::Passed,
-                    duration_ms: 0,
-                    output: Some(format!(
-                        "[DRY RUN] Would run: {} in /workspace/{}",
-                        step.ci_config.test_command.join(" "),
-                        step.repo_path
-                    )),
+            results.extend(run_test_step(workspace_root, plan, step, &image, dry_run));
+        }
+
+        if plan.coverage && build_passed && !step.ci_config.coverage_command.is_empty() {
+            results.push(run_coverage_step(workspace_root, step, &image, dry_run));
+        }
+    }
+
+    results
+}
+
+/// Test command runners that can split or narrow a test run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TestRunner {
+    Nextest,
+    Jest,
+    Other,
+}
+
+fn test_runner(command: &[String]) -> TestRunner {
+    if command.iter().any(|a| a == "nextest") {
+        TestRunner::Nextest
+    } else if command.iter().any(|a| a == "jest" || a.ends_with("/jest")) {
+        TestRunner::Jest
+    } else {
+        TestRunner::Other
+    }
+}
+
+/// Insert runner flags before any `--` separator so they aren't forwarded
+/// to the test binaries.
+fn with_args(command: &[String], extra: &[String]) -> Vec<String> {
+    let split = command.iter().position(|a| a == "--").unwrap_or(command.len());
+    let mut out = command[..split].to_vec();
+    out.extend(extra.iter().cloned());
+    out.extend(command[split..].iter().cloned());
+    out
+}
+
+/// The test command for each shard.
+///
+/// Nextest gets `--partition count:i/n` and jest `--shard=i/n`; other runners
+/// can't be partitioned and run as a single shard. With `changed_since`, jest
+/// also gets `--changedSince` so it only runs tests affected by the change;
+/// nextest and other runners have no equivalent and run their whole suite.
+pub fn test_shard_commands(
+    command: &[String],
+    shards: u32,
+    changed_since: Option<&str>,
+) -> Vec<Vec<String>> {
+    let runner = test_runner(command);
+    let mut base = Vec::new();
+    if let (TestRunner::Jest, Some(since)) = (runner, changed_since) {
+        base.push(format!("--changedSince={since}"));
+    }
+    let shards = if runner == TestRunner::Other { 1 } else { shards.max(1) };
+    if shards == 1 {
+        return vec![with_args(command, &base)];
+    }
+    (1..=shards)
+        .map(|i| {
+            let mut extra = base.clone();
+            match runner {
+                TestRunner::Nextest => {
+                    extra.push("--partition".to_string());
+                    extra.push(format!("count:{i}/{shards}"));
                 }
-            } else {
-                run_docker_step(
-                    workspace_root,
-                    &step.repo_path,
-                    &step.ci_config.test_command,
-                    &step.ci_config.env,
-                    &plan.default_image,
-                    step.ci_config.timeout_secs,
-                    "test",
-                    &step.repo_name,
+                TestRunner::Jest => extra.push(format!("--shard={i}/{shards}")),
+                TestRunner::Other => {}
+            }
+            with_args(command, &extra)
+        })
+        .collect()
+}
+
+/// Run a repo's tests, possibly split across parallel containers.
+///
+/// With `plan.changed_since`, a repo outside `plan.affected` is skipped, and
+/// jest narrows only in repos that changed themselves: in one tested for a
+/// dependency's change, `--changedSince` would select nothing.
+fn run_test_step(
+    workspace_root: &Path,
+    plan: &ValidationPlan,
+    step: &ValidationStep,
+    image: &str,
+    dry_run: bool,
+) -> Vec<ValidateStepResult> {
+    let ci = &step.ci_config;
+    let result = |name: String, status, output: String| ValidateStepResult {
+        repo_name: step.repo_name.clone(),
+        step: name,
+        status,
+        duration_ms: 0,
+        output: Some(output),
+    };
+
+    if let Some(since) = &plan.changed_since {
+        if !plan.affected.contains(&step.repo_name) {
+            return vec![result(
+                "test".to_string(),
+                StepStatus::Skipped,
+                format!("No changes since {since} in it or its dependencies"),
+            )];
+        }
+    }
+
+    let narrow_since = plan.changed_since.as_deref().filter(|_| plan.changed.contains(&step.repo_name));
+    let commands = test_shard_commands(&ci.test_command, ci.test_shards, narrow_since);
+    let count = commands.len();
+    let step_name = |i: usize| {
+        if count == 1 { "test".to_string() } else { format!("test[{}/{count}]", i + 1) }
+    };
+
+    if dry_run {
+        return commands
+            .iter()
+            .enumerate()
+            .map(|(i, cmd)| {
+                result(
+                    step_name(i),
+                    StepStatus::Passed,
+                    format!("[DRY RUN] Would run: {} in /workspace/{}", cmd.join(" "), step.repo_path),
                 )
-            };
-            results.push(test_result);
+            })
+            .collect();
+    }
+
+    let run = |i: usize, cmd: &[String]| {
+        run_docker_step(
+            workspace_root,
+            &step.repo_path,
+            cmd,
+            ci,
+            &[],
+            image,
+            ci.timeout_secs,
+            &step_name(i),
+            &step.repo_name,
+        )
+    };
+    if count == 1 {
+        return vec![run(0, &commands[0])];
+    }
+    std::thread::scope(|scope| {
+        // Spawn every shard before joining any, so they run concurrently
+        let mut handles = Vec::with_capacity(count);
+        for (i, cmd) in commands.iter().enumerate() {
+            handles.push(scope.spawn(move || run(i, cmd)));
+        }
+        handles
+            .into_iter()
+            .enumerate()
+            .map(|(i, h)| {
+                h.join().unwrap_or_else(|_| {
+                    result(step_name(i), StepStatus::Failed, "Shard thread panicked".to_string())
+                })
+            })
+            .collect()
+    })
+}
+
+/// Image tag for a repo's validation dockerfile: `hyperforge-validate/<repo>:<hash>`.
+///
+/// The hash covers the dockerfile content (not the rest of the build
+/// context), so editing the dockerfile is what triggers a rebuild.
+pub fn dockerfile_image_tag(repo_name: &str, dockerfile_content: &str) -> String {
+    let repo: String = repo_name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '-' })
+        .collect();
+    let hash = super::generated::content_hash(dockerfile_content);
+    format!("hyperforge-validate/{repo}:{}", &hash[..12])
+}
+
+/// Build the repo's validation image unless one for the same dockerfile
+/// content already exists.
+///
+/// Returns the tag plus an `image` step result when a build actually ran; a
+/// failed build is returned as the error.
+fn ensure_dockerfile_image(
+    workspace_root: &Path,
+    step: &ValidationStep,
+    dockerfile: &str,
+    dry_run: bool,
+) -> Result<(String, Option<ValidateStepResult>), ValidateStepResult> {
+    let repo_dir = workspace_root.join(&step.repo_path);
+    let dockerfile_path = repo_dir.join(dockerfile);
+    let result = |status, duration_ms, output: String| ValidateStepResult {
+        repo_name: step.repo_name.clone(),
+        step: "image".to_string(),
+        status,
+        duration_ms,
+        output: Some(output),
+    };
+
+    let content = std::fs::read_to_string(&dockerfile_path).map_err(|e| {
+        result(
+            StepStatus::Failed,
+            0,
+            format!("Failed to read ci.dockerfile {}: {e}", dockerfile_path.display()),
+        )
+    })?;
+    let tag = dockerfile_image_tag(&step.repo_name, &content);
+
+    let cached = Command::new("docker")
+        .args(["image", "inspect", &tag])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success());
+    if cached {
+        return Ok((tag, None));
+    }
+    if dry_run {
+        let output = format!("[DRY RUN] Would build {tag} from {dockerfile}");
+        return Ok((tag, Some(result(StepStatus::Passed, 0, output))));
+    }
+
+    let _slot = Scheduler::global().acquire_blocking(Resource::Container);
+    let start = Instant::now();
+    let output = Command::new("docker")
+        .arg("build")
+        .arg("-t")
+        .arg(&tag)
+        .arg("-f")
+        .arg(&dockerfile_path)
+        .arg(&repo_dir)
+        .output();
+    let duration_ms = start.elapsed().as_millis() as u64;
+
+    match output {
+        Ok(output) if output.status.success() => {
+            Ok((tag.clone(), Some(result(StepStatus::Passed, duration_ms, format!("Built {tag}")))))
         }
+        Ok(output) => Err(result(
+            StepStatus::Failed,
+            duration_ms,
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        )),
+        Err(e) => Err(result(StepStatus::Failed, duration_ms, format!("Failed to run docker: {e}"))),
     }
+}
 
-    results
+/// Run the repo's coverage command with its coverage dir mounted writable
+/// at `/coverage`.
+fn run_coverage_step(
+    workspace_root: &Path,
+    step: &ValidationStep,
+    image: &str,
+    dry_run: bool,
+) -> ValidateStepResult {
+    let ci = &step.ci_config;
+    if dry_run {
+        return ValidateStepResult {
+            repo_name: step.repo_name.clone(),
+            step: "coverage".to_string(),
+            status: StepStatus::Passed,
+            duration_ms: 0,
+            output: Some(format!(
+                "[DRY RUN] Would run: {} in /workspace/{}",
+                ci.coverage_command.join(" "),
+                step.repo_path
+            )),
+        };
+    }
+    let dir = match super::coverage::reset_repo_dir(workspace_root, &step.repo_name) {
+        Ok(dir) => dir,
+        Err(e) => {
+            return ValidateStepResult {
+                repo_name: step.repo_name.clone(),
+                step: "coverage".to_string(),
+                status: StepStatus::Failed,
+                duration_ms: 0,
+                output: Some(e),
+            };
+        }
+    };
+    let mount = vec![
+        "-v".to_string(),
+        format!("{}:{}", dir.display(), super::coverage::CONTAINER_DIR),
+    ];
+    run_docker_step(
+        workspace_root,
+        &step.repo_path,
+        &ci.coverage_command,
+        ci,
+        &mount,
+        image,
+        ci.timeout_secs,
+        "coverage",
+        &step.repo_name,
+    )
 }
 
 /// Run a single step inside a Docker container.
+///
+/// Env vars are forwarded by name (`-e KEY`) with values set on the docker
+/// process itself, so resolved secrets never appear in argv.
 fn run_docker_step(
     workspace_root: &Path,
     repo_path: &str,
     command: &[String],
-    env: &[(String, String)],
+    ci_config: &RepoCiConfig,
+    extra_args: &[String],
     image: &str,
     _timeout_secs: u64,
     step_name: &str,
@@ -248,16 +592,19 @@ fn run_docker_step(
         workdir,
     ];
 
-    for (key, val) in env {
+    for (key, _) in &ci_config.env {
         docker_args.push("-e".to_string());
-        docker_args.push(format!("{key}={val}"));
+        docker_args.push(key.clone());
     }
 
+    docker_args.extend(extra_args.iter().cloned());
     docker_args.push(image.to_string());
     docker_args.extend(command.iter().cloned());
 
+    let _slot = Scheduler::global().acquire_blocking(Resource::Container);
     let output = Command::new("docker")
         .args(&docker_args)
+        .envs(ci_config.env.iter().map(|(k, v)| (k, v)))
         .output();
 
     let duration_ms = start.elapsed().as_millis() as u64;
@@ -266,7 +613,10 @@ fn run_docker_step(
         Ok(output) => {
             let stdout = String::from_utf8_lossy(&output.stdout);
             let stderr = String::from_utf8_lossy(&output.stderr);
-            let combined = format!("{stdout}{stderr}");
+            let combined = crate::auth::secret_env::redact(
+                &format!("{stdout}{stderr}"),
+                &ci_config.secrets,
+            );
 
             if output.status.success() {
                 ValidateStepResult {
@@ -326,6 +676,32 @@ mod tests {
     use crate::build_system::dep_graph::{DepGraph, DepNode};
     use crate::build_system::DepRef;
 
+    #[test]
+    fn test_dockerfile_image_tag_tracks_content() {
+        let a = dockerfile_image_tag("My_Repo", "FROM rust:1.80\n");
+        let b = dockerfile_image_tag("My_Repo", "FROM rust:1.81\n");
+        assert!(a.starts_with("hyperforge-validate/my-repo:"));
+        assert_eq!(a.rsplit(':').next().unwrap().len(), 12);
+        assert_ne!(a, b);
+        assert_eq!(a, dockerfile_image_tag("My_Repo", "FROM rust:1.80\n"));
+    }
+
+    #[test]
+    fn test_shard_commands_per_runner() {
+        let cmd = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+
+        let nextest = test_shard_commands(&cmd("cargo nextest run -- --ignored"), 2, None);
+        assert_eq!(nextest.len(), 2);
+        assert_eq!(nextest[1].join(" "), "cargo nextest run --partition count:2/2 -- --ignored");
+
+        let jest = test_shard_commands(&cmd("npx jest"), 3, Some("origin/main"));
+        assert_eq!(jest.len(), 3);
+        assert_eq!(jest[0].join(" "), "npx jest --changedSince=origin/main --shard=1/3");
+
+        let plain = test_shard_commands(&cmd("cargo test"), 4, Some("origin/main"));
+        assert_eq!(plain, vec![cmd("cargo test")]);
+    }
+
     #[test]
     fn test_build_validation_plan() {
         let nodes = vec![
@@ -349,6 +725,7 @@ mod tests {
             is_path_dep: false,
             path: None,
             is_dev: false,
+            is_optional: false,
         }])];
 
         let graph = DepGraph::build(nodes, &deps);
@@ -361,6 +738,46 @@ mod tests {
         assert_eq!(plan.steps[1].tier, 1);
     }
 
+    #[test]
+    fn test_changed_since_tests_dependents() {
+        let node = |name: &str| DepNode {
+            name: name.to_string(),
+            version: Some("0.1.0".to_string()),
+            build_system: "npm".to_string(),
+            path: name.to_string(),
+        };
+        let dep = |name: &str| DepRef {
+            name: name.to_string(),
+            version_req: None,
+            is_path_dep: true,
+            path: None,
+            is_dev: false,
+            is_optional: false,
+        };
+        // app -> core, tool stands alone
+        let graph = DepGraph::build(vec![node("core"), node("app"), node("tool")], &[(1, vec![dep("core")])]);
+        let ci = |name: &str| RepoCiConfig {
+            repo_name: name.to_string(),
+            test_command: vec!["npx".to_string(), "jest".to_string()],
+            ..RepoCiConfig::default()
+        };
+        let mut plan = build_validation_plan(&graph, &[ci("core"), ci("app"), ci("tool")].map(|c| (c.repo_name.clone(), c)), true).unwrap();
+        plan.narrow_to_changes(&graph, "origin/main".to_string(), &["core".to_string()]);
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tested = |name: &str| {
+            let step = plan.steps.iter().find(|s| s.repo_name == name).unwrap();
+            run_test_step(tmp.path(), &plan, step, "node:22", true).remove(0)
+        };
+        let core = tested("core");
+        assert!(core.output.unwrap().contains("npx jest --changedSince=origin/main"));
+        // Unchanged itself, but its dependency changed: full jest run
+        let app = tested("app");
+        assert_eq!(app.status, StepStatus::Passed);
+        assert!(!app.output.unwrap().contains("--changedSince"));
+        assert_eq!(tested("tool").status, StepStatus::Skipped);
+    }
+
     #[test]
     fn test_dry_run_validation() {
         let nodes = vec![DepNode {
@@ -0,0 +1,256 @@
+//! `includeIf` stanzas in the user's gitconfig
+//!
+//! Instead of writing identity and `core.sshCommand` into every repo, each org
+//! with a `workspace_path` gets an include file under
+//! `~/.config/hyperforge/gitconfig/{org}.gitconfig`, and the user's global
+//! gitconfig gets one `[includeIf "gitdir:<workspace>/"]` stanza per org.
+//! The stanzas live between marker comments so regenerating them is
+//! idempotent and never touches the rest of the file.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::OrgConfig;
+
+/// First line of the managed block in the user's gitconfig
+pub const BEGIN_MARKER: &str = "# BEGIN hyperforge includeIf (managed; regenerate with config_gitconfig)";
+/// Last line of the managed block
+pub const END_MARKER: &str = "# END hyperforge includeIf";
+
+/// One org's include file and the directory it applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrgInclude {
+    pub org: String,
+    /// `gitdir:` pattern, always ending in `/` so it matches every repo below
+    pub gitdir: String,
+    pub path: PathBuf,
+    pub contents: String,
+}
+
+/// Path to an org's generated include file
+pub fn include_path(config_dir: &Path, org: &str) -> PathBuf {
+    config_dir.join("gitconfig").join(format!("{org}.gitconfig"))
+}
+
+/// The user's global gitconfig: `$GIT_CONFIG_GLOBAL`, else `~/.gitconfig`
+pub fn global_gitconfig_path() -> PathBuf {
+    std::env::var_os("GIT_CONFIG_GLOBAL").map_or_else(
+        || dirs::home_dir().unwrap_or_default().join(".gitconfig"),
+        PathBuf::from,
+    )
+}
+
+/// Include file contents for an org: its identity plus an SSH command. A
+/// single distinct key is used directly; several keys go through
+/// `hyperforge-ssh`, which picks one per forge.
+pub fn include_contents(org: &str, config: &OrgConfig) -> String {
+    let mut out = format!("# Generated by hyperforge for org '{org}'; do not edit\n");
+    let identity = &config.identity;
+    if !identity.is_empty() {
+        out.push_str("[user]\n");
+        if let Some(name) = &identity.name {
+            out.push_str(&format!("\tname = {name}\n"));
+        }
+        if let Some(email) = &identity.email {
+            out.push_str(&format!("\temail = {email}\n"));
+        }
+        if let Some(key) = &identity.signing_key {
+            out.push_str(&format!("\tsigningkey = {key}\n"));
+            out.push_str("[commit]\n\tgpgsign = true\n");
+        }
+    }
+
+    let mut keys: Vec<&String> = config.ssh.values().collect();
+    keys.sort();
+    keys.dedup();
+    match keys.as_slice() {
+        [] => {}
+        [key] => out.push_str(&format!("[core]\n\tsshCommand = ssh -i {key} -o IdentitiesOnly=yes\n")),
+        _ => out.push_str(&format!("[core]\n\tsshCommand = hyperforge-ssh\n[hyperforge]\n\torg = {org}\n")),
+    }
+    out
+}
+
+/// The include for an org, or `None` without a workspace path or anything
+/// to set
+pub fn org_include(config_dir: &Path, org: &str) -> Option<OrgInclude> {
+    let config = OrgConfig::load(config_dir, org);
+    let workspace = config.workspace_path.as_deref()?;
+    if config.identity.is_empty() && config.ssh.is_empty() {
+        return None;
+    }
+    let gitdir = format!("{}/", workspace.trim_end_matches('/'));
+    Some(OrgInclude {
+        org: org.to_string(),
+        gitdir,
+        path: include_path(config_dir, org),
+        contents: include_contents(org, &config),
+    })
+}
+
+/// The managed block for a set of includes (empty when there are none)
+pub fn managed_block(includes: &[OrgInclude]) -> String {
+    if includes.is_empty() {
+        return String::new();
+    }
+    let mut out = format!("{BEGIN_MARKER}\n");
+    for include in includes {
+        out.push_str(&format!(
+            "[includeIf \"gitdir:{}\"]\n\tpath = {}\n",
+            include.gitdir,
+            include.path.display()
+        ));
+    }
+    out.push_str(END_MARKER);
+    out.push('\n');
+    out
+}
+
+/// The managed block currently in `gitconfig`, if any
+pub fn current_block(gitconfig: &str) -> Option<&str> {
+    let start = gitconfig.find(BEGIN_MARKER)?;
+    let end = gitconfig[start..].find(END_MARKER)? + start + END_MARKER.len();
+    let end = if gitconfig[end..].starts_with('\n') { end + 1 } else { end };
+    Some(&gitconfig[start..end])
+}
+
+/// Replace the managed block in `gitconfig` with `block`, appending it if
+/// absent; an empty `block` removes it
+pub fn splice(gitconfig: &str, block: &str) -> String {
+    if let Some(current) = current_block(gitconfig) {
+        return gitconfig.replacen(current, block, 1);
+    }
+    if block.is_empty() {
+        return gitconfig.to_string();
+    }
+    let mut out = gitconfig.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out.push_str(block);
+    out
+}
+
+/// What regenerating the gitconfig stanzas would change
+#[derive(Debug, Clone)]
+pub struct GitconfigPlan {
+    pub gitconfig_path: PathBuf,
+    pub includes: Vec<OrgInclude>,
+    /// Orgs whose include file is missing or differs
+    pub stale_includes: Vec<String>,
+    /// Whether the managed block in the gitconfig differs
+    pub block_drifted: bool,
+    desired_gitconfig: String,
+}
+
+impl GitconfigPlan {
+    /// Compare the desired includes for `orgs` with what's on disk
+    pub fn new(config_dir: &Path, gitconfig_path: PathBuf, orgs: &[String]) -> Self {
+        let includes: Vec<OrgInclude> = orgs.iter().filter_map(|org| org_include(config_dir, org)).collect();
+        let stale_includes = includes
+            .iter()
+            .filter(|i| std::fs::read_to_string(&i.path).ok().as_deref() != Some(i.contents.as_str()))
+            .map(|i| i.org.clone())
+            .collect();
+        let current = std::fs::read_to_string(&gitconfig_path).unwrap_or_default();
+        let block = managed_block(&includes);
+        let block_drifted = current_block(&current).unwrap_or_default() != block;
+        Self {
+            desired_gitconfig: splice(&current, &block),
+            gitconfig_path,
+            includes,
+            stale_includes,
+            block_drifted,
+        }
+    }
+
+    pub const fn is_in_sync(&self) -> bool {
+        self.stale_includes.is_empty() && !self.block_drifted
+    }
+
+    /// Write stale include files and the gitconfig block
+    pub fn apply(&self) -> Result<(), String> {
+        for include in self.includes.iter().filter(|i| self.stale_includes.contains(&i.org)) {
+            if let Some(parent) = include.path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+            }
+            std::fs::write(&include.path, &include.contents)
+                .map_err(|e| format!("Failed to write {}: {e}", include.path.display()))?;
+        }
+        if self.block_drifted {
+            std::fs::write(&self.gitconfig_path, &self.desired_gitconfig)
+                .map_err(|e| format!("Failed to write {}: {e}", self.gitconfig_path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GitIdentity;
+    use tempfile::TempDir;
+
+    fn save_org(config_dir: &Path, org: &str, workspace: &str) {
+        let mut config = OrgConfig {
+            workspace_path: Some(workspace.to_string()),
+            identity: GitIdentity {
+                name: Some("Alice".to_string()),
+                email: Some(format!("alice@{org}.example")),
+                signing_key: None,
+            },
+            ..OrgConfig::default()
+        };
+        config.ssh.insert("github".to_string(), format!("~/.ssh/{org}"));
+        config.save(config_dir, org).unwrap();
+    }
+
+    #[test]
+    fn test_plan_apply_is_idempotent_and_preserves_user_config() {
+        let tmp = TempDir::new().unwrap();
+        let gitconfig = tmp.path().join(".gitconfig");
+        std::fs::write(&gitconfig, "[user]\n\tname = Personal\n").unwrap();
+        save_org(tmp.path(), "acme", "~/dev/acme");
+        let orgs = vec!["acme".to_string(), "unset".to_string()];
+
+        let plan = GitconfigPlan::new(tmp.path(), gitconfig.clone(), &orgs);
+        assert_eq!(plan.includes.len(), 1);
+        assert_eq!(plan.includes[0].gitdir, "~/dev/acme/");
+        assert!(!plan.is_in_sync());
+        plan.apply().unwrap();
+
+        let written = std::fs::read_to_string(&gitconfig).unwrap();
+        assert!(written.starts_with("[user]\n\tname = Personal\n"));
+        assert!(written.contains("[includeIf \"gitdir:~/dev/acme/\"]"));
+        let include = std::fs::read_to_string(include_path(tmp.path(), "acme")).unwrap();
+        assert!(include.contains("email = alice@acme.example"));
+        assert!(include.contains("sshCommand = ssh -i ~/.ssh/acme -o IdentitiesOnly=yes"));
+
+        let again = GitconfigPlan::new(tmp.path(), gitconfig.clone(), &orgs);
+        assert!(again.is_in_sync());
+        again.apply().unwrap();
+        assert_eq!(std::fs::read_to_string(&gitconfig).unwrap(), written);
+    }
+
+    #[test]
+    fn test_detects_drift_and_removes_block() {
+        let tmp = TempDir::new().unwrap();
+        let gitconfig = tmp.path().join(".gitconfig");
+        save_org(tmp.path(), "acme", "/work/acme/");
+        let orgs = vec!["acme".to_string()];
+        GitconfigPlan::new(tmp.path(), gitconfig.clone(), &orgs).apply().unwrap();
+
+        std::fs::write(include_path(tmp.path(), "acme"), "[user]\n\temail = edited\n").unwrap();
+        let plan = GitconfigPlan::new(tmp.path(), gitconfig.clone(), &orgs);
+        assert_eq!(plan.stale_includes, vec!["acme".to_string()]);
+        assert!(!plan.block_drifted);
+
+        let plan = GitconfigPlan::new(tmp.path(), gitconfig.clone(), &[]);
+        assert!(plan.block_drifted);
+        plan.apply().unwrap();
+        assert_eq!(std::fs::read_to_string(&gitconfig).unwrap(), "");
+    }
+}
@@ -7,11 +7,16 @@ use std::fs;
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 
+#[cfg(doc)]
+use crate::commands::push_guard;
+
 /// Pre-push hook script template
 ///
 /// This script is installed to `.hyperforge/hooks/pre-push` and validates:
 /// 1. The push target forge is in the repo's declared forges list
 /// 2. The push target org matches the repo's declared org (or forge-specific override)
+/// 3. With `scan_outgoing = true`, that outgoing commits carry no large blobs or
+///    secret-looking strings (patterns from [`push_guard::SECRET_PATTERNS`])
 ///
 /// Uses Python's tomllib (3.11+) for proper TOML parsing. Falls back to
 /// allowing the push if Python 3 is unavailable.
@@ -54,6 +59,37 @@ if [ -n "$LARGE_FILES" ]; then
     fi
 fi
 
+# --- Outgoing commit scan (when scan_outgoing = true in config) ---
+# Checks commits being pushed for large blobs and secret-looking strings.
+# Set HYPERFORGE_ALLOW_FINDINGS=1 to push anyway.
+SECRET_RE='((AKIA|ASIA)[0-9A-Z]{16})|(gh[pousr]_[A-Za-z0-9]{36})|(github_pat_[A-Za-z0-9_]{22,})|(glpat-[A-Za-z0-9_-]{20})|(xox[abprs]-[A-Za-z0-9-]{10,})|(-----BEGIN ([A-Z]+ )?PRIVATE KEY-----)'
+if grep -Eq '^scan_outgoing[[:space:]]*=[[:space:]]*true' "$CONFIG" && [ "${HYPERFORGE_ALLOW_FINDINGS:-0}" != "1" ]; then
+    FINDINGS=""
+    while read -r LOCAL_REF LOCAL_SHA REMOTE_REF REMOTE_SHA; do
+        # Branch deletion: nothing outgoing
+        case "$LOCAL_SHA" in *[!0]*) ;; *) continue ;; esac
+        case "$REMOTE_SHA" in
+            *[!0]*) RANGE="$REMOTE_SHA..$LOCAL_SHA" ;;
+            *)      RANGE="$LOCAL_SHA --not --remotes=$REMOTE" ;;
+        esac
+        BIG=$(git rev-list --objects $RANGE \
+            | git cat-file --batch-check='%(objecttype) %(objectsize) %(rest)' \
+            | awk -v t="$THRESHOLD" '$1 == "blob" && $2 > t { printf "  large_file %s (%dKB)\n", $3, $2 / 1024 }')
+        SECRETS=$(git log -p --no-color --unified=0 --format= $RANGE \
+            | grep '^+' | grep -v '^+++' | grep -Ec -e "$SECRET_RE")
+        [ -n "$BIG" ] && FINDINGS="$FINDINGS$BIG
+"
+        [ "$SECRETS" -gt 0 ] 2>/dev/null && FINDINGS="$FINDINGS  secret: $SECRETS added lines in $LOCAL_REF match secret patterns
+"
+    done
+    if [ -n "$FINDINGS" ]; then
+        printf '%s' "$FINDINGS" >&2
+        echo "hyperforge: BLOCKED — outgoing commits failed the push guard" >&2
+        echo "hyperforge: Run 'repo push --scan' for a per-commit report, or set HYPERFORGE_ALLOW_FINDINGS=1 to push anyway." >&2
+        exit 1
+    fi
+fi
+
 # Determine forge from URL
 FORGE=""
 case "$URL" in
@@ -163,3 +199,14 @@ pub fn install_pre_push_hook(repo_path: &Path, dry_run: bool) -> std::io::Result
 
     Ok(true) // Installed
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_secret_patterns_match_push_guard() {
+        let line = format!("SECRET_RE='{}'", crate::commands::push_guard::secret_pattern_ere());
+        assert!(PRE_PUSH_HOOK.contains(&line));
+    }
+}
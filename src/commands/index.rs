@@ -0,0 +1,151 @@
+//! Persistent workspace index
+//!
+//! Caches per-repo manifest parsing (build-system detection, deps, package
+//! name/version) in `<workspace>/.hyperforge/index.json`. Each entry is keyed
+//! by a fingerprint of the repo's top-level files (name, size, mtime), so a
+//! repo is re-parsed only when one of its manifests changes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::build_system::{self, BuildSystemKind, DepRef};
+
+/// Index file name under the workspace `.hyperforge/`
+pub const INDEX_FILE: &str = "index.json";
+
+/// Bumped whenever the entry layout or what it caches changes
+const INDEX_VERSION: u32 = 1;
+
+/// Cached manifest data for one repo
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub fingerprint: String,
+    pub build_system: BuildSystemKind,
+    pub build_systems: Vec<BuildSystemKind>,
+    #[serde(default)]
+    pub dependencies: Vec<DepRef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_version: Option<String>,
+}
+
+impl IndexEntry {
+    /// Parse the repo's manifests from scratch
+    pub fn parse(repo_path: &Path, fingerprint: String) -> Self {
+        let primary_bs = build_system::detect_build_system(repo_path);
+        Self {
+            fingerprint,
+            build_systems: build_system::detect_all_build_systems(repo_path),
+            dependencies: build_system::parse_dependencies(repo_path, &primary_bs),
+            package_name: build_system::package_name(repo_path, &primary_bs),
+            package_version: build_system::package_version(repo_path, &primary_bs),
+            build_system: primary_bs,
+        }
+    }
+}
+
+/// All cached entries for a workspace, keyed by directory name
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceIndex {
+    pub version: u32,
+    #[serde(default)]
+    pub repos: BTreeMap<String, IndexEntry>,
+}
+
+impl WorkspaceIndex {
+    pub fn path(workspace_root: &Path) -> PathBuf {
+        workspace_root.join(crate::config::CONFIG_DIR).join(INDEX_FILE)
+    }
+
+    /// Load the index; a missing, unreadable or outdated file is an empty index
+    pub fn load(workspace_root: &Path) -> Self {
+        std::fs::read_to_string(Self::path(workspace_root))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|index| index.version == INDEX_VERSION)
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, workspace_root: &Path) -> Result<(), String> {
+        let path = Self::path(workspace_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let index = Self {
+            version: INDEX_VERSION,
+            repos: self.repos.clone(),
+        };
+        let content = serde_json::to_string_pretty(&index)
+            .map_err(|e| format!("Failed to serialize workspace index: {e}"))?;
+        std::fs::write(&path, content)
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    /// The cached entry for `dir_name` if its fingerprint still matches
+    pub fn fresh(&self, dir_name: &str, fingerprint: &str) -> Option<&IndexEntry> {
+        self.repos
+            .get(dir_name)
+            .filter(|entry| entry.fingerprint == fingerprint)
+    }
+}
+
+/// Fingerprint of the files a repo's manifests live in: every regular file
+/// directly under `repo_path`, by name, size and mtime. Adding, removing or
+/// editing a top-level file changes it.
+pub fn fingerprint(repo_path: &Path) -> String {
+    let mut files: Vec<String> = std::fs::read_dir(repo_path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok().filter(std::fs::Metadata::is_file)?;
+            let mtime = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_nanos());
+            Some(format!("{}\t{}\t{mtime}", entry.file_name().to_string_lossy(), meta.len()))
+        })
+        .collect();
+    files.sort();
+    build_system::generated::content_hash(&files.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fingerprint_tracks_manifest_changes() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("Cargo.toml"), "[package]\nname = \"a\"\n").unwrap();
+        let before = fingerprint(tmp.path());
+        assert_eq!(before, fingerprint(tmp.path()));
+
+        std::fs::write(tmp.path().join("Cargo.toml"), "[package]\nname = \"ab\"\n").unwrap();
+        assert_ne!(before, fingerprint(tmp.path()));
+    }
+
+    #[test]
+    fn test_index_roundtrip_and_freshness() {
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path().join("repo-a");
+        std::fs::create_dir(&repo).unwrap();
+        std::fs::write(repo.join("Cargo.toml"), "[package]\nname = \"repo-a\"\nversion = \"0.1.0\"\n").unwrap();
+
+        let fp = fingerprint(&repo);
+        let mut index = WorkspaceIndex::default();
+        index.repos.insert("repo-a".to_string(), IndexEntry::parse(&repo, fp.clone()));
+        index.save(tmp.path()).unwrap();
+
+        let loaded = WorkspaceIndex::load(tmp.path());
+        let entry = loaded.fresh("repo-a", &fp).unwrap();
+        assert_eq!(entry.package_name.as_deref(), Some("repo-a"));
+        assert!(loaded.fresh("repo-a", "stale").is_none());
+    }
+}
@@ -11,7 +11,7 @@
 use std::path::Path;
 use thiserror::Error;
 
-use crate::config::HyperforgeConfig;
+use crate::config::{GitIdentity, HyperforgeConfig};
 use crate::git::{self, Git, GitError};
 use crate::types::Visibility;
 
@@ -71,6 +71,12 @@ pub struct InitOptions {
 
     /// Skip configuring SSH wrapper
     pub no_ssh_wrapper: bool,
+
+    /// Git identity to write into the repo's local git config
+    pub identity: GitIdentity,
+
+    /// Self-hosted hostnames per forge (e.g. gitlab -> `gitlab.mycompany.com`)
+    pub forge_hosts: Vec<(String, String)>,
 }
 
 impl Default for InitOptions {
@@ -86,6 +92,8 @@ impl Default for InitOptions {
             dry_run: false,
             no_hooks: false,
             no_ssh_wrapper: false,
+            identity: GitIdentity::default(),
+            forge_hosts: Vec::new(),
         }
     }
 }
@@ -142,6 +150,16 @@ impl InitOptions {
         self.no_ssh_wrapper = true;
         self
     }
+
+    pub fn with_identity(mut self, identity: GitIdentity) -> Self {
+        self.identity = identity;
+        self
+    }
+
+    pub fn with_forge_host(mut self, forge: impl Into<String>, host: impl Into<String>) -> Self {
+        self.forge_hosts.push((forge.into(), host.into()));
+        self
+    }
 }
 
 /// Result of init operation
@@ -167,6 +185,9 @@ pub struct InitReport {
 
     /// Whether SSH wrapper was configured
     pub ssh_configured: bool,
+
+    /// Git config keys set from the identity
+    pub identity_configured: Vec<&'static str>,
 }
 
 #[derive(Debug, Clone)]
@@ -209,6 +230,7 @@ pub fn init(path: &Path, options: InitOptions) -> InitResult<InitReport> {
         dry_run: options.dry_run,
         hooks_installed: false,
         ssh_configured: false,
+        identity_configured: Vec::new(),
     };
 
     // Initialize git if needed
@@ -240,6 +262,10 @@ pub fn init(path: &Path, options: InitOptions) -> InitResult<InitReport> {
         config = config.with_ssh_key(forge, key_path);
     }
 
+    for (forge, host) in &options.forge_hosts {
+        config.forge_config.entry(forge.clone()).or_default().host = Some(host.clone());
+    }
+
     // Validate config
     config.validate()?;
 
@@ -268,7 +294,9 @@ pub fn init(path: &Path, options: InitOptions) -> InitResult<InitReport> {
 
         for forge in &options.forges {
             let remote_name = config.remote_for_forge(forge);
-            let remote_url = git::build_remote_url(forge, org, &repo_name);
+            let (base, _) = HyperforgeConfig::split_account(forge);
+            let forge_org = config.org_for_forge(forge).unwrap_or(org);
+            let remote_url = git::build_remote_url_on(base, config.forge_host(forge), forge_org, &repo_name);
 
             if !options.dry_run {
                 // Check if remote already exists
@@ -336,6 +364,14 @@ pub fn init(path: &Path, options: InitOptions) -> InitResult<InitReport> {
         report.ssh_configured = true;
     }
 
+    // Write the org identity into the local git config
+    for (key, value) in options.identity.git_config() {
+        if !options.dry_run {
+            Git::config_set(path, key, value)?;
+        }
+        report.identity_configured.push(key);
+    }
+
     report.config = config;
     Ok(report)
 }
@@ -404,6 +440,23 @@ mod tests {
         assert_eq!(remotes.len(), 2);
     }
 
+    #[test]
+    fn test_init_forge_accounts() {
+        let temp = TempDir::new().unwrap();
+
+        let options = InitOptions::new(vec!["github".to_string(), "github:acme".to_string()])
+            .with_org("alice")
+            .with_repo_name("tool");
+
+        let report = init(temp.path(), options).unwrap();
+
+        let urls: Vec<_> = report.remotes_added.iter().map(|r| (r.name.as_str(), r.url.as_str())).collect();
+        assert_eq!(
+            urls,
+            vec![("origin", "git@github.com:alice/tool.git"), ("github-acme", "git@github.com:acme/tool.git")]
+        );
+    }
+
     #[test]
     fn test_init_already_exists() {
         let temp = TempDir::new().unwrap();
@@ -501,6 +554,27 @@ mod tests {
         assert!(ssh_cmd.unwrap().contains("github_key"));
     }
 
+    #[test]
+    fn test_init_writes_identity() {
+        let temp = TempDir::new().unwrap();
+
+        let options = InitOptions::new(vec!["github".to_string()])
+            .with_org("work")
+            .with_identity(GitIdentity {
+                name: Some("Alice".to_string()),
+                email: Some("alice@work.example".to_string()),
+                signing_key: Some("ABCD1234".to_string()),
+            });
+
+        let report = init(temp.path(), options).unwrap();
+
+        assert_eq!(report.identity_configured.len(), 4);
+        let get = |key| Git::config_get(temp.path(), key).unwrap();
+        assert_eq!(get("user.email").as_deref(), Some("alice@work.example"));
+        assert_eq!(get("user.signingkey").as_deref(), Some("ABCD1234"));
+        assert_eq!(get("commit.gpgsign").as_deref(), Some("true"));
+    }
+
     #[test]
     fn test_init_updates_existing_remote() {
         let temp = TempDir::new().unwrap();
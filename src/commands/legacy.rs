@@ -0,0 +1,116 @@
+//! Compatibility layer for the legacy org-based registry flow
+//!
+//! Before path-based workspaces, hyperforge worked from the registry alone:
+//! `~/.config/hyperforge/orgs/<org>/repos.yaml`, addressed with `--org` (and
+//! `--forge`) and no `--path`. `workspace diff` and `workspace verify` still
+//! accept that form while the `legacy-org-mode` cargo feature is enabled (the
+//! default), flagging each use as deprecated. Without the feature they ask
+//! for `--path` instead.
+//!
+//! `workspace migrate_legacy` moves an org over: every registry record gets
+//! a checkout with a generated `.hyperforge/config.toml` under the new
+//! workspace directory, cloning the repos that aren't on disk yet.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::HyperforgeConfig;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+
+/// Whether the org-only forms of `diff` and `verify` are compiled in
+pub const ENABLED: bool = cfg!(feature = "legacy-org-mode");
+
+/// The event to open a legacy-mode `method` call with: a deprecation notice,
+/// or (without `legacy-org-mode`) the error that ends it
+#[allow(clippy::result_large_err)]
+pub fn gate(method: &str) -> Result<HyperforgeEvent, HyperforgeEvent> {
+    if ENABLED {
+        Ok(HyperforgeEvent::Info {
+            message: format!(
+                "Deprecated: `{method}` without --path uses the legacy org-based registry flow. \
+                 Run `workspace migrate_legacy --path <dir>` and pass --path instead."
+            ),
+        })
+    } else {
+        Err(HyperforgeEvent::error(
+            HyperforgeError::Validation,
+            format!(
+                "`{method}` needs --path: the legacy org-based flow is disabled in this build. \
+                 Run `workspace migrate_legacy --path <dir>` to create a workspace from the registry."
+            ),
+        ))
+    }
+}
+
+/// Orgs with a legacy registry (`orgs/<org>/repos.yaml`) under `config_dir`, sorted
+pub fn legacy_orgs(config_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(config_dir.join("orgs")) else {
+        return Vec::new();
+    };
+    let mut orgs: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().join("repos.yaml").is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    orgs.sort();
+    orgs
+}
+
+/// The workspace directory `org` migrates into: `root` itself when it is the
+/// only org being migrated, else `root/<org>`
+pub fn workspace_dir(root: &Path, org: &str, org_count: usize) -> PathBuf {
+    if org_count == 1 { root.to_path_buf() } else { root.join(org) }
+}
+
+/// What migrating one registry record involves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationStep {
+    /// Nothing on disk yet
+    Clone,
+    /// A checkout without `.hyperforge/config.toml`
+    Materialize,
+    /// Already a path-based repo
+    Configured,
+}
+
+impl MigrationStep {
+    /// The step for a record whose checkout belongs at `repo_dir`
+    pub fn for_dir(repo_dir: &Path) -> Self {
+        if !repo_dir.exists() {
+            Self::Clone
+        } else if HyperforgeConfig::exists(repo_dir) {
+            Self::Configured
+        } else {
+            Self::Materialize
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_orgs_and_steps() {
+        let temp = tempfile::TempDir::new().unwrap();
+        for org in ["zeta", "acme"] {
+            let dir = temp.path().join("orgs").join(org);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("repos.yaml"), "repos: []\n").unwrap();
+        }
+        // An org directory without repos.yaml isn't a legacy registry
+        std::fs::create_dir_all(temp.path().join("orgs").join("empty")).unwrap();
+        assert_eq!(legacy_orgs(temp.path()), vec!["acme", "zeta"]);
+        assert!(legacy_orgs(&temp.path().join("missing")).is_empty());
+
+        let root = temp.path().join("ws");
+        assert_eq!(workspace_dir(&root, "acme", 1), root);
+        assert_eq!(workspace_dir(&root, "acme", 2), root.join("acme"));
+
+        assert_eq!(MigrationStep::for_dir(&root.join("tool")), MigrationStep::Clone);
+        std::fs::create_dir_all(root.join("tool")).unwrap();
+        assert_eq!(MigrationStep::for_dir(&root.join("tool")), MigrationStep::Materialize);
+        std::fs::create_dir_all(root.join("tool").join(".hyperforge")).unwrap();
+        std::fs::write(root.join("tool").join(".hyperforge").join("config.toml"), "org = \"acme\"\n").unwrap();
+        assert_eq!(MigrationStep::for_dir(&root.join("tool")), MigrationStep::Configured);
+    }
+}
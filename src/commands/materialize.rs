@@ -5,8 +5,8 @@
 
 use std::path::Path;
 
-use crate::config::{HyperforgeConfig, OrgConfig};
-use crate::git::{build_remote_url, Git};
+use crate::config::{HyperforgeConfig, OrgConfig, RemoteLayout};
+use crate::git::{build_remote_url_on, Git};
 use crate::types::RepoRecord;
 
 /// Options controlling which parts of materialization to perform.
@@ -23,6 +23,8 @@ pub struct MaterializeOpts {
     pub dry_run: bool,
     /// Auto-commit .hyperforge/ after materialization (default true)
     pub auto_commit: bool,
+    /// Write the org's `[identity]` into the repo's git config (default true)
+    pub identity: bool,
 }
 
 impl Default for MaterializeOpts {
@@ -34,6 +36,7 @@ impl Default for MaterializeOpts {
             ssh_wrapper: false,
             dry_run: false,
             auto_commit: true,
+            identity: true,
         }
     }
 }
@@ -50,6 +53,8 @@ pub struct MaterializeReport {
     pub hooks_installed: bool,
     /// Whether SSH wrapper was configured
     pub ssh_configured: bool,
+    /// Git config keys set from the org identity
+    pub identity_configured: Vec<&'static str>,
     /// Whether .hyperforge/ was auto-committed
     pub auto_committed: bool,
     /// Warnings emitted during materialization
@@ -73,6 +78,7 @@ pub fn materialize(
         remotes_updated: Vec::new(),
         hooks_installed: false,
         ssh_configured: false,
+        identity_configured: Vec::new(),
         auto_committed: false,
         warnings: Vec::new(),
     };
@@ -96,23 +102,55 @@ pub fn materialize(
         Some(record.default_branch.clone())
     };
 
-    let config = HyperforgeConfig {
-        repo_name,
-        org: Some(org.to_string()),
-        forges: record.forges.clone(),
-        visibility: record.visibility.clone(),
-        description: record.description.clone(),
-        ssh: record.ssh.clone(),
-        forge_config: record.forge_config.clone(),
-        default_branch,
-        ci: record.ci.clone(),
-        large_file_threshold_kb: None,
-        dist: record.dist.clone(),
+    // Local-only settings survive re-materialization
+    let existing = HyperforgeConfig::load(repo_path).ok();
+
+    // Materializing for a forge account of another org's repo (its
+    // `github:acme` entries): config and identity belong to the repo's own
+    // org, so only this account's remotes are reconciled
+    let account = existing
+        .as_ref()
+        .filter(|c| c.org.as_deref() != Some(org) && c.orgs().contains(&org))
+        .cloned();
+    if let Some(ref config) = account {
+        report.warnings.push(format!(
+            "{org} is a forge account of {}'s repo; config and identity left to that org",
+            config.org.as_deref().unwrap_or_default(),
+        ));
+    }
+
+    let config = match account.clone() {
+        Some(config) => config,
+        None => HyperforgeConfig {
+            repo_name,
+            org: Some(org.to_string()),
+            forges: record.forges.clone(),
+            visibility: record.visibility.clone(),
+            description: record.description.clone(),
+            topics: record.topics.clone(),
+            archived: record.archived,
+            ssh: record.ssh.clone(),
+            forge_config: record.forge_config.clone(),
+            default_branch,
+            ci: record.ci.clone(),
+            large_file_threshold_kb: None,
+            scan_outgoing: existing.as_ref().is_some_and(|c| c.scan_outgoing),
+            linear_history: existing.as_ref().and_then(|c| c.linear_history),
+            unmanaged_files: existing.as_ref().map(|c| c.unmanaged_files.clone()).unwrap_or_default(),
+            dist: record.dist.clone(),
+            image: existing.as_ref().and_then(|c| c.image.clone()),
+            webhooks: existing.as_ref().map(|c| c.webhooks.clone()).unwrap_or_default(),
+            ssh_deploy_keys: existing.as_ref().map(|c| c.ssh_deploy_keys.clone()).unwrap_or_default(),
+            branch_protection: existing.as_ref().and_then(|c| c.branch_protection.clone()),
+            remote_layout: existing.as_ref().map(|c| c.remote_layout).unwrap_or_default(),
+            inherited: existing.as_ref().map(|c| c.inherited.clone()).unwrap_or_default(),
+            depends_on: existing.map(|c| c.depends_on).unwrap_or_default(),
+        },
     };
 
     // ── Step 1: config ──────────────────────────────────────────────────
 
-    if opts.config {
+    if opts.config && account.is_none() {
         if !opts.dry_run {
             config
                 .save(repo_path)
@@ -126,16 +164,20 @@ pub fn materialize(
     if opts.remotes && repo_path.join(".git").exists() {
         // Compute desired remotes from record.forges
         let mut desired: Vec<(String, String)> = Vec::new(); // (remote_name, url)
+        let org_config = OrgConfig::load_default(org);
 
         for forge_str in &record.forges {
-            // Determine the org for this forge: check forge_config override, fall back to param
-            let org_for_forge = record
-                .forge_config
-                .get(forge_str)
-                .and_then(|fc| fc.org.as_deref())
-                .unwrap_or(org);
+            // Determine the org for this forge: forge_config override or
+            // account, falling back to param
+            let org_for_forge = record.forge_org(forge_str).unwrap_or(org);
+            let (base, _) = HyperforgeConfig::split_account(forge_str);
+            // Self-hosted instance: the record's host, else the org's
+            let host = [forge_str.as_str(), base]
+                .iter()
+                .find_map(|f| record.forge_config.get(*f).and_then(|fc| fc.host.as_deref()))
+                .or_else(|| org_config.forge_host(base));
 
-            let url = build_remote_url(forge_str, org_for_forge, &record.name);
+            let url = build_remote_url_on(base, host, org_for_forge, &record.name);
 
             // Use config.remote_for_forge to respect forge_config.remote overrides
             let remote_name = config.remote_for_forge(forge_str);
@@ -143,6 +185,16 @@ pub fn materialize(
             desired.push((remote_name, url));
         }
 
+        // Multi-push: the first forge's remote fetches from it and pushes
+        // to every forge
+        let push_urls: Vec<String> = match config.remote_layout {
+            RemoteLayout::MultiPush if desired.len() > 1 => desired.iter().map(|(_, url)| url.clone()).collect(),
+            _ => Vec::new(),
+        };
+        if !push_urls.is_empty() {
+            desired.truncate(1);
+        }
+
         // Get current remotes
         let current_remotes = Git::list_remotes(repo_path)
             .map_err(|e| format!("failed to list remotes: {e}"))?;
@@ -167,6 +219,20 @@ pub fn materialize(
                 report.remotes_added.push(remote_name.clone());
             }
         }
+
+        if let Some((remote_name, _)) = desired.first().filter(|_| !push_urls.is_empty()) {
+            let current = current_remotes.iter().find(|r| r.name == *remote_name);
+            if current.is_none_or(|r| r.push_urls() != push_urls) {
+                if !opts.dry_run {
+                    let urls: Vec<&str> = push_urls.iter().map(String::as_str).collect();
+                    Git::set_remote_push_urls(repo_path, remote_name, &urls)
+                        .map_err(|e| format!("failed to set push urls: {e}"))?;
+                }
+                if current.is_some() && !report.remotes_updated.contains(remote_name) {
+                    report.remotes_updated.push(remote_name.clone());
+                }
+            }
+        }
     }
 
     // ── Step 3: hooks ───────────────────────────────────────────────────
@@ -182,11 +248,7 @@ pub fn materialize(
     if opts.ssh_wrapper {
         // Resolve SSH key: per-repo first, then org-level defaults
         let ssh_key = record.ssh.iter().next().map(|(_f, k)| k.clone()).or_else(|| {
-            let config_dir = dirs::home_dir()
-                .unwrap_or_default()
-                .join(".config")
-                .join("hyperforge");
-            let org_config = OrgConfig::load(&config_dir, org);
+            let org_config = OrgConfig::load_default(org);
             // Pick the first org-level key that matches one of our forges
             record.forges.iter()
                 .find_map(|f| org_config.ssh_key_for_forge(f).map(std::string::ToString::to_string))
@@ -211,7 +273,22 @@ pub fn materialize(
         }
     }
 
-    // ── Step 5: auto-commit .hyperforge/ ──────────────────────────────
+    // ── Step 5: git identity ────────────────────────────────────────────
+    //
+    // Before the auto-commit, so even that commit carries the org identity.
+
+    if opts.identity && account.is_none() && repo_path.join(".git").exists() {
+        let identity = OrgConfig::load_default(org).identity;
+        for (key, value) in identity.git_config() {
+            if !opts.dry_run {
+                Git::config_set(repo_path, key, value)
+                    .map_err(|e| format!("failed to set {key}: {e}"))?;
+            }
+            report.identity_configured.push(key);
+        }
+    }
+
+    // ── Step 6: auto-commit .hyperforge/ ──────────────────────────────
 
     if opts.auto_commit && !opts.dry_run && report.config_written && repo_path.join(".git").exists()
     {
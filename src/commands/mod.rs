@@ -2,18 +2,29 @@
 //!
 //! This module contains the implementation of hyperforge CLI commands.
 
+pub mod gitconfig;
 pub mod hooks;
+pub mod index;
 pub mod init;
+pub mod legacy;
 pub mod materialize;
+pub mod pins;
+pub mod pull;
 pub mod push;
+pub mod push_guard;
+pub mod remotes;
 pub mod runner;
 pub mod status;
+pub mod upstreams;
 pub mod workspace;
 
 pub use init::{init, InitOptions, InitResult};
 pub use materialize::{materialize, MaterializeOpts, MaterializeReport};
+pub use pull::{pull, ForgePullResult, PullReport, PullState};
 pub use push::{push, ForgePushResult, PushOptions, PushReport, PushResult};
+pub use remotes::{converge as converge_remotes, RemoteAction, RemoteChange, RemotesReport};
 pub use status::{status, ForgeStatus, RepoStatusReport, StatusResult};
+pub use upstreams::{audit as audit_upstreams, UpstreamFinding, UpstreamProblem, UpstreamsReport};
 pub use workspace::{
-    discover_workspace, repo_from_config, DiscoveredRepo, WorkspaceContext, WorkspaceResult,
+    discover_workspace, repo_for_org, repo_from_config, DiscoveredRepo, WorkspaceContext, WorkspaceResult,
 };
@@ -0,0 +1,152 @@
+//! Pinned repos
+//!
+//! Repos listed under `[pins]` in the workspace config are held at a tag or
+//! commit rather than tracking a branch. `workspace check` reports drift from
+//! the pin and `workspace thaw` checks the pins out; every other repo is left
+//! on its branch. `push_all`, `pull_all` and `sync` skip pinned repos, whose
+//! detached HEAD has nothing to push or fast-forward.
+
+use std::path::Path;
+
+use crate::git::{Git, GitResult};
+
+/// How a pinned repo's HEAD relates to its pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinStatus {
+    /// HEAD is the pinned commit
+    InSync,
+    /// HEAD is some other commit
+    Drifted,
+    /// The pin doesn't name a commit in the local clone (not fetched, typo)
+    Unresolved,
+    /// `thaw` moved HEAD to the pin
+    CheckedOut,
+    /// `thaw --dry_run` would move HEAD to the pin
+    WouldCheckOut,
+}
+
+impl PinStatus {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InSync => "in_sync",
+            Self::Drifted => "drifted",
+            Self::Unresolved => "unresolved",
+            Self::CheckedOut => "checked_out",
+            Self::WouldCheckOut => "would_check_out",
+        }
+    }
+}
+
+/// A pinned repo's state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinReport {
+    pub pin: String,
+    /// Commit the pin resolves to
+    pub commit: Option<String>,
+    pub head: Option<String>,
+    pub status: PinStatus,
+}
+
+impl PinReport {
+    pub fn is_in_sync(&self) -> bool {
+        self.status == PinStatus::InSync
+    }
+}
+
+/// Compare HEAD with the commit `pin` names
+pub fn inspect(path: &Path, pin: &str) -> GitResult<PinReport> {
+    let commit = Git::resolve_commit(path, pin)?;
+    let head = Git::resolve_commit(path, "HEAD")?;
+    let status = match (&commit, &head) {
+        (None, _) => PinStatus::Unresolved,
+        (Some(c), Some(h)) if c == h => PinStatus::InSync,
+        _ => PinStatus::Drifted,
+    };
+    Ok(PinReport {
+        pin: pin.to_string(),
+        commit,
+        head,
+        status,
+    })
+}
+
+/// Check out `pin` with a detached HEAD. Refuses a dirty or mid-operation
+/// working tree; with `fetch`, fetches all remotes first so new tags resolve.
+pub fn checkout(path: &Path, pin: &str, fetch: bool, dry_run: bool) -> Result<PinReport, String> {
+    let mut report = inspect(path, pin).map_err(|e| e.to_string())?;
+    if report.status == PinStatus::Unresolved && fetch {
+        Git::fetch_all(path).map_err(|e| format!("fetch failed: {e}"))?;
+        report = inspect(path, pin).map_err(|e| e.to_string())?;
+    }
+    match report.status {
+        PinStatus::Unresolved => return Err(format!("pin '{pin}' does not name a commit")),
+        PinStatus::InSync => return Ok(report),
+        _ => {}
+    }
+
+    let status = Git::repo_status(path).map_err(|e| e.to_string())?;
+    if status.has_changes || status.has_staged {
+        return Err("working tree has uncommitted changes".to_string());
+    }
+    if status.in_progress.is_some() || status.conflicts > 0 {
+        return Err(status.blocking_state().unwrap_or_default());
+    }
+
+    if dry_run {
+        report.status = PinStatus::WouldCheckOut;
+        return Ok(report);
+    }
+    Git::checkout_detached(path, pin).map_err(|e| e.to_string())?;
+    report.head.clone_from(&report.commit);
+    report.status = PinStatus::CheckedOut;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn repo_with_tag() -> (TempDir, String) {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path();
+        Git::init(path).unwrap();
+        Git::config_set(path, "user.email", "test@test.com").unwrap();
+        Git::config_set(path, "user.name", "Test").unwrap();
+        std::fs::write(path.join("a.txt"), "one").unwrap();
+        Git::add(path, "a.txt").unwrap();
+        Git::commit(path, "one").unwrap();
+        let pinned = Git::head_commit(path).unwrap();
+        Git::tag(path, "v1.0.0", None).unwrap();
+        std::fs::write(path.join("a.txt"), "two").unwrap();
+        Git::add(path, "a.txt").unwrap();
+        Git::commit(path, "two").unwrap();
+        (temp, pinned)
+    }
+
+    #[test]
+    fn test_inspect_and_checkout_pin() {
+        let (temp, pinned) = repo_with_tag();
+        let path = temp.path();
+
+        assert_eq!(inspect(path, "v1.0.0").unwrap().status, PinStatus::Drifted);
+        assert_eq!(inspect(path, "nope").unwrap().status, PinStatus::Unresolved);
+
+        let dry = checkout(path, "v1.0.0", false, true).unwrap();
+        assert_eq!(dry.status, PinStatus::WouldCheckOut);
+        assert_ne!(Git::head_commit(path).unwrap(), pinned);
+
+        let done = checkout(path, "v1.0.0", false, false).unwrap();
+        assert_eq!(done.status, PinStatus::CheckedOut);
+        assert!(inspect(path, "v1.0.0").unwrap().is_in_sync());
+        assert_eq!(Git::head_commit(path).unwrap(), pinned);
+    }
+
+    #[test]
+    fn test_checkout_refuses_dirty_tree() {
+        let (temp, _) = repo_with_tag();
+        std::fs::write(temp.path().join("a.txt"), "local edit").unwrap();
+        let err = checkout(temp.path(), "v1.0.0", false, false).unwrap_err();
+        assert!(err.contains("uncommitted"));
+    }
+}
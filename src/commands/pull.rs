@@ -0,0 +1,284 @@
+//! Pull command - Fetch every forge and fast-forward the current branch
+//!
+//! This command:
+//! 1. Reads the hyperforge config
+//! 2. Fetches each configured forge's remote
+//! 3. Reports ahead/behind for the current branch against each forge
+//! 4. Fast-forwards the branch to the origin forge (the first in `forges`)
+//! 5. Leaves diverged branches alone, reporting them instead of merging
+
+use std::path::Path;
+use thiserror::Error;
+
+use crate::config::HyperforgeConfig;
+use crate::git::{Git, GitError, RepoStatus};
+
+/// Errors that can occur during pull
+#[derive(Debug, Error)]
+pub enum PullError {
+    #[error("Not a hyperforge repository. Run 'hyperforge init' first.")]
+    NotInitialized,
+
+    #[error("Not a git repository: {path}")]
+    NotAGitRepo { path: String },
+
+    #[error("Git error: {0}")]
+    GitError(#[from] GitError),
+
+    #[error("Config error: {0}")]
+    ConfigError(#[from] crate::config::ConfigError),
+
+    #[error("Repository is mid-operation ({}) — finish or abort it before pulling", status.blocking_state().unwrap_or_default())]
+    UnsafeState { status: Box<RepoStatus> },
+}
+
+pub type PullResult<T> = Result<T, PullError>;
+
+/// Where the local branch stands against one forge's copy of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullState {
+    UpToDate,
+    /// Local commits the forge doesn't have; nothing to pull
+    Ahead,
+    /// The forge has commits the branch can fast-forward to
+    Behind,
+    /// Both sides have commits the other lacks; never touched
+    Diverged,
+    /// The forge has no such branch (never pushed, or not fetched)
+    NoRemoteBranch,
+    /// The remote is missing or couldn't be fetched
+    Unreachable,
+}
+
+impl PullState {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::UpToDate => "up_to_date",
+            Self::Ahead => "ahead",
+            Self::Behind => "behind",
+            Self::Diverged => "diverged",
+            Self::NoRemoteBranch => "no_remote_branch",
+            Self::Unreachable => "unreachable",
+        }
+    }
+
+    const fn from_counts(ahead: u32, behind: u32) -> Self {
+        match (ahead, behind) {
+            (0, 0) => Self::UpToDate,
+            (_, 0) => Self::Ahead,
+            (0, _) => Self::Behind,
+            _ => Self::Diverged,
+        }
+    }
+}
+
+/// Result of comparing against a single forge
+#[derive(Debug, Clone)]
+pub struct ForgePullResult {
+    /// Forge name
+    pub forge: String,
+
+    /// Git remote name
+    pub remote_name: String,
+
+    /// Commits on the local branch the forge doesn't have
+    pub ahead: u32,
+
+    /// Commits on the forge the local branch doesn't have
+    pub behind: u32,
+
+    pub state: PullState,
+
+    /// Whether the branch was fast-forwarded to this forge (origin only)
+    pub fast_forwarded: bool,
+
+    /// Fetch or fast-forward error, if any
+    pub error: Option<String>,
+}
+
+/// Overall pull report
+#[derive(Debug)]
+pub struct PullReport {
+    /// Branch that was pulled
+    pub branch: String,
+
+    /// Results for each forge, origin first
+    pub results: Vec<ForgePullResult>,
+
+    /// Whether this was a dry run
+    pub dry_run: bool,
+}
+
+impl PullReport {
+    /// The origin forge's result
+    pub fn origin(&self) -> Option<&ForgePullResult> {
+        self.results.first()
+    }
+
+    /// Whether the branch could not fast-forward to origin
+    pub fn diverged(&self) -> bool {
+        self.origin().is_some_and(|r| r.state == PullState::Diverged)
+    }
+}
+
+/// Fetch all configured forges and fast-forward the current branch to origin
+///
+/// Dry runs still fetch (so the counts are current) but never move the
+/// branch. Repos mid-merge/rebase/bisect or on a detached HEAD are refused.
+pub fn pull(path: &Path, dry_run: bool) -> PullResult<PullReport> {
+    if !HyperforgeConfig::exists(path) {
+        return Err(PullError::NotInitialized);
+    }
+
+    if !Git::is_repo(path) {
+        return Err(PullError::NotAGitRepo {
+            path: path.display().to_string(),
+        });
+    }
+
+    let status = Git::repo_status(path)?;
+    if status.blocking_state().is_some() {
+        return Err(PullError::UnsafeState { status: Box::new(status) });
+    }
+    let branch = status.branch;
+
+    let config = HyperforgeConfig::load(path)?;
+
+    let mut results = Vec::new();
+    for (i, forge) in config.forges.iter().enumerate() {
+        let remote_name = config.remote_for_forge(forge);
+        let mut result = ForgePullResult {
+            forge: forge.clone(),
+            remote_name: remote_name.clone(),
+            ahead: 0,
+            behind: 0,
+            state: PullState::Unreachable,
+            fast_forwarded: false,
+            error: None,
+        };
+
+        if Git::get_remote(path, &remote_name).is_err() {
+            result.error = Some(format!("Remote not found: {remote_name}"));
+            results.push(result);
+            continue;
+        }
+        if let Err(e) = Git::fetch(path, &remote_name) {
+            result.error = Some(e.to_string());
+            results.push(result);
+            continue;
+        }
+
+        let remote_ref = format!("refs/remotes/{remote_name}/{branch}");
+        if Git::resolve_commit(path, &remote_ref)?.is_none() {
+            result.state = PullState::NoRemoteBranch;
+            results.push(result);
+            continue;
+        }
+
+        let (ahead, behind) = Git::ahead_behind(path, &remote_name, &branch)?;
+        result.ahead = ahead;
+        result.behind = behind;
+        result.state = PullState::from_counts(ahead, behind);
+
+        if i == 0 && result.state == PullState::Behind && !dry_run {
+            match Git::merge_ff_only(path, &remote_ref) {
+                Ok(()) => result.fast_forwarded = true,
+                Err(e) => result.error = Some(e.to_string()),
+            }
+        }
+
+        results.push(result);
+    }
+
+    Ok(PullReport {
+        branch,
+        results,
+        dry_run,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::init::{init, InitOptions};
+    use std::fs;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(path: &Path, args: &[&str]) {
+        let output = Command::new("git").args(args).current_dir(path).output().unwrap();
+        assert!(output.status.success(), "git {args:?}: {}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    fn commit(path: &Path, file: &str) {
+        fs::write(path.join(file), file).unwrap();
+        git(path, &["add", file]);
+        git(path, &["commit", "-m", file]);
+    }
+
+    /// A hyperforge repo whose `origin` is a local bare repo, plus a second
+    /// clone of that bare repo for making upstream commits
+    fn setup() -> (TempDir, std::path::PathBuf, std::path::PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let bare = temp.path().join("remote.git");
+        let repo = temp.path().join("repo");
+        let other = temp.path().join("other");
+        fs::create_dir_all(&bare).unwrap();
+        git(&bare, &["init", "--bare", "-b", "main"]);
+
+        fs::create_dir_all(&repo).unwrap();
+        init(&repo, InitOptions::new(vec!["github".to_string()]).with_org("alice")).unwrap();
+        git(&repo, &["checkout", "-B", "main"]);
+        Git::config_set(&repo, "user.email", "test@test.com").unwrap();
+        Git::config_set(&repo, "user.name", "Test").unwrap();
+        Git::set_remote_url(&repo, "origin", bare.to_str().unwrap()).unwrap();
+        commit(&repo, "a.txt");
+        git(&repo, &["push", "-u", "origin", "main"]);
+
+        git(temp.path(), &["clone", "-q", bare.to_str().unwrap(), "other"]);
+        Git::config_set(&other, "user.email", "test@test.com").unwrap();
+        Git::config_set(&other, "user.name", "Test").unwrap();
+        (temp, repo, other)
+    }
+
+    #[test]
+    fn test_pull_not_initialized() {
+        let temp = TempDir::new().unwrap();
+        Git::init(temp.path()).unwrap();
+
+        assert!(matches!(pull(temp.path(), false), Err(PullError::NotInitialized)));
+    }
+
+    #[test]
+    fn test_pull_fast_forwards_origin() {
+        let (_temp, repo, other) = setup();
+        commit(&other, "b.txt");
+        git(&other, &["push", "-q", "origin", "main"]);
+
+        let dry = pull(&repo, true).unwrap();
+        let origin = dry.origin().unwrap();
+        assert_eq!((origin.state, origin.behind, origin.fast_forwarded), (PullState::Behind, 1, false));
+        assert!(!repo.join("b.txt").exists());
+
+        let report = pull(&repo, false).unwrap();
+        let origin = report.origin().unwrap();
+        assert!(origin.fast_forwarded, "{:?}", origin.error);
+        assert!(repo.join("b.txt").exists());
+        assert_eq!(pull(&repo, false).unwrap().origin().unwrap().state, PullState::UpToDate);
+    }
+
+    #[test]
+    fn test_pull_leaves_diverged_branch() {
+        let (_temp, repo, other) = setup();
+        commit(&other, "b.txt");
+        git(&other, &["push", "-q", "origin", "main"]);
+        commit(&repo, "c.txt");
+        let head = Git::head_commit(&repo).unwrap();
+
+        let report = pull(&repo, false).unwrap();
+        assert!(report.diverged());
+        let origin = report.origin().unwrap();
+        assert_eq!((origin.ahead, origin.behind, origin.fast_forwarded), (1, 1, false));
+        assert_eq!(Git::head_commit(&repo).unwrap(), head);
+    }
+}
@@ -0,0 +1,400 @@
+//! Remotes command - Make a repo's git remotes match its config exactly
+//!
+//! Remotes are only added by `init` and `clone`, so later config changes (a
+//! new forge, a moved org, a `fetch = "https"` split) leave them behind.
+//! Converging:
+//! 1. Derives every forge entry's remote name, fetch URL and push URL
+//! 2. Renames a stale remote that already points at a missing remote's URL,
+//!    keeping its remote-tracking branches
+//! 3. Adds missing remotes and fixes differing fetch/push URLs
+//! 4. Removes remotes the config doesn't name, except the ones to keep
+//!
+//! Under `remote_layout = "multi_push"` the first forge's remote is the only
+//! one, carrying every forge's push URL.
+
+use std::path::Path;
+use thiserror::Error;
+
+use crate::config::{HyperforgeConfig, OrgConfig, RemoteLayout};
+use crate::git::{build_remote_url_on, build_remote_url_with, Git, GitError, RemoteInfo};
+
+/// Errors that can occur while converging remotes
+#[derive(Debug, Error)]
+pub enum RemotesError {
+    #[error("Not a hyperforge repository. Run 'hyperforge init' first.")]
+    NotInitialized,
+
+    #[error("Not a git repository: {path}")]
+    NotAGitRepo { path: String },
+
+    #[error("Git error: {0}")]
+    GitError(#[from] GitError),
+
+    #[error("Config error: {0}")]
+    ConfigError(#[from] crate::config::ConfigError),
+
+    #[error("Forges {first} and {second} both map to remote '{name}'; set [forge.<name>] remote to tell them apart")]
+    Collision { name: String, first: String, second: String },
+}
+
+pub type RemotesResult<T> = Result<T, RemotesError>;
+
+/// A remote as the config dictates it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesiredRemote {
+    pub name: String,
+    /// The `forges` entry it belongs to
+    pub forge: String,
+    pub fetch_url: String,
+    /// Same as `fetch_url` unless the forge splits fetch and push
+    pub push_url: String,
+    /// The other forges' push URLs, for a multi-push remote
+    pub extra_push_urls: Vec<String>,
+}
+
+impl DesiredRemote {
+    fn push_urls(&self) -> Vec<&str> {
+        std::iter::once(self.push_url.as_str())
+            .chain(self.extra_push_urls.iter().map(String::as_str))
+            .collect()
+    }
+}
+
+/// How one remote changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteAction {
+    Add,
+    /// A stale remote already pointing at the URL takes the configured name
+    Rename,
+    SetUrl,
+    Remove,
+}
+
+impl RemoteAction {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Rename => "rename",
+            Self::SetUrl => "set_url",
+            Self::Remove => "remove",
+        }
+    }
+}
+
+/// One change to a repo's remotes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteChange {
+    /// Remote name after the change (the removed name, for removals)
+    pub name: String,
+    pub action: RemoteAction,
+    /// Previous name, for renames
+    pub renamed_from: Option<String>,
+    pub old: Option<RemoteInfo>,
+    pub new: Option<DesiredRemote>,
+}
+
+impl RemoteChange {
+    /// `git remote -v` style lines, `-` for what goes and `+` for what comes
+    pub fn diff_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(old) = &self.old {
+            lines.push(format!("- {}\t{} (fetch)", old.name, old.fetch_url));
+            lines.extend(old.push_urls().iter().map(|url| format!("- {}\t{url} (push)", old.name)));
+        }
+        if let Some(new) = &self.new {
+            lines.push(format!("+ {}\t{} (fetch)", new.name, new.fetch_url));
+            lines.extend(new.push_urls().iter().map(|url| format!("+ {}\t{url} (push)", new.name)));
+        }
+        lines
+    }
+}
+
+/// Overall result of converging one repo
+#[derive(Debug)]
+pub struct RemotesReport {
+    pub changes: Vec<RemoteChange>,
+    /// Remotes already matching the config
+    pub in_sync: usize,
+    /// Whether `changes` were applied
+    pub applied: bool,
+}
+
+/// The remotes `config` dictates for the repo at `path`, in forge order.
+///
+/// Entries without an org are skipped: there's no URL to give them. A
+/// multi-push layout folds every later forge into the first one's remote.
+pub fn desired_remotes(path: &Path, config: &HyperforgeConfig) -> RemotesResult<Vec<DesiredRemote>> {
+    let repo_name = config.get_repo_name(path);
+    let mut desired: Vec<DesiredRemote> = Vec::new();
+
+    for entry in &config.forges {
+        let Some(org) = config.org_for_forge(entry) else { continue };
+        let (base, _) = HyperforgeConfig::split_account(entry);
+        // Self-hosted instance: the repo's host, else the org's
+        let host = config
+            .forge_host(entry)
+            .map(str::to_string)
+            .or_else(|| OrgConfig::load_default(org).forge_host(base).map(str::to_string));
+
+        let push_url = build_remote_url_on(base, host.as_deref(), org, &repo_name);
+        let fetch = [entry.as_str(), base]
+            .iter()
+            .find_map(|f| config.forge_config.get(*f).and_then(|fc| fc.fetch));
+        let fetch_url = match fetch {
+            Some(transport) => build_remote_url_with(host.as_deref().unwrap_or(base), org, &repo_name, transport),
+            None => push_url.clone(),
+        };
+
+        if config.remote_layout == RemoteLayout::MultiPush {
+            if let Some(first) = desired.first_mut() {
+                first.extra_push_urls.push(push_url);
+                continue;
+            }
+        }
+
+        let name = config.remote_for_forge(entry);
+        if let Some(other) = desired.iter().find(|d| d.name == name) {
+            return Err(RemotesError::Collision { name, first: other.forge.clone(), second: entry.clone() });
+        }
+        desired.push(DesiredRemote { name, forge: entry.clone(), fetch_url, push_url, extra_push_urls: Vec::new() });
+    }
+
+    Ok(desired)
+}
+
+/// Changes that turn `current` into `desired`. Remotes named in `keep` are
+/// never renamed or removed.
+pub fn plan(desired: &[DesiredRemote], current: &[RemoteInfo], keep: &[String]) -> (Vec<RemoteChange>, usize) {
+    let is_desired = |name: &str| desired.iter().any(|d| d.name == name);
+    let mut stale: Vec<&RemoteInfo> = current
+        .iter()
+        .filter(|r| !is_desired(&r.name) && !keep.contains(&r.name))
+        .collect();
+
+    let mut changes = Vec::new();
+    let mut in_sync = 0usize;
+    for want in desired {
+        if let Some(have) = current.iter().find(|r| r.name == want.name) {
+            if have.fetch_url == want.fetch_url && have.push_urls() == want.push_urls() {
+                in_sync += 1;
+            } else {
+                changes.push(RemoteChange {
+                    name: want.name.clone(),
+                    action: RemoteAction::SetUrl,
+                    renamed_from: None,
+                    old: Some(have.clone()),
+                    new: Some(want.clone()),
+                });
+            }
+            continue;
+        }
+
+        let reusable = stale
+            .iter()
+            .position(|r| r.fetch_url == want.fetch_url || r.push_url == want.push_url);
+        let change = match reusable.map(|i| stale.remove(i)) {
+            Some(have) => RemoteChange {
+                name: want.name.clone(),
+                action: RemoteAction::Rename,
+                renamed_from: Some(have.name.clone()),
+                old: Some(have.clone()),
+                new: Some(want.clone()),
+            },
+            None => RemoteChange {
+                name: want.name.clone(),
+                action: RemoteAction::Add,
+                renamed_from: None,
+                old: None,
+                new: Some(want.clone()),
+            },
+        };
+        changes.push(change);
+    }
+
+    for have in stale {
+        changes.push(RemoteChange {
+            name: have.name.clone(),
+            action: RemoteAction::Remove,
+            renamed_from: None,
+            old: Some(have.clone()),
+            new: None,
+        });
+    }
+
+    (changes, in_sync)
+}
+
+/// Converge the repo's remotes on its config. Dry runs only plan.
+pub fn converge(path: &Path, keep: &[String], dry_run: bool) -> RemotesResult<RemotesReport> {
+    if !HyperforgeConfig::exists(path) {
+        return Err(RemotesError::NotInitialized);
+    }
+
+    if !Git::is_repo(path) {
+        return Err(RemotesError::NotAGitRepo {
+            path: path.display().to_string(),
+        });
+    }
+
+    let config = HyperforgeConfig::load(path)?;
+    let desired = desired_remotes(path, &config)?;
+    let current = Git::list_remotes(path)?;
+    let (changes, in_sync) = plan(&desired, &current, keep);
+
+    if !dry_run {
+        for change in &changes {
+            apply(path, change)?;
+        }
+    }
+
+    Ok(RemotesReport {
+        applied: !dry_run && !changes.is_empty(),
+        changes,
+        in_sync,
+    })
+}
+
+fn apply(path: &Path, change: &RemoteChange) -> RemotesResult<()> {
+    let Some(want) = &change.new else {
+        Git::remove_remote(path, &change.name)?;
+        return Ok(());
+    };
+
+    match (change.action, &change.renamed_from) {
+        (RemoteAction::Add, _) => Git::add_remote(path, &want.name, &want.fetch_url)?,
+        (RemoteAction::Rename, Some(from)) => {
+            Git::rename_remote(path, from, &want.name)?;
+            Git::set_remote_url(path, &want.name, &want.fetch_url)?;
+        }
+        _ => Git::set_remote_url(path, &want.name, &want.fetch_url)?,
+    }
+    if want.extra_push_urls.is_empty() {
+        let push_url = (want.push_url != want.fetch_url).then_some(want.push_url.as_str());
+        Git::set_remote_push_url(path, &want.name, push_url)?;
+    } else {
+        Git::set_remote_push_urls(path, &want.name, &want.push_urls())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::init::{init, InitOptions};
+    use crate::config::ForgeConfig;
+    use crate::git::Transport;
+    use tempfile::TempDir;
+
+    fn remote(name: &str, url: &str) -> RemoteInfo {
+        RemoteInfo { name: name.to_string(), fetch_url: url.to_string(), push_url: url.to_string(), extra_push_urls: Vec::new() }
+    }
+
+    #[test]
+    fn test_plan_renames_fixes_and_removes() {
+        let desired = vec![
+            DesiredRemote {
+                name: "origin".to_string(),
+                forge: "github".to_string(),
+                fetch_url: "git@github.com:alice/tool.git".to_string(),
+                push_url: "git@github.com:alice/tool.git".to_string(),
+                extra_push_urls: Vec::new(),
+            },
+            DesiredRemote {
+                name: "codeberg".to_string(),
+                forge: "codeberg".to_string(),
+                fetch_url: "git@codeberg.org:alice/tool.git".to_string(),
+                push_url: "git@codeberg.org:alice/tool.git".to_string(),
+                extra_push_urls: Vec::new(),
+            },
+        ];
+        let current = vec![
+            remote("origin", "git@github.com:old/tool.git"),
+            remote("cb", "git@codeberg.org:alice/tool.git"),
+            remote("upstream", "git@github.com:someone/tool.git"),
+            remote("scratch", "/tmp/scratch.git"),
+        ];
+
+        let (changes, in_sync) = plan(&desired, &current, &["upstream".to_string()]);
+        assert_eq!(in_sync, 0);
+        let actions: Vec<_> = changes.iter().map(|c| (c.name.as_str(), c.action)).collect();
+        assert_eq!(
+            actions,
+            vec![("origin", RemoteAction::SetUrl), ("codeberg", RemoteAction::Rename), ("scratch", RemoteAction::Remove)]
+        );
+        assert_eq!(changes[1].renamed_from.as_deref(), Some("cb"));
+        assert_eq!(changes[0].diff_lines()[0], "- origin\tgit@github.com:old/tool.git (fetch)");
+    }
+
+    #[test]
+    fn test_converge_applies_fetch_push_split() {
+        let temp = TempDir::new().unwrap();
+        init(temp.path(), InitOptions::new(vec!["github".to_string(), "codeberg".to_string()]).with_org("alice").with_repo_name("tool")).unwrap();
+        Git::add_remote(temp.path(), "stale", "/tmp/stale.git").unwrap();
+
+        let mut config = HyperforgeConfig::load(temp.path()).unwrap();
+        config.forge_config.insert(
+            "github".to_string(),
+            ForgeConfig { fetch: Some(Transport::Https), ..Default::default() },
+        );
+        config.save(temp.path()).unwrap();
+
+        let dry = converge(temp.path(), &[], true).unwrap();
+        assert!(!dry.applied);
+        assert_eq!(dry.changes.len(), 2);
+        assert_eq!(Git::list_remotes(temp.path()).unwrap().len(), 3);
+
+        let report = converge(temp.path(), &[], false).unwrap();
+        assert!(report.applied);
+        let origin = Git::get_remote(temp.path(), "origin").unwrap();
+        assert_eq!(origin.fetch_url, "https://github.com/alice/tool.git");
+        assert_eq!(origin.push_url, "git@github.com:alice/tool.git");
+        assert!(Git::get_remote(temp.path(), "stale").is_err());
+
+        let again = converge(temp.path(), &[], false).unwrap();
+        assert!(again.changes.is_empty());
+        assert_eq!(again.in_sync, 2);
+    }
+
+    #[test]
+    fn test_converge_multi_push_layout() {
+        let temp = TempDir::new().unwrap();
+        init(temp.path(), InitOptions::new(vec!["github".to_string(), "codeberg".to_string()]).with_org("alice").with_repo_name("tool")).unwrap();
+
+        let mut config = HyperforgeConfig::load(temp.path()).unwrap();
+        config.remote_layout = RemoteLayout::MultiPush;
+        config.save(temp.path()).unwrap();
+        assert_eq!(config.remote_for_forge("codeberg"), "origin");
+
+        let report = converge(temp.path(), &[], false).unwrap();
+        let actions: Vec<_> = report.changes.iter().map(|c| (c.name.as_str(), c.action)).collect();
+        assert_eq!(actions, vec![("origin", RemoteAction::SetUrl), ("codeberg", RemoteAction::Remove)]);
+
+        let remotes = Git::list_remotes(temp.path()).unwrap();
+        assert_eq!(remotes.len(), 1);
+        assert_eq!(remotes[0].fetch_url, "git@github.com:alice/tool.git");
+        assert_eq!(remotes[0].push_urls(), vec!["git@github.com:alice/tool.git", "git@codeberg.org:alice/tool.git"]);
+
+        let again = converge(temp.path(), &[], false).unwrap();
+        assert!(again.changes.is_empty());
+
+        // Back to one remote per forge: the single push URL goes away
+        config.remote_layout = RemoteLayout::PerForge;
+        config.save(temp.path()).unwrap();
+        converge(temp.path(), &[], false).unwrap();
+        let origin = Git::get_remote(temp.path(), "origin").unwrap();
+        assert_eq!(origin.push_urls(), vec!["git@github.com:alice/tool.git"]);
+        assert!(Git::get_remote(temp.path(), "codeberg").is_ok());
+    }
+
+    #[test]
+    fn test_remote_name_collision() {
+        let temp = TempDir::new().unwrap();
+        let mut config = HyperforgeConfig::new(vec!["github".to_string(), "codeberg".to_string()]).with_org("alice");
+        config.forge_config.insert(
+            "codeberg".to_string(),
+            ForgeConfig { remote: Some("origin".to_string()), ..Default::default() },
+        );
+
+        assert!(matches!(desired_remotes(temp.path(), &config), Err(RemotesError::Collision { .. })));
+    }
+}
@@ -1,33 +1,27 @@
 //! `WorkspaceRunner` — reusable concurrency abstraction for workspace-level batch operations.
 //!
-//! Eliminates duplicated JoinSet/chunking boilerplate across workspace methods.
-//! All functions return `Vec<(usize, R)>` where usize is the original index,
-//! so callers can correlate results with inputs if needed.
+//! Eliminates duplicated `JoinSet` boilerplate across workspace methods.
+//!
+//! Results always come back in input order, whatever order the tasks finish
+//! in. Inputs are built from discovery (sorted by repo name) or from sorted
+//! org/forge pairs, so every report built from a batch lists repos in the
+//! same order on every run.
 
+use std::collections::HashMap;
 use std::future::Future;
-use tokio::task::JoinSet;
-
-/// Split a Vec into chunks without requiring Clone.
-fn chunk_vec<T>(items: Vec<T>, chunk_size: usize) -> Vec<Vec<T>> {
-    let mut result = Vec::new();
-    let mut current = Vec::new();
-    for item in items {
-        current.push(item);
-        if current.len() == chunk_size {
-            result.push(std::mem::take(&mut current));
-        }
-    }
-    if !current.is_empty() {
-        result.push(current);
-    }
-    result
-}
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::task::{Id, JoinSet};
+
+use crate::adapters::simulation;
+use crate::scheduler::{Resource, Scheduler};
 
 /// Run a batch of blocking operations with bounded concurrency.
 ///
-/// Each item is dispatched via `tokio::task::spawn_blocking`. Items are
-/// processed in chunks of `concurrency` to bound resource usage. Results
-/// are returned in completion order within each chunk.
+/// Each item is dispatched via `tokio::task::spawn_blocking` once it holds a
+/// global git slot from the [`Scheduler`], with at most `concurrency` items of
+/// this batch in flight (`0` = bounded only by the scheduler). Results are
+/// returned in input order.
 ///
 /// Use this for git CLI operations and other synchronous work.
 pub async fn run_batch_blocking<T, R, F>(
@@ -41,35 +35,45 @@ where
     F: Fn(T) -> R + Send + Sync + Clone + 'static,
 {
     let len = items.len();
-    let mut results = Vec::with_capacity(len);
-    let chunk_size = if concurrency == 0 { len.max(1) } else { concurrency };
-
-    let chunked = chunk_vec(items, chunk_size);
+    let mut results = InOrder::new(len);
+    let window = window(len, concurrency);
+    let mut join_set = JoinSet::new();
+
+    for (index, item) in items.into_iter().enumerate() {
+        let slot = acquire(&window).await;
+        let permit = Scheduler::global().acquire(Resource::Git).await;
+        let op = op.clone();
+        let task = join_set.spawn(tokio::task::spawn_blocking(move || {
+            let _slot = slot;
+            let _permit = permit;
+            op(item)
+        }));
+        results.started(task.id(), index);
+    }
 
-    for chunk in chunked {
-        let mut join_set = JoinSet::new();
+    while let Some(joined) = join_set.join_next_with_id().await {
+        results.finish(split_blocking(joined));
+    }
 
-        for item in chunk {
-            let op = op.clone();
-            join_set.spawn(tokio::task::spawn_blocking(move || op(item)));
-        }
+    results.into_vec()
+}
 
-        while let Some(result) = join_set.join_next().await {
-            match result {
-                Ok(Ok(value)) => results.push(Ok(value)),
-                Ok(Err(e)) => results.push(Err(format!("spawn_blocking panic: {e}"))),
-                Err(e) => results.push(Err(format!("JoinSet error: {e}"))),
-            }
-        }
+fn split_blocking<R>(
+    joined: Result<(Id, Result<R, tokio::task::JoinError>), tokio::task::JoinError>,
+) -> (Id, Result<R, String>) {
+    match joined {
+        Ok((id, Ok(value))) => (id, Ok(value)),
+        Ok((id, Err(e))) => (id, Err(format!("spawn_blocking panic: {e}"))),
+        Err(e) => (e.id(), Err(format!("JoinSet error: {e}"))),
     }
-
-    results
 }
 
 /// Run a batch of async operations with bounded concurrency.
 ///
-/// Items are processed in chunks of `concurrency`. Use `concurrency = 0`
-/// for unbounded (all items spawned at once).
+/// At most `concurrency` items are in flight; a new one starts as soon as
+/// any finishes. Use `concurrency = 0` for unbounded (all items spawned at
+/// once). Forge requests made by `op` are additionally bounded per forge by
+/// the global [`Scheduler`]. Results are returned in input order.
 ///
 /// Use this for forge API calls and other async work.
 pub async fn run_batch<T, R, F, Fut>(
@@ -84,26 +88,70 @@ where
     Fut: Future<Output = R> + Send + 'static,
 {
     let len = items.len();
-    let mut results = Vec::with_capacity(len);
-    let chunk_size = if concurrency == 0 { len.max(1) } else { concurrency };
+    let mut results = InOrder::new(len);
+    let window = window(len, concurrency);
+    let mut join_set = JoinSet::new();
+
+    for (index, item) in items.into_iter().enumerate() {
+        let slot = acquire(&window).await;
+        let op = op.clone();
+        let task = join_set.spawn(simulation::carry(async move {
+            let _slot = slot;
+            op(item).await
+        }));
+        results.started(task.id(), index);
+    }
 
-    for chunk in chunk_vec(items, chunk_size) {
-        let mut join_set = JoinSet::new();
+    while let Some(joined) = join_set.join_next_with_id().await {
+        results.finish(split(joined));
+    }
 
-        for item in chunk {
-            let op = op.clone();
-            join_set.spawn(async move { op(item).await });
-        }
+    results.into_vec()
+}
 
-        while let Some(result) = join_set.join_next().await {
-            match result {
-                Ok(value) => results.push(Ok(value)),
-                Err(e) => results.push(Err(format!("JoinSet error: {e}"))),
-            }
+/// A batch's own slots: `concurrency`, or one per item when it is `0`
+fn window(len: usize, concurrency: usize) -> Arc<Semaphore> {
+    Arc::new(Semaphore::new(if concurrency == 0 { len.max(1) } else { concurrency }))
+}
+
+/// Wait until a task of the batch finishes if all its slots are taken. The
+/// slot is released when the task holding it ends, not when it's joined.
+async fn acquire(window: &Arc<Semaphore>) -> OwnedSemaphorePermit {
+    window.clone().acquire_owned().await.expect("batch semaphores are never closed")
+}
+
+fn split<R>(joined: Result<(Id, R), tokio::task::JoinError>) -> (Id, Result<R, String>) {
+    match joined {
+        Ok((id, value)) => (id, Ok(value)),
+        Err(e) => (e.id(), Err(format!("JoinSet error: {e}"))),
+    }
+}
+
+/// A batch's results, filled in as tasks finish and read back in the order
+/// the tasks were started
+struct InOrder<R> {
+    index_of: HashMap<Id, usize>,
+    slots: Vec<Option<Result<R, String>>>,
+}
+
+impl<R> InOrder<R> {
+    fn new(len: usize) -> Self {
+        Self { index_of: HashMap::with_capacity(len), slots: (0..len).map(|_| None).collect() }
+    }
+
+    fn started(&mut self, id: Id, index: usize) {
+        self.index_of.insert(id, index);
+    }
+
+    fn finish(&mut self, (id, result): (Id, Result<R, String>)) {
+        if let Some(index) = self.index_of.remove(&id) {
+            self.slots[index] = Some(result);
         }
     }
 
-    results
+    fn into_vec(self) -> Vec<Result<R, String>> {
+        self.slots.into_iter().flatten().collect()
+    }
 }
 
 /// Discover workspace or return an error event.
@@ -123,17 +171,49 @@ pub fn discover_or_bail(
     path: &std::path::Path,
 ) -> Result<crate::commands::workspace::WorkspaceContext, crate::hub::HyperforgeEvent> {
     crate::commands::workspace::discover_workspace(path).map_err(|e| {
-        crate::hub::HyperforgeEvent::Error {
-            message: format!("Discovery failed: {e}"),
-        }
+        crate::hub::HyperforgeEvent::error(
+            crate::hub::HyperforgeError::Config,
+            format!("Discovery failed: {e}"),
+        )
     })
 }
 
+/// Progress from [`discover_streaming`]
+pub enum DiscoveryProgress {
+    /// A configured repo, as soon as its manifests are parsed
+    Repo(Box<crate::commands::workspace::DiscoveredRepo>),
+    /// The scan finished; carries the full sorted context
+    Done(crate::commands::workspace::WorkspaceResult<crate::commands::workspace::WorkspaceContext>),
+}
+
+/// Run workspace discovery on a blocking thread, yielding each repo as it is
+/// found and the finished context last. `reindex` ignores the workspace index.
+pub fn discover_streaming(
+    path: std::path::PathBuf,
+    reindex: bool,
+) -> impl futures::Stream<Item = DiscoveryProgress> + Send + 'static {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    tokio::task::spawn_blocking(move || {
+        let result = crate::commands::workspace::discover_workspace_with(&path, reindex, |repo| {
+            let _ = tx.send(DiscoveryProgress::Repo(Box::new(repo.clone())));
+        });
+        let _ = tx.send(DiscoveryProgress::Done(result));
+    });
+    async_stream::stream! {
+        while let Some(progress) = rx.recv().await {
+            yield progress;
+        }
+    }
+}
+
 /// Result of a parallel diff batch across org/forge pairs.
 pub struct DiffBatchEntry {
     pub org_name: String,
     pub forge_name: String,
     pub diff_result: Result<crate::services::SyncDiff, String>,
+    /// No token was stored, so the forge was listed anonymously and only
+    /// its public repos were seen
+    pub anonymous: bool,
 }
 
 /// Run diffs in parallel for a set of org/forge pairs.
@@ -149,19 +229,22 @@ pub async fn run_diff_batch(
         .iter()
         .map(|(o, f)| (o.clone(), f.clone(), state.clone(), sync_service.clone()))
         .collect();
-    run_batch(items, 8, |(org_name, forge_name, state, sync_service)| async move {
+    run_batch(items, Scheduler::global().batch(None), |(org_name, forge_name, state, sync_service)| async move {
         let local = state.get_local_forge(&org_name).await;
         let ot = local.owner_type();
-        let adapter = match crate::hubs::utils::make_adapter(&forge_name, &org_name, ot) {
+        let host = crate::hubs::utils::forge_host(&state.config_dir, &org_name, &forge_name, None);
+        let adapter = match crate::hubs::utils::make_adapter(&forge_name, &org_name, ot, host.as_deref()) {
             Ok(a) => a,
             Err(e) => {
                 return DiffBatchEntry {
                     org_name,
                     forge_name,
                     diff_result: Err(e),
+                    anonymous: false,
                 };
             }
         };
+        let anonymous = !adapter.has_token().await;
         let result = sync_service
             .diff(local, adapter, &org_name)
             .await
@@ -170,6 +253,7 @@ pub async fn run_diff_batch(
             org_name,
             forge_name,
             diff_result: result,
+            anonymous,
         }
     })
     .await
@@ -194,6 +278,39 @@ pub type PushBatchRow = (
     crate::commands::push::PushResult<crate::commands::push::PushReport>,
 );
 
+/// One `PushGuardFinding` event per push guard finding.
+pub fn guard_finding_events(
+    repo_name: &str,
+    findings: &[crate::commands::push_guard::GuardFinding],
+) -> Vec<crate::hub::HyperforgeEvent> {
+    findings
+        .iter()
+        .map(|f| crate::hub::HyperforgeEvent::PushGuardFinding {
+            repo_name: repo_name.to_string(),
+            kind: f.kind.as_str().to_string(),
+            commit: f.commit.clone(),
+            path: f.path.clone(),
+            detail: f.detail.clone(),
+        })
+        .collect()
+}
+
+/// `RepoBlocked` event for a repo refused because of its in-progress state.
+pub fn blocked_event(
+    repo_name: &str,
+    operation: &str,
+    status: &crate::git::RepoStatus,
+) -> crate::hub::HyperforgeEvent {
+    crate::hub::HyperforgeEvent::RepoBlocked {
+        repo_name: repo_name.to_string(),
+        operation: operation.to_string(),
+        state: status.blocking_state().unwrap_or_default(),
+        in_progress: status.in_progress.map(|op| op.as_str().to_string()),
+        conflicts: status.conflicts,
+        detached: status.detached,
+    }
+}
+
 /// Process the results of a parallel push batch into events and counts.
 ///
 /// This is the shared result processing used by both `push_all` and sync Phase 8.
@@ -209,9 +326,10 @@ pub fn collect_push_results(
         let (dir_name, path, push_result) = match result {
             Ok(v) => v,
             Err(e) => {
-                events.push(crate::hub::HyperforgeEvent::Error {
-                    message: format!("Task error: {e}"),
-                });
+                events.push(crate::hub::HyperforgeEvent::error(
+                    crate::hub::HyperforgeError::Internal,
+                    format!("Task error: {e}"),
+                ));
                 failed_count += 1;
                 continue;
             }
@@ -239,6 +357,15 @@ pub fn collect_push_results(
                 }
             }
             Err(e) => {
+                match &e {
+                    crate::commands::push::PushError::GuardBlocked { findings } => {
+                        events.extend(guard_finding_events(&dir_name, findings));
+                    }
+                    crate::commands::push::PushError::UnsafeState { status } => {
+                        events.push(blocked_event(&dir_name, "push", status));
+                    }
+                    _ => {}
+                }
                 events.push(crate::hub::HyperforgeEvent::RepoPush {
                     repo_name: dir_name.clone(),
                     path: path.display().to_string(),
@@ -302,12 +429,13 @@ pub fn run_validation_gate(
                 duration_ms: summary.duration_ms,
             });
             if !passed {
-                events.push(crate::hub::HyperforgeEvent::Error {
-                    message: format!(
+                events.push(crate::hub::HyperforgeEvent::error(
+                    crate::hub::HyperforgeError::Validation,
+                    format!(
                         "Validation failed ({}/{} steps failed) — aborting push.",
                         summary.failed, summary.total
                     ),
-                });
+                ));
             }
             ValidationGateResult {
                 events,
@@ -315,9 +443,10 @@ pub fn run_validation_gate(
             }
         }
         Err(e) => ValidationGateResult {
-            events: vec![crate::hub::HyperforgeEvent::Error {
-                message: format!("Validation plan failed: {e} — aborting push."),
-            }],
+            events: vec![crate::hub::HyperforgeEvent::error(
+                crate::hub::HyperforgeError::Validation,
+                format!("Validation plan failed: {e} — aborting push."),
+            )],
             passed: Some(false),
         },
     }
@@ -367,6 +496,28 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_results_come_back_in_input_order() {
+        // Later items finish first
+        let items: Vec<u64> = (0..8).collect();
+        let results = run_batch(items, 0, |x| async move {
+            tokio::time::sleep(std::time::Duration::from_millis(40 - x * 5)).await;
+            x
+        })
+        .await;
+        let values: Vec<u64> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(values, (0..8).collect::<Vec<_>>());
+
+        let items: Vec<u64> = (0..6).collect();
+        let results = run_batch_blocking(items, 3, |x| {
+            std::thread::sleep(std::time::Duration::from_millis(30 - x * 5));
+            x
+        })
+        .await;
+        let values: Vec<u64> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(values, (0..6).collect::<Vec<_>>());
+    }
+
     #[tokio::test]
     async fn test_run_batch_async_unbounded() {
         let items: Vec<i32> = (0..10).collect();
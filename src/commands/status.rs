@@ -7,9 +7,12 @@
 //! - Sync status for each configured forge (ahead/behind)
 //! - Working tree status (clean/dirty)
 
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 use thiserror::Error;
 
+use crate::build_system::cross_compile::sha256_hex;
 use crate::config::HyperforgeConfig;
 use crate::git::{Git, GitError};
 
@@ -138,6 +141,15 @@ pub struct RepoStatusReport {
     /// Whether there are untracked files
     pub has_untracked: bool,
 
+    /// Changed, added, deleted and untracked paths
+    pub dirty_files: usize,
+
+    /// Stash entries
+    pub stashes: usize,
+
+    /// Committer time of HEAD (unix seconds)
+    pub last_commit_at: Option<i64>,
+
     /// Value of core.sshCommand git config
     pub ssh_command: Option<String>,
 
@@ -209,6 +221,108 @@ impl RepoStatusReport {
     }
 }
 
+/// Canonical, diffable status of a whole workspace
+///
+/// Written by `workspace status --snapshot <file>`. Repos and forges are keyed
+/// in sorted maps and nothing time-relative is recorded, so two snapshots of
+/// unchanged repos are byte-identical and committing the file to git gives a
+/// readable history of the fleet.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusSnapshot {
+    pub repos: BTreeMap<String, RepoSnapshot>,
+}
+
+/// One repo's entry in a [`StatusSnapshot`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoSnapshot {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub branch: String,
+    #[serde(default)]
+    pub dirty: bool,
+    #[serde(default)]
+    pub dirty_files: usize,
+    #[serde(default)]
+    pub stashes: usize,
+    /// Committer time of HEAD (unix seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commit_at: Option<i64>,
+    /// SHA-256 of `.hyperforge/config.toml`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub forges: BTreeMap<String, ForgeSnapshot>,
+    /// Set when the repo's status couldn't be read; other fields are empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Sync state against one forge's remote
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForgeSnapshot {
+    pub remote: String,
+    pub exists: bool,
+    pub ahead: u32,
+    pub behind: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RepoSnapshot {
+    /// The entry for a repo at `repo_path` with status `report`
+    pub fn from_report(repo_path: &Path, report: &RepoStatusReport) -> Self {
+        Self {
+            branch: report.branch.clone(),
+            dirty: !report.is_clean(),
+            dirty_files: report.dirty_files,
+            stashes: report.stashes,
+            last_commit_at: report.last_commit_at,
+            config_sha256: std::fs::read(HyperforgeConfig::config_path(repo_path))
+                .ok()
+                .map(|bytes| sha256_hex(&bytes)),
+            forges: report
+                .forges
+                .iter()
+                .map(|f| {
+                    (f.forge.clone(), ForgeSnapshot {
+                        remote: f.remote_name.clone(),
+                        exists: f.remote_exists,
+                        ahead: f.ahead,
+                        behind: f.behind,
+                        error: f.error.clone(),
+                    })
+                })
+                .collect(),
+            error: None,
+        }
+    }
+
+    /// The entry for a repo whose status failed with `error`
+    pub fn failed(error: impl Into<String>) -> Self {
+        Self {
+            error: Some(error.into()),
+            ..Self::default()
+        }
+    }
+}
+
+impl StatusSnapshot {
+    /// The snapshot as written: pretty JSON with a trailing newline
+    pub fn to_canonical_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map(|json| json + "\n")
+            .map_err(|e| format!("Failed to serialize status snapshot: {e}"))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        std::fs::write(path, self.to_canonical_json()?)
+            .map_err(|e| format!("Failed to write status snapshot {}: {e}", path.display()))
+    }
+}
+
 /// Get status for a hyperforge repository
 ///
 /// # Arguments
@@ -217,6 +331,12 @@ impl RepoStatusReport {
 /// # Returns
 /// `RepoStatusReport` with status information
 pub fn status(path: &Path) -> StatusResult<RepoStatusReport> {
+    status_with(path, true)
+}
+
+/// [`status`], fetching all remotes first only if `fetch` is set; otherwise
+/// ahead/behind counts are against the remote-tracking refs as last fetched
+pub fn status_with(path: &Path, fetch: bool) -> StatusResult<RepoStatusReport> {
     // Check if hyperforge config exists
     if !HyperforgeConfig::exists(path) {
         return Err(StatusError::NotInitialized);
@@ -237,7 +357,9 @@ pub fn status(path: &Path) -> StatusResult<RepoStatusReport> {
 
     // Fetch all remotes to get accurate ahead/behind counts
     // (ignore errors - remote might not be reachable)
-    let _ = Git::fetch_all(path);
+    if fetch {
+        let _ = Git::fetch_all(path);
+    }
 
     // Check status for each forge
     let mut forge_statuses = Vec::new();
@@ -297,6 +419,9 @@ pub fn status(path: &Path) -> StatusResult<RepoStatusReport> {
         has_changes: repo_status.has_changes,
         has_staged: repo_status.has_staged,
         has_untracked: repo_status.has_untracked,
+        dirty_files: repo_status.files.total(),
+        stashes: Git::stash_count(path)?,
+        last_commit_at: Git::last_commit_time(path)?,
         ssh_command,
         hyperforge_org,
     })
@@ -412,6 +537,73 @@ mod tests {
         assert!(!report.is_clean());
     }
 
+    #[test]
+    fn test_status_stashes_and_last_commit() {
+        let temp = TempDir::new().unwrap();
+
+        let options = InitOptions::new(vec!["github".to_string()])
+            .with_org("alice");
+        init(temp.path(), options).unwrap();
+        setup_repo_with_commit(temp.path());
+
+        fs::write(temp.path().join("README.md"), "# Changed").unwrap();
+        Command::new("git")
+            .args(["stash"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        fs::write(temp.path().join("a.txt"), "a").unwrap();
+        fs::write(temp.path().join("b.txt"), "b").unwrap();
+
+        let report = status_with(temp.path(), false).unwrap();
+
+        assert_eq!(report.stashes, 1);
+        assert_eq!(report.dirty_files, 2);
+        let age = chrono::Utc::now().timestamp() - report.last_commit_at.unwrap();
+        assert!((0..600).contains(&age));
+    }
+
+    #[test]
+    fn test_status_snapshot_is_canonical() {
+        let temp = TempDir::new().unwrap();
+        for name in ["zeta", "alpha"] {
+            let dir = temp.path().join(name);
+            fs::create_dir_all(&dir).unwrap();
+            let options = InitOptions::new(vec!["github".to_string(), "codeberg".to_string()])
+                .with_org("alice");
+            init(&dir, options).unwrap();
+            setup_repo_with_commit(&dir);
+        }
+        fs::write(temp.path().join("zeta").join("new.txt"), "x").unwrap();
+
+        let take = || {
+            let mut snapshot = StatusSnapshot::default();
+            for name in ["zeta", "alpha"] {
+                let dir = temp.path().join(name);
+                let report = status_with(&dir, false).unwrap();
+                snapshot.repos.insert(name.to_string(), RepoSnapshot::from_report(&dir, &report));
+            }
+            snapshot.repos.insert("broken".to_string(), RepoSnapshot::failed("not a git repo"));
+            snapshot.to_canonical_json().unwrap()
+        };
+        let json = take();
+        assert_eq!(json, take());
+
+        let alpha = json.find("\"alpha\"").unwrap();
+        assert!(alpha < json.find("\"broken\"").unwrap());
+        assert!(json.find("\"codeberg\"").unwrap() < json.find("\"github\"").unwrap());
+
+        let snapshot: StatusSnapshot = serde_json::from_str(&json).unwrap();
+        assert!(snapshot.repos["zeta"].dirty);
+        assert!(!snapshot.repos["alpha"].dirty);
+        assert_eq!(snapshot.repos["alpha"].config_sha256.as_ref().map(String::len), Some(64));
+        assert_eq!(snapshot.repos["broken"].error.as_deref(), Some("not a git repo"));
+
+        let path = temp.path().join("out").join("status.json");
+        snapshot.save(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), json);
+    }
+
     #[test]
     fn test_forge_status_symbols() {
         let up_to_date = ForgeStatus {
@@ -520,6 +712,9 @@ mod tests {
             has_changes: false,
             has_staged: false,
             has_untracked: false,
+            dirty_files: 0,
+            stashes: 0,
+            last_commit_at: None,
             ssh_command: None,
             hyperforge_org: None,
         };
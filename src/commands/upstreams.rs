@@ -0,0 +1,254 @@
+//! Upstreams command - Audit and repair local branches' upstream tracking
+//!
+//! A fresh clone only tracks its default branch, so pushing any other branch
+//! fails with "no upstream". Every local branch with a same-named branch on
+//! one of the config's remotes should track it there, on the origin forge's
+//! remote when origin has it. Auditing (as of the last fetch):
+//! 1. Flags branches with no upstream, an upstream that's gone, one under
+//!    another name or one on a later forge than it could be
+//! 2. Leaves branches nothing on the forges matches, and upstreams on remotes
+//!    the config doesn't manage (a fork's `upstream`), alone
+//! 3. With `fix`, points each flagged branch at its expected upstream
+
+use std::path::Path;
+use thiserror::Error;
+
+use crate::config::HyperforgeConfig;
+use crate::git::{Git, GitError, LocalBranch};
+
+/// Errors that can occur while auditing upstreams
+#[derive(Debug, Error)]
+pub enum UpstreamsError {
+    #[error("Not a hyperforge repository. Run 'hyperforge init' first.")]
+    NotInitialized,
+
+    #[error("Not a git repository: {path}")]
+    NotAGitRepo { path: String },
+
+    #[error("Git error: {0}")]
+    GitError(#[from] GitError),
+
+    #[error("Config error: {0}")]
+    ConfigError(#[from] crate::config::ConfigError),
+}
+
+pub type UpstreamsResult<T> = Result<T, UpstreamsError>;
+
+/// What's wrong with a branch's upstream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpstreamProblem {
+    /// No upstream configured
+    Missing,
+    /// The upstream's remote-tracking branch no longer exists
+    Gone,
+    /// Tracks a differently named branch
+    WrongBranch,
+    /// Tracks the branch on a later forge than the one expected
+    WrongRemote,
+}
+
+impl UpstreamProblem {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::Gone => "gone",
+            Self::WrongBranch => "wrong_branch",
+            Self::WrongRemote => "wrong_remote",
+        }
+    }
+}
+
+/// A branch whose upstream isn't what it should be
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamFinding {
+    pub branch: String,
+    pub problem: UpstreamProblem,
+    /// Configured upstream as `<remote>/<branch>`
+    pub current: Option<String>,
+    pub expected_remote: String,
+}
+
+impl UpstreamFinding {
+    /// Expected upstream as `<remote>/<branch>`
+    pub fn expected(&self) -> String {
+        format!("{}/{}", self.expected_remote, self.branch)
+    }
+}
+
+/// Overall result of auditing one repo
+#[derive(Debug)]
+pub struct UpstreamsReport {
+    pub findings: Vec<UpstreamFinding>,
+    /// Branches already tracking the expected upstream
+    pub in_sync: usize,
+    /// Whether `findings` were fixed
+    pub applied: bool,
+}
+
+/// Check `branches` against the remote-tracking branches. `remotes` are the
+/// config's remotes in forge order, origin first.
+pub fn plan(branches: &[LocalBranch], remote_branches: &[String], remotes: &[String]) -> (Vec<UpstreamFinding>, usize) {
+    let exists = |remote: &str, branch: &str| remote_branches.iter().any(|r| *r == format!("{remote}/{branch}"));
+
+    let mut findings = Vec::new();
+    let mut in_sync = 0usize;
+    for branch in branches {
+        let Some(expected) = remotes.iter().find(|r| exists(r, &branch.name)) else {
+            continue;
+        };
+
+        let current = branch
+            .upstream_remote
+            .as_deref()
+            .zip(branch.upstream_branch.as_deref());
+        let problem = match current {
+            None => UpstreamProblem::Missing,
+            Some((remote, _)) if !remotes.iter().any(|r| r == remote) => continue,
+            Some((remote, upstream)) if !exists(remote, upstream) => UpstreamProblem::Gone,
+            Some((_, upstream)) if upstream != branch.name => UpstreamProblem::WrongBranch,
+            Some((remote, _)) if remote != expected => UpstreamProblem::WrongRemote,
+            Some(_) => {
+                in_sync += 1;
+                continue;
+            }
+        };
+
+        findings.push(UpstreamFinding {
+            branch: branch.name.clone(),
+            problem,
+            current: current.map(|(remote, upstream)| format!("{remote}/{upstream}")),
+            expected_remote: expected.clone(),
+        });
+    }
+
+    (findings, in_sync)
+}
+
+/// Audit the repo's branch upstreams; with `fix`, repair the ones flagged
+pub fn audit(path: &Path, fix: bool) -> UpstreamsResult<UpstreamsReport> {
+    if !HyperforgeConfig::exists(path) {
+        return Err(UpstreamsError::NotInitialized);
+    }
+
+    if !Git::is_repo(path) {
+        return Err(UpstreamsError::NotAGitRepo {
+            path: path.display().to_string(),
+        });
+    }
+
+    let config = HyperforgeConfig::load(path)?;
+    let mut remotes: Vec<String> = Vec::new();
+    for forge in &config.forges {
+        let name = config.remote_for_forge(forge);
+        if !remotes.contains(&name) {
+            remotes.push(name);
+        }
+    }
+
+    let branches = Git::local_branches(path)?;
+    let remote_branches = Git::remote_branches(path)?;
+    let (findings, in_sync) = plan(&branches, &remote_branches, &remotes);
+
+    if fix {
+        for finding in &findings {
+            Git::set_upstream(path, &finding.branch, &finding.expected_remote, &finding.branch)?;
+        }
+    }
+
+    Ok(UpstreamsReport {
+        applied: fix && !findings.is_empty(),
+        findings,
+        in_sync,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::init::{init, InitOptions};
+    use std::process::Command;
+
+    fn branch(name: &str, upstream: Option<(&str, &str)>) -> LocalBranch {
+        LocalBranch {
+            name: name.to_string(),
+            upstream_remote: upstream.map(|(r, _)| r.to_string()),
+            upstream_branch: upstream.map(|(_, b)| b.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_plan_flags_each_problem() {
+        let remotes = vec!["origin".to_string(), "codeberg".to_string()];
+        let remote_branches: Vec<String> = ["origin/main", "origin/feature", "codeberg/feature", "codeberg/mirror-only", "origin/fork", "upstream/main"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let branches = vec![
+            branch("main", Some(("origin", "main"))),
+            branch("feature", Some(("codeberg", "feature"))),
+            branch("mirror-only", None),
+            branch("renamed", Some(("origin", "old-name"))),
+            branch("fork", Some(("upstream", "main"))),
+            branch("local-only", None),
+        ];
+
+        let (findings, in_sync) = plan(&branches, &remote_branches, &remotes);
+        assert_eq!(in_sync, 1);
+        let found: Vec<_> = findings.iter().map(|f| (f.branch.as_str(), f.problem, f.expected())).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("feature", UpstreamProblem::WrongRemote, "origin/feature".to_string()),
+                ("mirror-only", UpstreamProblem::Missing, "codeberg/mirror-only".to_string()),
+            ]
+        );
+
+        // A gone upstream, or one under another name, is flagged once the
+        // branch is on a forge
+        let remote_branches = vec!["origin/renamed".to_string(), "origin/main".to_string()];
+        let branches = vec![branch("renamed", Some(("origin", "old-name"))), branch("main", Some(("origin", "gone")))];
+        let (findings, _) = plan(&branches, &remote_branches, &remotes);
+        let problems: Vec<_> = findings.iter().map(|f| f.problem).collect();
+        assert_eq!(problems, vec![UpstreamProblem::Gone, UpstreamProblem::Gone]);
+
+        let remote_branches = vec!["origin/renamed".to_string(), "origin/old-name".to_string()];
+        let (findings, _) = plan(&branches[..1], &remote_branches, &remotes);
+        assert_eq!(findings[0].problem, UpstreamProblem::WrongBranch);
+        assert_eq!(findings[0].current.as_deref(), Some("origin/old-name"));
+    }
+
+    #[test]
+    fn test_audit_fixes_fresh_clone_branches() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path();
+        let git = |args: &[&str]| {
+            let out = Command::new("git").args(args).current_dir(repo).output().unwrap();
+            assert!(out.status.success(), "git {args:?}: {}", String::from_utf8_lossy(&out.stderr));
+        };
+        init(repo, InitOptions::new(vec!["github".to_string(), "codeberg".to_string()]).with_org("alice")).unwrap();
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "a"]);
+        git(&["branch", "-M", "main"]);
+        git(&["branch", "feature"]);
+        for r in ["refs/remotes/origin/main", "refs/remotes/origin/feature", "refs/remotes/codeberg/main"] {
+            git(&["update-ref", r, "HEAD"]);
+        }
+        git(&["branch", "--set-upstream-to=codeberg/main", "main"]);
+
+        let dry = audit(repo, false).unwrap();
+        assert!(!dry.applied);
+        let problems: Vec<_> = dry.findings.iter().map(|f| (f.branch.as_str(), f.problem)).collect();
+        assert_eq!(problems, vec![("feature", UpstreamProblem::Missing), ("main", UpstreamProblem::WrongRemote)]);
+
+        let fixed = audit(repo, true).unwrap();
+        assert!(fixed.applied);
+        let branches = Git::local_branches(repo).unwrap();
+        assert!(branches.iter().all(|b| b.upstream_remote.as_deref() == Some("origin")
+            && b.upstream_branch.as_deref() == Some(b.name.as_str())));
+
+        let again = audit(repo, true).unwrap();
+        assert!(again.findings.is_empty());
+        assert_eq!(again.in_sync, 2);
+    }
+}
@@ -4,11 +4,12 @@
 //! with hyperforge configuration, building a `WorkspaceContext` that
 //! aggregates orgs and forges across all discovered repos.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
-use crate::build_system::{self, BuildSystemKind, DepRef};
+use crate::build_system::{BuildSystemKind, DepRef};
+use crate::commands::index::{fingerprint, IndexEntry, WorkspaceIndex};
 use crate::config::HyperforgeConfig;
 use crate::git::Git;
 use crate::types::Repo;
@@ -59,6 +60,11 @@ impl DiscoveredRepo {
         self.config.as_ref().and_then(|c| c.org.as_deref())
     }
 
+    /// Every org the repo's forge entries resolve to, account orgs included
+    pub fn orgs(&self) -> Vec<&str> {
+        self.config.as_ref().map(HyperforgeConfig::orgs).unwrap_or_default()
+    }
+
     /// Get the forges from config, if available
     pub fn forges(&self) -> Vec<&str> {
         self.config
@@ -91,23 +97,24 @@ pub struct WorkspaceContext {
 }
 
 impl WorkspaceContext {
-    /// Get repos filtered by org
+    /// Get repos filtered by org (default or account org)
     pub fn repos_for_org(&self, org: &str) -> Vec<&DiscoveredRepo> {
         self.repos
             .iter()
-            .filter(|r| r.org() == Some(org))
+            .filter(|r| r.orgs().contains(&org))
             .collect()
     }
 
-    /// Get repos filtered by org and forge
+    /// Get repos with a forge entry for `forge` under `org`
     pub fn repos_for_org_and_forge(&self, org: &str, forge: &str) -> Vec<&DiscoveredRepo> {
         self.repos
             .iter()
             .filter(|r| {
-                r.org() == Some(org)
-                    && r.config
-                        .as_ref()
-                        .is_some_and(|c| c.forges.iter().any(|f| f == forge))
+                r.config.as_ref().is_some_and(|c| {
+                    c.forges.iter().any(|f| {
+                        HyperforgeConfig::split_account(f).0 == forge && c.org_for_forge(f) == Some(org)
+                    })
+                })
             })
             .collect()
     }
@@ -139,15 +146,15 @@ impl WorkspaceContext {
             .collect()
     }
 
-    /// Get all unique (org, forge) pairs
+    /// Get all unique (org, forge) pairs. Account entries (`github:acme`)
+    /// pair their account org with the plain forge name.
     pub fn org_forge_pairs(&self) -> Vec<(String, String)> {
         let mut pairs = BTreeSet::new();
-        for repo in &self.repos {
-            if let Some(config) = &repo.config {
-                if let Some(org) = &config.org {
-                    for forge in &config.forges {
-                        pairs.insert((org.clone(), forge.clone()));
-                    }
+        for config in self.repos.iter().filter_map(|r| r.config.as_ref()) {
+            for forge in &config.forges {
+                if let Some(org) = config.org_for_forge(forge) {
+                    let (base, _) = HyperforgeConfig::split_account(forge);
+                    pairs.insert((org.to_string(), base.to_string()));
                 }
             }
         }
@@ -157,25 +164,15 @@ impl WorkspaceContext {
 
 /// Build a `DepGraph` from discovered repos.
 pub fn build_dep_graph(repos: &[DiscoveredRepo]) -> crate::build_system::dep_graph::DepGraph {
-    let mut nodes = Vec::new();
-    let mut all_deps = Vec::new();
-    for (idx, repo) in repos.iter().enumerate() {
-        let name = repo.effective_name();
-        nodes.push(crate::build_system::dep_graph::DepNode {
-            name,
-            version: repo.package_version.clone(),
-            build_system: format!("{}", repo.build_system),
-            path: repo.dir_name.clone(),
-        });
-        if !repo.dependencies.is_empty() {
-            all_deps.push((idx, repo.dependencies.clone()));
-        }
-    }
-    crate::build_system::dep_graph::DepGraph::build(nodes, &all_deps)
+    build_graph(repos, true)
 }
 
 /// Build a `DepGraph` excluding dev dependencies (for publish ordering).
 pub fn build_publish_dep_graph(repos: &[DiscoveredRepo]) -> crate::build_system::dep_graph::DepGraph {
+    build_graph(repos, false)
+}
+
+fn build_graph(repos: &[DiscoveredRepo], include_dev: bool) -> crate::build_system::dep_graph::DepGraph {
     let mut nodes = Vec::new();
     let mut all_deps = Vec::new();
     for (idx, repo) in repos.iter().enumerate() {
@@ -186,25 +183,64 @@ pub fn build_publish_dep_graph(repos: &[DiscoveredRepo]) -> crate::build_system:
             build_system: format!("{}", repo.build_system),
             path: repo.dir_name.clone(),
         });
-        let non_dev: Vec<_> = repo.dependencies.iter().filter(|d| !d.is_dev).cloned().collect();
-        if !non_dev.is_empty() {
-            all_deps.push((idx, non_dev));
+        let mut deps: Vec<DepRef> = repo
+            .dependencies
+            .iter()
+            .filter(|d| include_dev || !d.is_dev)
+            .cloned()
+            .collect();
+        deps.extend(declared_deps(repos, repo));
+        if !deps.is_empty() {
+            all_deps.push((idx, deps));
         }
     }
     crate::build_system::dep_graph::DepGraph::build(nodes, &all_deps)
 }
 
+/// Cross-build-system deps declared via `depends_on` in `.hyperforge/config.toml`.
+///
+/// Entries may name a sibling by package name or directory name; both resolve
+/// to the sibling's graph node. Unknown names are dropped, like manifest deps
+/// on packages outside the workspace.
+fn declared_deps(repos: &[DiscoveredRepo], repo: &DiscoveredRepo) -> Vec<DepRef> {
+    let Some(config) = &repo.config else {
+        return Vec::new();
+    };
+    config
+        .depends_on
+        .iter()
+        .filter_map(|wanted| {
+            repos
+                .iter()
+                .find(|r| r.effective_name() == *wanted || r.dir_name == *wanted)
+        })
+        .map(|target| DepRef {
+            name: target.effective_name(),
+            version_req: None,
+            is_path_dep: true,
+            path: Some(format!("../{}", target.dir_name)),
+            is_dev: false,
+            is_optional: false,
+        })
+        .collect()
+}
+
 /// Build a `Repo` (suitable for `LocalForge`) from a discovered repo's config.
 ///
 /// First forge → origin, remaining → mirrors.
 /// Returns `None` if config is missing, has no org, or has no valid forges.
 pub fn repo_from_config(discovered: &DiscoveredRepo) -> Option<Repo> {
+    repo_for_org(discovered, discovered.org()?)
+}
+
+/// Like [`repo_from_config`], limited to the forge entries that resolve to
+/// `org`, so each account's `LocalForge` only tracks its own forges.
+pub fn repo_for_org(discovered: &DiscoveredRepo, org: &str) -> Option<Repo> {
     let config = discovered.config.as_ref()?;
-    let _org = config.org.as_ref()?;
 
     // Parse forge strings into Forge enums, keeping only valid ones
     let parsed_forges: Vec<_> = config
-        .forges
+        .forges_for_org(org)
         .iter()
         .filter_map(|f| HyperforgeConfig::parse_forge(f))
         .collect();
@@ -220,11 +256,20 @@ pub fn repo_from_config(discovered: &DiscoveredRepo) -> Option<Repo> {
 
     let mut repo = Repo::new(repo_name, origin)
         .with_visibility(config.visibility.clone())
-        .with_mirrors(mirrors);
+        .with_mirrors(mirrors)
+        .with_archived(config.archived);
+    repo.visibility_on = config
+        .forge_config
+        .iter()
+        .filter_map(|(forge, fc)| Some((forge.clone(), fc.visibility.clone()?)))
+        .collect();
 
     if let Some(ref desc) = config.description {
         repo = repo.with_description(desc);
     }
+    if !config.topics.is_empty() {
+        repo = repo.with_topics(&config.topics);
+    }
 
     Some(repo)
 }
@@ -234,6 +279,24 @@ pub fn repo_from_config(discovered: &DiscoveredRepo) -> Option<Repo> {
 /// Pure filesystem reads — no git commands, no network.
 /// Only scans one level deep (immediate children).
 pub fn discover_workspace(workspace_path: &Path) -> WorkspaceResult<WorkspaceContext> {
+    discover_workspace_with(workspace_path, false, |_| {})
+}
+
+/// [`discover_workspace`] that hands each configured repo to `on_repo` as
+/// soon as its manifests are parsed, so callers can report progress or start
+/// work before the scan finishes.
+///
+/// The directory listing runs on one thread while manifests are parsed on a
+/// small worker pool. `on_repo` sees repos in name order, each as soon as it
+/// and every repo before it are parsed, so streamed output is as
+/// deterministic as the returned context. Manifest data comes from the
+/// workspace index when a repo's fingerprint is unchanged, unless `reindex`
+/// is set; the index is rewritten whenever an entry changed.
+pub fn discover_workspace_with(
+    workspace_path: &Path,
+    reindex: bool,
+    mut on_repo: impl FnMut(&DiscoveredRepo),
+) -> WorkspaceResult<WorkspaceContext> {
     let workspace_path = workspace_path
         .canonicalize()
         .map_err(|_| WorkspaceError::PathNotFound {
@@ -246,17 +309,102 @@ pub fn discover_workspace(workspace_path: &Path) -> WorkspaceResult<WorkspaceCon
         });
     }
 
+    let index = if reindex { WorkspaceIndex::default() } else { WorkspaceIndex::load(&workspace_path) };
+    let workers = std::thread::available_parallelism().map_or(4, |n| n.get().min(8));
+    let (work_tx, work_rx) = std::sync::mpsc::channel::<(usize, PathBuf, String)>();
+    let work_rx = std::sync::Mutex::new(work_rx);
+    let (repo_tx, repo_rx) = std::sync::mpsc::channel::<(usize, DiscoveredRepo, IndexEntry)>();
     let mut repos = Vec::new();
-    let mut unconfigured_repos = Vec::new();
-    let mut skipped_dirs = Vec::new();
+    let mut entries = BTreeMap::new();
+
+    let listing = std::thread::scope(|scope| {
+        let root = &workspace_path;
+        let lister = scope.spawn(move || list_workspace(root, work_tx));
+
+        for _ in 0..workers {
+            let repo_tx = repo_tx.clone();
+            let work_rx = &work_rx;
+            let index = &index;
+            scope.spawn(move || loop {
+                let next = work_rx.lock().map_or(None, |rx| rx.recv().ok());
+                let Some((seq, path, dir_name)) = next else { break };
+                let (repo, entry) = inspect_repo(path, dir_name, index);
+                if repo_tx.send((seq, repo, entry)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(repo_tx);
+
+        // Work is numbered in name order; hold early finishers back until
+        // every repo before them is through
+        let mut pending = BTreeMap::new();
+        for (seq, repo, entry) in repo_rx {
+            pending.insert(seq, (repo, entry));
+            while let Some((repo, entry)) = pending.remove(&repos.len()) {
+                on_repo(&repo);
+                entries.insert(repo.dir_name.clone(), entry);
+                repos.push(repo);
+            }
+        }
+        // A worker that panicked leaves a gap; keep what the others parsed
+        for (repo, entry) in pending.into_values() {
+            on_repo(&repo);
+            entries.insert(repo.dir_name.clone(), entry);
+            repos.push(repo);
+        }
+
+        lister.join().unwrap_or_else(|_| {
+            Err(std::io::Error::other("workspace listing thread panicked"))
+        })
+    });
+    let (unconfigured_repos, skipped_dirs) = listing?;
+
+    // Best effort: a read-only workspace just doesn't get a cache
+    if entries != index.repos {
+        let _ = WorkspaceIndex { repos: entries, ..index }.save(&workspace_path);
+    }
+
     let mut orgs_set = BTreeSet::new();
     let mut forges_set = BTreeSet::new();
+    for config in repos.iter().filter_map(|r| r.config.as_ref()) {
+        orgs_set.extend(config.orgs().into_iter().map(str::to_string));
+        for forge in &config.forges {
+            forges_set.insert(forge.clone());
+        }
+    }
+
+    // Sort repos by name for deterministic output
+    repos.sort_by(|a, b| a.dir_name.cmp(&b.dir_name));
+
+    Ok(WorkspaceContext {
+        root: workspace_path,
+        repos,
+        orgs: orgs_set.into_iter().collect(),
+        forges: forges_set.into_iter().collect(),
+        unconfigured_repos,
+        skipped_dirs,
+    })
+}
 
-    let entries = std::fs::read_dir(&workspace_path)?;
+/// List the workspace directory in name order, sending hyperforge repos to
+/// `work` (numbered from 0) for manifest parsing and returning the
+/// unconfigured and skipped directories. Dropping `work` on return is what
+/// lets the parsing workers finish.
+fn list_workspace(
+    workspace_path: &Path,
+    work: std::sync::mpsc::Sender<(usize, PathBuf, String)>,
+) -> std::io::Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let mut unconfigured_repos = Vec::new();
+    let mut skipped_dirs = Vec::new();
+    let mut seq = 0;
+
+    let mut paths = std::fs::read_dir(workspace_path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    paths.sort();
 
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
+    for path in paths {
 
         // Only look at directories
         if !path.is_dir() {
@@ -283,50 +431,41 @@ pub fn discover_workspace(workspace_path: &Path) -> WorkspaceResult<WorkspaceCon
             continue;
         }
 
-        // Load config for hyperforge repos
-        let config = HyperforgeConfig::load(&path).ok();
-
-        if let Some(ref config) = config {
-            if let Some(ref org) = config.org {
-                orgs_set.insert(org.clone());
-            }
-            for forge in &config.forges {
-                forges_set.insert(forge.clone());
-            }
-        }
-
-        // Detect build system
-        let primary_bs = build_system::detect_build_system(&path);
-        let all_bs = build_system::detect_all_build_systems(&path);
-        let deps = build_system::parse_dependencies(&path, &primary_bs);
-        let pkg_name = build_system::package_name(&path, &primary_bs);
-        let pkg_version = build_system::package_version(&path, &primary_bs);
-
-        repos.push(DiscoveredRepo {
-            path,
-            dir_name,
-            config,
-            is_git_repo,
-            is_hyperforge_repo,
-            build_system: primary_bs,
-            build_systems: all_bs,
-            dependencies: deps,
-            package_name: pkg_name,
-            package_version: pkg_version,
-        });
+        let _ = work.send((seq, path, dir_name));
+        seq += 1;
     }
 
-    // Sort repos by name for deterministic output
-    repos.sort_by(|a, b| a.dir_name.cmp(&b.dir_name));
+    Ok((unconfigured_repos, skipped_dirs))
+}
 
-    Ok(WorkspaceContext {
-        root: workspace_path,
-        repos,
-        orgs: orgs_set.into_iter().collect(),
-        forges: forges_set.into_iter().collect(),
-        unconfigured_repos,
-        skipped_dirs,
-    })
+/// Load config and build manifests for one hyperforge repo directory,
+/// taking manifest data from `index` when the repo is unchanged.
+fn inspect_repo(path: PathBuf, dir_name: String, index: &WorkspaceIndex) -> (DiscoveredRepo, IndexEntry) {
+    let is_git_repo = Git::is_repo(&path);
+
+    // Load config for hyperforge repos
+    let config = HyperforgeConfig::load(&path).ok();
+
+    // Detect build system
+    let fp = fingerprint(&path);
+    let entry = match index.fresh(&dir_name, &fp) {
+        Some(cached) => cached.clone(),
+        None => IndexEntry::parse(&path, fp),
+    };
+
+    let repo = DiscoveredRepo {
+        path,
+        dir_name,
+        config,
+        is_git_repo,
+        is_hyperforge_repo: true,
+        build_system: entry.build_system.clone(),
+        build_systems: entry.build_systems.clone(),
+        dependencies: entry.dependencies.clone(),
+        package_name: entry.package_name.clone(),
+        package_version: entry.package_version.clone(),
+    };
+    (repo, entry)
 }
 
 #[cfg(test)]
@@ -381,6 +520,18 @@ mod tests {
         assert_eq!(ctx.skipped_dirs.len(), 1);
     }
 
+    #[test]
+    fn test_discover_workspace_with_reports_each_configured_repo() {
+        let workspace = setup_workspace();
+        let mut seen = Vec::new();
+        let ctx = discover_workspace_with(workspace.path(), false, |r| seen.push(r.dir_name.clone())).unwrap();
+
+        seen.sort();
+        assert_eq!(seen, vec!["repo-a", "repo-b"]);
+        let names: Vec<_> = ctx.repos.iter().map(|r| r.dir_name.as_str()).collect();
+        assert_eq!(names, vec!["repo-a", "repo-b"]);
+    }
+
     #[test]
     fn test_discover_orgs_and_forges() {
         let workspace = setup_workspace();
@@ -485,6 +636,71 @@ mod tests {
         assert!(repo_from_config(&discovered).is_none());
     }
 
+    #[test]
+    fn test_discover_forge_accounts() {
+        let workspace = setup_workspace();
+        let repo_d = workspace.path().join("repo-d");
+        std::fs::create_dir(&repo_d).unwrap();
+        Git::init(&repo_d).unwrap();
+        HyperforgeConfig::new(vec!["github".to_string(), "github:acme".to_string()])
+            .with_org("alice")
+            .save(&repo_d)
+            .unwrap();
+        let ctx = discover_workspace(workspace.path()).unwrap();
+
+        assert_eq!(ctx.orgs, vec!["acme", "alice", "bob"]);
+        assert!(ctx.org_forge_pairs().contains(&("acme".to_string(), "github".to_string())));
+        let acme: Vec<_> = ctx.repos_for_org_and_forge("acme", "github").iter().map(|r| r.dir_name.clone()).collect();
+        assert_eq!(acme, vec!["repo-d"]);
+        assert_eq!(ctx.repos_for_org_and_forge("alice", "github").len(), 2);
+
+        let repo_d = ctx.repos.iter().find(|r| r.dir_name == "repo-d").unwrap();
+        let repo = repo_for_org(repo_d, "acme").unwrap();
+        assert_eq!((repo.origin, repo.mirrors.len()), (crate::types::Forge::GitHub, 0));
+        assert!(repo_for_org(repo_d, "bob").is_none());
+    }
+
+    #[test]
+    fn test_declared_cross_build_system_deps() {
+        let workspace = TempDir::new().unwrap();
+
+        let wasm = workspace.path().join("wasm-core");
+        std::fs::create_dir(&wasm).unwrap();
+        Git::init(&wasm).unwrap();
+        std::fs::write(
+            wasm.join("Cargo.toml"),
+            "[package]\nname = \"wasm-core\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        HyperforgeConfig::new(vec!["github".to_string()])
+            .with_org("alice")
+            .save(&wasm)
+            .unwrap();
+
+        let web = workspace.path().join("web");
+        std::fs::create_dir(&web).unwrap();
+        Git::init(&web).unwrap();
+        std::fs::write(web.join("package.json"), r#"{"name": "web", "version": "1.0.0"}"#).unwrap();
+        let mut config = HyperforgeConfig::new(vec!["github".to_string()]).with_org("alice");
+        config.depends_on = vec!["wasm-core".to_string(), "not-in-workspace".to_string()];
+        config.save(&web).unwrap();
+
+        let ctx = discover_workspace(workspace.path()).unwrap();
+        for graph in [build_dep_graph(&ctx.repos), build_publish_dep_graph(&ctx.repos)] {
+            let tiers = graph.build_tiers().unwrap();
+            let names: Vec<Vec<&str>> = tiers
+                .iter()
+                .map(|t| t.iter().map(|&i| graph.nodes[i].name.as_str()).collect())
+                .collect();
+            assert_eq!(names, vec![vec!["wasm-core"], vec!["web"]]);
+
+            let wasm_idx = graph.node_index("wasm-core").unwrap();
+            let web_idx = graph.node_index("web").unwrap();
+            // Discovery sorts by dir name, so wasm-core precedes web
+            assert_eq!(graph.affected(&[wasm_idx]), vec![wasm_idx, web_idx]);
+        }
+    }
+
     #[test]
     fn test_discover_empty_workspace() {
         let workspace = TempDir::new().unwrap();
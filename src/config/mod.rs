@@ -1,22 +1,28 @@
 //! Configuration management for hyperforge
 //!
 //! This module handles `.hyperforge/config.toml` files which store
-//! per-repository forge configuration, and org-level config at
-//! `~/.config/hyperforge/orgs/{org}.toml`.
+//! per-repository forge configuration, org-level config at
+//! `~/.config/hyperforge/orgs/{org}.toml`, workspace-level config at
+//! `<workspace>/.hyperforge/workspace.toml`, and user-level settings at
+//! `~/.config/hyperforge/config.toml`.
 
 pub mod org;
+pub mod user;
+pub mod workspace;
 
-pub use org::OrgConfig;
+pub use org::{GitIdentity, OrgConfig};
+pub use user::{ConcurrencyConfig, OAuthAppConfig, OutputConfig, TelemetryConfig, UpgradeConfig, UserConfig};
+pub use workspace::{RepoDefaults, WorkspaceConfig};
 
 use crate::types::{Forge, Visibility};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 // Re-export shared config types from types::config
-pub use crate::types::config::{CiConfig, DistConfig, ForgeConfig};
+pub use crate::types::config::{BranchProtectionConfig, CiConfig, DeployKeyConfig, DistConfig, ForgeConfig, RemoteLayout, WebhookConfig};
 
 /// Configuration directory name
 pub const CONFIG_DIR: &str = ".hyperforge";
@@ -71,6 +77,15 @@ pub struct HyperforgeConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// Repository topics (tags) `workspace sync` keeps on every forge
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
+
+    /// Retired: `workspace sync` archives the repo on every forge instead of
+    /// updating it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+
     /// SSH key paths per forge
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub ssh: HashMap<String, String>,
@@ -91,9 +106,85 @@ pub struct HyperforgeConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub large_file_threshold_kb: Option<u64>,
 
+    /// Scan outgoing commits for large blobs and secret-looking strings before
+    /// every push (also enforced by the generated pre-push hook)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub scan_outgoing: bool,
+
+    /// Override the workspace `linear_history` policy for this repo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linear_history: Option<bool>,
+
+    /// Workspace managed files this repo opts out of, by path (`*` for all)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unmanaged_files: Vec<String>,
+
     /// Distribution configuration for binary releases
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dist: Option<DistConfig>,
+
+    /// Container image build/push configuration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<crate::types::config::ImageConfig>,
+
+    /// Sibling repos this one builds after, by package or directory name.
+    ///
+    /// Declares cross-build-system edges the manifests can't express, e.g. a
+    /// Node frontend bundling a Rust wasm crate or a Haskell service linking
+    /// a Rust FFI library.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+
+    /// Webhooks `workspace sync` keeps on the repo's forges, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub webhooks: BTreeMap<String, WebhookConfig>,
+
+    /// Protection rule `workspace sync` keeps on the default branch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_protection: Option<BranchProtectionConfig>,
+
+    /// Deploy keys `repo sync_deploy_keys` keeps on the repo's forges, by title
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ssh_deploy_keys: BTreeMap<String, DeployKeyConfig>,
+
+    /// `multi_push` keeps a single `origin` that pushes to every forge
+    #[serde(default, skip_serializing_if = "RemoteLayout::is_per_forge")]
+    pub remote_layout: RemoteLayout,
+
+    /// Keys filled in from the workspace `[defaults]`, with their values;
+    /// `save` leaves them out of the repo's file while they still match
+    #[serde(skip)]
+    pub(crate) inherited: toml::Table,
+}
+
+/// Where an effective repo setting comes from, highest precedence first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// The repo's own `.hyperforge/config.toml`
+    Repo,
+    /// The workspace's `[defaults]`
+    Workspace,
+    /// Built in
+    Default,
+}
+
+impl ConfigSource {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Repo => "repo",
+            Self::Workspace => "workspace",
+            Self::Default => "default",
+        }
+    }
+}
+
+/// One effective top-level setting and its source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplainedSetting {
+    pub key: String,
+    /// Value as TOML
+    pub value: String,
+    pub source: ConfigSource,
 }
 
 impl Default for HyperforgeConfig {
@@ -109,7 +200,19 @@ impl Default for HyperforgeConfig {
             default_branch: None,
             ci: None,
             large_file_threshold_kb: None,
+            scan_outgoing: false,
+            linear_history: None,
+            unmanaged_files: Vec::new(),
             dist: None,
+            image: None,
+            depends_on: Vec::new(),
+            webhooks: BTreeMap::new(),
+            ssh_deploy_keys: BTreeMap::new(),
+            branch_protection: None,
+            remote_layout: RemoteLayout::PerForge,
+            topics: Vec::new(),
+            archived: false,
+            inherited: toml::Table::new(),
         }
     }
 }
@@ -179,8 +282,26 @@ impl HyperforgeConfig {
         Self::config_path(repo_path).exists()
     }
 
-    /// Load config from .hyperforge/config.toml in the given repo
+    /// Load config from .hyperforge/config.toml in the given repo, filling
+    /// in whatever it doesn't set from its workspace's `[defaults]`
     pub fn load(repo_path: &Path) -> ConfigResult<Self> {
+        let mut table = Self::load_own(repo_path)?;
+
+        let inherited = match WorkspaceConfig::find_root(repo_path) {
+            Some(root) => WorkspaceConfig::load(&root)
+                .map_err(|message| ConfigError::Invalid { message })?
+                .defaults
+                .inherit_into(&mut table)?,
+            None => toml::Table::new(),
+        };
+
+        let mut config: Self = table.try_into()?;
+        config.inherited = inherited;
+        Ok(config)
+    }
+
+    /// The repo's own config.toml as written, without inherited defaults
+    fn load_own(repo_path: &Path) -> ConfigResult<toml::Table> {
         let config_path = Self::config_path(repo_path);
 
         if !config_path.exists() {
@@ -188,9 +309,7 @@ impl HyperforgeConfig {
         }
 
         let content = fs::read_to_string(&config_path)?;
-        let config: Self = toml::from_str(&content)?;
-
-        Ok(config)
+        Ok(toml::from_str(&content)?)
     }
 
     /// Save config to .hyperforge/config.toml in the given repo
@@ -201,14 +320,56 @@ impl HyperforgeConfig {
         // Create .hyperforge directory if it doesn't exist
         fs::create_dir_all(&config_dir)?;
 
-        let content = toml::to_string_pretty(self)?;
+        let content = if self.inherited.is_empty() {
+            toml::to_string_pretty(self)?
+        } else {
+            // Inherited values stay in workspace.toml until the repo changes them
+            let mut table = toml::Table::try_from(self)?;
+            for (key, value) in &self.inherited {
+                if table.get(key) == Some(value) {
+                    table.remove(key);
+                }
+            }
+            toml::to_string_pretty(&table)?
+        };
         fs::write(&config_path, content)?;
 
         Ok(())
     }
 
-    /// Get the effective org for a forge (checks forge-specific override first)
-    pub fn org_for_forge(&self, forge: &str) -> Option<&str> {
+    /// Every effective top-level setting of the repo at `repo_path`, and
+    /// whether the repo, its workspace or the built-in default decides it
+    pub fn explain(repo_path: &Path) -> ConfigResult<Vec<ExplainedSetting>> {
+        let own = Self::load_own(repo_path)?;
+        let config = Self::load(repo_path)?;
+
+        Ok(toml::Table::try_from(&config)?
+            .into_iter()
+            .map(|(key, value)| {
+                let source = if own.contains_key(&key) {
+                    ConfigSource::Repo
+                } else if config.inherited.contains_key(&key) {
+                    ConfigSource::Workspace
+                } else {
+                    ConfigSource::Default
+                };
+                ExplainedSetting { key, value: value.to_string(), source }
+            })
+            .collect())
+    }
+
+    /// Split a `forges` entry into its forge and account (`"github:acme"` is
+    /// the `acme` account on GitHub). Entries without an account return `None`.
+    pub fn split_account(entry: &str) -> (&str, Option<&str>) {
+        match entry.split_once(':') {
+            Some((forge, account)) if !account.is_empty() && !account.contains(['/', ':']) => (forge, Some(account)),
+            _ => (entry, None),
+        }
+    }
+
+    /// Get the effective org for a forge entry: the forge-specific override,
+    /// then the entry's account, then the default org
+    pub fn org_for_forge<'a>(&'a self, forge: &'a str) -> Option<&'a str> {
         // Check forge-specific override first
         if let Some(forge_config) = self.forge_config.get(forge) {
             if let Some(ref org) = forge_config.org {
@@ -216,12 +377,63 @@ impl HyperforgeConfig {
             }
         }
 
+        if let (_, Some(account)) = Self::split_account(forge) {
+            return Some(account);
+        }
+
         // Fall back to default org
         self.org.as_deref()
     }
 
-    /// Get the remote name for a forge
+    /// Orgs this repo lives under, one per distinct forge account (sorted)
+    pub fn orgs(&self) -> Vec<&str> {
+        let mut orgs: Vec<&str> = self.forges.iter().filter_map(|f| self.org_for_forge(f)).collect();
+        orgs.sort_unstable();
+        orgs.dedup();
+        orgs
+    }
+
+    /// Forge entries that resolve to `org`
+    pub fn forges_for_org(&self, org: &str) -> Vec<String> {
+        self.forges.iter().filter(|f| self.org_for_forge(f) == Some(org)).cloned().collect()
+    }
+
+    /// The `forges` entry for `forge` (a plain forge name) under `org`
+    pub fn entry_for(&self, forge: &str, org: &str) -> Option<&str> {
+        self.forges
+            .iter()
+            .find(|f| Self::split_account(f).0 == forge && self.org_for_forge(f) == Some(org))
+            .map(String::as_str)
+    }
+
+    /// Self-hosted hostname configured for a forge (`[forge.gitlab] host`).
+    /// Account entries fall back to their forge's host.
+    pub fn forge_host(&self, forge: &str) -> Option<&str> {
+        self.forge_config
+            .get(forge)
+            .or_else(|| self.forge_config.get(Self::split_account(forge).0))
+            .and_then(|fc| fc.host.as_deref())
+    }
+
+    /// Visibility for a forge entry: its `[forge.<name>] visibility`
+    /// override, else the repo's
+    pub fn visibility_for(&self, forge: &str) -> Visibility {
+        self.forge_config
+            .get(forge)
+            .or_else(|| self.forge_config.get(Self::split_account(forge).0))
+            .and_then(|fc| fc.visibility.clone())
+            .unwrap_or_else(|| self.visibility.clone())
+    }
+
+    /// Get the remote name for a forge. Under the multi-push layout every
+    /// forge is reached through the first forge's remote.
     pub fn remote_for_forge(&self, forge: &str) -> String {
+        if self.remote_layout == RemoteLayout::MultiPush {
+            if let Some(first) = self.forges.first().filter(|f| f.as_str() != forge) {
+                return self.remote_for_forge(first);
+            }
+        }
+
         // Check forge-specific override
         if let Some(forge_config) = self.forge_config.get(forge) {
             if let Some(ref remote) = forge_config.remote {
@@ -229,11 +441,15 @@ impl HyperforgeConfig {
             }
         }
 
-        // Default: first forge is "origin", others use forge name
+        // Default: first forge is "origin", others use forge name, with the
+        // account appended so two accounts on one forge don't collide
         if self.forges.first().map(std::string::String::as_str) == Some(forge) {
             "origin".to_string()
         } else {
-            forge.to_string()
+            match Self::split_account(forge) {
+                (base, Some(account)) => format!("{base}-{account}"),
+                (base, None) => base.to_string(),
+            }
         }
     }
 
@@ -255,14 +471,9 @@ impl HyperforgeConfig {
             .unwrap_or_else(|| "unknown".to_string())
     }
 
-    /// Parse forge string to Forge enum
+    /// Parse forge string to Forge enum, ignoring any account suffix
     pub fn parse_forge(forge: &str) -> Option<Forge> {
-        match forge.to_lowercase().as_str() {
-            "github" => Some(Forge::GitHub),
-            "codeberg" => Some(Forge::Codeberg),
-            "gitlab" => Some(Forge::GitLab),
-            _ => None,
-        }
+        Self::split_account(forge).0.parse().ok()
     }
 
     /// Validate the config
@@ -278,12 +489,23 @@ impl HyperforgeConfig {
             if Self::parse_forge(forge).is_none() {
                 return Err(ConfigError::Invalid {
                     message: format!(
-                        "Unknown forge: {forge}. Valid forges: github, codeberg, gitlab"
+                        "Unknown forge: {forge}. Valid forges: github, codeberg, gitlab, or a Gitea/Forgejo hostname, optionally followed by :account"
                     ),
                 });
             }
         }
 
+        // Two entries for the same account would push to one repo twice
+        let mut accounts = std::collections::HashSet::new();
+        for forge in &self.forges {
+            let key = (Self::parse_forge(forge), self.org_for_forge(forge));
+            if !accounts.insert(key) {
+                return Err(ConfigError::Invalid {
+                    message: format!("Forge {forge} resolves to the same org as another entry in forges"),
+                });
+            }
+        }
+
         Ok(())
     }
 }
@@ -364,6 +586,9 @@ mod tests {
             ForgeConfig {
                 org: Some("codeberg-org".to_string()),
                 remote: None,
+                host: None,
+                fetch: None,
+                visibility: None,
             },
         );
 
@@ -382,6 +607,45 @@ mod tests {
         assert_eq!(config.remote_for_forge("codeberg"), "codeberg");
     }
 
+    #[test]
+    fn test_forge_accounts() {
+        let mut config = HyperforgeConfig::new(vec![
+            "github".to_string(),
+            "github:acme".to_string(),
+            "gitlab:acme".to_string(),
+        ])
+        .with_org("alice");
+        config.forge_config.insert(
+            "gitlab".to_string(),
+            ForgeConfig {
+                org: None,
+                remote: None,
+                host: Some("gitlab.acme.dev".to_string()),
+                fetch: None,
+                visibility: None,
+            },
+        );
+
+        assert_eq!(HyperforgeConfig::split_account("github:acme"), ("github", Some("acme")));
+        assert_eq!(HyperforgeConfig::split_account("github:"), ("github:", None));
+        assert_eq!(HyperforgeConfig::parse_forge("github:acme"), Some(Forge::GitHub));
+
+        assert_eq!(config.org_for_forge("github"), Some("alice"));
+        assert_eq!(config.org_for_forge("github:acme"), Some("acme"));
+        assert_eq!(config.orgs(), vec!["acme", "alice"]);
+        assert_eq!(config.forges_for_org("acme"), vec!["github:acme", "gitlab:acme"]);
+        assert_eq!(config.entry_for("github", "acme"), Some("github:acme"));
+        assert_eq!(config.entry_for("github", "alice"), Some("github"));
+        assert_eq!(config.remote_for_forge("github"), "origin");
+        assert_eq!(config.remote_for_forge("github:acme"), "github-acme");
+        assert_eq!(config.forge_host("gitlab:acme"), Some("gitlab.acme.dev"));
+        assert!(config.validate().is_ok());
+
+        // A second entry for the default org's account is a duplicate
+        config.forges.push("github:alice".to_string());
+        assert!(matches!(config.validate(), Err(ConfigError::Invalid { .. })));
+    }
+
     #[test]
     fn test_get_repo_name_explicit() {
         let config = HyperforgeConfig::default().with_repo_name("explicit-name");
@@ -417,6 +681,25 @@ mod tests {
         assert!(matches!(result, Err(ConfigError::Invalid { .. })));
     }
 
+    #[test]
+    fn test_parse_self_hosted_forge() {
+        let forge = HyperforgeConfig::parse_forge("Git.Acme.dev").unwrap();
+        assert_eq!(forge, Forge::Gitea { host: "git.acme.dev".to_string() });
+        assert_eq!(forge.as_str(), "git.acme.dev");
+        assert_eq!(HyperforgeConfig::parse_forge("GitHub"), Some(Forge::GitHub));
+        assert_eq!(HyperforgeConfig::parse_forge("https://git.acme.dev"), None);
+
+        // Serializes as the hostname, so it works as a map key in repos.yaml
+        let yaml = serde_yaml::to_string(&vec![forge.clone(), Forge::Codeberg]).unwrap();
+        assert_eq!(yaml, "- git.acme.dev\n- codeberg\n");
+        let back: Vec<Forge> = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(back, vec![forge, Forge::Codeberg]);
+
+        HyperforgeConfig::new(vec!["github".to_string(), "git.acme.dev".to_string()])
+            .validate()
+            .unwrap();
+    }
+
     #[test]
     fn test_validate_valid() {
         let config =
@@ -440,6 +723,9 @@ mod tests {
             ForgeConfig {
                 org: Some("different-org".to_string()),
                 remote: Some("cb".to_string()),
+                host: None,
+                fetch: None,
+                visibility: None,
             },
         );
 
@@ -451,4 +737,126 @@ mod tests {
         assert_eq!(parsed.forges, config.forges);
         assert_eq!(parsed.visibility, config.visibility);
     }
+
+    #[test]
+    fn test_self_hosted_gitlab_host() {
+        let config: HyperforgeConfig = toml::from_str(
+            "forges = [\"gitlab\"]\n\n[forge.gitlab]\nhost = \"gitlab.mycompany.com\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.forge_host("gitlab"), Some("gitlab.mycompany.com"));
+        assert_eq!(config.forge_host("github"), None);
+    }
+
+    #[test]
+    fn test_per_forge_visibility_override() {
+        let config: HyperforgeConfig = toml::from_str(
+            "visibility = \"private\"\nforges = [\"github\", \"codeberg:mirrors\"]\n\n[forge.codeberg]\nvisibility = \"public\"\n",
+        )
+        .unwrap();
+        // What the visibility audit expects on each forge
+        assert_eq!(config.visibility_for("github"), Visibility::Private);
+        assert_eq!(config.visibility_for("codeberg"), Visibility::Public);
+        assert_eq!(config.visibility_for("codeberg:mirrors"), Visibility::Public);
+    }
+
+    #[test]
+    fn test_webhooks_section() {
+        use crate::types::WebhookEvent;
+
+        let config: HyperforgeConfig = toml::from_str(
+            "forges = [\"github\", \"gitlab\"]\n\n[webhooks.ci]\nurl = \"https://ci.example.com/hook\"\nevents = [\"push\", \"pull_request\"]\nsecret_env = \"CI_HOOK_SECRET\"\n\n[webhooks.chat]\nurl = \"https://chat.example.com/hook\"\nforges = [\"gitlab\"]\n",
+        )
+        .unwrap();
+        let ci = &config.webhooks["ci"];
+        assert_eq!(ci.events, vec![WebhookEvent::Push, WebhookEvent::PullRequest]);
+        assert_eq!(ci.secret_env.as_deref(), Some("CI_HOOK_SECRET"));
+        let chat = &config.webhooks["chat"];
+        assert_eq!(chat.events, vec![WebhookEvent::Push]);
+        assert!(chat.applies_to("gitlab") && !chat.applies_to("github"));
+
+        let parsed: HyperforgeConfig = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(parsed.webhooks, config.webhooks);
+    }
+
+    #[test]
+    fn test_workspace_defaults_inherited() {
+        let temp = TempDir::new().unwrap();
+        let workspace = WorkspaceConfig {
+            defaults: RepoDefaults {
+                org: Some("acme".to_string()),
+                forges: vec!["github".to_string(), "codeberg".to_string()],
+                visibility: Some(Visibility::Private),
+                ci: None,
+            },
+            ..Default::default()
+        };
+        workspace.save(temp.path()).unwrap();
+
+        let repo = temp.path().join("tool");
+        fs::create_dir_all(HyperforgeConfig::config_dir(&repo)).unwrap();
+        fs::write(HyperforgeConfig::config_path(&repo), "visibility = \"public\"\n").unwrap();
+
+        // The repo's own keys win; the rest come from [defaults]
+        let mut config = HyperforgeConfig::load(&repo).unwrap();
+        assert_eq!(config.org.as_deref(), Some("acme"));
+        assert_eq!(config.forges, vec!["github", "codeberg"]);
+        assert_eq!(config.visibility, Visibility::Public);
+
+        let sources: Vec<_> = HyperforgeConfig::explain(&repo)
+            .unwrap()
+            .into_iter()
+            .map(|s| (s.key, s.source))
+            .collect();
+        assert!(sources.contains(&("org".to_string(), ConfigSource::Workspace)));
+        assert!(sources.contains(&("visibility".to_string(), ConfigSource::Repo)));
+        assert!(sources.contains(&("forges".to_string(), ConfigSource::Workspace)));
+
+        // Saving keeps inherited values out of the repo file until they change
+        config.description = Some("A tool".to_string());
+        config.save(&repo).unwrap();
+        let own = fs::read_to_string(HyperforgeConfig::config_path(&repo)).unwrap();
+        assert!(!own.contains("acme") && !own.contains("forges"));
+
+        config.forges = vec!["gitlab".to_string()];
+        config.save(&repo).unwrap();
+        let reloaded = HyperforgeConfig::load(&repo).unwrap();
+        assert_eq!(reloaded.forges, vec!["gitlab"]);
+        assert_eq!(reloaded.org.as_deref(), Some("acme"));
+        assert_eq!(reloaded.description.as_deref(), Some("A tool"));
+    }
+
+    #[test]
+    fn test_ssh_deploy_keys_section() {
+        let config: HyperforgeConfig = toml::from_str(
+            "forges = [\"github\", \"gitlab\"]\n\n[ssh_deploy_keys.ci]\nkey = \"keys/ci.pub\"\n\n[ssh_deploy_keys.release]\nkey = \"~/.ssh/release.pub\"\nread_only = false\nforges = [\"gitlab\"]\n",
+        )
+        .unwrap();
+        let ci = &config.ssh_deploy_keys["ci"];
+        assert!(ci.read_only && !ci.remove);
+        assert_eq!(ci.key_path(Path::new("/repo")), Path::new("/repo/keys/ci.pub"));
+        let release = &config.ssh_deploy_keys["release"];
+        assert!(!release.read_only);
+        assert!(release.applies_to("gitlab") && !release.applies_to("github"));
+
+        let parsed: HyperforgeConfig = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(parsed.ssh_deploy_keys, config.ssh_deploy_keys);
+    }
+
+    #[test]
+    fn test_branch_protection_section() {
+        let config: HyperforgeConfig = toml::from_str(
+            "forges = [\"github\"]\n\n[branch_protection]\nrequired_reviews = 2\nstatus_checks = [\"ci/build\"]\n",
+        )
+        .unwrap();
+        let protection = config.branch_protection.as_ref().unwrap();
+        assert_eq!(protection.required_reviews, 2);
+        assert_eq!(protection.status_checks, vec!["ci/build"]);
+        assert!(protection.block_force_push);
+        assert!(protection.branch.is_none());
+
+        let parsed: HyperforgeConfig = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(parsed.branch_protection, config.branch_protection);
+        assert!(HyperforgeConfig::default().branch_protection.is_none());
+    }
 }
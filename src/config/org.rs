@@ -1,13 +1,15 @@
 //! Org-level configuration (~/.config/hyperforge/orgs/{org}.toml)
 //!
-//! Stores org-wide defaults like SSH keys per forge. Per-repo config
-//! can override these, but this provides a sensible default so every
-//! `repo init` doesn't need `--ssh-keys`.
+//! Stores org-wide defaults like SSH keys per forge, self-hosted forge
+//! hostnames, and the git identity commits are made with. Per-repo config can override these, but this
+//! provides a sensible default so every `repo init` doesn't need `--ssh-keys`.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use crate::types::config::ForgeConfig;
+
 /// Org-level configuration
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct OrgConfig {
@@ -18,6 +20,56 @@ pub struct OrgConfig {
     /// Workspace path for this org's repos
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub workspace_path: Option<String>,
+
+    /// Git identity written into each repo's local git config on init/clone
+    #[serde(default, skip_serializing_if = "GitIdentity::is_empty")]
+    pub identity: GitIdentity,
+
+    /// Per-forge settings; `[forge.gitlab] host = "gitlab.mycompany.com"`
+    /// points the org at a self-hosted GitLab
+    #[serde(default, rename = "forge", skip_serializing_if = "HashMap::is_empty")]
+    pub forge_config: HashMap<String, ForgeConfig>,
+}
+
+/// Per-org git identity (`[identity]` in the org config)
+///
+/// ```toml
+/// [identity]
+/// name = "Alice Example"
+/// email = "alice@work.example"
+/// signing_key = "ABCD1234"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GitIdentity {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// GPG key ID or SSH key path; setting it also turns on `commit.gpgsign`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
+}
+
+impl GitIdentity {
+    pub const fn is_empty(&self) -> bool {
+        self.name.is_none() && self.email.is_none() && self.signing_key.is_none()
+    }
+
+    /// Local git config entries (key, value) this identity sets
+    pub fn git_config(&self) -> Vec<(&'static str, &str)> {
+        let mut entries = Vec::new();
+        if let Some(name) = &self.name {
+            entries.push(("user.name", name.as_str()));
+        }
+        if let Some(email) = &self.email {
+            entries.push(("user.email", email.as_str()));
+        }
+        if let Some(key) = &self.signing_key {
+            entries.push(("user.signingkey", key.as_str()));
+            entries.push(("commit.gpgsign", "true"));
+        }
+        entries
+    }
 }
 
 impl OrgConfig {
@@ -26,6 +78,20 @@ impl OrgConfig {
         config_dir.join("orgs").join(format!("{org}.toml"))
     }
 
+    /// Names of all orgs with a config file, sorted
+    pub fn list(config_dir: &Path) -> Vec<String> {
+        let mut orgs: Vec<String> = std::fs::read_dir(config_dir.join("orgs"))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|e| e == "toml"))
+            .filter_map(|path| path.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+            .collect();
+        orgs.sort();
+        orgs
+    }
+
     /// Load org config from disk. Returns default if file doesn't exist.
     pub fn load(config_dir: &Path, org: &str) -> Self {
         let path = Self::config_path(config_dir, org);
@@ -35,6 +101,15 @@ impl OrgConfig {
         }
     }
 
+    /// Load org config from the default config dir (~/.config/hyperforge)
+    pub fn load_default(org: &str) -> Self {
+        let config_dir = dirs::home_dir()
+            .unwrap_or_default()
+            .join(".config")
+            .join("hyperforge");
+        Self::load(&config_dir, org)
+    }
+
     /// Save org config to disk.
     pub fn save(&self, config_dir: &Path, org: &str) -> Result<(), String> {
         let path = Self::config_path(config_dir, org);
@@ -59,6 +134,11 @@ impl OrgConfig {
         self.ssh.get(forge).map(std::string::String::as_str)
     }
 
+    /// Self-hosted hostname configured for a forge
+    pub fn forge_host(&self, forge: &str) -> Option<&str> {
+        self.forge_config.get(forge).and_then(|fc| fc.host.as_deref())
+    }
+
     /// Directory for generated SSH keys: ~/.config/hyperforge/orgs/{org}/keys/
     pub fn keys_dir(config_dir: &Path, org: &str) -> PathBuf {
         config_dir.join("orgs").join(org).join("keys")
@@ -169,6 +249,28 @@ mod tests {
         assert!(config.workspace_path.is_none());
     }
 
+    #[test]
+    fn test_identity_roundtrip_and_git_config() {
+        let tmp = TempDir::new().unwrap();
+        let config = OrgConfig {
+            identity: GitIdentity {
+                name: Some("Alice".to_string()),
+                email: Some("alice@work.example".to_string()),
+                signing_key: None,
+            },
+            ..OrgConfig::default()
+        };
+        config.save(tmp.path(), "work").unwrap();
+
+        let loaded = OrgConfig::load(tmp.path(), "work");
+        assert_eq!(loaded.identity, config.identity);
+        assert_eq!(
+            loaded.identity.git_config(),
+            vec![("user.name", "Alice"), ("user.email", "alice@work.example")]
+        );
+        assert!(OrgConfig::load(tmp.path(), "personal").identity.is_empty());
+    }
+
     #[test]
     fn test_generate_ssh_key() {
         let tmp = TempDir::new().unwrap();
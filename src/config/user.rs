@@ -0,0 +1,271 @@
+//! User-level configuration (~/.config/hyperforge/config.toml)
+//!
+//! Settings that belong to the person running hyperforge rather than to an
+//! org or workspace, such as the locale user-facing messages render in and
+//! where secrets are kept.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::auth_hub::encryption::StorageFormat;
+use crate::auth_hub::namespaces::SecretBackend;
+
+/// User config file name, stored directly under the hyperforge config dir
+pub const USER_CONFIG_FILE: &str = "config.toml";
+
+/// User-level configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserConfig {
+    /// Locale for user-facing messages, e.g. `"de"`; a catalog is read from
+    /// `~/.config/hyperforge/locales/{locale}.toml`. English when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// Secret storage settings
+    #[serde(default, skip_serializing_if = "SecretsConfig::is_empty")]
+    pub secrets: SecretsConfig,
+
+    /// OAuth apps `auth.login` runs the device flow with, by forge
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub oauth: BTreeMap<String, OAuthAppConfig>,
+
+    /// Encryption and retention of the journal and run history
+    #[serde(default, skip_serializing_if = "ArtifactsConfig::is_empty")]
+    pub artifacts: ArtifactsConfig,
+
+    /// Anonymous usage counters; off unless enabled here
+    #[serde(default, skip_serializing_if = "TelemetryConfig::is_empty")]
+    pub telemetry: TelemetryConfig,
+
+    /// Where `upgrade` finds releases and how they are verified
+    #[serde(default, skip_serializing_if = "UpgradeConfig::is_default")]
+    pub upgrade: UpgradeConfig,
+
+    /// Default output format for hub streams
+    #[serde(default, skip_serializing_if = "OutputConfig::is_empty")]
+    pub output: OutputConfig,
+
+    /// How much work runs at once, overall and per forge
+    #[serde(default, skip_serializing_if = "ConcurrencyConfig::is_empty")]
+    pub concurrency: ConcurrencyConfig,
+}
+
+/// `[concurrency]`: limits for [`crate::scheduler`]; unset values keep the
+/// built-in defaults, and `HYPERFORGE_MAX_*` env vars override these
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConcurrencyConfig {
+    /// Repos one workspace method works on at once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch: Option<usize>,
+    /// Concurrent git processes across every running method
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<usize>,
+    /// Concurrent API requests to each forge
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api: Option<usize>,
+    /// Concurrent docker builds and runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub containers: Option<usize>,
+    /// `api` for individual forges, keyed `github`, `codeberg`, `gitlab` or
+    /// a self-hosted hostname, e.g. `codeberg = 2`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub forges: BTreeMap<String, usize>,
+}
+
+impl ConcurrencyConfig {
+    /// True when nothing is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `[output]`: how hub streams are emitted when a call doesn't say
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OutputConfig {
+    /// `text` (every event) or `json` (typed events only, as NDJSON)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+impl OutputConfig {
+    /// True when nothing is configured
+    pub const fn is_empty(&self) -> bool {
+        self.format.is_none()
+    }
+}
+
+/// `[upgrade]`: the release feed for `upgrade` and the startup version check
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UpgradeConfig {
+    /// Look for a newer release when the server starts
+    #[serde(default = "default_true")]
+    pub check_on_startup: bool,
+    /// Forge hosting the releases: `github` or `codeberg`
+    #[serde(default = "default_upgrade_forge")]
+    pub forge: String,
+    /// `<org>/<repo>` publishing the releases
+    #[serde(default = "default_upgrade_repo")]
+    pub repo: String,
+    /// cosign or minisign public key the release archives are signed
+    /// with; `upgrade` refuses to install without it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<PathBuf>,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+fn default_upgrade_forge() -> String {
+    "github".to_string()
+}
+
+fn default_upgrade_repo() -> String {
+    "hypermemetic/hyperforge".to_string()
+}
+
+impl Default for UpgradeConfig {
+    fn default() -> Self {
+        Self {
+            check_on_startup: true,
+            forge: default_upgrade_forge(),
+            repo: default_upgrade_repo(),
+            public_key: None,
+        }
+    }
+}
+
+impl UpgradeConfig {
+    /// True when nothing differs from the defaults
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// `[telemetry]`: opt-in usage counters, see [`crate::services::telemetry`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TelemetryConfig {
+    /// Send a report after each instrumented run
+    #[serde(default)]
+    pub enabled: bool,
+    /// Where reports are sent; nothing is sent without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+}
+
+impl TelemetryConfig {
+    /// True when nothing is configured
+    pub const fn is_empty(&self) -> bool {
+        !self.enabled && self.endpoint.is_none()
+    }
+}
+
+/// `[artifacts]`: how the registry journal and build run history are kept
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArtifactsConfig {
+    /// Encrypt entries with a key held in the OS keychain
+    #[serde(default)]
+    pub encrypt: bool,
+    /// Drop entries older than this many days when writing new ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_days: Option<u32>,
+}
+
+impl ArtifactsConfig {
+    /// True when nothing is configured
+    pub const fn is_empty(&self) -> bool {
+        !self.encrypt && self.retention_days.is_none()
+    }
+}
+
+/// An OAuth app under `[oauth.<forge>]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OAuthAppConfig {
+    /// The app's client ID; device flow needs no secret
+    pub client_id: String,
+    /// Scopes to request, space separated; the forge's default when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Device authorization endpoint, for forges without a built-in one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_url: Option<String>,
+    /// Token endpoint, for forges without a built-in one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_url: Option<String>,
+}
+
+/// Secret storage settings under `[secrets]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SecretsConfig {
+    /// Secret path patterns (`github/*`) and the backend holding them;
+    /// unmatched paths use secrets.yaml
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub namespaces: BTreeMap<String, SecretBackend>,
+
+    /// Whether secrets.yaml is encrypted at rest
+    #[serde(default, skip_serializing_if = "is_plaintext")]
+    pub storage: StorageFormat,
+}
+
+/// Serde `skip_serializing_if` helper; the signature must take `&T`
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_plaintext(format: &StorageFormat) -> bool {
+    *format == StorageFormat::Plaintext
+}
+
+impl SecretsConfig {
+    /// True when nothing is configured
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty() && is_plaintext(&self.storage)
+    }
+}
+
+impl UserConfig {
+    /// Path to the user config file: ~/.config/hyperforge/config.toml
+    pub fn config_path(config_dir: &Path) -> PathBuf {
+        config_dir.join(USER_CONFIG_FILE)
+    }
+
+    /// Load user config from disk. Returns default if the file doesn't exist
+    /// or can't be parsed.
+    pub fn load(config_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::config_path(config_dir))
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Set `[secrets] storage` in the config file, keeping the rest of it
+    /// as written
+    pub fn set_secret_storage(config_dir: &Path, format: StorageFormat) -> anyhow::Result<()> {
+        let path = Self::config_path(config_dir);
+        let content = std::fs::read_to_string(&path).unwrap_or_default();
+        let mut doc: toml_edit::DocumentMut = content.parse()?;
+        if !doc.contains_table("secrets") {
+            doc["secrets"] = toml_edit::table();
+        }
+        doc["secrets"]["storage"] = toml_edit::value(format.as_str());
+        std::fs::create_dir_all(config_dir)?;
+        std::fs::write(&path, doc.to_string())?;
+        Ok(())
+    }
+
+    /// Set `[telemetry] enabled`, and `endpoint` when given, keeping the
+    /// rest of the config file as written
+    pub fn set_telemetry(config_dir: &Path, enabled: bool, endpoint: Option<&str>) -> anyhow::Result<()> {
+        let path = Self::config_path(config_dir);
+        let content = std::fs::read_to_string(&path).unwrap_or_default();
+        let mut doc: toml_edit::DocumentMut = content.parse()?;
+        if !doc.contains_table("telemetry") {
+            doc["telemetry"] = toml_edit::table();
+        }
+        doc["telemetry"]["enabled"] = toml_edit::value(enabled);
+        if let Some(endpoint) = endpoint {
+            doc["telemetry"]["endpoint"] = toml_edit::value(endpoint);
+        }
+        std::fs::create_dir_all(config_dir)?;
+        std::fs::write(&path, doc.to_string())?;
+        Ok(())
+    }
+}
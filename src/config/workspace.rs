@@ -0,0 +1,476 @@
+//! Workspace-level configuration (`<workspace>/.hyperforge/workspace.toml`)
+//!
+//! Holds settings shared by every repo under a workspace root, such as the
+//! toolchain versions each repo should pin, who owns which paths and the
+//! `[defaults]` repo configs inherit. Discovery skips hidden
+//! directories, so the root `.hyperforge/` never shows up as a repo.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::types::config::CiConfig;
+use crate::types::Visibility;
+
+/// Workspace config file name, stored under the root `.hyperforge/` directory
+pub const WORKSPACE_CONFIG_FILE: &str = "workspace.toml";
+
+/// Workspace-level configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WorkspaceConfig {
+    /// Toolchain versions every repo should pin
+    #[serde(default, skip_serializing_if = "ToolchainPins::is_empty")]
+    pub toolchains: ToolchainPins,
+
+    /// Repository policies checked across the workspace
+    #[serde(default, skip_serializing_if = "Policy::is_default")]
+    pub policy: Policy,
+
+    /// Code owners: team or user (as written in CODEOWNERS) → path globs
+    ///
+    /// ```toml
+    /// [owners]
+    /// "@acme/platform" = ["*", "/.github/"]
+    /// "@acme/docs" = ["/docs/", "*.md"]
+    /// ```
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub owners: BTreeMap<String, Vec<String>>,
+
+    /// Where shared issue/PR templates and community files come from
+    #[serde(default, skip_serializing_if = "Templates::is_empty")]
+    pub templates: Templates,
+
+    /// Files enforced across repos by `build files_sync`
+    #[serde(default, skip_serializing_if = "ManagedFiles::is_empty")]
+    pub managed: ManagedFiles,
+
+    /// Dependency-update bot configuration written into every repo
+    #[serde(default, skip_serializing_if = "UpdateBots::is_empty")]
+    pub updates: UpdateBots,
+
+    /// Repos held at a fixed tag or commit instead of tracking a branch,
+    /// e.g. vendored dependencies nobody develops here
+    ///
+    /// ```toml
+    /// [pins]
+    /// libfoo = "v1.4.2"
+    /// vendored-bar = "3f2a9c1"
+    /// ```
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pins: BTreeMap<String, String>,
+
+    /// Named subsets of the org's repos (repo globs) for sparse workspaces;
+    /// `workspace clone --profile` and `workspace thaw --profile` only
+    /// materialize the matching repos
+    ///
+    /// ```toml
+    /// [profiles]
+    /// frontend = ["web-*", "design-system"]
+    /// core = ["api", "core-*"]
+    /// ```
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Vec<String>>,
+
+    /// Profile this workspace was materialized with (set by
+    /// `workspace clone --profile`). Repos outside it are expected to be
+    /// missing from disk, so `sync --reflect` never retires them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+
+    /// Settings every repo config under the workspace inherits unless it
+    /// sets them itself
+    #[serde(default, skip_serializing_if = "RepoDefaults::is_empty")]
+    pub defaults: RepoDefaults,
+}
+
+/// Repo config defaults, so a repo's own `.hyperforge/config.toml` only
+/// needs what sets it apart.
+///
+/// Precedence, per top-level key: the repo's `config.toml`, then these,
+/// then the built-in default. Keys are inherited whole, so a repo's `[ci]`
+/// replaces `[defaults.ci]` rather than merging with it.
+///
+/// ```toml
+/// [defaults]
+/// org = "acme"
+/// forges = ["github", "codeberg"]
+/// visibility = "private"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RepoDefaults {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forges: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci: Option<CiConfig>,
+}
+
+impl RepoDefaults {
+    /// True when nothing is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fill in the keys a repo's raw `config.toml` doesn't set. Returns the
+    /// keys filled in, with their values.
+    pub fn inherit_into(&self, repo: &mut toml::Table) -> Result<toml::Table, toml::ser::Error> {
+        let mut inherited = toml::Table::new();
+        for (key, value) in toml::Table::try_from(self)? {
+            if !repo.contains_key(&key) {
+                repo.insert(key.clone(), value.clone());
+                inherited.insert(key, value);
+            }
+        }
+        Ok(inherited)
+    }
+}
+
+/// Which dependency-update bot a workspace uses
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateBot {
+    Renovate,
+    Dependabot,
+}
+
+impl UpdateBot {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Renovate => "renovate",
+            Self::Dependabot => "dependabot",
+        }
+    }
+}
+
+/// Dependency-update bot settings.
+///
+/// ```toml
+/// [updates]
+/// bot = "renovate"
+/// schedule = "weekly"
+/// automerge = true
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UpdateBots {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot: Option<UpdateBot>,
+
+    /// `daily`, `weekly` or `monthly` (default: weekly)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
+
+    /// Let the bot merge passing minor/patch updates itself (Renovate only)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub automerge: bool,
+}
+
+impl UpdateBots {
+    /// True when nothing is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Declarative managed files, written into every targeted repo.
+///
+/// ```toml
+/// [managed]
+/// message = "chore: sync managed files"
+///
+/// [[managed.files]]
+/// path = ".editorconfig"
+/// source = "dotfiles/.editorconfig"
+///
+/// [[managed.files]]
+/// path = "LICENSE-HEADER.txt"
+/// content = "Copyright {{year}} {{org}}. Part of {{repo_name}}.\n"
+/// template = true
+/// repos = ["api-*"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ManagedFiles {
+    /// Commit message shared by every repo's sync commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// Commit onto this branch instead of the checked-out one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<ManagedFile>,
+}
+
+impl ManagedFiles {
+    /// True when nothing is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A single file enforced across repos. Exactly one of `source` and
+/// `content` must be set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ManagedFile {
+    /// Destination path, relative to each repo root
+    pub path: String,
+
+    /// File to copy, relative to the workspace root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Inline file content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+
+    /// Render `{{repo_name}}`, `{{dir_name}}`, `{{org}}` and `{{year}}`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub template: bool,
+
+    /// Glob patterns — repo must match at least one (empty: every repo)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub repos: Vec<String>,
+
+    /// Glob patterns — matching repos are skipped
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+/// Shared templates copied from a "dotfiles" repo into every repo.
+///
+/// ```toml
+/// [templates]
+/// source = "dotfiles"
+/// files = [".github/ISSUE_TEMPLATE", "CONTRIBUTING.md"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Templates {
+    /// Directory name of the source repo within the workspace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Files or directories to copy, relative to the repo root. Empty means
+    /// the standard issue/PR templates plus CONTRIBUTING and SECURITY.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+
+    /// Commit onto this branch instead of the checked-out one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+}
+
+impl Templates {
+    /// True when nothing is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Workspace-wide repository policies. Repos may override individual
+/// settings in their own `.hyperforge/config.toml`.
+///
+/// ```toml
+/// [policy]
+/// linear_history = true
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Policy {
+    /// Default branch must have no merge commits and forges must only allow
+    /// squash merges
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub linear_history: bool,
+}
+
+impl Policy {
+    /// True when no policy is set
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Declared toolchain versions, one per ecosystem.
+///
+/// ```toml
+/// [toolchains]
+/// rust = "1.85.0"
+/// ghc = "9.6.6"
+/// node = "22"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ToolchainPins {
+    /// Rust channel written to `rust-toolchain.toml`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rust: Option<String>,
+
+    /// GHC version written to `.ghcup`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ghc: Option<String>,
+
+    /// Node version written to `.nvmrc`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+}
+
+impl ToolchainPins {
+    /// True when no toolchain is declared
+    pub const fn is_empty(&self) -> bool {
+        self.rust.is_none() && self.ghc.is_none() && self.node.is_none()
+    }
+}
+
+impl WorkspaceConfig {
+    /// Path to the workspace config file: `<root>/.hyperforge/workspace.toml`
+    pub fn config_path(workspace_root: &Path) -> PathBuf {
+        workspace_root
+            .join(super::CONFIG_DIR)
+            .join(WORKSPACE_CONFIG_FILE)
+    }
+
+    /// Workspace root a repo belongs to: the nearest ancestor of
+    /// `repo_path` (not the repo itself) with a `workspace.toml`
+    pub fn find_root(repo_path: &Path) -> Option<PathBuf> {
+        let repo_path = repo_path.canonicalize().ok()?;
+        repo_path
+            .ancestors()
+            .skip(1)
+            .find(|dir| Self::config_path(dir).is_file())
+            .map(Path::to_path_buf)
+    }
+
+    /// Repo globs of the named profile
+    pub fn profile(&self, name: &str) -> Result<&[String], String> {
+        if let Some(globs) = self.profiles.get(name) {
+            return Ok(globs);
+        }
+        if self.profiles.is_empty() {
+            Err(format!("Unknown profile '{name}': no [profiles] in workspace.toml"))
+        } else {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            Err(format!("Unknown profile '{name}'. Defined: {}", known.join(", ")))
+        }
+    }
+
+    /// Globs of the active profile, if the workspace is sparse
+    pub fn active_profile(&self) -> Result<Option<&[String]>, String> {
+        self.profile.as_deref().map(|name| self.profile(name)).transpose()
+    }
+
+    /// Load workspace config. Returns default if the file doesn't exist.
+    pub fn load(workspace_root: &Path) -> Result<Self, String> {
+        let path = Self::config_path(workspace_root);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .map_err(|e| format!("Failed to parse {}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    /// Save workspace config to disk.
+    pub fn save(&self, workspace_root: &Path) -> Result<(), String> {
+        let path = Self::config_path(workspace_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create workspace config dir: {e}"))?;
+        }
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize workspace config: {e}"))?;
+        std::fs::write(&path, content)
+            .map_err(|e| format!("Failed to write workspace config: {e}"))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_missing_is_default() {
+        let temp = TempDir::new().unwrap();
+        let config = WorkspaceConfig::load(temp.path()).unwrap();
+        assert_eq!(config, WorkspaceConfig::default());
+        assert!(config.toolchains.is_empty());
+    }
+
+    #[test]
+    fn test_save_load_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let config = WorkspaceConfig {
+            toolchains: ToolchainPins {
+                rust: Some("1.85.0".to_string()),
+                ghc: None,
+                node: Some("22".to_string()),
+            },
+            policy: Policy {
+                linear_history: true,
+            },
+            owners: BTreeMap::from([(
+                "@acme/platform".to_string(),
+                vec!["*".to_string(), "/ci/".to_string()],
+            )]),
+            templates: Templates {
+                source: Some("dotfiles".to_string()),
+                files: vec![],
+                branch: Some("chore/templates".to_string()),
+            },
+            managed: ManagedFiles {
+                message: Some("chore: sync managed files".to_string()),
+                branch: None,
+                files: vec![ManagedFile {
+                    path: ".editorconfig".to_string(),
+                    source: Some("dotfiles/.editorconfig".to_string()),
+                    repos: vec!["api-*".to_string()],
+                    ..Default::default()
+                }],
+            },
+            updates: UpdateBots {
+                bot: Some(UpdateBot::Renovate),
+                schedule: Some("weekly".to_string()),
+                automerge: true,
+            },
+            pins: BTreeMap::from([("libfoo".to_string(), "v1.4.2".to_string())]),
+            profiles: BTreeMap::from([("frontend".to_string(), vec!["web-*".to_string()])]),
+            profile: Some("frontend".to_string()),
+            defaults: RepoDefaults {
+                org: Some("acme".to_string()),
+                forges: vec!["github".to_string(), "codeberg".to_string()],
+                visibility: Some(Visibility::Private),
+                ci: None,
+            },
+        };
+        config.save(temp.path()).unwrap();
+
+        let loaded = WorkspaceConfig::load(temp.path()).unwrap();
+        assert_eq!(loaded, config);
+    }
+
+    #[test]
+    fn test_active_profile() {
+        let mut config = WorkspaceConfig {
+            profiles: BTreeMap::from([("frontend".to_string(), vec!["web-*".to_string()])]),
+            ..Default::default()
+        };
+        assert_eq!(config.active_profile(), Ok(None));
+        config.profile = Some("frontend".to_string());
+        assert_eq!(config.active_profile(), Ok(Some(&["web-*".to_string()][..])));
+        config.profile = Some("backend".to_string());
+        assert!(config.active_profile().unwrap_err().contains("frontend"));
+    }
+
+    #[test]
+    fn test_load_invalid_errors() {
+        let temp = TempDir::new().unwrap();
+        let path = WorkspaceConfig::config_path(temp.path());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "toolchains = 3").unwrap();
+        assert!(WorkspaceConfig::load(temp.path()).is_err());
+    }
+}
@@ -109,6 +109,11 @@ pub async fn build_image(
     use bollard::image::BuildImageOptions;
     use futures::StreamExt;
 
+    // Builds count against the global container limit
+    let _slot = crate::scheduler::Scheduler::global()
+        .acquire(crate::scheduler::Resource::Container)
+        .await;
+
     // Create a tar archive of the build context
     info!(target: "hyperforge::docker::build", tag = %image_tag, dockerfile = %dockerfile, context = %build_context.display(), "Starting Docker build");
     let tar_data = create_build_tar(build_context, dockerfile)?;
@@ -202,13 +207,13 @@ pub fn to_docker_credentials(
         crate::types::RegistryAuth::Token(token) => bollard::auth::DockerCredentials {
             username: Some(org.to_string()),
             password: Some(token.clone()),
-            serveraddress: Some(format!("https://{}", registry.host())),
+            serveraddress: Some(registry.server_address()),
             ..Default::default()
         },
         crate::types::RegistryAuth::Basic { username, password } => bollard::auth::DockerCredentials {
             username: Some(username.clone()),
             password: Some(password.clone()),
-            serveraddress: Some(format!("https://{}", registry.host())),
+            serveraddress: Some(registry.server_address()),
             ..Default::default()
         },
         crate::types::RegistryAuth::Anonymous => bollard::auth::DockerCredentials::default(),
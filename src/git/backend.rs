@@ -0,0 +1,378 @@
+//! Git backends for the read-heavy `Git::*` operations
+//!
+//! Workspace scans ask every repo for its status, remotes, branch and HEAD.
+//! The CLI backend spawns `git` for each question; with the `git2` feature
+//! the libgit2 backend answers them in-process, which is much faster across
+//! hundreds of repos and doesn't depend on the installed git's version or
+//! locale. Anything that talks to a remote, runs hooks or rewrites history
+//! (push, fetch, commit, checkout) always goes through the CLI.
+//!
+//! `HYPERFORGE_GIT_BACKEND=cli|libgit2` picks the backend at runtime; the
+//! default is libgit2 when it's compiled in, the CLI otherwise.
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use super::{command_error_message, Git, GitError, GitResult, RemoteInfo, RepoStatus};
+
+/// The operations a backend implements. `path` is always a repo root
+/// (`Git` checks that first); `repo_status` leaves `in_progress` unset.
+pub trait GitBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn list_remotes(&self, path: &Path) -> GitResult<Vec<RemoteInfo>>;
+    /// Current branch name; empty on a detached HEAD
+    fn current_branch(&self, path: &Path) -> GitResult<String>;
+    fn head_commit(&self, path: &Path) -> GitResult<String>;
+    fn resolve_commit(&self, path: &Path, rev: &str) -> GitResult<Option<String>>;
+    fn config_get(&self, path: &Path, key: &str) -> GitResult<Option<String>>;
+    fn tag_exists(&self, path: &Path, tag: &str) -> bool;
+    fn repo_status(&self, path: &Path) -> GitResult<RepoStatus>;
+}
+
+/// The backend `Git::*` uses, chosen once per process
+pub fn backend() -> &'static dyn GitBackend {
+    static BACKEND: OnceLock<Box<dyn GitBackend>> = OnceLock::new();
+    BACKEND
+        .get_or_init(|| select(std::env::var("HYPERFORGE_GIT_BACKEND").ok().as_deref()))
+        .as_ref()
+}
+
+/// Backend for a `HYPERFORGE_GIT_BACKEND` value; unknown values and
+/// `libgit2` without the `git2` feature fall back to the default
+fn select(choice: Option<&str>) -> Box<dyn GitBackend> {
+    match choice {
+        Some(v) if v.eq_ignore_ascii_case("cli") => Box::new(CliBackend),
+        #[cfg(feature = "git2")]
+        _ => Box::new(Git2Backend),
+        #[cfg(not(feature = "git2"))]
+        _ => Box::new(CliBackend),
+    }
+}
+
+/// Run `git args` in `path`
+fn run(path: &Path, args: &[&str]) -> GitResult<std::process::Output> {
+    Ok(Command::new("git").args(args).current_dir(path).output()?)
+}
+
+/// Spawns `git` for every operation
+pub struct CliBackend;
+
+impl GitBackend for CliBackend {
+    fn name(&self) -> &'static str {
+        "cli"
+    }
+
+    fn list_remotes(&self, path: &Path) -> GitResult<Vec<RemoteInfo>> {
+        let output = run(path, &["remote", "-v"])?;
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+        Ok(Git::parse_remotes(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn current_branch(&self, path: &Path) -> GitResult<String> {
+        let output = run(path, &["branch", "--show-current"])?;
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn head_commit(&self, path: &Path) -> GitResult<String> {
+        let output = run(path, &["rev-parse", "HEAD"])?;
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: command_error_message(&output),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn resolve_commit(&self, path: &Path, rev: &str) -> GitResult<Option<String>> {
+        let output = run(path, &["rev-parse", "--verify", "--quiet", &format!("{rev}^{{commit}}")])?;
+        if !output.status.success() {
+            return Ok(None);
+        }
+        Ok(Some(String::from_utf8_lossy(&output.stdout).trim().to_string()))
+    }
+
+    fn config_get(&self, path: &Path, key: &str) -> GitResult<Option<String>> {
+        let output = run(path, &["config", "--get", key])?;
+        if !output.status.success() {
+            // Key not found is not an error
+            return Ok(None);
+        }
+        Ok(Some(String::from_utf8_lossy(&output.stdout).trim().to_string()))
+    }
+
+    fn tag_exists(&self, path: &Path, tag: &str) -> bool {
+        Command::new("git")
+            .args(["rev-parse", "--verify", &format!("refs/tags/{tag}")])
+            .current_dir(path)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    }
+
+    /// One `git status --porcelain=v2 --branch` call covers the branch,
+    /// upstream, working tree and conflicts
+    fn repo_status(&self, path: &Path) -> GitResult<RepoStatus> {
+        let output = run(path, &["status", "--porcelain=v2", "--branch"])?;
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+        Ok(Git::parse_status_v2(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+#[cfg(feature = "git2")]
+pub use libgit2::Git2Backend;
+
+#[cfg(feature = "git2")]
+mod libgit2 {
+    use std::path::Path;
+
+    use git2::{ErrorCode, Repository, Status, StatusOptions};
+
+    use super::GitBackend;
+    use crate::git::{BranchStatus, GitError, GitResult, RemoteInfo, RepoStatus};
+
+    impl From<git2::Error> for GitError {
+        fn from(e: git2::Error) -> Self {
+            Self::CommandFailed {
+                message: e.message().to_string(),
+            }
+        }
+    }
+
+    fn open(path: &Path) -> GitResult<Repository> {
+        Ok(Repository::open(path)?)
+    }
+
+    /// Branch HEAD points at, whether or not it has commits yet; `None` when
+    /// detached
+    fn head_branch(repo: &Repository) -> GitResult<Option<String>> {
+        let head = repo.find_reference("HEAD")?;
+        Ok(head
+            .symbolic_target()?
+            .and_then(|t| t.strip_prefix("refs/heads/"))
+            .map(str::to_string))
+    }
+
+    /// Answers in-process through libgit2
+    pub struct Git2Backend;
+
+    impl GitBackend for Git2Backend {
+        fn name(&self) -> &'static str {
+            "libgit2"
+        }
+
+        fn list_remotes(&self, path: &Path) -> GitResult<Vec<RemoteInfo>> {
+            let repo = open(path)?;
+            let names = repo.remotes()?;
+            let mut remotes = Vec::new();
+            for name in names.iter().filter_map(|n| n.ok().flatten()) {
+                let remote = repo.find_remote(name)?;
+                let Ok(fetch_url) = remote.url().map(str::to_string) else {
+                    continue;
+                };
+                // `pushurl` may be set several times; libgit2 only reports the last
+                let mut push_urls = Vec::new();
+                let config = repo.config()?;
+                let mut entries = config.multivar(&format!("remote.{name}.pushurl"), None)?;
+                while let Some(entry) = entries.next() {
+                    if let Ok(url) = entry?.value() {
+                        push_urls.push(url.to_string());
+                    }
+                }
+                let push_url = if push_urls.is_empty() { fetch_url.clone() } else { push_urls.remove(0) };
+                remotes.push(RemoteInfo {
+                    name: name.to_string(),
+                    fetch_url,
+                    push_url,
+                    extra_push_urls: push_urls,
+                });
+            }
+            Ok(remotes)
+        }
+
+        fn current_branch(&self, path: &Path) -> GitResult<String> {
+            Ok(head_branch(&open(path)?)?.unwrap_or_default())
+        }
+
+        fn head_commit(&self, path: &Path) -> GitResult<String> {
+            let repo = open(path)?;
+            let commit = repo.head()?.peel_to_commit()?;
+            Ok(commit.id().to_string())
+        }
+
+        fn resolve_commit(&self, path: &Path, rev: &str) -> GitResult<Option<String>> {
+            let repo = open(path)?;
+            Ok(repo
+                .revparse_single(rev)
+                .and_then(|object| object.peel_to_commit())
+                .ok()
+                .map(|commit| commit.id().to_string()))
+        }
+
+        fn config_get(&self, path: &Path, key: &str) -> GitResult<Option<String>> {
+            let config = open(path)?.config()?;
+            match config.get_string(key) {
+                Ok(value) => Ok(Some(value)),
+                Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        }
+
+        fn tag_exists(&self, path: &Path, tag: &str) -> bool {
+            open(path).is_ok_and(|repo| repo.find_reference(&format!("refs/tags/{tag}")).is_ok())
+        }
+
+        fn repo_status(&self, path: &Path) -> GitResult<RepoStatus> {
+            let repo = open(path)?;
+            let mut status = RepoStatus {
+                head_oid: repo
+                    .head()
+                    .ok()
+                    .and_then(|h| h.peel_to_commit().ok())
+                    .map(|c| c.id().to_string()),
+                ..RepoStatus::default()
+            };
+            match head_branch(&repo)? {
+                Some(branch) => status.branch = branch,
+                None => status.detached = true,
+            }
+
+            let mut opts = StatusOptions::new();
+            opts.include_untracked(true)
+                .recurse_untracked_dirs(false)
+                .renames_head_to_index(true);
+            for entry in repo.statuses(Some(&mut opts))?.iter() {
+                let flags = entry.status();
+                // Renames are listed under their new path
+                let path = entry
+                    .head_to_index()
+                    .and_then(|d| d.new_file().path().map(Path::to_path_buf))
+                    .or_else(|| entry.index_to_workdir().and_then(|d| d.new_file().path().map(Path::to_path_buf)))
+                    .map_or_else(|| entry.path().unwrap_or_default().to_string(), |p| p.display().to_string());
+
+                if flags.contains(Status::CONFLICTED) {
+                    status.conflicts += 1;
+                    status.has_staged = true;
+                    status.has_changes = true;
+                    status.files.modified.push(path);
+                    continue;
+                }
+                if flags == Status::WT_NEW {
+                    status.has_untracked = true;
+                    status.files.untracked.push(path);
+                    continue;
+                }
+                let staged = flags.intersects(
+                    Status::INDEX_NEW
+                        | Status::INDEX_MODIFIED
+                        | Status::INDEX_DELETED
+                        | Status::INDEX_RENAMED
+                        | Status::INDEX_TYPECHANGE,
+                );
+                let changed = flags.intersects(
+                    Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED | Status::WT_TYPECHANGE,
+                );
+                status.has_staged |= staged;
+                status.has_changes |= changed;
+                if flags.contains(Status::INDEX_NEW) {
+                    status.files.added.push(path);
+                } else if flags.intersects(Status::INDEX_DELETED | Status::WT_DELETED) {
+                    status.files.deleted.push(path);
+                } else {
+                    status.files.modified.push(path);
+                }
+            }
+
+            if !status.branch.is_empty() {
+                let local_ref = format!("refs/heads/{}", status.branch);
+                // The configured upstream, whether or not its ref still exists
+                let upstream_ref = repo
+                    .branch_upstream_name(&local_ref)
+                    .ok()
+                    .and_then(|buf| buf.as_str().ok().map(str::to_string));
+                let upstream = upstream_ref
+                    .as_deref()
+                    .map(|r| r.strip_prefix("refs/remotes/").unwrap_or(r).to_string());
+                let upstream_oid = upstream_ref
+                    .as_deref()
+                    .and_then(|r| repo.refname_to_id(r).ok());
+                status.upstream_gone = upstream.is_some() && upstream_oid.is_none();
+
+                let (ahead, behind) = match (repo.refname_to_id(&local_ref).ok(), upstream_oid) {
+                    (Some(local), Some(upstream)) => repo.graph_ahead_behind(local, upstream)?,
+                    _ => (0, 0),
+                };
+                status.tracking = Some(BranchStatus {
+                    name: status.branch.clone(),
+                    upstream,
+                    ahead: u32::try_from(ahead).unwrap_or(u32::MAX),
+                    behind: u32::try_from(behind).unwrap_or(u32::MAX),
+                });
+            }
+            Ok(status)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_backend() {
+        assert_eq!(select(Some("cli")).name(), "cli");
+        assert_eq!(select(Some("CLI")).name(), "cli");
+        let default = if cfg!(feature = "git2") { "libgit2" } else { "cli" };
+        assert_eq!(select(None).name(), default);
+        assert_eq!(select(Some("libgit2")).name(), default);
+    }
+
+    #[cfg(feature = "git2")]
+    #[test]
+    fn test_backends_agree() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path();
+        Git::init(path).unwrap();
+        Git::config_set(path, "user.email", "test@test.com").unwrap();
+        Git::config_set(path, "user.name", "Test").unwrap();
+        std::fs::write(path.join("a.txt"), "one").unwrap();
+        Git::add(path, "a.txt").unwrap();
+        Git::commit(path, "one").unwrap();
+        Git::tag(path, "v1.0.0", None).unwrap();
+        Git::add_remote(path, "origin", "git@github.com:acme/tool.git").unwrap();
+        std::fs::write(path.join("a.txt"), "two").unwrap();
+        std::fs::write(path.join("b.txt"), "new").unwrap();
+        std::fs::create_dir(path.join("dir")).unwrap();
+        std::fs::write(path.join("dir/c.txt"), "untracked").unwrap();
+        Git::add(path, "b.txt").unwrap();
+
+        let (cli, lib) = (CliBackend, Git2Backend);
+        assert_eq!(cli.list_remotes(path).unwrap(), lib.list_remotes(path).unwrap());
+        assert_eq!(cli.current_branch(path).unwrap(), lib.current_branch(path).unwrap());
+        assert_eq!(cli.head_commit(path).unwrap(), lib.head_commit(path).unwrap());
+        assert_eq!(cli.resolve_commit(path, "v1.0.0").unwrap(), lib.resolve_commit(path, "v1.0.0").unwrap());
+        assert_eq!(lib.resolve_commit(path, "nope").unwrap(), None);
+        assert_eq!(cli.config_get(path, "user.name").unwrap(), lib.config_get(path, "user.name").unwrap());
+        assert_eq!(lib.config_get(path, "no.such").unwrap(), None);
+        assert!(lib.tag_exists(path, "v1.0.0") && !lib.tag_exists(path, "v2"));
+
+        let (a, b) = (cli.repo_status(path).unwrap(), lib.repo_status(path).unwrap());
+        assert_eq!((a.has_changes, a.has_staged, a.has_untracked), (b.has_changes, b.has_staged, b.has_untracked));
+        assert_eq!((&a.branch, &a.head_oid, a.detached), (&b.branch, &b.head_oid, b.detached));
+        assert_eq!(a.files, b.files);
+    }
+}
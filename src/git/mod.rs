@@ -1,10 +1,18 @@
 //! Git operations and remote management
 //!
 //! This module provides git command execution and parsing for hyperforge.
-//! It uses git as the source of truth for repository state.
+//! It uses git as the source of truth for repository state. Read-heavy
+//! queries go through a [`GitBackend`] (the git CLI, or libgit2 with the
+//! `git2` feature); everything else spawns `git`.
 
-use std::collections::HashMap;
-use std::path::Path;
+mod backend;
+
+pub use backend::{backend, CliBackend, GitBackend};
+#[cfg(feature = "git2")]
+pub use backend::Git2Backend;
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use thiserror::Error;
 
@@ -57,6 +65,17 @@ pub struct RemoteInfo {
     pub name: String,
     pub fetch_url: String,
     pub push_url: String,
+    /// Further push URLs when a `git push` fans out to several forges
+    pub extra_push_urls: Vec<String>,
+}
+
+impl RemoteInfo {
+    /// Every URL a push to this remote goes to, in config order
+    pub fn push_urls(&self) -> Vec<&str> {
+        std::iter::once(self.push_url.as_str())
+            .chain(self.extra_push_urls.iter().map(String::as_str))
+            .collect()
+    }
 }
 
 /// Branch tracking information
@@ -68,14 +87,132 @@ pub struct BranchStatus {
     pub behind: u32,
 }
 
+/// A local branch and the upstream it's configured to track
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalBranch {
+    pub name: String,
+    /// `branch.<name>.remote` (`.` for a local upstream)
+    pub upstream_remote: Option<String>,
+    /// Branch name on that remote, from `branch.<name>.merge`
+    pub upstream_branch: Option<String>,
+}
+
+/// A multi-step operation stopped partway in a working tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InProgressOp {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+    Bisect,
+}
+
+impl InProgressOp {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Rebase => "rebase",
+            Self::CherryPick => "cherry-pick",
+            Self::Revert => "revert",
+            Self::Bisect => "bisect",
+        }
+    }
+}
+
+/// Paths behind a dirty working tree, by kind of change
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DirtyFiles {
+    pub modified: Vec<String>,
+    pub added: Vec<String>,
+    pub deleted: Vec<String>,
+    pub untracked: Vec<String>,
+}
+
+impl DirtyFiles {
+    pub const fn total(&self) -> usize {
+        self.modified.len() + self.added.len() + self.deleted.len() + self.untracked.len()
+    }
+
+    /// Keep at most `cap` paths per list; returns how many were dropped
+    pub fn truncate(&mut self, cap: usize) -> usize {
+        let before = self.total();
+        for list in [&mut self.modified, &mut self.added, &mut self.deleted, &mut self.untracked] {
+            list.truncate(cap);
+        }
+        before - self.total()
+    }
+
+    fn record(&mut self, xy: &str, path: &str) {
+        let path = path.to_string();
+        if xy.contains('A') {
+            self.added.push(path);
+        } else if xy.contains('D') {
+            self.deleted.push(path);
+        } else {
+            self.modified.push(path);
+        }
+    }
+}
+
+/// A commit listed in a divergence, newest first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitSummary {
+    pub oid: String,
+    pub subject: String,
+}
+
+/// How a local ref and a remote-tracking ref relate in the commit graph
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Divergence {
+    /// `None` when the histories are unrelated
+    pub merge_base: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    /// Commits only on the local side (capped)
+    pub local_only: Vec<CommitSummary>,
+    /// Commits only on the remote side (capped)
+    pub remote_only: Vec<CommitSummary>,
+}
+
 /// Repository status
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct RepoStatus {
     pub branch: String,
     pub tracking: Option<BranchStatus>,
     pub has_changes: bool,
     pub has_staged: bool,
     pub has_untracked: bool,
+    /// HEAD commit; `None` before the first commit
+    pub head_oid: Option<String>,
+    /// The branch tracks an upstream that no longer exists on the remote
+    pub upstream_gone: bool,
+    /// Merge, rebase, cherry-pick, revert or bisect left in progress
+    pub in_progress: Option<InProgressOp>,
+    /// Unmerged (conflicted) paths
+    pub conflicts: usize,
+    /// HEAD points at a commit rather than a branch
+    pub detached: bool,
+    /// Dirty paths; renames are listed as modified under their new path
+    pub files: DirtyFiles,
+}
+
+impl RepoStatus {
+    /// Why the repo isn't in a state to push or sync, if it isn't
+    /// (`"rebase in progress, 2 conflicted files"`)
+    pub fn blocking_state(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(op) = self.in_progress {
+            parts.push(format!("{} in progress", op.as_str()));
+        }
+        if self.conflicts > 0 {
+            let plural = if self.conflicts == 1 { "" } else { "s" };
+            parts.push(format!("{} conflicted file{plural}", self.conflicts));
+        }
+        if self.detached {
+            parts.push("detached HEAD".to_string());
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
 }
 
 /// Maximum number of retries for transient push errors
@@ -129,6 +266,86 @@ where
     Err(last_err.unwrap())
 }
 
+/// Which objects a partial clone leaves on the server until needed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloneFilter {
+    /// All commits and trees, file contents on demand (`blob:none`)
+    Blobless,
+    /// All commits, trees and file contents on demand (`tree:0`)
+    Treeless,
+}
+
+impl CloneFilter {
+    pub const fn spec(self) -> &'static str {
+        match self {
+            Self::Blobless => "blob:none",
+            Self::Treeless => "tree:0",
+        }
+    }
+}
+
+impl std::str::FromStr for CloneFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "blobless" | "blob:none" => Ok(Self::Blobless),
+            "treeless" | "tree:0" => Ok(Self::Treeless),
+            other => Err(format!("Unknown clone filter '{other}' (expected blobless or treeless)")),
+        }
+    }
+}
+
+/// How much of a repository `git clone` fetches; the default is everything
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CloneOptions {
+    /// Truncate history to this many commits
+    pub depth: Option<u32>,
+    pub filter: Option<CloneFilter>,
+    /// Fetch only the default branch
+    pub single_branch: bool,
+}
+
+impl CloneOptions {
+    /// The `git clone` flags for these options
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(depth) = self.depth {
+            args.push(format!("--depth={depth}"));
+        }
+        if let Some(filter) = self.filter {
+            args.push(format!("--filter={}", filter.spec()));
+        }
+        // --depth implies --single-branch; say so only when asked for
+        if self.single_branch {
+            args.push("--single-branch".to_string());
+        } else if self.depth.is_some() {
+            args.push("--no-single-branch".to_string());
+        }
+        args
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.depth.is_none() && self.filter.is_none() && !self.single_branch
+    }
+}
+
+/// What a clone is missing compared to a full one
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CloneCompleteness {
+    pub shallow: bool,
+    /// The partial clone filter the remote was cloned with
+    pub filter: Option<String>,
+    /// The remote fetches only some branches
+    pub single_branch: bool,
+}
+
+impl CloneCompleteness {
+    pub const fn is_full(&self) -> bool {
+        !self.shallow && self.filter.is_none() && !self.single_branch
+    }
+}
+
 /// Git operations helper
 pub struct Git;
 
@@ -154,10 +371,13 @@ impl Git {
         Ok(())
     }
 
-    /// Clone a git repository
-    pub fn clone(url: &str, target_path: &str) -> GitResult<()> {
+    /// Clone a git repository; `options` can make it shallow, partial or
+    /// single-branch
+    pub fn clone(url: &str, target_path: &str, options: &CloneOptions) -> GitResult<()> {
         let output = Command::new("git")
-            .args(["clone", url, target_path])
+            .arg("clone")
+            .args(options.args())
+            .args([url, target_path])
             .output()?;
 
         if !output.status.success() {
@@ -172,20 +392,7 @@ impl Git {
     /// List all remotes with their URLs
     pub fn list_remotes(path: &Path) -> GitResult<Vec<RemoteInfo>> {
         Self::ensure_repo(path)?;
-
-        let output = Command::new("git")
-            .args(["remote", "-v"])
-            .current_dir(path)
-            .output()?;
-
-        if !output.status.success() {
-            return Err(GitError::CommandFailed {
-                message: String::from_utf8_lossy(&output.stderr).to_string(),
-            });
-        }
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        Ok(Self::parse_remotes(&stdout))
+        backend().list_remotes(path)
     }
 
     /// Get a specific remote's info
@@ -271,6 +478,89 @@ impl Git {
         Ok(())
     }
 
+    /// Set a remote's push URL, or drop it (`None`) so pushes use the fetch URL
+    pub fn set_remote_push_url(path: &Path, name: &str, url: Option<&str>) -> GitResult<()> {
+        Self::ensure_repo(path)?;
+
+        let key = format!("remote.{name}.pushurl");
+        let output = match url {
+            Some(url) => Command::new("git")
+                .args(["remote", "set-url", "--push", name, url])
+                .current_dir(path)
+                .output()?,
+            None => Command::new("git")
+                .args(["config", "--unset-all", &key])
+                .current_dir(path)
+                .output()?,
+        };
+
+        // `config --unset-all` exits 5 when there was nothing to unset
+        let nothing_to_unset = url.is_none() && output.status.code() == Some(5);
+        if !output.status.success() && !nothing_to_unset {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("No such remote") {
+                return Err(GitError::RemoteNotFound {
+                    name: name.to_string(),
+                });
+            }
+            return Err(GitError::CommandFailed {
+                message: stderr.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Replace a remote's push URLs with `urls`, so one `git push` goes to
+    /// each of them in order
+    pub fn set_remote_push_urls(path: &Path, name: &str, urls: &[&str]) -> GitResult<()> {
+        Self::set_remote_push_url(path, name, None)?;
+
+        for url in urls {
+            let output = Command::new("git")
+                .args(["remote", "set-url", "--add", "--push", name, url])
+                .current_dir(path)
+                .output()?;
+
+            if !output.status.success() {
+                return Err(GitError::CommandFailed {
+                    message: String::from_utf8_lossy(&output.stderr).to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rename a remote, moving its remote-tracking branches with it
+    pub fn rename_remote(path: &Path, from: &str, to: &str) -> GitResult<()> {
+        Self::ensure_repo(path)?;
+
+        let output = Command::new("git")
+            .args(["remote", "rename", from, to])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("No such remote") {
+                return Err(GitError::RemoteNotFound {
+                    name: from.to_string(),
+                });
+            }
+            if stderr.contains("already exists") {
+                return Err(GitError::RemoteAlreadyExists {
+                    name: to.to_string(),
+                });
+            }
+            return Err(GitError::CommandFailed {
+                message: stderr.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
     /// Configure SSH key for the repository using core.sshCommand
     pub fn configure_ssh(path: &Path, key_path: &str) -> GitResult<()> {
         Self::ensure_repo(path)?;
@@ -304,19 +594,7 @@ impl Git {
     /// Get current branch name
     pub fn current_branch(path: &Path) -> GitResult<String> {
         Self::ensure_repo(path)?;
-
-        let output = Command::new("git")
-            .args(["branch", "--show-current"])
-            .current_dir(path)
-            .output()?;
-
-        if !output.status.success() {
-            return Err(GitError::CommandFailed {
-                message: String::from_utf8_lossy(&output.stderr).to_string(),
-            });
-        }
-
-        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        backend().current_branch(path)
     }
 
     /// Get branch tracking status (ahead/behind)
@@ -339,56 +617,123 @@ impl Git {
         Ok(Self::parse_branch_status(&stdout))
     }
 
-    /// Get repository status (changes, staged, untracked)
+    /// Get repository status (branch, tracking, changes, staged, untracked)
+    ///
+    /// The backend covers the branch, upstream, working tree and conflicts;
+    /// in-progress operations are read from the git dir without spawning
+    /// anything else.
     pub fn repo_status(path: &Path) -> GitResult<RepoStatus> {
         Self::ensure_repo(path)?;
+        let mut status = backend().repo_status(path)?;
+        status.in_progress = Self::in_progress_op(path);
+        Ok(status)
+    }
 
-        let branch = Self::current_branch(path)?;
-        let tracking = Self::branch_status(path)?;
-
-        // Get porcelain status for parsing
-        let output = Command::new("git")
-            .args(["status", "--porcelain=v1"])
-            .current_dir(path)
-            .output()?;
-
-        if !output.status.success() {
-            return Err(GitError::CommandFailed {
-                message: String::from_utf8_lossy(&output.stderr).to_string(),
-            });
+    /// The repo's git dir: `.git` itself, or where a worktree/submodule
+    /// `.git` file points
+    fn git_dir(path: &Path) -> PathBuf {
+        let dot_git = path.join(".git");
+        if dot_git.is_file() {
+            if let Some(dir) = std::fs::read_to_string(&dot_git)
+                .ok()
+                .and_then(|c| c.trim().strip_prefix("gitdir:").map(|d| d.trim().to_string()))
+            {
+                return path.join(dir);
+            }
         }
+        dot_git
+    }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let mut has_changes = false;
-        let mut has_staged = false;
-        let mut has_untracked = false;
+    fn in_progress_op(path: &Path) -> Option<InProgressOp> {
+        let git_dir = Self::git_dir(path);
+        // A rebase stopped on a conflicting pick also leaves CHERRY_PICK_HEAD,
+        // so rebase is checked first
+        [
+            ("rebase-merge", InProgressOp::Rebase),
+            ("rebase-apply", InProgressOp::Rebase),
+            ("MERGE_HEAD", InProgressOp::Merge),
+            ("CHERRY_PICK_HEAD", InProgressOp::CherryPick),
+            ("REVERT_HEAD", InProgressOp::Revert),
+            ("BISECT_LOG", InProgressOp::Bisect),
+        ]
+        .into_iter()
+        .find(|(marker, _)| git_dir.join(marker).exists())
+        .map(|(_, op)| op)
+    }
+
+    /// Parse `git status --porcelain=v2 --branch` output
+    fn parse_status_v2(output: &str) -> RepoStatus {
+        let mut status = RepoStatus::default();
+        let mut upstream = None;
+        let mut ahead_behind = None;
 
-        for line in stdout.lines() {
-            if line.len() < 2 {
+        for line in output.lines() {
+            if let Some(header) = line.strip_prefix("# ") {
+                let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+                match key {
+                    "branch.oid" if value != "(initial)" => status.head_oid = Some(value.to_string()),
+                    "branch.head" if value == "(detached)" => status.detached = true,
+                    "branch.head" => status.branch = value.to_string(),
+                    "branch.upstream" => upstream = Some(value.to_string()),
+                    "branch.ab" => {
+                        let mut parts = value.split_whitespace();
+                        let ahead = parts.next().and_then(|a| a.trim_start_matches('+').parse().ok());
+                        let behind = parts.next().and_then(|b| b.trim_start_matches('-').parse().ok());
+                        ahead_behind = Some((ahead.unwrap_or(0), behind.unwrap_or(0)));
+                    }
+                    _ => {}
+                }
                 continue;
             }
-            let index_status = line.chars().next().unwrap_or(' ');
-            let worktree_status = line.chars().nth(1).unwrap_or(' ');
 
-            if index_status == '?' {
-                has_untracked = true;
-            } else {
-                if index_status != ' ' {
-                    has_staged = true;
-                }
-                if worktree_status != ' ' {
-                    has_changes = true;
+            // Ordinary entries carry 6 fields before the path, renames 7
+            // (plus a tab-separated original path), unmerged entries 8
+            let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let path_at = match kind {
+                "1" => 7,
+                "2" => 8,
+                "u" => 9,
+                "?" => {
+                    status.has_untracked = true;
+                    status.files.untracked.push(rest.to_string());
+                    continue;
                 }
+                _ => continue,
+            };
+            let mut fields = rest.splitn(path_at + 1, ' ');
+            let xy = fields.next().unwrap_or("..");
+            let path = fields.nth(path_at - 1).unwrap_or("");
+            let path = path.split('\t').next().unwrap_or(path);
+
+            if kind == "u" {
+                status.conflicts += 1;
+                status.has_staged = true;
+                status.has_changes = true;
+                status.files.modified.push(path.to_string());
+                continue;
+            }
+            let mut chars = xy.chars();
+            if chars.next().is_some_and(|x| x != '.') {
+                status.has_staged = true;
             }
+            if chars.next().is_some_and(|y| y != '.') {
+                status.has_changes = true;
+            }
+            status.files.record(xy, path);
         }
 
-        Ok(RepoStatus {
-            branch,
-            tracking,
-            has_changes,
-            has_staged,
-            has_untracked,
-        })
+        // An upstream without `branch.ab` is configured but gone
+        status.upstream_gone = upstream.is_some() && ahead_behind.is_none();
+        if !status.branch.is_empty() {
+            let (ahead, behind) = ahead_behind.unwrap_or((0, 0));
+            status.tracking = Some(BranchStatus {
+                name: status.branch.clone(),
+                upstream,
+                ahead,
+                behind,
+            });
+        }
+        status
     }
 
     /// Push to a remote (retries transient SSH/network failures)
@@ -440,21 +785,380 @@ impl Git {
                 });
             }
 
-            Ok(())
-        })
+            Ok(())
+        })
+    }
+
+    /// Push with extra `git push` flags and environment (retries transient failures).
+    ///
+    /// The environment reaches hooks, e.g. `HYPERFORGE_ALLOW_FINDINGS=1` tells
+    /// the pre-push hook to skip its outgoing-commit scan.
+    pub fn push_with_env(
+        path: &Path,
+        remote: &str,
+        branch: &str,
+        flags: &[&str],
+        env: &[(&str, &str)],
+    ) -> GitResult<()> {
+        Self::ensure_repo(path)?;
+
+        let path = path.to_path_buf();
+        let mut args: Vec<String> = vec!["push".to_string()];
+        args.extend(flags.iter().map(|f| (*f).to_string()));
+        args.push(remote.to_string());
+        args.push(branch.to_string());
+        let env: Vec<(String, String)> = env
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+
+        push_with_retry(move || {
+            let output = Command::new("git")
+                .args(&args)
+                .envs(env.iter().map(|(k, v)| (k, v)))
+                .current_dir(&path)
+                .output()?;
+
+            if !output.status.success() {
+                return Err(GitError::CommandFailed {
+                    message: command_error_message(&output),
+                });
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Pull from a remote
+    pub fn pull(path: &Path, remote: Option<&str>) -> GitResult<()> {
+        Self::ensure_repo(path)?;
+
+        let mut args = vec!["pull"];
+        if let Some(r) = remote {
+            args.push(r);
+        }
+
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Fast-forward the current branch to `rev`; fails instead of merging
+    /// when the histories have diverged
+    pub fn merge_ff_only(path: &Path, rev: &str) -> GitResult<()> {
+        Self::ensure_repo(path)?;
+
+        let output = Command::new("git")
+            .args(["merge", "--ff-only", "--quiet", rev])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: command_error_message(&output),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checkout a branch
+    pub fn checkout(path: &Path, branch: &str) -> GitResult<()> {
+        Self::ensure_repo(path)?;
+
+        let output = Command::new("git")
+            .args(["checkout", branch])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check out `branch`, creating it from HEAD if it doesn't exist yet
+    pub fn checkout_or_create(path: &Path, branch: &str) -> GitResult<()> {
+        let exists = Command::new("git")
+            .args(["rev-parse", "--verify", &format!("refs/heads/{branch}")])
+            .current_dir(path)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success());
+        if exists {
+            return Self::checkout(path, branch);
+        }
+
+        let output = Command::new("git")
+            .args(["checkout", "-b", branch])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check out `branch`, creating it or resetting it to `start_point`
+    pub fn checkout_reset(path: &Path, branch: &str, start_point: &str) -> GitResult<()> {
+        Self::ensure_repo(path)?;
+
+        let output = Command::new("git")
+            .args(["checkout", "--quiet", "-B", branch, start_point])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: command_error_message(&output),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Point `origin` at a fork and `upstream` at the repo it was forked
+    /// from, adding either remote if it's missing
+    pub fn wire_fork_remotes(path: &Path, fork_url: &str, upstream_url: &str) -> GitResult<()> {
+        let remotes = Self::list_remotes(path)?;
+        for (name, url) in [("upstream", upstream_url), ("origin", fork_url)] {
+            if remotes.iter().any(|r| r.name == name) {
+                Self::set_remote_url(path, name, url)?;
+            } else {
+                Self::add_remote(path, name, url)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetch from all remotes
+    pub fn fetch_all(path: &Path) -> GitResult<()> {
+        Self::ensure_repo(path)?;
+
+        let output = Command::new("git")
+            .args(["fetch", "--all"])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Fetch from a specific remote
+    pub fn fetch(path: &Path, remote: &str) -> GitResult<()> {
+        Self::ensure_repo(path)?;
+
+        let output = Command::new("git")
+            .args(["fetch", remote])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// How far the clone's `remote` falls short of a full clone
+    pub fn clone_completeness(path: &Path, remote: &str) -> GitResult<CloneCompleteness> {
+        Self::ensure_repo(path)?;
+
+        let output = Command::new("git")
+            .args(["config", "--get-all", &format!("remote.{remote}.fetch")])
+            .current_dir(path)
+            .output()?;
+        let wildcard = format!("+refs/heads/*:refs/remotes/{remote}/*");
+        let single_branch = !String::from_utf8_lossy(&output.stdout).lines().any(|refspec| refspec.trim() == wildcard);
+
+        Ok(CloneCompleteness {
+            shallow: Self::git_dir(path).join("shallow").exists(),
+            filter: Self::config_get(path, &format!("remote.{remote}.partialclonefilter"))?,
+            single_branch,
+        })
+    }
+
+    /// Turn a shallow, partial or single-branch clone into a full one: track
+    /// every branch of `remote`, drop the partial clone filter and fetch the
+    /// whole history. Returns what was missing before.
+    pub fn unshallow(path: &Path, remote: &str) -> GitResult<CloneCompleteness> {
+        let before = Self::clone_completeness(path, remote)?;
+        if before.is_full() {
+            return Ok(before);
+        }
+
+        let run = |args: &[&str]| -> GitResult<()> {
+            let output = Command::new("git").args(args).current_dir(path).output()?;
+            if output.status.success() {
+                Ok(())
+            } else {
+                Err(GitError::CommandFailed { message: command_error_message(&output) })
+            }
+        };
+        if before.single_branch {
+            run(&["remote", "set-branches", remote, "*"])?;
+        }
+        if before.filter.is_some() {
+            run(&["config", "--unset", &format!("remote.{remote}.partialclonefilter")])?;
+            run(&["config", &format!("remote.{remote}.promisor"), "false"])?;
+        }
+
+        let mut fetch = vec!["fetch", "--quiet", remote];
+        if before.shallow {
+            fetch.push("--unshallow");
+        }
+        if before.filter.is_some() {
+            fetch.push("--refetch");
+        }
+        run(&fetch)?;
+        Ok(before)
+    }
+
+    /// Branch and tag refs a remote advertises, as full ref name → SHA.
+    /// Peeled tag entries (`^{}`) and other namespaces are left out.
+    pub fn ls_remote_refs(path: &Path, remote: &str) -> GitResult<BTreeMap<String, String>> {
+        Self::ensure_repo(path)?;
+
+        let output = Command::new("git")
+            .args(["ls-remote", "--heads", "--tags", remote])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: command_error_message(&output),
+            });
+        }
+
+        Ok(Self::parse_ls_remote(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Parse `git ls-remote` output into ref name → SHA
+    pub fn parse_ls_remote(output: &str) -> BTreeMap<String, String> {
+        output
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .filter(|(_, name)| {
+                (name.starts_with("refs/heads/") || name.starts_with("refs/tags/")) && !name.ends_with("^{}")
+            })
+            .map(|(oid, name)| (name.to_string(), oid.to_string()))
+            .collect()
+    }
+
+    /// Fetch `refspecs` from `remote` without touching local tags
+    pub fn fetch_refspecs(path: &Path, remote: &str, refspecs: &[&str]) -> GitResult<()> {
+        Self::ensure_repo(path)?;
+
+        let output = Command::new("git")
+            .args(["fetch", "--quiet", "--no-tags", "--prune", remote])
+            .args(refspecs)
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: command_error_message(&output),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Push explicit `refspecs` (`+src:dst`, or `:dst` to delete) in one
+    /// call, retrying transient failures. Hooks are skipped: the refs come
+    /// from another forge, not from local work.
+    pub fn push_refspecs(path: &Path, remote: &str, refspecs: &[String]) -> GitResult<()> {
+        Self::ensure_repo(path)?;
+
+        let path = path.to_path_buf();
+        let mut args = vec!["push".to_string(), "--no-verify".to_string(), remote.to_string()];
+        args.extend(refspecs.iter().cloned());
+
+        push_with_retry(move || {
+            let output = Command::new("git")
+                .args(&args)
+                .current_dir(&path)
+                .output()?;
+
+            if !output.status.success() {
+                return Err(GitError::CommandFailed {
+                    message: command_error_message(&output),
+                });
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Get ahead/behind count for a specific remote
+    pub fn ahead_behind(path: &Path, remote: &str, branch: &str) -> GitResult<(u32, u32)> {
+        Self::ensure_repo(path)?;
+
+        let upstream = format!("{remote}/{branch}");
+        let output = Command::new("git")
+            .args(["rev-list", "--left-right", "--count", &format!("{upstream}...HEAD")])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            // Remote branch might not exist yet
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("unknown revision") {
+                return Ok((0, 0));
+            }
+            return Err(GitError::CommandFailed {
+                message: stderr.to_string(),
+            });
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let parts: Vec<&str> = stdout.split_whitespace().collect();
+        if parts.len() != 2 {
+            return Ok((0, 0));
+        }
+
+        let behind = parts[0].parse().unwrap_or(0);
+        let ahead = parts[1].parse().unwrap_or(0);
+
+        Ok((ahead, behind))
     }
 
-    /// Pull from a remote
-    pub fn pull(path: &Path, remote: Option<&str>) -> GitResult<()> {
+    /// Get git config value
+    pub fn config_get(path: &Path, key: &str) -> GitResult<Option<String>> {
         Self::ensure_repo(path)?;
+        backend().config_get(path, key)
+    }
 
-        let mut args = vec!["pull"];
-        if let Some(r) = remote {
-            args.push(r);
-        }
+    /// Set git config value
+    pub fn config_set(path: &Path, key: &str, value: &str) -> GitResult<()> {
+        Self::ensure_repo(path)?;
 
         let output = Command::new("git")
-            .args(&args)
+            .args(["config", key, value])
             .current_dir(path)
             .output()?;
 
@@ -467,152 +1171,199 @@ impl Git {
         Ok(())
     }
 
-    /// Checkout a branch
-    pub fn checkout(path: &Path, branch: &str) -> GitResult<()> {
+    /// Stage a file for commit
+    pub fn add(path: &Path, file: &str) -> GitResult<()> {
         Self::ensure_repo(path)?;
 
         let output = Command::new("git")
-            .args(["checkout", branch])
+            .args(["add", file])
             .current_dir(path)
             .output()?;
 
         if !output.status.success() {
             return Err(GitError::CommandFailed {
-                message: String::from_utf8_lossy(&output.stderr).to_string(),
+                message: command_error_message(&output),
             });
         }
 
         Ok(())
     }
 
-    /// Fetch from all remotes
-    pub fn fetch_all(path: &Path) -> GitResult<()> {
+    /// Create a commit with the given message
+    pub fn commit(path: &Path, message: &str) -> GitResult<()> {
         Self::ensure_repo(path)?;
 
         let output = Command::new("git")
-            .args(["fetch", "--all"])
+            .args(["commit", "-m", message])
             .current_dir(path)
             .output()?;
 
         if !output.status.success() {
             return Err(GitError::CommandFailed {
-                message: String::from_utf8_lossy(&output.stderr).to_string(),
+                message: command_error_message(&output),
             });
         }
 
         Ok(())
     }
 
-    /// Fetch from a specific remote
-    pub fn fetch(path: &Path, remote: &str) -> GitResult<()> {
+    /// Subject line of the commit `rev` (branch, tag or SHA) points at
+    pub fn commit_subject(path: &Path, rev: &str) -> GitResult<String> {
         Self::ensure_repo(path)?;
 
         let output = Command::new("git")
-            .args(["fetch", remote])
+            .args(["log", "-1", "--format=%s", rev, "--"])
             .current_dir(path)
             .output()?;
 
         if !output.status.success() {
             return Err(GitError::CommandFailed {
-                message: String::from_utf8_lossy(&output.stderr).to_string(),
+                message: command_error_message(&output),
             });
         }
 
-        Ok(())
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
 
-    /// Get ahead/behind count for a specific remote
-    pub fn ahead_behind(path: &Path, remote: &str, branch: &str) -> GitResult<(u32, u32)> {
+    /// Committer time of HEAD (unix seconds); `None` before the first commit
+    pub fn last_commit_time(path: &Path) -> GitResult<Option<i64>> {
         Self::ensure_repo(path)?;
 
-        let upstream = format!("{remote}/{branch}");
         let output = Command::new("git")
-            .args(["rev-list", "--left-right", "--count", &format!("{upstream}...HEAD")])
+            .args(["log", "-1", "--format=%ct"])
             .current_dir(path)
             .output()?;
 
+        // `git log` fails on an unborn branch
         if !output.status.success() {
-            // Remote branch might not exist yet
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            if stderr.contains("unknown revision") {
-                return Ok((0, 0));
-            }
-            return Err(GitError::CommandFailed {
-                message: stderr.to_string(),
-            });
-        }
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let parts: Vec<&str> = stdout.split_whitespace().collect();
-        if parts.len() != 2 {
-            return Ok((0, 0));
+            return Ok(None);
         }
 
-        let behind = parts[0].parse().unwrap_or(0);
-        let ahead = parts[1].parse().unwrap_or(0);
-
-        Ok((ahead, behind))
+        Ok(String::from_utf8_lossy(&output.stdout).trim().parse().ok())
     }
 
-    /// Get git config value
-    pub fn config_get(path: &Path, key: &str) -> GitResult<Option<String>> {
+    /// Number of stash entries
+    pub fn stash_count(path: &Path) -> GitResult<usize> {
         Self::ensure_repo(path)?;
 
         let output = Command::new("git")
-            .args(["config", "--get", key])
+            .args(["stash", "list"])
             .current_dir(path)
             .output()?;
 
         if !output.status.success() {
-            // Key not found is not an error
-            return Ok(None);
+            return Err(GitError::CommandFailed {
+                message: command_error_message(&output),
+            });
         }
 
-        Ok(Some(String::from_utf8_lossy(&output.stdout).trim().to_string()))
+        Ok(String::from_utf8_lossy(&output.stdout).lines().count())
     }
 
-    /// Set git config value
-    pub fn config_set(path: &Path, key: &str, value: &str) -> GitResult<()> {
+    /// Full SHA of HEAD
+    pub fn head_commit(path: &Path) -> GitResult<String> {
+        Self::ensure_repo(path)?;
+        backend().head_commit(path)
+    }
+
+    /// Full SHA of the commit `rev` (tag, branch or SHA) names; `None` if it
+    /// doesn't resolve to a commit
+    pub fn resolve_commit(path: &Path, rev: &str) -> GitResult<Option<String>> {
+        Self::ensure_repo(path)?;
+        backend().resolve_commit(path, rev)
+    }
+
+    /// Local branches with their configured upstreams, sorted by name
+    pub fn local_branches(path: &Path) -> GitResult<Vec<LocalBranch>> {
+        let lines = Self::for_each_ref(
+            path,
+            "%(refname:short)%09%(upstream:remotename)%09%(upstream:remoteref)",
+            "refs/heads",
+        )?;
+
+        Ok(lines
+            .iter()
+            .map(|line| {
+                let mut fields = line.split('\t');
+                let name = fields.next().unwrap_or_default().to_string();
+                let mut field = || fields.next().filter(|f| !f.is_empty());
+                LocalBranch {
+                    name,
+                    upstream_remote: field().map(str::to_string),
+                    upstream_branch: field()
+                        .map(|r| r.strip_prefix("refs/heads/").unwrap_or(r).to_string()),
+                }
+            })
+            .collect())
+    }
+
+    /// Remote-tracking branches as `<remote>/<branch>`, without `<remote>/HEAD`
+    pub fn remote_branches(path: &Path) -> GitResult<Vec<String>> {
+        let lines = Self::for_each_ref(path, "%(refname)%09%(symref)", "refs/remotes")?;
+
+        Ok(lines
+            .iter()
+            .filter_map(|line| {
+                let (refname, symref) = line.split_once('\t').unwrap_or((line, ""));
+                symref.is_empty().then(|| refname.trim_start_matches("refs/remotes/").to_string())
+            })
+            .collect())
+    }
+
+    fn for_each_ref(path: &Path, format: &str, prefix: &str) -> GitResult<Vec<String>> {
         Self::ensure_repo(path)?;
 
         let output = Command::new("git")
-            .args(["config", key, value])
+            .args(["for-each-ref", &format!("--format={format}"), prefix])
             .current_dir(path)
             .output()?;
 
         if !output.status.success() {
             return Err(GitError::CommandFailed {
-                message: String::from_utf8_lossy(&output.stderr).to_string(),
+                message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
             });
         }
 
-        Ok(())
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect())
     }
 
-    /// Stage a file for commit
-    pub fn add(path: &Path, file: &str) -> GitResult<()> {
+    /// Make `branch` track `remote_branch` on `remote`
+    pub fn set_upstream(path: &Path, branch: &str, remote: &str, remote_branch: &str) -> GitResult<()> {
         Self::ensure_repo(path)?;
 
         let output = Command::new("git")
-            .args(["add", file])
+            .args(["branch", &format!("--set-upstream-to={remote}/{remote_branch}"), branch])
             .current_dir(path)
             .output()?;
 
         if !output.status.success() {
             return Err(GitError::CommandFailed {
-                message: command_error_message(&output),
+                message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
             });
         }
 
         Ok(())
     }
 
-    /// Create a commit with the given message
-    pub fn commit(path: &Path, message: &str) -> GitResult<()> {
+    /// Put HEAD back where `status` found it: on its branch, or detached at
+    /// the same commit
+    pub fn restore_head(path: &Path, status: &RepoStatus) -> GitResult<()> {
+        match (&status.head_oid, status.detached) {
+            (Some(oid), true) => Self::checkout_detached(path, oid),
+            _ => Self::checkout(path, &status.branch),
+        }
+    }
+
+    /// Check out `rev` with a detached HEAD
+    pub fn checkout_detached(path: &Path, rev: &str) -> GitResult<()> {
         Self::ensure_repo(path)?;
 
         let output = Command::new("git")
-            .args(["commit", "-m", message])
+            .args(["checkout", "--quiet", "--detach", rev])
             .current_dir(path)
             .output()?;
 
@@ -627,14 +1378,7 @@ impl Git {
 
     /// Check if a tag exists in the repo.
     pub fn tag_exists(path: &Path, tag: &str) -> bool {
-        Command::new("git")
-            .args(["rev-parse", "--verify", &format!("refs/tags/{tag}")])
-            .current_dir(path)
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status()
-            .map(|s| s.success())
-            .unwrap_or(false)
+        backend().tag_exists(path, tag)
     }
 
     /// Count commits since a tag.
@@ -659,6 +1403,25 @@ impl Git {
             })
     }
 
+    /// First-parent merge commits on `branch` (most recent first).
+    pub fn merge_commits(path: &Path, branch: &str) -> GitResult<Vec<String>> {
+        let output = Command::new("git")
+            .args(["rev-list", "--merges", "--first-parent", branch])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
     /// Count commits since a tag that touch any of the given files.
     ///
     /// More precise than `commits_since_tag` — only counts commits where
@@ -695,6 +1458,111 @@ impl Git {
             })
     }
 
+    /// Files that differ between `since` and the working tree, relative to the
+    /// repo root. Includes uncommitted changes.
+    pub fn changed_files(path: &Path, since: &str) -> GitResult<Vec<String>> {
+        let output = Command::new("git")
+            .args(["diff", "--name-only", since])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Commit graph relationship between `local` and `remote_ref`: their
+    /// merge-base, counts each side, and up to `limit` commits each side
+    /// with subjects. `Ok(None)` when `remote_ref` doesn't exist (never
+    /// pushed or not fetched).
+    pub fn divergence(
+        path: &Path,
+        local: &str,
+        remote_ref: &str,
+        limit: usize,
+    ) -> GitResult<Option<Divergence>> {
+        Self::ensure_repo(path)?;
+
+        let exists = Command::new("git")
+            .args(["rev-parse", "--verify", "--quiet", &format!("{remote_ref}^{{commit}}")])
+            .current_dir(path)
+            .stdout(std::process::Stdio::null())
+            .status()?
+            .success();
+        if !exists {
+            return Ok(None);
+        }
+
+        // Exit 1 without output means no common ancestor
+        let output = Command::new("git")
+            .args(["merge-base", local, remote_ref])
+            .current_dir(path)
+            .output()?;
+        let merge_base = output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+        let output = Command::new("git")
+            .args(["rev-list", "--left-right", "--count", &format!("{remote_ref}...{local}")])
+            .current_dir(path)
+            .output()?;
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: command_error_message(&output),
+            });
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut counts = stdout.split_whitespace().map(|n| n.parse().unwrap_or(0));
+        let behind = counts.next().unwrap_or(0);
+        let ahead = counts.next().unwrap_or(0);
+
+        Ok(Some(Divergence {
+            merge_base,
+            ahead,
+            behind,
+            local_only: Self::commits_between(path, remote_ref, local, limit)?,
+            remote_only: Self::commits_between(path, local, remote_ref, limit)?,
+        }))
+    }
+
+    /// Up to `limit` commits reachable from `to` but not `from`, newest first
+    fn commits_between(path: &Path, from: &str, to: &str, limit: usize) -> GitResult<Vec<CommitSummary>> {
+        let output = Command::new("git")
+            .args([
+                "log",
+                "--format=%H%x1f%s",
+                &format!("--max-count={limit}"),
+                &format!("{from}..{to}"),
+            ])
+            .current_dir(path)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(GitError::CommandFailed {
+                message: command_error_message(&output),
+            });
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (oid, subject) = line.split_once('\x1f')?;
+                Some(CommitSummary {
+                    oid: oid.to_string(),
+                    subject: subject.to_string(),
+                })
+            })
+            .collect())
+    }
+
     /// Create an annotated or lightweight tag
     pub fn tag(path: &Path, name: &str, message: Option<&str>) -> GitResult<()> {
         Self::ensure_repo(path)?;
@@ -732,7 +1600,7 @@ impl Git {
     }
 
     fn parse_remotes(output: &str) -> Vec<RemoteInfo> {
-        let mut remotes: HashMap<String, (Option<String>, Option<String>)> = HashMap::new();
+        let mut remotes: HashMap<String, (Option<String>, Vec<String>)> = HashMap::new();
 
         for line in output.lines() {
             let parts: Vec<&str> = line.split_whitespace().collect();
@@ -744,23 +1612,24 @@ impl Git {
             let url = parts[1].to_string();
             let kind = parts[2]; // (fetch) or (push)
 
-            let entry = remotes.entry(name).or_insert((None, None));
+            let entry = remotes.entry(name).or_insert((None, Vec::new()));
             if kind.contains("fetch") {
                 entry.0 = Some(url);
             } else if kind.contains("push") {
-                entry.1 = Some(url);
+                entry.1.push(url);
             }
         }
 
         remotes
             .into_iter()
-            .filter_map(|(name, (fetch, push))| {
+            .filter_map(|(name, (fetch, mut push))| {
                 let fetch_url = fetch?;
-                let push_url = push.unwrap_or_else(|| fetch_url.clone());
+                let push_url = if push.is_empty() { fetch_url.clone() } else { push.remove(0) };
                 Some(RemoteInfo {
                     name,
                     fetch_url,
                     push_url,
+                    extra_push_urls: push,
                 })
             })
             .collect()
@@ -861,7 +1730,8 @@ pub fn force_push_with_retry(path: &Path, remote: &str, branch: &str) -> GitResu
 }
 
 /// Git transport for remote URLs.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Transport {
     Ssh,
     Https,
@@ -882,6 +1752,12 @@ pub fn build_remote_url(forge: &str, org: &str, repo: &str) -> String {
     build_remote_url_with(forge, org, repo, Transport::from_env())
 }
 
+/// Build a git remote URL for a forge, on `host` when it's a self-hosted
+/// instance (e.g. GitLab at `gitlab.mycompany.com`).
+pub fn build_remote_url_on(forge: &str, host: Option<&str>, org: &str, repo: &str) -> String {
+    build_remote_url(host.unwrap_or(forge), org, repo)
+}
+
 /// Build a git remote URL for a forge with an explicit transport.
 pub fn build_remote_url_with(forge: &str, org: &str, repo: &str, transport: Transport) -> String {
     let host = match forge.to_lowercase().as_str() {
@@ -954,12 +1830,70 @@ pub fn parse_remote_url(url: &str) -> Option<(String, String, String)> {
     None
 }
 
+/// `url` pointing at `new_org` instead of `org`, transport and host kept;
+/// `None` when `url` isn't a forge URL owned by `org`
+pub fn retarget_remote_url(url: &str, org: &str, new_org: &str) -> Option<String> {
+    let (_, owner, _) = parse_remote_url(url)?;
+    if owner != org {
+        return None;
+    }
+    // The owner follows the `:` of an SSH URL, or the host of an HTTPS one
+    let separator = if url.starts_with("git@") { ':' } else { '/' };
+    let needle = format!("{separator}{org}/");
+    let start = if separator == ':' {
+        url.find(&needle)?
+    } else {
+        let host_end = url.find("://")? + 3;
+        host_end + url[host_end..].find(&needle)?
+    };
+    Some(format!("{}{separator}{new_org}/{}", &url[..start], &url[start + needle.len()..]))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_wire_fork_remotes() {
+        let temp = TempDir::new().unwrap();
+        Git::init(temp.path()).unwrap();
+        Git::add_remote(temp.path(), "origin", "git@github.com:acme/tool.git").unwrap();
+
+        Git::wire_fork_remotes(
+            temp.path(),
+            "git@github.com:alice/tool.git",
+            "git@github.com:acme/tool.git",
+        )
+        .unwrap();
+
+        let origin = Git::get_remote(temp.path(), "origin").unwrap();
+        let upstream = Git::get_remote(temp.path(), "upstream").unwrap();
+        assert_eq!(origin.fetch_url, "git@github.com:alice/tool.git");
+        assert_eq!(upstream.fetch_url, "git@github.com:acme/tool.git");
+    }
+
+    #[test]
+    fn test_commit_subject_of_other_branch() {
+        let temp = TempDir::new().unwrap();
+        let p = temp.path();
+        for args in [
+            &["init", "-q", "-b", "main"][..],
+            &["config", "user.email", "test@example.com"],
+            &["config", "user.name", "Test"],
+            &["commit", "-q", "--allow-empty", "-m", "on main"],
+            &["checkout", "-q", "-b", "fix"],
+            &["commit", "-q", "--allow-empty", "-m", "the fix"],
+            &["checkout", "-q", "main"],
+        ] {
+            assert!(Command::new("git").args(args).current_dir(p).status().unwrap().success());
+        }
+
+        assert_eq!(Git::commit_subject(p, "fix").unwrap(), "the fix");
+        assert_eq!(Git::commit_subject(p, "HEAD").unwrap(), "on main");
+    }
+
     #[test]
     fn test_build_remote_url_ssh() {
         assert_eq!(
@@ -1016,6 +1950,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_retarget_remote_url() {
+        assert_eq!(
+            retarget_remote_url("git@github.com:alice/my-repo.git", "alice", "acme").as_deref(),
+            Some("git@github.com:acme/my-repo.git")
+        );
+        assert_eq!(
+            retarget_remote_url("https://gitlab.example.com/alice/alice.git", "alice", "acme").as_deref(),
+            Some("https://gitlab.example.com/acme/alice.git")
+        );
+        assert_eq!(retarget_remote_url("git@github.com:bob/my-repo.git", "alice", "acme"), None);
+        assert_eq!(retarget_remote_url("/srv/git/alice/repo", "alice", "acme"), None);
+    }
+
     #[test]
     fn test_parse_remotes() {
         let output = "origin\tgit@github.com:alice/repo.git (fetch)\n\
@@ -1028,6 +1976,20 @@ mod tests {
 
         let origin = remotes.iter().find(|r| r.name == "origin").unwrap();
         assert_eq!(origin.fetch_url, "git@github.com:alice/repo.git");
+        assert!(origin.extra_push_urls.is_empty());
+    }
+
+    #[test]
+    fn test_parse_remotes_multiple_push_urls() {
+        let output = "origin\tgit@github.com:alice/repo.git (fetch)\n\
+                      origin\tgit@github.com:alice/repo.git (push)\n\
+                      origin\tgit@codeberg.org:alice/repo.git (push)";
+
+        let remotes = Git::parse_remotes(output);
+        assert_eq!(
+            remotes[0].push_urls(),
+            vec!["git@github.com:alice/repo.git", "git@codeberg.org:alice/repo.git"]
+        );
     }
 
     #[test]
@@ -1061,6 +2023,185 @@ mod tests {
         assert_eq!(status.upstream, None);
     }
 
+    #[test]
+    fn test_parse_status_v2() {
+        let output = "\
+# branch.oid 1234567890abcdef1234567890abcdef12345678
+# branch.head main
+# branch.upstream origin/main
+# branch.ab +2 -1
+1 M. N... 100644 100644 100644 aaa bbb src/lib.rs
+1 .D N... 100644 100644 000000 aaa aaa old.rs
+? notes.txt
+";
+        let status = Git::parse_status_v2(output);
+        assert_eq!(status.branch, "main");
+        assert_eq!(status.head_oid.as_deref(), Some("1234567890abcdef1234567890abcdef12345678"));
+        let tracking = status.tracking.unwrap();
+        assert_eq!(tracking.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((tracking.ahead, tracking.behind), (2, 1));
+        assert!(status.has_staged && status.has_changes && status.has_untracked);
+        assert!(!status.upstream_gone);
+        assert_eq!(status.files.modified, vec!["src/lib.rs"]);
+        assert_eq!(status.files.deleted, vec!["old.rs"]);
+        assert_eq!(status.files.untracked, vec!["notes.txt"]);
+    }
+
+    #[test]
+    fn test_parse_status_v2_files() {
+        let output = "\
+# branch.head main
+1 A. N... 000000 100644 100644 000 aaa docs/new file.md
+2 R. N... 100644 100644 100644 aaa aaa R100 lib/renamed.rs\tlib/orig.rs
+? a.txt
+? b.txt
+? c.txt
+";
+        let mut files = Git::parse_status_v2(output).files;
+        assert_eq!(files.added, vec!["docs/new file.md"]);
+        assert_eq!(files.modified, vec!["lib/renamed.rs"]);
+        assert_eq!(files.total(), 5);
+        assert_eq!(files.truncate(2), 1);
+        assert_eq!(files.untracked, vec!["a.txt", "b.txt"]);
+    }
+
+    #[test]
+    fn test_parse_status_v2_gone_detached_and_initial() {
+        let gone = Git::parse_status_v2("# branch.oid abc\n# branch.head feature\n# branch.upstream origin/feature\n");
+        assert!(gone.upstream_gone);
+        assert!(!gone.has_changes && !gone.has_staged && !gone.has_untracked);
+
+        let detached = Git::parse_status_v2("# branch.oid abc\n# branch.head (detached)\n");
+        assert_eq!(detached.branch, "");
+        assert!(detached.detached);
+        assert!(detached.tracking.is_none());
+        assert_eq!(detached.blocking_state().as_deref(), Some("detached HEAD"));
+
+        let initial = Git::parse_status_v2("# branch.oid (initial)\n# branch.head main\n");
+        assert_eq!(initial.head_oid, None);
+        assert_eq!(initial.tracking.unwrap().upstream, None);
+    }
+
+    #[test]
+    fn test_parse_status_v2_conflicts() {
+        let output = "\
+# branch.oid abc
+# branch.head main
+u UU N... 100644 100644 100644 100644 aaa bbb ccc src/lib.rs
+u AA N... 000000 100644 100644 100644 000 bbb ccc new.rs
+";
+        let mut status = Git::parse_status_v2(output);
+        assert_eq!(status.conflicts, 2);
+        assert!(!status.detached);
+        status.in_progress = Some(InProgressOp::Merge);
+        assert_eq!(
+            status.blocking_state().as_deref(),
+            Some("merge in progress, 2 conflicted files")
+        );
+        assert_eq!(Git::parse_status_v2("# branch.head main\n").blocking_state(), None);
+    }
+
+    #[test]
+    fn test_repo_status_detects_merge_in_progress() {
+        let temp = TempDir::new().unwrap();
+        Git::init(temp.path()).unwrap();
+        Git::config_set(temp.path(), "user.email", "test@test.com").unwrap();
+        Git::config_set(temp.path(), "user.name", "Test").unwrap();
+        fs::write(temp.path().join("a.txt"), "a").unwrap();
+        Git::add(temp.path(), "a.txt").unwrap();
+        Git::commit(temp.path(), "initial").unwrap();
+
+        let status = Git::repo_status(temp.path()).unwrap();
+        assert!(status.head_oid.is_some());
+        assert_eq!(status.in_progress, None);
+
+        fs::write(temp.path().join(".git/MERGE_HEAD"), status.head_oid.unwrap()).unwrap();
+        assert_eq!(Git::repo_status(temp.path()).unwrap().in_progress, Some(InProgressOp::Merge));
+
+        fs::remove_file(temp.path().join(".git/MERGE_HEAD")).unwrap();
+        fs::write(temp.path().join(".git/BISECT_LOG"), "").unwrap();
+        assert_eq!(Git::repo_status(temp.path()).unwrap().in_progress, Some(InProgressOp::Bisect));
+    }
+
+    #[test]
+    fn test_divergence_against_remote_tracking_ref() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path();
+        Git::init(path).unwrap();
+        Git::config_set(path, "user.email", "test@test.com").unwrap();
+        Git::config_set(path, "user.name", "Test").unwrap();
+        let commit = |file: &str, message: &str| {
+            fs::write(path.join(file), message).unwrap();
+            Git::add(path, file).unwrap();
+            Git::commit(path, message).unwrap();
+        };
+
+        commit("a.txt", "base");
+        let base = Git::head_commit(path).unwrap();
+        assert_eq!(Git::divergence(path, "HEAD", "refs/remotes/codeberg/main", 10).unwrap(), None);
+
+        // Simulate a remote that moved on separately from the local branch
+        let run = |args: &[&str]| {
+            assert!(Command::new("git").args(args).current_dir(path).output().unwrap().status.success());
+        };
+        run(&["checkout", "-q", "-b", "remote-side"]);
+        commit("b.txt", "remote change");
+        run(&["update-ref", "refs/remotes/codeberg/main", "HEAD"]);
+        run(&["checkout", "-q", "-"]);
+        commit("c.txt", "local one");
+        commit("d.txt", "local two");
+
+        let div = Git::divergence(path, "HEAD", "refs/remotes/codeberg/main", 1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(div.merge_base.as_deref(), Some(base.as_str()));
+        assert_eq!((div.ahead, div.behind), (2, 1));
+        let subjects: Vec<&str> = div.local_only.iter().map(|c| c.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["local two"]);
+        assert_eq!(div.remote_only[0].subject, "remote change");
+    }
+
+    #[test]
+    fn test_shallow_partial_clone_and_unshallow() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source");
+        fs::create_dir(&source).unwrap();
+        Git::init(&source).unwrap();
+        Git::config_set(&source, "user.email", "test@test.com").unwrap();
+        Git::config_set(&source, "user.name", "Test").unwrap();
+        Git::config_set(&source, "uploadpack.allowFilter", "true").unwrap();
+        for n in 0..3 {
+            fs::write(source.join("file.txt"), n.to_string()).unwrap();
+            Git::add(&source, "file.txt").unwrap();
+            Git::commit(&source, &format!("commit {n}")).unwrap();
+        }
+        let run = |args: &[&str]| {
+            assert!(Command::new("git").args(args).current_dir(&source).output().unwrap().status.success());
+        };
+        run(&["branch", "feature"]);
+
+        let options = CloneOptions { depth: Some(1), filter: Some("blobless".parse().unwrap()), single_branch: true };
+        assert_eq!(options.args(), vec!["--depth=1", "--filter=blob:none", "--single-branch"]);
+        assert_eq!(CloneOptions { depth: Some(5), ..CloneOptions::default() }.args(), vec!["--depth=5", "--no-single-branch"]);
+        assert!("sparse".parse::<CloneFilter>().is_err());
+
+        // Shallow and partial clones need a real transport, not a local path
+        let url = format!("file://{}", source.display());
+        let clone = temp.path().join("clone");
+        Git::clone(&url, &clone.display().to_string(), &options).unwrap();
+        let before = Git::clone_completeness(&clone, "origin").unwrap();
+        assert_eq!(
+            before,
+            CloneCompleteness { shallow: true, filter: Some("blob:none".to_string()), single_branch: true }
+        );
+
+        assert_eq!(Git::unshallow(&clone, "origin").unwrap(), before);
+        assert!(Git::clone_completeness(&clone, "origin").unwrap().is_full());
+        assert_eq!(Git::remote_branches(&clone).unwrap().len(), 2, "feature is fetched too");
+        let count = Command::new("git").args(["rev-list", "--count", "HEAD"]).current_dir(&clone).output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&count.stdout).trim(), "3");
+    }
+
     #[test]
     fn test_is_repo_false() {
         let temp = TempDir::new().unwrap();
@@ -1196,4 +2337,29 @@ mod tests {
         assert!(tags.contains("v0.1.0"));
         assert!(tags.contains("my-crate-v0.1.0"));
     }
+
+    #[test]
+    fn test_resolve_commit_and_checkout_detached() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path();
+        Git::init(path).unwrap();
+        Git::config_set(path, "user.email", "test@test.com").unwrap();
+        Git::config_set(path, "user.name", "Test").unwrap();
+        fs::write(path.join("a.txt"), "one").unwrap();
+        Git::add(path, "a.txt").unwrap();
+        Git::commit(path, "one").unwrap();
+        let first = Git::head_commit(path).unwrap();
+        Git::tag(path, "v1.0.0", Some("first")).unwrap();
+        fs::write(path.join("a.txt"), "two").unwrap();
+        Git::add(path, "a.txt").unwrap();
+        Git::commit(path, "two").unwrap();
+
+        // Annotated tags peel to their commit
+        assert_eq!(Git::resolve_commit(path, "v1.0.0").unwrap(), Some(first.clone()));
+        assert_eq!(Git::resolve_commit(path, "v9.9.9").unwrap(), None);
+
+        Git::checkout_detached(path, "v1.0.0").unwrap();
+        assert_eq!(Git::head_commit(path).unwrap(), first);
+        assert!(Git::repo_status(path).unwrap().detached);
+    }
 }
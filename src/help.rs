@@ -0,0 +1,171 @@
+//! Method catalogs for interactive clients
+//!
+//! Every hub has a `help` method that reads the hub's own plexus schema (the
+//! `description` and `params(...)` given to `#[plexus_macros::method]`) and
+//! streams one entry per method: what it does, each parameter with its type
+//! and whether it's required, and a `synapse` command line to start from.
+//! TUIs, MCP clients and REPLs can present usage from it directly, and it
+//! can't drift from the code because it is the code's own metadata.
+
+use plexus_core::plexus::{MethodRole, MethodSchema, PluginSchema};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+
+/// One parameter of a method
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ParamHelp {
+    pub name: String,
+    /// JSON type: `string`, `boolean`, `integer`, `string[]`, ...
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub required: bool,
+    pub description: String,
+}
+
+/// Usage of one method
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodHelp {
+    pub method: String,
+    pub description: String,
+    /// Required parameters first (in declaration order), then optional ones by name
+    pub params: Vec<ParamHelp>,
+    /// The method invoked with a placeholder for each required parameter
+    pub example: String,
+}
+
+impl MethodHelp {
+    pub fn into_event(self, hub: &str) -> HyperforgeEvent {
+        HyperforgeEvent::MethodHelp {
+            hub: hub.to_string(),
+            method: self.method,
+            description: self.description,
+            params: self.params,
+            example: self.example,
+        }
+    }
+}
+
+/// Usage of every method in `schema`, or only of `method`. `invocation` is
+/// the command line that reaches the hub, e.g. `synapse substrate hyperforge
+/// workspace`.
+pub fn catalog(schema: &PluginSchema, invocation: &str, method: Option<&str>) -> Result<Vec<MethodHelp>, String> {
+    // Child hubs have catalogs of their own
+    let rpc: Vec<&MethodSchema> = schema.methods.iter().filter(|m| matches!(m.role, MethodRole::Rpc)).collect();
+    let methods: Vec<&MethodSchema> = rpc.iter().copied().filter(|m| method.is_none_or(|name| m.name == name)).collect();
+    if let (Some(name), true) = (method, methods.is_empty()) {
+        let known: Vec<&str> = rpc.iter().map(|m| m.name.as_str()).collect();
+        return Err(format!("No method '{name}' on {}. Methods: {}", schema.namespace, known.join(", ")));
+    }
+    Ok(methods.into_iter().map(|m| describe(m, invocation)).collect())
+}
+
+/// [`catalog`] as `method_help` events, or the validation error for an
+/// unknown method
+pub fn events(schema: &PluginSchema, invocation: &str, method: Option<&str>) -> Vec<HyperforgeEvent> {
+    match catalog(schema, invocation, method) {
+        Ok(entries) => entries.into_iter().map(|entry| entry.into_event(&schema.namespace)).collect(),
+        Err(e) => vec![HyperforgeEvent::error(HyperforgeError::Validation, e)],
+    }
+}
+
+fn describe(method: &MethodSchema, invocation: &str) -> MethodHelp {
+    let params = method
+        .params
+        .as_ref()
+        .and_then(|schema| serde_json::to_value(schema).ok())
+        .map(|schema| params(&schema))
+        .unwrap_or_default();
+    let mut example = format!("{invocation} {}", method.name);
+    for param in params.iter().filter(|p| p.required) {
+        example.push_str(&format!(" --{0} <{0}>", param.name));
+    }
+    MethodHelp {
+        method: method.name.clone(),
+        description: method.description.clone(),
+        params,
+        example,
+    }
+}
+
+fn params(schema: &Value) -> Vec<ParamHelp> {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let param = |name: &str, property: &Value| ParamHelp {
+        name: name.to_string(),
+        ty: type_name(property),
+        required: required.contains(&name),
+        description: property.get("description").and_then(Value::as_str).unwrap_or_default().to_string(),
+    };
+    let mut optional: Vec<ParamHelp> = properties
+        .iter()
+        .filter(|(name, _)| !required.contains(&name.as_str()))
+        .map(|(name, property)| param(name, property))
+        .collect();
+    optional.sort_by(|a, b| a.name.cmp(&b.name));
+
+    required
+        .iter()
+        .filter_map(|name| Some(param(name, properties.get(*name)?)))
+        .chain(optional)
+        .collect()
+}
+
+/// The non-null JSON type of a property, `<item>[]` for arrays
+fn type_name(property: &Value) -> String {
+    let ty = match property.get("type") {
+        Some(Value::String(ty)) => Some(ty.as_str()),
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).find(|ty| *ty != "null"),
+        _ => None,
+    };
+    match ty {
+        Some("array") => format!("{}[]", property.get("items").map_or_else(|| "value".to_string(), type_name)),
+        Some(ty) => ty.to_string(),
+        None => "value".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_params_and_example() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "path": { "description": "Path to workspace directory", "type": "string" },
+                "fetch": { "description": "Fetch first", "type": ["boolean", "null"], "default": null },
+                "exclude": { "type": ["array", "null"], "items": { "type": "string" } },
+                "org": { "type": "string" }
+            },
+            "required": ["path", "org"]
+        });
+        let params = params(&schema);
+        let names: Vec<&str> = params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["path", "org", "exclude", "fetch"]);
+        assert!(params[0].required && !params[3].required);
+        assert_eq!(params[0].description, "Path to workspace directory");
+        assert_eq!(params[2].ty, "string[]");
+        assert_eq!(params[3].ty, "boolean");
+
+        let method: MethodSchema = serde_json::from_value(json!({
+            "name": "status",
+            "description": "Report git status",
+            "hash": "",
+            "params": schema,
+        }))
+        .unwrap();
+        let help = describe(&method, "synapse substrate hyperforge workspace");
+        assert_eq!(help.example, "synapse substrate hyperforge workspace status --path <path> --org <org>");
+    }
+}
@@ -5,12 +5,13 @@
 //! - workspace: Multi-repo workspace orchestration
 
 use async_stream::stream;
+use plexus_core::plexus::Activation;
 use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use chrono::Utc;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use crate::adapters::{ForgePort, ForgeSyncState};
 use crate::auth::credentials::{
@@ -21,13 +22,21 @@ use crate::auth::AuthProvider;
 use crate::auth::YamlAuthProvider;
 use crate::auth_hub::storage::YamlStorage;
 use crate::auth_hub::types::SecretPath;
+use crate::commands::gitconfig;
 use crate::commands::runner::discover_or_bail;
-use crate::config::{HyperforgeConfig, OrgConfig};
-use crate::hubs::utils::{make_adapter, RepoFilter};
-use crate::hubs::{BuildHub, HyperforgeState, RepoHub, WorkspaceHub};
+use crate::config::{HyperforgeConfig, OrgConfig, UserConfig};
+use crate::help::{self, ParamHelp};
+use crate::services::telemetry::{TelemetryState, REPORT_FIELDS};
+use crate::services::upgrade::{self, CURRENT_VERSION};
+use crate::hubs::utils::{etag_scope, make_adapter, RepoFilter};
+use crate::hubs::{BuildHub, HyperforgeState, RegistryHub, RepoHub, WorkspaceHub};
+use crate::messages::{Catalog, Msg};
+use crate::msg_error;
 use crate::types::config::DistChannel;
 use crate::types::repo::RepoRecord;
 use crate::types::Forge;
+use crate::output;
+use crate::scheduler::Scheduler;
 
 /// Package registry identifier
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
@@ -36,6 +45,9 @@ pub enum PackageRegistry {
     CratesIo,
     Hackage,
     Npm,
+    GitHubPackages,
+    GitLabPackages,
+    CodebergPackages,
 }
 
 impl std::fmt::Display for PackageRegistry {
@@ -44,6 +56,9 @@ impl std::fmt::Display for PackageRegistry {
             Self::CratesIo => write!(f, "crates.io"),
             Self::Hackage => write!(f, "hackage"),
             Self::Npm => write!(f, "npm"),
+            Self::GitHubPackages => write!(f, "github-packages"),
+            Self::GitLabPackages => write!(f, "gitlab-packages"),
+            Self::CodebergPackages => write!(f, "codeberg-packages"),
         }
     }
 }
@@ -82,6 +97,82 @@ pub enum PublishActionKind {
     Failed,
 }
 
+/// One open pull request in a [`HyperforgeEvent::PullRequestSummary`]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PullRequestInfo {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub head_branch: String,
+    pub base_branch: String,
+    pub draft: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_days: Option<i64>,
+    pub url: String,
+}
+
+/// One remote's position against the local branch in a
+/// [`HyperforgeEvent::WorkspaceRepoStatus`]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RemoteSyncInfo {
+    pub forge: String,
+    pub remote: String,
+    /// Whether the remote is configured in git
+    pub exists: bool,
+    pub ahead: u32,
+    pub behind: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Failure category carried by [`HyperforgeEvent::Error`]
+///
+/// Serialized as a stable `snake_case` code that automation can branch on and
+/// documentation can reference. Codes are never renamed or reused; new
+/// categories may be added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HyperforgeError {
+    /// `config`: workspace, org or repo configuration (or local state such
+    /// as `repos.yaml`) is missing, unreadable or inconsistent
+    Config,
+    /// `git`: a local git operation failed (status, fetch, push, clone, ...)
+    Git,
+    /// `forge_api`: a forge or registry API call failed or returned an
+    /// unexpected result
+    ForgeApi,
+    /// `auth`: credentials or SSH keys are missing, invalid or unreadable
+    Auth,
+    /// `validation`: invalid arguments, or a build/test/validation check failed
+    Validation,
+    /// `policy`: refused by a safety rule (protection, dismissal state,
+    /// thresholds, existing targets)
+    Policy,
+    /// `internal`: unexpected failure inside hyperforge (task panics, I/O)
+    Internal,
+}
+
+impl HyperforgeError {
+    /// The stable code this category serializes as
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::Config => "config",
+            Self::Git => "git",
+            Self::ForgeApi => "forge_api",
+            Self::Auth => "auth",
+            Self::Validation => "validation",
+            Self::Policy => "policy",
+            Self::Internal => "internal",
+        }
+    }
+}
+
+impl std::fmt::Display for HyperforgeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
 /// Hyperforge event types
 #[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -93,8 +184,15 @@ pub enum HyperforgeEvent {
     },
     /// General info message
     Info { message: String },
-    /// Error message
-    Error { message: String },
+    /// Error message with a stable failure category
+    Error {
+        code: HyperforgeError,
+        message: String,
+        /// What the error is about (repo, forge, path) when not already
+        /// obvious from the message
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        context: Option<String>,
+    },
     /// Repository information
     Repo {
         name: String,
@@ -109,7 +207,7 @@ pub enum HyperforgeEvent {
     /// Sync diff result - repo operation
     SyncOp {
         repo_name: String,
-        operation: String, // "create", "update", "delete", "in_sync"
+        operation: String, // "create", "update", "delete", "archive", "in_sync"
         forge: String,
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         details: Vec<String>,
@@ -121,6 +219,7 @@ pub enum HyperforgeEvent {
         to_create: usize,
         to_update: usize,
         to_delete: usize,
+        to_archive: usize,
         in_sync: usize,
     },
     /// Per-repo check result (branch + clean status)
@@ -132,6 +231,57 @@ pub enum HyperforgeEvent {
         is_clean: bool,
         on_correct_branch: bool,
     },
+    /// Per-repo git status (`workspace status`)
+    WorkspaceRepoStatus {
+        repo_name: String,
+        path: String,
+        branch: String,
+        remotes: Vec<RemoteSyncInfo>,
+        /// Changed, added, deleted and untracked paths
+        dirty_files: usize,
+        stashes: usize,
+        /// Seconds since the HEAD commit; absent before the first commit
+        #[serde(skip_serializing_if = "Option::is_none")]
+        last_commit_age_secs: Option<i64>,
+    },
+    /// Totals for `workspace status`; the name lists are sorted
+    WorkspaceStatusSummary {
+        total_repos: usize,
+        clean_repos: usize,
+        /// Repos with uncommitted or untracked files
+        dirty: Vec<String>,
+        /// Repos ahead of at least one remote
+        ahead: Vec<String>,
+        /// Repos behind at least one remote
+        behind: Vec<String>,
+        /// Repos with stash entries
+        stashed: Vec<String>,
+        /// Repos whose status couldn't be read
+        failed: Vec<String>,
+    },
+    /// A pinned repo's HEAD against its `[pins]` entry in workspace.toml
+    /// (`workspace check` and `workspace thaw`)
+    RepoPin {
+        repo_name: String,
+        pin: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        commit: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        head: Option<String>,
+        /// `in_sync`, `drifted`, `unresolved`, `checked_out` or `would_check_out`
+        status: String,
+    },
+    /// What makes a repo dirty (`workspace check --verbose`); each list is
+    /// capped, `omitted` counts the paths left out
+    RepoDirtyFiles {
+        repo_name: String,
+        modified: Vec<String>,
+        added: Vec<String>,
+        deleted: Vec<String>,
+        untracked: Vec<String>,
+        total: usize,
+        omitted: usize,
+    },
     /// Per-repo push result
     RepoPush {
         repo_name: String,
@@ -140,6 +290,62 @@ pub enum HyperforgeEvent {
         success: bool,
         error: Option<String>,
     },
+    /// Per-repo, per-forge pull result
+    RepoPull {
+        repo_name: String,
+        forge: String,
+        remote_name: String,
+        branch: String,
+        ahead: u32,
+        behind: u32,
+        status: String, // "up_to_date", "ahead", "behind", "diverged", "no_remote_branch", "unreachable"
+        fast_forwarded: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// A ref that differs between the origin forge and a mirror
+    MirrorRef {
+        repo_name: String,
+        forge: String,
+        ref_name: String,
+        change: String, // "create", "update", "delete"
+        #[serde(skip_serializing_if = "Option::is_none")]
+        old: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        new: Option<String>,
+        applied: bool,
+    },
+    /// A git remote changed (or would change) to match the repo's config.
+    /// `diff` holds `git remote -v` style `-`/`+` lines.
+    RemoteChanged {
+        repo_name: String,
+        remote: String,
+        action: String, // "add", "rename", "set_url", "remove"
+        #[serde(skip_serializing_if = "Option::is_none")]
+        renamed_from: Option<String>,
+        diff: Vec<String>,
+        applied: bool,
+    },
+    /// A local branch whose upstream is missing or points at the wrong
+    /// remote-tracking branch
+    UpstreamMismatch {
+        repo_name: String,
+        branch: String,
+        problem: String, // "missing", "gone", "wrong_branch", "wrong_remote"
+        #[serde(skip_serializing_if = "Option::is_none")]
+        current: Option<String>,
+        expected: String,
+        fixed: bool,
+    },
+    /// A configured repo found during workspace discovery, emitted as
+    /// soon as its manifests are parsed
+    RepoDiscovered {
+        repo_name: String,
+        org: Option<String>,
+        forges: Vec<String>,
+        build_system: String,
+        is_git_repo: bool,
+    },
     /// Workspace-level summary
     WorkspaceSummary {
         total_repos: usize,
@@ -159,6 +365,13 @@ pub enum HyperforgeEvent {
         file_path: String,
         action: String, // "created", "updated", "unchanged"
     },
+    /// A generated unify file was edited by hand and the new content wasn't written
+    UnifyConflict {
+        language: String,
+        file_path: String,
+        recorded_hash: String,
+        current_hash: String,
+    },
     /// Dependency version mismatch between pinned and local
     DepMismatch {
         repo: String,
@@ -166,6 +379,23 @@ pub enum HyperforgeEvent {
         pinned_version: String,
         local_version: String,
     },
+    /// Dependency graph rendered in a standard graph format
+    DepGraphExport {
+        format: String, // "dot", "mermaid", "json"
+        content: String,
+    },
+    /// A dependency cycle, as a closed path of package names
+    DepCycle {
+        cycle: Vec<String>,
+    },
+    /// Suggested edge to remove to break dependency cycles
+    CycleBreak {
+        from: String,
+        to: String,
+        edge_kind: String, // "dev", "optional", "normal"
+        cycles_broken: usize,
+        suggestion: String,
+    },
     /// Validation step result
     ValidateStep {
         repo_name: String,
@@ -181,6 +411,59 @@ pub enum HyperforgeEvent {
         skipped: usize,
         duration_ms: u64,
     },
+    /// A validation step that alternates between pass and fail across runs
+    FlakyStep {
+        repo_name: String,
+        step: String,
+        runs: usize,
+        flips: usize,
+        pass_rate_pct: u8,
+    },
+    /// A validation step whose recent passing runs are slower than earlier ones
+    DurationTrend {
+        repo_name: String,
+        step: String,
+        baseline_ms: u64,
+        recent_ms: u64,
+        change_pct: i64,
+    },
+    /// Line coverage of one repo from its validation coverage run
+    CoverageReport {
+        repo_name: String,
+        lines_found: u64,
+        lines_hit: u64,
+        line_pct: f64,
+        min_line_pct: Option<f64>,
+        passed: bool,
+    },
+    /// Workspace-wide line coverage across every reporting repo
+    CoverageSummary {
+        repos: usize,
+        lines_found: u64,
+        lines_hit: u64,
+        line_pct: f64,
+        min_line_pct: Option<f64>,
+        passed: bool,
+        /// Concatenated LCOV report
+        lcov_path: String,
+    },
+    /// Signature or provenance check of one release artifact
+    ArtifactVerify {
+        artifact: String,
+        check: String,
+        success: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// One benchmark compared against the repo's previous baseline
+    BenchResult {
+        repo_name: String,
+        bench: String,
+        current_ns: f64,
+        baseline_ns: Option<f64>,
+        change_pct: Option<f64>,
+        regressed: bool,
+    },
     /// Per-repo move step result
     RepoMove {
         repo_name: String,
@@ -256,6 +539,23 @@ pub enum HyperforgeEvent {
         has_untracked: bool,
         branch: String,
     },
+    /// Commit graph relationship between the local branch and one forge remote
+    RepoDivergence {
+        forge: String,
+        remote_name: String,
+        branch: String,
+        /// `None` when the histories share no commit
+        merge_base: Option<String>,
+        ahead: usize,
+        behind: usize,
+    },
+    /// A commit present on only one side of a divergence (`side`: "local" or "remote")
+    DivergenceCommit {
+        forge: String,
+        side: String,
+        oid: String,
+        subject: String,
+    },
     /// Container image tag
     ImageTag {
         repo_name: String,
@@ -359,6 +659,391 @@ pub enum HyperforgeEvent {
         assets_uploaded: usize,
         failed: usize,
     },
+    /// One row of the CI matrix: a repo's default-branch CI state per forge
+    CiMatrixRow {
+        repo_name: String,
+        branch: String,
+        /// Forge → "success", "failure", "pending", "unknown" or "error"
+        states: std::collections::BTreeMap<String, String>,
+    },
+    /// An open dependency-update pull request found on a forge
+    UpdatePr {
+        repo_name: String,
+        forge: String,
+        number: u64,
+        title: String,
+        bot: String, // "renovate", "dependabot"
+        checks: String, // "success", "failure", "pending", "unknown"
+        #[serde(skip_serializing_if = "Option::is_none")]
+        age_days: Option<i64>,
+        url: String,
+        /// What triage did: `approved`, `merged`, `would_approve`, `would_merge` or `failed: ...`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        action: Option<String>,
+    },
+    /// How far behind a repo is on dependency updates
+    UpdateLag {
+        repo_name: String,
+        forge: String,
+        open_prs: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        oldest_days: Option<i64>,
+    },
+    /// Open pull requests (merge requests on GitLab) on one repo's forge,
+    /// oldest first
+    PullRequestSummary {
+        repo_name: String,
+        org: String,
+        forge: String,
+        open: usize,
+        drafts: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        oldest_days: Option<i64>,
+        pull_requests: Vec<PullRequestInfo>,
+    },
+    /// Where one repo stands in a change campaign
+    CampaignRepo {
+        campaign: String,
+        repo_name: String,
+        status: String, // "pending", "unchanged", "pr_open", "merged", "failed"
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// A pull request proposing a branch to one repo (managed change or fork contribution)
+    ChangePr {
+        repo_name: String,
+        forge: String,
+        branch: String,
+        /// Empty in a dry run
+        url: String,
+        action: String, // "opened", "would_open", "failed"
+    },
+    /// Result of syncing one managed or template file into a repo
+    ManagedFile {
+        repo_name: String,
+        file_path: String,
+        action: String, // "created", "updated", "merged", "conflict"
+    },
+    /// Result of generating a repo's CODEOWNERS file from the ownership map
+    CodeownersFile {
+        repo_name: String,
+        file_path: String,
+        action: String, // "created", "updated", "unchanged", "merged", "conflict"
+    },
+    /// Result of converging one forge approval rule from the ownership map
+    ApprovalRuleSync {
+        repo_name: String,
+        forge: String,
+        rule: String,
+        action: String, // "created", "updated", "deleted", "unchanged"
+    },
+    /// A branch's protection on a forge, checked against `[branch_protection]`
+    BranchProtectionSync {
+        repo_name: String,
+        forge: String,
+        branch: String,
+        action: String, // "updated", "unchanged"
+        /// Settings that differ(ed): `required_reviews`, `status_checks`, `force_push`
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        details: Vec<String>,
+    },
+    /// Result of converging one `[webhooks]` entry on a forge
+    WebhookSync {
+        repo_name: String,
+        forge: String,
+        hook: String,
+        url: String,
+        action: String, // "created", "replaced", "deleted", "unchanged"
+    },
+    /// Result of converging one `[ssh_deploy_keys]` entry on a forge
+    DeployKeySync {
+        repo_name: String,
+        forge: String,
+        key: String,
+        action: String, // "added", "replaced", "removed", "unchanged"
+    },
+    /// One effective repo config setting and where it comes from
+    ConfigSetting {
+        key: String,
+        /// Value as TOML
+        value: String,
+        source: String, // "repo", "workspace", "default"
+    },
+    /// A repo's visibility on a forge differs from its config
+    VisibilityFinding {
+        repo_name: String,
+        org: String,
+        forge: String,
+        expected: String,
+        actual: String,
+        severity: String, // "high" (private repo exposed publicly), "low"
+    },
+    /// A repo breaks its linear-history policy
+    LinearHistoryViolation {
+        repo_name: String,
+        /// Set for forge-side merge setting violations
+        #[serde(skip_serializing_if = "Option::is_none")]
+        forge: Option<String>,
+        check: String, // "merge_commits", "merge_settings"
+        detail: String,
+    },
+    /// A problem found in an outgoing commit by the push guard
+    PushGuardFinding {
+        repo_name: String,
+        kind: String, // "large_file", "secret"
+        commit: String,
+        path: String,
+        detail: String,
+    },
+    /// A repo refused by push/sync because it's mid-operation
+    RepoBlocked {
+        repo_name: String,
+        operation: String, // "push"
+        /// Human-readable summary, e.g. "rebase in progress, 2 conflicted files"
+        state: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        in_progress: Option<String>, // "merge", "rebase", "cherry-pick", "revert", "bisect"
+        conflicts: usize,
+        detached: bool,
+    },
+    /// Storage a forge attributes to one repo (bytes)
+    RepoUsage {
+        repo_name: String,
+        org: String,
+        forge: String,
+        repo_bytes: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        lfs_bytes: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        artifact_bytes: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        package_bytes: Option<u64>,
+        total_bytes: u64,
+        near_limit: bool,
+    },
+    /// Storage totals for one org on one forge (bytes)
+    UsageSummary {
+        org: String,
+        forge: String,
+        repos: usize,
+        repo_bytes: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        lfs_bytes: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        artifact_bytes: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        package_bytes: Option<u64>,
+        total_bytes: u64,
+    },
+    /// A fork whose upstream needs attention
+    ForkHealth {
+        repo_name: String,
+        forge: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        upstream: Option<String>,
+        upstream_status: String, // "archived", "deleted"
+        suggestion: String,
+    },
+    /// A configured repo exists on a forge its config doesn't declare
+    UndeclaredForgeRepo {
+        org: String,
+        repo_name: String,
+        forge: String,
+        declared_forges: Vec<String>,
+    },
+    /// A description rewritten to fit a forge's limits before create/update
+    DescriptionFitted {
+        repo_name: String,
+        forge: String,
+        changes: Vec<String>, // "control characters", "truncated"
+        /// The forge's description limit, in characters
+        limit: usize,
+        /// The description as sent to the forge
+        description: String,
+    },
+    /// A repo whose local and forge names differ only in case
+    NameCaseMismatch {
+        org: String,
+        forge: String,
+        local_name: String,
+        remote_name: String,
+        suggestion: String,
+    },
+    /// A configured repo that neither `LocalForge` nor its forges know
+    OrphanedRepo {
+        repo_name: String,
+        path: String,
+        org: String,
+        /// Repo name on the forges (config `repo_name`, else the directory name)
+        name: String,
+        forges: Vec<String>,
+        suggestions: Vec<String>,
+    },
+    /// An issue copied (or previewed) from one forge to another
+    IssueMigrated {
+        repo_name: String,
+        from: String,
+        to: String,
+        number: u64,
+        title: String,
+        state: String,   // "open", "closed"
+        outcome: String, // "created", "already_migrated"
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+        dry_run: bool,
+    },
+    /// A mutation skipped because the token can read but not administer
+    /// the repo on that forge
+    InsufficientPermissions {
+        org: String,
+        forge: String,
+        repo_name: String,
+        operation: String, // "update", "privatize", "stage", "delete"
+    },
+    /// A remote-state snapshot was written
+    SnapshotSaved {
+        path: String,
+        taken_at: String,
+        scopes: usize,
+        repos: usize,
+        failed_scopes: usize,
+    },
+    /// A `workspace plan` file was written
+    PlanSaved {
+        path: String,
+        scopes: usize,
+        changes: usize,
+    },
+    /// A forge API write recorded by `workspace apply --dry_run` instead of
+    /// being sent
+    ApiCall {
+        forge: String,
+        method: String,
+        endpoint: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<String>,
+    },
+    /// The writes a `workspace apply --dry_run` recorded for one forge
+    SimulationReport {
+        forge: String,
+        calls: usize,
+        by_method: BTreeMap<String, usize>,
+    },
+    /// Usage of one hub method, from `help`
+    MethodHelp {
+        hub: String,
+        method: String,
+        description: String,
+        params: Vec<ParamHelp>,
+        /// The method invoked with a placeholder for each required parameter
+        example: String,
+    },
+    /// A `workspace status --snapshot` file was written
+    StatusSnapshotSaved {
+        path: String,
+        repos: usize,
+        failed: usize,
+    },
+    /// A difference between two remote-state snapshots
+    SnapshotChange {
+        org: String,
+        forge: String,
+        repo_name: String,
+        change: String, // "added", "removed", "changed"
+        #[serde(skip_serializing_if = "Option::is_none")]
+        field: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        before: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        after: Option<String>,
+    },
+    /// A `LocalForge` record matched by `registry query`
+    RegistryRecord {
+        org: String,
+        repo_name: String,
+        visibility: String,
+        present_on: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        forges: Vec<String>,
+        managed: bool,
+        dismissed: bool,
+        protected: bool,
+        staged: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        local_path: Option<String>,
+    },
+    /// A field change from a registry bulk edit (applied, or previewed)
+    RegistryEdit {
+        org: String,
+        repo_name: String,
+        field: String,
+        before: String,
+        after: String,
+        dry_run: bool,
+    },
+    /// A forge rate-limited us; its requests resume after `retry_after`
+    /// seconds and the rate-limited ones are retried
+    RateLimited {
+        forge: String,
+        retry_after: u64,
+    },
+    /// A newer hyperforge release than the running binary
+    UpdateAvailable {
+        current: String,
+        latest: String,
+    },
+    /// `upgrade` replaced the hyperforge binary at `path`
+    Upgraded {
+        from: String,
+        to: String,
+        path: String,
+    },
+    /// Whether anonymous usage telemetry is on (`telemetry_status`)
+    TelemetryStatus {
+        /// `disabled`, `vetoed`, `no_endpoint` or `enabled`
+        state: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        endpoint: Option<String>,
+        /// The only fields a report carries
+        fields: Vec<String>,
+        message: String,
+    },
+    /// Toolchain pin file state for a repository
+    ToolchainPin {
+        repo_name: String,
+        toolchain: String,
+        file_path: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        current: Option<String>,
+        expected: String,
+        action: String, // "created", "updated", "unchanged"
+    },
+}
+
+impl HyperforgeEvent {
+    /// An `Error` event without context
+    pub fn error(code: HyperforgeError, message: impl Into<String>) -> Self {
+        Self::Error {
+            code,
+            message: message.into(),
+            context: None,
+        }
+    }
+
+    /// Attach context to an `Error` event; other events are returned unchanged
+    #[must_use]
+    pub fn with_context(self, context: impl Into<String>) -> Self {
+        match self {
+            Self::Error { code, message, .. } => Self::Error {
+                code,
+                message,
+                context: Some(context.into()),
+            },
+            other => other,
+        }
+    }
 }
 
 /// Validate a single credential token against its spec's validation method.
@@ -457,9 +1142,20 @@ pub struct HyperforgeHub {
 impl HyperforgeHub {
     /// Create a new `HyperforgeHub` instance
     pub fn new() -> Self {
-        Self {
-            state: HyperforgeState::new(),
-        }
+        Self::with_state(HyperforgeState::new())
+    }
+
+    /// Hub over `state`; the process-wide message catalog and scheduler are
+    /// loaded from its config dir unless something loaded them already
+    pub fn with_state(state: HyperforgeState) -> Self {
+        Catalog::init(&state.config_dir);
+        Scheduler::init(&state.config_dir);
+        Self { state }
+    }
+
+    /// Base config directory this hub reads and writes
+    pub fn config_dir(&self) -> &std::path::Path {
+        &self.state.config_dir
     }
 }
 
@@ -475,6 +1171,20 @@ impl Default for HyperforgeHub {
     crate_path = "plexus_core"
 )]
 impl HyperforgeHub {
+    /// This hub's method catalog
+    #[plexus_macros::method(
+        description = "List this hub's methods: what each does, its parameters (type, required or optional, description) and an example invocation",
+        params(
+            method = "Only describe this method (optional)"
+        )
+    )]
+    pub async fn help(
+        &self,
+        method: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, futures::stream::iter(help::events(&self.plugin_schema(), "synapse substrate hyperforge", method.as_deref())))
+    }
+
     /// Single-repo operations and registry CRUD.
     #[plexus_macros::child]
     fn repo(&self) -> RepoHub {
@@ -487,33 +1197,98 @@ impl HyperforgeHub {
         WorkspaceHub::new(self.state.clone())
     }
 
+    /// Read-only queries over `LocalForge` records.
+    #[plexus_macros::child]
+    fn registry(&self) -> RegistryHub {
+        RegistryHub::new(self.state.clone())
+    }
+
     /// Build system orchestration (cargo, cabal, node, packaging).
-    ///
-    /// `&self` is required by `#[plexus_macros::child]` (the macro
-    /// generates a `ChildRouter` accessor that takes `&Self`). `BuildHub`
-    /// carries no state of its own, so the receiver is unused here.
     #[plexus_macros::child]
-    #[allow(clippy::unused_self)]
-    const fn build(&self) -> BuildHub {
-        BuildHub::new()
+    fn build(&self) -> BuildHub {
+        BuildHub::new(self.state.clone())
     }
 
     /// Show hyperforge status
     #[plexus_macros::method(description = "Show hyperforge status and version")]
     pub async fn status(&self) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             yield HyperforgeEvent::Status {
                 version: env!("CARGO_PKG_VERSION").to_string(),
                 description: "Multi-forge repository management (FORGE4: state mirror + SSH safety)".to_string(),
             };
-        }
+            if let Some(available) = upgrade::known_update() {
+                yield HyperforgeEvent::UpdateAvailable {
+                    current: CURRENT_VERSION.to_string(),
+                    latest: available.version.clone(),
+                };
+            }
+        })
+    }
+
+    /// Check for, verify and install a newer hyperforge release
+    #[plexus_macros::method(
+        description = "Install the latest hyperforge release (or --version) from its own forge, after verifying the release signature. Replaces the running binary; restart to use it.",
+        params(
+            check = "Only report whether a newer release exists (default: false)",
+            version = "Install this release instead of the latest, e.g. 4.2.0 (optional)"
+        )
+    )]
+    pub async fn upgrade(
+        &self,
+        check: Option<bool>,
+        version: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let config = UserConfig::load(&config_dir).upgrade;
+            let releases = match upgrade::list_releases(&config).await {
+                Ok(releases) => releases,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::ForgeApi, format!("Failed to list hyperforge releases: {e}"));
+                    return;
+                }
+            };
+            let Some(available) = upgrade::select_release(&releases, CURRENT_VERSION, version.as_deref()) else {
+                yield HyperforgeEvent::Info {
+                    message: match &version {
+                        Some(v) => format!("No release {v} in {}", config.repo),
+                        None => format!("hyperforge {CURRENT_VERSION} is the latest release"),
+                    },
+                };
+                return;
+            };
+            yield HyperforgeEvent::UpdateAvailable {
+                current: CURRENT_VERSION.to_string(),
+                latest: available.version.clone(),
+            };
+            if check.unwrap_or(false) {
+                return;
+            }
+
+            let exe = match std::env::current_exe() {
+                Ok(exe) => exe,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Internal, format!("Can't locate the running binary: {e}"));
+                    return;
+                }
+            };
+            match upgrade::install(&config, &available, &exe).await {
+                Ok(()) => yield HyperforgeEvent::Upgraded {
+                    from: CURRENT_VERSION.to_string(),
+                    to: available.version.clone(),
+                    path: exe.display().to_string(),
+                },
+                Err(e) => yield HyperforgeEvent::error(HyperforgeError::Validation, format!("Upgrade to {} failed: {e}", available.version)),
+            }
+        })
     }
 
     /// Reload cached state from disk (repos.yaml for all known orgs)
     #[plexus_macros::method(description = "Reload all cached LocalForge state from disk")]
     pub async fn reload(&self) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let reloaded = state.reload().await;
             if reloaded.is_empty() {
                 yield HyperforgeEvent::Info {
@@ -524,7 +1299,65 @@ impl HyperforgeHub {
                     message: format!("Reloaded {} org(s): {}", reloaded.len(), reloaded.join(", ")),
                 };
             }
-        }
+        })
+    }
+
+    /// Report whether usage telemetry is on and what it sends
+    #[plexus_macros::method(description = "Show whether anonymous usage telemetry is on, where it goes and what a report contains")]
+    pub async fn telemetry_status(&self) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let state = TelemetryState::load(&config_dir);
+            let (label, endpoint) = match &state {
+                TelemetryState::Disabled => ("disabled", None),
+                TelemetryState::Vetoed(_) => ("vetoed", None),
+                TelemetryState::NoEndpoint => ("no_endpoint", None),
+                TelemetryState::Enabled(endpoint) => ("enabled", Some(endpoint.clone())),
+            };
+            yield HyperforgeEvent::TelemetryStatus {
+                state: label.to_string(),
+                endpoint,
+                fields: REPORT_FIELDS.iter().map(ToString::to_string).collect(),
+                message: state.describe(),
+            };
+        })
+    }
+
+    /// Opt in to usage telemetry
+    #[plexus_macros::method(
+        description = "Turn on anonymous usage telemetry: one bucketed report (method, repo count, duration, error class) per workspace run",
+        params(endpoint = "URL reports are POSTed to (optional if already configured)")
+    )]
+    pub async fn telemetry_enable(
+        &self,
+        endpoint: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let configured = UserConfig::load(&config_dir).telemetry.endpoint;
+            if endpoint.is_none() && configured.is_none() {
+                yield HyperforgeEvent::error(HyperforgeError::Validation, "No telemetry endpoint configured: pass --endpoint <url>");
+                return;
+            }
+            if let Err(e) = UserConfig::set_telemetry(&config_dir, true, endpoint.as_deref()) {
+                yield HyperforgeEvent::error(HyperforgeError::Config, format!("Failed to update config.toml: {e}"));
+                return;
+            }
+            yield HyperforgeEvent::Info { message: TelemetryState::load(&config_dir).describe() };
+        })
+    }
+
+    /// Opt back out of usage telemetry
+    #[plexus_macros::method(description = "Turn off anonymous usage telemetry")]
+    pub async fn telemetry_disable(&self) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            if let Err(e) = UserConfig::set_telemetry(&config_dir, false, None) {
+                yield HyperforgeEvent::error(HyperforgeError::Config, format!("Failed to update config.toml: {e}"));
+                return;
+            }
+            yield HyperforgeEvent::Info { message: TelemetryState::load(&config_dir).describe() };
+        })
     }
 
     /// Bootstrap an org — import all repos from remote forges into `LocalForge`
@@ -551,7 +1384,7 @@ impl HyperforgeHub {
         let do_keygen = generate_ssh_key.unwrap_or(false);
         let config_dir = self.state.config_dir.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let dry_prefix = if is_dry_run { "[dry-run] " } else { "" };
 
             // Parse and validate forge strings
@@ -563,18 +1396,17 @@ impl HyperforgeHub {
                         continue;
                     }
                     if let Some(forge) = HyperforgeConfig::parse_forge(part) { parsed_forges.push((part.to_lowercase().clone(), forge)) } else {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Invalid forge: {part}. Must be github, codeberg, or gitlab"),
-                        };
+                        yield msg_error!(Msg::InvalidForge, forge = part);
                         return;
                     }
                 }
             }
 
             if parsed_forges.is_empty() {
-                yield HyperforgeEvent::Error {
-                    message: "No forges specified. Provide at least one forge (github, codeberg, gitlab).".to_string(),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    "No forges specified. Provide at least one forge (github, codeberg, gitlab).".to_string(),
+                );
                 return;
             }
 
@@ -614,9 +1446,10 @@ impl HyperforgeHub {
                                     };
                                 }
                                 Err(e) => {
-                                    yield HyperforgeEvent::Error {
-                                        message: format!("  Failed to generate SSH key for {forge_str}: {e}"),
-                                    };
+                                    yield HyperforgeEvent::error(
+                                        HyperforgeError::Auth,
+                                        format!("  Failed to generate SSH key for {forge_str}: {e}"),
+                                    );
                                     return;
                                 }
                             }
@@ -639,9 +1472,10 @@ impl HyperforgeHub {
 
                 if !is_dry_run {
                     if let Err(e) = org_config.save(&config_dir, &org) {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to save org config: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("Failed to save org config: {e}"),
+                        );
                         return;
                     }
                 }
@@ -652,7 +1486,8 @@ impl HyperforgeHub {
 
             let ot = state.get_local_forge(&org).await.owner_type();
             for (forge_str, forge_enum) in &parsed_forges {
-                match make_adapter(forge_str, &org, ot.clone()) {
+                let host = crate::hubs::utils::forge_host(&config_dir, &org, forge_str, None);
+                match make_adapter(forge_str, &org, ot.clone(), host.as_deref()) {
                     Ok(adapter) => {
                         yield HyperforgeEvent::Info {
                             message: format!("  Authenticated with {forge_str}"),
@@ -660,9 +1495,10 @@ impl HyperforgeHub {
                         adapters.push((forge_str.clone(), forge_enum.clone(), adapter));
                     }
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("  Failed to authenticate with {forge_str}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Auth,
+                            format!("  Failed to authenticate with {forge_str}: {e}"),
+                        );
                         return;
                     }
                 }
@@ -670,7 +1506,7 @@ impl HyperforgeHub {
 
             // Phase 2: Import repos from each forge
             let local = state.get_local_forge(&org).await;
-            let mut per_forge_counts: HashMap<String, usize> = HashMap::new();
+            let mut per_forge_counts: BTreeMap<String, usize> = BTreeMap::new();
             let mut total_upserted = 0usize;
 
             for (forge_str, forge_enum, adapter) in &adapters {
@@ -681,9 +1517,10 @@ impl HyperforgeHub {
                 let list_result = match adapter.list_repos_incremental(&org, None).await {
                     Ok(lr) => lr,
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("  Failed to list repos from {forge_str}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  Failed to list repos from {forge_str}: {e}"),
+                        );
                         continue;
                     }
                 };
@@ -697,9 +1534,10 @@ impl HyperforgeHub {
 
                             if !is_dry_run {
                                 if let Err(e) = local.upsert_record(record) {
-                                    yield HyperforgeEvent::Error {
-                                        message: format!("  Failed to upsert {}: {}", repo.name, e),
-                                    };
+                                    yield HyperforgeEvent::error(
+                                        HyperforgeError::Config,
+                                        format!("  Failed to upsert {}: {}", repo.name, e),
+                                    );
                                     continue;
                                 }
                             }
@@ -725,10 +1563,12 @@ impl HyperforgeHub {
                     if let Err(e) = local.set_forge_state(forge_enum.clone(), ForgeSyncState {
                         last_synced: Utc::now(),
                         etag: list_result.etag.clone(),
+                        scope: etag_scope(forge_str, &org, ot.clone()).await,
                     }) {
-                        yield HyperforgeEvent::Error {
-                            message: format!("  Failed to store sync state for {forge_str}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("  Failed to store sync state for {forge_str}: {e}"),
+                        );
                     }
                 }
             }
@@ -736,15 +1576,13 @@ impl HyperforgeHub {
             // Save LocalForge to disk
             if !is_dry_run && total_upserted > 0 {
                 if let Err(e) = local.save_to_yaml().await {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to save LocalForge for {org}: {e}"),
-                    };
+                    yield msg_error!(Msg::LocalForgeSaveFailed, org = org, error = e);
                 }
             }
 
             // Summary
-            let unique_count = match local.all_records() {
-                Ok(records) => records.len(),
+            let unique_count = match local.record_count() {
+                Ok(count) => count,
                 Err(_) => total_upserted,
             };
 
@@ -765,7 +1603,7 @@ impl HyperforgeHub {
                     message: "Dry run — no changes written to disk.".to_string(),
                 };
             }
-        }
+        })
     }
 
     /// Show org-level configuration (SSH keys, defaults)
@@ -780,11 +1618,11 @@ impl HyperforgeHub {
         org: String,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let config_dir = self.state.config_dir.clone();
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let org_config = OrgConfig::load(&config_dir, &org);
             let config_path = OrgConfig::config_path(&config_dir, &org);
 
-            if org_config.ssh.is_empty() {
+            if org_config.ssh.is_empty() && org_config.identity.is_empty() {
                 yield HyperforgeEvent::Info {
                     message: format!(
                         "No org config for '{}'. Expected at: {}",
@@ -799,16 +1637,28 @@ impl HyperforgeHub {
                 yield HyperforgeEvent::Info {
                     message: format!("Org config for '{}' ({})", org, config_path.display()),
                 };
-                yield HyperforgeEvent::Info {
-                    message: "SSH keys:".to_string(),
-                };
+                if !org_config.ssh.is_empty() {
+                    yield HyperforgeEvent::Info {
+                        message: "SSH keys:".to_string(),
+                    };
+                }
                 for (forge, key_path) in &org_config.ssh {
                     yield HyperforgeEvent::Info {
                         message: format!("  {forge}: {key_path}"),
                     };
                 }
+                if !org_config.identity.is_empty() {
+                    yield HyperforgeEvent::Info {
+                        message: "Identity:".to_string(),
+                    };
+                    for (key, value) in org_config.identity.git_config() {
+                        yield HyperforgeEvent::Info {
+                            message: format!("  {key}: {value}"),
+                        };
+                    }
+                }
             }
-        }
+        })
     }
 
     /// Set an org-level default SSH key for a forge
@@ -827,12 +1677,10 @@ impl HyperforgeHub {
         key: String,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let config_dir = self.state.config_dir.clone();
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             // Validate forge name
             if HyperforgeConfig::parse_forge(&forge).is_none() {
-                yield HyperforgeEvent::Error {
-                    message: format!("Invalid forge: {forge}. Must be github, codeberg, or gitlab"),
-                };
+                yield msg_error!(Msg::InvalidForge, forge = forge);
                 return;
             }
 
@@ -844,9 +1692,10 @@ impl HyperforgeHub {
             };
 
             if !expanded.exists() {
-                yield HyperforgeEvent::Error {
-                    message: format!("SSH key not found: {} (expanded: {})", key, expanded.display()),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Auth,
+                    format!("SSH key not found: {} (expanded: {})", key, expanded.display()),
+                );
                 return;
             }
 
@@ -864,12 +1713,137 @@ impl HyperforgeHub {
                     };
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to save org config: {e}"),
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to save org config: {e}"),
+                    );
+                }
+            }
+        })
+    }
+
+    /// Set the git identity used for an org's repos
+    #[plexus_macros::method(
+        description = "Set the git identity (user.name, user.email, signing key) written into each of an org's repos on init and clone",
+        params(
+            org = "Organization name",
+            name = "Value for user.name (optional)",
+            email = "Value for user.email (optional)",
+            signing_key = "Value for user.signingkey; also enables commit.gpgsign (optional)"
+        )
+    )]
+    pub async fn config_set_identity(
+        &self,
+        org: String,
+        name: Option<String>,
+        email: Option<String>,
+        signing_key: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            if name.is_none() && email.is_none() && signing_key.is_none() {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    "Nothing to set: pass at least one of --name, --email, --signing_key".to_string(),
+                );
+                return;
+            }
+            if email.as_deref().is_some_and(|e| !e.contains('@')) {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("Invalid email: {}", email.unwrap_or_default()),
+                );
+                return;
+            }
+
+            let mut org_config = OrgConfig::load(&config_dir, &org);
+            let identity = &mut org_config.identity;
+            if name.is_some() {
+                identity.name = name;
+            }
+            if email.is_some() {
+                identity.email = email;
+            }
+            if signing_key.is_some() {
+                identity.signing_key = signing_key;
+            }
+            let entries: Vec<String> = identity.git_config().iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect();
+
+            match org_config.save(&config_dir, &org) {
+                Ok(()) => {
+                    yield HyperforgeEvent::Info {
+                        message: format!("Set identity for org '{org}': {}", entries.join(", ")),
+                    };
+                    yield HyperforgeEvent::Info {
+                        message: format!("Saved to {}", OrgConfig::config_path(&config_dir, &org).display()),
                     };
                 }
+                Err(e) => {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to save org config: {e}"),
+                    );
+                }
             }
-        }
+        })
+    }
+
+    /// Generate includeIf stanzas in the user's gitconfig from org settings
+    #[plexus_macros::method(
+        description = "Generate includeIf \"gitdir:<workspace_path>/\" stanzas in the global gitconfig so each org's identity and sshCommand follow its workspace directory",
+        params(
+            check = "Only report drift between org settings and the gitconfig; write nothing (optional, default: false)"
+        )
+    )]
+    pub async fn config_gitconfig(
+        &self,
+        check: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let config_dir = self.state.config_dir.clone();
+        let is_check = check.unwrap_or(false);
+        output::structured(None, &self.state.config_dir, stream! {
+            let gitconfig_path = gitconfig::global_gitconfig_path();
+            let plan = gitconfig::GitconfigPlan::new(&config_dir, gitconfig_path.clone(), &OrgConfig::list(&config_dir));
+
+            if plan.includes.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No org has a workspace_path with an identity or SSH key; no includeIf stanzas to generate".to_string(),
+                };
+            }
+            for include in &plan.includes {
+                let state = if plan.stale_includes.contains(&include.org) { "stale" } else { "in sync" };
+                yield HyperforgeEvent::Info {
+                    message: format!(
+                        "  {}: gitdir:{} → {} ({state})",
+                        include.org, include.gitdir, include.path.display(),
+                    ),
+                };
+            }
+            if plan.block_drifted {
+                yield HyperforgeEvent::Info {
+                    message: format!("  includeIf block in {} differs", gitconfig_path.display()),
+                };
+            }
+
+            if plan.is_in_sync() {
+                yield HyperforgeEvent::Info {
+                    message: format!("{} is in sync with org settings", gitconfig_path.display()),
+                };
+            } else if is_check {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    format!("{} has drifted from org settings; run config_gitconfig to regenerate", gitconfig_path.display()),
+                );
+            } else if let Err(e) = plan.apply() {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+            } else {
+                yield HyperforgeEvent::Info {
+                    message: format!("Updated includeIf stanzas in {}", gitconfig_path.display()),
+                };
+            }
+        })
     }
 
     /// Show the public SSH key for an org/forge (pipe to pbcopy)
@@ -886,19 +1860,18 @@ impl HyperforgeHub {
         forge: String,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let config_dir = self.state.config_dir.clone();
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             if HyperforgeConfig::parse_forge(&forge).is_none() {
-                yield HyperforgeEvent::Error {
-                    message: format!("Invalid forge: {forge}. Must be github, codeberg, or gitlab"),
-                };
+                yield msg_error!(Msg::InvalidForge, forge = forge);
                 return;
             }
 
             let org_config = OrgConfig::load(&config_dir, &org);
             if org_config.ssh_key_for_forge(&forge).is_none() {
-                yield HyperforgeEvent::Error {
-                    message: format!("No SSH key configured for {forge} on org '{org}'. Run begin with --generate_ssh_key true first."),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Auth,
+                    format!("No SSH key configured for {forge} on org '{org}'. Run begin with --generate_ssh_key true first."),
+                );
                 return;
             }
 
@@ -907,17 +1880,17 @@ impl HyperforgeHub {
                     yield HyperforgeEvent::Info { message: pubkey };
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                 }
             }
-        }
+        })
     }
 
     /// List all known organizations
     #[plexus_macros::method(description = "List all organizations configured in hyperforge")]
     pub async fn orgs_list(&self) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let config_dir = self.state.config_dir.clone();
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let orgs_dir = config_dir.join("orgs");
             if !orgs_dir.exists() {
                 yield HyperforgeEvent::Info {
@@ -926,19 +1899,7 @@ impl HyperforgeHub {
                 return;
             }
 
-            let mut orgs: Vec<String> = Vec::new();
-            if let Ok(entries) = std::fs::read_dir(&orgs_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.extension().is_some_and(|e| e == "toml") {
-                        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                            orgs.push(stem.to_string());
-                        }
-                    }
-                }
-            }
-
-            orgs.sort();
+            let orgs = OrgConfig::list(&config_dir);
 
             if orgs.is_empty() {
                 yield HyperforgeEvent::Info {
@@ -964,7 +1925,7 @@ impl HyperforgeHub {
             yield HyperforgeEvent::Info {
                 message: format!("{} org(s) configured.", orgs.len()),
             };
-        }
+        })
     }
 
     /// Delete an organization — removes config, keys, repos.yaml, and optionally workspace dir
@@ -987,7 +1948,7 @@ impl HyperforgeHub {
         let is_dry_run = !confirm.unwrap_or(false);
         let do_remove_workspace = remove_workspace.unwrap_or(false);
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let dry_prefix = if is_dry_run { "[dry-run] " } else { "" };
             let org_config = OrgConfig::load(&config_dir, &org);
 
@@ -995,9 +1956,10 @@ impl HyperforgeHub {
             let config_path = OrgConfig::config_path(&config_dir, &org);
             let org_data_dir = config_dir.join("orgs").join(&org);
             if !config_path.exists() && !org_data_dir.exists() {
-                yield HyperforgeEvent::Error {
-                    message: format!("Organization '{org}' not found."),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    format!("Organization '{org}' not found."),
+                );
                 return;
             }
 
@@ -1012,9 +1974,10 @@ impl HyperforgeHub {
                 };
                 if !is_dry_run {
                     if let Err(e) = std::fs::remove_file(&config_path) {
-                        yield HyperforgeEvent::Error {
-                            message: format!("  Failed to remove config: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("  Failed to remove config: {e}"),
+                        );
                     }
                 }
             }
@@ -1026,9 +1989,10 @@ impl HyperforgeHub {
                 };
                 if !is_dry_run {
                     if let Err(e) = std::fs::remove_dir_all(&org_data_dir) {
-                        yield HyperforgeEvent::Error {
-                            message: format!("  Failed to remove data dir: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("  Failed to remove data dir: {e}"),
+                        );
                     }
                 }
             }
@@ -1043,9 +2007,10 @@ impl HyperforgeHub {
                         };
                         if !is_dry_run {
                             if let Err(e) = std::fs::remove_dir_all(&wp_path) {
-                                yield HyperforgeEvent::Error {
-                                    message: format!("  Failed to remove workspace dir: {e}"),
-                                };
+                                yield HyperforgeEvent::error(
+                                    HyperforgeError::Config,
+                                    format!("  Failed to remove workspace dir: {e}"),
+                                );
                             }
                         }
                     }
@@ -1064,7 +2029,7 @@ impl HyperforgeHub {
             yield HyperforgeEvent::Info {
                 message: format!("{dry_prefix}Organization '{org}' deleted."),
             };
-        }
+        })
     }
 
     /// Derive needed credentials from workspace dist configs and check which are present
@@ -1082,7 +2047,7 @@ impl HyperforgeHub {
         include: Option<Vec<String>>,
         exclude: Option<Vec<String>>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let workspace_path = std::path::PathBuf::from(&path);
             let ctx = match discover_or_bail(&workspace_path) {
                 Ok(ctx) => ctx,
@@ -1187,9 +2152,10 @@ impl HyperforgeHub {
             let auth = match YamlAuthProvider::new() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to create auth provider: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Auth,
+                        format!("Failed to create auth provider: {e}"),
+                    );
                     return;
                 }
             };
@@ -1248,7 +2214,7 @@ impl HyperforgeHub {
                     },
                 ),
             };
-        }
+        })
     }
 
     /// Guided credential setup — shows what tokens are needed, where to create them, and how to store them
@@ -1269,7 +2235,7 @@ impl HyperforgeHub {
         let config_dir = self.state.config_dir.clone();
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             const SECRETS_PORT: u16 = 44105;
 
             // 1. Determine which forges to set up
@@ -1287,7 +2253,7 @@ impl HyperforgeHub {
                 // If no SSH keys configured, also check LocalForge records for present_on
                 if forges.is_empty() {
                     let local = state.get_local_forge(&org).await;
-                    if let Ok(records) = local.all_records() {
+                    if let Ok(records) = local.records() {
                         let mut seen = std::collections::HashSet::new();
                         for rec in &records {
                             for f in &rec.present_on {
@@ -1300,11 +2266,12 @@ impl HyperforgeHub {
                 }
 
                 if forges.is_empty() {
-                    yield HyperforgeEvent::Error {
-                        message: format!(
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!(
                             "No forges configured for org '{org}'. Run `begin` first or specify --forge."
                         ),
-                    };
+                    );
                     return;
                 }
 
@@ -1346,26 +2313,24 @@ impl HyperforgeHub {
             let storage = match YamlStorage::default_location() {
                 Ok(s) => s,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to open secrets store: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Auth,
+                        format!("Failed to open secrets store: {e}"),
+                    );
                     return;
                 }
             };
             if let Err(e) = storage.load().await {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to load secrets store: {e}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Auth,
+                    format!("Failed to load secrets store: {e}"),
+                );
                 return;
             }
 
             let forge_names: Vec<String> = target_forges
                 .iter()
-                .map(|f| match f {
-                    Forge::GitHub => "github".to_string(),
-                    Forge::Codeberg => "codeberg".to_string(),
-                    Forge::GitLab => "gitlab".to_string(),
-                })
+                .map(ToString::to_string)
                 .collect();
 
             yield HyperforgeEvent::Info {
@@ -1468,7 +2433,7 @@ impl HyperforgeHub {
                     "{configured_count} configured, {missing_count} need setup",
                 ),
             };
-        }
+        })
     }
 
     /// Validate all configured tokens — check existence, validity, and scopes
@@ -1487,21 +2452,23 @@ impl HyperforgeHub {
         channel: Option<Vec<DistChannel>>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let config_dir = self.state.config_dir.clone();
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             // Initialize storage and load secrets from disk
             let storage = match YamlStorage::default_location() {
                 Ok(s) => s,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to initialize secrets storage: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Auth,
+                        format!("Failed to initialize secrets storage: {e}"),
+                    );
                     return;
                 }
             };
             if let Err(e) = storage.load().await {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to load secrets: {e}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Auth,
+                    format!("Failed to load secrets: {e}"),
+                );
                 return;
             }
 
@@ -1627,7 +2594,46 @@ impl HyperforgeHub {
                     "{valid} valid, {missing} missing, {invalid} invalid out of {total} credentials checked",
                 ),
             };
-        }
+        })
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_event_serializes_stable_code() {
+        let event = HyperforgeEvent::error(HyperforgeError::ForgeApi, "boom");
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"type": "error", "code": "forge_api", "message": "boom"})
+        );
+
+        let event = event.with_context("github");
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["context"], "github");
+        let back: HyperforgeEvent = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            back,
+            HyperforgeEvent::Error { code: HyperforgeError::ForgeApi, context: Some(c), .. } if c == "github"
+        ));
+    }
+
+    #[test]
+    fn test_error_codes_match_serde_names() {
+        for code in [
+            HyperforgeError::Config,
+            HyperforgeError::Git,
+            HyperforgeError::ForgeApi,
+            HyperforgeError::Auth,
+            HyperforgeError::Validation,
+            HyperforgeError::Policy,
+            HyperforgeError::Internal,
+        ] {
+            assert_eq!(serde_json::to_value(code).unwrap(), code.code());
+        }
+    }
+}
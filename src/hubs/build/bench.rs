@@ -0,0 +1,179 @@
+//! Benchmark regression gating: run each repo's `[ci.bench]` command and
+//! compare against the baseline recorded for the previous commit.
+
+use async_stream::stream;
+use futures::Stream;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::build_system::bench::{self, BenchHistory, BenchRun};
+use crate::commands::runner::discover_or_bail;
+use crate::git::Git;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
+use crate::hubs::utils::{dry_prefix, RepoFilter};
+
+/// Default slowdown, in percent, that counts as a regression
+const DEFAULT_THRESHOLD_PCT: f64 = 10.0;
+
+pub fn bench(
+    path: String,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    threshold: Option<f64>,
+    warn_only: Option<bool>,
+    dry_run: Option<bool>,
+) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+    let filter = RepoFilter::new(include, exclude);
+    let is_dry_run = dry_run.unwrap_or(false);
+    let warn_only = warn_only.unwrap_or(false);
+    let default_threshold = threshold.unwrap_or(DEFAULT_THRESHOLD_PCT);
+
+    stream! {
+        let workspace_path = PathBuf::from(&path);
+        let prefix = dry_prefix(is_dry_run);
+
+        let ctx = match discover_or_bail(&workspace_path) {
+            Ok(ctx) => ctx,
+            Err(event) => { yield event; return; }
+        };
+
+        // (dir name, path, command, threshold) for repos that declare benchmarks
+        let work: Vec<(String, PathBuf, Vec<String>, f64)> = ctx
+            .repos
+            .iter()
+            .filter(|r| filter.matches(&r.dir_name))
+            .filter_map(|r| {
+                let cfg = r.config.as_ref()?.ci.as_ref()?.bench.as_ref()?;
+                (!cfg.command.is_empty()).then(|| {
+                    (
+                        r.dir_name.clone(),
+                        r.path.clone(),
+                        cfg.command.clone(),
+                        cfg.max_regression_pct.unwrap_or(default_threshold),
+                    )
+                })
+            })
+            .collect();
+
+        if work.is_empty() {
+            yield HyperforgeEvent::Info {
+                message: "No repos declare benchmarks — set [ci.bench].command in a repo's .hyperforge/config.toml.".to_string(),
+            };
+            return;
+        }
+
+        yield HyperforgeEvent::Info {
+            message: format!("{prefix}Benchmarking {} repos (one at a time to limit noise)...", work.len()),
+        };
+
+        let mut regressions = Vec::new();
+        for (dir_name, repo_path, command, threshold_pct) in work {
+            if is_dry_run {
+                yield HyperforgeEvent::Info {
+                    message: format!("{prefix}  {dir_name}: would run {}", command.join(" ")),
+                };
+                continue;
+            }
+
+            let commit = match Git::head_commit(&repo_path) {
+                Ok(c) => c,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Git, format!("  {dir_name}: {e}"));
+                    continue;
+                }
+            };
+
+            let cmd = command.clone();
+            let dir = repo_path.clone();
+            let output = tokio::task::spawn_blocking(move || {
+                let start = Instant::now();
+                let output = std::process::Command::new(&cmd[0])
+                    .args(&cmd[1..])
+                    .current_dir(&dir)
+                    .output();
+                (output, start.elapsed())
+            })
+            .await;
+            let (output, elapsed) = match output {
+                Ok((Ok(output), elapsed)) if output.status.success() => (output, elapsed),
+                Ok((Ok(output), _)) => {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Validation,
+                        format!(
+                            "  {dir_name}: {} failed (exit {}): {}",
+                            command.join(" "),
+                            output.status.code().unwrap_or(-1),
+                            stderr.chars().take(200).collect::<String>()
+                        ),
+                    );
+                    continue;
+                }
+                Ok((Err(e), _)) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Validation, format!("  {dir_name}: failed to run {}: {e}", command[0]));
+                    continue;
+                }
+                Err(e) => {
+                    yield msg_error!(Msg::TaskFailed, error = e);
+                    continue;
+                }
+            };
+
+            let mut results = bench::parse_results(&String::from_utf8_lossy(&output.stdout));
+            if results.is_empty() {
+                results.insert(bench::WALL_CLOCK.to_string(), elapsed.as_secs_f64() * 1e9);
+            }
+            let run = BenchRun { commit, run_at: chrono::Utc::now(), results };
+
+            let mut history = match BenchHistory::load(&ctx.root, &dir_name) {
+                Ok(h) => h,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    continue;
+                }
+            };
+            let baseline = history.baseline_for(&run.commit);
+            if baseline.is_none() {
+                yield HyperforgeEvent::Info {
+                    message: format!("  {dir_name}: no baseline from an earlier commit yet; recording this run"),
+                };
+            }
+            for delta in bench::compare(baseline, &run, threshold_pct) {
+                if delta.regressed {
+                    regressions.push(format!("{dir_name}/{}", delta.name));
+                }
+                yield HyperforgeEvent::BenchResult {
+                    repo_name: dir_name.clone(),
+                    bench: delta.name,
+                    current_ns: delta.current_ns,
+                    baseline_ns: delta.baseline_ns,
+                    change_pct: delta.change_pct,
+                    regressed: delta.regressed,
+                };
+            }
+
+            history.record(run);
+            if let Err(e) = history.save(&ctx.root, &dir_name) {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+            }
+        }
+
+        if is_dry_run {
+            return;
+        }
+        if regressions.is_empty() {
+            yield HyperforgeEvent::Info { message: "No benchmark regressions.".to_string() };
+        } else if warn_only {
+            yield HyperforgeEvent::Info {
+                message: format!("Warning: {} benchmarks regressed: {}", regressions.len(), regressions.join(", ")),
+            };
+        } else {
+            yield HyperforgeEvent::error(
+                HyperforgeError::Policy,
+                format!("{} benchmarks regressed: {}", regressions.len(), regressions.join(", ")),
+            );
+        }
+    }
+}
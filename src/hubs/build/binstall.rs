@@ -6,7 +6,7 @@ use std::path::{Path, PathBuf};
 
 use crate::commands::runner::discover_or_bail;
 use crate::config::HyperforgeConfig;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
 use crate::hubs::utils::{dry_prefix, RepoFilter};
 use crate::types::Forge;
 
@@ -191,9 +191,10 @@ pub fn binstall_init(
                 }
                 Err(e) => {
                     failed += 1;
-                    yield HyperforgeEvent::Error {
-                        message: format!("  {dir_name}: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("  {dir_name}: {e}"),
+                    );
                 }
             }
         }
@@ -0,0 +1,374 @@
+//! Post-release distribution channel updates.
+//!
+//! After a forge release uploads its archives, repos whose `[dist]` lists the
+//! `brew` or `aur` channel get their Homebrew tap formula and AUR `-bin`
+//! package bumped to the new version and checksums, committed, and pushed.
+//! Taps and AUR repos are used in place when a local clone is configured
+//! (`brew_tap_path`, `aur_repo_path`), otherwise cloned to a temp dir.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::build_system::cross_compile::sha256_file;
+use crate::commands::workspace::DiscoveredRepo;
+use crate::git::{CloneOptions, Git};
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::types::config::{DistChannel, DistConfig};
+
+use super::homebrew::{generate_formula, parse_asset_filename, to_class_name, BrewPlatform, PlatformAsset};
+
+/// A release archive as published: its target, download URL, and checksum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ReleasedAsset {
+    pub triple: String,
+    pub url: String,
+    pub sha256: String,
+}
+
+/// Match local archives to their uploaded download URLs, hashing each.
+/// Archives that weren't uploaded or don't follow the binstall naming
+/// convention are left out.
+pub(crate) fn released_assets(
+    archives: &[PathBuf],
+    urls: &HashMap<String, String>,
+) -> Result<Vec<ReleasedAsset>, String> {
+    let mut assets = Vec::new();
+    for archive in archives {
+        let Some(filename) = archive.file_name().and_then(|n| n.to_str()) else { continue };
+        let (Some(url), Some((_, triple, _))) = (urls.get(filename), parse_asset_filename(filename)) else {
+            continue;
+        };
+        assets.push(ReleasedAsset {
+            triple,
+            url: url.clone(),
+            sha256: sha256_file(archive)?,
+        });
+    }
+    Ok(assets)
+}
+
+/// What the channels need to know about the release
+pub(crate) struct ReleaseMeta<'a> {
+    pub name: &'a str,
+    pub version: &'a str,
+    pub binary: &'a str,
+    pub description: &'a str,
+    pub homepage: &'a str,
+}
+
+/// Update every brew/aur channel in `dist` for a finished release
+pub(crate) fn update_channels(
+    repo: &DiscoveredRepo,
+    dist: &DistConfig,
+    meta: &ReleaseMeta<'_>,
+    assets: &[ReleasedAsset],
+    dry_run: bool,
+) -> (Vec<HyperforgeEvent>, usize) {
+    let mut events = Vec::new();
+    let mut failed = 0usize;
+    let dry_prefix = if dry_run { "[dry-run] " } else { "" };
+
+    if dist.channels.contains(&DistChannel::Brew) {
+        match (&dist.brew_tap_path, &dist.brew_tap) {
+            (None, None) => events.push(HyperforgeEvent::Info {
+                message: format!("  {}: brew channel has no brew_tap configured -- skipping", repo.dir_name),
+            }),
+            (local, remote) => {
+                let target = local.clone().unwrap_or_else(|| remote.clone().unwrap_or_default());
+                if dry_run {
+                    events.push(HyperforgeEvent::Info {
+                        message: format!("{dry_prefix}  Would bump Formula/{}.rb in {target} to {}", meta.name, meta.version),
+                    });
+                } else {
+                    let remote_url = remote.as_ref().map(|t| format!("git@github.com:{t}.git"));
+                    match update_brew_tap(local.as_deref(), remote_url.as_deref(), meta, assets) {
+                        Ok(msg) => events.push(HyperforgeEvent::Info { message: format!("  {}: {msg}", repo.dir_name) }),
+                        Err(e) => {
+                            events.push(HyperforgeEvent::error(
+                                HyperforgeError::ForgeApi,
+                                format!("  {}: brew tap update failed: {e}", repo.dir_name),
+                            ));
+                            failed += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if dist.channels.contains(&DistChannel::Aur) {
+        let pkgname = dist.aur_package.clone().unwrap_or_else(|| format!("{}-bin", meta.name));
+        if dry_run {
+            events.push(HyperforgeEvent::Info {
+                message: format!("{dry_prefix}  Would bump AUR package {pkgname} to {}", meta.version),
+            });
+        } else {
+            let remote_url = format!("ssh://aur@aur.archlinux.org/{pkgname}.git");
+            match update_aur(dist.aur_repo_path.as_deref(), &remote_url, &pkgname, meta, assets) {
+                Ok(msg) => events.push(HyperforgeEvent::Info { message: format!("  {}: {msg}", repo.dir_name) }),
+                Err(e) => {
+                    events.push(HyperforgeEvent::error(
+                        HyperforgeError::ForgeApi,
+                        format!("  {}: AUR update failed: {e}", repo.dir_name),
+                    ));
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    (events, failed)
+}
+
+fn update_brew_tap(
+    local: Option<&str>,
+    remote_url: Option<&str>,
+    meta: &ReleaseMeta<'_>,
+    assets: &[ReleasedAsset],
+) -> Result<String, String> {
+    let platforms: BTreeMap<BrewPlatform, PlatformAsset> = assets
+        .iter()
+        .filter_map(|a| {
+            let platform = BrewPlatform::from_triple(&a.triple)?;
+            Some((platform, PlatformAsset { url: a.url.clone(), sha256: a.sha256.clone() }))
+        })
+        .collect();
+    if platforms.is_empty() {
+        return Err("no macOS or Linux archives in the release".to_string());
+    }
+    let formula = generate_formula(
+        &to_class_name(meta.name),
+        meta.description,
+        meta.homepage,
+        meta.version,
+        meta.binary,
+        &platforms,
+    );
+
+    let file = format!("Formula/{}.rb", meta.name);
+    with_checkout(local, remote_url, |dir| {
+        write_file(&dir.join(&file), &formula)?;
+        commit_and_push(dir, &[&file], &format!("{} {}", meta.name, meta.version))
+    })
+    .map(|changed| {
+        if changed {
+            format!("bumped {file} to {}", meta.version)
+        } else {
+            format!("{file} already at {}", meta.version)
+        }
+    })
+}
+
+fn update_aur(
+    local: Option<&str>,
+    remote_url: &str,
+    pkgname: &str,
+    meta: &ReleaseMeta<'_>,
+    assets: &[ReleasedAsset],
+) -> Result<String, String> {
+    let sources = aur_sources(assets);
+    if sources.is_empty() {
+        return Err("no Linux archives in the release".to_string());
+    }
+    let pkg = AurPackage { pkgname, meta, sources };
+
+    with_checkout(local, Some(remote_url), |dir| {
+        write_file(&dir.join("PKGBUILD"), &pkg.pkgbuild())?;
+        write_file(&dir.join(".SRCINFO"), &pkg.srcinfo())?;
+        commit_and_push(dir, &["PKGBUILD", ".SRCINFO"], &format!("Update to {}", meta.version))
+    })
+    .map(|changed| {
+        if changed {
+            format!("bumped AUR package {pkgname} to {}", meta.version)
+        } else {
+            format!("AUR package {pkgname} already at {}", meta.version)
+        }
+    })
+}
+
+/// Linux archives by pacman arch, preferring glibc builds over musl
+fn aur_sources(assets: &[ReleasedAsset]) -> BTreeMap<&'static str, &ReleasedAsset> {
+    let mut sources: BTreeMap<&'static str, &ReleasedAsset> = BTreeMap::new();
+    for asset in assets.iter().filter(|a| a.triple.contains("linux")) {
+        let arch = if asset.triple.starts_with("x86_64") {
+            "x86_64"
+        } else if asset.triple.starts_with("aarch64") {
+            "aarch64"
+        } else {
+            continue;
+        };
+        let replace = sources.get(arch).is_none_or(|existing| existing.triple.contains("musl"));
+        if replace {
+            sources.insert(arch, asset);
+        }
+    }
+    sources
+}
+
+/// A `-bin` AUR package repackaging the release archives
+struct AurPackage<'a> {
+    pkgname: &'a str,
+    meta: &'a ReleaseMeta<'a>,
+    sources: BTreeMap<&'static str, &'a ReleasedAsset>,
+}
+
+impl AurPackage<'_> {
+    /// pacman versions can't contain hyphens
+    fn pkgver(&self) -> String {
+        self.meta.version.replace('-', "_")
+    }
+
+    fn pkgbuild(&self) -> String {
+        let m = self.meta;
+        let arches: Vec<String> = self.sources.keys().map(|a| format!("'{a}'")).collect();
+        let mut lines = vec![
+            "# Maintained by hyperforge; regenerated on every release".to_string(),
+            format!("pkgname={}", self.pkgname),
+            format!("pkgver={}", self.pkgver()),
+            "pkgrel=1".to_string(),
+            format!("pkgdesc=\"{}\"", m.description.replace('"', "'")),
+            format!("arch=({})", arches.join(" ")),
+            format!("url=\"{}\"", m.homepage),
+            "license=('custom')".to_string(),
+            format!("provides=('{}')", m.name),
+            format!("conflicts=('{}')", m.name),
+        ];
+        for (arch, asset) in &self.sources {
+            lines.push(format!("source_{arch}=(\"{}\")", asset.url));
+            lines.push(format!("sha256sums_{arch}=('{}')", asset.sha256));
+        }
+        lines.push(String::new());
+        lines.push("package() {".to_string());
+        lines.push(format!(
+            "  install -Dm755 \"$srcdir\"/{}-*/{} \"$pkgdir/usr/bin/{}\"",
+            m.name, m.binary, m.binary
+        ));
+        lines.push("}".to_string());
+        lines.join("\n") + "\n"
+    }
+
+    fn srcinfo(&self) -> String {
+        let m = self.meta;
+        let mut lines = vec![
+            format!("pkgbase = {}", self.pkgname),
+            format!("\tpkgdesc = {}", m.description),
+            format!("\tpkgver = {}", self.pkgver()),
+            "\tpkgrel = 1".to_string(),
+            format!("\turl = {}", m.homepage),
+        ];
+        lines.extend(self.sources.keys().map(|a| format!("\tarch = {a}")));
+        lines.push("\tlicense = custom".to_string());
+        lines.push(format!("\tprovides = {}", m.name));
+        lines.push(format!("\tconflicts = {}", m.name));
+        for (arch, asset) in &self.sources {
+            lines.push(format!("\tsource_{arch} = {}", asset.url));
+            lines.push(format!("\tsha256sums_{arch} = {}", asset.sha256));
+        }
+        lines.push(String::new());
+        lines.push(format!("pkgname = {}", self.pkgname));
+        lines.join("\n") + "\n"
+    }
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// Run `f` in the local clone (pulled first) or in a fresh temp clone of
+/// `remote_url`, which is removed afterwards.
+fn with_checkout<T>(
+    local: Option<&str>,
+    remote_url: Option<&str>,
+    f: impl FnOnce(&Path) -> Result<T, String>,
+) -> Result<T, String> {
+    if let Some(local) = local {
+        let dir = PathBuf::from(local);
+        Git::pull(&dir, None).map_err(|e| format!("git pull in {local}: {e}"))?;
+        return f(&dir);
+    }
+    let url = remote_url.ok_or_else(|| "no repo to update".to_string())?;
+    let dir = std::env::temp_dir().join(format!("hyperforge-channel-{}", uuid::Uuid::new_v4()));
+    Git::clone(url, &dir.display().to_string(), &CloneOptions::default()).map_err(|e| format!("git clone {url}: {e}"))?;
+    let result = f(&dir);
+    let _ = std::fs::remove_dir_all(&dir);
+    result
+}
+
+/// Commit `files` and push; `false` when they were already up to date
+fn commit_and_push(dir: &Path, files: &[&str], message: &str) -> Result<bool, String> {
+    for file in files {
+        Git::add(dir, file).map_err(|e| format!("git add {file}: {e}"))?;
+    }
+    let status = Git::repo_status(dir).map_err(|e| format!("git status: {e}"))?;
+    if !status.has_staged {
+        return Ok(false);
+    }
+    Git::commit(dir, message).map_err(|e| format!("git commit: {e}"))?;
+    Git::push(dir, "origin", Some("HEAD")).map_err(|e| format!("git push: {e}"))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(triple: &str) -> ReleasedAsset {
+        ReleasedAsset {
+            triple: triple.to_string(),
+            url: format!("https://github.com/acme/tool/releases/download/v1.2.0-rc1/tool-{triple}-v1.2.0-rc1.tar.gz"),
+            sha256: format!("{:0>64}", triple.len()),
+        }
+    }
+
+    #[test]
+    fn test_aur_package_prefers_gnu_and_skips_other_platforms() {
+        let assets = [
+            asset("x86_64-unknown-linux-musl"),
+            asset("x86_64-unknown-linux-gnu"),
+            asset("aarch64-unknown-linux-musl"),
+            asset("aarch64-apple-darwin"),
+        ];
+        let meta = ReleaseMeta {
+            name: "tool",
+            version: "1.2.0-rc1",
+            binary: "tool",
+            description: "A \"fast\" tool",
+            homepage: "https://github.com/acme/tool",
+        };
+        let pkg = AurPackage { pkgname: "tool-bin", meta: &meta, sources: aur_sources(&assets) };
+
+        let pkgbuild = pkg.pkgbuild();
+        assert!(pkgbuild.contains("pkgver=1.2.0_rc1\n"));
+        assert!(pkgbuild.contains("arch=('aarch64' 'x86_64')"));
+        assert!(pkgbuild.contains("tool-x86_64-unknown-linux-gnu-v1.2.0-rc1.tar.gz"));
+        assert!(pkgbuild.contains("tool-aarch64-unknown-linux-musl-v1.2.0-rc1.tar.gz"));
+        assert!(!pkgbuild.contains("x86_64-unknown-linux-musl"));
+        assert!(!pkgbuild.contains("darwin"));
+        assert!(pkgbuild.contains("pkgdesc=\"A 'fast' tool\""));
+
+        let srcinfo = pkg.srcinfo();
+        assert!(srcinfo.starts_with("pkgbase = tool-bin\n\tpkgdesc = A \"fast\" tool\n\tpkgver = 1.2.0_rc1\n"));
+        assert!(srcinfo.ends_with("\npkgname = tool-bin\n"));
+    }
+
+    #[test]
+    fn test_released_assets_match_uploaded_archives() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let archive = temp.path().join("tool-x86_64-unknown-linux-gnu-v1.0.0.tar.gz");
+        let skipped = temp.path().join("tool-aarch64-unknown-linux-gnu-v1.0.0.tar.gz");
+        std::fs::write(&archive, b"").unwrap();
+        std::fs::write(&skipped, b"").unwrap();
+        let urls = HashMap::from([(
+            "tool-x86_64-unknown-linux-gnu-v1.0.0.tar.gz".to_string(),
+            "https://example.com/a.tar.gz".to_string(),
+        )]);
+
+        let assets = released_assets(&[archive, skipped], &urls).unwrap();
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].triple, "x86_64-unknown-linux-gnu");
+        assert_eq!(assets[0].sha256, "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    }
+}
@@ -0,0 +1,346 @@
+//! CODEOWNERS generation from the `[owners]` map in workspace config.
+
+use async_stream::stream;
+use futures::Stream;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::adapters::{ApprovalRule, APPROVAL_RULE_PREFIX};
+use crate::build_system::generated::{self, DriftPolicy, GeneratedManifest, ManifestEntry, Reconcile};
+use crate::commands::runner::{discover_or_bail, run_batch_blocking};
+use crate::config::WorkspaceConfig;
+use crate::git::Git;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
+use crate::hubs::utils::{dry_prefix, RepoFilter};
+use crate::scheduler::Scheduler;
+
+/// Locations forges read CODEOWNERS from, in the order an existing file is
+/// looked for. The root location is read by GitHub, GitLab and Forgejo alike,
+/// so new files go there.
+const CODEOWNERS_LOCATIONS: &[&str] = &[
+    "CODEOWNERS",
+    ".github/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+    ".gitea/CODEOWNERS",
+    "docs/CODEOWNERS",
+];
+
+/// Where a repo's CODEOWNERS lives (or should be created), relative to the repo
+fn codeowners_location(repo_path: &Path) -> &'static str {
+    CODEOWNERS_LOCATIONS
+        .iter()
+        .find(|rel| repo_path.join(rel).exists())
+        .unwrap_or(&CODEOWNERS_LOCATIONS[0])
+}
+
+/// Render CODEOWNERS content from `owners` (owner → globs).
+///
+/// Owners sharing a glob go on one line. The last matching line wins, so the
+/// catch-all `*` comes first and deeper paths come later.
+pub(crate) fn render_codeowners(owners: &BTreeMap<String, Vec<String>>) -> String {
+    let mut by_glob: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (owner, globs) in owners {
+        for glob in globs {
+            by_glob.entry(glob.as_str()).or_default().push(owner.as_str());
+        }
+    }
+    let mut globs: Vec<&str> = by_glob.keys().copied().collect();
+    globs.sort_by_key(|g| (*g != "*", g.matches('/').count(), g.len(), *g));
+
+    let mut content = String::new();
+    content.push_str("# Generated by hyperforge build codeowners from [owners] in .hyperforge/workspace.toml\n");
+    content.push_str("# Do not edit manually; re-run `build codeowners` to regenerate.\n\n");
+    for glob in globs {
+        content.push_str(glob);
+        for owner in &by_glob[glob] {
+            content.push(' ');
+            content.push_str(owner);
+        }
+        content.push('\n');
+    }
+    content
+}
+
+/// One forge approval rule per `@user` / `@group` owner. Email owners have
+/// no forge-side equivalent and are skipped.
+pub(crate) fn approval_rules(
+    owners: &BTreeMap<String, Vec<String>>,
+    approvals_required: u32,
+) -> Vec<ApprovalRule> {
+    owners
+        .keys()
+        .filter_map(|owner| owner.strip_prefix('@'))
+        .map(|approver| ApprovalRule {
+            name: format!("{APPROVAL_RULE_PREFIX}{approver}"),
+            approvers: vec![approver.to_string()],
+            approvals_required,
+        })
+        .collect()
+}
+
+/// Reconcile one repo's CODEOWNERS against `content`, then commit it.
+///
+/// Drift is tracked in the workspace manifest, keyed by the file's path
+/// relative to the workspace root. The manifest entry is returned rather
+/// than recorded, since repos sync in parallel.
+fn sync_repo(
+    workspace_root: &Path,
+    repo_path: &Path,
+    content: &str,
+    policy: DriftPolicy,
+    dry_run: bool,
+    commit: bool,
+) -> Result<(PathBuf, Reconcile, Option<ManifestEntry>), String> {
+    let location = codeowners_location(repo_path);
+    let file_path = repo_path.join(location);
+    let rel_path = file_path
+        .strip_prefix(workspace_root)
+        .map_err(|_| format!("{} is outside the workspace", repo_path.display()))?
+        .to_string_lossy()
+        .to_string();
+
+    let reconciled = generated::reconcile(workspace_root, &rel_path, content, policy)?;
+    let changed = reconciled.write.is_some();
+    if dry_run {
+        return Ok((file_path, reconciled.action, None));
+    }
+
+    // Refuse to commit on top of someone else's staged work
+    if commit && changed {
+        let status = Git::repo_status(repo_path).map_err(|e| format!("git status failed: {e}"))?;
+        if status.has_staged {
+            return Err("repo has staged changes — commit or unstage first".to_string());
+        }
+    }
+
+    let entry = generated::write(workspace_root, &rel_path, &reconciled, content)?;
+
+    if commit && changed {
+        Git::add(repo_path, location).map_err(|e| format!("git add {location} failed: {e}"))?;
+        Git::commit(repo_path, "chore: sync CODEOWNERS")
+            .map_err(|e| format!("git commit failed: {e}"))?;
+    }
+
+    Ok((file_path, reconciled.action, entry))
+}
+
+pub fn codeowners(
+    path: String,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    dry_run: Option<bool>,
+    no_commit: Option<bool>,
+    force: Option<bool>,
+) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+    let is_dry_run = dry_run.unwrap_or(false);
+    let commit = !no_commit.unwrap_or(false);
+    let policy = if force.unwrap_or(false) {
+        DriftPolicy::Force
+    } else {
+        DriftPolicy::Refuse
+    };
+    let filter = RepoFilter::new(include, exclude);
+
+    stream! {
+        let workspace_path = PathBuf::from(&path);
+        let prefix = dry_prefix(is_dry_run);
+
+        let ctx = match discover_or_bail(&workspace_path) {
+            Ok(ctx) => ctx,
+            Err(event) => { yield event; return; }
+        };
+
+        let config = match WorkspaceConfig::load(&ctx.root) {
+            Ok(c) => c,
+            Err(e) => {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                return;
+            }
+        };
+
+        if config.owners.is_empty() {
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "No owners declared in {} — add an [owners] table (owner → path globs).",
+                    WorkspaceConfig::config_path(&ctx.root).display()
+                ),
+            };
+            return;
+        }
+
+        let content = render_codeowners(&config.owners);
+        let root = ctx.root.clone();
+        let work: Vec<(String, PathBuf)> = ctx
+            .repos
+            .iter()
+            .filter(|r| filter.matches(&r.dir_name))
+            .map(|r| (r.dir_name.clone(), r.path.clone()))
+            .collect();
+
+        if work.is_empty() {
+            yield HyperforgeEvent::Info {
+                message: "No repos matched filter.".to_string(),
+            };
+            return;
+        }
+
+        yield HyperforgeEvent::Info {
+            message: format!("{}Syncing CODEOWNERS across {} repos...", prefix, work.len()),
+        };
+
+        let results = run_batch_blocking(work, Scheduler::global().batch(None), move |(dir_name, repo_path)| {
+            let result = sync_repo(&root, &repo_path, &content, policy, is_dry_run, commit);
+            (dir_name, result)
+        })
+        .await;
+
+        let mut changed = 0usize;
+        let mut conflicts = 0usize;
+        let mut failed = 0usize;
+        let mut entries = Vec::new();
+
+        for result in results {
+            match result {
+                Ok((name, Ok((file_path, action, entry)))) => {
+                    entries.extend(entry);
+                    let action = match action {
+                        Reconcile::Created => "created",
+                        Reconcile::Updated => "updated",
+                        Reconcile::Unchanged => "unchanged",
+                        Reconcile::Merged => "merged",
+                        Reconcile::Conflict => "conflict",
+                    };
+                    match action {
+                        "unchanged" => {}
+                        "conflict" => conflicts += 1,
+                        _ => changed += 1,
+                    }
+                    yield HyperforgeEvent::CodeownersFile {
+                        repo_name: name,
+                        file_path: file_path.to_string_lossy().to_string(),
+                        action: action.to_string(),
+                    };
+                }
+                Ok((name, Err(e))) => {
+                    failed += 1;
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("  {name}: {e}"),
+                    );
+                }
+                Err(e) => {
+                    failed += 1;
+                    yield msg_error!(Msg::TaskFailed, error = e);
+                }
+            }
+        }
+
+        if let Err(e) = GeneratedManifest::record(&ctx.root, &entries) {
+            yield HyperforgeEvent::error(HyperforgeError::Config, e);
+        }
+
+        if conflicts > 0 {
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{conflicts} CODEOWNERS file(s) were edited by hand and left untouched — re-run with --force to overwrite."
+                ),
+            };
+        }
+        yield HyperforgeEvent::Info {
+            message: format!(
+                "{prefix}CODEOWNERS: {changed} changed, {conflicts} conflicts, {failed} failed"
+            ),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owners() -> BTreeMap<String, Vec<String>> {
+        BTreeMap::from([
+            (
+                "@acme/platform".to_string(),
+                vec!["*".to_string(), "/ci/".to_string()],
+            ),
+            (
+                "@acme/docs".to_string(),
+                vec!["/docs/".to_string(), "*.md".to_string()],
+            ),
+            ("@alice".to_string(), vec!["/ci/".to_string()]),
+            ("ops@acme.dev".to_string(), vec!["/deploy/".to_string()]),
+        ])
+    }
+
+    #[test]
+    fn test_render_orders_catch_all_first_and_groups_owners() {
+        let content = render_codeowners(&owners());
+        let rules: Vec<&str> = content
+            .lines()
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                "* @acme/platform",
+                "*.md @acme/docs",
+                "/ci/ @acme/platform @alice",
+                "/docs/ @acme/docs",
+                "/deploy/ ops@acme.dev",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_approval_rules_skip_email_owners() {
+        let rules = approval_rules(&owners(), 1);
+        let names: Vec<&str> = rules.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["codeowners: acme/docs", "codeowners: acme/platform", "codeowners: alice"]
+        );
+        assert_eq!(rules[0].approvers, vec!["acme/docs"]);
+    }
+
+    #[test]
+    fn test_sync_repo_prefers_existing_location() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("api");
+        std::fs::create_dir_all(repo.join(".github")).unwrap();
+        std::fs::write(repo.join(".github/CODEOWNERS"), "* @someone\n").unwrap();
+
+        let content = render_codeowners(&owners());
+        let (file_path, action, entry) =
+            sync_repo(temp.path(), &repo, &content, DriftPolicy::Refuse, false, false).unwrap();
+
+        assert_eq!(file_path, repo.join(".github/CODEOWNERS"));
+        assert_eq!(action, Reconcile::Updated);
+        assert_eq!(entry.unwrap().0, "api/.github/CODEOWNERS");
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), content);
+        assert!(!repo.join("CODEOWNERS").exists());
+    }
+
+    #[test]
+    fn test_force_overwrites_hand_edit_of_unchanged_owners() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("api");
+        std::fs::create_dir_all(&repo).unwrap();
+        let content = render_codeowners(&owners());
+
+        let (file_path, _, entry) =
+            sync_repo(temp.path(), &repo, &content, DriftPolicy::Refuse, false, false).unwrap();
+        GeneratedManifest::record(temp.path(), &[entry.unwrap()]).unwrap();
+
+        // Hand edit while the ownership map stays the same
+        std::fs::write(&file_path, "* @someone\n").unwrap();
+        let (_, kept, _) = sync_repo(temp.path(), &repo, &content, DriftPolicy::Refuse, false, false).unwrap();
+        assert_eq!(kept, Reconcile::Unchanged);
+
+        let (_, forced, _) = sync_repo(temp.path(), &repo, &content, DriftPolicy::Force, false, false).unwrap();
+        assert_eq!(forced, Reconcile::Updated);
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), content);
+    }
+}
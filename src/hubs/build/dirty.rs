@@ -6,7 +6,9 @@ use std::path::PathBuf;
 
 use crate::commands::runner::discover_or_bail;
 use crate::git::Git;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
 use crate::hubs::utils::RepoFilter;
 
 /// Check dirty status for a single repo path.
@@ -84,9 +86,7 @@ pub fn dirty(
             match result {
                 Ok(entry) => entries.push(entry),
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Task error: {e}"),
-                    };
+                    yield msg_error!(Msg::TaskFailed, error = e);
                 }
             }
         }
@@ -94,9 +94,10 @@ pub fn dirty(
 
         for (repo_name, branch, has_staged, has_changes, has_untracked, error) in entries {
             if let Some(e) = error {
-                yield HyperforgeEvent::Error {
-                    message: format!("{repo_name}: {e}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Git,
+                    format!("{repo_name}: {e}"),
+                );
                 continue;
             }
 
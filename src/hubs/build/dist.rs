@@ -7,7 +7,7 @@ use std::path::PathBuf;
 use crate::build_system::BuildSystemKind;
 use crate::commands::runner::discover_or_bail;
 use crate::config::HyperforgeConfig;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
 use crate::hubs::utils::{dry_prefix, RepoFilter};
 use crate::types::config::{DistChannel, DistConfig};
 
@@ -92,6 +92,9 @@ pub fn dist_show(
                 if let Some(ref tap_path) = dist.brew_tap_path {
                     parts.push(format!("brew_tap_path: {tap_path}"));
                 }
+                if let Some(ref pkg) = dist.aur_package {
+                    parts.push(format!("aur_package: {pkg}"));
+                }
 
                 yield HyperforgeEvent::Info {
                     message: format!("  {} — {}", name, parts.join(", ")),
@@ -186,6 +189,10 @@ pub fn dist_init(
                 targets: final_targets.clone(),
                 brew_tap: brew_tap.clone(),
                 brew_tap_path: None,
+                aur_package: None,
+                aur_repo_path: None,
+                signing: None,
+                forge_packages: Vec::new(),
             };
 
             let channels_str: Vec<String> = dist.channels.iter().map(std::string::ToString::to_string).collect();
@@ -210,9 +217,10 @@ pub fn dist_init(
                 match config.save(&repo.path) {
                     Ok(()) => { written += 1; }
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to write config for {name}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("Failed to write config for {name}: {e}"),
+                        );
                     }
                 }
             }
@@ -1,13 +1,20 @@
-//! Cross-repo execution and validation: exec, validate.
+//! Cross-repo execution and validation: exec, validate, `validate_report`.
 
 use async_stream::stream;
 use futures::Stream;
 use std::path::PathBuf;
 
+use crate::auth::secret_env;
+use crate::build_system::coverage;
+use crate::build_system::history::{self, StepRecord};
+use crate::services::ArtifactPolicy;
+use crate::build_system::validate::StepStatus;
 use crate::commands::runner::discover_or_bail;
 use crate::commands::workspace::build_dep_graph;
 use crate::git::Git;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
 use crate::hubs::utils::{dry_prefix, RepoFilter};
 
 pub fn exec(
@@ -103,9 +110,7 @@ pub fn exec(
                     };
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Task error: {e}"),
-                    };
+                    yield msg_error!(Msg::TaskFailed, error = e);
                 }
             }
         }
@@ -123,8 +128,13 @@ pub fn validate(
     test: Option<bool>,
     dry_run: Option<bool>,
     image: Option<String>,
+    shards: Option<u32>,
+    changed_since: Option<String>,
+    coverage: Option<bool>,
+    min_coverage: Option<f64>,
 ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
     let is_dry_run = dry_run.unwrap_or(false);
+    let collect_coverage = coverage.unwrap_or(false);
     let run_tests = test.unwrap_or(false);
     let docker_image = image.unwrap_or_else(|| "rust:latest".to_string());
 
@@ -142,7 +152,7 @@ pub fn validate(
 
         // Build CI configs from per-repo .hyperforge/config.toml [ci] sections
         // For validate (Docker), find the first docker-type runner in the runners array
-        let ci_configs: Vec<(String, crate::build_system::validate::RepoCiConfig)> = ctx
+        let mut ci_configs: Vec<(String, crate::build_system::validate::RepoCiConfig)> = ctx
             .repos
             .iter()
             .map(|repo| {
@@ -153,6 +163,16 @@ pub fn validate(
                 let mut cfg = crate::build_system::validate::RepoCiConfig {
                     repo_name: name.clone(),
                     skip: ci.skip_validate,
+                    dockerfile: ci.dockerfile.clone(),
+                    test_shards: shards.or(ci.test_shards).unwrap_or(1),
+                    coverage_command: ci
+                        .coverage
+                        .as_ref()
+                        .filter(|c| !c.command.is_empty())
+                        .map(|c| c.command.clone())
+                        .or_else(|| coverage::default_command(&repo.build_systems))
+                        .unwrap_or_default(),
+                    min_line_pct: ci.coverage.as_ref().and_then(|c| c.min_line_pct),
                     ..crate::build_system::validate::RepoCiConfig::default()
                 };
 
@@ -166,7 +186,6 @@ pub fn validate(
                     if !docker_runner.test.is_empty() {
                         cfg.test_command = docker_runner.test.clone();
                     }
-                    cfg.dockerfile = None; // Docker runners use image, not dockerfile
                     cfg.timeout_secs = docker_runner.timeout_secs;
                     cfg.env = docker_runner.env.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
                 } else if let Some(last_runner) = ci.runners.last() {
@@ -185,6 +204,32 @@ pub fn validate(
             })
             .collect();
 
+        // Resolve `secret:` env refs; dry runs leave them unresolved
+        if !is_dry_run && ci_configs.iter().any(|(_, c)| secret_env::has_secret_refs(c.env.iter().map(|(_, v)| v))) {
+            let storage = match secret_env::load_store().await {
+                Ok(s) => s,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
+                    return;
+                }
+            };
+            for (name, cfg) in &mut ci_configs {
+                match secret_env::resolve_env(cfg.env.iter().map(|(k, v)| (k, v)), Some(&storage)) {
+                    Ok((env, secrets)) => {
+                        cfg.env = env;
+                        cfg.secrets = secrets;
+                    }
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Validation,
+                            format!("{name}: failed to resolve validation env: {e}"),
+                        );
+                        return;
+                    }
+                }
+            }
+        }
+
         // Build validation plan
         let plan = match crate::build_system::validate::build_validation_plan(
             &graph,
@@ -193,16 +238,31 @@ pub fn validate(
         ) {
             Ok(mut p) => {
                 p.default_image = docker_image;
+                p.coverage = collect_coverage;
                 p
             }
             Err(e) => {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to build validation plan: {e}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("Failed to build validation plan: {e}"),
+                );
                 return;
             }
         };
 
+        let mut plan = plan;
+        if let Some(since) = changed_since.filter(|_| run_tests) {
+            let changed = crate::build_system::validate::changed_repos(&graph, &ctx.root, &since);
+            plan.narrow_to_changes(&graph, since.clone(), &changed);
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{dry_prefix}Changed since {since}: {} repo(s); testing {} with their dependents",
+                    changed.len(),
+                    plan.affected.len(),
+                ),
+            };
+        }
+
         yield HyperforgeEvent::Info {
             message: format!(
                 "{}Validation plan: {} steps, tests={}",
@@ -228,6 +288,85 @@ pub fn validate(
             };
         }
 
+        if !is_dry_run {
+            let run_at = chrono::Utc::now();
+            let records: Vec<StepRecord> = results
+                .iter()
+                .filter(|r| r.status != StepStatus::Skipped)
+                .map(|r| StepRecord {
+                    run_at,
+                    repo: r.repo_name.clone(),
+                    step: r.step.clone(),
+                    passed: r.status == StepStatus::Passed,
+                    duration_ms: r.duration_ms,
+                })
+                .collect();
+            let recorded = match ArtifactPolicy::load_default().await {
+                Ok(policy) => history::append(&ctx.root, &records, &policy),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = recorded {
+                yield msg_error!(Msg::RunHistoryFailed, error = e);
+            }
+        }
+
+        let mut coverage_failures = Vec::new();
+        if collect_coverage && !is_dry_run {
+            let mut reports = Vec::new();
+            let mut total = coverage::LcovSummary::default();
+            for r in results.iter().filter(|r| r.step == "coverage" && r.status == StepStatus::Passed) {
+                let Some(report) = coverage::load_repo_report(&ctx.root, &r.repo_name) else {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Validation,
+                        format!("{}: coverage command left no /coverage/lcov.info", r.repo_name),
+                    );
+                    coverage_failures.push(r.repo_name.clone());
+                    continue;
+                };
+                let summary = coverage::parse_lcov(&report);
+                let min_line_pct = plan
+                    .steps
+                    .iter()
+                    .find(|s| s.repo_name == r.repo_name)
+                    .and_then(|s| s.ci_config.min_line_pct);
+                let passed = min_line_pct.is_none_or(|min| summary.line_pct() >= min);
+                if !passed {
+                    coverage_failures.push(r.repo_name.clone());
+                }
+                yield HyperforgeEvent::CoverageReport {
+                    repo_name: r.repo_name.clone(),
+                    lines_found: summary.lines_found,
+                    lines_hit: summary.lines_hit,
+                    line_pct: summary.line_pct(),
+                    min_line_pct,
+                    passed,
+                };
+                total = total.merge(summary);
+                reports.push(report);
+            }
+
+            if !reports.is_empty() {
+                match coverage::write_workspace_report(&ctx.root, &reports) {
+                    Ok(lcov_path) => {
+                        let passed = min_coverage.is_none_or(|min| total.line_pct() >= min);
+                        if !passed {
+                            coverage_failures.push("workspace".to_string());
+                        }
+                        yield HyperforgeEvent::CoverageSummary {
+                            repos: reports.len(),
+                            lines_found: total.lines_found,
+                            lines_hit: total.lines_hit,
+                            line_pct: total.line_pct(),
+                            min_line_pct: min_coverage,
+                            passed,
+                            lcov_path: lcov_path.display().to_string(),
+                        };
+                    }
+                    Err(e) => yield HyperforgeEvent::error(HyperforgeError::Validation, e),
+                }
+            }
+        }
+
         let summary = crate::build_system::validate::summarize_results(&results);
         yield HyperforgeEvent::ValidateSummary {
             total: summary.total,
@@ -238,12 +377,18 @@ pub fn validate(
         };
 
         if summary.failed > 0 {
-            yield HyperforgeEvent::Error {
-                message: format!(
+            yield HyperforgeEvent::error(
+                HyperforgeError::Validation,
+                format!(
                     "Validation failed: {}/{} steps failed",
                     summary.failed, summary.total
                 ),
-            };
+            );
+        } else if !coverage_failures.is_empty() {
+            yield HyperforgeEvent::error(
+                HyperforgeError::Policy,
+                format!("Coverage below threshold: {}", coverage_failures.join(", ")),
+            );
         } else {
             yield HyperforgeEvent::Info {
                 message: format!(
@@ -254,3 +399,72 @@ pub fn validate(
         }
     }
 }
+
+/// Report flaky steps and slowing builds from the validation run history
+pub fn validate_report(
+    path: String,
+    window: Option<usize>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+    let filter = RepoFilter::new(include, exclude);
+    let window = window.unwrap_or(10).max(2);
+
+    stream! {
+        let workspace_path = PathBuf::from(&path);
+
+        let ctx = match discover_or_bail(&workspace_path) {
+            Ok(ctx) => ctx,
+            Err(event) => { yield event; return; }
+        };
+
+        let policy = match ArtifactPolicy::load_default().await {
+            Ok(policy) => policy,
+            Err(e) => {
+                yield msg_error!(Msg::RunHistoryFailed, error = e);
+                return;
+            }
+        };
+        let records: Vec<StepRecord> = history::load(&ctx.root, &policy)
+            .into_iter()
+            .filter(|r| filter.matches(&r.repo))
+            .collect();
+        if records.is_empty() {
+            yield HyperforgeEvent::Info {
+                message: "No run history yet — run `build validate` or `build run` first.".to_string(),
+            };
+            return;
+        }
+
+        let flaky = history::flaky_steps(&records, window);
+        for f in &flaky {
+            yield HyperforgeEvent::FlakyStep {
+                repo_name: f.repo.clone(),
+                step: f.step.clone(),
+                runs: f.runs,
+                flips: f.flips,
+                pass_rate_pct: f.pass_rate_pct,
+            };
+        }
+
+        let trends = history::duration_trends(&records, window);
+        for t in &trends {
+            yield HyperforgeEvent::DurationTrend {
+                repo_name: t.repo.clone(),
+                step: t.step.clone(),
+                baseline_ms: t.baseline_ms,
+                recent_ms: t.recent_ms,
+                change_pct: t.change_pct,
+            };
+        }
+
+        yield HyperforgeEvent::Info {
+            message: format!(
+                "Run history ({} step records, last {window} runs per step): {} flaky, {} trending slower",
+                records.len(),
+                flaky.len(),
+                trends.len()
+            ),
+        };
+    }
+}
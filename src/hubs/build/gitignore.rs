@@ -7,7 +7,9 @@ use std::path::{Path, PathBuf};
 
 use crate::build_system::BuildSystemKind;
 use crate::commands::runner::{discover_or_bail, run_batch_blocking};
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
 use crate::hubs::utils::{dry_prefix, RepoFilter};
 
 // ---------------------------------------------------------------------------
@@ -210,6 +212,7 @@ pub fn gitignore_sync(
     include: Option<Vec<String>>,
     exclude: Option<Vec<String>>,
     dry_run: Option<bool>,
+    batch: usize,
 ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
     let is_dry_run = dry_run.unwrap_or(false);
     let extra_patterns = patterns.unwrap_or_default();
@@ -294,7 +297,7 @@ pub fn gitignore_sync(
             })
             .collect();
 
-        let results = run_batch_blocking(work, 8, |(dir_name, repo_path, build_systems, extra, dry)| {
+        let results = run_batch_blocking(work, batch, |(dir_name, repo_path, build_systems, extra, dry)| {
             let result = ensure_patterns(&repo_path, &build_systems, &extra, dry);
             (dir_name, result)
         })
@@ -327,15 +330,14 @@ pub fn gitignore_sync(
                 }
                 Ok((name, Err(e))) => {
                     failed += 1;
-                    yield HyperforgeEvent::Error {
-                        message: format!("  {name}: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("  {name}: {e}"),
+                    );
                 }
                 Err(e) => {
                     failed += 1;
-                    yield HyperforgeEvent::Error {
-                        message: format!("Task error: {e}"),
-                    };
+                    yield msg_error!(Msg::TaskFailed, error = e);
                 }
             }
         }
@@ -11,20 +11,20 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::auth::AuthProvider;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
 use crate::types::Forge;
 
 use super::release::{make_auth, make_release_adapter};
 
 /// A matched platform asset with its download URL and sha256 hash.
-struct PlatformAsset {
-    url: String,
-    sha256: String,
+pub(super) struct PlatformAsset {
+    pub(super) url: String,
+    pub(super) sha256: String,
 }
 
 /// Homebrew platform selector key derived from a target triple.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-enum BrewPlatform {
+pub(super) enum BrewPlatform {
     MacosArm,
     MacosIntel,
     LinuxArm,
@@ -33,7 +33,7 @@ enum BrewPlatform {
 
 impl BrewPlatform {
     /// Try to map a target triple string to a Homebrew platform.
-    fn from_triple(triple: &str) -> Option<Self> {
+    pub(super) fn from_triple(triple: &str) -> Option<Self> {
         match triple {
             _ if triple.contains("aarch64") && triple.contains("apple-darwin") => {
                 Some(Self::MacosArm)
@@ -52,7 +52,7 @@ impl BrewPlatform {
 ///
 /// Expected format: `{name}-{target}-v{version}.{ext}`
 /// e.g. `synapse-aarch64-apple-darwin-v3.10.1.tar.gz`
-fn parse_asset_filename(filename: &str) -> Option<(String, String, String)> {
+pub(super) fn parse_asset_filename(filename: &str) -> Option<(String, String, String)> {
     // Strip known archive extensions
     let stem = filename
         .strip_suffix(".tar.gz")
@@ -83,6 +83,8 @@ fn parse_asset_filename(filename: &str) -> Option<(String, String, String)> {
         "x86_64-apple-darwin",
         "x86_64-pc-windows-msvc",
         "aarch64-pc-windows-msvc",
+        "x86_64-pc-windows-gnu",
+        "aarch64-pc-windows-gnullvm",
     ];
 
     for target in &known_targets {
@@ -95,7 +97,7 @@ fn parse_asset_filename(filename: &str) -> Option<(String, String, String)> {
 }
 
 /// Convert a package name to a Ruby class name (`PascalCase`).
-fn to_class_name(name: &str) -> String {
+pub(super) fn to_class_name(name: &str) -> String {
     name.split(['-', '_'])
         .map(|part| {
             let mut chars = part.chars();
@@ -111,7 +113,7 @@ fn to_class_name(name: &str) -> String {
 }
 
 /// Build the Ruby formula string from collected platform assets.
-fn generate_formula(
+pub(super) fn generate_formula(
     class_name: &str,
     description: &str,
     homepage: &str,
@@ -239,7 +241,7 @@ pub fn brew_formula(
         let auth = match make_auth() {
             Ok(a) => a,
             Err(e) => {
-                yield HyperforgeEvent::Error { message: e };
+                yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                 return;
             }
         };
@@ -247,9 +249,10 @@ pub fn brew_formula(
         let adapter = match make_release_adapter(&forge_name, auth.clone(), &org) {
             Ok(a) => a,
             Err(e) => {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to create release adapter: {e}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("Failed to create release adapter: {e}"),
+                );
                 return;
             }
         };
@@ -262,15 +265,17 @@ pub fn brew_formula(
         let release = match adapter.get_release_by_tag(&org, &name, &tag).await {
             Ok(Some(r)) => r,
             Ok(None) => {
-                yield HyperforgeEvent::Error {
-                    message: format!("Release {tag} not found for {org}/{name}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("Release {tag} not found for {org}/{name}"),
+                );
                 return;
             }
             Err(e) => {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to fetch release: {e}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("Failed to fetch release: {e}"),
+                );
                 return;
             }
         };
@@ -333,17 +338,19 @@ pub fn brew_formula(
                     });
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to hash {}: {}", asset.name, e),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::ForgeApi,
+                        format!("Failed to hash {}: {}", asset.name, e),
+                    );
                 }
             }
         }
 
         if platforms.is_empty() {
-            yield HyperforgeEvent::Error {
-                message: "No matching platform assets found in release".to_string(),
-            };
+            yield HyperforgeEvent::error(
+                HyperforgeError::ForgeApi,
+                "No matching platform assets found in release".to_string(),
+            );
             return;
         }
 
@@ -377,9 +384,10 @@ pub fn brew_formula(
                 yield HyperforgeEvent::Info { message: formula };
             } else {
                 if let Err(e) = std::fs::create_dir_all(&formula_dir) {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to create Formula directory: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Internal,
+                        format!("Failed to create Formula directory: {e}"),
+                    );
                     return;
                 }
 
@@ -390,9 +398,10 @@ pub fn brew_formula(
                         };
                     }
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to write formula: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Internal,
+                            format!("Failed to write formula: {e}"),
+                        );
                         return;
                     }
                 }
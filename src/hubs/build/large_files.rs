@@ -6,7 +6,9 @@ use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 use crate::commands::runner::discover_or_bail;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
 use crate::hubs::utils::RepoFilter;
 
 /// A large file found in a repo.
@@ -152,9 +154,10 @@ pub fn large_files(
             match result {
                 Ok((repo_name, entries, error)) => {
                     if let Some(e) = error {
-                        yield HyperforgeEvent::Error {
-                            message: format!("{repo_name}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Git,
+                            format!("{repo_name}: {e}"),
+                        );
                         continue;
                     }
                     if !entries.is_empty() {
@@ -174,9 +177,7 @@ pub fn large_files(
                     }
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Task error: {e}"),
-                    };
+                    yield msg_error!(Msg::TaskFailed, error = e);
                 }
             }
         }
@@ -7,7 +7,9 @@ use std::io::BufRead;
 use std::path::PathBuf;
 
 use crate::commands::runner::discover_or_bail;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
 use crate::hubs::utils::RepoFilter;
 
 /// Count lines of code for a single repo, broken down by file extension.
@@ -102,17 +104,16 @@ pub fn loc(
             match result {
                 Ok((repo_name, total_lines, total_files, by_extension, error)) => {
                     if let Some(e) = error {
-                        yield HyperforgeEvent::Error {
-                            message: format!("{repo_name}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Git,
+                            format!("{repo_name}: {e}"),
+                        );
                     } else {
                         entries.push((repo_name, total_lines, total_files, by_extension));
                     }
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Task error: {e}"),
-                    };
+                    yield msg_error!(Msg::TaskFailed, error = e);
                 }
             }
         }
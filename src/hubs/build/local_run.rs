@@ -5,10 +5,16 @@ use futures::Stream;
 use std::path::PathBuf;
 use std::time::Instant;
 
+use crate::auth::secret_env;
+use crate::build_system::history::{self, StepRecord};
+use crate::services::ArtifactPolicy;
 use crate::commands::runner::discover_or_bail;
 use crate::commands::workspace::build_publish_dep_graph;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
 use crate::hubs::utils::{dry_prefix, RepoFilter};
+use crate::scheduler::{Resource, Scheduler};
 use crate::types::config::{resolve_ci_config, RunnerConfig, RunnerType};
 
 /// Run build/test commands using layered CI runners in dependency order.
@@ -44,9 +50,10 @@ pub fn run(
         let tiers = match graph.build_tiers() {
             Ok(t) => t,
             Err(e) => {
-                yield HyperforgeEvent::Error {
-                    message: format!("Dependency cycle detected: {e:?}"),
-                };
+                yield msg_error!(Msg::DependencyCycle, error = e);
+                for event in super::manifest::cycle_diagnosis_events(&graph) {
+                    yield event;
+                }
                 return;
             }
         };
@@ -69,6 +76,39 @@ pub fn run(
             repo_runners.insert(name, (repo.path.clone(), runners, skip));
         }
 
+        // Resolve `secret:` env refs up front; values are only ever handed to
+        // the child process and are redacted from reported stderr
+        let mut secrets: Vec<String> = Vec::new();
+        let needs_secrets = repo_runners.values().any(|(_, runners, _)| {
+            runners.iter().any(|r| secret_env::has_secret_refs(r.env.values()))
+        });
+        if !is_dry_run && needs_secrets {
+            let storage = match secret_env::load_store().await {
+                Ok(s) => s,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
+                    return;
+                }
+            };
+            for (name, (_, runners, _)) in &mut repo_runners {
+                for runner in runners.iter_mut() {
+                    match secret_env::resolve_env(&runner.env, Some(&storage)) {
+                        Ok((env, values)) => {
+                            runner.env = env.into_iter().collect();
+                            secrets.extend(values);
+                        }
+                        Err(e) => {
+                            yield HyperforgeEvent::error(
+                                HyperforgeError::Validation,
+                                format!("{name}: failed to resolve runner env: {e}"),
+                            );
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+
         // Determine which runner indices to execute per repo
         // --level N: run only runner at index N
         // no --level: run all local-type runners
@@ -191,80 +231,93 @@ pub fn run(
             }).collect();
 
             let run_tests_copy = run_tests;
+            let tier_secrets = secrets.clone();
             let results = crate::commands::runner::run_batch(
                 batch_inputs,
                 concurrency,
-                move |(repo_name, repo_path, runners, indices)| async move {
-                    let run_tests = run_tests_copy;
-                    let mut steps = Vec::new();
-                    for idx in indices {
-                        let runner = &runners[idx];
-                        // Build step
-                        if !runner.build.is_empty() {
-                            let start = Instant::now();
-                            let result = execute_runner_cmd(
-                                &runner.build,
-                                &repo_path,
-                                &runner.env,
-                                runner.timeout_secs,
-                                &runner.runner_type,
-                                runner.image.as_deref(),
-                            ).await;
-                            let duration_ms = start.elapsed().as_millis() as u64;
-                            let (status, ok) = match result {
-                                Ok(output) if output.success => ("passed".into(), true),
-                                Ok(output) => (format!("failed (exit {}): {}", output.code, output.stderr.chars().take(200).collect::<String>()), false),
-                                Err(e) => (format!("error: {e}"), false),
-                            };
-                            steps.push((
-                                repo_name.clone(),
-                                format!("L{idx} build"),
-                                status,
-                                duration_ms,
-                                ok,
-                            ));
-                            if !ok {
-                                break; // Don't continue to test if build failed
+                move |(repo_name, repo_path, runners, indices)| {
+                    let secrets = tier_secrets.clone();
+                    async move {
+                        let run_tests = run_tests_copy;
+                        let mut steps = Vec::new();
+                        for idx in indices {
+                            let runner = &runners[idx];
+                            // Build step
+                            if !runner.build.is_empty() {
+                                let start = Instant::now();
+                                let result = execute_runner_cmd(
+                                    &runner.build,
+                                    &repo_path,
+                                    &runner.env,
+                                    runner.timeout_secs,
+                                    &runner.runner_type,
+                                    runner.image.as_deref(),
+                                ).await;
+                                let duration_ms = start.elapsed().as_millis() as u64;
+                                let (status, ok) = match result {
+                                    Ok(output) if output.success => ("passed".into(), true),
+                                    Ok(output) => (format!("failed (exit {}): {}", output.code, secret_env::redact(&output.stderr, &secrets).chars().take(200).collect::<String>()), false),
+                                    Err(e) => (format!("error: {e}"), false),
+                                };
+                                steps.push((
+                                    repo_name.clone(),
+                                    format!("L{idx} build"),
+                                    status,
+                                    duration_ms,
+                                    ok,
+                                ));
+                                if !ok {
+                                    break; // Don't continue to test if build failed
+                                }
                             }
-                        }
 
-                        // Test step
-                        if run_tests && !runner.test.is_empty() {
-                            let start = Instant::now();
-                            let result = execute_runner_cmd(
-                                &runner.test,
-                                &repo_path,
-                                &runner.env,
-                                runner.timeout_secs,
-                                &runner.runner_type,
-                                runner.image.as_deref(),
-                            ).await;
-                            let duration_ms = start.elapsed().as_millis() as u64;
-                            let (status, ok) = match result {
-                                Ok(output) if output.success => ("passed".into(), true),
-                                Ok(output) => (format!("failed (exit {}): {}", output.code, output.stderr.chars().take(200).collect::<String>()), false),
-                                Err(e) => (format!("error: {e}"), false),
-                            };
-                            steps.push((
-                                repo_name.clone(),
-                                format!("L{idx} test"),
-                                status,
-                                duration_ms,
-                                ok,
-                            ));
-                            if !ok {
-                                break;
+                            // Test step
+                            if run_tests && !runner.test.is_empty() {
+                                let start = Instant::now();
+                                let result = execute_runner_cmd(
+                                    &runner.test,
+                                    &repo_path,
+                                    &runner.env,
+                                    runner.timeout_secs,
+                                    &runner.runner_type,
+                                    runner.image.as_deref(),
+                                ).await;
+                                let duration_ms = start.elapsed().as_millis() as u64;
+                                let (status, ok) = match result {
+                                    Ok(output) if output.success => ("passed".into(), true),
+                                    Ok(output) => (format!("failed (exit {}): {}", output.code, secret_env::redact(&output.stderr, &secrets).chars().take(200).collect::<String>()), false),
+                                    Err(e) => (format!("error: {e}"), false),
+                                };
+                                steps.push((
+                                    repo_name.clone(),
+                                    format!("L{idx} test"),
+                                    status,
+                                    duration_ms,
+                                    ok,
+                                ));
+                                if !ok {
+                                    break;
+                                }
                             }
                         }
+                        steps
                     }
-                    steps
                 },
             ).await;
 
+            let run_at = chrono::Utc::now();
+            let mut records = Vec::new();
             for result in results {
                 match result {
                     Ok(steps) => {
                         for (repo_name, step, status, duration_ms, ok) in steps {
+                            records.push(StepRecord {
+                                run_at,
+                                repo: repo_name.clone(),
+                                step: step.clone(),
+                                passed: ok,
+                                duration_ms,
+                            });
                             yield HyperforgeEvent::ValidateStep {
                                 repo_name,
                                 step,
@@ -275,19 +328,26 @@ pub fn run(
                         }
                     }
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Task error: {e}"),
-                        };
+                        yield msg_error!(Msg::TaskFailed, error = e);
                         failed += 1;
                     }
                 }
             }
 
+            let recorded = match ArtifactPolicy::load_default().await {
+                Ok(policy) => history::append(&ctx.root, &records, &policy),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = recorded {
+                yield msg_error!(Msg::RunHistoryFailed, error = e);
+            }
+
             // If any failures in this tier, stop (deps failed, downstream won't work)
             if failed > 0 {
-                yield HyperforgeEvent::Error {
-                    message: format!("Tier {tier_idx} had failures — stopping"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("Tier {tier_idx} had failures — stopping"),
+                );
                 break;
             }
         }
@@ -387,9 +447,10 @@ pub fn init_configs(
                 match config.save(&repo.path) {
                     Ok(()) => { written += 1; }
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to write config for {name}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("Failed to write config for {name}: {e}"),
+                        );
                     }
                 }
             }
@@ -449,9 +510,10 @@ async fn execute_runner_cmd(
                 "-w".to_string(),
                 "/workspace".to_string(),
             ];
-            for (k, v) in env {
+            // Forward env by name so values (possibly secrets) stay out of argv
+            for k in env.keys() {
                 docker_args.push("-e".to_string());
-                docker_args.push(format!("{k}={v}"));
+                docker_args.push(k.clone());
             }
             docker_args.push(img.to_string());
             docker_args.push("sh".to_string());
@@ -459,7 +521,8 @@ async fn execute_runner_cmd(
             docker_args.push(shell_cmd);
 
             let mut command = tokio::process::Command::new("docker");
-            command.args(&docker_args);
+            command.args(&docker_args).envs(env);
+            let _slot = Scheduler::global().acquire(Resource::Container).await;
 
             let result = tokio::time::timeout(
                 std::time::Duration::from_secs(timeout_secs),
@@ -0,0 +1,568 @@
+//! Managed files: enforce declared files across repos.
+//!
+//! `[managed]` in workspace config lists files (`.editorconfig`,
+//! `renovate.json`, license headers, ...) with glob targeting and optional
+//! templating; repos opt out per path via `unmanaged_files` in their own
+//! config. The same engine backs `templates_sync`. Drift is tracked in the
+//! workspace generated-file manifest, so hand edits are never silently lost.
+
+use async_stream::stream;
+use futures::Stream;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::build_system::generated::{self, DriftPolicy, GeneratedManifest, ManifestEntry, Reconcile};
+use crate::commands::runner::{discover_or_bail, run_batch_blocking};
+use crate::commands::workspace::DiscoveredRepo;
+use crate::config::workspace::ManagedFile;
+use crate::config::WorkspaceConfig;
+use crate::git::Git;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
+use crate::hubs::utils::{dry_prefix, RepoFilter};
+use crate::scheduler::Scheduler;
+
+/// Commit message when `[managed].message` isn't set
+pub(crate) const DEFAULT_MESSAGE: &str = "chore: sync managed files";
+
+/// Files to write into one repo, as `(path relative to repo, content)`
+pub(crate) type RepoFiles = Vec<(String, String)>;
+
+/// Batch result for one repo: `(dir_name, outcomes or error)`
+pub(crate) type RepoSyncResult = Result<(String, Result<Vec<FileOutcome>, String>), String>;
+
+/// Settings shared by every repo in one sync run
+#[derive(Clone)]
+pub(crate) struct SyncOptions {
+    message: String,
+    branch: Option<String>,
+    /// `(remote, branch)` to cut `branch` from, after a fetch; `None` cuts
+    /// it from HEAD
+    base: Option<(String, String)>,
+    policy: DriftPolicy,
+    dry_run: bool,
+    commit: bool,
+}
+
+impl SyncOptions {
+    pub(crate) fn new(
+        message: String,
+        branch: Option<String>,
+        dry_run: Option<bool>,
+        no_commit: Option<bool>,
+        force: Option<bool>,
+    ) -> Self {
+        Self {
+            message,
+            branch,
+            base: None,
+            policy: if force.unwrap_or(false) {
+                DriftPolicy::Force
+            } else {
+                DriftPolicy::Refuse
+            },
+            dry_run: dry_run.unwrap_or(false),
+            commit: !no_commit.unwrap_or(false),
+        }
+    }
+
+    /// Cut the branch from `<remote>/<base>` instead of HEAD, resetting it
+    /// if it already exists, so every run starts from the up-to-date base
+    pub(crate) fn cut_from(mut self, remote: &str, base: &str) -> Self {
+        self.base = Some((remote.to_string(), base.to_string()));
+        self
+    }
+}
+
+/// Outcome of syncing one file into one repo
+pub(crate) struct FileOutcome {
+    file_path: PathBuf,
+    action: Reconcile,
+    /// Manifest update to record once the whole batch is done
+    entry: Option<ManifestEntry>,
+}
+
+impl FileOutcome {
+    /// Whether this file was (or, in a dry run, would be) written
+    pub(crate) const fn is_change(&self) -> bool {
+        matches!(self.action, Reconcile::Created | Reconcile::Updated | Reconcile::Merged)
+    }
+}
+
+/// Whether a repo's `unmanaged_files` opts it out of `path`
+pub(crate) fn opted_out(unmanaged: &[String], path: &str) -> bool {
+    unmanaged.iter().any(|p| p == "*" || p.trim_end_matches('/') == path)
+}
+
+/// The `unmanaged_files` opt-outs of a discovered repo
+pub(crate) fn unmanaged_files(repo: &DiscoveredRepo) -> Vec<String> {
+    repo.config
+        .as_ref()
+        .map(|c| c.unmanaged_files.clone())
+        .unwrap_or_default()
+}
+
+/// Reconcile `files` (`(path relative to repo, content)`) into one repo and
+/// commit the changes, either on the checked-out branch or on
+/// `options.branch` (created from HEAD if missing, or cut from
+/// `options.base` when set).
+pub(crate) fn sync_repo(
+    workspace_root: &Path,
+    repo_path: &Path,
+    files: &[(String, String)],
+    options: &SyncOptions,
+) -> Result<Vec<FileOutcome>, String> {
+    let switch_back = match (&options.branch, options.dry_run || !options.commit) {
+        (Some(branch), false) => {
+            let status = Git::repo_status(repo_path).map_err(|e| format!("git status failed: {e}"))?;
+            if status.has_changes || status.has_staged {
+                return Err("repo has uncommitted changes — commit or stash first".to_string());
+            }
+            match &options.base {
+                Some((remote, base)) => {
+                    Git::fetch(repo_path, remote)
+                        .map_err(|e| format!("git fetch {remote} failed: {e}"))?;
+                    Git::checkout_reset(repo_path, branch, &format!("{remote}/{base}"))
+                        .map_err(|e| format!("git checkout -B {branch} {remote}/{base} failed: {e}"))?;
+                }
+                None => Git::checkout_or_create(repo_path, branch)
+                    .map_err(|e| format!("git checkout {branch} failed: {e}"))?,
+            }
+            Some(status)
+        }
+        _ => None,
+    };
+
+    let result = write_and_commit(workspace_root, repo_path, files, options);
+
+    if let Some(original) = switch_back {
+        Git::restore_head(repo_path, &original)
+            .map_err(|e| format!("failed to return to the original HEAD: {e}"))?;
+    }
+    result
+}
+
+fn write_and_commit(
+    workspace_root: &Path,
+    repo_path: &Path,
+    files: &[(String, String)],
+    options: &SyncOptions,
+) -> Result<Vec<FileOutcome>, String> {
+    let repo_rel = repo_path
+        .strip_prefix(workspace_root)
+        .map_err(|_| format!("{} is outside the workspace", repo_path.display()))?;
+
+    // Refuse to commit on top of someone else's staged work
+    if options.commit && !options.dry_run && options.branch.is_none() {
+        let status = Git::repo_status(repo_path).map_err(|e| format!("git status failed: {e}"))?;
+        if status.has_staged {
+            return Err("repo has staged changes — commit or unstage first".to_string());
+        }
+    }
+
+    let mut outcomes = Vec::new();
+    let mut written = Vec::new();
+    for (rel, content) in files {
+        let manifest_key = repo_rel.join(rel).to_string_lossy().to_string();
+        let reconciled = generated::reconcile(workspace_root, &manifest_key, content, options.policy)?;
+        let mut entry = None;
+        if !options.dry_run {
+            entry = generated::write(workspace_root, &manifest_key, &reconciled, content)?;
+            if reconciled.write.is_some() {
+                written.push(rel.as_str());
+            }
+        }
+        outcomes.push(FileOutcome {
+            file_path: repo_path.join(rel),
+            action: reconciled.action,
+            entry,
+        });
+    }
+
+    if options.commit && !written.is_empty() {
+        for rel in &written {
+            Git::add(repo_path, rel).map_err(|e| format!("git add {rel} failed: {e}"))?;
+        }
+        Git::commit(repo_path, &options.message).map_err(|e| format!("git commit failed: {e}"))?;
+    }
+
+    Ok(outcomes)
+}
+
+/// Record the manifest entries of every repo in one write. Repos sync in
+/// parallel, so workers hand their entries back instead of touching the
+/// manifest themselves.
+pub(crate) fn record_manifest(
+    workspace_root: &Path,
+    results: &[RepoSyncResult],
+) -> Result<(), String> {
+    let entries: Vec<ManifestEntry> = results
+        .iter()
+        .filter_map(|r| match r {
+            Ok((_, Ok(outcomes))) => Some(outcomes),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|o| o.entry.clone())
+        .collect();
+    GeneratedManifest::record(workspace_root, &entries)
+}
+
+/// Turn per-repo sync results into events: one `ManagedFile` per changed or
+/// conflicting file, errors per failed repo, and a closing summary.
+pub(crate) fn outcome_events(
+    results: Vec<RepoSyncResult>,
+    prefix: &str,
+    label: &str,
+) -> Vec<HyperforgeEvent> {
+    let mut events = Vec::new();
+    let mut changed = 0usize;
+    let mut conflicts = 0usize;
+    let mut failed = 0usize;
+
+    for result in results {
+        match result {
+            Ok((name, Ok(outcomes))) => {
+                for o in outcomes {
+                    let action = match o.action {
+                        Reconcile::Created => "created",
+                        Reconcile::Updated => "updated",
+                        Reconcile::Unchanged => continue,
+                        Reconcile::Merged => "merged",
+                        Reconcile::Conflict => "conflict",
+                    };
+                    if action == "conflict" {
+                        conflicts += 1;
+                    } else {
+                        changed += 1;
+                    }
+                    events.push(HyperforgeEvent::ManagedFile {
+                        repo_name: name.clone(),
+                        file_path: o.file_path.to_string_lossy().to_string(),
+                        action: action.to_string(),
+                    });
+                }
+            }
+            Ok((name, Err(e))) => {
+                failed += 1;
+                events.push(HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    format!("  {name}: {e}"),
+                ));
+            }
+            Err(e) => {
+                failed += 1;
+                events.push(msg_error!(Msg::TaskFailed, error = e));
+            }
+        }
+    }
+
+    if conflicts > 0 {
+        events.push(HyperforgeEvent::Info {
+            message: format!(
+                "{conflicts} file(s) were edited by hand since the last sync and left untouched — re-run with --force to overwrite."
+            ),
+        });
+    }
+    events.push(HyperforgeEvent::Info {
+        message: format!(
+            "{prefix}{label}: {changed} files changed, {conflicts} drifted, {failed} repos failed"
+        ),
+    });
+    events
+}
+
+/// Substitute `{{var}}` placeholders. Unknown variables are an error so a
+/// typo never ships into every repo.
+fn render_template(text: &str, vars: &BTreeMap<&str, String>) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| "unterminated {{ in template".to_string())?;
+        let name = after[..end].trim();
+        let value = vars
+            .get(name)
+            .ok_or_else(|| format!("unknown template variable '{name}'"))?;
+        out.push_str(value);
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// The files `repo` should carry: targeted, not opted out, rendered.
+pub(crate) fn plan_repo(
+    workspace_root: &Path,
+    repo: &DiscoveredRepo,
+    files: &[ManagedFile],
+    year: &str,
+) -> Result<RepoFiles, String> {
+    let unmanaged = unmanaged_files(repo);
+    let mut planned = Vec::new();
+
+    for file in files {
+        let targeting = RepoFilter::new(Some(file.repos.clone()), Some(file.exclude.clone()));
+        if !targeting.matches(&repo.dir_name) || opted_out(&unmanaged, &file.path) {
+            continue;
+        }
+        let raw = match (&file.source, &file.content) {
+            (Some(source), None) => std::fs::read_to_string(workspace_root.join(source))
+                .map_err(|e| format!("{}: failed to read source {source}: {e}", file.path))?,
+            (None, Some(content)) => content.clone(),
+            _ => {
+                return Err(format!(
+                    "{}: exactly one of `source` and `content` must be set",
+                    file.path
+                ))
+            }
+        };
+        let content = if file.template {
+            let vars = BTreeMap::from([
+                ("repo_name", repo.effective_name()),
+                ("dir_name", repo.dir_name.clone()),
+                (
+                    "org",
+                    repo.config
+                        .as_ref()
+                        .and_then(|c| c.org.clone())
+                        .unwrap_or_default(),
+                ),
+                ("year", year.to_string()),
+            ]);
+            render_template(&raw, &vars).map_err(|e| format!("{}: {e}", file.path))?
+        } else {
+            raw
+        };
+        planned.push((file.path.clone(), content));
+    }
+
+    Ok(planned)
+}
+
+pub fn files_sync(
+    path: String,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    dry_run: Option<bool>,
+    no_commit: Option<bool>,
+    force: Option<bool>,
+) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+    let filter = RepoFilter::new(include, exclude);
+
+    stream! {
+        let workspace_path = PathBuf::from(&path);
+        let prefix = dry_prefix(dry_run.unwrap_or(false));
+
+        let ctx = match discover_or_bail(&workspace_path) {
+            Ok(ctx) => ctx,
+            Err(event) => { yield event; return; }
+        };
+
+        let managed = match WorkspaceConfig::load(&ctx.root) {
+            Ok(c) => c.managed,
+            Err(e) => {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                return;
+            }
+        };
+
+        if managed.files.is_empty() {
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "No managed files declared in {} — add [[managed.files]] entries.",
+                    WorkspaceConfig::config_path(&ctx.root).display()
+                ),
+            };
+            return;
+        }
+
+        let year = chrono::Utc::now().format("%Y").to_string();
+        let mut work: Vec<(String, PathBuf, RepoFiles)> = Vec::new();
+        for repo in ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)) {
+            match plan_repo(&ctx.root, repo, &managed.files, &year) {
+                Ok(files) if files.is_empty() => {}
+                Ok(files) => work.push((repo.dir_name.clone(), repo.path.clone(), files)),
+                Err(e) => {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Git,
+                        format!("  {}: {e}", repo.dir_name),
+                    );
+                }
+            }
+        }
+
+        if work.is_empty() {
+            yield HyperforgeEvent::Info {
+                message: "No repos targeted by managed files.".to_string(),
+            };
+            return;
+        }
+
+        yield HyperforgeEvent::Info {
+            message: format!("{}Syncing managed files across {} repos...", prefix, work.len()),
+        };
+
+        let options = SyncOptions::new(
+            managed.message.unwrap_or_else(|| DEFAULT_MESSAGE.to_string()),
+            managed.branch,
+            dry_run,
+            no_commit,
+            force,
+        );
+        let root = ctx.root.clone();
+        let results = run_batch_blocking(work, Scheduler::global().batch(None), move |(dir_name, repo_path, files)| {
+            let result = sync_repo(&root, &repo_path, &files, &options);
+            (dir_name, result)
+        })
+        .await;
+
+        if let Err(e) = record_manifest(&ctx.root, &results) {
+            yield HyperforgeEvent::error(HyperforgeError::Config, e);
+        }
+        for event in outcome_events(results, prefix, "Managed files") {
+            yield event;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let vars = BTreeMap::from([("repo_name", "api".to_string()), ("year", "2026".to_string())]);
+        assert_eq!(
+            render_template("(c) {{year}} {{ repo_name }}\n", &vars).unwrap(),
+            "(c) 2026 api\n"
+        );
+        assert!(render_template("{{nope}}", &vars).unwrap_err().contains("nope"));
+        assert!(render_template("{{year", &vars).is_err());
+    }
+
+    #[test]
+    fn test_opted_out() {
+        let unmanaged = vec![".editorconfig".to_string()];
+        assert!(opted_out(&unmanaged, ".editorconfig"));
+        assert!(!opted_out(&unmanaged, "renovate.json"));
+        assert!(opted_out(&["*".to_string()], "renovate.json"));
+    }
+
+    #[test]
+    fn test_sync_detects_drift_on_later_runs() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("api");
+        std::fs::create_dir_all(&repo).unwrap();
+        let options = SyncOptions::new(DEFAULT_MESSAGE.to_string(), None, None, Some(true), None);
+        let v1 = vec![(".editorconfig".to_string(), "v1\n".to_string())];
+
+        let first = write_and_commit(temp.path(), &repo, &v1, &options).unwrap();
+        assert_eq!(first[0].action, Reconcile::Created);
+        record_manifest(temp.path(), &[Ok(("api".to_string(), Ok(first)))]).unwrap();
+
+        // Hand edit, then the declared content moves on
+        std::fs::write(repo.join(".editorconfig"), "v1\nlocal\n").unwrap();
+        let v2 = vec![(".editorconfig".to_string(), "v2\n".to_string())];
+        let second = write_and_commit(temp.path(), &repo, &v2, &options).unwrap();
+        assert_eq!(second[0].action, Reconcile::Conflict);
+        assert_eq!(
+            std::fs::read_to_string(repo.join(".editorconfig")).unwrap(),
+            "v1\nlocal\n"
+        );
+    }
+
+    #[test]
+    fn test_force_enforces_unchanged_file_over_hand_edit() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("api");
+        std::fs::create_dir_all(&repo).unwrap();
+        let files = vec![(".editorconfig".to_string(), "v1\n".to_string())];
+
+        let options = SyncOptions::new(DEFAULT_MESSAGE.to_string(), None, None, Some(true), None);
+        let first = write_and_commit(temp.path(), &repo, &files, &options).unwrap();
+        record_manifest(temp.path(), &[Ok(("api".to_string(), Ok(first)))]).unwrap();
+
+        // Hand edit while the declared content stays the same
+        std::fs::write(repo.join(".editorconfig"), "v1\nlocal\n").unwrap();
+        let kept = write_and_commit(temp.path(), &repo, &files, &options).unwrap();
+        assert_eq!(kept[0].action, Reconcile::Unchanged);
+
+        let forced = SyncOptions::new(DEFAULT_MESSAGE.to_string(), None, None, Some(true), Some(true));
+        let enforced = write_and_commit(temp.path(), &repo, &files, &forced).unwrap();
+        assert_eq!(enforced[0].action, Reconcile::Updated);
+        assert_eq!(std::fs::read_to_string(repo.join(".editorconfig")).unwrap(), "v1\n");
+    }
+
+    #[test]
+    fn test_sync_on_branch_restores_detached_head() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("api");
+        std::fs::create_dir_all(&repo).unwrap();
+        for args in [
+            &["init", "-q", "-b", "main"][..],
+            &["config", "user.email", "test@example.com"],
+            &["config", "user.name", "Test"],
+            &["commit", "-q", "--allow-empty", "-m", "init"],
+            &["checkout", "-q", "--detach"],
+        ] {
+            let out = std::process::Command::new("git").args(args).current_dir(&repo).output().unwrap();
+            assert!(out.status.success(), "git {args:?}");
+        }
+        let head = Git::head_commit(&repo).unwrap();
+
+        let options = SyncOptions::new(
+            DEFAULT_MESSAGE.to_string(),
+            Some("hyperforge/managed".to_string()),
+            None,
+            None,
+            None,
+        );
+        let files = vec![(".editorconfig".to_string(), "root = true\n".to_string())];
+        sync_repo(temp.path(), &repo, &files, &options).unwrap();
+
+        assert!(Git::repo_status(&repo).unwrap().detached);
+        assert_eq!(Git::head_commit(&repo).unwrap(), head);
+    }
+
+    #[test]
+    fn test_sync_cuts_branch_from_base() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("api");
+        std::fs::create_dir_all(&repo).unwrap();
+        let git = |args: &[&str]| {
+            let out = std::process::Command::new("git").args(args).current_dir(&repo).output().unwrap();
+            assert!(out.status.success(), "git {args:?}");
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "Test"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "init"]);
+        let base = Git::head_commit(&repo).unwrap();
+        git(&["clone", "-q", "--bare", ".", "../origin.git"]);
+        git(&["remote", "add", "origin", "../origin.git"]);
+        git(&["checkout", "-q", "-b", "feature"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "unrelated work"]);
+
+        let options = SyncOptions::new(
+            DEFAULT_MESSAGE.to_string(),
+            Some("hyperforge/managed".to_string()),
+            None,
+            None,
+            None,
+        )
+        .cut_from("origin", "main");
+        let files = vec![(".editorconfig".to_string(), "root = true\n".to_string())];
+        sync_repo(temp.path(), &repo, &files, &options).unwrap();
+
+        assert_eq!(Git::current_branch(&repo).unwrap(), "feature");
+        assert_eq!(
+            Git::resolve_commit(&repo, "hyperforge/managed~1").unwrap(),
+            Some(base)
+        );
+    }
+}
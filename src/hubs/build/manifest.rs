@@ -4,21 +4,40 @@ use async_stream::stream;
 use futures::Stream;
 use std::path::PathBuf;
 
+use crate::build_system::dep_graph::DepGraph;
+use crate::build_system::generated::{Drift, DriftPolicy};
+use crate::build_system::graph_export::GraphFormat;
 use crate::commands::runner::discover_or_bail;
 use crate::commands::workspace::build_dep_graph;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
 use crate::hubs::utils::dry_prefix;
 
 pub fn unify(
     path: String,
     dry_run: Option<bool>,
+    force: Option<bool>,
+    merge: Option<bool>,
 ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
     let is_dry_run = dry_run.unwrap_or(false);
+    let policy = match (force.unwrap_or(false), merge.unwrap_or(false)) {
+        (true, true) => None,
+        (true, false) => Some(DriftPolicy::Force),
+        (false, true) => Some(DriftPolicy::Merge),
+        (false, false) => Some(DriftPolicy::Refuse),
+    };
 
     stream! {
         let workspace_path = PathBuf::from(&path);
         let dry_prefix = dry_prefix(is_dry_run);
 
+        let Some(policy) = policy else {
+            yield HyperforgeEvent::error(
+                HyperforgeError::Validation,
+                "--force and --merge are mutually exclusive".to_string(),
+            );
+            return;
+        };
+
         // Discover workspace
         let ctx = match discover_or_bail(&workspace_path) {
             Ok(ctx) => ctx,
@@ -51,10 +70,11 @@ pub fn unify(
                 })
                 .collect();
 
-            match crate::build_system::cargo_config::generate_cargo_config(
+            match crate::build_system::cargo_config::generate_cargo_config_with(
                 &ctx.root,
                 &crates,
                 is_dry_run,
+                policy,
             ) {
                 Ok(report) => {
                     let action_str = match report.action {
@@ -62,11 +82,20 @@ pub fn unify(
                         crate::build_system::cargo_config::FileAction::Updated => "updated",
                         crate::build_system::cargo_config::FileAction::Unchanged => "unchanged",
                         crate::build_system::cargo_config::FileAction::Removed => "removed",
+                        crate::build_system::cargo_config::FileAction::Merged => "merged",
+                        crate::build_system::cargo_config::FileAction::Conflict => "conflict",
                     };
+                    let file_path = ctx.root.join(".cargo/config.toml").to_string_lossy().to_string();
+
+                    if report.action == crate::build_system::cargo_config::FileAction::Conflict {
+                        if let Some(drift) = &report.drift {
+                            yield conflict_event("rust", &file_path, drift);
+                        }
+                    }
 
                     yield HyperforgeEvent::UnifyResult {
                         language: "rust".to_string(),
-                        file_path: ctx.root.join(".cargo/config.toml").to_string_lossy().to_string(),
+                        file_path,
                         action: action_str.to_string(),
                     };
 
@@ -93,6 +122,8 @@ pub fn unify(
                             crate::build_system::cargo_config::FileAction::Updated => "updated",
                             crate::build_system::cargo_config::FileAction::Created => "created",
                             crate::build_system::cargo_config::FileAction::Unchanged => "unchanged",
+                            crate::build_system::cargo_config::FileAction::Merged => "merged",
+                            crate::build_system::cargo_config::FileAction::Conflict => "conflict",
                         };
                         yield HyperforgeEvent::UnifyResult {
                             language: "rust".to_string(),
@@ -105,9 +136,10 @@ pub fn unify(
                     }
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to generate .cargo/config.toml: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to generate .cargo/config.toml: {e}"),
+                    );
                 }
             }
         }
@@ -127,21 +159,31 @@ pub fn unify(
                 })
                 .collect();
 
-            match crate::build_system::cabal_project::generate_cabal_project(
+            match crate::build_system::cabal_project::generate_cabal_project_with(
                 &ctx.root,
                 &packages,
                 is_dry_run,
+                policy,
             ) {
                 Ok(report) => {
                     let action_str = match report.action {
                         crate::build_system::cabal_project::FileAction::Created => "created",
                         crate::build_system::cabal_project::FileAction::Updated => "updated",
                         crate::build_system::cabal_project::FileAction::Unchanged => "unchanged",
+                        crate::build_system::cabal_project::FileAction::Merged => "merged",
+                        crate::build_system::cabal_project::FileAction::Conflict => "conflict",
                     };
+                    let file_path = ctx.root.join("cabal.project").to_string_lossy().to_string();
+
+                    if report.action == crate::build_system::cabal_project::FileAction::Conflict {
+                        if let Some(drift) = &report.drift {
+                            yield conflict_event("haskell", &file_path, drift);
+                        }
+                    }
 
                     yield HyperforgeEvent::UnifyResult {
                         language: "haskell".to_string(),
-                        file_path: ctx.root.join("cabal.project").to_string_lossy().to_string(),
+                        file_path,
                         action: action_str.to_string(),
                     };
 
@@ -155,21 +197,131 @@ pub fn unify(
                     };
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to generate cabal.project: {e}"),
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to generate cabal.project: {e}"),
+                    );
+                }
+            }
+        }
+
+        // Collect Node packages
+        let node_repos = ctx.repos_for_build_system(&crate::build_system::BuildSystemKind::Node);
+        if !node_repos.is_empty() {
+            yield HyperforgeEvent::Info {
+                message: format!("  Found {} Node packages", node_repos.len()),
+            };
+
+            let packages: Vec<crate::build_system::node_workspace::NodePackageInfo> = node_repos
+                .iter()
+                .map(|repo| crate::build_system::node_workspace::NodePackageInfo {
+                    name: crate::build_system::node::node_package_name(&repo.path)
+                        .unwrap_or_else(|| repo.dir_name.clone()),
+                    path: repo.dir_name.clone(),
+                    dependencies: crate::build_system::node::parse_node_deps(&repo.path),
+                })
+                .collect();
+
+            match crate::build_system::node_workspace::generate_node_workspace(
+                &ctx.root,
+                &packages,
+                is_dry_run,
+                policy,
+            ) {
+                Ok(report) => {
+                    let action_str = match report.action {
+                        crate::build_system::node_workspace::FileAction::Created => "created",
+                        crate::build_system::node_workspace::FileAction::Updated => "updated",
+                        crate::build_system::node_workspace::FileAction::Unchanged => "unchanged",
+                        crate::build_system::node_workspace::FileAction::Merged => "merged",
+                        crate::build_system::node_workspace::FileAction::Conflict => "conflict",
+                    };
+                    let file_name = report.manager.file_name();
+                    let file_path = ctx.root.join(file_name).to_string_lossy().to_string();
+
+                    if report.action == crate::build_system::node_workspace::FileAction::Conflict {
+                        if let Some(drift) = &report.drift {
+                            yield conflict_event("node", &file_path, drift);
+                        }
+                    }
+
+                    yield HyperforgeEvent::UnifyResult {
+                        language: "node".to_string(),
+                        file_path,
+                        action: action_str.to_string(),
+                    };
+
+                    yield HyperforgeEvent::Info {
+                        message: format!(
+                            "{}{} ({}): {} packages, {} overrides [{}]",
+                            dry_prefix,
+                            file_name,
+                            report.manager,
+                            report.packages.len(),
+                            report.overrides.len(),
+                            action_str
+                        ),
                     };
+
+                    for (name, spec) in &report.overrides {
+                        yield HyperforgeEvent::Info {
+                            message: format!("  override: {name} -> {spec}"),
+                        };
+                    }
+                }
+                Err(e) => {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to generate Node workspace file: {e}"),
+                    );
                 }
             }
         }
 
-        if rust_repos.is_empty() && cabal_repos.is_empty() {
+        if rust_repos.is_empty() && cabal_repos.is_empty() && node_repos.is_empty() {
             yield HyperforgeEvent::Info {
-                message: "No Rust or Haskell projects found — nothing to unify.".to_string(),
+                message: "No Rust, Haskell, or Node projects found — nothing to unify.".to_string(),
             };
         }
     }
 }
 
+/// Build the conflict event for a hand-edited generated file.
+fn conflict_event(language: &str, file_path: &str, drift: &Drift) -> HyperforgeEvent {
+    HyperforgeEvent::UnifyConflict {
+        language: language.to_string(),
+        file_path: file_path.to_string(),
+        recorded_hash: drift.recorded_hash.clone(),
+        current_hash: drift.current_hash.clone(),
+    }
+}
+
+/// Events describing every cycle in `graph` and the edges suggested to break them.
+pub(crate) fn cycle_diagnosis_events(graph: &DepGraph) -> Vec<HyperforgeEvent> {
+    let diagnosis = graph.diagnose_cycles();
+    let mut events: Vec<HyperforgeEvent> = diagnosis
+        .cycles
+        .into_iter()
+        .map(|cycle| HyperforgeEvent::DepCycle { cycle })
+        .collect();
+    if diagnosis.truncated {
+        events.push(HyperforgeEvent::Info {
+            message: format!(
+                "  (showing the first {} cycles)",
+                crate::build_system::dep_graph::MAX_CYCLES
+            ),
+        });
+    }
+    events.extend(diagnosis.breaks.into_iter().map(|b| HyperforgeEvent::CycleBreak {
+        from: b.from,
+        to: b.to,
+        edge_kind: b.kind.as_str().to_string(),
+        cycles_broken: b.cycles_broken,
+        suggestion: b.suggestion,
+    }));
+    events
+}
+
 pub fn analyze(
     path: String,
     format: Option<String>,
@@ -219,9 +371,13 @@ pub fn analyze(
                         }
                     }
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Cycle detected: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Validation,
+                            format!("Cycle detected: {e}"),
+                        );
+                        for event in cycle_diagnosis_events(&graph) {
+                            yield event;
+                        }
                     }
                 }
 
@@ -300,11 +456,19 @@ pub fn analyze(
             }
 
             other => {
-                yield HyperforgeEvent::Error {
-                    message: format!(
-                        "Unknown format '{other}'. Valid: summary, graph, mismatches"
-                    ),
-                };
+                if let Some(format) = GraphFormat::parse(other) {
+                    yield HyperforgeEvent::DepGraphExport {
+                        format: format.as_str().to_string(),
+                        content: crate::build_system::graph_export::render(&graph, format),
+                    };
+                } else {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Validation,
+                        format!(
+                            "Unknown format '{other}'. Valid: summary, graph, mismatches, dot, mermaid, json"
+                        ),
+                    );
+                }
             }
         }
     }
@@ -3,7 +3,10 @@
 //! These methods never write `LocalForge` or call forge APIs. They operate purely
 //! on the filesystem via workspace discovery.
 
+pub mod bench;
 pub mod binstall;
+pub mod channels;
+pub mod codeowners;
 pub mod dirty;
 pub mod dist;
 pub mod execution;
@@ -12,30 +15,36 @@ pub mod homebrew;
 pub mod large_files;
 pub mod loc;
 pub mod local_run;
+pub mod managed_files;
 pub mod manifest;
 pub mod packaging;
 pub mod release;
 pub mod repo_size;
+pub mod templates;
+pub mod toolchains;
+pub mod update_bots;
+pub mod verify;
 
+use plexus_core::plexus::Activation;
 use futures::Stream;
 
+use crate::help;
 use crate::hub::HyperforgeEvent;
+use crate::hubs::HyperforgeState;
+use crate::output;
 use crate::types::config::DistChannel;
 use crate::types::Forge;
+use crate::scheduler::Scheduler;
 
 /// Sub-hub for development tools: manifest generation, publishing, cross-repo execution.
 #[derive(Clone)]
-pub struct BuildHub;
-
-impl BuildHub {
-    pub const fn new() -> Self {
-        Self
-    }
+pub struct BuildHub {
+    state: HyperforgeState,
 }
 
-impl Default for BuildHub {
-    fn default() -> Self {
-        Self::new()
+impl BuildHub {
+    pub const fn new(state: HyperforgeState) -> Self {
+        Self { state }
     }
 }
 
@@ -45,28 +54,46 @@ impl Default for BuildHub {
     crate_path = "plexus_core"
 )]
 impl BuildHub {
-    /// Generate/update native workspace manifests (Cargo.toml, cabal.project)
+    /// This hub's method catalog
+    #[plexus_macros::method(
+        description = "List this hub's methods: what each does, its parameters (type, required or optional, description) and an example invocation",
+        params(
+            method = "Only describe this method (optional)"
+        )
+    )]
+    pub async fn help(
+        &self,
+        method: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, futures::stream::iter(help::events(&self.plugin_schema(), "synapse substrate hyperforge build", method.as_deref())))
+    }
+
+    /// Generate/update native workspace manifests (Cargo.toml, cabal.project, pnpm/yarn workspaces)
     #[plexus_macros::method(
-        description = "Generate workspace config files (.cargo/config.toml with [patch.crates-io], cabal.project) from detected build systems. Each repo stays independent while sibling crates resolve locally.",
+        description = "Generate workspace config files (.cargo/config.toml with [patch.crates-io], cabal.project, pnpm-workspace.yaml or yarn workspaces with file: overrides) from detected build systems. Each repo stays independent while sibling crates resolve locally. Hand-edited files are detected by hash and reported as conflicts instead of being overwritten.",
         params(
             path = "Path to workspace directory",
-            dry_run = "Preview without writing files (optional, default: false)"
+            dry_run = "Preview without writing files (optional, default: false)",
+            force = "Overwrite generated files even if they were edited by hand (optional, default: false)",
+            merge = "Three-way merge hand edits with the regenerated content; conflicting edits are left untouched (optional, default: false)"
         )
     )]
     pub async fn unify(
         &self,
         path: String,
         dry_run: Option<bool>,
+        force: Option<bool>,
+        merge: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        manifest::unify(path, dry_run)
+        output::structured(None, &self.state.config_dir, manifest::unify(path, dry_run, force, merge))
     }
 
     /// Analyze workspace dependency graph and detect version mismatches
     #[plexus_macros::method(
-        description = "Analyze workspace dependency graph: show build tiers, dependency relationships, and version mismatches between pinned and local versions.",
+        description = "Analyze workspace dependency graph: show build tiers, dependency relationships, and version mismatches between pinned and local versions. Export the graph as DOT, Mermaid, or JSON for visualization.",
         params(
             path = "Path to workspace directory",
-            format = "Output format: 'summary' (default), 'graph', or 'mismatches'"
+            format = "Output format: 'summary' (default), 'graph', 'mismatches', or a graph export: 'dot', 'mermaid', 'json'"
         )
     )]
     pub async fn analyze(
@@ -74,7 +101,7 @@ impl BuildHub {
         path: String,
         format: Option<String>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        manifest::analyze(path, format)
+        output::structured(None, &self.state.config_dir, manifest::analyze(path, format))
     }
 
     /// Detect mismatches between directory names and package names
@@ -88,7 +115,7 @@ impl BuildHub {
         &self,
         path: String,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        manifest::detect_name_mismatches(path)
+        output::structured(None, &self.state.config_dir, manifest::detect_name_mismatches(path))
     }
 
     /// Compare local package versions against their registries
@@ -97,7 +124,8 @@ impl BuildHub {
         params(
             path = "Path to workspace root directory",
             include = "Glob patterns — repo must match at least one (optional, repeatable)",
-            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)"
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            concurrency = "Repos worked on at once (optional, default: [concurrency] batch in config.toml, else 8; 0 = unbounded)"
         )
     )]
     pub async fn package_diff(
@@ -105,8 +133,10 @@ impl BuildHub {
         path: String,
         include: Option<Vec<String>>,
         exclude: Option<Vec<String>>,
+        concurrency: Option<u32>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        packaging::package_diff(path, include, exclude)
+        let batch = Scheduler::global().batch(concurrency.map(|n| n as usize));
+        output::structured(None, &self.state.config_dir, packaging::package_diff(path, include, exclude, batch))
     }
 
     /// Publish packages with transitive dependency resolution
@@ -119,7 +149,8 @@ impl BuildHub {
             execute = "Actually publish to registries (default: false, dry-run unless set)",
             no_tag = "Skip creating git tags after publish (optional, default: false)",
             no_commit = "Skip auto-commit after version bumps (optional, default: false)",
-            bump = "Version bump kind for auto-bump: patch, minor, major (optional, default: patch)"
+            bump = "Version bump kind for auto-bump: patch, minor, major (optional, default: patch)",
+            format = "Output format: text, or json for typed events only as NDJSON (optional, default: [output] format in config.toml, else text)"
         )
     )]
     pub async fn publish(
@@ -131,8 +162,13 @@ impl BuildHub {
         no_tag: Option<bool>,
         no_commit: Option<bool>,
         bump: Option<String>,
+        format: Option<String>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        packaging::publish(path, include, exclude, execute, no_tag, no_commit, bump)
+        output::structured(
+            format.as_deref(),
+            &self.state.config_dir,
+            packaging::publish(path, include, exclude, execute, no_tag, no_commit, bump),
+        )
     }
 
     /// Bump versions for workspace packages
@@ -156,7 +192,7 @@ impl BuildHub {
         commit: Option<bool>,
         dry_run: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        packaging::bump(path, include, exclude, bump, commit, dry_run)
+        output::structured(None, &self.state.config_dir, packaging::bump(path, include, exclude, bump, commit, dry_run))
     }
 
     /// Run a command across all workspace repos
@@ -180,7 +216,7 @@ impl BuildHub {
         sequential: Option<bool>,
         dirty: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        execution::exec(path, command, include, exclude, sequential, dirty)
+        output::structured(None, &self.state.config_dir, execution::exec(path, command, include, exclude, sequential, dirty))
     }
 
     /// Validate workspace builds in Docker containers
@@ -190,7 +226,11 @@ impl BuildHub {
             path = "Path to workspace directory",
             test = "Also run tests after builds (optional, default: false)",
             dry_run = "Preview validation plan without running Docker (optional, default: false)",
-            image = "Docker image to use (optional, default: rust:latest)"
+            image = "Docker image to use (optional, default: rust:latest)",
+            shards = "Split each repo's tests across this many parallel containers when the runner supports it (cargo nextest, jest); overrides ci.test_shards (optional)",
+            changed_since = "Git ref: test only repos changed since it plus the repos depending on them; jest in a repo that changed itself also gets --changedSince, nextest and other runners run their full suite (optional)",
+            coverage = "Also run each repo's coverage command (ci.coverage.command, default cargo-llvm-cov or jest) and report line coverage against ci.coverage.min_line_pct; writes .hyperforge/coverage/lcov.info (optional, default: false)",
+            min_coverage = "Fail when workspace-wide line coverage is below this percentage (optional)"
         )
     )]
     pub async fn validate(
@@ -199,8 +239,74 @@ impl BuildHub {
         test: Option<bool>,
         dry_run: Option<bool>,
         image: Option<String>,
+        shards: Option<u32>,
+        changed_since: Option<String>,
+        coverage: Option<bool>,
+        min_coverage: Option<f64>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, execution::validate(path, test, dry_run, image, shards, changed_since, coverage, min_coverage))
+    }
+
+    /// Report flaky steps and build-time trends from run history
+    #[plexus_macros::method(
+        description = "Report validation steps that alternate between pass and fail (flaky) and steps whose passing runs are getting slower, from the run history that `validate` and `run` record in .hyperforge/history.",
+        params(
+            path = "Path to workspace directory",
+            window = "Recent runs per step to consider (optional, default: 10)",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)"
+        )
+    )]
+    pub async fn validate_report(
+        &self,
+        path: String,
+        window: Option<usize>,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        execution::validate(path, test, dry_run, image)
+        output::structured(None, &self.state.config_dir, execution::validate_report(path, window, include, exclude))
+    }
+
+    /// Run declared benchmarks and gate on regressions against the last baseline
+    #[plexus_macros::method(
+        description = "Run each repo's [ci.bench].command, parse libtest/criterion timings (or time the whole command), store a baseline keyed by HEAD commit in .hyperforge/bench, and compare against the previous commit's baseline. Regressions beyond the threshold fail the run unless warn_only is set.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            threshold = "Slowdown in percent that counts as a regression; [ci.bench].max_regression_pct overrides it per repo (optional, default: 10)",
+            warn_only = "Report regressions as warnings instead of failing (optional, default: false)",
+            dry_run = "List the benchmark commands without running them (optional, default: false)"
+        )
+    )]
+    pub async fn bench(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        threshold: Option<f64>,
+        warn_only: Option<bool>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, bench::bench(path, include, exclude, threshold, warn_only, dry_run))
+    }
+
+    /// Verify release artifact signatures and provenance
+    #[plexus_macros::method(
+        description = "Check the release assets in a directory: each cosign .sig or minisign .minisig against the public key, and each asset's sha256 against the SLSA provenance statement (*.intoto.json) written by a signed release.",
+        params(
+            path = "Directory holding the downloaded or built assets (e.g. target/dist)",
+            public_key = "Public key file matching the signing key (optional; signatures are skipped without it)",
+            provenance = "Provenance statement to check against (optional, default: the *.intoto.json in path)"
+        )
+    )]
+    pub async fn verify_artifacts(
+        &self,
+        path: String,
+        public_key: Option<String>,
+        provenance: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, verify::verify_artifacts(path, public_key, provenance))
     }
 
     /// Run build/test commands using layered CI runners
@@ -226,7 +332,7 @@ impl BuildHub {
         dry_run: Option<bool>,
         parallel: Option<usize>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        local_run::run(path, test, level, include, exclude, dry_run, parallel)
+        output::structured(None, &self.state.config_dir, local_run::run(path, test, level, include, exclude, dry_run, parallel))
     }
 
     /// Initialize CI configs for repos that lack them
@@ -246,7 +352,7 @@ impl BuildHub {
         exclude: Option<Vec<String>>,
         dry_run: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        local_run::init_configs(path, include, exclude, dry_run)
+        output::structured(None, &self.state.config_dir, local_run::init_configs(path, include, exclude, dry_run))
     }
 
     /// Ensure sane .gitignore patterns across all workspace repos
@@ -257,7 +363,8 @@ impl BuildHub {
             patterns = "Extra patterns to add beyond defaults (optional)",
             include = "Glob patterns — repo must match at least one (optional, repeatable)",
             exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
-            dry_run = "Preview without writing files (optional, default: false)"
+            dry_run = "Preview without writing files (optional, default: false)",
+            concurrency = "Repos worked on at once (optional, default: [concurrency] batch in config.toml, else 8; 0 = unbounded)"
         )
     )]
     pub async fn gitignore_sync(
@@ -267,8 +374,110 @@ impl BuildHub {
         include: Option<Vec<String>>,
         exclude: Option<Vec<String>>,
         dry_run: Option<bool>,
+        concurrency: Option<u32>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let batch = Scheduler::global().batch(concurrency.map(|n| n as usize));
+        output::structured(None, &self.state.config_dir, gitignore::gitignore_sync(path, patterns, include, exclude, dry_run, batch))
+    }
+
+    /// Generate CODEOWNERS in every repo from the workspace ownership map
+    #[plexus_macros::method(
+        description = "Generate each repo's CODEOWNERS from the [owners] table (owner → path globs) in .hyperforge/workspace.toml. An existing CODEOWNERS location (.github/, .gitlab/, docs/) is kept, otherwise the root is used. Hand-edited files are reported as conflicts; changed files are committed per repo.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            dry_run = "Preview without writing files (optional, default: false)",
+            no_commit = "Write CODEOWNERS but don't commit it (optional, default: false)",
+            force = "Overwrite CODEOWNERS even if it was edited by hand (optional, default: false)"
+        )
+    )]
+    pub async fn codeowners(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        dry_run: Option<bool>,
+        no_commit: Option<bool>,
+        force: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, codeowners::codeowners(path, include, exclude, dry_run, no_commit, force))
+    }
+
+    /// Copy issue/PR templates and community files from a source repo
+    #[plexus_macros::method(
+        description = "Copy issue templates, PR/MR templates, CONTRIBUTING.md and SECURITY.md from a designated \"dotfiles\" repo in the workspace into every other repo. Source, file list and target branch default to [templates] in .hyperforge/workspace.toml. Files edited by hand since the last sync are reported as drift and left untouched; changes are committed per repo.",
+        params(
+            path = "Path to workspace directory",
+            source = "Directory name of the template source repo (optional, default: [templates].source)",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            branch = "Commit onto this branch (created from HEAD if missing) instead of the checked-out branch (optional, default: [templates].branch)",
+            dry_run = "Preview without writing files (optional, default: false)",
+            no_commit = "Write files but don't commit them (optional, default: false)",
+            force = "Overwrite template files even if they were edited by hand (optional, default: false)"
+        )
+    )]
+    pub async fn templates_sync(
+        &self,
+        path: String,
+        source: Option<String>,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        branch: Option<String>,
+        dry_run: Option<bool>,
+        no_commit: Option<bool>,
+        force: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, templates::templates_sync(path, source, include, exclude, branch, dry_run, no_commit, force))
+    }
+
+    /// Enforce the managed files declared in workspace config
+    #[plexus_macros::method(
+        description = "Write the files declared under [[managed.files]] in .hyperforge/workspace.toml (e.g. renovate.json, .editorconfig, license headers) into every targeted repo. Each file has repo globs, an inline or workspace-relative source, and optional {{repo_name}}/{{dir_name}}/{{org}}/{{year}} templating; repos opt out via unmanaged_files in their config. Changes are committed per repo with the shared [managed].message; hand-edited files are reported as drift.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            dry_run = "Preview without writing files (optional, default: false)",
+            no_commit = "Write files but don't commit them (optional, default: false)",
+            force = "Overwrite managed files even if they were edited by hand (optional, default: false)"
+        )
+    )]
+    pub async fn files_sync(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        dry_run: Option<bool>,
+        no_commit: Option<bool>,
+        force: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, managed_files::files_sync(path, include, exclude, dry_run, no_commit, force))
+    }
+
+    /// Write dependency-update bot config into every repo
+    #[plexus_macros::method(
+        description = "Write Renovate (renovate.json) or Dependabot (.github/dependabot.yml) configuration into every repo from [updates] in .hyperforge/workspace.toml. Dependabot entries are generated per detected ecosystem (cargo, npm, GitHub Actions). Hand-edited configs are reported as drift; changes are committed per repo.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            dry_run = "Preview without writing files (optional, default: false)",
+            no_commit = "Write config files but don't commit them (optional, default: false)",
+            force = "Overwrite bot config even if it was edited by hand (optional, default: false)"
+        )
+    )]
+    pub async fn updates_config(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        dry_run: Option<bool>,
+        no_commit: Option<bool>,
+        force: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        gitignore::gitignore_sync(path, patterns, include, exclude, dry_run)
+        output::structured(None, &self.state.config_dir, update_bots::updates_config(path, include, exclude, dry_run, no_commit, force))
     }
 
     /// Find large tracked files across workspace repos
@@ -288,7 +497,7 @@ impl BuildHub {
         include: Option<Vec<String>>,
         exclude: Option<Vec<String>>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        large_files::large_files(path, threshold_kb, include, exclude)
+        output::structured(None, &self.state.config_dir, large_files::large_files(path, threshold_kb, include, exclude))
     }
 
     /// Show total tracked-file size for each workspace repo
@@ -306,7 +515,7 @@ impl BuildHub {
         include: Option<Vec<String>>,
         exclude: Option<Vec<String>>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        repo_size::repo_sizes(path, include, exclude)
+        output::structured(None, &self.state.config_dir, repo_size::repo_sizes(path, include, exclude))
     }
 
     /// Count lines of code per repo
@@ -324,7 +533,7 @@ impl BuildHub {
         include: Option<Vec<String>>,
         exclude: Option<Vec<String>>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        loc::loc(path, include, exclude)
+        output::structured(None, &self.state.config_dir, loc::loc(path, include, exclude))
     }
 
     /// Check which repos have uncommitted changes
@@ -344,7 +553,7 @@ impl BuildHub {
         exclude: Option<Vec<String>>,
         all_git: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        dirty::dirty(path, include, exclude, all_git)
+        output::structured(None, &self.state.config_dir, dirty::dirty(path, include, exclude, all_git))
     }
 
     /// Cross-compile, package, create forge releases, and upload assets
@@ -353,7 +562,7 @@ impl BuildHub {
         params(
             path = "Path to workspace or repo directory",
             tag = "Git tag for the release (e.g. v4.1.0)",
-            targets = "Comma-separated target triples (optional, defaults to native host)",
+            targets = "Comma-separated target triples or matrix shorthands: all, linux, macos, windows, linux/aarch64 (optional, defaults to dist config, then native host)",
             include = "Glob patterns — repo must match at least one (optional, repeatable)",
             exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
             forge = "Target forge: github, codeberg, or gitlab (optional, defaults to all configured)",
@@ -378,7 +587,7 @@ impl BuildHub {
         dry_run: Option<bool>,
         skip_auth_check: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        release::release(path, tag, targets, include, exclude, forge, title, body, draft, dry_run, skip_auth_check)
+        output::structured(None, &self.state.config_dir, release::release(path, tag, targets, include, exclude, forge, title, body, draft, dry_run, skip_auth_check))
     }
 
     /// Release all binary-producing packages in workspace in dependency order
@@ -387,7 +596,7 @@ impl BuildHub {
         params(
             path = "Path to workspace directory",
             tag = "Git tag for the release (e.g. v4.1.0)",
-            targets = "Comma-separated target triples (optional, defaults to native host)",
+            targets = "Comma-separated target triples or matrix shorthands: all, linux, macos, windows, linux/aarch64 (optional, defaults to dist config, then native host)",
             include = "Glob patterns — repo must match at least one (optional, repeatable)",
             exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
             forge = "Target forge: github, codeberg, or gitlab (optional, defaults to all configured)",
@@ -412,7 +621,7 @@ impl BuildHub {
         dry_run: Option<bool>,
         skip_auth_check: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        release::release_all(path, tag, targets, include, exclude, forge, title, body, draft, dry_run, skip_auth_check)
+        output::structured(None, &self.state.config_dir, release::release_all(path, tag, targets, include, exclude, forge, title, body, draft, dry_run, skip_auth_check))
     }
 
     /// Inject cargo-binstall metadata into Cargo.toml files
@@ -434,7 +643,7 @@ impl BuildHub {
         forge: Option<Forge>,
         dry_run: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        binstall::binstall_init(path, include, exclude, forge, dry_run)
+        output::structured(None, &self.state.config_dir, binstall::binstall_init(path, include, exclude, forge, dry_run))
     }
 
     /// Generate a Homebrew formula from release assets
@@ -460,7 +669,7 @@ impl BuildHub {
         description: Option<String>,
         dry_run: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        homebrew::brew_formula(org, name, tag, forge, tap_path, description, dry_run)
+        output::structured(None, &self.state.config_dir, homebrew::brew_formula(org, name, tag, forge, tap_path, description, dry_run))
     }
 
     /// Show distribution config for workspace repos
@@ -478,7 +687,7 @@ impl BuildHub {
         include: Option<Vec<String>>,
         exclude: Option<Vec<String>>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        dist::dist_show(path, include, exclude)
+        output::structured(None, &self.state.config_dir, dist::dist_show(path, include, exclude))
     }
 
     /// Initialize distribution config for workspace repos
@@ -489,7 +698,7 @@ impl BuildHub {
             include = "Glob patterns — repo must match at least one (optional, repeatable)",
             exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
             channels = "Distribution channels to configure (optional, repeatable; overrides auto-detect)",
-            targets = "Comma-separated target triples (optional, overrides auto-detect)",
+            targets = "Comma-separated target triples or matrix shorthands like all or linux (optional, overrides auto-detect)",
             brew_tap = "Homebrew tap repo (e.g. hypermemetic/homebrew-tap) (optional)",
             force = "Overwrite existing [dist] config (optional, default: false)",
             dry_run = "Preview without writing files (optional, default: false)"
@@ -506,6 +715,6 @@ impl BuildHub {
         force: Option<bool>,
         dry_run: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        dist::dist_init(path, include, exclude, channels, targets, brew_tap, force, dry_run)
+        output::structured(None, &self.state.config_dir, dist::dist_init(path, include, exclude, channels, targets, brew_tap, force, dry_run))
     }
 }
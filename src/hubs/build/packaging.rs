@@ -9,9 +9,59 @@ use crate::build_system::BuildSystemKind;
 use crate::commands::runner::{discover_or_bail, run_batch};
 use crate::commands::workspace::build_publish_dep_graph;
 use crate::git::Git;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
 use crate::hubs::utils::{dry_prefix, RepoFilter};
-use crate::package::DriftResult;
+use crate::package::{DriftResult, RegistryClient};
+
+/// Sign the package cargo left in `target/package/` and write signed SLSA
+/// provenance next to it, for repos with `[dist.signing]`. npm doesn't keep
+/// its tarball, so only crates are attested. Returns a message to report.
+async fn attest_package(
+    repo_path: &Path,
+    build_system: &BuildSystemKind,
+    name: &str,
+    version: &str,
+) -> Option<String> {
+    use crate::build_system::signing::{provenance_statement, write_provenance, BuildSource, Signer};
+
+    let config = crate::config::HyperforgeConfig::load(repo_path).ok()?;
+    let signing = config.dist.as_ref()?.signing.clone()?;
+    if *build_system != BuildSystemKind::Cargo {
+        return None;
+    }
+    let package_dir = repo_path.join("target").join("package");
+    let artifact = package_dir.join(format!("{name}-{version}.crate"));
+    if !artifact.exists() {
+        return Some(format!("  Warning: {} not found, package not attested", artifact.display()));
+    }
+
+    let result = async {
+        let signer = Signer::from_config(&signing).await?;
+        let mut signed = vec![signer.sign(&artifact).await?];
+        if signing.provenance {
+            let forge = config.forges.first().map_or("github", String::as_str);
+            let org = config.org.as_deref().unwrap_or_default();
+            let repository = super::release::forge_homepage(forge, org, &config.get_repo_name(repo_path));
+            let commit = Git::head_commit(repo_path).map_err(|e| e.to_string())?;
+            let tag = format!("{name}-v{version}");
+            let source = BuildSource { repository: &repository, tag: &tag, commit: &commit, started_on: chrono::Utc::now() };
+            let statement = provenance_statement(std::slice::from_ref(&artifact), &source)?;
+            let provenance = write_provenance(&package_dir, &format!("{name}-{version}"), &statement)?;
+            signed.push(signer.sign(&provenance).await?);
+            signed.push(provenance);
+        }
+        Ok::<_, String>(signed)
+    }
+    .await;
+
+    Some(match result {
+        Ok(files) => format!(
+            "  Attested {name} v{version}: {}",
+            files.iter().filter_map(|f| f.file_name()?.to_str()).collect::<Vec<_>>().join(", ")
+        ),
+        Err(e) => format!("  Warning: failed to attest {name} v{version}: {e}"),
+    })
+}
 
 /// Result of a version bump + commit + tag operation.
 struct BumpResult {
@@ -139,6 +189,7 @@ pub fn package_diff(
     path: String,
     include: Option<Vec<String>>,
     exclude: Option<Vec<String>>,
+    batch: usize,
 ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
     stream! {
         let filter = RepoFilter::new(include, exclude);
@@ -226,7 +277,7 @@ pub fn package_diff(
         }
 
         // Run all registry queries + drift detection in parallel
-        let results = run_batch(work_items, 8, |item| async move {
+        let results = run_batch(work_items, batch, |item| async move {
             let registry = match crate::package::registry_for(&item.build_system) {
                 Some(r) => r,
                 None => return None,
@@ -284,9 +335,9 @@ pub fn package_diff(
         for result in results {
             match result {
                 Ok(Some(Ok(event))) => yield event,
-                Ok(Some(Err(msg))) => yield HyperforgeEvent::Error { message: msg },
+                Ok(Some(Err(msg))) => yield HyperforgeEvent::error(HyperforgeError::ForgeApi, msg),
                 Ok(None) => {}
-                Err(e) => yield HyperforgeEvent::Error { message: e },
+                Err(e) => yield HyperforgeEvent::error(HyperforgeError::Internal, e),
             }
         }
     }
@@ -354,9 +405,10 @@ pub fn publish(
         ).await {
             Ok(p) => p,
             Err(e) => {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to build publish plan: {e}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("Failed to build publish plan: {e}"),
+                );
                 return;
             }
         };
@@ -405,9 +457,17 @@ pub fn publish(
             }
 
             let build_system = &step.build_system;
-            let registry = match crate::package::registry_for(build_system) {
-                Some(r) => r,
-                None => continue,
+            // Forge package registries publish after the primary one; without a
+            // public registry the first usable forge registry is the primary
+            let mut forge_registries = crate::package::forge_registries_for(&step.path, build_system).await;
+            let registry: Box<dyn RegistryClient> = if let Some(r) = crate::package::registry_for(build_system) {
+                r
+            } else {
+                let Some(i) = forge_registries.iter().position(|(_, c)| c.is_ok()) else { continue };
+                match forge_registries.remove(i).1 {
+                    Ok(client) => Box::new(client),
+                    Err(_) => continue,
+                }
             };
             let registry_kind = registry.registry_kind();
 
@@ -479,7 +539,7 @@ pub fn publish(
                                 package_name: step.name.clone(),
                                 version: step.target_version.clone(),
                                 registry: registry_kind.clone(),
-                                action: action_kind,
+                                action: action_kind.clone(),
                                 success: true,
                                 error: None,
                             };
@@ -504,6 +564,37 @@ pub fn publish(
                                     };
                                 }
                             }
+
+                            // Sign and attest the packaged artifact when the repo signs releases
+                            if !is_dry_run {
+                                if let Some(message) = attest_package(&step.path, build_system, &step.name, &step.target_version).await {
+                                    yield HyperforgeEvent::Info { message };
+                                }
+                            }
+
+                            // Mirror to forge package registries. A failure here
+                            // doesn't fail dependents: the primary publish succeeded.
+                            for (forge_kind, client) in forge_registries {
+                                let outcome = match client {
+                                    Ok(client) => client
+                                        .publish(&step.path, &step.name, is_dry_run)
+                                        .await
+                                        .map_err(|e| e.to_string())
+                                        .and_then(|r| if r.success { Ok(()) } else { Err(r.error.unwrap_or_default()) }),
+                                    Err(e) => Err(e),
+                                };
+                                if outcome.is_err() {
+                                    failed_count += 1;
+                                }
+                                yield HyperforgeEvent::PublishStep {
+                                    package_name: step.name.clone(),
+                                    version: step.target_version.clone(),
+                                    registry: forge_kind,
+                                    action: if outcome.is_ok() { action_kind.clone() } else { crate::hub::PublishActionKind::Failed },
+                                    success: outcome.is_ok(),
+                                    error: outcome.err(),
+                                };
+                            }
                         }
                         Ok(pr) => {
                             // Publish returned but was not successful
@@ -1,8 +1,9 @@
-//! Build release orchestrator: cross-compile, package, create forge releases, upload assets.
+//! Build release orchestrator: cross-compile, package, create forge releases, upload assets,
+//! then bump the brew/aur channels (see [`super::channels`]).
 
 use async_stream::stream;
 use futures::Stream;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -11,17 +12,27 @@ use crate::adapters::releases::github::GitHubReleaseAdapter;
 use crate::adapters::releases::ReleasePort;
 use crate::auth::credentials::preflight_check;
 use crate::auth::YamlAuthProvider;
-use crate::build_system::cross_compile::{compile_and_package, host_triple, TargetTriple};
+use crate::build_system::cross_compile::{
+    builder_for, compile_and_package, expand_targets, host_triple, write_checksums, TargetTriple,
+    CHECKSUMS_FILE,
+};
 use crate::build_system::{self, BinaryTarget};
 use crate::commands::runner::discover_or_bail;
 use crate::commands::workspace::{build_publish_dep_graph, DiscoveredRepo};
 use crate::config::HyperforgeConfig;
 use crate::git::Git;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
 use crate::hubs::utils::RepoFilter;
 use crate::types::config::DistChannel;
 use crate::types::Forge;
 
+use crate::build_system::signing::{provenance_statement, write_provenance, BuildSource, Signer};
+use crate::types::config::SigningConfig;
+
+use super::channels::{released_assets, update_channels, ReleaseMeta};
+
 pub(crate) fn make_auth() -> Result<Arc<YamlAuthProvider>, String> {
     YamlAuthProvider::new()
         .map(Arc::new)
@@ -50,6 +61,10 @@ fn guess_content_type(filename: &str) -> &'static str {
         "application/gzip"
     } else if filename.ends_with(".zip") {
         "application/zip"
+    } else if filename == CHECKSUMS_FILE {
+        "text/plain"
+    } else if filename.ends_with(".json") {
+        "application/json"
     } else {
         "application/octet-stream"
     }
@@ -160,6 +175,7 @@ fn load_dist_config(repo: &DiscoveredRepo) -> Option<crate::types::config::DistC
 }
 
 /// Resolve target triples for a repo, consulting dist config when no CLI override is provided.
+/// Entries may be matrix shorthands (`all`, `linux`, `macos/aarch64`); see [`expand_targets`].
 fn resolve_targets_with_dist(
     cli_targets: &Option<String>,
     repo: &DiscoveredRepo,
@@ -167,13 +183,13 @@ fn resolve_targets_with_dist(
     // CLI override takes priority
     if let Some(ref s) = cli_targets {
         if !s.is_empty() {
-            return s.split(',').map(|t| TargetTriple::new(t.trim())).collect();
+            return expand_targets(&s.split(',').collect::<Vec<_>>());
         }
     }
     // Check dist config
     if let Some(dist) = load_dist_config(repo) {
         if !dist.targets.is_empty() {
-            return dist.targets.iter().map(TargetTriple::new).collect();
+            return expand_targets(&dist.targets);
         }
     }
     // Fallback to native host
@@ -255,9 +271,10 @@ async fn run_release_preflight(
     let auth = match make_auth() {
         Ok(a) => a,
         Err(e) => {
-            return vec![HyperforgeEvent::Error {
-                message: format!("Pre-flight: {e}"),
-            }];
+            return vec![HyperforgeEvent::error(
+                HyperforgeError::Auth,
+                format!("Pre-flight: {e}"),
+            )];
         }
     };
 
@@ -296,6 +313,7 @@ async fn release_single_repo(
     };
 
     let dry_prefix = if is_dry_run { "[dry-run] " } else { "" };
+    let started_on = chrono::Utc::now();
 
     let (bin_targets, version) = if let Some(info) = repo_binary_info(repo) { info } else {
         events.push(HyperforgeEvent::Info {
@@ -327,6 +345,19 @@ async fn release_single_repo(
     let mut archives: Vec<PathBuf> = Vec::new();
 
     for triple in target_triples {
+        // Targets this host can't build (macOS/MSVC elsewhere) are skipped, not failed
+        if *bs_kind == build_system::BuildSystemKind::Cargo {
+            if let Err(reason) = builder_for(triple) {
+                events.push(HyperforgeEvent::ReleaseBuildStep {
+                    repo_name: repo_name.clone(),
+                    target: triple.triple.clone(),
+                    status: "skipped".to_string(),
+                    detail: Some(reason),
+                });
+                continue;
+            }
+        }
+
         events.push(HyperforgeEvent::ReleaseBuildStep {
             repo_name: repo_name.clone(),
             target: triple.triple.clone(),
@@ -379,17 +410,86 @@ async fn release_single_repo(
                 }
             } else {
                 let err = result.error.unwrap_or_else(|| "unknown error".to_string());
-                events.push(HyperforgeEvent::Error {
-                    message: format!(
+                events.push(HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!(
                         "{}: compile/package failed for {}: {}",
                         repo_name, triple.triple, err
                     ),
+                ));
+                counts.failed += 1;
+            }
+        }
+    }
+
+    // Checksum the archives; the checksum file is uploaded with them
+    if is_dry_run {
+        if counts.targets > 0 {
+            events.push(HyperforgeEvent::ReleaseBuildStep {
+                repo_name: repo_name.clone(),
+                target: "all".to_string(),
+                status: "checksumming".to_string(),
+                detail: Some(format!("would write {CHECKSUMS_FILE}")),
+            });
+        }
+    } else if !archives.is_empty() {
+        match write_checksums(&archives, &output_dir) {
+            Ok(path) => {
+                events.push(HyperforgeEvent::ReleaseBuildStep {
+                    repo_name: repo_name.clone(),
+                    target: "all".to_string(),
+                    status: "checksumming".to_string(),
+                    detail: Some(format!("wrote {CHECKSUMS_FILE} for {} archive(s)", archives.len())),
                 });
+                archives.push(path);
+            }
+            Err(e) => {
+                events.push(HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("{repo_name}: checksums failed: {e}"),
+                ));
                 counts.failed += 1;
             }
         }
     }
 
+    // Sign every asset and attach provenance over them
+    if let Some(signing) = load_dist_config(repo).and_then(|d| d.signing) {
+        if is_dry_run {
+            if counts.targets > 0 {
+                events.push(HyperforgeEvent::ReleaseBuildStep {
+                    repo_name: repo_name.clone(),
+                    target: "all".to_string(),
+                    status: "signing".to_string(),
+                    detail: Some(format!(
+                        "would sign with {}{}",
+                        signing.tool,
+                        if signing.provenance { " and attach SLSA provenance" } else { "" }
+                    )),
+                });
+            }
+        } else if !archives.is_empty() {
+            match sign_release(repo, &signing, &archives, &output_dir, tag, &version, started_on).await {
+                Ok(signed) => {
+                    events.push(HyperforgeEvent::ReleaseBuildStep {
+                        repo_name: repo_name.clone(),
+                        target: "all".to_string(),
+                        status: "signing".to_string(),
+                        detail: Some(format!("signed {} asset(s) with {}", archives.len(), signing.tool)),
+                    });
+                    archives.extend(signed);
+                }
+                Err(e) => {
+                    events.push(HyperforgeEvent::error(
+                        HyperforgeError::Validation,
+                        format!("{repo_name}: signing failed: {e}"),
+                    ));
+                    counts.failed += 1;
+                }
+            }
+        }
+    }
+
     // Create git tag if needed
     if !is_dry_run && !Git::tag_exists(&repo.path, tag) {
         events.push(HyperforgeEvent::Info {
@@ -404,14 +504,16 @@ async fn release_single_repo(
             Ok(output) if output.status.success() => {}
             Ok(output) => {
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                events.push(HyperforgeEvent::Error {
-                    message: format!("  Failed to create tag {tag}: {stderr}"),
-                });
+                events.push(HyperforgeEvent::error(
+                    HyperforgeError::Git,
+                    format!("  Failed to create tag {tag}: {stderr}"),
+                ));
             }
             Err(e) => {
-                events.push(HyperforgeEvent::Error {
-                    message: format!("  Failed to run git tag: {e}"),
-                });
+                events.push(HyperforgeEvent::error(
+                    HyperforgeError::Git,
+                    format!("  Failed to run git tag: {e}"),
+                ));
             }
         }
     } else if is_dry_run {
@@ -452,11 +554,23 @@ async fn release_single_repo(
                     org,
                     repo_name,
                     forge_name,
-                    target_triples.len(),
+                    counts.targets + usize::from(counts.targets > 0),
                 ),
             });
             counts.forges.insert(forge_name.clone());
         }
+        if let Some(dist) = load_dist_config(repo) {
+            let homepage = forge_homepage(&target_forges[0], &org, &repo_name);
+            let description = release_description(repo);
+            let meta = ReleaseMeta {
+                name: &repo_name,
+                version: &version,
+                binary: &binary_names[0],
+                description: &description,
+                homepage: &homepage,
+            };
+            events.extend(update_channels(repo, &dist, &meta, &[], true).0);
+        }
         return (events, counts);
     }
 
@@ -464,12 +578,15 @@ async fn release_single_repo(
     let auth = match make_auth() {
         Ok(a) => a,
         Err(e) => {
-            events.push(HyperforgeEvent::Error { message: e });
+            events.push(HyperforgeEvent::error(HyperforgeError::Auth, e));
             counts.failed += 1;
             return (events, counts);
         }
     };
 
+    // forge → uploaded asset filename → download URL, for channel updates
+    let mut uploaded: HashMap<String, HashMap<String, String>> = HashMap::new();
+
     for forge_name in &target_forges {
         let adapter = match make_release_adapter(forge_name, auth.clone(), &org) {
             Ok(a) => a,
@@ -555,7 +672,11 @@ async fn release_single_repo(
                         )
                         .await
                     {
-                        Ok(_) => {
+                        Ok(asset) => {
+                            uploaded
+                                .entry(forge_name.clone())
+                                .or_default()
+                                .insert(filename.clone(), asset.download_url);
                             events.push(HyperforgeEvent::ReleaseUpload {
                                 repo_name: repo_name.clone(),
                                 forge: forge_name.clone(),
@@ -595,9 +716,83 @@ async fn release_single_repo(
         }
     }
 
+    // Bump brew/aur from the first forge that got the archives
+    let dist = load_dist_config(repo);
+    let urls = target_forges.iter().find_map(|f| uploaded.get(f).map(|u| (f, u)));
+    if let (Some(dist), Some((forge_name, urls))) = (dist, urls) {
+        match released_assets(&archives, urls) {
+            Ok(assets) => {
+                let homepage = forge_homepage(forge_name, &org, &repo_name);
+                let description = release_description(repo);
+                let meta = ReleaseMeta {
+                    name: &repo_name,
+                    version: &version,
+                    binary: &binary_names[0],
+                    description: &description,
+                    homepage: &homepage,
+                };
+                let (channel_events, channel_failed) = update_channels(repo, &dist, &meta, &assets, false);
+                events.extend(channel_events);
+                counts.failed += channel_failed;
+            }
+            Err(e) => {
+                events.push(HyperforgeEvent::error(
+                    HyperforgeError::Internal,
+                    format!("  {repo_name}: can't checksum archives for channel updates: {e}"),
+                ));
+                counts.failed += 1;
+            }
+        }
+    }
+
     (events, counts)
 }
 
+/// Sign `archives` and, if configured, write a provenance statement over
+/// them (signed as well). Returns the files to upload alongside.
+async fn sign_release(
+    repo: &DiscoveredRepo,
+    signing: &SigningConfig,
+    archives: &[PathBuf],
+    output_dir: &std::path::Path,
+    tag: &str,
+    version: &str,
+    started_on: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<PathBuf>, String> {
+    let signer = Signer::from_config(signing).await?;
+    let mut to_sign = archives.to_vec();
+    if signing.provenance {
+        let repository = forge_homepage(repo.forges().first().copied().unwrap_or("github"), repo.org().unwrap_or_default(), &repo.dir_name);
+        let commit = Git::head_commit(&repo.path).map_err(|e| e.to_string())?;
+        let source = BuildSource { repository: &repository, tag, commit: &commit, started_on };
+        let statement = provenance_statement(archives, &source)?;
+        to_sign.push(write_provenance(output_dir, &format!("{}-v{version}", repo.dir_name), &statement)?);
+    }
+
+    let mut extra = to_sign[archives.len()..].to_vec();
+    for artifact in &to_sign {
+        extra.push(signer.sign(artifact).await?);
+    }
+    Ok(extra)
+}
+
+/// Web URL of a repo on a forge
+pub(crate) fn forge_homepage(forge: &str, org: &str, repo: &str) -> String {
+    match forge {
+        "codeberg" => format!("https://codeberg.org/{org}/{repo}"),
+        "gitlab" => format!("https://gitlab.com/{org}/{repo}"),
+        _ => format!("https://github.com/{org}/{repo}"),
+    }
+}
+
+/// Package description for channel metadata
+fn release_description(repo: &DiscoveredRepo) -> String {
+    repo.config
+        .as_ref()
+        .and_then(|c| c.description.clone())
+        .unwrap_or_else(|| format!("{} prebuilt binaries", repo.dir_name))
+}
+
 pub fn release(
     path: String,
     tag: String,
@@ -628,9 +823,10 @@ pub fn release(
             match discover_single_repo(&workspace_path) {
                 Ok(r) => vec![r],
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to discover repo: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to discover repo: {e}"),
+                    );
                     return;
                 }
             }
@@ -803,9 +999,7 @@ pub fn release_all(
         let ordered_indices = match dep_graph.topo_order() {
             Ok(order) => order,
             Err(e) => {
-                yield HyperforgeEvent::Error {
-                    message: format!("Dependency cycle detected: {e}"),
-                };
+                yield msg_error!(Msg::DependencyCycle, error = e);
                 // Fall back to original order
                 (0..all_repos.len()).collect()
             }
@@ -5,7 +5,9 @@ use futures::Stream;
 use std::path::PathBuf;
 
 use crate::commands::runner::discover_or_bail;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
 use crate::hubs::utils::RepoFilter;
 
 /// Measure total tracked-file size for a single repo.
@@ -88,17 +90,16 @@ pub fn repo_sizes(
             match result {
                 Ok((repo_name, tracked_files, total_bytes, error)) => {
                     if let Some(e) = error {
-                        yield HyperforgeEvent::Error {
-                            message: format!("{repo_name}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Git,
+                            format!("{repo_name}: {e}"),
+                        );
                     } else {
                         entries.push((repo_name, tracked_files, total_bytes));
                     }
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Task error: {e}"),
-                    };
+                    yield msg_error!(Msg::TaskFailed, error = e);
                 }
             }
         }
@@ -0,0 +1,206 @@
+//! Template propagation: copy issue/PR templates and community files from a
+//! designated source repo into every other repo in the workspace.
+//!
+//! A preset over the managed-files engine: the file list comes from the
+//! source repo rather than `[managed]`.
+
+use async_stream::stream;
+use futures::Stream;
+use std::path::{Path, PathBuf};
+
+use super::managed_files::{self, opted_out, outcome_events, RepoFiles, SyncOptions};
+use crate::commands::runner::{discover_or_bail, run_batch_blocking};
+use crate::config::WorkspaceConfig;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::hubs::utils::{dry_prefix, RepoFilter};
+use crate::scheduler::Scheduler;
+
+/// Paths copied when `[templates].files` is empty
+const DEFAULT_TEMPLATE_PATHS: &[&str] = &[
+    ".github/ISSUE_TEMPLATE",
+    ".github/PULL_REQUEST_TEMPLATE.md",
+    ".github/pull_request_template.md",
+    ".gitlab/issue_templates",
+    ".gitlab/merge_request_templates",
+    "CONTRIBUTING.md",
+    "SECURITY.md",
+];
+
+/// Template files in `source`, as `(path relative to repo, content)`.
+/// Directories in `paths` are expanded recursively; missing paths are skipped.
+fn collect_templates(source: &Path, paths: &[String]) -> Result<Vec<(String, String)>, String> {
+    fn walk(root: &Path, rel: &str, out: &mut Vec<(String, String)>) -> Result<(), String> {
+        let full = root.join(rel);
+        if full.is_dir() {
+            let mut entries: Vec<String> = std::fs::read_dir(&full)
+                .map_err(|e| format!("Failed to read {}: {e}", full.display()))?
+                .filter_map(Result::ok)
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect();
+            entries.sort();
+            for name in entries {
+                walk(root, &format!("{rel}/{name}"), out)?;
+            }
+        } else if full.is_file() {
+            let content = std::fs::read_to_string(&full)
+                .map_err(|e| format!("Failed to read template {rel}: {e}"))?;
+            out.push((rel.to_string(), content));
+        }
+        Ok(())
+    }
+
+    let mut out = Vec::new();
+    for path in paths {
+        walk(source, path.trim_end_matches('/'), &mut out)?;
+    }
+    Ok(out)
+}
+
+pub fn templates_sync(
+    path: String,
+    source: Option<String>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    branch: Option<String>,
+    dry_run: Option<bool>,
+    no_commit: Option<bool>,
+    force: Option<bool>,
+) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+    let filter = RepoFilter::new(include, exclude);
+
+    stream! {
+        let workspace_path = PathBuf::from(&path);
+        let prefix = dry_prefix(dry_run.unwrap_or(false));
+
+        let ctx = match discover_or_bail(&workspace_path) {
+            Ok(ctx) => ctx,
+            Err(event) => { yield event; return; }
+        };
+
+        let config = match WorkspaceConfig::load(&ctx.root) {
+            Ok(c) => c.templates,
+            Err(e) => {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                return;
+            }
+        };
+
+        let Some(source) = source.or(config.source) else {
+            yield HyperforgeEvent::error(
+                HyperforgeError::Config,
+                format!(
+                    "No template source — pass --source or set [templates].source in {}",
+                    WorkspaceConfig::config_path(&ctx.root).display()
+                ),
+            );
+            return;
+        };
+        let Some(source_repo) = ctx.repos.iter().find(|r| r.dir_name == source) else {
+            yield HyperforgeEvent::error(
+                HyperforgeError::Config,
+                format!("Template source repo '{source}' not found in workspace"),
+            );
+            return;
+        };
+
+        let paths: Vec<String> = if config.files.is_empty() {
+            DEFAULT_TEMPLATE_PATHS.iter().map(|p| (*p).to_string()).collect()
+        } else {
+            config.files
+        };
+        let templates = match collect_templates(&source_repo.path, &paths) {
+            Ok(t) if t.is_empty() => {
+                yield HyperforgeEvent::Info {
+                    message: format!("No template files found in {source}."),
+                };
+                return;
+            }
+            Ok(t) => t,
+            Err(e) => {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                return;
+            }
+        };
+
+        // Repos opt out of individual template files via `unmanaged_files`
+        let work: Vec<(String, PathBuf, RepoFiles)> = ctx
+            .repos
+            .iter()
+            .filter(|r| r.dir_name != source && filter.matches(&r.dir_name))
+            .map(|r| {
+                let unmanaged = managed_files::unmanaged_files(r);
+                let files = templates
+                    .iter()
+                    .filter(|(rel, _)| !opted_out(&unmanaged, rel))
+                    .cloned()
+                    .collect();
+                (r.dir_name.clone(), r.path.clone(), files)
+            })
+            .collect();
+
+        if work.is_empty() {
+            yield HyperforgeEvent::Info {
+                message: "No repos matched filter.".to_string(),
+            };
+            return;
+        }
+
+        yield HyperforgeEvent::Info {
+            message: format!(
+                "{}Syncing {} template files from {} into {} repos...",
+                prefix,
+                templates.len(),
+                source,
+                work.len()
+            ),
+        };
+
+        let options = SyncOptions::new(
+            format!("chore: sync templates from {source}"),
+            branch.or(config.branch),
+            dry_run,
+            no_commit,
+            force,
+        );
+        let root = ctx.root.clone();
+        let results = run_batch_blocking(work, Scheduler::global().batch(None), move |(dir_name, repo_path, files)| {
+            let result = managed_files::sync_repo(&root, &repo_path, &files, &options);
+            (dir_name, result)
+        })
+        .await;
+
+        if let Err(e) = managed_files::record_manifest(&ctx.root, &results) {
+            yield HyperforgeEvent::error(HyperforgeError::Config, e);
+        }
+        for event in outcome_events(results, prefix, "Templates") {
+            yield event;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_templates_expands_directories() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let src = temp.path();
+        std::fs::create_dir_all(src.join(".github/ISSUE_TEMPLATE")).unwrap();
+        std::fs::write(src.join(".github/ISSUE_TEMPLATE/bug.md"), "bug\n").unwrap();
+        std::fs::write(src.join(".github/ISSUE_TEMPLATE/feature.md"), "feature\n").unwrap();
+        std::fs::write(src.join("SECURITY.md"), "report to security@\n").unwrap();
+
+        let paths: Vec<String> = DEFAULT_TEMPLATE_PATHS.iter().map(|p| (*p).to_string()).collect();
+        let templates = collect_templates(src, &paths).unwrap();
+        let rels: Vec<&str> = templates.iter().map(|(r, _)| r.as_str()).collect();
+        assert_eq!(
+            rels,
+            vec![
+                ".github/ISSUE_TEMPLATE/bug.md",
+                ".github/ISSUE_TEMPLATE/feature.md",
+                "SECURITY.md",
+            ]
+        );
+    }
+}
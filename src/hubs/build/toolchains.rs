@@ -0,0 +1,362 @@
+//! Toolchain pin files: report and converge `rust-toolchain.toml`, `.ghcup`, `.nvmrc`.
+
+use async_stream::stream;
+use futures::Stream;
+use std::path::{Path, PathBuf};
+
+use crate::build_system::BuildSystemKind;
+use crate::commands::runner::{discover_or_bail, run_batch_blocking};
+use crate::config::workspace::ToolchainPins;
+use crate::config::WorkspaceConfig;
+use crate::git::Git;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
+use crate::hubs::utils::{dry_prefix, RepoFilter};
+use crate::scheduler::Scheduler;
+
+/// A toolchain pin file kind, tied to the build system that reads it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PinKind {
+    Rust,
+    Ghc,
+    Node,
+}
+
+impl PinKind {
+    const fn toolchain(self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::Ghc => "ghc",
+            Self::Node => "node",
+        }
+    }
+
+    const fn file_name(self) -> &'static str {
+        match self {
+            Self::Rust => "rust-toolchain.toml",
+            Self::Ghc => ".ghcup",
+            Self::Node => ".nvmrc",
+        }
+    }
+
+    const fn for_build_system(kind: &BuildSystemKind) -> Option<Self> {
+        match kind {
+            BuildSystemKind::Cargo => Some(Self::Rust),
+            BuildSystemKind::Cabal => Some(Self::Ghc),
+            BuildSystemKind::Node => Some(Self::Node),
+            BuildSystemKind::Unknown => None,
+        }
+    }
+
+    fn expected(self, pins: &ToolchainPins) -> Option<&str> {
+        match self {
+            Self::Rust => pins.rust.as_deref(),
+            Self::Ghc => pins.ghc.as_deref(),
+            Self::Node => pins.node.as_deref(),
+        }
+    }
+
+    /// Extract the pinned version from existing file contents.
+    fn parse(self, content: &str) -> Option<String> {
+        match self {
+            Self::Rust => {
+                let doc: toml_edit::DocumentMut = content.parse().ok()?;
+                doc.get("toolchain")?
+                    .get("channel")?
+                    .as_str()
+                    .map(str::to_string)
+            }
+            Self::Ghc | Self::Node => content
+                .lines()
+                .map(str::trim)
+                .find(|l| !l.is_empty() && !l.starts_with('#'))
+                .map(str::to_string),
+        }
+    }
+
+    /// Render new file contents pinning `version`, preserving unrelated
+    /// settings (components, targets) in an existing `rust-toolchain.toml`.
+    fn render(self, existing: Option<&str>, version: &str) -> String {
+        match self {
+            Self::Rust => {
+                let mut doc: toml_edit::DocumentMut = existing
+                    .and_then(|c| c.parse().ok())
+                    .unwrap_or_default();
+                if !doc.contains_table("toolchain") {
+                    doc["toolchain"] = toml_edit::table();
+                }
+                doc["toolchain"]["channel"] = toml_edit::value(version);
+                doc.to_string()
+            }
+            Self::Ghc | Self::Node => format!("{version}\n"),
+        }
+    }
+}
+
+/// Outcome of converging a single pin file.
+struct PinOutcome {
+    kind: PinKind,
+    file_path: PathBuf,
+    current: Option<String>,
+    expected: String,
+    action: &'static str,
+}
+
+/// Converge every applicable pin file in one repo, then commit the changes.
+///
+/// Returns the per-file outcomes, or an error if the repo couldn't be updated.
+fn converge_repo(
+    repo_path: &Path,
+    build_systems: &[BuildSystemKind],
+    pins: &ToolchainPins,
+    dry_run: bool,
+    commit: bool,
+) -> Result<Vec<PinOutcome>, String> {
+    let mut outcomes = Vec::new();
+    let mut kinds: Vec<PinKind> = build_systems
+        .iter()
+        .filter_map(PinKind::for_build_system)
+        .collect();
+    kinds.dedup();
+
+    for kind in kinds {
+        let Some(expected) = kind.expected(pins) else {
+            continue;
+        };
+        let file_path = repo_path.join(kind.file_name());
+        let existing = std::fs::read_to_string(&file_path).ok();
+        let current = existing.as_deref().and_then(|c| kind.parse(c));
+
+        let action = match (&existing, current.as_deref()) {
+            (_, Some(v)) if v == expected => "unchanged",
+            (None, _) => "created",
+            (Some(_), _) => "updated",
+        };
+        outcomes.push(PinOutcome {
+            kind,
+            file_path,
+            current,
+            expected: expected.to_string(),
+            action,
+        });
+    }
+
+    let changed: Vec<&PinOutcome> = outcomes.iter().filter(|o| o.action != "unchanged").collect();
+    if changed.is_empty() || dry_run {
+        return Ok(outcomes);
+    }
+
+    // Refuse to commit on top of someone else's staged work
+    if commit {
+        let status = Git::repo_status(repo_path).map_err(|e| format!("git status failed: {e}"))?;
+        if status.has_staged {
+            return Err("repo has staged changes — commit or unstage first".to_string());
+        }
+    }
+
+    for outcome in &changed {
+        let existing = std::fs::read_to_string(&outcome.file_path).ok();
+        let content = outcome.kind.render(existing.as_deref(), &outcome.expected);
+        std::fs::write(&outcome.file_path, content)
+            .map_err(|e| format!("Failed to write {}: {e}", outcome.kind.file_name()))?;
+    }
+
+    if commit {
+        for outcome in &changed {
+            Git::add(repo_path, outcome.kind.file_name())
+                .map_err(|e| format!("git add {} failed: {e}", outcome.kind.file_name()))?;
+        }
+        let summary: Vec<String> = changed
+            .iter()
+            .map(|o| format!("{} {}", o.kind.toolchain(), o.expected))
+            .collect();
+        let message = format!("chore: pin toolchains ({})", summary.join(", "));
+        Git::commit(repo_path, &message).map_err(|e| format!("git commit failed: {e}"))?;
+    }
+
+    Ok(outcomes)
+}
+
+pub fn toolchains(
+    path: String,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    dry_run: Option<bool>,
+    no_commit: Option<bool>,
+) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+    let is_dry_run = dry_run.unwrap_or(false);
+    let commit = !no_commit.unwrap_or(false);
+    let filter = RepoFilter::new(include, exclude);
+
+    stream! {
+        let workspace_path = PathBuf::from(&path);
+        let prefix = dry_prefix(is_dry_run);
+
+        let ctx = match discover_or_bail(&workspace_path) {
+            Ok(ctx) => ctx,
+            Err(event) => { yield event; return; }
+        };
+
+        let config = match WorkspaceConfig::load(&ctx.root) {
+            Ok(c) => c,
+            Err(e) => {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                return;
+            }
+        };
+        let pins = config.toolchains;
+
+        if pins.is_empty() {
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "No toolchains declared in {} — add a [toolchains] table (rust, ghc, node).",
+                    WorkspaceConfig::config_path(&ctx.root).display()
+                ),
+            };
+            return;
+        }
+
+        let work: Vec<(String, PathBuf, Vec<BuildSystemKind>, ToolchainPins)> = ctx
+            .repos
+            .iter()
+            .filter(|r| filter.matches(&r.dir_name))
+            .map(|r| (r.dir_name.clone(), r.path.clone(), r.build_systems.clone(), pins.clone()))
+            .collect();
+
+        if work.is_empty() {
+            yield HyperforgeEvent::Info {
+                message: "No repos matched filter.".to_string(),
+            };
+            return;
+        }
+
+        yield HyperforgeEvent::Info {
+            message: format!("{}Checking toolchain pins across {} repos...", prefix, work.len()),
+        };
+
+        let results = run_batch_blocking(work, Scheduler::global().batch(None), move |(dir_name, repo_path, build_systems, pins)| {
+            let result = converge_repo(&repo_path, &build_systems, &pins, is_dry_run, commit);
+            (dir_name, result)
+        })
+        .await;
+
+        let mut unchanged = 0usize;
+        let mut changed = 0usize;
+        let mut failed = 0usize;
+
+        for result in results {
+            match result {
+                Ok((name, Ok(outcomes))) => {
+                    for o in outcomes {
+                        if o.action == "unchanged" {
+                            unchanged += 1;
+                        } else {
+                            changed += 1;
+                        }
+                        yield HyperforgeEvent::ToolchainPin {
+                            repo_name: name.clone(),
+                            toolchain: o.kind.toolchain().to_string(),
+                            file_path: o.file_path.to_string_lossy().to_string(),
+                            current: o.current,
+                            expected: o.expected,
+                            action: o.action.to_string(),
+                        };
+                    }
+                }
+                Ok((name, Err(e))) => {
+                    failed += 1;
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("  {name}: {e}"),
+                    );
+                }
+                Err(e) => {
+                    failed += 1;
+                    yield msg_error!(Msg::TaskFailed, error = e);
+                }
+            }
+        }
+
+        yield HyperforgeEvent::Info {
+            message: format!(
+                "{prefix}Toolchain pins: {changed} changed, {unchanged} unchanged, {failed} failed"
+            ),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rust_toolchain() {
+        let content = "[toolchain]\nchannel = \"1.85.0\"\ncomponents = [\"clippy\"]\n";
+        assert_eq!(PinKind::Rust.parse(content), Some("1.85.0".to_string()));
+        assert_eq!(PinKind::Rust.parse("[other]\n"), None);
+    }
+
+    #[test]
+    fn test_render_rust_preserves_components() {
+        let content = "[toolchain]\nchannel = \"1.80.0\"\ncomponents = [\"clippy\"]\n";
+        let rendered = PinKind::Rust.render(Some(content), "1.85.0");
+        assert_eq!(PinKind::Rust.parse(&rendered), Some("1.85.0".to_string()));
+        assert!(rendered.contains("components"));
+
+        let fresh = PinKind::Rust.render(None, "stable");
+        assert_eq!(PinKind::Rust.parse(&fresh), Some("stable".to_string()));
+    }
+
+    #[test]
+    fn test_parse_single_line_pins() {
+        assert_eq!(PinKind::Node.parse("# lts\n22\n"), Some("22".to_string()));
+        assert_eq!(PinKind::Ghc.parse("  9.6.6  \n"), Some("9.6.6".to_string()));
+        assert_eq!(PinKind::Node.parse(""), None);
+    }
+
+    #[test]
+    fn test_converge_dry_run_reports_without_writing() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join(".nvmrc"), "20\n").unwrap();
+        let pins = ToolchainPins {
+            rust: Some("1.85.0".to_string()),
+            ghc: None,
+            node: Some("20".to_string()),
+        };
+
+        let outcomes = converge_repo(
+            temp.path(),
+            &[BuildSystemKind::Cargo, BuildSystemKind::Node],
+            &pins,
+            true,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].action, "created");
+        assert_eq!(outcomes[1].action, "unchanged");
+        assert!(!temp.path().join("rust-toolchain.toml").exists());
+    }
+
+    #[test]
+    fn test_converge_writes_without_commit() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join(".ghcup"), "9.4.8\n").unwrap();
+        let pins = ToolchainPins {
+            rust: None,
+            ghc: Some("9.6.6".to_string()),
+            node: None,
+        };
+
+        let outcomes =
+            converge_repo(temp.path(), &[BuildSystemKind::Cabal], &pins, false, false).unwrap();
+
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].action, "updated");
+        assert_eq!(outcomes[0].current.as_deref(), Some("9.4.8"));
+        let written = std::fs::read_to_string(temp.path().join(".ghcup")).unwrap();
+        assert_eq!(written, "9.6.6\n");
+    }
+}
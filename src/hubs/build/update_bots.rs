@@ -0,0 +1,101 @@
+//! Dependency-update bot config: write `renovate.json` or
+//! `.github/dependabot.yml` into every repo from `[updates]`.
+
+use async_stream::stream;
+use futures::Stream;
+use std::path::PathBuf;
+
+use super::managed_files::{self, opted_out, outcome_events, RepoFiles, SyncOptions};
+use crate::commands::runner::{discover_or_bail, run_batch_blocking};
+use crate::config::WorkspaceConfig;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::hubs::utils::{dry_prefix, RepoFilter};
+use crate::services::updates::config_file;
+use crate::scheduler::Scheduler;
+
+pub fn updates_config(
+    path: String,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    dry_run: Option<bool>,
+    no_commit: Option<bool>,
+    force: Option<bool>,
+) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+    let filter = RepoFilter::new(include, exclude);
+
+    stream! {
+        let workspace_path = PathBuf::from(&path);
+        let prefix = dry_prefix(dry_run.unwrap_or(false));
+
+        let ctx = match discover_or_bail(&workspace_path) {
+            Ok(ctx) => ctx,
+            Err(event) => { yield event; return; }
+        };
+
+        let settings = match WorkspaceConfig::load(&ctx.root) {
+            Ok(c) => c.updates,
+            Err(e) => {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                return;
+            }
+        };
+        let Some(bot) = settings.bot else {
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "No update bot declared in {} — set [updates].bot to renovate or dependabot.",
+                    WorkspaceConfig::config_path(&ctx.root).display()
+                ),
+            };
+            return;
+        };
+
+        let mut work: Vec<(String, PathBuf, RepoFiles)> = Vec::new();
+        for repo in ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)) {
+            let has_workflows = repo.path.join(".github/workflows").is_dir();
+            match config_file(&settings, bot, &repo.build_systems, has_workflows) {
+                Ok(Some((rel, content))) => {
+                    if !opted_out(&managed_files::unmanaged_files(repo), rel) {
+                        work.push((repo.dir_name.clone(), repo.path.clone(), vec![(rel.to_string(), content)]));
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    return;
+                }
+            }
+        }
+
+        if work.is_empty() {
+            yield HyperforgeEvent::Info {
+                message: format!("No repos need a {} config.", bot.as_str()),
+            };
+            return;
+        }
+
+        yield HyperforgeEvent::Info {
+            message: format!("{}Writing {} config into {} repos...", prefix, bot.as_str(), work.len()),
+        };
+
+        let options = SyncOptions::new(
+            format!("chore: configure {}", bot.as_str()),
+            None,
+            dry_run,
+            no_commit,
+            force,
+        );
+        let root = ctx.root.clone();
+        let results = run_batch_blocking(work, Scheduler::global().batch(None), move |(dir_name, repo_path, files)| {
+            let result = managed_files::sync_repo(&root, &repo_path, &files, &options);
+            (dir_name, result)
+        })
+        .await;
+
+        if let Err(e) = managed_files::record_manifest(&ctx.root, &results) {
+            yield HyperforgeEvent::error(HyperforgeError::Config, e);
+        }
+        for event in outcome_events(results, prefix, "Update bot config") {
+            yield event;
+        }
+    }
+}
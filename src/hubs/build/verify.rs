@@ -0,0 +1,132 @@
+//! Verification of signed release assets: signatures against a public key,
+//! and file digests against an SLSA provenance statement.
+
+use async_stream::stream;
+use futures::Stream;
+use std::path::PathBuf;
+
+use crate::build_system::signing::{verify_provenance, verify_signature, SubjectCheck};
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::types::config::SigningTool;
+
+const PROVENANCE_SUFFIX: &str = ".intoto.json";
+
+pub fn verify_artifacts(
+    path: String,
+    public_key: Option<String>,
+    provenance: Option<String>,
+) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+    stream! {
+        let dir = PathBuf::from(&path);
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                yield HyperforgeEvent::error(HyperforgeError::Config, format!("Cannot read {path}: {e}"));
+                return;
+            }
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|e| e.path())
+            .filter(|p| p.is_file())
+            .collect();
+        files.sort();
+
+        let is_provenance = |p: &PathBuf| p.to_string_lossy().ends_with(PROVENANCE_SUFFIX);
+        let signatures: Vec<(PathBuf, SigningTool)> = files
+            .iter()
+            .filter_map(|p| SigningTool::for_signature(p).map(|t| (p.clone(), t)))
+            .collect();
+        let artifacts: Vec<PathBuf> = files
+            .iter()
+            .filter(|p| SigningTool::for_signature(p).is_none() && !is_provenance(p))
+            .cloned()
+            .collect();
+
+        let mut verified = 0usize;
+        let mut failures = Vec::new();
+
+        // Signatures
+        match &public_key {
+            None if !signatures.is_empty() => {
+                yield HyperforgeEvent::Info {
+                    message: format!("{} signature(s) found; pass public_key to check them", signatures.len()),
+                };
+            }
+            None => {}
+            Some(key) => {
+                for (signature, tool) in &signatures {
+                    let name = signature.file_name().unwrap_or_default().to_string_lossy();
+                    let artifact = signature.with_file_name(name.trim_end_matches(tool.signature_extension()));
+                    let artifact_name = artifact.file_name().unwrap_or_default().to_string_lossy().to_string();
+                    let result = if artifact.exists() {
+                        verify_signature(*tool, std::path::Path::new(key), &artifact, signature).await
+                    } else {
+                        Err(format!("{name} has no matching artifact"))
+                    };
+                    match &result {
+                        Ok(()) => verified += 1,
+                        Err(_) => failures.push(artifact_name.clone()),
+                    }
+                    yield HyperforgeEvent::ArtifactVerify {
+                        artifact: artifact_name,
+                        check: format!("{tool} signature"),
+                        success: result.is_ok(),
+                        error: result.err(),
+                    };
+                }
+            }
+        }
+
+        // Provenance
+        let statement_path = provenance.map(PathBuf::from).or_else(|| files.iter().find(|p| is_provenance(p)).cloned());
+        if let Some(statement_path) = statement_path {
+            let statement = std::fs::read_to_string(&statement_path)
+                .map_err(|e| format!("Cannot read {}: {e}", statement_path.display()))
+                .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).map_err(|e| format!("Invalid provenance JSON: {e}")))
+                .and_then(|statement| verify_provenance(&statement, &artifacts));
+            match statement {
+                Ok(results) => {
+                    for (artifact, check) in results {
+                        let artifact = artifact.file_name().unwrap_or_default().to_string_lossy().to_string();
+                        let error = match check {
+                            SubjectCheck::Match => {
+                                verified += 1;
+                                None
+                            }
+                            SubjectCheck::Missing => {
+                                yield HyperforgeEvent::Info {
+                                    message: format!("  {artifact}: not covered by provenance"),
+                                };
+                                continue;
+                            }
+                            SubjectCheck::Mismatch { expected, actual } => {
+                                failures.push(artifact.clone());
+                                Some(format!("sha256 {actual}, provenance says {expected}"))
+                            }
+                        };
+                        yield HyperforgeEvent::ArtifactVerify {
+                            artifact,
+                            check: "provenance".to_string(),
+                            success: error.is_none(),
+                            error,
+                        };
+                    }
+                }
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Validation, e);
+                    return;
+                }
+            }
+        }
+
+        if failures.is_empty() {
+            yield HyperforgeEvent::Info { message: format!("Verified {verified} check(s), no failures") };
+        } else {
+            yield HyperforgeEvent::error(
+                HyperforgeError::Validation,
+                format!("Verification failed for: {}", failures.join(", ")),
+            );
+        }
+    }
+}
@@ -0,0 +1,565 @@
+//! `CampaignsHub` — multi-repo change campaigns
+//!
+//! A child plugin under `WorkspaceHub`, accessible as:
+//!   synapse lforge hyperforge workspace campaigns create --path . --name edition-2021 --script "..."
+//!   synapse lforge hyperforge workspace campaigns run --path . --name edition-2021
+//!   synapse lforge hyperforge workspace campaigns status --path .
+
+use async_stream::stream;
+use plexus_core::plexus::Activation;
+use futures::Stream;
+use std::path::{Path, PathBuf};
+
+use crate::adapters::{NewPullRequest, PullRequestState};
+use crate::commands::runner::{discover_or_bail, run_batch, run_batch_blocking};
+use crate::git::Git;
+use crate::help;
+use crate::output;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
+use crate::hubs::utils::{dry_prefix, forge_host, make_adapter, RepoFilter};
+use crate::hubs::HyperforgeState;
+use crate::services::campaigns::{validate_name, Campaign, CampaignStatus, RepoProgress};
+use crate::scheduler::Scheduler;
+
+/// Sub-hub for change campaigns. Campaign state lives in the workspace; the
+/// hub only needs the config dir, for self-hosted forge hosts.
+#[derive(Clone)]
+pub struct CampaignsHub {
+    state: HyperforgeState,
+}
+
+impl CampaignsHub {
+    pub const fn new(state: HyperforgeState) -> Self {
+        Self { state }
+    }
+}
+
+/// Where a repo's campaign branch goes: its origin forge
+#[derive(Clone)]
+struct CampaignTarget {
+    dir_name: String,
+    path: PathBuf,
+    forge: String,
+    org: String,
+    name: String,
+    remote: String,
+    base: String,
+    /// Self-hosted instance of `forge`, if any
+    host: Option<String>,
+}
+
+/// Outcome of running the campaign script in one repo
+enum ScriptOutcome {
+    Unchanged,
+    /// Changes committed and the branch pushed
+    Pushed,
+}
+
+/// Run `script` on a fresh `branch` off `base`, commit and push whatever it
+/// changed, and return to the previously checked-out branch (or commit, if
+/// HEAD was detached).
+fn apply_script(target: &CampaignTarget, campaign: &Campaign) -> Result<ScriptOutcome, String> {
+    let path = &target.path;
+    let status = Git::repo_status(path).map_err(|e| format!("git status failed: {e}"))?;
+    if status.has_changes || status.has_staged {
+        return Err("repo has uncommitted changes — commit or stash first".to_string());
+    }
+
+    Git::checkout(path, &target.base).map_err(|e| format!("git checkout {} failed: {e}", target.base))?;
+    Git::checkout_or_create(path, &campaign.branch)
+        .map_err(|e| format!("git checkout {} failed: {e}", campaign.branch))?;
+
+    let result = commit_script_changes(path, target, campaign);
+
+    // Never leave the repo on the campaign branch
+    let restore = Git::restore_head(path, &status)
+        .map_err(|e| format!("failed to return to the original HEAD: {e}"));
+    let outcome = result?;
+    restore?;
+    Ok(outcome)
+}
+
+fn commit_script_changes(
+    path: &Path,
+    target: &CampaignTarget,
+    campaign: &Campaign,
+) -> Result<ScriptOutcome, String> {
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(&campaign.script)
+        .current_dir(path)
+        .env("HYPERFORGE_REPO", &target.dir_name)
+        .env("HYPERFORGE_CAMPAIGN", &campaign.name)
+        .output()
+        .map_err(|e| format!("Failed to execute script: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "script exited with {}: {}",
+            output.status.code().unwrap_or(-1),
+            stderr.trim()
+        ));
+    }
+
+    let status = Git::repo_status(path).map_err(|e| format!("git status failed: {e}"))?;
+    if !(status.has_changes || status.has_staged || status.has_untracked) {
+        return Ok(ScriptOutcome::Unchanged);
+    }
+
+    Git::add(path, ".").map_err(|e| format!("git add failed: {e}"))?;
+    Git::commit(path, &campaign.description).map_err(|e| format!("git commit failed: {e}"))?;
+    Git::push_set_upstream(path, &target.remote, &campaign.branch)
+        .map_err(|e| format!("git push {} {} failed: {e}", target.remote, campaign.branch))?;
+    Ok(ScriptOutcome::Pushed)
+}
+
+fn progress_event(campaign: &str, repo_name: &str, progress: &RepoProgress) -> HyperforgeEvent {
+    HyperforgeEvent::CampaignRepo {
+        campaign: campaign.to_string(),
+        repo_name: repo_name.to_string(),
+        status: progress.status.as_str().to_string(),
+        url: progress.url.clone(),
+        error: progress.error.clone(),
+    }
+}
+
+fn pushed_progress(forge: &str, error: Option<String>) -> RepoProgress {
+    RepoProgress {
+        status: CampaignStatus::Pushed,
+        forge: Some(forge.to_string()),
+        url: None,
+        error,
+    }
+}
+
+fn failed(forge: &str, error: String) -> RepoProgress {
+    RepoProgress {
+        status: CampaignStatus::Failed,
+        forge: Some(forge.to_string()),
+        url: None,
+        error: Some(error),
+    }
+}
+
+#[plexus_macros::activation(
+    namespace = "campaigns",
+    description = "Multi-repo change campaigns: scripted changes proposed as PRs and tracked to merge",
+    crate_path = "plexus_core"
+)]
+impl CampaignsHub {
+    /// This hub's method catalog
+    #[plexus_macros::method(
+        description = "List this hub's methods: what each does, its parameters (type, required or optional, description) and an example invocation",
+        params(
+            method = "Only describe this method (optional)"
+        )
+    )]
+    pub async fn help(
+        &self,
+        method: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, futures::stream::iter(help::events(&self.plugin_schema(), "synapse substrate hyperforge campaigns", method.as_deref())))
+    }
+
+    /// Define a campaign
+    #[plexus_macros::method(
+        description = "Define a campaign in .hyperforge/campaigns/<name>.toml: a change description (commit message and PR title/body), a shell script run in each repo's root, and the repos it targets. Every matched repo starts as pending.",
+        params(
+            path = "Path to workspace directory",
+            name = "Campaign name (letters, digits, '-' and '_')",
+            description = "What the change does; the first line becomes the PR title",
+            script = "Shell command run in each repo root ($HYPERFORGE_REPO holds the repo name)",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            branch = "Branch the change is proposed from (optional, default: campaign/<name>)",
+            labels = "Labels to apply to every PR (optional, repeatable)"
+        )
+    )]
+    pub async fn create(
+        &self,
+        path: String,
+        name: String,
+        description: String,
+        script: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        branch: Option<String>,
+        labels: Option<Vec<String>>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, stream! {
+            if let Err(e) = validate_name(&name) {
+                yield HyperforgeEvent::error(HyperforgeError::Validation, e);
+                return;
+            }
+
+            let workspace_path = PathBuf::from(&path);
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+            if Campaign::exists(&ctx.root, &name) {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Policy,
+                    format!("Campaign '{name}' already exists at {}", Campaign::path(&ctx.root, &name).display()),
+                );
+                return;
+            }
+
+            let include = include.unwrap_or_default();
+            let exclude = exclude.unwrap_or_default();
+            let filter = RepoFilter::new(Some(include.clone()), Some(exclude.clone()));
+            let mut campaign = Campaign {
+                branch: branch.unwrap_or_else(|| format!("campaign/{name}")),
+                name,
+                description,
+                script,
+                labels: labels.unwrap_or_default(),
+                include,
+                exclude,
+                repos: std::collections::BTreeMap::new(),
+            };
+            for repo in ctx.repos.iter().filter(|r| r.config.is_some() && filter.matches(&r.dir_name)) {
+                campaign.repos.insert(repo.dir_name.clone(), RepoProgress::default());
+            }
+
+            if campaign.repos.is_empty() {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    "No configured repos matched filter.".to_string(),
+                );
+                return;
+            }
+            if let Err(e) = campaign.save(&ctx.root) {
+                yield HyperforgeEvent::error(HyperforgeError::Validation, e);
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "Created campaign '{}' targeting {} repos on branch '{}'",
+                    campaign.name,
+                    campaign.repos.len(),
+                    campaign.branch
+                ),
+            };
+        })
+    }
+
+    /// Run a campaign's script in pending and failed repos
+    #[plexus_macros::method(
+        description = "Run a campaign: for every pending or failed repo, run the script on the campaign branch (cut from the default branch), commit and push the result to the origin forge, and open a PR. Repos the script leaves untouched are marked unchanged; repos whose branch was pushed but whose PR didn't open only retry the PR. Progress is saved after every run.",
+        params(
+            path = "Path to workspace directory",
+            name = "Campaign name",
+            dry_run = "List the repos that would be processed without running anything (optional, default: false)"
+        )
+    )]
+    pub async fn run(
+        &self,
+        path: String,
+        name: String,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let is_dry_run = dry_run.unwrap_or(false);
+        let config_dir = self.state.config_dir.clone();
+
+        output::structured(None, &self.state.config_dir, stream! {
+            if let Err(e) = validate_name(&name) {
+                yield HyperforgeEvent::error(HyperforgeError::Validation, e);
+                return;
+            }
+
+            let workspace_path = PathBuf::from(&path);
+            let prefix = dry_prefix(is_dry_run);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+            let mut campaign = match Campaign::load(&ctx.root, &name) {
+                Ok(c) => c,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    return;
+                }
+            };
+
+            // Repos whose branch an earlier run already pushed skip the script
+            let mut targets = Vec::new();
+            let mut pushed = Vec::new();
+            for (dir_name, progress) in &mut campaign.repos {
+                if !progress.status.is_runnable() {
+                    continue;
+                }
+                let target = ctx.repos.iter().find(|r| &r.dir_name == dir_name).and_then(|repo| {
+                    let config = repo.config.as_ref()?;
+                    let forge = config.forges.first()?;
+                    Some(CampaignTarget {
+                        dir_name: dir_name.clone(),
+                        path: repo.path.clone(),
+                        forge: forge.clone(),
+                        org: config.org_for_forge(forge)?.to_string(),
+                        name: repo.effective_name(),
+                        remote: config.remote_for_forge(forge),
+                        base: config.effective_default_branch().to_string(),
+                        host: forge_host(&config_dir, config.org_for_forge(forge)?, forge, config.forge_host(forge)),
+                    })
+                });
+                if let Some(t) = target {
+                    if progress.status == CampaignStatus::Pushed {
+                        pushed.push(t);
+                    } else {
+                        targets.push(t);
+                    }
+                } else {
+                    *progress = RepoProgress {
+                        status: CampaignStatus::Failed,
+                        error: Some("repo is no longer a configured workspace repo".to_string()),
+                        ..RepoProgress::default()
+                    };
+                    yield progress_event(&campaign.name, dir_name, progress);
+                }
+            }
+
+            if targets.is_empty() && pushed.is_empty() {
+                if !is_dry_run {
+                    if let Err(e) = campaign.save(&ctx.root) {
+                        yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    }
+                }
+                yield HyperforgeEvent::Info {
+                    message: format!("Nothing to run for '{}': {}", campaign.name, campaign.summary()),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{}Running campaign '{}' in {} repos...",
+                    prefix,
+                    campaign.name,
+                    targets.len() + pushed.len()
+                ),
+            };
+
+            if is_dry_run {
+                for t in targets.iter().chain(&pushed) {
+                    yield HyperforgeEvent::Info {
+                        message: format!("{prefix}  {} → PR on {} ({} → {})", t.dir_name, t.forge, campaign.branch, t.base),
+                    };
+                }
+                return;
+            }
+
+            // Phase 1: script, commit, push (blocking git and shell)
+            let shared = campaign.clone();
+            let applied = run_batch_blocking(targets, Scheduler::global().batch(None), move |t| {
+                let outcome = apply_script(&t, &shared);
+                (t, outcome)
+            })
+            .await;
+
+            let mut to_open = pushed;
+            for result in applied {
+                let (target, outcome) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                let progress = match outcome {
+                    Ok(ScriptOutcome::Pushed) => {
+                        campaign.repos.insert(target.dir_name.clone(), pushed_progress(&target.forge, None));
+                        to_open.push(target);
+                        continue;
+                    }
+                    Ok(ScriptOutcome::Unchanged) => RepoProgress {
+                        status: CampaignStatus::Unchanged,
+                        forge: Some(target.forge.clone()),
+                        ..RepoProgress::default()
+                    },
+                    Err(e) => failed(&target.forge, e),
+                };
+                yield progress_event(&campaign.name, &target.dir_name, &progress);
+                campaign.repos.insert(target.dir_name, progress);
+            }
+
+            // Record the pushes before talking to forges, so a retry never
+            // re-runs the script on a branch that's already up
+            if let Err(e) = campaign.save(&ctx.root) {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+            }
+
+            // Phase 2: open (or reuse) the PRs
+            let new_pr = NewPullRequest {
+                title: campaign.title().to_string(),
+                body: campaign.description.clone(),
+                head: campaign.branch.clone(),
+                head_owner: None,
+                base: String::new(),
+                labels: campaign.labels.clone(),
+            };
+            let opened = run_batch(to_open, Scheduler::global().batch(None), move |target| {
+                let pr = NewPullRequest { base: target.base.clone(), ..new_pr.clone() };
+                async move {
+                    let result = match make_adapter(&target.forge, &target.org, None, target.host.as_deref()) {
+                        Ok(adapter) => adapter
+                            .create_pull_request(&target.org, &target.name, &pr)
+                            .await
+                            .map_err(|e| e.to_string()),
+                        Err(e) => Err(e),
+                    };
+                    (target, result)
+                }
+            }).await;
+
+            for result in opened {
+                let (target, created) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                let progress = match created {
+                    Ok(Some(url)) => RepoProgress {
+                        status: CampaignStatus::PrOpen,
+                        forge: Some(target.forge.clone()),
+                        url: Some(url),
+                        error: None,
+                    },
+                    Ok(None) => failed(&target.forge, format!("{} doesn't support pull requests", target.forge)),
+                    Err(e) => pushed_progress(&target.forge, Some(format!("opening the PR failed: {e}"))),
+                };
+                yield progress_event(&campaign.name, &target.dir_name, &progress);
+                campaign.repos.insert(target.dir_name, progress);
+            }
+
+            if let Err(e) = campaign.save(&ctx.root) {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                return;
+            }
+            yield HyperforgeEvent::Info {
+                message: format!("Campaign '{}': {}", campaign.name, campaign.summary()),
+            };
+        })
+    }
+
+    /// Report campaign progress
+    #[plexus_macros::method(
+        description = "Report campaign progress. With a name, refreshes every open PR from its forge (merged, or closed without merging → failed), saves the result and reports each repo. Without a name, summarizes every campaign in the workspace.",
+        params(
+            path = "Path to workspace directory",
+            name = "Campaign name (optional, default: summarize all campaigns)"
+        )
+    )]
+    pub async fn status(
+        &self,
+        path: String,
+        name: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let Some(name) = name else {
+                let names = Campaign::list(&ctx.root);
+                if names.is_empty() {
+                    yield HyperforgeEvent::Info { message: "No campaigns defined.".to_string() };
+                }
+                for name in names {
+                    match Campaign::load(&ctx.root, &name) {
+                        Ok(c) => yield HyperforgeEvent::Info {
+                            message: format!("{name}: {}", c.summary()),
+                        },
+                        Err(e) => yield HyperforgeEvent::error(HyperforgeError::Config, e),
+                    }
+                }
+                return;
+            };
+
+            if let Err(e) = validate_name(&name) {
+                yield HyperforgeEvent::error(HyperforgeError::Validation, e);
+                return;
+            }
+            let mut campaign = match Campaign::load(&ctx.root, &name) {
+                Ok(c) => c,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    return;
+                }
+            };
+
+            let open: Vec<(String, String, String, String, Option<String>)> = campaign
+                .repos
+                .iter()
+                .filter(|(_, p)| p.status == CampaignStatus::PrOpen)
+                .filter_map(|(dir_name, p)| {
+                    let repo = ctx.repos.iter().find(|r| &r.dir_name == dir_name)?;
+                    let forge = p.forge.clone()?;
+                    let config = repo.config.as_ref()?;
+                    let org = config.org_for_forge(&forge)?.to_string();
+                    let host = forge_host(&config_dir, &org, &forge, config.forge_host(&forge));
+                    Some((dir_name.clone(), forge, org, repo.effective_name(), host))
+                })
+                .collect();
+
+            let branch = campaign.branch.clone();
+            let states = run_batch(open, Scheduler::global().batch(None), move |(dir_name, forge, org, repo_name, host)| {
+                let branch = branch.clone();
+                async move {
+                    let state = match make_adapter(&forge, &org, None, host.as_deref()) {
+                        Ok(adapter) => adapter
+                            .pull_request_state(&org, &repo_name, &branch)
+                            .await
+                            .map_err(|e| e.to_string()),
+                        Err(e) => Err(e),
+                    };
+                    (dir_name, state)
+                }
+            }).await;
+
+            for result in states {
+                let (dir_name, state) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                let Some(progress) = campaign.repos.get_mut(&dir_name) else { continue };
+                match state {
+                    Ok(Some(PullRequestState::Merged)) => progress.status = CampaignStatus::Merged,
+                    Ok(Some(PullRequestState::Closed)) => {
+                        progress.status = CampaignStatus::Failed;
+                        progress.error = Some("PR closed without merging".to_string());
+                    }
+                    Ok(Some(PullRequestState::Open) | None) => {}
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  {dir_name}: failed to refresh PR state: {e}"),
+                        );
+                    }
+                }
+            }
+
+            for (dir_name, progress) in &campaign.repos {
+                yield progress_event(&campaign.name, dir_name, progress);
+            }
+            if let Err(e) = campaign.save(&ctx.root) {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+            }
+            yield HyperforgeEvent::Info {
+                message: format!("Campaign '{}': {}", campaign.name, campaign.summary()),
+            };
+        })
+    }
+}
@@ -5,6 +5,7 @@
 //!   synapse lforge hyperforge repo images delete --org foo --name bar --tag v1.0
 
 use async_stream::stream;
+use plexus_core::plexus::Activation;
 use futures::Stream;
 use std::sync::Arc;
 
@@ -13,7 +14,11 @@ use crate::adapters::registry::codeberg::CodebergRegistryAdapter;
 use crate::adapters::registry::github::GitHubRegistryAdapter;
 use crate::adapters::registry::RegistryPort;
 use crate::auth::YamlAuthProvider;
-use crate::hub::HyperforgeEvent;
+use crate::help;
+use crate::output;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
 use crate::hubs::HyperforgeState;
 use crate::types::Forge;
 
@@ -45,6 +50,54 @@ fn make_registry_adapter(
     }
 }
 
+/// Dockerfile locations tried, in order, when none is given
+pub(crate) const DOCKERFILE_CANDIDATES: [&str; 3] = ["Dockerfile", "Containerfile", "docker/Dockerfile"];
+
+/// The first of [`DOCKERFILE_CANDIDATES`] present in a build context
+pub(crate) fn detect_dockerfile(build_path: &std::path::Path) -> Option<&'static str> {
+    DOCKERFILE_CANDIDATES.into_iter().find(|c| build_path.join(c).exists())
+}
+
+/// Tag a locally built image for `image_ref`'s registry and push it with
+/// that registry's credentials, reporting the outcome as an `ImagePush`.
+pub(crate) async fn push_to_registry(
+    docker: &bollard::Docker,
+    auth: &YamlAuthProvider,
+    image_ref: &crate::types::registry::ImageRef,
+    local_tag: &str,
+    dry_run: bool,
+) -> HyperforgeEvent {
+    let registry = &image_ref.registry;
+    let result = if dry_run {
+        Ok(())
+    } else {
+        push_tagged(docker, auth, image_ref, local_tag).await
+    };
+    HyperforgeEvent::ImagePush {
+        repo_name: image_ref.name.clone(),
+        forge: registry.token_forge_name().to_string(),
+        tag: image_ref.tag.clone(),
+        image: image_ref.full_name(),
+        success: result.is_ok(),
+        error: result.err(),
+    }
+}
+
+async fn push_tagged(
+    docker: &bollard::Docker,
+    auth: &YamlAuthProvider,
+    image_ref: &crate::types::registry::ImageRef,
+    local_tag: &str,
+) -> Result<(), String> {
+    let registry = &image_ref.registry;
+    let reg_auth = crate::types::RegistryAuth::resolve(registry, &image_ref.org, auth).await?;
+    let credentials = crate::docker::to_docker_credentials(&reg_auth, registry, &image_ref.org);
+
+    let remote_repo = format!("{}/{}/{}", registry.host(), image_ref.org, image_ref.name);
+    crate::docker::tag_image(docker, local_tag, &remote_repo, &image_ref.tag).await?;
+    crate::docker::push_image(docker, &remote_repo, &image_ref.tag, credentials).await
+}
+
 fn make_auth() -> Result<Arc<YamlAuthProvider>, String> {
     YamlAuthProvider::new()
         .map(Arc::new)
@@ -57,6 +110,20 @@ fn make_auth() -> Result<Arc<YamlAuthProvider>, String> {
     crate_path = "plexus_core"
 )]
 impl ImagesHub {
+    /// This hub's method catalog
+    #[plexus_macros::method(
+        description = "List this hub's methods: what each does, its parameters (type, required or optional, description) and an example invocation",
+        params(
+            method = "Only describe this method (optional)"
+        )
+    )]
+    pub async fn help(
+        &self,
+        method: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, futures::stream::iter(help::events(&self.plugin_schema(), "synapse substrate hyperforge images", method.as_deref())))
+    }
+
     /// List container image tags for a repo on its configured forges
     #[plexus_macros::method(
         description = "List container image tags for a repository",
@@ -76,14 +143,12 @@ impl ImagesHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let re = match &filter {
                 Some(pattern) => match regex::Regex::new(pattern) {
                     Ok(r) => Some(r),
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Invalid regex '{pattern}': {e}"),
-                        };
+                        yield msg_error!(Msg::InvalidRegex, pattern = pattern, error = e);
                         return;
                     }
                 },
@@ -93,7 +158,7 @@ impl ImagesHub {
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -161,13 +226,14 @@ impl ImagesHub {
                         }
                     }
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to list images on {forge_name}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("Failed to list images on {forge_name}: {e}"),
+                        );
                     }
                 }
             }
-        }
+        })
     }
 
     /// List all container packages for an org across its configured forges
@@ -187,14 +253,12 @@ impl ImagesHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let re = match &filter {
                 Some(pattern) => match regex::Regex::new(pattern) {
                     Ok(r) => Some(r),
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Invalid regex '{pattern}': {e}"),
-                        };
+                        yield msg_error!(Msg::InvalidRegex, pattern = pattern, error = e);
                         return;
                     }
                 },
@@ -204,7 +268,7 @@ impl ImagesHub {
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -260,9 +324,10 @@ impl ImagesHub {
                         }
                     }
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to list packages on {forge_name}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("Failed to list packages on {forge_name}: {e}"),
+                        );
                     }
                 }
             }
@@ -270,7 +335,7 @@ impl ImagesHub {
             yield HyperforgeEvent::Info {
                 message: format!("{total} package(s) found."),
             };
-        }
+        })
     }
 
     /// Build and push a container image to forge registries
@@ -299,36 +364,38 @@ impl ImagesHub {
         let state = self.state.clone();
         let is_dry_run = dry_run.unwrap_or(false);
 
-        stream! {
-            use crate::types::registry::{ContainerRegistry, ImageRef, RegistryAuth};
+        output::structured(None, &self.state.config_dir, stream! {
+            use crate::types::registry::{ContainerRegistry, ImageRef};
 
             let dry_prefix = if is_dry_run { "[dry-run] " } else { "" };
             let build_path = std::path::PathBuf::from(&path);
 
             if !build_path.exists() {
-                yield HyperforgeEvent::Error {
-                    message: format!("Build path does not exist: {path}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("Build path does not exist: {path}"),
+                );
                 return;
             }
 
             // Detect Dockerfile
             let df_relative = if let Some(ref df) = dockerfile {
                 df.clone()
+            } else if let Some(c) = detect_dockerfile(&build_path) {
+                c.to_string()
             } else {
-                let candidates = ["Dockerfile", "Containerfile", "docker/Dockerfile"];
-                if let Some(c) = candidates.iter().find(|c| build_path.join(c).exists()) { c.to_string() } else {
-                    yield HyperforgeEvent::Error {
-                        message: format!("No Dockerfile found in {}. Tried: {}", path, candidates.join(", ")),
-                    };
-                    return;
-                }
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("No Dockerfile found in {}. Tried: {}", path, DOCKERFILE_CANDIDATES.join(", ")),
+                );
+                return;
             };
 
             if !build_path.join(&df_relative).exists() {
-                yield HyperforgeEvent::Error {
-                    message: format!("Dockerfile not found: {}", build_path.join(&df_relative).display()),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("Dockerfile not found: {}", build_path.join(&df_relative).display()),
+                );
                 return;
             }
 
@@ -344,21 +411,11 @@ impl ImagesHub {
 
             // Resolve target registries from forge names
             let target_registries: Vec<ContainerRegistry> = if let Some(f) = forge {
-                vec![match f {
-                    Forge::GitHub => ContainerRegistry::Ghcr,
-                    Forge::Codeberg => ContainerRegistry::Codeberg,
-                    Forge::GitLab => ContainerRegistry::GitLab,
-                }]
+                vec![ContainerRegistry::from(f)]
             } else {
                 let org_config = crate::config::OrgConfig::load(&state.config_dir, &org);
-                let mut regs: Vec<ContainerRegistry> = org_config.ssh.keys().map(|k| {
-                    match k.as_str() {
-                        "github" => ContainerRegistry::Ghcr,
-                        "codeberg" => ContainerRegistry::Codeberg,
-                        "gitlab" => ContainerRegistry::GitLab,
-                        other => ContainerRegistry::Custom(other.to_string()),
-                    }
-                }).collect();
+                let mut regs: Vec<ContainerRegistry> =
+                    org_config.ssh.keys().map(|k| ContainerRegistry::from_name(k)).collect();
                 if regs.is_empty() {
                     regs.push(ContainerRegistry::Ghcr);
                 }
@@ -369,9 +426,7 @@ impl ImagesHub {
             let docker = match crate::docker::connect() {
                 Ok(d) => d,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Docker unavailable: {e}. Is Docker/Colima running?"),
-                    };
+                    yield msg_error!(Msg::DockerUnavailable, error = e);
                     return;
                 }
             };
@@ -384,15 +439,11 @@ impl ImagesHub {
                     };
                 }
                 crate::docker::DockerState::NotRunning => {
-                    yield HyperforgeEvent::Error {
-                        message: "Docker daemon is not running. Start Docker or Colima first.".to_string(),
-                    };
+                    yield msg_error!(Msg::DockerNotRunning);
                     return;
                 }
                 crate::docker::DockerState::NotInstalled => {
-                    yield HyperforgeEvent::Error {
-                        message: "Docker is not installed.".to_string(),
-                    };
+                    yield msg_error!(Msg::DockerNotInstalled);
                     return;
                 }
             }
@@ -410,9 +461,10 @@ impl ImagesHub {
                         };
                     }
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Build failed: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Validation,
+                            format!("Build failed: {e}"),
+                        );
                         return;
                     }
                 }
@@ -422,7 +474,7 @@ impl ImagesHub {
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -434,75 +486,9 @@ impl ImagesHub {
                     message: format!("{dry_prefix}Pushing {image_ref}"),
                 };
 
-                if is_dry_run {
-                    yield HyperforgeEvent::ImagePush {
-                        repo_name: image_name.clone(),
-                        forge: registry.token_forge_name().to_string(),
-                        tag: image_tag.clone(),
-                        image: image_ref.full_name(),
-                        success: true,
-                        error: None,
-                    };
-                    continue;
-                }
-
-                // Resolve registry credentials
-                let reg_auth = match RegistryAuth::resolve(registry, &org, auth.as_ref()).await {
-                    Ok(a) => a,
-                    Err(e) => {
-                        yield HyperforgeEvent::ImagePush {
-                            repo_name: image_name.clone(),
-                            forge: registry.token_forge_name().to_string(),
-                            tag: image_tag.clone(),
-                            image: image_ref.full_name(),
-                            success: false,
-                            error: Some(e),
-                        };
-                        continue;
-                    }
-                };
-
-                let credentials = crate::docker::to_docker_credentials(&reg_auth, registry, &org);
-
-                // Tag for remote registry
-                let remote_repo = format!("{}/{}/{}", registry.host(), org, image_name);
-                if let Err(e) = crate::docker::tag_image(&docker, &local_tag, &remote_repo, &image_tag).await {
-                    yield HyperforgeEvent::ImagePush {
-                        repo_name: image_name.clone(),
-                        forge: registry.token_forge_name().to_string(),
-                        tag: image_tag.clone(),
-                        image: image_ref.full_name(),
-                        success: false,
-                        error: Some(e),
-                    };
-                    continue;
-                }
-
-                // Push via bollard
-                match crate::docker::push_image(&docker, &remote_repo, &image_tag, credentials).await {
-                    Ok(()) => {
-                        yield HyperforgeEvent::ImagePush {
-                            repo_name: image_name.clone(),
-                            forge: registry.token_forge_name().to_string(),
-                            tag: image_tag.clone(),
-                            image: image_ref.full_name(),
-                            success: true,
-                            error: None,
-                        };
-                    }
-                    Err(e) => {
-                        yield HyperforgeEvent::ImagePush {
-                            repo_name: image_name.clone(),
-                            forge: registry.token_forge_name().to_string(),
-                            tag: image_tag.clone(),
-                            image: image_ref.full_name(),
-                            success: false,
-                            error: Some(e),
-                        };
-                    }
-                }
+                yield push_to_registry(&docker, auth.as_ref(), &image_ref, &local_tag, is_dry_run).await;
             }
-        }
+        })
     }
 
     /// Delete a container image tag
@@ -527,13 +513,13 @@ impl ImagesHub {
         let is_dry_run = !confirm.unwrap_or(false);
         let forge_str = forge.as_str().to_string();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let dry_prefix = if is_dry_run { "[dry-run] " } else { "" };
 
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -541,7 +527,7 @@ impl ImagesHub {
             let adapter = match make_registry_adapter(&forge_str, auth, &org) {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -573,6 +559,6 @@ impl ImagesHub {
                     };
                 }
             }
-        }
+        })
     }
 }
@@ -3,7 +3,9 @@
 //! Each sub-hub is a leaf plugin under the root `hyperforge` hub.
 
 pub mod build;
+pub mod campaigns;
 pub mod images;
+pub mod registry;
 pub mod releases;
 pub mod repo;
 pub mod utils;
@@ -17,6 +19,7 @@ use crate::adapters::LocalForge;
 use crate::services::SymmetricSyncService;
 
 pub use build::BuildHub;
+pub use registry::RegistryHub;
 pub use repo::RepoHub;
 pub use workspace::WorkspaceHub;
 
@@ -32,10 +35,7 @@ pub struct HyperforgeState {
 
 impl HyperforgeState {
     pub fn new() -> Self {
-        let config_dir = dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".config")
-            .join("hyperforge");
+        let config_dir = Self::default_config_dir();
 
         Self {
             sync_service: Arc::new(SymmetricSyncService::new()),
@@ -44,6 +44,15 @@ impl HyperforgeState {
         }
     }
 
+    /// ~/.config/hyperforge, where state lives unless a caller picks
+    /// another directory
+    pub fn default_config_dir() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".config")
+            .join("hyperforge")
+    }
+
     /// Get or create `LocalForge` for an org with file persistence
     pub async fn get_local_forge(&self, org: &str) -> Arc<LocalForge> {
         // Try to get existing
@@ -0,0 +1,214 @@
+//! `RegistryHub` — queries and bulk edits over `LocalForge` records
+//!
+//! A child plugin under the root hub, accessible as:
+//!   synapse lforge hyperforge registry query --org foo --filter "on:github and not on:codeberg"
+
+use async_stream::stream;
+use chrono::Utc;
+use plexus_core::plexus::Activation;
+use futures::Stream;
+
+use crate::help;
+use crate::output;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::hubs::utils::dry_prefix;
+use crate::hubs::HyperforgeState;
+use crate::messages::Msg;
+use crate::msg_error;
+use crate::services::bulk_edit::Edit;
+use crate::services::journal::{self, JournalEntry};
+use crate::services::ArtifactPolicy;
+use crate::services::query::Query;
+
+/// Sub-hub for registry inspection and bulk edits
+#[derive(Clone)]
+pub struct RegistryHub {
+    state: HyperforgeState,
+}
+
+impl RegistryHub {
+    pub const fn new(state: HyperforgeState) -> Self {
+        Self { state }
+    }
+}
+
+#[plexus_macros::activation(
+    namespace = "registry",
+    description = "Query and bulk-edit LocalForge records",
+    crate_path = "plexus_core"
+)]
+impl RegistryHub {
+    /// This hub's method catalog
+    #[plexus_macros::method(
+        description = "List this hub's methods: what each does, its parameters (type, required or optional, description) and an example invocation",
+        params(
+            method = "Only describe this method (optional)"
+        )
+    )]
+    pub async fn help(
+        &self,
+        method: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, futures::stream::iter(help::events(&self.plugin_schema(), "synapse substrate hyperforge registry", method.as_deref())))
+    }
+
+    /// Query an org's records with a filter expression
+    #[plexus_macros::method(
+        description = "List an org's LocalForge records matching a filter expression. Terms: on:<forge>, forge:<forge>, visibility:<v> (or public/private), managed, dismissed, protected, staged, name:<glob>, branch:<name>, has:<ci|dist|local_path|description>; combine with and/or/not and parentheses.",
+        params(
+            org = "Organization name",
+            filter = "Filter expression, e.g. \"on:github and not on:codeberg\" (optional, default: all records)"
+        )
+    )]
+    pub async fn query(
+        &self,
+        org: String,
+        filter: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let state = self.state.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let query = match Query::parse(filter.as_deref().unwrap_or_default()) {
+                Ok(q) => q,
+                Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Validation, e); return; }
+            };
+            let local = state.get_local_forge(&org).await;
+            let records = match local.records() {
+                Ok(r) => r,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, format!("Failed to load repos: {e}"));
+                    return;
+                }
+            };
+
+            let total = records.len();
+            let mut matching: Vec<_> = records.into_iter().filter(|r| query.matches(r)).collect();
+            matching.sort_by(|a, b| a.name.cmp(&b.name));
+            for record in &matching {
+                let mut present_on: Vec<String> = record.present_on.iter().map(ToString::to_string).collect();
+                present_on.sort();
+                yield HyperforgeEvent::RegistryRecord {
+                    org: org.clone(),
+                    repo_name: record.name.clone(),
+                    visibility: record.visibility.as_str().to_string(),
+                    present_on,
+                    forges: record.forges.clone(),
+                    managed: record.managed,
+                    dismissed: record.dismissed,
+                    protected: record.protected,
+                    staged: record.dismissed || !record.privatized_on.is_empty(),
+                    local_path: record.local_path.as_ref().map(|p| p.display().to_string()),
+                };
+            }
+            yield HyperforgeEvent::Info {
+                message: format!("{} of {total} records match", matching.len()),
+            };
+        })
+    }
+
+    /// Apply field assignments to every record matching a filter
+    #[plexus_macros::method(
+        description = "Set fields on every LocalForge record matching a filter expression, e.g. protected=true, visibility=private, forges+=codeberg, forges-=gitlab, managed=false, default_branch=main. Changes are written to repos.yaml and the org journal; use dry_run to preview.",
+        params(
+            org = "Organization name",
+            filter = "Filter expression selecting records (same language as registry query; required)",
+            set = "Field assignments, e.g. [\"protected=true\", \"forges+=codeberg\"]",
+            dry_run = "Preview changes without writing (optional, default: false)"
+        )
+    )]
+    pub async fn bulk_set(
+        &self,
+        org: String,
+        filter: String,
+        set: Vec<String>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let state = self.state.clone();
+        let is_dry_run = dry_run.unwrap_or(false);
+        output::structured(None, &self.state.config_dir, stream! {
+            let dry = dry_prefix(is_dry_run);
+            // An empty filter would edit the whole org; make that explicit
+            if filter.trim().is_empty() {
+                yield HyperforgeEvent::error(HyperforgeError::Validation, "A filter is required (use \"managed or not managed\" to edit every record)");
+                return;
+            }
+            let query = match Query::parse(&filter) {
+                Ok(q) => q,
+                Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Validation, e); return; }
+            };
+            let edits = match set.iter().map(|a| Edit::parse(a)).collect::<Result<Vec<_>, _>>() {
+                Ok(edits) if !edits.is_empty() => edits,
+                Ok(_) => { yield HyperforgeEvent::error(HyperforgeError::Validation, "Nothing to set"); return; }
+                Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Validation, e); return; }
+            };
+
+            let local = state.get_local_forge(&org).await;
+            let records = match local.records() {
+                Ok(r) => r,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, format!("Failed to load repos: {e}"));
+                    return;
+                }
+            };
+            let mut matching: Vec<_> = records.into_iter().filter(|r| query.matches(r)).collect();
+            matching.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let now = Utc::now();
+            let mut entries = Vec::new();
+            let mut changed_records = 0usize;
+            for record in &matching {
+                let mut updated = (**record).clone();
+                let changes: Vec<_> = edits.iter().filter_map(|edit| edit.apply(&mut updated)).collect();
+                if changes.is_empty() {
+                    continue;
+                }
+                changed_records += 1;
+                for change in changes {
+                    yield HyperforgeEvent::RegistryEdit {
+                        org: org.clone(),
+                        repo_name: record.name.clone(),
+                        field: change.field.to_string(),
+                        before: change.before.clone(),
+                        after: change.after.clone(),
+                        dry_run: is_dry_run,
+                    };
+                    entries.push(JournalEntry {
+                        at: now,
+                        action: "registry.bulk_set".to_string(),
+                        repo: record.name.clone(),
+                        field: change.field.to_string(),
+                        before: change.before,
+                        after: change.after,
+                    });
+                }
+                if !is_dry_run {
+                    if let Err(e) = local.update_record(&updated) {
+                        yield HyperforgeEvent::error(HyperforgeError::Config, format!("Failed to update {}: {e}", record.name));
+                        return;
+                    }
+                }
+            }
+
+            if !is_dry_run && !entries.is_empty() {
+                if let Err(e) = local.save_to_yaml().await {
+                    yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
+                    return;
+                }
+                let recorded = match ArtifactPolicy::load(&state.config_dir).await {
+                    Ok(policy) => journal::append(&state.config_dir, &org, &entries, &policy),
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = recorded {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    return;
+                }
+            }
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{dry}{changed_records} of {} matching records changed ({} field changes)",
+                    matching.len(),
+                    entries.len()
+                ),
+            };
+        })
+    }
+}
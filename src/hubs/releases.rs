@@ -6,6 +6,7 @@
 //!   synapse lforge hyperforge repo releases upload --org foo --name bar --tag v1.0.0 --file ./dist/app.tar.gz
 
 use async_stream::stream;
+use plexus_core::plexus::Activation;
 use futures::Stream;
 use std::sync::Arc;
 
@@ -14,7 +15,9 @@ use crate::adapters::releases::codeberg::CodebergReleaseAdapter;
 use crate::adapters::releases::github::GitHubReleaseAdapter;
 use crate::adapters::releases::ReleasePort;
 use crate::auth::YamlAuthProvider;
-use crate::hub::HyperforgeEvent;
+use crate::help;
+use crate::output;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
 use crate::hubs::HyperforgeState;
 use crate::types::Forge;
 
@@ -87,6 +90,20 @@ fn guess_content_type(filename: &str) -> &'static str {
     crate_path = "plexus_core"
 )]
 impl ReleasesHub {
+    /// This hub's method catalog
+    #[plexus_macros::method(
+        description = "List this hub's methods: what each does, its parameters (type, required or optional, description) and an example invocation",
+        params(
+            method = "Only describe this method (optional)"
+        )
+    )]
+    pub async fn help(
+        &self,
+        method: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, futures::stream::iter(help::events(&self.plugin_schema(), "synapse substrate hyperforge releases", method.as_deref())))
+    }
+
     /// List releases for a repo across configured forges
     #[plexus_macros::method(
         description = "List releases for a repository across its configured forges",
@@ -104,11 +121,11 @@ impl ReleasesHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -149,13 +166,14 @@ impl ReleasesHub {
                         }
                     }
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to list releases on {forge_name}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("Failed to list releases on {forge_name}: {e}"),
+                        );
                     }
                 }
             }
-        }
+        })
     }
 
     /// Create a tagged release on forge(s)
@@ -189,11 +207,11 @@ impl ReleasesHub {
         let is_draft = draft.unwrap_or(false);
         let is_prerelease = prerelease.unwrap_or(false);
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -239,7 +257,7 @@ impl ReleasesHub {
                     }
                 }
             }
-        }
+        })
     }
 
     /// Upload a file as a release asset
@@ -263,13 +281,14 @@ impl ReleasesHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             // Read the file from disk
             let file_path = std::path::Path::new(&file);
             if !file_path.exists() {
-                yield HyperforgeEvent::Error {
-                    message: format!("File not found: {file}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    format!("File not found: {file}"),
+                );
                 return;
             }
 
@@ -282,9 +301,10 @@ impl ReleasesHub {
             let data = match tokio::fs::read(&file).await {
                 Ok(d) => d,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to read file {file}: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to read file {file}: {e}"),
+                    );
                     return;
                 }
             };
@@ -295,7 +315,7 @@ impl ReleasesHub {
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -376,7 +396,7 @@ impl ReleasesHub {
                     }
                 }
             }
-        }
+        })
     }
 
     /// Delete a release by tag
@@ -401,13 +421,13 @@ impl ReleasesHub {
         let state = self.state.clone();
         let is_dry_run = !confirm.unwrap_or(false);
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let dry_prefix = if is_dry_run { "[dry-run] " } else { "" };
 
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -482,7 +502,7 @@ impl ReleasesHub {
                     }
                 }
             }
-        }
+        })
     }
 
     /// List assets attached to a specific release
@@ -504,11 +524,11 @@ impl ReleasesHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -536,9 +556,10 @@ impl ReleasesHub {
                         continue;
                     }
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to find release on {forge_name}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("Failed to find release on {forge_name}: {e}"),
+                        );
                         continue;
                     }
                 };
@@ -564,7 +585,7 @@ impl ReleasesHub {
                     };
                 }
             }
-        }
+        })
     }
 }
 
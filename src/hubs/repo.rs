@@ -1,27 +1,42 @@
 //! `RepoHub` - Single-repo operations and registry CRUD
 
 use async_stream::stream;
+use plexus_core::plexus::Activation;
 use futures::Stream;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use crate::adapters::{CodebergAdapter, ForgePort, GitHubAdapter, GitLabAdapter};
-use crate::auth::YamlAuthProvider;
+use crate::adapters::{simulation, CodebergAdapter, ForgePort, GiteaAdapter, GitHubAdapter};
+use crate::auth::{SecretSession, YamlAuthProvider};
 use crate::commands::materialize::{materialize, MaterializeOpts, MaterializeReport};
 use crate::commands::{push, status};
+use crate::commands::runner::{blocked_event, guard_finding_events};
 use crate::config::HyperforgeConfig;
-use crate::hub::HyperforgeEvent;
+use crate::git::{parse_remote_url, retarget_remote_url, CloneOptions, Git};
+use crate::help;
+use crate::output;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::messages::Msg;
+use crate::msg_error;
 use crate::hubs::images::ImagesHub;
 use crate::hubs::releases::ReleasesHub;
+use crate::hubs::utils::{forge_host, gitlab_adapter, make_adapter};
 use crate::hubs::HyperforgeState;
-use crate::types::{Forge, Repo, RepoRecord, Visibility};
+use crate::scheduler::{Resource, Scheduler};
+use crate::services::{DeployKeyAction, DeployKeyService, IssueMigrationService, IssueOutcome};
+use crate::types::repo::normalize_topics;
+use crate::types::{fit_repo, Forge, ForgeConfig, Repo, RepoRecord, Visibility};
 
 /// Create a forge adapter for the given forge, org, and auth provider.
+/// A self-hosted GitLab host comes from `record`'s forge override, else the
+/// org config in `config_dir`.
 fn make_repo_adapter(
     forge: &Forge,
     auth: Arc<YamlAuthProvider>,
     org: &str,
+    config_dir: &Path,
+    record: Option<&RepoRecord>,
 ) -> Result<Box<dyn ForgePort>, String> {
     match forge {
         Forge::GitHub => GitHubAdapter::new(auth, org)
@@ -30,7 +45,13 @@ fn make_repo_adapter(
         Forge::Codeberg => CodebergAdapter::new(auth, org)
             .map(|a| Box::new(a) as Box<dyn ForgePort>)
             .map_err(|e| format!("{forge:?}: {e}")),
-        Forge::GitLab => GitLabAdapter::new(auth, org)
+        Forge::GitLab => {
+            let host = forge_host(config_dir, org, "gitlab", record.and_then(|r| r.forge_host("gitlab")));
+            gitlab_adapter(auth, org, host.as_deref())
+                .map(|a| Box::new(a) as Box<dyn ForgePort>)
+                .map_err(|e| format!("{forge:?}: {e}"))
+        }
+        Forge::Gitea { host } => GiteaAdapter::gitea(auth, org, host)
             .map(|a| Box::new(a) as Box<dyn ForgePort>)
             .map_err(|e| format!("{forge:?}: {e}")),
     }
@@ -43,17 +64,92 @@ fn make_auth() -> Result<Arc<YamlAuthProvider>, String> {
         .map_err(|e| format!("Failed to create auth provider: {e}"))
 }
 
+/// A forge and its adapter, or why the adapter couldn't be made
+type ForgeAdapter = (Forge, Result<Box<dyn ForgePort>, String>);
+
+/// Forges a rename got through, and the refusal that stopped it, if any
+struct ForgeRenames {
+    events: Vec<HyperforgeEvent>,
+    renamed: Vec<(Forge, Box<dyn ForgePort>)>,
+    failure: Option<String>,
+}
+
+/// Rename `old_name` to `new_name` on each forge in turn, stopping at the
+/// first adapter error or refusal
+async fn rename_on_forges(
+    adapters: Vec<ForgeAdapter>,
+    org: &str,
+    old_name: &str,
+    new_name: &str,
+) -> ForgeRenames {
+    let mut events = Vec::new();
+    let mut renamed = Vec::new();
+    for (forge, adapter) in adapters {
+        let adapter = match adapter {
+            Ok(a) => a,
+            Err(e) => return ForgeRenames { events, renamed, failure: Some(e) },
+        };
+        match adapter.rename_repo(org, old_name, new_name).await {
+            Ok(()) => {
+                events.push(HyperforgeEvent::Info {
+                    message: format!("  Renamed on {forge}: {old_name} -> {new_name}"),
+                });
+                renamed.push((forge, adapter));
+            }
+            Err(e) => {
+                let failure = Some(format!("{forge}: {e}"));
+                return ForgeRenames { events, renamed, failure };
+            }
+        }
+    }
+    ForgeRenames { events, renamed, failure: None }
+}
+
+/// Rename the forges a rename got through back to `old_name`, last first
+async fn undo_forge_renames(
+    renamed: &[(Forge, Box<dyn ForgePort>)],
+    org: &str,
+    old_name: &str,
+    new_name: &str,
+) -> Vec<HyperforgeEvent> {
+    let mut events = Vec::new();
+    for (forge, adapter) in renamed.iter().rev() {
+        events.push(match adapter.rename_repo(org, new_name, old_name).await {
+            Ok(()) => HyperforgeEvent::Info {
+                message: format!("  Rolled back on {forge}: {new_name} -> {old_name}"),
+            },
+            Err(e) => HyperforgeEvent::error(
+                HyperforgeError::ForgeApi,
+                format!("Rollback failed on {forge}, it's still named '{new_name}' there: {e}"),
+            ),
+        });
+    }
+    events
+}
+
+/// Clone options from the `depth`/`filter`/`single_branch` method params.
+pub(crate) fn clone_options(depth: Option<u32>, filter: Option<&str>, single_branch: Option<bool>) -> Result<CloneOptions, String> {
+    if depth == Some(0) {
+        return Err("depth must be at least 1".to_string());
+    }
+    Ok(CloneOptions {
+        depth,
+        filter: filter.map(str::parse).transpose()?,
+        single_branch: single_branch.unwrap_or(false),
+    })
+}
+
 /// Build a `HyperforgeEvent::Repo` from a `Repo` struct.
 fn repo_event(repo: &crate::types::Repo) -> HyperforgeEvent {
     HyperforgeEvent::Repo {
         name: repo.name.clone(),
         description: repo.description.clone(),
         visibility: format!("{:?}", repo.visibility).to_lowercase(),
-        origin: format!("{:?}", repo.origin).to_lowercase(),
+        origin: repo.origin.to_string(),
         mirrors: repo
             .mirrors
             .iter()
-            .map(|f| format!("{f:?}").to_lowercase())
+            .map(ToString::to_string)
             .collect(),
         protected: repo.protected,
         staged_for_deletion: repo.staged_for_deletion,
@@ -104,6 +200,20 @@ impl RepoHub {
     crate_path = "plexus_core"
 )]
 impl RepoHub {
+    /// This hub's method catalog
+    #[plexus_macros::method(
+        description = "List this hub's methods: what each does, its parameters (type, required or optional, description) and an example invocation",
+        params(
+            method = "Only describe this method (optional)"
+        )
+    )]
+    pub async fn help(
+        &self,
+        method: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, futures::stream::iter(help::events(&self.plugin_schema(), "synapse substrate hyperforge repo", method.as_deref())))
+    }
+
     /// Container image operations (list/pull/push/delete images in forge registries).
     #[plexus_macros::child]
     fn images(&self) -> ImagesHub {
@@ -131,14 +241,12 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let re = match &filter {
                 Some(pattern) => match regex::Regex::new(pattern) {
                     Ok(r) => Some(r),
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Invalid regex '{pattern}': {e}"),
-                        };
+                        yield msg_error!(Msg::InvalidRegex, pattern = pattern, error = e);
                         return;
                     }
                 },
@@ -159,12 +267,13 @@ impl RepoHub {
                     }
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to list repos: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::ForgeApi,
+                        format!("Failed to list repos: {e}"),
+                    );
                 }
             }
-        }
+        })
     }
 
     /// Create a new repository in `LocalForge`
@@ -190,12 +299,13 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             // Parse forge from string
             let origin_forge = if let Some(f) = HyperforgeConfig::parse_forge(&origin) { f } else {
-                yield HyperforgeEvent::Error {
-                    message: format!("Invalid origin forge: {origin}. Must be github, codeberg, or gitlab"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("Invalid origin forge: {origin}. Must be github, codeberg, or gitlab"),
+                );
                 return;
             };
 
@@ -203,7 +313,7 @@ impl RepoHub {
             let vis = match Visibility::parse(&visibility) {
                 Ok(v) => v,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Validation, e);
                     return;
                 }
             };
@@ -233,9 +343,7 @@ impl RepoHub {
                 Ok(()) => {
                     // Save to YAML
                     if let Err(e) = local.save_to_yaml().await {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to save repos.yaml: {e}"),
-                        };
+                        yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
                         return;
                     }
 
@@ -245,12 +353,13 @@ impl RepoHub {
                     yield repo_event(&repo);
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to create repo: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::ForgeApi,
+                        format!("Failed to create repo: {e}"),
+                    );
                 }
             }
-        }
+        })
     }
 
     /// Update an existing repository
@@ -272,16 +381,17 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let local = state.get_local_forge(&org).await;
 
             // Get existing repo
             let mut repo = match local.get_repo(&org, &name).await {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to get repo: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::ForgeApi,
+                        format!("Failed to get repo: {e}"),
+                    );
                     return;
                 }
             };
@@ -295,7 +405,7 @@ impl RepoHub {
                 repo.visibility = match Visibility::parse(&vis) {
                     Ok(v) => v,
                     Err(e) => {
-                        yield HyperforgeEvent::Error { message: e };
+                        yield HyperforgeEvent::error(HyperforgeError::Validation, e);
                         return;
                     }
                 };
@@ -304,9 +414,7 @@ impl RepoHub {
             match local.update_repo(&org, &repo).await {
                 Ok(()) => {
                     if let Err(e) = local.save_to_yaml().await {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to save repos.yaml: {e}"),
-                        };
+                        yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
                         return;
                     }
 
@@ -324,21 +432,20 @@ impl RepoHub {
                                     }
                                 }
                                 Err(e) => {
-                                    yield HyperforgeEvent::Error {
-                                        message: format!("Failed to materialize config: {e}"),
-                                    };
+                                    yield msg_error!(Msg::MaterializeFailed, error = e);
                                 }
                             }
                         }
                     }
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to update repo: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::ForgeApi,
+                        format!("Failed to update repo: {e}"),
+                    );
                 }
             }
-        }
+        })
     }
 
     /// Soft-delete a repository: privatize on remote forges, then mark dismissed locally
@@ -356,25 +463,27 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let local = state.get_local_forge(&org).await;
 
             // Get the record to find which forges it's on
             let record = match local.get_record(&name) {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repository not found: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Repository not found: {e}"),
+                    );
                     return;
                 }
             };
 
             // Protected repos cannot be deleted
             if record.protected {
-                yield HyperforgeEvent::Error {
-                    message: format!("Cannot delete '{name}': repo is protected. Remove protection first with: repo update --org {org} --name {name} --protected false"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Policy,
+                    format!("Cannot delete '{name}': repo is protected. Remove protection first with: repo update --org {org} --name {name} --protected false"),
+                );
                 return;
             }
 
@@ -382,7 +491,7 @@ impl RepoHub {
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -390,7 +499,7 @@ impl RepoHub {
             let mut privatize_errors = Vec::new();
             let mut privatized_forges = Vec::new();
             for forge in &record.present_on {
-                let adapter = match make_repo_adapter(forge, auth.clone(), &org) {
+                let adapter = match make_repo_adapter(forge, auth.clone(), &org, &state.config_dir, Some(&record)) {
                     Ok(a) => a,
                     Err(e) => {
                         privatize_errors.push(e);
@@ -415,9 +524,10 @@ impl RepoHub {
             }
 
             for error in &privatize_errors {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to privatize - {error}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("Failed to privatize - {error}"),
+                );
             }
 
             // Soft-delete locally (always, even if remote privatization had errors)
@@ -435,9 +545,7 @@ impl RepoHub {
                     }
 
                     if let Err(e) = local.save_to_yaml().await {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to save repos.yaml: {e}"),
-                        };
+                        yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
                         return;
                     }
 
@@ -448,18 +556,20 @@ impl RepoHub {
                     };
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to delete repo: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::ForgeApi,
+                        format!("Failed to delete repo: {e}"),
+                    );
                 }
             }
 
             if !privatize_errors.is_empty() {
-                yield HyperforgeEvent::Error {
-                    message: format!("Completed with {} privatization error(s)", privatize_errors.len()),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("Completed with {} privatization error(s)", privatize_errors.len()),
+                );
             }
-        }
+        })
     }
 
     /// Purge a soft-deleted repository: hard-delete from remote forges and remove from repos.yaml
@@ -480,32 +590,35 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let local = state.get_local_forge(&org).await;
 
             let record = match local.get_record(&name) {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repository not found: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Repository not found: {e}"),
+                    );
                     return;
                 }
             };
 
             // Must be dismissed first
             if !record.dismissed {
-                yield HyperforgeEvent::Error {
-                    message: format!("Cannot purge '{name}': repo is not dismissed. Run 'repo delete' first."),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Policy,
+                    format!("Cannot purge '{name}': repo is not dismissed. Run 'repo delete' first."),
+                );
                 return;
             }
 
             // Protected repos cannot be purged
             if record.protected {
-                yield HyperforgeEvent::Error {
-                    message: format!("Cannot purge '{name}': repo is protected. Remove protection first with: repo update --org {org} --name {name} --protected false"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Policy,
+                    format!("Cannot purge '{name}': repo is protected. Remove protection first with: repo update --org {org} --name {name} --protected false"),
+                );
                 return;
             }
 
@@ -513,7 +626,7 @@ impl RepoHub {
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -522,7 +635,7 @@ impl RepoHub {
             let mut deleted_forges = Vec::new();
             let forges_to_delete: Vec<_> = record.present_on.iter().cloned().collect();
             for forge in &forges_to_delete {
-                let adapter = match make_repo_adapter(forge, auth.clone(), &org) {
+                let adapter = match make_repo_adapter(forge, auth.clone(), &org, &state.config_dir, Some(&record)) {
                     Ok(a) => a,
                     Err(e) => {
                         delete_errors.push(e);
@@ -558,22 +671,22 @@ impl RepoHub {
             }
 
             for error in &delete_errors {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to delete - {error}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("Failed to delete - {error}"),
+                );
             }
 
             // Hard-delete locally (remove record from repos.yaml entirely)
             if delete_errors.is_empty() {
                 if let Err(e) = local.remove_repo(&name) {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to remove local record: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to remove local record: {e}"),
+                    );
                 } else {
                     if let Err(e) = local.save_to_yaml().await {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to save repos.yaml: {e}"),
-                        };
+                        yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
                         return;
                     }
                     yield HyperforgeEvent::Info {
@@ -581,21 +694,22 @@ impl RepoHub {
                     };
                 }
             } else {
-                yield HyperforgeEvent::Error {
-                    message: format!("Purge incomplete: {} remote deletion error(s). Local record preserved.", delete_errors.len()),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    format!("Purge incomplete: {} remote deletion error(s). Local record preserved.", delete_errors.len()),
+                );
             }
-        }
+        })
     }
 
-    /// Rename a repository on remote forge(s) and in local config
+    /// Rename a repository on its forges and in local config, all or nothing
     #[plexus_macros::method(
-        description = "Rename a repository on remote forge(s) and update local configuration",
+        description = "Rename a repository on every forge it's on, then in LocalForge, its git remotes and repo_name in .hyperforge/config.toml. If any forge refuses, or the local update or rewrite fails, the forges already renamed are renamed back and the local record, remotes and config are restored.",
         params(
             org = "Organization name",
             old_name = "Current repository name",
             new_name = "New repository name",
-            forges = "Comma-separated forges to rename on (optional, defaults to origin only)"
+            forges = "Comma-separated forges to rename on (optional, defaults to every forge the repo is on)"
         )
     )]
     pub async fn rename(
@@ -607,202 +721,557 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
-            // Get local forge and verify repo exists
+        output::structured(None, &self.state.config_dir, stream! {
+            let _session = SecretSession::begin();
             let local = state.get_local_forge(&org).await;
 
-            let repo = match local.get_repo(&org, &old_name).await {
+            let record = match local.get_record(&old_name) {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repository not found in local config: {e}"),
-                    };
+                    yield msg_error!(Msg::RepoNotInLocalConfig, error = e);
                     return;
                 }
             };
+            // A case-only rename is fine; anything else must be free
+            if !old_name.eq_ignore_ascii_case(&new_name) && local.get_record(&new_name).is_ok() {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("'{new_name}' already exists in {org}"),
+                );
+                return;
+            }
 
-            // Determine which forges to rename on
             let target_forges: Vec<Forge> = if let Some(forge_list) = forges {
                 forge_list
                     .split(',')
                     .filter_map(|f| HyperforgeConfig::parse_forge(f.trim()))
                     .collect()
             } else {
-                // Default to origin only
-                vec![repo.origin.clone()]
+                // Entries for another account belong to that org's LocalForge
+                let mut on: Vec<Forge> = record
+                    .present_on
+                    .iter()
+                    .filter(|f| record.forge_org(f.as_str()).is_none_or(|o| o == org))
+                    .cloned()
+                    .collect();
+                on.sort_by_key(ToString::to_string);
+                on
             };
 
-            // Get auth provider
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
 
-            // Rename on each target forge
-            let mut errors = Vec::new();
-            for forge in &target_forges {
-                let adapter = match make_repo_adapter(forge, auth.clone(), &org) {
+            let adapters = target_forges
+                .iter()
+                .map(|forge| (forge.clone(), make_repo_adapter(forge, auth.clone(), &org, &state.config_dir, Some(&record))))
+                .collect();
+            let ForgeRenames { events, renamed, failure } =
+                rename_on_forges(adapters, &org, &old_name, &new_name).await;
+            for event in events {
+                yield event;
+            }
+
+            let mut local_renamed = false;
+            let mut local_result = match failure {
+                Some(e) => Err(format!("Remote rename failed - {e}")),
+                None => match local.rename_repo(&org, &old_name, &new_name).await {
+                    Ok(()) => {
+                        local_renamed = true;
+                        local.save_to_yaml().await.map_err(|e| format!("Failed to save repos.yaml: {e}"))
+                    }
+                    Err(e) => Err(format!("Failed to update local config: {e}")),
+                },
+            };
+
+            // Rewrite the remotes and repo_name on disk from the renamed
+            // record; a failure here rolls everything back too
+            let mut materialize_failed = false;
+            if local_result.is_ok() {
+                if let Ok(renamed_record) = local.get_record(&new_name) {
+                    if let Some(ref local_path) = renamed_record.local_path {
+                        match materialize(&org, &renamed_record, local_path, MaterializeOpts::default()) {
+                            Ok(report) => {
+                                for event in materialize_events(&report) {
+                                    yield event;
+                                }
+                            }
+                            Err(e) => {
+                                materialize_failed = true;
+                                local_result = Err(format!("Failed to materialize config: {e}"));
+                            }
+                        }
+                    }
+                }
+            }
+
+            if let Err(e) = local_result {
+                yield HyperforgeEvent::error(HyperforgeError::ForgeApi, e);
+                // Put the original record back as it was; renaming it back
+                // would record the new name as a previous one
+                if local_renamed {
+                    let restored = match local.remove_repo(&new_name).and_then(|()| local.upsert_record(record.clone())) {
+                        Ok(()) => local.save_to_yaml().await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = restored {
+                        yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
+                    }
+                }
+                // Undo whatever part of the rewrite landed
+                if materialize_failed {
+                    if let Some(ref local_path) = record.local_path {
+                        if let Err(e) = materialize(&org, &record, local_path, MaterializeOpts::default()) {
+                            yield msg_error!(Msg::MaterializeFailed, error = e);
+                        }
+                    }
+                }
+                for event in undo_forge_renames(&renamed, &org, &old_name, &new_name).await {
+                    yield event;
+                }
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("Rename of '{old_name}' abandoned"),
+                );
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("Local config updated: {old_name} -> {new_name}"),
+            };
+
+            yield HyperforgeEvent::Info {
+                message: format!("Renamed repository: {old_name} -> {new_name}"),
+            };
+        })
+    }
+
+    /// Archive a retired repo on all its forges
+    #[plexus_macros::method(
+        description = "Archive a retired repository on every forge it's on and mark it archived in LocalForge and its .hyperforge/config.toml, so workspace sync keeps it archived (and never privatizes or deletes it). --unarchive reverses it.",
+        params(
+            org = "Organization name",
+            name = "Repository name",
+            unarchive = "Make the repository writable again instead (optional, default: false)",
+            dry_run = "Preview changes without applying (optional, default: false)"
+        )
+    )]
+    pub async fn archive(
+        &self,
+        org: String,
+        name: String,
+        unarchive: Option<bool>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let state = self.state.clone();
+        let archived = !unarchive.unwrap_or(false);
+        let is_dry_run = dry_run.unwrap_or(false);
+
+        output::structured(None, &self.state.config_dir, stream! {
+            let _session = SecretSession::begin();
+            let dry_prefix = if is_dry_run { "[DRY RUN] " } else { "" };
+            let local = state.get_local_forge(&org).await;
+
+            let mut record = match local.get_record(&name) {
+                Ok(r) => r,
+                Err(e) => {
+                    yield msg_error!(Msg::RepoNotInLocalForge, name = name, error = e);
+                    return;
+                }
+            };
+            let mut forges: Vec<Forge> = record.present_on.iter().cloned().collect();
+            forges.sort_by_key(ToString::to_string);
+
+            let auth = match make_auth() {
+                Ok(a) => a,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
+                    return;
+                }
+            };
+
+            let action = if archived { "Archived" } else { "Unarchived" };
+            let mut errors = 0usize;
+            for forge in &forges {
+                // Entries for another account belong to that org's LocalForge
+                if record.forge_org(forge.as_str()).is_some_and(|o| o != org) {
+                    continue;
+                }
+                let adapter = match make_repo_adapter(forge, auth.clone(), &org, &state.config_dir, Some(&record)) {
                     Ok(a) => a,
                     Err(e) => {
-                        errors.push(e);
+                        yield HyperforgeEvent::error(HyperforgeError::ForgeApi, format!("{forge}: {e}"));
+                        errors += 1;
                         continue;
                     }
                 };
-
-                match adapter.rename_repo(&org, &old_name, &new_name).await {
-                    Ok(()) => {
-                        yield HyperforgeEvent::Info {
-                            message: format!("Renamed on {forge:?}: {old_name} -> {new_name}"),
-                        };
-                    }
+                if is_dry_run {
+                    yield HyperforgeEvent::Info { message: format!("  {dry_prefix}{action} '{name}' on {forge}") };
+                    continue;
+                }
+                let result = if archived {
+                    adapter.archive_repo(&org, &name).await
+                } else {
+                    adapter.unarchive_repo(&org, &name).await
+                };
+                match result {
+                    Ok(()) => yield HyperforgeEvent::Info { message: format!("  {action} '{name}' on {forge}") },
                     Err(e) => {
-                        errors.push(format!("{forge:?}: {e}"));
+                        yield HyperforgeEvent::error(HyperforgeError::ForgeApi, format!("{forge}: {e}"));
+                        errors += 1;
                     }
                 }
             }
 
-            // Report any errors from remote renames
-            for error in &errors {
-                yield HyperforgeEvent::Error {
-                    message: format!("Remote rename failed - {error}"),
-                };
+            if errors > 0 {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("{errors} forge(s) failed; '{name}' left as it was in LocalForge and config"),
+                );
+                return;
+            }
+            if is_dry_run {
+                return;
             }
 
-            // Update local config regardless of remote errors (user may want to fix manually)
-            match local.rename_repo(&org, &old_name, &new_name).await {
-                Ok(()) => {
-                    if let Err(e) = local.save_to_yaml().await {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to save repos.yaml: {e}"),
-                        };
-                        return;
+            // Record it where sync reads it, so the next sync agrees
+            record.archived = archived;
+            if let Err(e) = local.update_record(&record) {
+                yield HyperforgeEvent::error(HyperforgeError::Config, format!("Failed to update LocalForge record: {e}"));
+            } else if let Err(e) = local.save_to_yaml().await {
+                yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
+            }
+            if let Some(path) = record.local_path.as_deref().filter(|p| HyperforgeConfig::exists(p)) {
+                let saved = HyperforgeConfig::load(path).and_then(|mut config| {
+                    config.archived = archived;
+                    config.save(path)
+                });
+                if let Err(e) = saved {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to update {}: {e}", HyperforgeConfig::config_path(path).display()),
+                    );
+                }
+            }
+        })
+    }
+
+    /// Move a repo to another org on its forges and locally
+    #[plexus_macros::method(
+        description = "Transfer a repository to another org or user on every forge it's on (GitHub, GitLab, Codeberg/Gitea), then move its LocalForge record, point its git remotes at the new org and update org in its .hyperforge/config.toml. Forges that fail keep the old org as a per-forge override; re-run to finish.",
+        params(
+            org = "Current organization name",
+            name = "Repository name",
+            new_org = "Organization or user to transfer to",
+            dry_run = "Preview changes without applying (optional, default: false)"
+        )
+    )]
+    pub async fn transfer(
+        &self,
+        org: String,
+        name: String,
+        new_org: String,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let state = self.state.clone();
+        let is_dry_run = dry_run.unwrap_or(false);
+
+        output::structured(None, &self.state.config_dir, stream! {
+            let _session = SecretSession::begin();
+            let dry_prefix = if is_dry_run { "[DRY RUN] " } else { "" };
+            if new_org == org {
+                yield HyperforgeEvent::error(HyperforgeError::Validation, format!("'{name}' is already in {org}"));
+                return;
+            }
+            let local = state.get_local_forge(&org).await;
+
+            let mut record = match local.get_record(&name) {
+                Ok(r) => r,
+                Err(e) => {
+                    yield msg_error!(Msg::RepoNotInLocalForge, name = name, error = e);
+                    return;
+                }
+            };
+            // Entries pinned to another account (or already transferred) stay put
+            let mut forges: Vec<Forge> = record
+                .present_on
+                .iter()
+                .filter(|f| record.forge_org(f.as_str()).is_none_or(|o| o == org))
+                .cloned()
+                .collect();
+            forges.sort_by_key(ToString::to_string);
+
+            let auth = match make_auth() {
+                Ok(a) => a,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
+                    return;
+                }
+            };
+
+            let mut moved = Vec::new();
+            let mut errors = 0usize;
+            for forge in &forges {
+                let adapter = match make_repo_adapter(forge, auth.clone(), &org, &state.config_dir, Some(&record)) {
+                    Ok(a) => a,
+                    Err(e) => {
+                        yield HyperforgeEvent::error(HyperforgeError::ForgeApi, format!("{forge}: {e}"));
+                        errors += 1;
+                        continue;
+                    }
+                };
+                if is_dry_run {
+                    yield HyperforgeEvent::Info { message: format!("  {dry_prefix}Transfer '{name}' on {forge}: {org} -> {new_org}") };
+                    continue;
+                }
+                match adapter.transfer_repo(&org, &name, &new_org).await {
+                    Ok(()) => {
+                        yield HyperforgeEvent::Info { message: format!("  Transferred '{name}' on {forge}: {org} -> {new_org}") };
+                        moved.push(forge.clone());
                     }
+                    Err(e) => {
+                        yield HyperforgeEvent::error(HyperforgeError::ForgeApi, format!("{forge}: {e}"));
+                        errors += 1;
+                    }
+                }
+            }
+            if is_dry_run || moved.is_empty() {
+                return;
+            }
 
-                    yield HyperforgeEvent::Info {
-                        message: format!("Local config updated: {old_name} -> {new_name}"),
+            // Everything moved: the record belongs to the new org. Otherwise the
+            // moved forges get an org override so neither org recreates them.
+            let complete = errors == 0;
+            for forge in &moved {
+                let entry = record.forge_config.entry(forge.as_str().to_string()).or_default();
+                entry.org = (!complete).then(|| new_org.clone());
+            }
+            record.forge_config.retain(|_, fc| *fc != ForgeConfig::default());
+
+            let saved = if complete {
+                // The new org is written first, so a failed save never
+                // leaves the record in neither repos.yaml
+                let target = state.get_local_forge(&new_org).await;
+                match target.upsert_record(record.clone()) {
+                    Ok(()) => match target.save_to_yaml().await {
+                        Ok(()) => match local.remove_repo(&name) {
+                            Ok(()) => local.save_to_yaml().await,
+                            Err(e) => Err(e),
+                        },
+                        Err(e) => Err(e),
+                    },
+                    Err(e) => Err(e),
+                }
+            } else {
+                match local.update_record(&record) {
+                    Ok(()) => local.save_to_yaml().await,
+                    Err(e) => Err(e),
+                }
+            };
+            if let Err(e) = saved {
+                yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
+            }
+
+            if let Some(path) = record.local_path.clone() {
+                // Remotes of the moved forges, matched by forge (or self-hosted host)
+                let hosts: Vec<String> = moved
+                    .iter()
+                    .map(|f| {
+                        record
+                            .forge_config
+                            .get(f.as_str())
+                            .and_then(|fc| fc.host.clone())
+                            .unwrap_or_else(|| f.as_str().to_string())
+                    })
+                    .collect();
+                for remote in Git::list_remotes(&path).unwrap_or_default() {
+                    let on_moved = parse_remote_url(&remote.fetch_url)
+                        .is_some_and(|(forge, _, repo)| repo == name && hosts.contains(&forge));
+                    let Some(url) = retarget_remote_url(&remote.fetch_url, &org, &new_org).filter(|_| on_moved) else {
+                        continue;
                     };
+                    match Git::set_remote_url(&path, &remote.name, &url) {
+                        Ok(()) => yield HyperforgeEvent::Info { message: format!("  Remote {} -> {url}", remote.name) },
+                        Err(e) => yield HyperforgeEvent::error(HyperforgeError::Git, format!("Failed to update remote {}: {e}", remote.name)),
+                    }
+                }
 
-                    // Materialize to disk if the renamed record has a local_path
-                    if let Ok(record) = local.get_record(&new_name) {
-                        if let Some(ref local_path) = record.local_path {
-                            match materialize(&org, &record, local_path, MaterializeOpts::default()) {
-                                Ok(report) => {
-                                    for event in materialize_events(&report) {
-                                        yield event;
-                                    }
-                                }
-                                Err(e) => {
-                                    yield HyperforgeEvent::Error {
-                                        message: format!("Failed to materialize config: {e}"),
-                                    };
-                                }
-                            }
+                if HyperforgeConfig::exists(&path) {
+                    let saved = HyperforgeConfig::load(&path).and_then(|mut config| {
+                        if complete {
+                            config.org = Some(new_org.clone());
                         }
+                        for forge in &moved {
+                            let entry = config.forge_config.entry(forge.as_str().to_string()).or_default();
+                            entry.org = (!complete).then(|| new_org.clone());
+                        }
+                        config.forge_config.retain(|_, fc| *fc != ForgeConfig::default());
+                        config.save(&path)
+                    });
+                    if let Err(e) = saved {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("Failed to update {}: {e}", HyperforgeConfig::config_path(&path).display()),
+                        );
                     }
                 }
-                Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to update local config: {e}"),
-                    };
-                }
             }
 
-            if errors.is_empty() {
-                yield HyperforgeEvent::Info {
-                    message: format!("Renamed repository: {old_name} -> {new_name}"),
-                };
+            if complete {
+                yield HyperforgeEvent::Info { message: format!("Transferred repository: {org}/{name} -> {new_org}/{name}") };
             } else {
-                yield HyperforgeEvent::Error {
-                    message: format!("Rename completed with {} error(s)", errors.len()),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("{errors} forge(s) failed; '{name}' stays in {org} there, re-run to finish"),
+                );
             }
-        }
+        })
     }
 
-    /// Set or unset archive status on remote forges
+    /// Copy a repo's issues from one forge to another, e.g. onto a new mirror
     #[plexus_macros::method(
-        description = "Archive or unarchive a repository on its remote forges",
+        description = "Copy open issues (title, body, labels, state) from one forge to another, e.g. after adding a mirror. Re-runs skip issues already copied.",
         params(
             org = "Organization name",
             name = "Repository name",
-            archived = "Set archived (true) or unarchived (false)"
+            to = "Forge to copy issues to",
+            from = "Forge to copy issues from (optional, defaults to the origin forge)",
+            include_closed = "Also copy closed issues, closing the copies (optional, default: false)",
+            dry_run = "Preview which issues would be copied (optional, default: false)"
         )
     )]
-    pub async fn set_archived(
+    pub async fn migrate_issues(
         &self,
         org: String,
         name: String,
-        archived: bool,
+        to: String,
+        from: Option<String>,
+        include_closed: Option<bool>,
+        dry_run: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
+        let is_dry_run = dry_run.unwrap_or(false);
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
+            let dry_prefix = if is_dry_run { "[DRY RUN] " } else { "" };
             let local = state.get_local_forge(&org).await;
 
-            let repo = match local.get_repo(&org, &name).await {
+            let record = match local.get_record(&name) {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repository not found in local config: {e}"),
-                    };
+                    yield msg_error!(Msg::RepoNotInLocalForge, name = name, error = e);
                     return;
                 }
             };
 
-            let mut target_forges = vec![repo.origin.clone()];
-            for mirror in &repo.mirrors {
-                if !target_forges.contains(mirror) {
-                    target_forges.push(mirror.clone());
-                }
+            let from_name = from
+                .or_else(|| record.forges.first().cloned())
+                .unwrap_or_else(|| record.to_repo().origin.to_string());
+            let (Some(from_forge), Some(to_forge)) =
+                (HyperforgeConfig::parse_forge(&from_name), HyperforgeConfig::parse_forge(&to))
+            else {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("Invalid forge: {from_name} -> {to}"),
+                );
+                return;
+            };
+            if from_forge == to_forge {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("Source and target forge are both {to}"),
+                );
+                return;
             }
 
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
+                    return;
+                }
+            };
+            let (source, target) = match (
+                make_repo_adapter(&from_forge, auth.clone(), &org, &state.config_dir, Some(&record)),
+                make_repo_adapter(&to_forge, auth, &org, &state.config_dir, Some(&record)),
+            ) {
+                (Ok(s), Ok(t)) => (s, t),
+                (Err(e), _) | (_, Err(e)) => {
+                    yield HyperforgeEvent::error(HyperforgeError::ForgeApi, e);
                     return;
                 }
             };
+            let (Some(source_issues), Some(target_issues)) = (source.issues(), target.issues()) else {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("{from_name} or {to} has no issue tracker"),
+                );
+                return;
+            };
 
-            let action = if archived { "Archived" } else { "Unarchived" };
-            let mut errors = Vec::new();
-            for forge in &target_forges {
-                let adapter = match make_repo_adapter(forge, auth.clone(), &org) {
-                    Ok(a) => a,
-                    Err(e) => {
-                        errors.push(e);
-                        continue;
-                    }
-                };
+            yield HyperforgeEvent::Info {
+                message: format!("{dry_prefix}Migrating issues for '{name}': {from_name} -> {to}"),
+            };
 
-                match adapter.set_archived(&org, &name, archived).await {
-                    Ok(()) => {
-                        yield HyperforgeEvent::Info {
-                            message: format!("{action} '{name}' on {forge:?}"),
-                        };
-                    }
-                    Err(e) => {
-                        errors.push(format!("{forge:?}: {e}"));
+            let results = match IssueMigrationService::migrate(
+                source_issues,
+                target_issues,
+                &org,
+                &name,
+                include_closed.unwrap_or(false),
+                is_dry_run,
+            ).await {
+                Ok(r) => r,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::ForgeApi, format!("Issue migration failed: {e}"));
+                    return;
+                }
+            };
+
+            let mut created = 0usize;
+            let mut skipped = 0usize;
+            let mut failed = 0usize;
+            for result in results {
+                match result.outcome {
+                    IssueOutcome::Created => created += 1,
+                    IssueOutcome::AlreadyMigrated => skipped += 1,
+                    IssueOutcome::Failed => {
+                        failed += 1;
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!(
+                                "{to}: failed to copy #{} '{}': {}",
+                                result.source.number,
+                                result.source.title,
+                                result.error.unwrap_or_default(),
+                            ),
+                        ).with_context(to.clone());
+                        continue;
                     }
                 }
-            }
-
-            for error in &errors {
-                yield HyperforgeEvent::Error {
-                    message: format!("  Error: {error}"),
+                yield HyperforgeEvent::IssueMigrated {
+                    repo_name: name.clone(),
+                    from: from_name.clone(),
+                    to: to.clone(),
+                    number: result.source.number,
+                    title: result.source.title,
+                    state: result.source.state.as_str().to_string(),
+                    outcome: result.outcome.as_str().to_string(),
+                    url: result.target_url,
+                    dry_run: is_dry_run,
                 };
             }
 
-            // Update local state
-            if let Err(e) = local.set_archived(&org, &name, archived).await {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to update local state: {e}"),
-                };
-            }
-        }
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{dry_prefix}Issue migration complete: {created} copied, {skipped} already migrated, {failed} failed",
+                ),
+            };
+        })
     }
 
     /// Set the default branch on remote forges and optionally checkout locally
@@ -826,16 +1295,14 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             // Get local forge to find repo config (forges)
             let local = state.get_local_forge(&org).await;
 
             let repo = match local.get_repo(&org, &name).await {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repository not found in local config: {e}"),
-                    };
+                    yield msg_error!(Msg::RepoNotInLocalConfig, error = e);
                     return;
                 }
             };
@@ -852,15 +1319,16 @@ impl RepoHub {
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
 
             // Set default branch on each forge
+            let record = local.get_record(&name).ok();
             let mut errors = Vec::new();
             for forge in &target_forges {
-                let adapter = match make_repo_adapter(forge, auth.clone(), &org) {
+                let adapter = match make_repo_adapter(forge, auth.clone(), &org, &state.config_dir, record.as_ref()) {
                     Ok(a) => a,
                     Err(e) => {
                         errors.push(e);
@@ -882,21 +1350,21 @@ impl RepoHub {
 
             // Report errors
             for error in &errors {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to set default branch - {error}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Git,
+                    format!("Failed to set default branch - {error}"),
+                );
             }
 
             // Update LocalForge record with the new default branch
             if errors.is_empty() {
                 if let Err(e) = local.set_default_branch(&org, &name, &branch).await {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to update LocalForge default_branch: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to update LocalForge default_branch: {e}"),
+                    );
                 } else if let Err(e) = local.save_to_yaml().await {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to save repos.yaml: {e}"),
-                    };
+                    yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
                 }
             }
 
@@ -911,15 +1379,17 @@ impl RepoHub {
                             };
                         }
                         Err(e) => {
-                            yield HyperforgeEvent::Error {
-                                message: format!("Git checkout failed: {e}"),
-                            };
+                            yield HyperforgeEvent::error(
+                                HyperforgeError::Git,
+                                format!("Git checkout failed: {e}"),
+                            );
                         }
                     }
                 } else {
-                    yield HyperforgeEvent::Error {
-                        message: "--path is required when --checkout is true".to_string(),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Validation,
+                        "--path is required when --checkout is true".to_string(),
+                    );
                 }
             }
 
@@ -928,11 +1398,12 @@ impl RepoHub {
                     message: format!("Default branch set to '{branch}' on all forges"),
                 };
             } else {
-                yield HyperforgeEvent::Error {
-                    message: format!("Completed with {} error(s)", errors.len()),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("Completed with {} error(s)", errors.len()),
+                );
             }
-        }
+        })
     }
 
     /// Import repositories from a remote forge
@@ -950,12 +1421,10 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             // Parse forge
             let source_forge = if let Some(f) = HyperforgeConfig::parse_forge(&forge) { f } else {
-                yield HyperforgeEvent::Error {
-                    message: format!("Invalid forge: {forge}. Must be github, codeberg, or gitlab"),
-                };
+                yield msg_error!(Msg::InvalidForge, forge = forge);
                 return;
             };
 
@@ -963,14 +1432,14 @@ impl RepoHub {
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
-            let adapter: Arc<dyn ForgePort> = match make_repo_adapter(&source_forge, auth, &org) {
+            let adapter: Arc<dyn ForgePort> = match make_repo_adapter(&source_forge, auth, &org, &state.config_dir, None) {
                 Ok(a) => Arc::from(a),
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -983,9 +1452,10 @@ impl RepoHub {
             let repos = match adapter.list_repos(&org).await {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to list repos from {forge}: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::ForgeApi,
+                        format!("Failed to list repos from {forge}: {e}"),
+                    );
                     return;
                 }
             };
@@ -1008,9 +1478,10 @@ impl RepoHub {
                     Ok(exists) => exists,
                     Err(e) => {
                         errors += 1;
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to check if {} exists: {}", repo.name, e),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("Failed to check if {} exists: {}", repo.name, e),
+                        );
                         continue;
                     }
                 };
@@ -1028,18 +1499,17 @@ impl RepoHub {
                     }
                     Err(e) => {
                         errors += 1;
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to import {}: {}", repo.name, e),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("Failed to import {}: {}", repo.name, e),
+                        );
                     }
                 }
             }
 
             // Save to YAML
             if let Err(e) = local.save_to_yaml().await {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to save repos.yaml: {e}"),
-                };
+                yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
                 return;
             }
 
@@ -1048,7 +1518,7 @@ impl RepoHub {
                     "Import complete: {imported} imported, {skipped} skipped (already exist), {errors} errors"
                 ),
             };
-        }
+        })
     }
 
     /// Initialize hyperforge for a git repository
@@ -1084,7 +1554,7 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let repo_path = PathBuf::from(&path);
             let is_dry_run = dry_run.unwrap_or(false);
 
@@ -1095,9 +1565,10 @@ impl RepoHub {
                 .collect();
 
             if forge_list.is_empty() {
-                yield HyperforgeEvent::Error {
-                    message: "At least one forge required".to_string(),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    "At least one forge required".to_string(),
+                );
                 return;
             }
 
@@ -1107,7 +1578,7 @@ impl RepoHub {
                 Some(s) => match Visibility::parse(s) {
                     Ok(v) => v,
                     Err(e) => {
-                        yield HyperforgeEvent::Error { message: e };
+                        yield HyperforgeEvent::error(HyperforgeError::Validation, e);
                         return;
                     }
                 },
@@ -1153,12 +1624,15 @@ impl RepoHub {
                     default_branch: "main".to_string(),
                     present_on: HashSet::new(),
                     protected: false,
+                    archived: false,
                     managed: false,
                     dismissed: false,
                     deleted_from: Vec::new(),
                     deleted_at: None,
                     privatized_on: HashSet::new(),
+                    read_only_on: HashSet::new(),
                     previous_names: Vec::new(),
+                    topics: Vec::new(),
                     local_path: None,
                     forges: Vec::new(),
                     ssh: HashMap::new(),
@@ -1183,25 +1657,25 @@ impl RepoHub {
             // Check if config already exists (unless --force)
             let config_exists = HyperforgeConfig::exists(&repo_path);
             if config_exists && !force.unwrap_or(false) {
-                yield HyperforgeEvent::Error {
-                    message: "Config already exists. Use --force to reinitialize.".to_string(),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Policy,
+                    "Config already exists. Use --force to reinitialize.".to_string(),
+                );
                 return;
             }
 
             // Register in LocalForge
             if !is_dry_run {
                 if let Err(e) = local.upsert_record(record.clone()) {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to register in LocalForge: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to register in LocalForge: {e}"),
+                    );
                     return;
                 }
 
                 if let Err(e) = local.save_to_yaml().await {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to save repos.yaml: {e}"),
-                    };
+                    yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
                     return;
                 }
 
@@ -1218,6 +1692,7 @@ impl RepoHub {
                 ssh_wrapper: !no_ssh_wrapper.unwrap_or(false),
                 dry_run: is_dry_run,
                 auto_commit: true,
+                identity: true,
             };
 
             match materialize(&org, &record, &repo_path, opts) {
@@ -1259,6 +1734,12 @@ impl RepoHub {
                         };
                     }
 
+                    if !report.identity_configured.is_empty() {
+                        yield HyperforgeEvent::Info {
+                            message: format!("Set org identity: {}", report.identity_configured.join(", ")),
+                        };
+                    }
+
                     if report.auto_committed {
                         yield HyperforgeEvent::Info {
                             message: "Auto-committed .hyperforge/ config".to_string(),
@@ -1276,12 +1757,13 @@ impl RepoHub {
                     };
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Materialize failed: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Materialize failed: {e}"),
+                    );
                 }
             }
-        }
+        })
     }
 
     /// Show git repository status
@@ -1295,7 +1777,7 @@ impl RepoHub {
         &self,
         path: String,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let repo_path = std::path::Path::new(&path);
 
             match status::status(repo_path) {
@@ -1355,9 +1837,10 @@ impl RepoHub {
                             };
                         }
                         (Some(cmd), None) if cmd == "hyperforge-ssh" => {
-                            yield HyperforgeEvent::Error {
-                                message: "SSH: hyperforge-ssh configured but hyperforge.org NOT SET — pushes will use wrong key".to_string(),
-                            };
+                            yield HyperforgeEvent::error(
+                                HyperforgeError::Config,
+                                "SSH: hyperforge-ssh configured but hyperforge.org NOT SET — pushes will use wrong key".to_string(),
+                            );
                         }
                         (Some(cmd), _) => {
                             yield HyperforgeEvent::Info {
@@ -1372,12 +1855,255 @@ impl RepoHub {
                     }
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Status failed: {e}"),
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Git,
+                        format!("Status failed: {e}"),
+                    );
+                }
+            }
+        })
+    }
+
+    /// Show each effective config setting and where it comes from
+    #[plexus_macros::method(
+        description = "Explain a repo's effective config: each setting with its value and whether the repo's config.toml, the workspace [defaults] in workspace.toml, or the built-in default decides it",
+        params(
+            path = "Repository path (absolute)"
+        )
+    )]
+    pub async fn explain(
+        &self,
+        path: String,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, stream! {
+            let repo_path = PathBuf::from(&path);
+
+            let settings = match HyperforgeConfig::explain(&repo_path) {
+                Ok(s) => s,
+                Err(e) => {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to load config: {e}"),
+                    );
+                    return;
+                }
+            };
+
+            let workspace = crate::config::WorkspaceConfig::find_root(&repo_path)
+                .map_or_else(|| "none".to_string(), |root| crate::config::WorkspaceConfig::config_path(&root).display().to_string());
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "Precedence: {} > workspace [defaults] ({workspace}) > built-in defaults",
+                    HyperforgeConfig::config_path(&repo_path).display(),
+                ),
+            };
+
+            for setting in settings {
+                yield HyperforgeEvent::ConfigSetting {
+                    key: setting.key,
+                    value: setting.value,
+                    source: setting.source.as_str().to_string(),
+                };
+            }
+        })
+    }
+
+    /// Converge the repo's `[ssh_deploy_keys]` on each of its forges
+    #[plexus_macros::method(
+        description = "Add, replace or remove deploy keys on every forge so they match the repo's [ssh_deploy_keys] config",
+        params(
+            path = "Repository path (absolute)",
+            dry_run = "Preview changes without applying (optional, default: false)"
+        )
+    )]
+    pub async fn sync_deploy_keys(
+        &self,
+        path: String,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let is_dry_run = dry_run.unwrap_or(false);
+        let config_dir = self.state.config_dir.clone();
+
+        output::structured(None, &self.state.config_dir, stream! {
+            let repo_path = PathBuf::from(&path);
+            let dry_prefix = if is_dry_run { "[DRY RUN] " } else { "" };
+
+            let config = match HyperforgeConfig::load(&repo_path) {
+                Ok(c) => c,
+                Err(e) => {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to load config: {e}"),
+                    );
+                    return;
+                }
+            };
+            if config.ssh_deploy_keys.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No [ssh_deploy_keys] configured".to_string(),
+                };
+                return;
+            }
+            let name = config.get_repo_name(&repo_path);
+
+            let mut changed = 0usize;
+            for forge in &config.forges {
+                let Some(org) = config.org_for_forge(forge) else {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("{forge}: no org configured"),
+                    ).with_context(forge.clone());
+                    continue;
+                };
+                let result = match (
+                    DeployKeyService::read_public_keys(&config.ssh_deploy_keys, &repo_path, forge),
+                    make_adapter(forge, org, None, forge_host(&config_dir, org, forge, config.forge_host(forge)).as_deref()),
+                ) {
+                    (Err(e), _) => Err(e.to_string()),
+                    (_, Err(e)) => Err(e),
+                    (Ok(keys), Ok(adapter)) => DeployKeyService::converge(
+                        adapter.as_ref(), org, &name, forge, &config.ssh_deploy_keys, &keys, is_dry_run,
+                    ).await.map_err(|e| e.to_string()),
+                };
+
+                match result {
+                    Ok(Some(changes)) => {
+                        for change in changes {
+                            if change.action != DeployKeyAction::Unchanged {
+                                changed += 1;
+                            }
+                            yield HyperforgeEvent::DeployKeySync {
+                                repo_name: name.clone(),
+                                forge: forge.clone(),
+                                key: change.key,
+                                action: change.action.as_str().to_string(),
+                            };
+                        }
+                    }
+                    Ok(None) => {
+                        yield HyperforgeEvent::Info {
+                            message: format!("{forge}: deploy keys not supported, skipped"),
+                        };
+                    }
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("{forge}: deploy keys: {e}"),
+                        ).with_context(forge.clone());
+                    }
+                }
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("{dry_prefix}Deploy keys: {changed} change(s) across {} forge(s)", config.forges.len()),
+            };
+        })
+    }
+
+    /// Show how the local branch and each forge remote diverge
+    #[plexus_macros::method(
+        description = "Show merge-base and commits unique to each side between the local branch and every configured forge remote",
+        params(
+            path = "Repository path (absolute)",
+            branch = "Branch to compare (optional, default: current branch)",
+            limit = "Maximum commits listed per side (optional, default: 20)",
+            fetch = "Fetch each forge remote first (optional, default: true)"
+        )
+    )]
+    pub async fn divergence(
+        &self,
+        path: String,
+        branch: Option<String>,
+        limit: Option<usize>,
+        fetch: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let limit = limit.unwrap_or(20);
+        let should_fetch = fetch.unwrap_or(true);
+
+        output::structured(None, &self.state.config_dir, stream! {
+            let repo_path = PathBuf::from(&path);
+
+            let config = match HyperforgeConfig::load(&repo_path) {
+                Ok(c) => c,
+                Err(e) => {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to load config: {e}"),
+                    );
+                    return;
+                }
+            };
+
+            let branch = match branch {
+                Some(b) => b,
+                None => match crate::git::Git::current_branch(&repo_path) {
+                    Ok(b) => b,
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Git,
+                            format!("Failed to get current branch: {e}"),
+                        );
+                        return;
+                    }
+                },
+            };
+
+            for forge in &config.forges {
+                let remote_name = config.remote_for_forge(forge);
+
+                if crate::git::Git::get_remote(&repo_path, &remote_name).is_err() {
+                    yield HyperforgeEvent::Info {
+                        message: format!("{forge}: remote '{remote_name}' not configured"),
                     };
+                    continue;
+                }
+
+                if should_fetch {
+                    if let Err(e) = crate::git::Git::fetch(&repo_path, &remote_name) {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Git,
+                            format!("{forge}: fetch from '{remote_name}' failed, comparing against last fetched state: {e}"),
+                        ).with_context(forge.clone());
+                    }
+                }
+
+                let remote_ref = format!("refs/remotes/{remote_name}/{branch}");
+                match crate::git::Git::divergence(&repo_path, &branch, &remote_ref, limit) {
+                    Ok(Some(div)) => {
+                        yield HyperforgeEvent::RepoDivergence {
+                            forge: forge.clone(),
+                            remote_name: remote_name.clone(),
+                            branch: branch.clone(),
+                            merge_base: div.merge_base,
+                            ahead: div.ahead,
+                            behind: div.behind,
+                        };
+                        let sides = [("local", div.local_only), ("remote", div.remote_only)];
+                        for (side, commits) in sides {
+                            for commit in commits {
+                                yield HyperforgeEvent::DivergenceCommit {
+                                    forge: forge.clone(),
+                                    side: side.to_string(),
+                                    oid: commit.oid,
+                                    subject: commit.subject,
+                                };
+                            }
+                        }
+                    }
+                    Ok(None) => {
+                        yield HyperforgeEvent::Info {
+                            message: format!("{forge}: {remote_name}/{branch} does not exist (never pushed or not fetched)"),
+                        };
+                    }
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Git,
+                            format!("{forge}: failed to compare {branch} with {remote_name}/{branch}: {e}"),
+                        ).with_context(forge.clone());
+                    }
                 }
             }
-        }
+        })
     }
 
     /// Push to configured forges
@@ -1388,7 +2114,9 @@ impl RepoHub {
             set_upstream = "Set upstream tracking (optional, default: false)",
             force = "Force push (optional, default: false)",
             dry_run = "Preview push without executing (optional, default: false)",
-            only_forges = "Only push to specific forges, comma-separated (optional)"
+            only_forges = "Only push to specific forges, comma-separated (optional)",
+            scan = "Scan outgoing commits for large files and secrets first (optional, default: from config scan_outgoing)",
+            allow_findings = "Push even if the outgoing-commit scan reports findings (optional, default: false)"
         )
     )]
     pub async fn push(
@@ -1398,8 +2126,10 @@ impl RepoHub {
         force: Option<bool>,
         dry_run: Option<bool>,
         only_forges: Option<String>,
+        scan: Option<bool>,
+        allow_findings: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let repo_path = std::path::Path::new(&path);
 
             // Build options
@@ -1417,6 +2147,14 @@ impl RepoHub {
                 options = options.dry_run();
             }
 
+            if scan.unwrap_or(false) {
+                options = options.scan_outgoing();
+            }
+
+            if allow_findings.unwrap_or(false) {
+                options = options.allow_findings();
+            }
+
             if let Some(forges_str) = only_forges {
                 let forges: Vec<String> = forges_str.split(',')
                     .map(|s| s.trim().to_string())
@@ -1444,12 +2182,13 @@ impl RepoHub {
                                 ),
                             };
                         } else {
-                            yield HyperforgeEvent::Error {
-                                message: format!("✗ Failed to push to {}: {}",
+                            yield HyperforgeEvent::error(
+                                HyperforgeError::Git,
+                                format!("✗ Failed to push to {}: {}",
                                     result.forge,
                                     result.error.as_deref().unwrap_or("unknown error")
                                 ),
-                            };
+                            ).with_context(result.forge.clone());
                         }
                     }
 
@@ -1458,18 +2197,32 @@ impl RepoHub {
                             message: "All pushes succeeded".to_string(),
                         };
                     } else {
-                        yield HyperforgeEvent::Error {
-                            message: "Some pushes failed".to_string(),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Git,
+                            "Some pushes failed".to_string(),
+                        );
                     }
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Push failed: {e}"),
-                    };
+                    let repo_name = repo_path.file_name().and_then(|n| n.to_str()).unwrap_or("?");
+                    match &e {
+                        push::PushError::GuardBlocked { findings } => {
+                            for event in guard_finding_events(repo_name, findings) {
+                                yield event;
+                            }
+                        }
+                        push::PushError::UnsafeState { status } => {
+                            yield blocked_event(repo_name, "push", status);
+                        }
+                        _ => {}
+                    }
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Git,
+                        format!("Push failed: {e}"),
+                    );
                 }
             }
-        }
+        })
     }
 
     /// Clone a repository from `LocalForge`
@@ -1479,7 +2232,10 @@ impl RepoHub {
             org = "Organization name",
             name = "Repository name (must exist in LocalForge)",
             path = "Target directory path (optional, defaults to ./<name>)",
-            forge = "Preferred forge to clone from (optional, defaults to first in present_on)"
+            forge = "Preferred forge to clone from (optional, defaults to first in present_on)",
+            depth = "Fetch only this many commits of history (optional, default: full history)",
+            filter = "Partial clone: blobless or treeless, fetching contents on demand (optional)",
+            single_branch = "Fetch only the default branch (optional, default: false)"
         )
     )]
     pub async fn clone(
@@ -1488,19 +2244,28 @@ impl RepoHub {
         name: String,
         path: Option<String>,
         forge: Option<String>,
+        depth: Option<u32>,
+        filter: Option<String>,
+        single_branch: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
+            let options = match clone_options(depth, filter.as_deref(), single_branch) {
+                Ok(options) => options,
+                Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Validation, e); return; }
+            };
+
             // 1. Lookup repo in LocalForge
             let local = state.get_local_forge(&org).await;
 
             let record = match local.get_record(&name) {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repository not found in LocalForge: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Repository not found in LocalForge: {e}"),
+                    );
                     return;
                 }
             };
@@ -1509,31 +2274,34 @@ impl RepoHub {
             let clone_forge = if let Some(ref forge_str) = forge {
                 if let Some(f) = HyperforgeConfig::parse_forge(forge_str) {
                     if !record.present_on.contains(&f) {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Repository not present on forge: {forge_str}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("Repository not present on forge: {forge_str}"),
+                        );
                         return;
                     }
                     f
                 } else {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Invalid forge: {forge_str}. Must be github, codeberg, or gitlab"),
-                    };
+                    yield msg_error!(Msg::InvalidForge, forge = forge_str);
                     return;
                 }
             } else {
                 // Use first forge from present_on
                 if let Some(f) = record.present_on.iter().next() { f.clone() } else {
-                    yield HyperforgeEvent::Error {
-                        message: "Repository has no forges in present_on".to_string(),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        "Repository has no forges in present_on".to_string(),
+                    );
                     return;
                 }
             };
 
             // 3. Build clone URL
-            let forge_str = format!("{clone_forge:?}").to_lowercase();
-            let clone_url = crate::git::build_remote_url(&forge_str, &org, &name);
+            let forge_str = clone_forge.to_string();
+            let host = record.forge_config.get(&forge_str)
+                .and_then(|fc| fc.host.clone())
+                .or_else(|| crate::config::OrgConfig::load(&state.config_dir, &org).forge_host(&forge_str).map(str::to_string));
+            let clone_url = crate::git::build_remote_url_on(&forge_str, host.as_deref(), &org, &name);
 
             // 4. Determine target path
             let target_path = path.unwrap_or_else(|| name.clone());
@@ -1543,10 +2311,14 @@ impl RepoHub {
             };
 
             // 5. Clone
-            if let Err(e) = crate::git::Git::clone(&clone_url, &target_path) {
-                yield HyperforgeEvent::Error {
-                    message: format!("Git clone failed: {e}"),
-                };
+            let slot = Scheduler::global().acquire(Resource::Git).await;
+            let cloned = crate::git::Git::clone(&clone_url, &target_path, &options);
+            drop(slot);
+            if let Err(e) = cloned {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Git,
+                    format!("Git clone failed: {e}"),
+                );
                 return;
             }
 
@@ -1562,7 +2334,7 @@ impl RepoHub {
             updated_record.local_path = Some(clone_path.clone());
             if updated_record.forges.is_empty() {
                 updated_record.forges = updated_record.present_on.iter()
-                    .map(|f| format!("{f:?}").to_lowercase())
+                    .map(ToString::to_string)
                     .collect();
             }
 
@@ -1585,30 +2357,32 @@ impl RepoHub {
                             message: format!("Updated remote: {remote}"),
                         };
                     }
+                    if !report.identity_configured.is_empty() {
+                        yield HyperforgeEvent::Info {
+                            message: format!("Set org identity: {}", report.identity_configured.join(", ")),
+                        };
+                    }
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to materialize config: {e}"),
-                    };
+                    yield msg_error!(Msg::MaterializeFailed, error = e);
                     // Continue anyway - clone succeeded
                 }
             }
 
             // 7. Update LocalForge with local_path
             if let Err(e) = local.update_record(&updated_record) {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to update LocalForge record: {e}"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    format!("Failed to update LocalForge record: {e}"),
+                );
             } else if let Err(e) = local.save_to_yaml().await {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to save repos.yaml: {e}"),
-                };
+                yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
             }
 
             yield HyperforgeEvent::Info {
                 message: format!("Repository {name} cloned and configured"),
             };
-        }
+        })
     }
 
     /// Sync a repo from `LocalForge` to its remote forges
@@ -1629,7 +2403,9 @@ impl RepoHub {
         let state = self.state.clone();
         let is_dry_run = dry_run.unwrap_or(false);
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
+            let _session = SecretSession::begin();
+
             let dry_prefix = if is_dry_run { "[DRY RUN] " } else { "" };
             let local = state.get_local_forge(&org).await;
 
@@ -1637,9 +2413,7 @@ impl RepoHub {
             let record = match local.get_record(&name) {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repo '{name}' not found in LocalForge: {e}"),
-                    };
+                    yield msg_error!(Msg::RepoNotInLocalForge, name = name, error = e);
                     return;
                 }
             };
@@ -1647,16 +2421,17 @@ impl RepoHub {
             let repo = record.to_repo();
 
             if record.forges.is_empty() {
-                yield HyperforgeEvent::Error {
-                    message: format!("Repo '{name}' has no target forges configured"),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    format!("Repo '{name}' has no target forges configured"),
+                );
                 return;
             }
 
             let auth = match make_auth() {
                 Ok(a) => a,
                 Err(e) => {
-                    yield HyperforgeEvent::Error { message: e };
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, e);
                     return;
                 }
             };
@@ -1671,49 +2446,121 @@ impl RepoHub {
             let mut created = 0usize;
             let mut updated = 0usize;
             let mut in_sync = 0usize;
+            let mut read_only = 0usize;
+            let mut archived = 0usize;
             let mut errors = 0usize;
             let mut record = record;
 
             for forge_name in &record.forges.clone() {
+                // Entries for another account are synced from that org's LocalForge
+                if record.forge_org(forge_name).is_some_and(|o| o != org) {
+                    continue;
+                }
                 let forge = if let Some(f) = HyperforgeConfig::parse_forge(forge_name) { f } else {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Invalid forge: {forge_name}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Validation,
+                        format!("Invalid forge: {forge_name}"),
+                    );
                     errors += 1;
                     continue;
                 };
 
-                let adapter = match make_repo_adapter(&forge, auth.clone(), &org) {
+                let adapter = match make_repo_adapter(&forge, auth.clone(), &org, &state.config_dir, Some(&record)) {
                     Ok(a) => a,
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("{forge_name}: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("{forge_name}: {e}"),
+                        );
                         errors += 1;
                         continue;
                     }
                 };
 
+                // Fit the description to this forge's limits up front rather
+                // than have the API reject the create/update
+                let (repo, fit) = fit_repo(&forge, &repo);
+                if let Some(fit) = fit {
+                    yield HyperforgeEvent::DescriptionFitted {
+                        repo_name: name.clone(),
+                        forge: forge_name.clone(),
+                        changes: fit.changes.iter().map(ToString::to_string).collect(),
+                        limit: forge.description_limit(),
+                        description: fit.text,
+                    };
+                }
+
                 // Check if repo exists on this forge
                 let exists = match adapter.repo_exists(&org, &name).await {
                     Ok(v) => v,
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("{forge_name}: failed to check existence: {e}"),
-                        };
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("{forge_name}: failed to check existence: {e}"),
+                        );
                         errors += 1;
                         continue;
                     }
                 };
 
+                // Archived: archive where still active, never create or update
+                if repo.archived {
+                    if !exists {
+                        yield HyperforgeEvent::Info {
+                            message: format!("  {name} is archived; not creating it on {forge_name}"),
+                        };
+                        continue;
+                    }
+                    let remote = match adapter.get_repo(&org, &name).await {
+                        Ok(r) => r,
+                        Err(e) => {
+                            yield HyperforgeEvent::error(
+                                HyperforgeError::ForgeApi,
+                                format!("{forge_name}: failed to fetch remote: {e}"),
+                            );
+                            errors += 1;
+                            continue;
+                        }
+                    };
+                    record.present_on.insert(forge.clone());
+                    if remote.archived {
+                        in_sync += 1;
+                    } else if remote.read_only {
+                        yield HyperforgeEvent::InsufficientPermissions {
+                            org: org.clone(),
+                            forge: forge_name.clone(),
+                            repo_name: name.clone(),
+                            operation: "archive".to_string(),
+                        };
+                        read_only += 1;
+                    } else {
+                        yield HyperforgeEvent::Info {
+                            message: format!("  {dry_prefix}Archiving {name} on {forge_name}"),
+                        };
+                        if is_dry_run {
+                            archived += 1;
+                        } else if let Err(e) = adapter.archive_repo(&org, &name).await {
+                            yield HyperforgeEvent::error(
+                                HyperforgeError::ForgeApi,
+                                format!("{forge_name}: archive failed: {e}"),
+                            );
+                            errors += 1;
+                        } else {
+                            archived += 1;
+                        }
+                    }
+                    continue;
+                }
+
                 if exists {
                     // Check for drift
                     let remote = match adapter.get_repo(&org, &name).await {
                         Ok(r) => r,
                         Err(e) => {
-                            yield HyperforgeEvent::Error {
-                                message: format!("{forge_name}: failed to fetch remote: {e}"),
-                            };
+                            yield HyperforgeEvent::error(
+                                HyperforgeError::Git,
+                                format!("{forge_name}: failed to fetch remote: {e}"),
+                            );
                             errors += 1;
                             continue;
                         }
@@ -1727,11 +2574,31 @@ impl RepoHub {
                     };
                     let desc_drifted = norm_desc(&repo.description) != norm_desc(&remote.description);
                     let vis_drifted = repo.visibility != remote.visibility;
+                    // No local topics means topics aren't managed for the repo
+                    let topics_drifted = !repo.topics.is_empty()
+                        && normalize_topics(&repo.topics) != normalize_topics(&remote.topics);
+                    let drifted = desc_drifted || vis_drifted || topics_drifted;
+                    if remote.read_only {
+                        record.read_only_on.insert(forge.clone());
+                    } else {
+                        record.read_only_on.remove(&forge);
+                    }
 
-                    if desc_drifted || vis_drifted {
+                    if drifted && remote.read_only {
+                        // Updating would only fail again on every sync
+                        yield HyperforgeEvent::InsufficientPermissions {
+                            org: org.clone(),
+                            forge: forge_name.clone(),
+                            repo_name: name.clone(),
+                            operation: "update".to_string(),
+                        };
+                        record.present_on.insert(forge.clone());
+                        read_only += 1;
+                    } else if drifted {
                         let mut diffs = Vec::new();
                         if desc_drifted { diffs.push("description"); }
                         if vis_drifted { diffs.push("visibility"); }
+                        if topics_drifted { diffs.push("topics"); }
 
                         yield HyperforgeEvent::Info {
                             message: format!(
@@ -1749,9 +2616,10 @@ impl RepoHub {
                                     record.present_on.insert(forge.clone());
                                 }
                                 Err(e) => {
-                                    yield HyperforgeEvent::Error {
-                                        message: format!("{forge_name}: update failed: {e}"),
-                                    };
+                                    yield HyperforgeEvent::error(
+                                        HyperforgeError::ForgeApi,
+                                        format!("{forge_name}: update failed: {e}"),
+                                    );
                                     errors += 1;
                                 }
                             }
@@ -1774,9 +2642,10 @@ impl RepoHub {
                                 record.present_on.insert(forge.clone());
                             }
                             Err(e) => {
-                                yield HyperforgeEvent::Error {
-                                    message: format!("{forge_name}: create failed: {e}"),
-                                };
+                                yield HyperforgeEvent::error(
+                                    HyperforgeError::ForgeApi,
+                                    format!("{forge_name}: create failed: {e}"),
+                                );
                                 errors += 1;
                             }
                         }
@@ -1784,25 +2653,24 @@ impl RepoHub {
                 }
             }
 
-            // Persist present_on updates to LocalForge
-            if !is_dry_run && (created > 0 || updated > 0 || in_sync > 0) {
+            // Persist present_on updates to LocalForge (simulated writes changed nothing)
+            if !is_dry_run && !simulation::is_recording() && (created > 0 || updated > 0 || archived > 0 || in_sync > 0 || read_only > 0) {
                 if let Err(e) = local.update_record(&record) {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to update LocalForge record: {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to update LocalForge record: {e}"),
+                    );
                 } else if let Err(e) = local.save_to_yaml().await {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to save repos.yaml: {e}"),
-                    };
+                    yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
                 }
             }
 
             yield HyperforgeEvent::Info {
                 message: format!(
-                    "{dry_prefix}Sync complete: {created} created, {updated} updated, {in_sync} in sync, {errors} errors",
+                    "{dry_prefix}Sync complete: {created} created, {updated} updated, {archived} archived, {in_sync} in sync, {read_only} read-only, {errors} errors",
                 ),
             };
-        }
+        })
     }
 
     /// Find large tracked files in a repository
@@ -1823,30 +2691,24 @@ impl RepoHub {
         let state = self.state.clone();
         let threshold = threshold_kb.unwrap_or(100) * 1024;
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let local = state.get_local_forge(&org).await;
 
             let record = match local.get_record(&name) {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repo '{name}' not found in LocalForge: {e}"),
-                    };
+                    yield msg_error!(Msg::RepoNotInLocalForge, name = name, error = e);
                     return;
                 }
             };
 
             let repo_path = if let Some(p) = &record.local_path { PathBuf::from(p) } else {
-                yield HyperforgeEvent::Error {
-                    message: format!("Repo '{name}' has no local_path set in LocalForge"),
-                };
+                yield msg_error!(Msg::RepoNoLocalPath, name = name);
                 return;
             };
 
             if !repo_path.exists() {
-                yield HyperforgeEvent::Error {
-                    message: format!("Repo path does not exist: {}", repo_path.display()),
-                };
+                yield msg_error!(Msg::RepoPathMissing, path = repo_path.display());
                 return;
             }
 
@@ -1886,12 +2748,13 @@ impl RepoHub {
                     };
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Scan failed for '{name}': {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Internal,
+                        format!("Scan failed for '{name}': {e}"),
+                    );
                 }
             }
-        }
+        })
     }
 
     /// Show total size of tracked files in a repository
@@ -1909,30 +2772,24 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let local = state.get_local_forge(&org).await;
 
             let record = match local.get_record(&name) {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repo '{name}' not found in LocalForge: {e}"),
-                    };
+                    yield msg_error!(Msg::RepoNotInLocalForge, name = name, error = e);
                     return;
                 }
             };
 
             let repo_path = if let Some(p) = &record.local_path { PathBuf::from(p) } else {
-                yield HyperforgeEvent::Error {
-                    message: format!("Repo '{name}' has no local_path set in LocalForge"),
-                };
+                yield msg_error!(Msg::RepoNoLocalPath, name = name);
                 return;
             };
 
             if !repo_path.exists() {
-                yield HyperforgeEvent::Error {
-                    message: format!("Repo path does not exist: {}", repo_path.display()),
-                };
+                yield msg_error!(Msg::RepoPathMissing, path = repo_path.display());
                 return;
             }
 
@@ -1943,20 +2800,19 @@ impl RepoHub {
             {
                 Ok(o) => o,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to run git ls-files: {e}"),
-                    };
+                    yield msg_error!(Msg::GitLsFilesFailed, error = e);
                     return;
                 }
             };
 
             if !output.status.success() {
-                yield HyperforgeEvent::Error {
-                    message: format!(
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Git,
+                    format!(
                         "git ls-files failed: {}",
                         String::from_utf8_lossy(&output.stderr)
                     ),
-                };
+                );
                 return;
             }
 
@@ -1980,7 +2836,7 @@ impl RepoHub {
                 tracked_files,
                 total_bytes,
             };
-        }
+        })
     }
 
     /// Count lines of code in a repository
@@ -1998,30 +2854,24 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let local = state.get_local_forge(&org).await;
 
             let record = match local.get_record(&name) {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repo '{name}' not found in LocalForge: {e}"),
-                    };
+                    yield msg_error!(Msg::RepoNotInLocalForge, name = name, error = e);
                     return;
                 }
             };
 
             let repo_path = if let Some(p) = &record.local_path { PathBuf::from(p) } else {
-                yield HyperforgeEvent::Error {
-                    message: format!("Repo '{name}' has no local_path set in LocalForge"),
-                };
+                yield msg_error!(Msg::RepoNoLocalPath, name = name);
                 return;
             };
 
             if !repo_path.exists() {
-                yield HyperforgeEvent::Error {
-                    message: format!("Repo path does not exist: {}", repo_path.display()),
-                };
+                yield msg_error!(Msg::RepoPathMissing, path = repo_path.display());
                 return;
             }
 
@@ -2032,20 +2882,19 @@ impl RepoHub {
             {
                 Ok(o) => o,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to run git ls-files: {e}"),
-                    };
+                    yield msg_error!(Msg::GitLsFilesFailed, error = e);
                     return;
                 }
             };
 
             if !output.status.success() {
-                yield HyperforgeEvent::Error {
-                    message: format!(
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Git,
+                    format!(
                         "git ls-files failed: {}",
                         String::from_utf8_lossy(&output.stderr)
                     ),
-                };
+                );
                 return;
             }
 
@@ -2080,7 +2929,7 @@ impl RepoHub {
                 total_files,
                 by_extension,
             };
-        }
+        })
     }
 
     /// Check if a repository has uncommitted changes
@@ -2098,30 +2947,24 @@ impl RepoHub {
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let local = state.get_local_forge(&org).await;
 
             let record = match local.get_record(&name) {
                 Ok(r) => r,
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repo '{name}' not found in LocalForge: {e}"),
-                    };
+                    yield msg_error!(Msg::RepoNotInLocalForge, name = name, error = e);
                     return;
                 }
             };
 
             let repo_path = if let Some(p) = &record.local_path { PathBuf::from(p) } else {
-                yield HyperforgeEvent::Error {
-                    message: format!("Repo '{name}' has no local_path set in LocalForge"),
-                };
+                yield msg_error!(Msg::RepoNoLocalPath, name = name);
                 return;
             };
 
             if !repo_path.exists() {
-                yield HyperforgeEvent::Error {
-                    message: format!("Repo path does not exist: {}", repo_path.display()),
-                };
+                yield msg_error!(Msg::RepoPathMissing, path = repo_path.display());
                 return;
             }
 
@@ -2136,11 +2979,40 @@ impl RepoHub {
                     };
                 }
                 Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to get status for '{name}': {e}"),
-                    };
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Git,
+                        format!("Failed to get status for '{name}': {e}"),
+                    );
                 }
             }
-        }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::LocalForge;
+
+    #[tokio::test]
+    async fn test_rename_rolls_back_when_a_forge_refuses() {
+        let github = LocalForge::new("acme");
+        github.create_repo("acme", &Repo::new("widget", Forge::GitHub)).await.unwrap();
+        // Codeberg doesn't have the repo, so it refuses the rename
+        let codeberg = LocalForge::new("acme");
+        let adapters: Vec<ForgeAdapter> = vec![
+            (Forge::GitHub, Ok(Box::new(github.clone()))),
+            (Forge::Codeberg, Ok(Box::new(codeberg))),
+        ];
+
+        let ForgeRenames { renamed, failure, .. } = rename_on_forges(adapters, "acme", "widget", "gadget").await;
+        assert_eq!(renamed.len(), 1);
+        assert!(failure.is_some_and(|e| e.starts_with("codeberg")));
+        assert!(github.repo_exists("acme", "gadget").await.unwrap());
+
+        let events = undo_forge_renames(&renamed, "acme", "widget", "gadget").await;
+        assert!(events.iter().all(|e| matches!(e, HyperforgeEvent::Info { .. })));
+        assert!(github.repo_exists("acme", "widget").await.unwrap());
+        assert!(!github.repo_exists("acme", "gadget").await.unwrap());
     }
 }
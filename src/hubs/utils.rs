@@ -1,24 +1,58 @@
 //! Shared helpers used by both `WorkspaceHub` and `BuildHub`.
 
+use std::path::Path;
 use std::sync::Arc;
 
-use crate::adapters::{CodebergAdapter, ForgePort, GitHubAdapter, GitLabAdapter};
-use crate::auth::YamlAuthProvider;
-use crate::config::HyperforgeConfig;
+use crate::adapters::{CodebergAdapter, ForgePort, GiteaAdapter, GitHubAdapter, GitLabAdapter};
+use crate::adapters::{EtagScope, ForgeResult};
+use crate::auth::{AuthProvider, YamlAuthProvider};
+use crate::config::{HyperforgeConfig, OrgConfig};
 use crate::hub::HyperforgeEvent;
 use crate::types::{Forge, OwnerType};
 
-/// Create a forge adapter from a forge name string.
+/// Self-hosted instance for a `forges` entry under `org`, resolved the way
+/// the git remotes are: the repo's own `[forge.<name>] host` (`repo_host`),
+/// else the org config's in `config_dir`
+pub(crate) fn forge_host(
+    config_dir: &Path,
+    org: &str,
+    forge: &str,
+    repo_host: Option<&str>,
+) -> Option<String> {
+    repo_host
+        .map(str::to_string)
+        .or_else(|| {
+            let (base, _) = HyperforgeConfig::split_account(forge);
+            OrgConfig::load(config_dir, org).forge_host(base).map(str::to_string)
+        })
+}
+
+/// GitLab adapter for an org, on the self-hosted instance `host` (see
+/// [`forge_host`]) if set
+pub(crate) fn gitlab_adapter(auth: Arc<dyn AuthProvider>, org: &str, host: Option<&str>) -> ForgeResult<GitLabAdapter> {
+    match host {
+        Some(host) => GitLabAdapter::with_host(auth, org, host),
+        None => GitLabAdapter::new(auth, org),
+    }
+}
+
+/// Create a forge adapter from a forge name string. `host` is the
+/// self-hosted GitLab instance, from [`forge_host`].
+///
+/// The adapter asks for `<forge>/<org>/token`; the provider answers with
+/// the org's own token when one is stored and the forge-wide
+/// `<forge>/token` otherwise, so orgs with separate PATs don't share one.
 pub(crate) fn make_adapter(
     forge: &str,
     org: &str,
     owner_type: Option<OwnerType>,
+    host: Option<&str>,
 ) -> Result<Arc<dyn ForgePort>, String> {
     let auth = YamlAuthProvider::new()
         .map_err(|e| format!("Failed to create auth provider: {e}"))?;
     let auth = Arc::new(auth);
     let target_forge = HyperforgeConfig::parse_forge(forge)
-        .ok_or_else(|| format!("Invalid forge: {forge}. Must be github, codeberg, or gitlab"))?;
+        .ok_or_else(|| format!("Invalid forge: {forge}. Must be github, codeberg, gitlab, or a Gitea/Forgejo hostname"))?;
     let adapter: Arc<dyn ForgePort> = match target_forge {
         Forge::GitHub => {
             let a = GitHubAdapter::new(auth, org)
@@ -37,17 +71,35 @@ pub(crate) fn make_adapter(
             })
         }
         Forge::GitLab => {
-            let a = GitLabAdapter::new(auth, org)
+            let a = gitlab_adapter(auth, org, host)
                 .map_err(|e| format!("Failed to create GitLab adapter: {e}"))?;
             Arc::new(match owner_type {
                 Some(ot) => a.with_owner_type(ot),
                 None => a,
             })
         }
+        Forge::Gitea { host } => {
+            let a = GiteaAdapter::gitea(auth, org, &host)
+                .map_err(|e| format!("Failed to create Gitea adapter for {host}: {e}"))?;
+            Arc::new(match owner_type {
+                Some(ot) => a.with_owner_type(ot),
+                None => a,
+            })
+        }
     };
     Ok(adapter)
 }
 
+/// The [`EtagScope`] a listing of `org` on `forge` is made under: the stored
+/// token `make_adapter`'s provider resolves for it, and the owner type.
+pub(crate) async fn etag_scope(forge: &str, org: &str, owner_type: Option<OwnerType>) -> EtagScope {
+    let token = match YamlAuthProvider::new() {
+        Ok(auth) => auth.get_secret(&format!("{forge}/{org}/token")).await.ok().flatten(),
+        Err(_) => None,
+    };
+    EtagScope::new(token.as_deref(), owner_type)
+}
+
 /// Simple glob matching for repo name filtering.
 pub fn glob_match(pattern: &str, name: &str) -> bool {
     if pattern == "*" {
@@ -74,6 +126,7 @@ pub fn glob_match(pattern: &str, name: &str) -> bool {
 pub struct RepoFilter {
     include: Vec<String>,
     exclude: Vec<String>,
+    profile: Vec<String>,
 }
 
 impl RepoFilter {
@@ -81,17 +134,30 @@ impl RepoFilter {
         Self {
             include: include.unwrap_or_default(),
             exclude: exclude.unwrap_or_default(),
+            profile: Vec::new(),
         }
     }
 
+    /// Also require a match against one of a workspace profile's globs, on
+    /// top of include/exclude
+    #[must_use]
+    pub fn with_profile(mut self, globs: &[String]) -> Self {
+        self.profile = globs.to_vec();
+        self
+    }
+
     /// Returns true if the name passes the filter.
     /// - If excludes match, always false (exclude wins)
+    /// - With a profile, name must match one of its globs
     /// - If includes are non-empty, name must match at least one
     /// - If both are empty, everything passes
     pub fn matches(&self, name: &str) -> bool {
         if self.exclude.iter().any(|pat| glob_match(pat, name)) {
             return false;
         }
+        if !self.profile.is_empty() && !self.profile.iter().any(|pat| glob_match(pat, name)) {
+            return false;
+        }
         if self.include.is_empty() {
             return true;
         }
@@ -99,7 +165,7 @@ impl RepoFilter {
     }
 
     pub const fn is_empty(&self) -> bool {
-        self.include.is_empty() && self.exclude.is_empty()
+        self.include.is_empty() && self.exclude.is_empty() && self.profile.is_empty()
     }
 }
 
@@ -181,4 +247,18 @@ mod tests {
         assert!(!f.matches("core-deprecated"));
         assert!(!f.matches("synapse"));
     }
+
+    #[test]
+    fn profile_narrows_include() {
+        let f = RepoFilter::new(Some(vec!["web-*".into(), "api".into()]), None)
+            .with_profile(&["web-*".to_string(), "design-system".to_string()]);
+        assert!(f.matches("web-app"));
+        assert!(!f.matches("api"));
+        assert!(!f.matches("design-system-extra"));
+
+        let profile_only = RepoFilter::new(None, None).with_profile(&["design-system".to_string()]);
+        assert!(profile_only.matches("design-system"));
+        assert!(!profile_only.matches("web-app"));
+        assert!(!profile_only.is_empty());
+    }
 }
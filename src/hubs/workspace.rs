@@ -4,27 +4,50 @@
 //! (manifest generation, publishing, cross-repo execution) live in [`super::build`].
 
 use async_stream::stream;
+use plexus_core::plexus::Activation;
 use futures::{Stream, StreamExt};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use chrono::Utc;
 
-use crate::adapters::{ForgePort, ForgeSyncState};
+use crate::adapters::{rate_limit, simulation};
+use crate::adapters::{BranchProtection, EtagScope, ForgePort, ForgeSyncState, LocalForge, NewPullRequest, StoredEtag, UpstreamStatus};
 use crate::commands::init::{init, InitOptions};
+use crate::commands::legacy::{self, MigrationStep};
+use crate::commands::pins::{self, PinReport};
 use crate::auth::credentials::preflight_check;
-use crate::auth::YamlAuthProvider;
+use crate::auth::{SecretSession, YamlAuthProvider};
+use crate::commands::pull::{pull, PullError, PullState};
+use crate::commands::remotes::{converge as converge_remotes, RemotesError};
+use crate::commands::status::{RepoSnapshot, StatusSnapshot};
+use crate::commands::upstreams::{audit as audit_upstreams, UpstreamsError};
 use crate::commands::push::{push, PushOptions};
-use crate::commands::runner::{collect_push_results, discover_or_bail, run_batch, run_batch_blocking, run_diff_batch, run_validation_gate};
-use crate::commands::workspace::{repo_from_config, DiscoveredRepo, WorkspaceContext};
+use crate::commands::runner::{blocked_event, collect_push_results, discover_or_bail, discover_streaming, DiscoveryProgress, run_batch, run_batch_blocking, run_diff_batch, run_validation_gate};
+use crate::commands::workspace::{repo_for_org, repo_from_config, DiscoveredRepo, WorkspaceContext};
 use crate::config::HyperforgeConfig;
+use crate::help;
+use crate::hubs::build::managed_files::{self, RepoFiles, SyncOptions};
 use crate::git::Git;
-use crate::hub::HyperforgeEvent;
+use crate::hub::{HyperforgeError, HyperforgeEvent, PullRequestInfo, RemoteSyncInfo};
+use crate::messages::Msg;
+use crate::{msg, msg_error};
+use crate::hubs::campaigns::CampaignsHub;
 use crate::hubs::HyperforgeState;
-use crate::hubs::repo::RepoHub;
-use crate::hubs::utils::{dry_prefix, make_adapter, workspace_summary, RepoFilter};
-use crate::services::SyncOp;
-use crate::types::Visibility;
-use std::collections::HashSet;
+use crate::hubs::repo::{clone_options, RepoHub};
+use crate::hubs::utils::{dry_prefix, etag_scope, forge_host, make_adapter, workspace_summary, RepoFilter};
+use crate::services::changes::{ChangeManifest, ChangePr};
+use crate::services::snapshot::{RepoMeta, Snapshot, SnapshotScope};
+use crate::output;
+use crate::scheduler::Scheduler;
+use crate::services::sync_checkpoint::{self, SyncCheckpoint};
+use crate::services::sync_plan::{PlannedOp, SyncPlan};
+use crate::services::{telemetry, MirrorService, SyncOp, WebhookAction, WebhookService};
+use crate::types::repo::name_key;
+use crate::types::{Forge, Visibility};
+use std::collections::{HashMap, HashSet};
+
+/// Paths listed per change kind by `check --verbose`
+const DIRTY_FILES_CAP: usize = 20;
 
 /// Sub-hub for multi-repo workspace orchestration
 #[derive(Clone)]
@@ -44,13 +67,35 @@ impl WorkspaceHub {
     crate_path = "plexus_core"
 )]
 impl WorkspaceHub {
+    /// This hub's method catalog
+    #[plexus_macros::method(
+        description = "List this hub's methods: what each does, its parameters (type, required or optional, description) and an example invocation",
+        params(
+            method = "Only describe this method (optional)"
+        )
+    )]
+    pub async fn help(
+        &self,
+        method: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, futures::stream::iter(help::events(&self.plugin_schema(), "synapse substrate hyperforge workspace", method.as_deref())))
+    }
+
+    /// Multi-repo change campaigns (define, run, track to merge).
+    #[plexus_macros::child]
+    fn campaigns(&self) -> CampaignsHub {
+        CampaignsHub::new(self.state.clone())
+    }
+
     /// Discover repos in a workspace directory
     #[plexus_macros::method(
         description = "Scan a workspace directory and report discovered repos, orgs, and forges",
         params(
             path = "Path to workspace directory",
             include = "Glob patterns — repo must match at least one (optional, repeatable)",
-            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)"
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            reindex = "Re-parse every manifest and rebuild the workspace index (optional, default: false)",
+            orphans = "Report configured repos missing from LocalForge and from every declared forge (optional, default: false)"
         )
     )]
     pub async fn discover(
@@ -58,36 +103,65 @@ impl WorkspaceHub {
         path: String,
         include: Option<Vec<String>>,
         exclude: Option<Vec<String>>,
+        reindex: Option<bool>,
+        orphans: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        stream! {
+        let should_reindex = reindex.unwrap_or(false);
+        let check_orphans = orphans.unwrap_or(false);
+        let state = self.state.clone();
+        output::structured(None, &self.state.config_dir, stream! {
             let filter = RepoFilter::new(include, exclude);
             let workspace_path = PathBuf::from(&path);
-            let ctx = match discover_or_bail(&workspace_path) {
-                Ok(ctx) => ctx,
-                Err(event) => { yield event; return; }
-            };
 
             yield HyperforgeEvent::Info {
-                message: format!("Scanning workspace: {}", ctx.root.display()),
+                message: format!("Scanning workspace: {}", workspace_path.display()),
             };
 
-            // Filter repos by name glob if provided
-            let filtered_repos: Vec<_> = ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)).collect();
-
-            // Report each discovered repo
-            for repo in &filtered_repos {
-                let org = repo.org().unwrap_or("(none)");
-                let forges = repo.forges().join(", ");
-                let git_status = if repo.is_git_repo { "git" } else { "no-git" };
-                let bs_label = format!("{}", repo.build_system);
+            // Report each repo as soon as discovery parses it
+            let progress = discover_streaming(workspace_path, should_reindex);
+            tokio::pin!(progress);
+            let mut outcome = None;
+            while let Some(update) = progress.next().await {
+                match update {
+                    DiscoveryProgress::Repo(repo) => {
+                        if filter.matches(&repo.dir_name) {
+                            let org = repo.org().unwrap_or("(none)");
+                            let forges = repo.forges().join(", ");
+                            let git_status = if repo.is_git_repo { "git" } else { "no-git" };
+                            let bs_label = format!("{}", repo.build_system);
 
-                yield HyperforgeEvent::Info {
-                    message: format!(
-                        "  {} [{}] org={} forges=[{}] build=[{}]",
-                        repo.dir_name, git_status, org, forges, bs_label
-                    ),
-                };
+                            yield HyperforgeEvent::Info {
+                                message: format!(
+                                    "  {} [{}] org={} forges=[{}] build=[{}]",
+                                    repo.dir_name, git_status, org, forges, bs_label
+                                ),
+                            };
+                            yield HyperforgeEvent::RepoDiscovered {
+                                repo_name: repo.dir_name.clone(),
+                                org: repo.org().map(str::to_string),
+                                forges: repo.forges().iter().map(|f| (*f).to_string()).collect(),
+                                build_system: bs_label,
+                                is_git_repo: repo.is_git_repo,
+                            };
+                        }
+                    }
+                    DiscoveryProgress::Done(result) => outcome = Some(result),
+                }
             }
+            let ctx = match outcome {
+                Some(Ok(ctx)) => ctx,
+                Some(Err(e)) => {
+                    yield msg_error!(Msg::DiscoveryFailed, error = e);
+                    return;
+                }
+                None => {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        "Discovery failed: scan task ended without a result".to_string(),
+                    );
+                    return;
+                }
+            };
 
             // Report unconfigured repos
             let filtered_unconfigured: Vec<_> = ctx.unconfigured_repos.iter().filter(|p| {
@@ -102,6 +176,13 @@ impl WorkspaceHub {
                 };
             }
 
+            if check_orphans {
+                let repos: Vec<_> = ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)).collect();
+                for event in discover_orphans(&repos, &state, &path).await {
+                    yield event;
+                }
+            }
+
             // Report orgs, forges, and build systems
             yield HyperforgeEvent::Info {
                 message: format!("Orgs: {}", ctx.orgs.join(", ")),
@@ -117,7 +198,7 @@ impl WorkspaceHub {
             }
 
             yield workspace_summary(&ctx);
-        }
+        })
     }
 
     /// Initialize unconfigured repos in a workspace
@@ -154,7 +235,7 @@ impl WorkspaceHub {
         let state = self.state.clone();
         let filter = RepoFilter::new(include, exclude);
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
             let workspace_path = PathBuf::from(&path);
             let dry_prefix = dry_prefix(is_dry_run);
 
@@ -222,15 +303,17 @@ impl WorkspaceHub {
             }
 
             if inferred_org.is_none() {
-                yield HyperforgeEvent::Error {
-                    message: "Cannot init repos: multiple orgs found and --org not specified.".to_string(),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    "Cannot init repos: multiple orgs found and --org not specified.".to_string(),
+                );
                 return;
             }
             if inferred_forges.is_empty() {
-                yield HyperforgeEvent::Error {
-                    message: "Cannot init repos: no forges found and --forges not specified.".to_string(),
-                };
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    "Cannot init repos: no forges found and --forges not specified.".to_string(),
+                );
                 return;
             }
 
@@ -323,9 +406,10 @@ impl WorkspaceHub {
                         if record.ci.is_none() {
                             record.ci = Some(ci.clone());
                             if let Err(e) = local.update_record(&record) {
-                                yield HyperforgeEvent::Error {
-                                    message: format!("Failed to update CI for {name}: {e}"),
-                                };
+                                yield HyperforgeEvent::error(
+                                    HyperforgeError::ForgeApi,
+                                    format!("Failed to update CI for {name}: {e}"),
+                                );
                                 continue;
                             }
                             // Re-materialize to write CI to config.toml
@@ -335,9 +419,10 @@ impl WorkspaceHub {
                                 &repo.path,
                                 crate::commands::materialize::MaterializeOpts::default(),
                             ) {
-                                yield HyperforgeEvent::Error {
-                                    message: format!("Failed to materialize CI for {name}: {e}"),
-                                };
+                                yield HyperforgeEvent::error(
+                                    HyperforgeError::Config,
+                                    format!("Failed to materialize CI for {name}: {e}"),
+                                );
                                 continue;
                             }
                             ci_injected += 1;
@@ -379,7 +464,7 @@ impl WorkspaceHub {
             };
 
             yield workspace_summary(&ctx);
-        }
+        })
     }
 
     /// Check all repos are on expected branch and clean
@@ -389,7 +474,8 @@ impl WorkspaceHub {
             path = "Path to workspace directory",
             branch = "Expected branch name (optional, default: main)",
             include = "Glob patterns — repo must match at least one (optional, repeatable)",
-            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)"
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            verbose = "List the modified, added, deleted and untracked files of dirty repos (optional, default: false)"
         )
     )]
     pub async fn check(
@@ -398,8 +484,10 @@ impl WorkspaceHub {
         branch: Option<String>,
         include: Option<Vec<String>>,
         exclude: Option<Vec<String>>,
+        verbose: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        stream! {
+        let is_verbose = verbose.unwrap_or(false);
+        output::structured(None, &self.state.config_dir, telemetry::instrument(&self.state.config_dir, "workspace.check", rate_limit::with_notices(stream! {
             let filter = RepoFilter::new(include, exclude);
             let workspace_path = PathBuf::from(&path);
             let expected_branch = branch.unwrap_or_else(|| "main".to_string());
@@ -412,6 +500,15 @@ impl WorkspaceHub {
             // Filter repos by name glob if provided
             let repos: Vec<_> = ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)).collect();
 
+            // Pinned repos are expected at their pin instead of on the branch
+            let pins = match crate::config::WorkspaceConfig::load(&ctx.root) {
+                Ok(c) => c.pins,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, format!("{e} — checking without pins"));
+                    std::collections::BTreeMap::default()
+                }
+            };
+
             yield HyperforgeEvent::Info {
                 message: format!(
                     "Checking {} repos (expected branch: {})",
@@ -427,36 +524,41 @@ impl WorkspaceHub {
             // Collect inputs for run_batch_blocking
             let check_inputs: Vec<_> = repos.iter()
                 .filter(|r| r.is_git_repo)
-                .map(|r| (r.dir_name.clone(), r.path.clone(), expected_branch.clone()))
+                .map(|r| (r.dir_name.clone(), r.path.clone(), expected_branch.clone(), pins.get(&r.dir_name).cloned()))
                 .collect();
 
-            let results = run_batch_blocking(check_inputs, 8, |(dir_name, path, exp_branch)| {
-                let current_branch = Git::current_branch(&path)
-                    .map_err(|e| format!("{dir_name}: failed to get branch: {e}"));
+            let results = run_batch_blocking(check_inputs, Scheduler::global().batch(None), |(dir_name, path, exp_branch, pin)| {
                 let status = Git::repo_status(&path)
                     .map_err(|e| format!("{dir_name}: failed to get status: {e}"));
                 let ssh_cmd = Git::config_get(&path, "core.sshCommand").ok().flatten();
                 let hf_org = Git::config_get(&path, "hyperforge.org").ok().flatten();
-                (dir_name, path, exp_branch, current_branch, status, ssh_cmd, hf_org)
+                let pin = pin.map(|p| pins::inspect(&path, &p).map_err(|e| format!("{dir_name}: failed to resolve pin '{p}': {e}")));
+                (dir_name, path, exp_branch, status, ssh_cmd, hf_org, pin)
             }).await;
 
             for result in results {
-                let (dir_name, path, exp_branch, current_branch, status, ssh_cmd, hf_org) = match result {
+                let (dir_name, path, exp_branch, status, ssh_cmd, hf_org, pin) = match result {
                     Ok(v) => v,
-                    Err(e) => { yield HyperforgeEvent::Error { message: e }; continue; }
+                    Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Internal, e); continue; }
                 };
 
-                let current_branch = match current_branch {
-                    Ok(b) => b,
-                    Err(e) => { yield HyperforgeEvent::Error { message: e }; continue; }
-                };
                 let status = match status {
                     Ok(s) => s,
-                    Err(e) => { yield HyperforgeEvent::Error { message: e }; continue; }
+                    Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Git, e); continue; }
                 };
+                let current_branch = status.branch.clone();
 
+                let pin = match pin.transpose() {
+                    Ok(p) => p,
+                    Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Git, e); continue; }
+                };
                 let is_clean = !status.has_changes && !status.has_staged && !status.has_untracked;
-                let on_correct_branch = current_branch == exp_branch;
+                let (exp_branch, on_correct_branch) = if let Some(report) = &pin {
+                    (report.pin.clone(), report.is_in_sync())
+                } else {
+                    let on = current_branch == exp_branch;
+                    (exp_branch, on)
+                };
 
                 if is_clean { clean_count += 1; } else { dirty_count += 1; }
                 if !on_correct_branch { wrong_branch_count += 1; }
@@ -470,11 +572,31 @@ impl WorkspaceHub {
                     on_correct_branch,
                 };
 
-                if ssh_cmd.as_deref() == Some("hyperforge-ssh") && hf_org.is_none() {
-                    yield HyperforgeEvent::Error {
-                        message: format!("{dir_name}: SSH misconfigured — hyperforge-ssh set but hyperforge.org missing"),
+                if let Some(report) = pin {
+                    yield pin_event(&dir_name, report);
+                }
+
+                if is_verbose && !is_clean {
+                    let mut files = status.files;
+                    let total = files.total();
+                    let omitted = files.truncate(DIRTY_FILES_CAP);
+                    yield HyperforgeEvent::RepoDirtyFiles {
+                        repo_name: dir_name.clone(),
+                        modified: files.modified,
+                        added: files.added,
+                        deleted: files.deleted,
+                        untracked: files.untracked,
+                        total,
+                        omitted,
                     };
                 }
+
+                if ssh_cmd.as_deref() == Some("hyperforge-ssh") && hf_org.is_none() {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("{dir_name}: SSH misconfigured — hyperforge-ssh set but hyperforge.org missing"),
+                    );
+                }
             }
 
             yield HyperforgeEvent::WorkspaceSummary {
@@ -488,7 +610,197 @@ impl WorkspaceHub {
                 push_failed: None,
                 validation_passed: None,
             };
-        }
+        })))
+    }
+
+    /// Git status of every repo in the workspace
+    #[plexus_macros::method(
+        description = "Report git status for every repo in a workspace: branch, ahead/behind per remote, dirty file count, stash count and last commit age, then a summary listing which repos are dirty, ahead, behind or stashed",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            fetch = "Fetch all remotes first so ahead/behind is current (optional, default: false — compares against the last fetch)",
+            snapshot = "Also write a canonical JSON snapshot (branch, dirty, ahead/behind per forge, config hash) to this file, for committing or diffing between runs (optional)"
+        )
+    )]
+    pub async fn status(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        fetch: Option<bool>,
+        snapshot: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let should_fetch = fetch.unwrap_or(false);
+        output::structured(None, &self.state.config_dir, stream! {
+            let filter = RepoFilter::new(include, exclude);
+            let ctx = match discover_or_bail(&PathBuf::from(&path)) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let inputs: Vec<_> = ctx.repos.iter()
+                .filter(|r| r.is_git_repo && filter.matches(&r.dir_name))
+                .map(|r| (r.dir_name.clone(), r.path.clone()))
+                .collect();
+            let total_repos = inputs.len();
+
+            let results = run_batch_blocking(inputs, Scheduler::global().batch(None), move |(dir_name, path)| {
+                let report = crate::commands::status::status_with(&path, should_fetch)
+                    .map_err(|e| format!("{dir_name}: failed to get status: {e}"));
+                (dir_name, path, report)
+            }).await;
+
+            let now = Utc::now().timestamp();
+            let mut state = StatusSnapshot::default();
+            let mut clean_repos = 0usize;
+            let (mut dirty, mut ahead, mut behind, mut stashed, mut failed) =
+                (Vec::new(), Vec::new(), Vec::new(), Vec::new(), Vec::new());
+
+            for result in results {
+                let (dir_name, path, report) = match result {
+                    Ok(v) => v,
+                    Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Internal, e); continue; }
+                };
+                let report = match report {
+                    Ok(r) => r,
+                    Err(e) => {
+                        state.repos.insert(dir_name.clone(), RepoSnapshot::failed(e.clone()));
+                        yield HyperforgeEvent::error(HyperforgeError::Git, e);
+                        failed.push(dir_name);
+                        continue;
+                    }
+                };
+
+                state.repos.insert(dir_name.clone(), RepoSnapshot::from_report(&path, &report));
+                if report.is_clean() { clean_repos += 1; } else { dirty.push(dir_name.clone()); }
+                if report.needs_push() { ahead.push(dir_name.clone()); }
+                if report.needs_pull() { behind.push(dir_name.clone()); }
+                if report.stashes > 0 { stashed.push(dir_name.clone()); }
+
+                yield HyperforgeEvent::WorkspaceRepoStatus {
+                    repo_name: dir_name,
+                    path: path.display().to_string(),
+                    branch: report.branch,
+                    remotes: report.forges.into_iter().map(|f| RemoteSyncInfo {
+                        forge: f.forge,
+                        remote: f.remote_name,
+                        exists: f.remote_exists,
+                        ahead: f.ahead,
+                        behind: f.behind,
+                        error: f.error,
+                    }).collect(),
+                    dirty_files: report.dirty_files,
+                    stashes: report.stashes,
+                    last_commit_age_secs: report.last_commit_at.map(|at| (now - at).max(0)),
+                };
+            }
+
+            let failed_count = failed.len();
+            yield HyperforgeEvent::WorkspaceStatusSummary {
+                total_repos,
+                clean_repos,
+                dirty,
+                ahead,
+                behind,
+                stashed,
+                failed,
+            };
+
+            if let Some(out) = snapshot {
+                match state.save(Path::new(&out)) {
+                    Ok(()) => yield HyperforgeEvent::StatusSnapshotSaved {
+                        path: out,
+                        repos: state.repos.len(),
+                        failed: failed_count,
+                    },
+                    Err(e) => yield HyperforgeEvent::error(HyperforgeError::Internal, e),
+                }
+            }
+        })
+    }
+
+    /// Check out every pinned repo at its pin
+    #[plexus_macros::method(
+        description = "Check out every repo pinned under [pins] in workspace.toml at its tag or commit (detached HEAD); unpinned repos are left on their branches",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            fetch = "Fetch all remotes when a pin doesn't resolve locally (optional, default: true)",
+            dry_run = "Report what would be checked out without touching any repo (optional, default: false)",
+            profile = "Only thaw the pinned repos of this [profiles] entry (optional, defaults to the workspace's active profile)"
+        )
+    )]
+    pub async fn thaw(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        fetch: Option<bool>,
+        dry_run: Option<bool>,
+        profile: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let should_fetch = fetch.unwrap_or(true);
+        let is_dry_run = dry_run.unwrap_or(false);
+        output::structured(None, &self.state.config_dir, stream! {
+            let ctx = match discover_or_bail(&PathBuf::from(&path)) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+            let filter = match profile_filter(&ctx.root, RepoFilter::new(include, exclude), profile.as_deref(), false) {
+                Ok(f) => f,
+                Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Config, e); return; }
+            };
+            let pins = match crate::config::WorkspaceConfig::load(&ctx.root) {
+                Ok(c) => c.pins,
+                Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Config, e); return; }
+            };
+            if pins.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No pins in workspace.toml — nothing to thaw".to_string(),
+                };
+                return;
+            }
+
+            let mut work = Vec::new();
+            for (dir_name, pin) in pins.into_iter().filter(|(name, _)| filter.matches(name)) {
+                match ctx.repos.iter().find(|r| r.dir_name == dir_name && r.is_git_repo) {
+                    Some(repo) => work.push((dir_name, repo.path.clone(), pin)),
+                    None => yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Pinned repo '{dir_name}' is not a git repo in this workspace"),
+                    ).with_context(dir_name),
+                }
+            }
+
+            let results = run_batch_blocking(work, Scheduler::global().batch(None), move |(dir_name, path, pin)| {
+                let report = pins::checkout(&path, &pin, should_fetch, is_dry_run);
+                (dir_name, pin, report)
+            }).await;
+
+            let mut failed = 0usize;
+            for result in results {
+                match result {
+                    Ok((dir_name, _, Ok(report))) => yield pin_event(&dir_name, report),
+                    Ok((dir_name, pin, Err(e))) => {
+                        failed += 1;
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Git,
+                            format!("  {dir_name}: can't check out pin '{pin}': {e}"),
+                        ).with_context(dir_name);
+                    }
+                    Err(e) => { failed += 1; yield msg_error!(Msg::TaskFailed, error = e); }
+                }
+            }
+            if failed > 0 {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Git,
+                    format!("Thaw: {failed} pinned repo(s) could not be checked out"),
+                );
+            }
+        })
     }
 
     /// Push all repos to their configured forges
@@ -502,7 +814,10 @@ impl WorkspaceHub {
             dry_run = "Preview pushes without executing (optional, default: false)",
             set_upstream = "Set upstream tracking (optional, default: false)",
             validate = "Run containerized validation before pushing (optional, default: false)",
-            skip_auth_check = "Skip pre-flight credential check (optional, default: false)"
+            skip_auth_check = "Skip pre-flight credential check (optional, default: false)",
+            scan = "Scan outgoing commits for large files and secrets first (optional, default: per-repo config scan_outgoing)",
+            allow_findings = "Push even if the outgoing-commit scan reports findings (optional, default: false)",
+            concurrency = "Repos worked on at once (optional, default: [concurrency] batch in config.toml, else 8; 0 = unbounded)"
         )
     )]
     pub async fn push_all(
@@ -515,11 +830,19 @@ impl WorkspaceHub {
         set_upstream: Option<bool>,
         validate: Option<bool>,
         skip_auth_check: Option<bool>,
+        scan: Option<bool>,
+        allow_findings: Option<bool>,
+        concurrency: Option<u32>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let push_branch = branch; // passed through to PushOptions
+        let is_scan = scan.unwrap_or(false);
+        let is_allow_findings = allow_findings.unwrap_or(false);
         let filter = RepoFilter::new(include, exclude);
         let is_skip_auth = skip_auth_check.unwrap_or(false);
-        stream! {
+        let batch = Scheduler::global().batch(concurrency.map(|n| n as usize));
+        output::structured(None, &self.state.config_dir, telemetry::instrument(&self.state.config_dir, "workspace.push_all", rate_limit::with_notices(stream! {
+            let _session = SecretSession::begin();
+
             let workspace_path = PathBuf::from(&path);
             let is_dry_run = dry_run.unwrap_or(false);
             let is_set_upstream = set_upstream.unwrap_or(false);
@@ -571,19 +894,32 @@ impl WorkspaceHub {
                 };
             }
 
+            let pins = match pinned_repos(&ctx.root) {
+                Ok(p) => p,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    std::collections::BTreeMap::default()
+                }
+            };
+            for (dir_name, pin) in repos.iter().filter_map(|r| Some((&r.dir_name, pins.get(&r.dir_name)?))) {
+                yield pinned_skip_event(dir_name, pin);
+            }
+
             // Parallel push via run_batch_blocking
             let push_inputs: Vec<_> = repos.iter()
-                .filter(|r| r.is_git_repo)
+                .filter(|r| r.is_git_repo && !pins.contains_key(&r.dir_name))
                 .map(|r| {
                     let mut options = PushOptions::new();
                     if is_dry_run { options = options.dry_run(); }
                     if is_set_upstream { options = options.set_upstream(); }
+                    if is_scan { options = options.scan_outgoing(); }
+                    if is_allow_findings { options = options.allow_findings(); }
                     if let Some(ref b) = push_branch { options = options.with_branch(b.clone()); }
                     (r.dir_name.clone(), r.path.clone(), options)
                 })
                 .collect();
 
-            let push_results = run_batch_blocking(push_inputs, 8, |(dir_name, path, options)| {
+            let push_results = run_batch_blocking(push_inputs, batch, |(dir_name, path, options)| {
                 let result = push(&path, options);
                 (dir_name, path, result)
             }).await;
@@ -604,340 +940,524 @@ impl WorkspaceHub {
                 push_failed: Some(batch.failed_count),
                 validation_passed: None,
             };
-        }
+        })))
     }
 
-    /// Compute sync diff between local and a remote forge
+    /// Fetch and fast-forward all repos in a workspace
     #[plexus_macros::method(
-        description = "Compute diff between local configuration and a remote forge. Use --path to discover from disk, or --org and --forge for direct registry access.",
+        description = "Fetch every repo's forges and fast-forward its current branch to origin in parallel. Reports ahead/behind per forge; diverged branches and repos mid-merge/rebase are reported and left untouched.",
         params(
-            path = "Path to workspace directory (discovers orgs/forges from disk)",
-            org = "Organization name (required if --path not provided)",
-            forge = "Target forge: github, codeberg, or gitlab (required if --path not provided)"
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            dry_run = "Fetch and report without fast-forwarding (optional, default: false)",
+            concurrency = "Repos worked on at once (optional, default: [concurrency] batch in config.toml, else 8; 0 = unbounded)"
         )
     )]
-    pub async fn diff(
+    pub async fn pull_all(
         &self,
-        path: Option<String>,
-        org: Option<String>,
-        forge: Option<String>,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        dry_run: Option<bool>,
+        concurrency: Option<u32>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        let state = self.state.clone();
-        let sync_service = self.state.sync_service.clone();
+        let filter = RepoFilter::new(include, exclude);
+        let is_dry_run = dry_run.unwrap_or(false);
+        let batch = Scheduler::global().batch(concurrency.map(|n| n as usize));
+        output::structured(None, &self.state.config_dir, telemetry::instrument(&self.state.config_dir, "workspace.pull_all", rate_limit::with_notices(stream! {
+            let workspace_path = PathBuf::from(&path);
 
-        stream! {
-            // Resolve org/forge pairs to diff
-            let pairs: Vec<(String, String)> = if let Some(ref workspace_path) = path {
-                let workspace_path = PathBuf::from(workspace_path);
-                let ctx = match discover_or_bail(&workspace_path) {
-                    Ok(ctx) => ctx,
-                    Err(event) => { yield event; return; }
-                };
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
 
-                let all_pairs = ctx.org_forge_pairs();
+            let repos: Vec<_> = ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)).collect();
 
-                // Filter by explicit org/forge if provided
-                match (&org, &forge) {
-                    (Some(o), Some(f)) => all_pairs.into_iter().filter(|(ao, af)| ao == o && af == f).collect(),
-                    (Some(o), None) => all_pairs.into_iter().filter(|(ao, _)| ao == o).collect(),
-                    _ => all_pairs,
-                }
-            } else if let (Some(o), Some(f)) = (&org, &forge) {
-                vec![(o.clone(), f.clone())]
-            } else {
-                yield HyperforgeEvent::Error {
-                    message: "Must provide --path or both --org and --forge".to_string(),
-                };
-                return;
+            yield HyperforgeEvent::Info {
+                message: format!("{}Pulling {} repos...", dry_prefix(is_dry_run), repos.len()),
             };
 
-            if pairs.is_empty() {
+            for repo in repos.iter().filter(|r| !r.is_git_repo) {
                 yield HyperforgeEvent::Info {
-                    message: "No org/forge pairs found to diff.".to_string(),
+                    message: format!("  Skipping {} (not a git repo)", repo.dir_name),
                 };
-                return;
             }
 
-            // Parallel diff: spawn per org/forge pair
-            {
-                let results = run_diff_batch(&pairs, &state, &sync_service).await;
+            let pins = match pinned_repos(&ctx.root) {
+                Ok(p) => p,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    std::collections::BTreeMap::default()
+                }
+            };
+            for (dir_name, pin) in repos.iter().filter_map(|r| Some((&r.dir_name, pins.get(&r.dir_name)?))) {
+                yield pinned_skip_event(dir_name, pin);
+            }
 
-                for result in results {
-                    let entry = match result {
-                        Ok(v) => v,
-                        Err(e) => {
-                            yield HyperforgeEvent::Error { message: format!("Task join error: {e}") };
-                            continue;
-                        }
-                    };
+            let inputs: Vec<_> = repos.iter()
+                .filter(|r| r.is_git_repo && !pins.contains_key(&r.dir_name))
+                .map(|r| (r.dir_name.clone(), r.path.clone()))
+                .collect();
+            let results = run_batch_blocking(inputs, batch, move |(dir_name, path)| {
+                let result = pull(&path, is_dry_run);
+                (dir_name, result)
+            }).await;
+
+            let (mut forwarded, mut up_to_date, mut diverged, mut blocked, mut failed) = (0usize, 0usize, 0usize, 0usize, 0usize);
+            for result in results {
+                let (dir_name, pull_result) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        failed += 1;
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                let report = match pull_result {
+                    Ok(report) => report,
+                    Err(PullError::UnsafeState { status }) => {
+                        blocked += 1;
+                        yield blocked_event(&dir_name, "pull", &status);
+                        continue;
+                    }
+                    Err(e) => {
+                        failed += 1;
+                        yield HyperforgeEvent::error(HyperforgeError::Git, format!("  {dir_name}: {e}"));
+                        continue;
+                    }
+                };
 
+                match report.origin() {
+                    Some(origin) if origin.fast_forwarded || (report.dry_run && origin.state == PullState::Behind) => forwarded += 1,
+                    Some(origin) if origin.error.is_some() => failed += 1,
+                    _ if report.diverged() => diverged += 1,
+                    _ => up_to_date += 1,
+                }
+                if report.diverged() {
                     yield HyperforgeEvent::Info {
-                        message: format!("Computing diff for {}/{}", entry.org_name, entry.forge_name),
+                        message: format!("  {dir_name}: {} has diverged from origin — not touched; rebase or merge it by hand", report.branch),
                     };
+                }
+                for r in report.results {
+                    yield HyperforgeEvent::RepoPull {
+                        repo_name: dir_name.clone(),
+                        forge: r.forge,
+                        remote_name: r.remote_name,
+                        branch: report.branch.clone(),
+                        ahead: r.ahead,
+                        behind: r.behind,
+                        status: r.state.as_str().to_string(),
+                        fast_forwarded: r.fast_forwarded,
+                        error: r.error,
+                    };
+                }
+            }
 
-                    match entry.diff_result {
-                        Ok(mut diff) => {
-                            // Enrich with git ahead/behind state
-                            let local = state.get_local_forge(&entry.org_name).await;
-                            if let Ok(records) = local.all_records() {
-                                enrich_diff_with_git_state(&mut diff, &entry.forge_name, &state, &records);
-                            }
-
-                            yield HyperforgeEvent::SyncSummary {
-                                forge: entry.forge_name.clone(),
-                                total: diff.ops.len(),
-                                to_create: diff.to_create().len(),
-                                to_update: diff.to_update().len(),
-                                to_delete: diff.to_delete().len(),
-                                in_sync: diff.in_sync().len(),
-                            };
-
-                            for op in diff.ops {
-                                yield HyperforgeEvent::SyncOp {
-                                    repo_name: op.repo.name.clone(),
-                                    operation: format!("{:?}", op.op).to_lowercase(),
-                                    forge: entry.forge_name.clone(),
-                                    details: op.details.clone(),
-                                };
-                            }
-                        }
-                        Err(e) => {
-                            yield HyperforgeEvent::Error { message: e };
-                        }
-                    }
-                }
-            }
-        }
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{}Pull: {forwarded} {}, {up_to_date} up to date, {diverged} diverged, {blocked} blocked, {failed} failed",
+                    dry_prefix(is_dry_run),
+                    if is_dry_run { "would fast-forward" } else { "fast-forwarded" },
+                ),
+            };
+        })))
     }
 
-    /// Full safe sync pipeline: discover → init → register → import → diff → apply (no deletes) → push
+    /// Mirror branches and tags from each repo's origin forge to its mirrors
     #[plexus_macros::method(
-        description = "Sync workspace to remote forges. With --reflect, retire remote-only repos. With --purge, delete previously staged repos.",
+        description = "Force-push every branch and tag from each repo's origin forge (the first in forges) to its other forges, reporting each ref that differs. With --prune, refs only a mirror has are deleted from it.",
         params(
-            path = "Path to workspace directory (required)",
-            org = "Organization name (inferred from workspace if only one org exists)",
-            forges = "Forges for unconfigured repos (inferred from existing configs if not specified)",
+            path = "Path to workspace directory",
             include = "Glob patterns — repo must match at least one (optional, repeatable)",
             exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
-            dry_run = "Preview all phases without making changes (optional, default: false)",
-            no_push = "Skip the git push phase (optional, default: false)",
-            no_init = "Skip initializing unconfigured repos (optional, default: true)",
-            validate = "Run containerized validation before pushing (optional, default: false)",
-            reflect = "Enable reflect mode: retire remote-only repos (optional, default: false)",
-            purge = "Delete repos previously staged for deletion. Implies --reflect (optional, default: false)",
-            branch = "Branch to push (optional, default: current checked-out branch per repo)",
-            skip_auth_check = "Skip pre-flight credential check (optional, default: false)"
+            prune = "Delete branches and tags that only exist on a mirror (optional, default: false)",
+            dry_run = "List differing refs without pushing (optional, default: false)"
         )
     )]
-    pub async fn sync(
+    pub async fn mirror(
         &self,
         path: String,
-        org: Option<String>,
-        forges: Option<Vec<String>>,
         include: Option<Vec<String>>,
         exclude: Option<Vec<String>>,
+        prune: Option<bool>,
         dry_run: Option<bool>,
-        no_push: Option<bool>,
-        no_init: Option<bool>,
-        validate: Option<bool>,
-        reflect: Option<bool>,
-        purge: Option<bool>,
-        branch: Option<String>,
-        skip_auth_check: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        let state = self.state.clone();
-        let sync_service = self.state.sync_service.clone();
-        let is_dry_run = dry_run.unwrap_or(false);
-        let is_no_push = no_push.unwrap_or(false);
-        let is_no_init = no_init.unwrap_or(true);
-        let is_validate = validate.unwrap_or(false);
-        let is_purge = purge.unwrap_or(false);
-        let is_reflect = reflect.unwrap_or(false) || is_purge;
-        let is_skip_auth = skip_auth_check.unwrap_or(false);
         let filter = RepoFilter::new(include, exclude);
+        let is_prune = prune.unwrap_or(false);
+        let is_dry_run = dry_run.unwrap_or(false);
+        output::structured(None, &self.state.config_dir, telemetry::instrument(&self.state.config_dir, "workspace.mirror", rate_limit::with_notices(stream! {
+            let _session = SecretSession::begin();
 
-        stream! {
             let workspace_path = PathBuf::from(&path);
-            let dry_prefix = dry_prefix(is_dry_run);
-
-            // ── Phase 1: Discover ──
-            yield HyperforgeEvent::Info {
-                message: format!("{dry_prefix}Phase 1/8: Discovering workspace..."),
-            };
 
             let ctx = match discover_or_bail(&workspace_path) {
                 Ok(ctx) => ctx,
                 Err(event) => { yield event; return; }
             };
 
-            yield HyperforgeEvent::Info {
-                message: format!(
-                    "  Found {} configured, {} unconfigured, {} non-git dirs. Orgs: [{}], Forges: [{}]",
-                    ctx.repos.len(),
-                    ctx.unconfigured_repos.len(),
-                    ctx.skipped_dirs.len(),
-                    ctx.orgs.join(", "),
-                    ctx.forges.join(", "),
-                ),
-            };
-
-            // Report non-git directories (no .git, no .hyperforge)
-            if !ctx.skipped_dirs.is_empty() {
-                for dir in &ctx.skipped_dirs {
-                    let name = dir.file_name().and_then(|n| n.to_str()).unwrap_or("?");
-                    yield HyperforgeEvent::Info {
-                        message: format!("  {name} [no git — needs git init]"),
-                    };
-                }
-            }
-
-            // ── Infer org and forges for unconfigured repos ──
-            let inferred_org: Option<String> = org.clone().or_else(|| {
-                if ctx.orgs.len() == 1 {
-                    Some(ctx.orgs[0].clone())
-                } else {
-                    None
-                }
-            });
-
-            let inferred_forges: Vec<String> = forges.clone().unwrap_or_else(|| ctx.forges.clone());
-
-            // Apply filter to unconfigured repos
-            let unconfigured: Vec<PathBuf> = ctx.unconfigured_repos.iter()
-                .filter(|p| {
-                    let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                    filter.matches(name)
-                })
-                .cloned()
+            // Only git repos with at least one mirror forge
+            let inputs: Vec<_> = ctx.repos.iter()
+                .filter(|r| filter.matches(&r.dir_name) && r.is_git_repo)
+                .filter(|r| r.config.as_ref().is_some_and(|c| c.forges.len() > 1))
+                .map(|r| (r.dir_name.clone(), r.path.clone()))
                 .collect();
 
-            let has_unconfigured = !unconfigured.is_empty();
-
-            if has_unconfigured && inferred_org.is_none() {
-                yield HyperforgeEvent::Error {
-                    message: "Cannot init unconfigured repos: multiple orgs found and --org not specified. Skipping init phase.".to_string(),
-                };
-            }
-            if has_unconfigured && inferred_forges.is_empty() {
-                yield HyperforgeEvent::Error {
-                    message: "Cannot init unconfigured repos: no forges found and --forges not specified. Skipping init phase.".to_string(),
+            if inputs.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No configured repos with mirror forges matched filter.".to_string(),
                 };
+                return;
             }
 
-            // ── Phase 2: Init unconfigured repos ──
-            let inits_performed;
+            yield HyperforgeEvent::Info {
+                message: format!("{}Mirroring {} repos...", dry_prefix(is_dry_run), inputs.len()),
+            };
 
-            if !is_no_init && has_unconfigured && inferred_org.is_some() && !inferred_forges.is_empty() {
-                yield HyperforgeEvent::Info {
-                    message: format!(
-                        "{}Phase 2/8: Initializing {} unconfigured repos (org={}, forges=[{}])...",
-                        dry_prefix,
-                        unconfigured.len(),
-                        inferred_org.as_deref().unwrap_or("?"),
-                        inferred_forges.join(", "),
-                    ),
-                };
+            let results = run_batch_blocking(inputs, Scheduler::global().batch(None), move |(dir_name, path)| {
+                let result = MirrorService::mirror(&path, is_prune, is_dry_run);
+                (dir_name, result)
+            }).await;
 
-                let (events, count) = sync_init_unconfigured(
-                    &unconfigured, &inferred_org, &inferred_forges, is_dry_run, dry_prefix,
-                );
-                inits_performed = count;
-                for event in events { yield event; }
-            } else {
-                inits_performed = 0;
-                yield HyperforgeEvent::Info {
-                    message: format!("{dry_prefix}Phase 2/8: Init skipped."),
+            let (mut pushed, mut in_sync, mut extra, mut failed) = (0usize, 0usize, 0usize, 0usize);
+            for result in results {
+                let (dir_name, mirrored) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        failed += 1;
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
                 };
-            }
-
-            // ── Phase 3: Re-discover ──
-            let ctx = if inits_performed > 0 && !is_dry_run {
-                yield HyperforgeEvent::Info {
-                    message: format!("{dry_prefix}Phase 3/8: Re-discovering after {inits_performed} inits..."),
+                let forges = match mirrored {
+                    Ok(forges) => forges,
+                    Err(e) => {
+                        failed += 1;
+                        yield HyperforgeEvent::error(HyperforgeError::Git, format!("  {dir_name}: {e}"));
+                        continue;
+                    }
                 };
-                match discover_or_bail(&workspace_path) {
-                    Ok(ctx) => ctx,
-                    Err(event) => { yield event; return; }
+                for r in forges {
+                    in_sync += r.in_sync;
+                    extra += r.extra_refs;
+                    if r.applied || is_dry_run {
+                        pushed += r.updates.len();
+                    }
+                    for update in r.updates {
+                        yield HyperforgeEvent::MirrorRef {
+                            repo_name: dir_name.clone(),
+                            forge: r.forge.clone(),
+                            ref_name: update.name,
+                            change: update.change.as_str().to_string(),
+                            old: update.old,
+                            new: update.new,
+                            applied: r.applied,
+                        };
+                    }
+                    if let Some(e) = r.error {
+                        failed += 1;
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Git,
+                            format!("  {dir_name}: mirror to {} ({}) failed: {e}", r.forge, r.remote_name),
+                        ).with_context(r.forge.clone());
+                    }
                 }
+            }
+
+            let extra_note = if extra > 0 {
+                format!(", {extra} mirror-only refs kept (use --prune to delete)")
             } else {
-                yield HyperforgeEvent::Info {
-                    message: format!("{dry_prefix}Phase 3/8: Re-discover skipped."),
-                };
-                ctx
+                String::new()
             };
-
-            // Apply filter to discovered repos
-            let filtered_repos: Vec<_> = ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)).cloned().collect();
-
-            // ── Phase 4: Register configured repos in LocalForge ──
             yield HyperforgeEvent::Info {
-                message: format!("{}Phase 4/8: Registering {} configured repos in LocalForge...", dry_prefix, filtered_repos.len()),
+                message: format!(
+                    "{}Mirror: {pushed} refs {}, {in_sync} in sync, {failed} failures{extra_note}",
+                    dry_prefix(is_dry_run),
+                    if is_dry_run { "to push" } else { "pushed" },
+                ),
             };
+        })))
+    }
 
-            let (events, registered, already_registered, unstaged) =
-                sync_register_repos(&filtered_repos, &ctx.orgs, &state, is_reflect, is_dry_run, dry_prefix).await;
-            for event in events { yield event; }
+    /// Make every repo's git remotes match its config
+    #[plexus_macros::method(
+        description = "Make each repo's git remotes match its config exactly: add missing remotes, rename stale ones that already point at the right URL, fix differing fetch/push URLs (including a [forge.<name>] fetch transport split) and remove remotes the config doesn't name. Prints a git remote -v style diff per change.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            keep = "Remote names never renamed or removed, e.g. upstream (optional, repeatable)",
+            dry_run = "Show the diff without changing any remote (optional, default: false)"
+        )
+    )]
+    pub async fn remotes_converge(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        keep: Option<Vec<String>>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+        let keep = keep.unwrap_or_default();
+        let is_dry_run = dry_run.unwrap_or(false);
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
 
-            yield HyperforgeEvent::Info {
-                message: format!(
-                    "  {} newly registered, {} already in LocalForge{}",
-                    registered, already_registered,
-                    if unstaged > 0 { format!(", {unstaged} unstaged") } else { String::new() },
-                ),
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
             };
 
-            // ── Phase 5: Import remote-only repos into LocalForge (ETag-based) ──
-            let pairs = ctx.org_forge_pairs();
+            let inputs: Vec<_> = ctx.repos.iter()
+                .filter(|r| filter.matches(&r.dir_name) && r.is_git_repo && r.config.is_some())
+                .map(|r| (r.dir_name.clone(), r.path.clone()))
+                .collect();
 
-            if is_reflect {
-                yield HyperforgeEvent::Info {
-                    message: format!("{dry_prefix}Phase 5/8: Import skipped (reflect mode)."),
-                };
-            } else {
+            if inputs.is_empty() {
                 yield HyperforgeEvent::Info {
-                    message: format!("{}Phase 5/8: Importing remote-only repos for {} org/forge pairs...", dry_prefix, pairs.len()),
+                    message: "No configured git repos matched filter.".to_string(),
                 };
-
-                let events = sync_import_remote(&pairs, &ctx.orgs, &state, is_dry_run, dry_prefix).await;
-                for event in events { yield event; }
+                return;
             }
 
-            // ── Pre-flight auth check (between import and diff) ──
-            if !is_skip_auth && !is_dry_run {
-                let preflight_errors = run_sync_preflight(&pairs).await;
-                if !preflight_errors.is_empty() {
-                    for event in preflight_errors {
-                        yield event;
+            yield HyperforgeEvent::Info {
+                message: format!("{}Converging remotes in {} repos...", dry_prefix(is_dry_run), inputs.len()),
+            };
+
+            let results = run_batch_blocking(inputs, Scheduler::global().batch(None), move |(dir_name, path)| {
+                let result = converge_remotes(&path, &keep, is_dry_run);
+                (dir_name, result)
+            }).await;
+
+            let (mut changed, mut in_sync, mut failed) = (0usize, 0usize, 0usize);
+            for result in results {
+                let (dir_name, converged) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        failed += 1;
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
                     }
-                    return;
+                };
+                let report = match converged {
+                    Ok(report) => report,
+                    Err(e) => {
+                        failed += 1;
+                        let category = match e {
+                            RemotesError::Collision { .. } | RemotesError::ConfigError(_) => HyperforgeError::Config,
+                            _ => HyperforgeError::Git,
+                        };
+                        yield HyperforgeEvent::error(category, format!("  {dir_name}: {e}"));
+                        continue;
+                    }
+                };
+                in_sync += report.in_sync;
+                changed += report.changes.len();
+                for change in report.changes {
+                    yield HyperforgeEvent::RemoteChanged {
+                        repo_name: dir_name.clone(),
+                        diff: change.diff_lines(),
+                        remote: change.name,
+                        action: change.action.as_str().to_string(),
+                        renamed_from: change.renamed_from,
+                        applied: report.applied,
+                    };
                 }
             }
 
-            // ── Phase 6: Diff ──
             yield HyperforgeEvent::Info {
-                message: format!("{dry_prefix}Phase 6/8: Computing diffs..."),
+                message: format!(
+                    "{}Remotes: {changed} changes {}, {in_sync} in sync, {failed} failures",
+                    dry_prefix(is_dry_run),
+                    if is_dry_run { "to apply" } else { "applied" },
+                ),
             };
+        })
+    }
 
-            // Collect diffs for phase 7 (parallel)
-            let mut all_diffs: Vec<(String, String, crate::services::SyncDiff)> = Vec::new();
-
-            {
-                let results = run_diff_batch(&pairs, &state, &sync_service).await;
-
-                for result in results {
-                    let entry = match result {
-                        Ok(v) => v,
-                        Err(e) => {
-                            yield HyperforgeEvent::Error { message: format!("Task join error: {e}") };
+    /// Check every branch's upstream, optionally repairing it
+    #[plexus_macros::method(
+        description = "Check that every local branch with a same-named branch on a configured remote tracks it, on the origin forge when origin has it (as of the last fetch). Flags missing, gone, misnamed and wrong-remote upstreams; --fix points them at the expected one. Upstreams on remotes the config doesn't manage are left alone.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            fix = "Set each flagged branch's upstream (optional, default: false)"
+        )
+    )]
+    pub async fn upstreams(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        fix: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+        let fix = fix.unwrap_or(false);
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let inputs: Vec<_> = ctx.repos.iter()
+                .filter(|r| filter.matches(&r.dir_name) && r.is_git_repo && r.config.is_some())
+                .map(|r| (r.dir_name.clone(), r.path.clone()))
+                .collect();
+
+            if inputs.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No configured git repos matched filter.".to_string(),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("Checking branch upstreams in {} repos...", inputs.len()),
+            };
+
+            let results = run_batch_blocking(inputs, Scheduler::global().batch(None), move |(dir_name, path)| {
+                let result = audit_upstreams(&path, fix);
+                (dir_name, result)
+            }).await;
+
+            let (mut flagged, mut in_sync, mut failed) = (0usize, 0usize, 0usize);
+            for result in results {
+                let (dir_name, audited) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        failed += 1;
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                let report = match audited {
+                    Ok(report) => report,
+                    Err(e) => {
+                        failed += 1;
+                        let category = match e {
+                            UpstreamsError::ConfigError(_) => HyperforgeError::Config,
+                            _ => HyperforgeError::Git,
+                        };
+                        yield HyperforgeEvent::error(category, format!("  {dir_name}: {e}"));
+                        continue;
+                    }
+                };
+                in_sync += report.in_sync;
+                flagged += report.findings.len();
+                for finding in report.findings {
+                    yield HyperforgeEvent::UpstreamMismatch {
+                        repo_name: dir_name.clone(),
+                        expected: finding.expected(),
+                        branch: finding.branch,
+                        problem: finding.problem.as_str().to_string(),
+                        current: finding.current,
+                        fixed: report.applied,
+                    };
+                }
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "Upstreams: {flagged} {}, {in_sync} correct, {failed} failures",
+                    if fix { "fixed" } else { "to fix (run with --fix)" },
+                ),
+            };
+        })
+    }
+
+    /// Compute sync diff between local and a remote forge
+    #[plexus_macros::method(
+        description = "Compute diff between local configuration and a remote forge. Use --path to discover from disk, or --org and --forge for direct registry access.",
+        params(
+            path = "Path to workspace directory (discovers orgs/forges from disk)",
+            org = "Organization name (required if --path not provided)",
+            forge = "Target forge: github, codeberg, or gitlab (required if --path not provided)"
+        )
+    )]
+    pub async fn diff(
+        &self,
+        path: Option<String>,
+        org: Option<String>,
+        forge: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let state = self.state.clone();
+        let sync_service = self.state.sync_service.clone();
+
+        output::structured(None, &self.state.config_dir, telemetry::instrument(&self.state.config_dir, "workspace.diff", rate_limit::with_notices(stream! {
+            let _session = SecretSession::begin();
+
+            // Resolve org/forge pairs to diff
+            let pairs: Vec<(String, String)> = if let Some(ref workspace_path) = path {
+                let workspace_path = PathBuf::from(workspace_path);
+                let ctx = match discover_or_bail(&workspace_path) {
+                    Ok(ctx) => ctx,
+                    Err(event) => { yield event; return; }
+                };
+
+                let all_pairs = ctx.org_forge_pairs();
+
+                // Filter by explicit org/forge if provided
+                match (&org, &forge) {
+                    (Some(o), Some(f)) => all_pairs.into_iter().filter(|(ao, af)| ao == o && af == f).collect(),
+                    (Some(o), None) => all_pairs.into_iter().filter(|(ao, _)| ao == o).collect(),
+                    _ => all_pairs,
+                }
+            } else if let (Some(o), Some(f)) = (&org, &forge) {
+                match legacy::gate("workspace diff") {
+                    Ok(notice) => yield notice,
+                    Err(event) => { yield event; return; }
+                }
+                vec![(o.clone(), f.clone())]
+            } else {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    "Must provide --path or both --org and --forge".to_string(),
+                );
+                return;
+            };
+
+            if pairs.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No org/forge pairs found to diff.".to_string(),
+                };
+                return;
+            }
+
+            // Parallel diff: spawn per org/forge pair
+            {
+                let results = run_diff_batch(&pairs, &state, &sync_service).await;
+
+                for result in results {
+                    let entry = match result {
+                        Ok(v) => v,
+                        Err(e) => {
+                            yield msg_error!(Msg::TaskJoinFailed, error = e);
                             continue;
                         }
                     };
 
+                    yield HyperforgeEvent::Info {
+                        message: format!("Computing diff for {}/{}", entry.org_name, entry.forge_name),
+                    };
+                    if entry.anonymous {
+                        yield HyperforgeEvent::Info {
+                            message: format!(
+                                "No {} token for {}: listed anonymously, so private repos show as missing",
+                                entry.forge_name, entry.org_name,
+                            ),
+                        };
+                    }
+
                     match entry.diff_result {
                         Ok(mut diff) => {
                             // Enrich with git ahead/behind state
                             let local = state.get_local_forge(&entry.org_name).await;
-                            if let Ok(records) = local.all_records() {
+                            if let Ok(records) = local.records() {
                                 enrich_diff_with_git_state(&mut diff, &entry.forge_name, &state, &records);
                             }
 
@@ -947,1269 +1467,3970 @@ impl WorkspaceHub {
                                 to_create: diff.to_create().len(),
                                 to_update: diff.to_update().len(),
                                 to_delete: diff.to_delete().len(),
+                                to_archive: diff.to_archive().len(),
                                 in_sync: diff.in_sync().len(),
                             };
 
-                            if !diff.to_delete().is_empty() {
-                                yield HyperforgeEvent::Info {
-                                    message: format!(
-                                        "  {} repos would be deleted on {} — skipped by sync (use 'workspace apply' to delete)",
-                                        diff.to_delete().len(),
-                                        entry.forge_name,
-                                    ),
-                                };
+                            for event in case_mismatch_events(&diff, &entry.forge_name) {
+                                yield event;
                             }
 
-                            all_diffs.push((entry.org_name, entry.forge_name, diff));
+                            for op in diff.ops {
+                                yield HyperforgeEvent::SyncOp {
+                                    repo_name: op.repo.name.clone(),
+                                    operation: format!("{:?}", op.op).to_lowercase(),
+                                    forge: entry.forge_name.clone(),
+                                    details: op.details.clone(),
+                                };
+                            }
                         }
                         Err(e) => {
-                            yield HyperforgeEvent::Error { message: e };
+                            yield HyperforgeEvent::error(HyperforgeError::ForgeApi, e);
                         }
                     }
                 }
             }
+        })))
+    }
 
-            // ── Phase 7: Apply creates/updates via repo sync + inline privatization ──
-            yield HyperforgeEvent::Info {
-                message: format!("{dry_prefix}Phase 7/8: Applying creates and updates..."),
-            };
+    /// Full safe sync pipeline: discover → init → register → import → diff → apply (no deletes) → push
+    #[plexus_macros::method(
+        description = "Sync workspace to remote forges. With --reflect, retire remote-only repos. With --purge, delete previously staged repos.",
+        params(
+            path = "Path to workspace directory (required)",
+            org = "Organization name (inferred from workspace if only one org exists)",
+            forges = "Forges for unconfigured repos (inferred from existing configs if not specified)",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            dry_run = "Preview all phases without making changes (optional, default: false)",
+            no_push = "Skip the git push phase (optional, default: false)",
+            no_init = "Skip initializing unconfigured repos (optional, default: true)",
+            validate = "Run containerized validation before pushing (optional, default: false)",
+            reflect = "Enable reflect mode: retire remote-only repos (optional, default: false)",
+            purge = "Delete repos previously staged for deletion. Implies --reflect (optional, default: false)",
+            branch = "Branch to push (optional, default: current checked-out branch per repo)",
+            skip_auth_check = "Skip pre-flight credential check (optional, default: false)",
+            probe_forges = "Also probe forges a repo doesn't declare and warn about undeclared copies, e.g. forgotten legacy mirrors (optional, default: false)",
+            resume = "Skip the repos and phases an interrupted sync with the same options already finished, per .hyperforge/sync-checkpoint.toml (optional, default: false)",
+            format = "Output format: text, or json for typed events only as NDJSON (optional, default: [output] format in config.toml, else text)",
+            concurrency = "Repos worked on at once (optional, default: [concurrency] batch in config.toml, else 8; 0 = unbounded)"
+        )
+    )]
+    pub async fn sync(
+        &self,
+        path: String,
+        org: Option<String>,
+        forges: Option<Vec<String>>,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        dry_run: Option<bool>,
+        no_push: Option<bool>,
+        no_init: Option<bool>,
+        validate: Option<bool>,
+        reflect: Option<bool>,
+        purge: Option<bool>,
+        branch: Option<String>,
+        skip_auth_check: Option<bool>,
+        probe_forges: Option<bool>,
+        resume: Option<bool>,
+        format: Option<String>,
+        concurrency: Option<u32>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let state = self.state.clone();
+        let sync_service = self.state.sync_service.clone();
+        let is_dry_run = dry_run.unwrap_or(false);
+        let is_no_push = no_push.unwrap_or(false);
+        let is_no_init = no_init.unwrap_or(true);
+        let is_validate = validate.unwrap_or(false);
+        let is_purge = purge.unwrap_or(false);
+        let is_reflect = reflect.unwrap_or(false) || is_purge;
+        let is_skip_auth = skip_auth_check.unwrap_or(false);
+        let is_probe = probe_forges.unwrap_or(false);
+        let is_resume = resume.unwrap_or(false);
+        let checkpoint_options = sync_checkpoint::options_key(
+            org.as_deref(), forges.as_deref(), include.as_deref(), exclude.as_deref(),
+            is_no_push, is_no_init, is_validate, is_reflect, is_purge, branch.as_deref(),
+        );
+        let filter = RepoFilter::new(include, exclude);
 
-            // Collect unique repos needing create/update, and handle deletes (privatize) inline
-            let mut repos_to_sync: Vec<(String, String)> = Vec::new(); // (org, name)
-            let mut seen_sync = HashSet::new();
-            let mut privatize_items: Vec<(String, String, crate::types::Repo)> = Vec::new(); // (org, forge, repo)
+        let batch = Scheduler::global().batch(concurrency.map(|n| n as usize));
+        output::structured(format.as_deref(), &self.state.config_dir, telemetry::instrument(&self.state.config_dir, "workspace.sync", rate_limit::with_notices(stream! {
+            let _session = SecretSession::begin();
 
-            for (org_name, forge_name, diff) in &all_diffs {
-                for repo_op in &diff.ops {
-                    match repo_op.op {
-                        SyncOp::Create | SyncOp::Update => {
-                            let key = (org_name.clone(), repo_op.repo.name.clone());
-                            if seen_sync.insert(key.clone()) {
-                                repos_to_sync.push(key);
-                            }
-                        }
-                        SyncOp::Delete => {
-                            privatize_items.push((org_name.clone(), forge_name.clone(), repo_op.repo.clone()));
-                        }
-                        SyncOp::InSync => {}
-                    }
-                }
-            }
+            let workspace_path = PathBuf::from(&path);
+            let dry_prefix = dry_prefix(is_dry_run);
 
-            // Delegate creates/updates to repo sync
-            let mut total_synced = 0usize;
-            let mut total_sync_errors = 0usize;
+            // ── Phase 1: Discover ──
+            yield HyperforgeEvent::Info {
+                message: format!("{dry_prefix}Phase 1/8: Discovering workspace..."),
+            };
 
-            if !repos_to_sync.is_empty() {
-                let repo_hub = RepoHub::new(state.clone());
-                let sync_items: Vec<_> = repos_to_sync.into_iter().map(|(org, name)| {
-                    let hub = Clone::clone(&repo_hub);
-                    (hub, org, name)
-                }).collect();
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
 
-                let sync_results = run_batch(sync_items, 8, {
-                    let dry_run = Some(is_dry_run);
-                    move |(hub, org, name): (RepoHub, String, String)| async move {
-                        let stream = hub.sync(org, name.clone(), dry_run).await;
-                        tokio::pin!(stream);
-                        let events: Vec<HyperforgeEvent> = stream.collect().await;
-                        let has_error = events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. }));
-                        (name, events, has_error)
+            let mut checkpoint = if is_resume {
+                match SyncCheckpoint::load(&ctx.root) {
+                    Ok(Some(checkpoint)) if checkpoint.options == checkpoint_options => {
+                        yield HyperforgeEvent::Info {
+                            message: format!(
+                                "  Resuming the sync started {}: {} repo operations and {} phases already done",
+                                checkpoint.started_at.format("%Y-%m-%d %H:%M UTC"),
+                                checkpoint.done.len(),
+                                checkpoint.phases.len(),
+                            ),
+                        };
+                        checkpoint
                     }
-                }).await;
-
-                for result in sync_results {
-                    match result {
-                        Ok((_name, events, has_error)) => {
-                            for event in events { yield event; }
-                            if has_error { total_sync_errors += 1; } else { total_synced += 1; }
-                        }
-                        Err(e) => {
-                            total_sync_errors += 1;
-                            yield HyperforgeEvent::Error { message: format!("Task join error: {e}") };
-                        }
+                    Ok(Some(checkpoint)) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Validation,
+                            format!(
+                                "Can't resume: the interrupted sync ran with other options ({}). Rerun it with those, or without --resume to start over.",
+                                checkpoint.options,
+                            ),
+                        );
+                        return;
                     }
+                    Ok(None) => {
+                        yield HyperforgeEvent::Info {
+                            message: "  No interrupted sync to resume — running in full".to_string(),
+                        };
+                        SyncCheckpoint::new(checkpoint_options)
+                    }
+                    Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Config, e); return; }
                 }
-            }
-
-            // Handle deletes (privatization) inline — this is workspace-specific logic
-            for (org_name, forge_name, repo) in &privatize_items {
-                let local = state.get_local_forge(org_name).await;
-                let record_info = local.get_record(&repo.name).ok();
+            } else {
+                SyncCheckpoint::new(checkpoint_options)
+            };
+            let mut run_failures = 0usize;
 
-                if record_info.as_ref().is_some_and(|r| r.protected) {
-                    yield HyperforgeEvent::SyncOp {
-                        repo_name: repo.name.clone(),
-                        operation: "skip_protected".to_string(),
-                        forge: forge_name.clone(),
-                        details: vec![],
-                    };
-                    continue;
-                }
-
-                let already_privatized = record_info.as_ref()
-                    .and_then(|rec| {
-                        HyperforgeConfig::parse_forge(forge_name)
-                            .map(|fe| rec.privatized_on.contains(&fe))
-                    })
-                    .unwrap_or(false);
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "  Found {} configured, {} unconfigured, {} non-git dirs. Orgs: [{}], Forges: [{}]",
+                    ctx.repos.len(),
+                    ctx.unconfigured_repos.len(),
+                    ctx.skipped_dirs.len(),
+                    ctx.orgs.join(", "),
+                    ctx.forges.join(", "),
+                ),
+            };
 
-                if already_privatized {
-                    yield HyperforgeEvent::SyncOp {
-                        repo_name: repo.name.clone(),
-                        operation: "already_privatized".to_string(),
-                        forge: forge_name.clone(),
-                        details: vec![],
+            // Report non-git directories (no .git, no .hyperforge)
+            if !ctx.skipped_dirs.is_empty() {
+                for dir in &ctx.skipped_dirs {
+                    let name = dir.file_name().and_then(|n| n.to_str()).unwrap_or("?");
+                    yield HyperforgeEvent::Info {
+                        message: format!("  {name} [no git — needs git init]"),
                     };
-                } else {
-                    let private_repo = crate::types::Repo::new(
-                        &repo.name,
-                        repo.origin.clone(),
-                    ).with_visibility(crate::types::Visibility::Private);
+                }
+            }
 
-                    if !is_dry_run {
-                        let ot = local.owner_type();
-                        match make_adapter(forge_name, org_name, ot) {
-                            Ok(adapter) => {
-                                match adapter.update_repo(org_name, &private_repo).await {
-                                    Ok(()) => {
-                                        if let Some(forge_enum) = HyperforgeConfig::parse_forge(forge_name) {
-                                            if let Ok(mut rec) = local.get_record(&repo.name) {
-                                                rec.privatized_on.insert(forge_enum);
-                                                let _ = local.update_record(&rec);
-                                                let _ = local.save_to_yaml().await;
-                                            }
-                                        }
-                                    }
-                                    Err(e) => {
-                                        yield HyperforgeEvent::Error {
-                                            message: format!("  Failed to privatize {} on {}: {}", repo.name, forge_name, e),
-                                        };
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                yield HyperforgeEvent::Error { message: e };
-                            }
-                        }
-                    }
-                    yield HyperforgeEvent::SyncOp {
-                        repo_name: repo.name.clone(),
-                        operation: "privatize".to_string(),
-                        forge: forge_name.clone(),
-                        details: vec![],
-                    };
+            // ── Infer org and forges for unconfigured repos ──
+            let inferred_org: Option<String> = org.clone().or_else(|| {
+                if ctx.orgs.len() == 1 {
+                    Some(ctx.orgs[0].clone())
+                } else {
+                    None
                 }
+            });
+
+            let inferred_forges: Vec<String> = forges.clone().unwrap_or_else(|| ctx.forges.clone());
+
+            // Apply filter to unconfigured repos
+            let unconfigured: Vec<PathBuf> = ctx.unconfigured_repos.iter()
+                .filter(|p| {
+                    let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                    filter.matches(name)
+                })
+                .cloned()
+                .collect();
+
+            let has_unconfigured = !unconfigured.is_empty();
+
+            if has_unconfigured && inferred_org.is_none() {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    "Cannot init unconfigured repos: multiple orgs found and --org not specified. Skipping init phase.".to_string(),
+                );
+            }
+            if has_unconfigured && inferred_forges.is_empty() {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    "Cannot init unconfigured repos: no forges found and --forges not specified. Skipping init phase.".to_string(),
+                );
             }
 
-            yield HyperforgeEvent::Info {
-                message: format!(
-                    "  {}{} repos synced, {} sync errors, {} privatization ops",
-                    dry_prefix, total_synced, total_sync_errors, privatize_items.len(),
-                ),
-            };
+            // ── Phase 2: Init unconfigured repos ──
+            let inits_performed;
 
-            // ── Phase 7.5: Retire remote-only repos (reflect mode) ──
-            if is_reflect {
+            if !is_no_init && has_unconfigured && inferred_org.is_some() && !inferred_forges.is_empty() {
                 yield HyperforgeEvent::Info {
                     message: format!(
-                        "{}Retire: {}retiring remote-only repos...",
+                        "{}Phase 2/8: Initializing {} unconfigured repos (org={}, forges=[{}])...",
                         dry_prefix,
-                        if is_purge { "Purging previously " } else { "Staging and " },
+                        unconfigured.len(),
+                        inferred_org.as_deref().unwrap_or("?"),
+                        inferred_forges.join(", "),
                     ),
                 };
 
-                let (events, staged_count, purged_count, protected_skipped) =
-                    sync_retire_remote_only(&pairs, &ctx, &state, is_dry_run, is_purge).await;
+                let org_config = inferred_org.as_deref()
+                    .map(|o| crate::config::OrgConfig::load(&state.config_dir, o))
+                    .unwrap_or_default();
+                let (events, count) = sync_init_unconfigured(
+                    &unconfigured, &inferred_org, &inferred_forges, &org_config, is_dry_run, dry_prefix,
+                );
+                inits_performed = count;
                 for event in events { yield event; }
-
+            } else {
+                inits_performed = 0;
                 yield HyperforgeEvent::Info {
-                    message: format!(
-                        "  {dry_prefix}{staged_count} staged, {purged_count} purged, {protected_skipped} protected (skipped)",
-                    ),
+                    message: format!("{dry_prefix}Phase 2/8: Init skipped."),
                 };
             }
 
-            // ── Validation gate (if --validate) ──
-            let validation_passed_result: Option<bool> = if is_validate {
+            // ── Phase 3: Re-discover ──
+            let ctx = if inits_performed > 0 && !is_dry_run {
                 yield HyperforgeEvent::Info {
-                    message: format!("{dry_prefix}Validation: Running containerized build check..."),
+                    message: format!("{dry_prefix}Phase 3/8: Re-discovering after {inits_performed} inits..."),
                 };
-
-                let gate = run_validation_gate(&filtered_repos, &ctx.root, is_dry_run);
-                for event in gate.events {
-                    yield event;
-                }
-                gate.passed
-            } else {
-                None
-            };
-
-            // ── Phase 8: Push git content ──
-            let skip_push_for_validation = validation_passed_result == Some(false);
-            if is_no_push || skip_push_for_validation {
-                if skip_push_for_validation {
-                    yield HyperforgeEvent::Info {
-                        message: format!("{dry_prefix}Phase 8/8: Push skipped (validation failed)."),
-                    };
-                } else {
-                    yield HyperforgeEvent::Info {
-                        message: format!("{dry_prefix}Phase 8/8: Push skipped (--no_push)."),
-                    };
+                match discover_or_bail(&workspace_path) {
+                    Ok(ctx) => ctx,
+                    Err(event) => { yield event; return; }
                 }
             } else {
                 yield HyperforgeEvent::Info {
-                    message: format!("{}Phase 8/8: Pushing {} repos...", dry_prefix, filtered_repos.len()),
+                    message: format!("{dry_prefix}Phase 3/8: Re-discover skipped."),
                 };
-
-                // Parallel push: spawn_blocking per repo
-                let push_inputs: Vec<_> = filtered_repos.iter()
-                    .filter(|r| r.is_git_repo)
-                    .map(|repo| {
-                        let dir_name = repo.dir_name.clone();
-                        let path = repo.path.clone();
-                        let mut options = PushOptions::new();
-                        if is_dry_run { options = options.dry_run(); }
-                        if let Some(ref b) = branch { options = options.with_branch(b.clone()); }
-                        (dir_name, path, options)
-                    })
-                    .collect();
-
-                let push_results = run_batch_blocking(push_inputs, 8, |(dir_name, path, options)| {
-                    let result = push(&path, options);
-                    (dir_name, path, result)
-                }).await;
-
-                let batch = collect_push_results(push_results);
-                for event in batch.events {
-                    yield event;
-                }
-
-                if batch.failed_repos.is_empty() {
-                    yield HyperforgeEvent::Info {
-                        message: format!(
-                            "  {}{} pushed successfully",
-                            dry_prefix, batch.success_count,
-                        ),
-                    };
-                } else {
-                    yield HyperforgeEvent::Info {
-                        message: format!(
-                            "  {}{} pushed successfully, {} failed: {}",
-                            dry_prefix, batch.success_count, batch.failed_count,
-                            batch.failed_repos.join(", "),
-                        ),
-                    };
-                }
-            }
-
-            // ── Summary ──
-            yield HyperforgeEvent::Info {
-                message: format!("{}{}pipeline complete.", dry_prefix,
-                    if is_reflect { "Reflect " } else { "Sync " }),
-            };
-
-            yield HyperforgeEvent::WorkspaceSummary {
-                total_repos: ctx.repos.len() + ctx.unconfigured_repos.len(),
-                configured_repos: ctx.repos.len(),
-                unconfigured_repos: ctx.unconfigured_repos.len(),
-                clean_repos: None,
-                dirty_repos: None,
-                wrong_branch_repos: None,
-                push_success: None,
-                push_failed: None,
-                validation_passed: validation_passed_result,
+                ctx
             };
-        }
-    }
-
-    /// Set default branch on all repos in a workspace
-    #[plexus_macros::method(
-        description = "Set the default branch on all remote forges for every repo in a workspace, and optionally git checkout locally",
-        params(
-            path = "Path to workspace directory",
-            branch = "Branch to set as default",
-            include = "Glob patterns — repo must match at least one (optional, repeatable)",
-            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
-            checkout = "Also run git checkout locally in each repo (optional, default: false)",
-            dry_run = "Preview changes without applying (optional, default: false)"
-        )
-    )]
-    pub async fn set_default_branch(
-        &self,
-        path: String,
-        branch: String,
-        include: Option<Vec<String>>,
-        exclude: Option<Vec<String>>,
-        checkout: Option<bool>,
-        dry_run: Option<bool>,
-    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        let is_dry_run = dry_run.unwrap_or(false);
-        let is_checkout = checkout.unwrap_or(false);
-        let state = self.state.clone();
-        let filter = RepoFilter::new(include, exclude);
 
-        stream! {
-            let workspace_path = PathBuf::from(&path);
-            let dry_prefix = dry_prefix(is_dry_run);
+            // Apply filter to discovered repos
+            let filtered_repos: Vec<_> = ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)).cloned().collect();
 
-            let ctx = match discover_or_bail(&workspace_path) {
-                Ok(ctx) => ctx,
-                Err(event) => { yield event; return; }
+            // ── Phase 4: Register configured repos in LocalForge ──
+            yield HyperforgeEvent::Info {
+                message: format!("{}Phase 4/8: Registering {} configured repos in LocalForge...", dry_prefix, filtered_repos.len()),
             };
 
-            // Filter repos by name glob if provided
-            let repos: Vec<_> = ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)).collect();
+            let (events, registered, already_registered, unstaged) =
+                sync_register_repos(&filtered_repos, &ctx.orgs, &state, is_reflect, is_dry_run, dry_prefix).await;
+            for event in events { yield event; }
 
             yield HyperforgeEvent::Info {
                 message: format!(
-                    "{}Setting default branch to '{}' for {} repos...",
-                    dry_prefix, branch, repos.len(),
+                    "  {} newly registered, {} already in LocalForge{}",
+                    registered, already_registered,
+                    if unstaged > 0 { format!(", {unstaged} unstaged") } else { String::new() },
                 ),
             };
 
-            let mut success_count = 0usize;
-            let mut error_count = 0usize;
+            // ── Phase 5: Import remote-only repos into LocalForge (ETag-based) ──
+            let pairs = ctx.org_forge_pairs();
 
-            // Report repos without org
-            for repo in &repos {
-                if repo.config.is_some() && repo.org().is_none() {
-                    yield HyperforgeEvent::Error {
-                        message: format!("  {}: no org configured, skipping", repo.dir_name),
-                    };
-                    error_count += 1;
-                }
+            if is_reflect {
+                yield HyperforgeEvent::Info {
+                    message: format!("{dry_prefix}Phase 5/8: Import skipped (reflect mode)."),
+                };
+            } else {
+                yield HyperforgeEvent::Info {
+                    message: format!("{}Phase 5/8: Importing remote-only repos for {} org/forge pairs...", dry_prefix, pairs.len()),
+                };
+
+                let events = sync_import_remote(&pairs, &ctx.orgs, &state, is_dry_run, dry_prefix).await;
+                for event in events { yield event; }
             }
 
-            // Build items for delegation to repo hub
-            let repo_hub = RepoHub::new(state);
-            let items: Vec<_> = repos.iter()
-                .filter(|r| r.config.is_some() && r.org().is_some())
-                .map(|r| (**r).clone())
-                .map(|repo| {
-                    let config = repo.config.clone().unwrap();
-                    let org = repo.org().unwrap().to_string();
-                    let repo_name = config.repo_name
-                        .unwrap_or_else(|| repo.dir_name.clone());
-                    let repo_path = repo.path.display().to_string();
-                    (org, repo_name, repo_path)
-                })
-                .map(|(org, repo_name, repo_path)| {
-                    let hub = Clone::clone(&repo_hub);
-                    (hub, org, repo_name, repo_path)
-                })
-                .collect();
-
-            let results = run_batch(items, 8, {
-                let branch = branch.clone();
-                move |(hub, org, repo_name, repo_path): (RepoHub, String, String, String)| {
-                    let branch = branch.clone();
-                    let path = if is_checkout { Some(repo_path) } else { None };
-                    async move {
-                        let stream = hub.set_default_branch(org, repo_name.clone(), branch, Some(is_checkout), path).await;
-                        tokio::pin!(stream);
-                        let events: Vec<HyperforgeEvent> = stream.collect().await;
-                        let has_error = events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. }));
-                        (repo_name, events, has_error)
+            // ── Pre-flight auth check (between import and diff) ──
+            if !is_skip_auth && !is_dry_run {
+                let preflight_errors = run_sync_preflight(&pairs).await;
+                if !preflight_errors.is_empty() {
+                    for event in preflight_errors {
+                        yield event;
                     }
+                    return;
                 }
-            }).await;
+            }
 
-            for result in results {
-                let (_repo_name, events, has_error) = match result {
-                    Ok(v) => v,
-                    Err(e) => {
-                        yield HyperforgeEvent::Error { message: format!("Task join error: {e}") };
-                        error_count += 1;
+            // ── Phase 6: Diff ──
+            yield HyperforgeEvent::Info {
+                message: format!("{dry_prefix}Phase 6/8: Computing diffs..."),
+            };
+
+            // Collect diffs for phase 7 (parallel)
+            let mut all_diffs: Vec<(String, String, crate::services::SyncDiff)> = Vec::new();
+
+            {
+                let results = run_diff_batch(&pairs, &state, &sync_service).await;
+
+                for result in results {
+                    let entry = match result {
+                        Ok(v) => v,
+                        Err(e) => {
+                            yield msg_error!(Msg::TaskJoinFailed, error = e);
+                            continue;
+                        }
+                    };
+
+                    // An anonymous listing misses private repos, and sync
+                    // couldn't write to the forge anyway
+                    if entry.anonymous {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Auth,
+                            format!("No {} token for {}: skipped (read-only diff works without one)", entry.forge_name, entry.org_name),
+                        ).with_context(entry.forge_name.clone());
                         continue;
                     }
-                };
 
-                for event in events {
-                    yield event;
-                }
+                    match entry.diff_result {
+                        Ok(mut diff) => {
+                            // Enrich with git ahead/behind state
+                            let local = state.get_local_forge(&entry.org_name).await;
+                            if let Ok(records) = local.records() {
+                                enrich_diff_with_git_state(&mut diff, &entry.forge_name, &state, &records);
+                            }
 
-                if has_error {
-                    error_count += 1;
-                } else {
-                    success_count += 1;
-                }
-            }
+                            yield HyperforgeEvent::SyncSummary {
+                                forge: entry.forge_name.clone(),
+                                total: diff.ops.len(),
+                                to_create: diff.to_create().len(),
+                                to_update: diff.to_update().len(),
+                                to_delete: diff.to_delete().len(),
+                                to_archive: diff.to_archive().len(),
+                                in_sync: diff.in_sync().len(),
+                            };
 
-            yield HyperforgeEvent::Info {
-                message: format!(
-                    "{dry_prefix}Set default branch complete: {success_count} succeeded, {error_count} failed",
-                ),
-            };
-        }
-    }
+                            for event in case_mismatch_events(&diff, &entry.forge_name) {
+                                yield event;
+                            }
 
-    /// Check remote default branch settings
-    #[plexus_macros::method(
-        description = "Verify all workspace repos have the expected default branch set on remote forges. Queries each forge API directly.",
-        params(
-            path = "Path to workspace directory",
-            branch = "Expected default branch (optional, default: main)",
-            include = "Glob patterns — repo must match at least one (optional, repeatable)",
-            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)"
-        )
-    )]
-    pub async fn check_default_branch(
-        &self,
-        path: String,
-        branch: Option<String>,
-        include: Option<Vec<String>>,
-        exclude: Option<Vec<String>>,
-    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        stream! {
-            let filter = RepoFilter::new(include, exclude);
-            let workspace_path = PathBuf::from(&path);
-            let expected = branch.unwrap_or_else(|| "main".to_string());
+                            if !diff.to_delete().is_empty() {
+                                yield HyperforgeEvent::Info {
+                                    message: format!(
+                                        "  {} repos would be deleted on {} — skipped by sync (use 'workspace apply' to delete)",
+                                        diff.to_delete().len(),
+                                        entry.forge_name,
+                                    ),
+                                };
+                            }
 
-            let ctx = match discover_or_bail(&workspace_path) {
-                Ok(ctx) => ctx,
-                Err(event) => { yield event; return; }
-            };
+                            all_diffs.push((entry.org_name, entry.forge_name, diff));
+                        }
+                        Err(e) => {
+                            yield HyperforgeEvent::error(HyperforgeError::ForgeApi, e);
+                        }
+                    }
+                }
+            }
 
-            let repos: Vec<_> = ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)).collect();
+            // ── Phase 6.5: Probe undeclared forges (read-only) ──
+            if is_probe {
+                yield HyperforgeEvent::Info {
+                    message: "Probe: checking undeclared forges for copies of configured repos...".to_string(),
+                };
+                let events = sync_probe_undeclared_forges(&filtered_repos, &state.config_dir).await;
+                for event in events { yield event; }
+            }
 
+            // ── Phase 7: Apply creates/updates via repo sync + inline privatization ──
             yield HyperforgeEvent::Info {
-                message: format!(
-                    "Checking remote default branch (expected: '{}') for {} repos across {} forges...",
-                    expected, repos.len(), ctx.forges.len(),
-                ),
+                message: format!("{dry_prefix}Phase 7/8: Applying creates and updates..."),
             };
 
-            // Build work items: (dir_name, repo_name, forge_name, org, expected)
-            let mut items: Vec<(String, String, String, String, String)> = Vec::new();
-            for repo in &repos {
-                let config = match &repo.config {
-                    Some(c) => c,
-                    None => continue,
-                };
-                let org = match repo.org() {
-                    Some(o) => o.to_string(),
-                    None => continue,
-                };
-                let repo_name = config.repo_name.clone()
-                    .unwrap_or_else(|| repo.dir_name.clone());
+            // Collect unique repos needing create/update, and handle deletes (privatize) inline
+            let mut repos_to_sync: Vec<(String, String)> = Vec::new(); // (org, name)
+            let mut seen_sync = HashSet::new();
+            let mut privatize_items: Vec<(String, String, crate::types::Repo)> = Vec::new(); // (org, forge, repo)
+            let mut archive_items: Vec<(String, String, crate::types::Repo)> = Vec::new();
+            let mut read_only_skipped = 0usize;
 
-                for forge_name in repo.forges() {
-                    items.push((
-                        repo.dir_name.clone(),
-                        repo_name.clone(),
-                        forge_name.to_string(),
-                        org.clone(),
-                        expected.clone(),
-                    ));
+            for (org_name, forge_name, diff) in &all_diffs {
+                for repo_op in &diff.ops {
+                    match repo_op.op {
+                        SyncOp::Create | SyncOp::Update => {
+                            let key = (org_name.clone(), repo_op.repo.name.clone());
+                            if seen_sync.insert(key.clone()) {
+                                repos_to_sync.push(key);
+                            }
+                        }
+                        SyncOp::Delete => {
+                            privatize_items.push((org_name.clone(), forge_name.clone(), repo_op.repo.clone()));
+                        }
+                        SyncOp::Archive => {
+                            archive_items.push((org_name.clone(), forge_name.clone(), repo_op.repo.clone()));
+                        }
+                        SyncOp::ReadOnly => {
+                            read_only_skipped += 1;
+                            let operation = if repo_op.repo.archived {
+                                "archive"
+                            } else if repo_op.details.is_empty() {
+                                "privatize"
+                            } else {
+                                "update"
+                            };
+                            yield HyperforgeEvent::InsufficientPermissions {
+                                org: org_name.clone(),
+                                forge: forge_name.clone(),
+                                repo_name: repo_op.repo.name.clone(),
+                                operation: operation.to_string(),
+                            };
+                        }
+                        SyncOp::InSync => {}
+                    }
                 }
             }
 
-            // Query each forge API in parallel
-            let results = run_batch(items, 8, |(dir_name, repo_name, forge_name, org, expected)| async move {
-                let ot = None; // owner type not needed for get_repo
-                let adapter = match make_adapter(&forge_name, &org, ot) {
-                    Ok(a) => a,
-                    Err(e) => return (dir_name, forge_name, None::<String>, expected, Some(e)),
+            let pending = repos_to_sync.len();
+            repos_to_sync.retain(|(org, name)| !checkpoint.is_done(&sync_checkpoint::apply_key(org, name)));
+            if repos_to_sync.len() < pending {
+                yield HyperforgeEvent::Info {
+                    message: format!("  Skipping {} repos the interrupted sync already applied", pending - repos_to_sync.len()),
                 };
+            }
 
-                match adapter.get_repo(&org, &repo_name).await {
-                    Ok(repo) => (dir_name, forge_name, repo.default_branch, expected, None),
-                    Err(e) => (dir_name, forge_name, None, expected, Some(e.to_string())),
-                }
-            }).await;
+            // Delegate creates/updates to repo sync
+            let mut total_synced = 0usize;
+            let mut total_sync_errors = 0usize;
 
-            let mut ok_count = 0usize;
-            let mut mismatch_count = 0usize;
-            let mut error_count = 0usize;
+            if !repos_to_sync.is_empty() {
+                let repo_hub = RepoHub::new(state.clone());
+                let sync_items: Vec<_> = repos_to_sync.into_iter().map(|(org, name)| {
+                    let hub = Clone::clone(&repo_hub);
+                    (hub, org, name)
+                }).collect();
 
-            for result in results {
-                let (dir_name, forge_name, remote_branch, expected, error) = match result {
-                    Ok(v) => v,
-                    Err(e) => {
-                        yield HyperforgeEvent::Error { message: format!("Task error: {e}") };
-                        error_count += 1;
-                        continue;
+                let sync_results = run_batch(sync_items, batch, {
+                    let dry_run = Some(is_dry_run);
+                    move |(hub, org, name): (RepoHub, String, String)| async move {
+                        let stream = hub.sync(org.clone(), name.clone(), dry_run).await;
+                        tokio::pin!(stream);
+                        let events: Vec<HyperforgeEvent> = stream.collect().await;
+                        let has_error = events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. }));
+                        (org, name, events, has_error)
                     }
-                };
+                }).await;
 
-                if let Some(err) = error {
-                    error_count += 1;
-                    yield HyperforgeEvent::Error {
-                        message: format!("  {dir_name} ({forge_name}): query failed: {err}"),
-                    };
+                for result in sync_results {
+                    match result {
+                        Ok((org, name, events, has_error)) => {
+                            for event in events { yield event; }
+                            if has_error {
+                                total_sync_errors += 1;
+                            } else {
+                                total_synced += 1;
+                                checkpoint.mark_done(sync_checkpoint::apply_key(&org, &name));
+                            }
+                        }
+                        Err(e) => {
+                            total_sync_errors += 1;
+                            yield msg_error!(Msg::TaskJoinFailed, error = e);
+                        }
+                    }
+                }
+            }
+
+            run_failures += total_sync_errors;
+            if let Some(event) = save_checkpoint(&checkpoint, &ctx.root, is_dry_run) { yield event; }
+
+            // Handle deletes (privatization) inline — this is workspace-specific logic
+            for (org_name, forge_name, repo) in &privatize_items {
+                let privatize_key = sync_checkpoint::privatize_key(org_name, forge_name, &repo.name);
+                if checkpoint.is_done(&privatize_key) {
                     continue;
                 }
+                let events = privatize_remote(&state, org_name, forge_name, repo, is_dry_run).await;
+                let failed = events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. }));
+                for event in events { yield event; }
+                if failed {
+                    run_failures += 1;
+                } else if !is_dry_run {
+                    checkpoint.mark_done(privatize_key);
+                    if let Some(event) = save_checkpoint(&checkpoint, &ctx.root, is_dry_run) { yield event; }
+                }
+            }
 
-                match remote_branch {
-                    Some(ref b) if b == &expected => {
-                        ok_count += 1;
-                    }
-                    Some(ref b) => {
-                        mismatch_count += 1;
-                        yield HyperforgeEvent::Error {
-                            message: format!("  {dir_name} ({forge_name}): default is '{b}', expected '{expected}'"),
-                        };
-                    }
-                    None => {
-                        // Forge didn't report default_branch — assume ok
-                        ok_count += 1;
-                    }
+            // Archive retired repos; re-running is harmless, so no checkpoint
+            for (org_name, forge_name, repo) in &archive_items {
+                let events = archive_remote(&state, org_name, forge_name, repo, is_dry_run).await;
+                if events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. })) {
+                    run_failures += 1;
                 }
+                for event in events { yield event; }
             }
 
             yield HyperforgeEvent::Info {
                 message: format!(
-                    "Default branch check: {ok_count} ok, {mismatch_count} mismatched, {error_count} errors"
+                    "  {}{} repos synced, {} sync errors, {} privatization ops, {} archive ops, {} read-only skipped",
+                    dry_prefix, total_synced, total_sync_errors, privatize_items.len(), archive_items.len(), read_only_skipped,
                 ),
             };
-        }
-    }
 
-    /// Verify workspace sync state
-    #[plexus_macros::method(
-        description = "Verify workspace configuration including orgs, SSH keys, and auth tokens. Use --path to discover from disk, or --org for registry access.",
-        params(
-            path = "Path to workspace directory (discovers orgs from disk)",
-            org = "Organization to verify (optional, verifies all if not specified)"
-        )
-    )]
-    pub async fn verify(
-        &self,
-        path: Option<String>,
-        org: Option<String>,
-    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        let state = self.state.clone();
-        let config_dir = self.state.config_dir.clone();
+            // ── Phase 7.25: Converge [webhooks] on every forge ──
+            if checkpoint.phase_done(sync_checkpoint::PHASE_WEBHOOKS) {
+                yield HyperforgeEvent::Info {
+                    message: format!("{dry_prefix}Webhooks: already converged by the interrupted sync."),
+                };
+            } else if filtered_repos.iter().any(|r| r.config.as_ref().is_some_and(|c| !c.webhooks.is_empty())) {
+                yield HyperforgeEvent::Info {
+                    message: format!("{dry_prefix}Webhooks: converging configured webhooks..."),
+                };
 
-        stream! {
-            yield HyperforgeEvent::Info {
-                message: "Starting workspace verification...".to_string(),
-            };
+                let (events, changed, failed) = sync_converge_webhooks(&filtered_repos, &state.config_dir, is_dry_run).await;
+                for event in events { yield event; }
+                run_failures += failed;
+                if failed == 0 {
+                    checkpoint.mark_phase(sync_checkpoint::PHASE_WEBHOOKS);
+                    if let Some(event) = save_checkpoint(&checkpoint, &ctx.root, is_dry_run) { yield event; }
+                }
 
-            // Determine orgs to check
-            let orgs_to_check: Vec<String> = if let Some(ref workspace_path) = path {
-                let workspace_path = PathBuf::from(workspace_path);
-                let ctx = match discover_or_bail(&workspace_path) {
-                    Ok(ctx) => ctx,
-                    Err(event) => { yield event; return; }
+                yield HyperforgeEvent::Info {
+                    message: format!("  {dry_prefix}{changed} webhook changes, {failed} failed"),
                 };
+            }
 
-                // If org specified, filter; otherwise use all discovered orgs
-                if let Some(ref org_name) = org {
-                    if ctx.orgs.contains(org_name) {
-                        vec![org_name.clone()]
-                    } else {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Org '{org_name}' not found in workspace"),
-                        };
-                        return;
-                    }
-                } else {
-                    ctx.orgs.clone()
+            // ── Phase 7.3: Converge [branch_protection] on every forge ──
+            if checkpoint.phase_done(sync_checkpoint::PHASE_BRANCH_PROTECTION) {
+                yield HyperforgeEvent::Info {
+                    message: format!("{dry_prefix}Branch protection: already converged by the interrupted sync."),
+                };
+            } else if filtered_repos.iter().any(|r| r.config.as_ref().is_some_and(|c| c.branch_protection.is_some())) {
+                yield HyperforgeEvent::Info {
+                    message: format!("{dry_prefix}Branch protection: checking protected branches..."),
+                };
+
+                let (events, updated, failed) =
+                    sync_branch_protection(&filtered_repos, &state.config_dir, &sync_service, is_dry_run).await;
+                for event in events { yield event; }
+                run_failures += failed;
+                if failed == 0 {
+                    checkpoint.mark_phase(sync_checkpoint::PHASE_BRANCH_PROTECTION);
+                    if let Some(event) = save_checkpoint(&checkpoint, &ctx.root, is_dry_run) { yield event; }
                 }
-            } else if let Some(org_name) = org {
-                vec![org_name]
-            } else {
-                // Legacy: list all orgs from config dir
-                let orgs_path = config_dir.join("orgs");
-                if let Ok(mut entries) = tokio::fs::read_dir(&orgs_path).await {
-                    let mut orgs = Vec::new();
-                    while let Ok(Some(entry)) = entries.next_entry().await {
-                        if let Some(name) = entry.file_name().to_str() {
-                            if name != "." && name != ".." {
-                                orgs.push(name.to_string());
-                            }
-                        }
-                    }
-                    orgs
-                } else {
-                    yield HyperforgeEvent::Error {
-                        message: "No organizations configured. Provide --path or --org.".to_string(),
-                    };
-                    return;
+
+                yield HyperforgeEvent::Info {
+                    message: format!("  {dry_prefix}{updated} branch protections updated, {failed} failed"),
+                };
+            }
+
+            // ── Phase 7.5: Retire remote-only repos (reflect mode) ──
+            if is_reflect && checkpoint.phase_done(sync_checkpoint::PHASE_RETIRE) {
+                yield HyperforgeEvent::Info {
+                    message: format!("{dry_prefix}Retire: already done by the interrupted sync."),
+                };
+            } else if is_reflect {
+                yield HyperforgeEvent::Info {
+                    message: format!(
+                        "{}Retire: {}retiring remote-only repos...",
+                        dry_prefix,
+                        if is_purge { "Purging previously " } else { "Staging and " },
+                    ),
+                };
+
+                let (events, staged_count, purged_count, protected_skipped) =
+                    sync_retire_remote_only(&pairs, &ctx, &state, is_dry_run, is_purge).await;
+                let failed = events.iter().filter(|e| matches!(e, HyperforgeEvent::Error { .. })).count();
+                for event in events { yield event; }
+                run_failures += failed;
+                if failed == 0 {
+                    checkpoint.mark_phase(sync_checkpoint::PHASE_RETIRE);
+                    if let Some(event) = save_checkpoint(&checkpoint, &ctx.root, is_dry_run) { yield event; }
                 }
-            };
 
-            let mut total_repos = 0;
-            let mut total_issues = 0;
+                yield HyperforgeEvent::Info {
+                    message: format!(
+                        "  {dry_prefix}{staged_count} staged, {purged_count} purged, {protected_skipped} protected (skipped)",
+                    ),
+                };
+            }
 
-            // Verify each org
-            for org_name in orgs_to_check {
+            // ── Validation gate (if --validate) ──
+            let validation_passed_result: Option<bool> = if is_validate {
                 yield HyperforgeEvent::Info {
-                    message: format!("Verifying org: {org_name}"),
+                    message: format!("{dry_prefix}Validation: Running containerized build check..."),
                 };
 
-                // Check org repos.yaml exists
-                let repos_yaml = config_dir.join("orgs").join(&org_name).join("repos.yaml");
-                if !repos_yaml.exists() {
-                    yield HyperforgeEvent::Error {
-                        message: format!("  ✗ Missing repos.yaml for org: {org_name}"),
-                    };
-                    total_issues += 1;
-                    continue;
+                let gate = run_validation_gate(&filtered_repos, &ctx.root, is_dry_run);
+                for event in gate.events {
+                    yield event;
                 }
+                gate.passed
+            } else {
+                None
+            };
 
-                // Load and count repos
-                let local_forge = state.get_local_forge(&org_name).await;
-                match local_forge.all_repos() {
-                    Ok(repos) => {
-                        let repo_count = repos.len();
-                        total_repos += repo_count;
+            // ── Phase 8: Push git content ──
+            let skip_push_for_validation = validation_passed_result == Some(false);
+            if skip_push_for_validation {
+                run_failures += 1;
+            }
+            if is_no_push || skip_push_for_validation {
+                if skip_push_for_validation {
+                    yield HyperforgeEvent::Info {
+                        message: format!("{dry_prefix}Phase 8/8: Push skipped (validation failed)."),
+                    };
+                } else {
+                    yield HyperforgeEvent::Info {
+                        message: format!("{dry_prefix}Phase 8/8: Push skipped (--no_push)."),
+                    };
+                }
+            } else {
+                yield HyperforgeEvent::Info {
+                    message: format!("{}Phase 8/8: Pushing {} repos...", dry_prefix, filtered_repos.len()),
+                };
 
-                        yield HyperforgeEvent::Info {
-                            message: format!("  ✓ Found {repo_count} repos in {org_name}"),
-                        };
-                    }
+                let pins = match pinned_repos(&ctx.root) {
+                    Ok(p) => p,
                     Err(e) => {
-                        yield HyperforgeEvent::Error {
-                            message: format!("  ✗ Failed to load repos: {e}"),
-                        };
-                        total_issues += 1;
+                        yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                        std::collections::BTreeMap::default()
                     }
+                };
+                for (dir_name, pin) in filtered_repos.iter().filter_map(|r| Some((&r.dir_name, pins.get(&r.dir_name)?))) {
+                    yield pinned_skip_event(dir_name, pin);
                 }
 
-                // Check auth tokens for common forges
-                for forge in &["github", "codeberg", "gitlab"] {
-                    let _token_key = format!("{forge}/{org_name}/token");
+                // Parallel push: spawn_blocking per repo
+                let push_inputs: Vec<_> = filtered_repos.iter()
+                    .filter(|r| r.is_git_repo && !pins.contains_key(&r.dir_name))
+                    .filter(|r| !checkpoint.is_done(&sync_checkpoint::push_key(&r.dir_name)))
+                    .map(|repo| {
+                        let dir_name = repo.dir_name.clone();
+                        let path = repo.path.clone();
+                        let mut options = PushOptions::new();
+                        if is_dry_run { options = options.dry_run(); }
+                        if let Some(ref b) = branch { options = options.with_branch(b.clone()); }
+                        (dir_name, path, options)
+                    })
+                    .collect();
+
+                let already_pushed = filtered_repos.iter()
+                    .filter(|r| r.is_git_repo && !pins.contains_key(&r.dir_name))
+                    .count() - push_inputs.len();
+                if already_pushed > 0 {
                     yield HyperforgeEvent::Info {
-                        message: format!("  ℹ Auth check for {forge}/{org_name} (use auth hub to verify)"),
+                        message: format!("  Skipping {already_pushed} repos the interrupted sync already pushed"),
                     };
                 }
-            }
-
-            // Check SSH keys
-            let ssh_dir = dirs::home_dir()
-                .unwrap_or_else(|| std::path::PathBuf::from("."))
-                .join(".ssh");
 
-            if ssh_dir.exists() {
-                let ssh_keys = vec!["hyperforge_ed25519", "id_ed25519", "id_rsa"];
-                let mut found_keys = Vec::new();
+                let push_results = run_batch_blocking(push_inputs, batch, |(dir_name, path, options)| {
+                    let result = push(&path, options);
+                    (dir_name, path, result)
+                }).await;
 
-                for key_name in ssh_keys {
-                    let key_path = ssh_dir.join(key_name);
-                    if key_path.exists() {
-                        found_keys.push(key_name);
+                for (dir_name, _, result) in push_results.iter().flatten() {
+                    if result.as_ref().is_ok_and(|report| report.all_success) {
+                        checkpoint.mark_done(sync_checkpoint::push_key(dir_name));
                     }
                 }
+                let batch = collect_push_results(push_results);
+                run_failures += batch.failed_count;
+                for event in batch.events {
+                    yield event;
+                }
 
-                if found_keys.is_empty() {
-                    yield HyperforgeEvent::Error {
-                        message: "✗ No SSH keys found in ~/.ssh/".to_string(),
+                if batch.failed_repos.is_empty() {
+                    yield HyperforgeEvent::Info {
+                        message: format!(
+                            "  {}{} pushed successfully",
+                            dry_prefix, batch.success_count,
+                        ),
                     };
-                    total_issues += 1;
                 } else {
                     yield HyperforgeEvent::Info {
-                        message: format!("✓ Found SSH keys: {}", found_keys.join(", ")),
+                        message: format!(
+                            "  {}{} pushed successfully, {} failed: {}",
+                            dry_prefix, batch.success_count, batch.failed_count,
+                            batch.failed_repos.join(", "),
+                        ),
+                    };
+                }
+            }
+
+            // ── Summary ──
+            if !is_dry_run {
+                if run_failures == 0 {
+                    if let Err(e) = SyncCheckpoint::remove(&ctx.root) {
+                        yield HyperforgeEvent::error(HyperforgeError::Internal, e);
+                    }
+                } else if let Some(event) = save_checkpoint(&checkpoint, &ctx.root, is_dry_run) {
+                    yield event;
+                } else {
+                    yield HyperforgeEvent::Info {
+                        message: format!("{run_failures} operations failed; rerun with --resume true to retry only what didn't finish"),
                     };
                 }
-            } else {
-                yield HyperforgeEvent::Error {
-                    message: "✗ ~/.ssh/ directory not found".to_string(),
-                };
-                total_issues += 1;
             }
 
-            // Summary
-            yield HyperforgeEvent::Info {
-                message: "=== Verification Summary ===".to_string(),
-            };
-            yield HyperforgeEvent::Info {
-                message: format!("Total repositories: {total_repos}"),
-            };
             yield HyperforgeEvent::Info {
-                message: format!("Issues found: {total_issues}"),
+                message: format!("{}{}pipeline complete.", dry_prefix,
+                    if is_reflect { "Reflect " } else { "Sync " }),
             };
 
-            if total_issues == 0 {
-                yield HyperforgeEvent::Info {
-                    message: "✓ Workspace configuration verified successfully!".to_string(),
-                };
-            } else {
-                yield HyperforgeEvent::Error {
-                    message: format!("✗ Found {total_issues} issues that need attention"),
-                };
-            }
-        }
+            yield HyperforgeEvent::WorkspaceSummary {
+                total_repos: ctx.repos.len() + ctx.unconfigured_repos.len(),
+                configured_repos: ctx.repos.len(),
+                unconfigured_repos: ctx.unconfigured_repos.len(),
+                clean_repos: None,
+                dirty_repos: None,
+                wrong_branch_repos: None,
+                push_success: None,
+                push_failed: None,
+                validation_passed: validation_passed_result,
+            };
+        })))
     }
 
-
-    /// Clone all repos for an org from `LocalForge` into a workspace directory
+    /// Save the changes a sync would make to a plan file for review
     #[plexus_macros::method(
-        description = "Clone all repos for an org from LocalForge into a workspace directory. Skips repos already on disk.",
+        description = "Compute the creates, updates and deletes (privatizations) `workspace sync` would make on every org/forge pair and write them to a plan file for review. Nothing is changed; `workspace apply --plan_file` carries the plan out later.",
         params(
-            org = "Organization name (must have repos in LocalForge)",
-            path = "Target workspace directory",
-            include = "Glob patterns — repo must match at least one (optional, repeatable)",
-            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
-            forge = "Preferred forge to clone from (optional, defaults to first in present_on)",
-            concurrency = "Max parallel clones (optional, default: 4)"
+            path = "Path to workspace directory (determines org/forge pairs)",
+            org = "Only plan for this org (optional)",
+            output = "Plan file to write (optional, default: <path>/.hyperforge/sync-plan.json)"
         )
     )]
-    pub async fn clone(
+    pub async fn plan(
         &self,
-        org: String,
         path: String,
-        include: Option<Vec<String>>,
-        exclude: Option<Vec<String>>,
-        forge: Option<String>,
-        concurrency: Option<u32>,
+        org: Option<String>,
+        output: Option<String>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
         let state = self.state.clone();
-        let max_concurrent = concurrency.unwrap_or(4) as usize;
-        let filter = RepoFilter::new(include, exclude);
-
-        stream! {
-            let workspace_path = PathBuf::from(&path);
+        let sync_service = self.state.sync_service.clone();
 
-            // 1. Load LocalForge
-            let local = state.get_local_forge(&org).await;
+        output::structured(None, &self.state.config_dir, telemetry::instrument(&self.state.config_dir, "workspace.plan", rate_limit::with_notices(stream! {
+            let _session = SecretSession::begin();
 
-            // 2. Get all records
-            let records = match local.all_records() {
-                Ok(r) => r,
-                Err(e) => {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to load repos: {e}"),
-                    };
-                    return;
-                }
+            let ctx = match discover_or_bail(&PathBuf::from(&path)) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
             };
-
-            if records.is_empty() {
-                yield HyperforgeEvent::Error {
-                    message: format!("No repos found in LocalForge for org '{org}'"),
-                };
+            let pairs: Vec<(String, String)> = ctx
+                .org_forge_pairs()
+                .into_iter()
+                .filter(|(o, _)| org.as_ref().is_none_or(|wanted| wanted == o))
+                .collect();
+            if pairs.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No org/forge pairs found — nothing to plan.".to_string(),
+                };
                 return;
             }
 
-            // 3. Filter by glob
-            let filtered: Vec<_> = records.into_iter().filter(|r| filter.matches(&r.name)).collect();
+            yield HyperforgeEvent::Info {
+                message: format!("Computing diffs for {} org/forge pairs...", pairs.len()),
+            };
 
-            // 4. Create workspace dir if needed
-            if let Err(e) = std::fs::create_dir_all(&workspace_path) {
-                yield HyperforgeEvent::Error {
-                    message: format!("Failed to create workspace directory: {e}"),
+            let mut diffs = Vec::new();
+            for result in run_diff_batch(&pairs, &state, &sync_service).await {
+                let entry = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskJoinFailed, error = e);
+                        return;
+                    }
                 };
-                return;
+                if entry.anonymous {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Auth,
+                        format!("No {} token for {}: left out of the plan", entry.forge_name, entry.org_name),
+                    ).with_context(entry.forge_name.clone());
+                    continue;
+                }
+                match entry.diff_result {
+                    Ok(diff) => diffs.push((entry.org_name, entry.forge_name, diff)),
+                    Err(e) => {
+                        // A plan missing a pair would look like a clean one
+                        yield HyperforgeEvent::error(HyperforgeError::ForgeApi, format!("{e}; no plan written"));
+                        return;
+                    }
+                }
             }
 
-            // 5. Skip repos already on disk
-            let total_filtered = filtered.len();
-            let to_clone: Vec<_> = filtered.into_iter().filter(|r| {
-                !workspace_path.join(&r.name).exists()
-            }).collect();
-
-            let skipped_count = total_filtered - to_clone.len();
-
-            if to_clone.is_empty() {
-                yield HyperforgeEvent::Info {
-                    message: format!(
-                        "All {total_filtered} repos already exist on disk. Nothing to clone.",
-                    ),
+            let plan = SyncPlan::new(&diffs);
+            for change in &plan.changes {
+                yield HyperforgeEvent::SyncOp {
+                    repo_name: change.repo.name.clone(),
+                    operation: change.op.as_str().to_string(),
+                    forge: change.forge.clone(),
+                    details: change.details.clone(),
                 };
+            }
+
+            let out_path = output.map_or_else(|| SyncPlan::default_path(&ctx.root), PathBuf::from);
+            if let Err(e) = plan.save(&out_path) {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
                 return;
             }
+            yield HyperforgeEvent::PlanSaved {
+                path: out_path.display().to_string(),
+                scopes: plan.scopes.len(),
+                changes: plan.changes.len(),
+            };
+        })))
+    }
+
+    /// Carry out a plan written by `workspace plan`
+    #[plexus_macros::method(
+        description = "Apply a plan written by `workspace plan`: creates and updates go through repo sync, deletes privatize the repo, archives archive it. The diffs are recomputed first and nothing is applied unless they still match the plan exactly.",
+        params(
+            plan_file = "Plan file written by `workspace plan`",
+            skip_auth_check = "Skip pre-flight credential check (optional, default: false)",
+            dry_run = "Record every forge API write the apply would make (method, endpoint, payload summary) instead of sending it (optional, default: false)"
+        )
+    )]
+    pub async fn apply(
+        &self,
+        plan_file: String,
+        skip_auth_check: Option<bool>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let state = self.state.clone();
+        let sync_service = self.state.sync_service.clone();
+        let is_skip_auth = skip_auth_check.unwrap_or(false);
+        let is_dry_run = dry_run.unwrap_or(false);
+
+        output::structured(None, &self.state.config_dir, telemetry::instrument(&self.state.config_dir, "workspace.apply", rate_limit::with_notices(stream! {
+            let _session = SecretSession::begin();
+
+            let plan = match SyncPlan::load(&PathBuf::from(&plan_file)) {
+                Ok(plan) => plan,
+                Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Config, e); return; }
+            };
+            let pairs: Vec<(String, String)> = plan.scopes.iter().map(|s| (s.org.clone(), s.forge.clone())).collect();
 
             yield HyperforgeEvent::Info {
                 message: format!(
-                    "Cloning {} repos (skipping {} already on disk, concurrency: {})...",
-                    to_clone.len(), skipped_count, max_concurrent,
+                    "Checking the plan from {} ({} changes) against {} org/forge pairs...",
+                    plan.created_at.format("%Y-%m-%d %H:%M UTC"),
+                    plan.changes.len(),
+                    pairs.len(),
                 ),
             };
 
-            // Validate forge preference if provided
-            if let Some(ref f) = forge {
-                if HyperforgeConfig::parse_forge(f).is_none() {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Invalid forge: {f}. Must be github, codeberg, or gitlab"),
-                    };
+            if !is_skip_auth {
+                let preflight_errors = run_sync_preflight(&pairs).await;
+                if !preflight_errors.is_empty() {
+                    for event in preflight_errors {
+                        yield event;
+                    }
                     return;
                 }
             }
 
-            // 6. Clone via delegation to repo hub
-            let mut success_count = 0usize;
-            let mut failed_count = 0usize;
-
-            let repo_hub = RepoHub::new(state);
-            let clone_inputs: Vec<_> = to_clone.into_iter()
-                .map(|r| {
-                    let hub = Clone::clone(&repo_hub);
-                    let target = workspace_path.join(&r.name).display().to_string();
-                    (hub, org.clone(), r.name, target, forge.clone())
-                })
-                .collect();
-
-            let clone_results = run_batch(
-                clone_inputs,
-                max_concurrent,
-                |(hub, org, name, target_path, forge_pref): (RepoHub, String, String, String, Option<String>)| async move {
-                    let stream = RepoHub::clone(&hub, org, name.clone(), Some(target_path), forge_pref).await;
-                    tokio::pin!(stream);
-                    let events: Vec<HyperforgeEvent> = stream.collect().await;
-                    let has_error = events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. }));
-                    (name, events, has_error)
-                },
-            ).await;
-
-            for result in clone_results {
-                match result {
-                    Ok((_name, events, has_error)) => {
-                        for event in events {
-                            yield event;
-                        }
-                        if has_error {
-                            failed_count += 1;
-                        } else {
-                            success_count += 1;
-                        }
+            let mut diffs = Vec::new();
+            for result in run_diff_batch(&pairs, &state, &sync_service).await {
+                let entry = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskJoinFailed, error = e);
+                        return;
                     }
+                };
+                match entry.diff_result {
+                    Ok(diff) => diffs.push((entry.org_name, entry.forge_name, diff)),
                     Err(e) => {
-                        failed_count += 1;
-                        yield HyperforgeEvent::Error {
-                            message: format!("  Task error: {e}"),
-                        };
+                        yield HyperforgeEvent::error(HyperforgeError::ForgeApi, format!("{e}; nothing applied"));
+                        return;
                     }
                 }
             }
 
-            // Summary
-            yield HyperforgeEvent::WorkspaceSummary {
-                total_repos: success_count + failed_count + skipped_count,
-                configured_repos: success_count,
-                unconfigured_repos: 0,
-                clean_repos: None,
-                dirty_repos: None,
-                wrong_branch_repos: None,
-                push_success: Some(success_count),
-                push_failed: Some(failed_count),
-                validation_passed: None,
-            };
-        }
-    }
+            let drift = plan.drift(&SyncPlan::new(&diffs));
+            if !drift.is_empty() {
+                for line in &drift {
+                    yield HyperforgeEvent::Info { message: format!("  {line}") };
+                }
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!(
+                        "The plan is stale: {} changes differ from what the config and forges show now. Nothing was applied; run workspace plan again and review it.",
+                        drift.len(),
+                    ),
+                );
+                return;
+            }
+
+            if !is_dry_run {
+                for event in apply_plan_changes(&state, &plan, "").await {
+                    yield event;
+                }
+                return;
+            }
 
+            // Run the same code path with every forge write recorded instead of sent
+            let (events, calls) = simulation::record(apply_plan_changes(&state, &plan, dry_prefix(true))).await;
+            for event in events {
+                yield event;
+            }
+            for (forge, by_method) in simulation::tally(&calls) {
+                for call in calls.iter().filter(|c| c.forge == forge) {
+                    yield HyperforgeEvent::ApiCall {
+                        forge: call.forge.clone(),
+                        method: call.method.clone(),
+                        endpoint: call.endpoint.clone(),
+                        payload: call.payload.clone(),
+                    };
+                }
+                yield HyperforgeEvent::SimulationReport {
+                    calls: by_method.values().sum(),
+                    forge,
+                    by_method,
+                };
+            }
+        })))
+    }
 
-    /// Move repos from one workspace to another
+    /// Set default branch on all repos in a workspace
     #[plexus_macros::method(
-        description = "Move repos from one workspace to another, updating config, git remotes, and LocalForge registry",
+        description = "Set the default branch on all remote forges for every repo in a workspace, and optionally git checkout locally",
         params(
-            path = "Source workspace directory",
-            target_path = "Target workspace directory",
-            target_org = "Target organization name (optional — inferred from target workspace if unambiguous)",
-            repo = "Repo names to move (optional, repeat: --repo a --repo b)",
+            path = "Path to workspace directory",
+            branch = "Branch to set as default",
             include = "Glob patterns — repo must match at least one (optional, repeatable)",
             exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
-            dry_run = "Preview without making changes (optional, default: false)"
+            checkout = "Also run git checkout locally in each repo (optional, default: false)",
+            dry_run = "Preview changes without applying (optional, default: false)"
         )
     )]
-    pub async fn move_repos(
+    pub async fn set_default_branch(
         &self,
         path: String,
-        target_path: String,
-        target_org: Option<String>,
-        repo: Option<Vec<String>>,
+        branch: String,
         include: Option<Vec<String>>,
         exclude: Option<Vec<String>>,
+        checkout: Option<bool>,
         dry_run: Option<bool>,
     ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
-        let state = self.state.clone();
         let is_dry_run = dry_run.unwrap_or(false);
+        let is_checkout = checkout.unwrap_or(false);
+        let state = self.state.clone();
         let filter = RepoFilter::new(include, exclude);
 
-        stream! {
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
             let dry_prefix = dry_prefix(is_dry_run);
-            let source_path = PathBuf::from(&path);
-            let dest_path = PathBuf::from(&target_path);
 
-            // ── Discover both workspaces ──
-            let ctx = match discover_or_bail(&source_path) {
-                Ok(ctx) => ctx,
-                Err(event) => { yield event; return; }
-            };
-            let target_ctx = match discover_or_bail(&dest_path) {
+            let ctx = match discover_or_bail(&workspace_path) {
                 Ok(ctx) => ctx,
                 Err(event) => { yield event; return; }
             };
 
-            // ── Infer target_org if not provided ──
-            let target_org: String = match target_org {
-                Some(org) => org,
-                None => {
-                    match target_ctx.orgs.len() {
-                        0 => {
-                            yield HyperforgeEvent::Error {
-                                message: "Target workspace has no configured orgs, specify --target-org".to_string(),
-                            };
-                            return;
-                        }
-                        1 => {
-                            let org = target_ctx.orgs[0].clone();
-                            yield HyperforgeEvent::Info {
-                                message: format!("{dry_prefix}Inferred target org: {org}"),
-                            };
-                            org
-                        }
-                        _ => {
-                            yield HyperforgeEvent::Error {
-                                message: format!(
-                                    "Target workspace has multiple orgs ({}), specify --target-org",
-                                    target_ctx.orgs.join(", "),
-                                ),
-                            };
-                            return;
-                        }
-                    }
-                }
+            // Filter repos by name glob if provided
+            let repos: Vec<_> = ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)).collect();
+
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{}Setting default branch to '{}' for {} repos...",
+                    dry_prefix, branch, repos.len(),
+                ),
             };
 
-            // ── Build repo list from filter and/or explicit names ──
-            if repo.is_none() && filter.is_empty() {
-                yield HyperforgeEvent::Error {
-                    message: "No repos specified. Use --repo <name> and/or --include <glob>.".to_string(),
-                };
-                return;
-            }
+            let mut success_count = 0usize;
+            let mut error_count = 0usize;
 
-            // Build a lookup of discovered repos by dir_name
-            let mut discovered_map: std::collections::HashMap<String, &crate::commands::workspace::DiscoveredRepo> =
-                std::collections::HashMap::new();
-            for dr in &ctx.repos {
-                discovered_map.insert(dr.dir_name.clone(), dr);
+            // Report repos without org
+            for repo in &repos {
+                if repo.config.is_some() && repo.org().is_none() {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("  {}: no org configured, skipping", repo.dir_name),
+                    );
+                    error_count += 1;
+                }
             }
-            // Also check unconfigured repos (they have no DiscoveredRepo, just paths)
-            let unconfigured_names: HashSet<String> = ctx.unconfigured_repos.iter()
-                .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(std::string::ToString::to_string))
-                .collect();
 
-            // All known source names (configured + unconfigured)
-            let all_source_names: Vec<String> = discovered_map.keys()
-                .chain(unconfigured_names.iter())
-                .cloned()
+            // Build items for delegation to repo hub
+            let repo_hub = RepoHub::new(state);
+            let items: Vec<_> = repos.iter()
+                .filter(|r| r.config.is_some() && r.org().is_some())
+                .map(|r| (**r).clone())
+                .map(|repo| {
+                    let config = repo.config.clone().unwrap();
+                    let org = repo.org().unwrap().to_string();
+                    let repo_name = config.repo_name
+                        .unwrap_or_else(|| repo.dir_name.clone());
+                    let repo_path = repo.path.display().to_string();
+                    (org, repo_name, repo_path)
+                })
+                .map(|(org, repo_name, repo_path)| {
+                    let hub = Clone::clone(&repo_hub);
+                    (hub, org, repo_name, repo_path)
+                })
                 .collect();
 
-            // Collect from filter
-            let mut selected: HashSet<String> = HashSet::new();
-            if !filter.is_empty() {
-                for name in &all_source_names {
-                    if filter.matches(name) {
-                        selected.insert(name.clone());
-                    }
-                }
-                if selected.is_empty() {
-                    yield HyperforgeEvent::Error {
-                        message: "Filter matched no repos in source workspace".to_string(),
-                    };
-                    return;
-                }
-            }
-
-            // Collect from explicit names
-            if let Some(ref names) = repo {
-                for name in names {
-                    selected.insert(name.clone());
+            let results = run_batch(items, Scheduler::global().batch(None), {
+                let branch = branch.clone();
+                move |(hub, org, repo_name, repo_path): (RepoHub, String, String, String)| {
+                    let branch = branch.clone();
+                    let path = if is_checkout { Some(repo_path) } else { None };
+                    async move {
+                        let stream = hub.set_default_branch(org, repo_name.clone(), branch, Some(is_checkout), path).await;
+                        tokio::pin!(stream);
+                        let events: Vec<HyperforgeEvent> = stream.collect().await;
+                        let has_error = events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. }));
+                        (repo_name, events, has_error)
+                    }
                 }
-            }
-
-            // Stable sort for deterministic output
-            let repo: Vec<String> = {
-                let mut v: Vec<String> = selected.into_iter().collect();
-                v.sort();
-                v
-            };
+            }).await;
 
-            // Build target lookup for collision detection
-            let target_repo_names: HashSet<String> = target_ctx.repos.iter()
-                .map(|r| r.dir_name.clone())
-                .chain(
-                    target_ctx.unconfigured_repos.iter()
-                        .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(std::string::ToString::to_string))
-                )
-                .collect();
+            for result in results {
+                let (_repo_name, events, has_error) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskJoinFailed, error = e);
+                        error_count += 1;
+                        continue;
+                    }
+                };
 
-            // Validate all requested repos exist in source and don't collide in target
-            for name in &repo {
-                if !discovered_map.contains_key(name.as_str()) && !unconfigured_names.contains(name.as_str()) {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Repo '{}' not found in source workspace {}", name, source_path.display()),
-                    };
-                    return;
-                }
-                // Check target doesn't already have it (discovered repos OR directory on disk)
-                if target_repo_names.contains(name.as_str()) {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Target workspace already contains repo '{name}'"),
-                    };
-                    return;
+                for event in events {
+                    yield event;
                 }
-                let target_repo_path = dest_path.join(name);
-                if target_repo_path.exists() {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Target already contains '{}': {}", name, target_repo_path.display()),
-                    };
-                    return;
+
+                if has_error {
+                    error_count += 1;
+                } else {
+                    success_count += 1;
                 }
             }
 
-            let filter_info = if filter.is_empty() {
-                String::new()
-            } else {
-                " (filtered)".to_string()
-            };
             yield HyperforgeEvent::Info {
                 message: format!(
-                    "{}Moving {} repo(s) from {} to {} (target org: {}){}\n{}",
-                    dry_prefix, repo.len(), source_path.display(), dest_path.display(), target_org,
-                    filter_info,
-                    repo.iter().map(|n| format!("  - {n}")).collect::<Vec<_>>().join("\n"),
+                    "{dry_prefix}Set default branch complete: {success_count} succeeded, {error_count} failed",
                 ),
             };
+        })
+    }
 
-            let mut moved = 0usize;
-            let mut failed = 0usize;
+    /// Check remote default branch settings
+    #[plexus_macros::method(
+        description = "Verify all workspace repos have the expected default branch set on remote forges. Queries each forge API directly.",
+        params(
+            path = "Path to workspace directory",
+            branch = "Expected default branch (optional, default: main)",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)"
+        )
+    )]
+    pub async fn check_default_branch(
+        &self,
+        path: String,
+        branch: Option<String>,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let filter = RepoFilter::new(include, exclude);
+            let workspace_path = PathBuf::from(&path);
+            let expected = branch.unwrap_or_else(|| "main".to_string());
 
-            for name in &repo {
-                let repo_path = source_path.join(name);
-                let target_repo_path = dest_path.join(name);
-                let discovered = discovered_map.get(name.as_str()).copied();
-                let source_org = discovered.and_then(|d| d.org().map(std::string::ToString::to_string));
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
 
-                yield HyperforgeEvent::Info {
-                    message: format!("{dry_prefix}── {name} ──"),
-                };
+            let repos: Vec<_> = ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)).collect();
 
-                // ── Step 1+2: Materialize config + remotes ──
-                // Build a RepoRecord for the target org, then materialize
-                let mut record = if let Some(ref src_org) = source_org {
-                    let source_forge = state.get_local_forge(src_org).await;
-                    source_forge.get_record(name).unwrap_or_else(|_| {
-                        crate::types::RepoRecord::from_repo(
-                            &crate::types::Repo::new(name.clone(), crate::types::Forge::GitHub),
-                        )
-                    })
-                } else {
-                    crate::types::RepoRecord::from_repo(
-                        &crate::types::Repo::new(name.clone(), crate::types::Forge::GitHub),
-                    )
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "Checking remote default branch (expected: '{}') for {} repos across {} forges...",
+                    expected, repos.len(), ctx.forges.len(),
+                ),
+            };
+
+            // Build work items: (dir_name, repo_name, forge_name, org, expected, host)
+            let mut items: Vec<(String, String, String, String, String, Option<String>)> = Vec::new();
+            for repo in &repos {
+                let config = match &repo.config {
+                    Some(c) => c,
+                    None => continue,
+                };
+                let org = match repo.org() {
+                    Some(o) => o.to_string(),
+                    None => continue,
                 };
+                let repo_name = config.repo_name.clone()
+                    .unwrap_or_else(|| repo.dir_name.clone());
 
-                // Absorb per-repo config if available
-                if let Some(dr) = discovered {
-                    if let Some(ref config) = dr.config {
-                        record.merge_from_config(config);
-                    }
+                for forge_name in repo.forges() {
+                    items.push((
+                        repo.dir_name.clone(),
+                        repo_name.clone(),
+                        forge_name.to_string(),
+                        org.clone(),
+                        expected.clone(),
+                        forge_host(&config_dir, &org, forge_name, config.forge_host(forge_name)),
+                    ));
                 }
-                record.local_path = Some(repo_path.clone());
+            }
 
-                let materialize_opts = crate::commands::materialize::MaterializeOpts {
-                    config: true,
-                    remotes: true,
-                    hooks: false,
-                    ssh_wrapper: false,
-                    dry_run: is_dry_run,
-                    auto_commit: true,
+            // Query each forge API in parallel
+            let results = run_batch(items, Scheduler::global().batch(None), |(dir_name, repo_name, forge_name, org, expected, host)| async move {
+                let ot = None; // owner type not needed for get_repo
+                let adapter = match make_adapter(&forge_name, &org, ot, host.as_deref()) {
+                    Ok(a) => a,
+                    Err(e) => return (dir_name, forge_name, None::<String>, expected, Some(e)),
                 };
 
-                match crate::commands::materialize::materialize(&target_org, &record, &repo_path, materialize_opts) {
-                    Ok(report) => {
-                        let action = if report.config_written { "updated config" } else { "config unchanged" };
-                        yield HyperforgeEvent::RepoMove {
-                            repo_name: name.clone(),
-                            step: "config".to_string(),
-                            success: true,
-                            message: format!("{dry_prefix}{action}"),
-                        };
-                        for remote in &report.remotes_updated {
-                            yield HyperforgeEvent::RepoMove {
-                                repo_name: name.clone(),
-                                step: "remotes".to_string(),
-                                success: true,
-                                message: format!("{dry_prefix}updated remote: {remote}"),
-                            };
-                        }
-                        for remote in &report.remotes_added {
-                            yield HyperforgeEvent::RepoMove {
-                                repo_name: name.clone(),
-                                step: "remotes".to_string(),
-                                success: true,
-                                message: format!("{dry_prefix}added remote: {remote}"),
-                            };
-                        }
-                    }
+                match adapter.get_repo(&org, &repo_name).await {
+                    Ok(repo) => (dir_name, forge_name, repo.default_branch, expected, None),
+                    Err(e) => (dir_name, forge_name, None, expected, Some(e.to_string())),
+                }
+            }).await;
+
+            let mut ok_count = 0usize;
+            let mut mismatch_count = 0usize;
+            let mut error_count = 0usize;
+
+            for result in results {
+                let (dir_name, forge_name, remote_branch, expected, error) = match result {
+                    Ok(v) => v,
                     Err(e) => {
-                        yield HyperforgeEvent::RepoMove {
-                            repo_name: name.clone(),
-                            step: "config".to_string(),
-                            success: false,
-                            message: format!("Materialize failed: {e}"),
-                        };
-                        failed += 1;
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        error_count += 1;
                         continue;
                     }
+                };
+
+                if let Some(err) = error {
+                    error_count += 1;
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::ForgeApi,
+                        format!("  {dir_name} ({forge_name}): query failed: {err}"),
+                    );
+                    continue;
                 }
 
-                // ── Step 3: Update LocalForge registry ──
-                if let Some(ref src_org) = source_org {
-                    let source_forge = state.get_local_forge(src_org).await;
-                    let target_forge = state.get_local_forge(&target_org).await;
+                match remote_branch {
+                    Some(ref b) if b == &expected => {
+                        ok_count += 1;
+                    }
+                    Some(ref b) => {
+                        mismatch_count += 1;
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Policy,
+                            format!("  {dir_name} ({forge_name}): default is '{b}', expected '{expected}'"),
+                        );
+                    }
+                    None => {
+                        // Forge didn't report default_branch — assume ok
+                        ok_count += 1;
+                    }
+                }
+            }
 
-                    // Copy record to target, then remove from source
-                    let record_opt = source_forge.all_records().ok().and_then(|records| {
-                        records.into_iter().find(|r| r.name == *name)
-                    });
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "Default branch check: {ok_count} ok, {mismatch_count} mismatched, {error_count} errors"
+                ),
+            };
+        })
+    }
 
-                    if let Some(record) = record_opt {
-                        if is_dry_run {
-                            yield HyperforgeEvent::RepoMove {
-                                repo_name: name.clone(),
-                                step: "registry".to_string(),
-                                success: true,
-                                message: format!("{dry_prefix}would move {src_org} → {target_org} in LocalForge"),
-                            };
-                        } else if let Err(e) = target_forge.upsert_record(record) {
-                            yield HyperforgeEvent::RepoMove {
-                                repo_name: name.clone(),
-                                step: "registry".to_string(),
-                                success: false,
-                                message: format!("Failed to add to target LocalForge: {e}"),
-                            };
-                        } else if let Err(e) = source_forge.remove_repo(name) {
-                            yield HyperforgeEvent::RepoMove {
-                                repo_name: name.clone(),
-                                step: "registry".to_string(),
-                                success: false,
-                                message: format!("Added to target but failed to remove from source LocalForge: {e}"),
-                            };
-                        } else {
-                            yield HyperforgeEvent::RepoMove {
-                                repo_name: name.clone(),
-                                step: "registry".to_string(),
-                                success: true,
-                                message: format!("{dry_prefix}moved {src_org} → {target_org} in LocalForge"),
-                            };
-                        }
+    /// Verify workspace sync state
+    #[plexus_macros::method(
+        description = "Verify workspace configuration including orgs, SSH keys, and auth tokens. Use --path to discover from disk, or --org for registry access.",
+        params(
+            path = "Path to workspace directory (discovers orgs from disk)",
+            org = "Organization to verify (optional, verifies all if not specified)"
+        )
+    )]
+    pub async fn verify(
+        &self,
+        path: Option<String>,
+        org: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let state = self.state.clone();
+        let config_dir = self.state.config_dir.clone();
+
+        output::structured(None, &self.state.config_dir, telemetry::instrument(&self.state.config_dir, "workspace.verify", rate_limit::with_notices(stream! {
+            let _session = SecretSession::begin();
+
+            yield HyperforgeEvent::Info {
+                message: "Starting workspace verification...".to_string(),
+            };
+
+            // Determine orgs to check
+            let orgs_to_check: Vec<String> = if let Some(ref workspace_path) = path {
+                let workspace_path = PathBuf::from(workspace_path);
+                let ctx = match discover_or_bail(&workspace_path) {
+                    Ok(ctx) => ctx,
+                    Err(event) => { yield event; return; }
+                };
+
+                // If org specified, filter; otherwise use all discovered orgs
+                if let Some(ref org_name) = org {
+                    if ctx.orgs.contains(org_name) {
+                        vec![org_name.clone()]
                     } else {
-                        yield HyperforgeEvent::RepoMove {
-                            repo_name: name.clone(),
-                            step: "registry".to_string(),
-                            success: true,
-                            message: format!("{dry_prefix}not in source LocalForge, skipping registry"),
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("Org '{org_name}' not found in workspace"),
+                        );
+                        return;
+                    }
+                } else {
+                    ctx.orgs.clone()
+                }
+            } else {
+                match legacy::gate("workspace verify") {
+                    Ok(notice) => yield notice,
+                    Err(event) => { yield event; return; }
+                }
+                if let Some(org_name) = org {
+                    vec![org_name]
+                } else {
+                    let orgs = legacy::legacy_orgs(&config_dir);
+                    if orgs.is_empty() {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            "No organizations configured. Provide --path or --org.".to_string(),
+                        );
+                        return;
+                    }
+                    orgs
+                }
+            };
+
+            let mut total_repos = 0;
+            let mut total_issues = 0;
+
+            // Verify each org
+            for org_name in orgs_to_check {
+                yield HyperforgeEvent::Info {
+                    message: format!("Verifying org: {org_name}"),
+                };
+
+                // Check org repos.yaml exists
+                let repos_yaml = config_dir.join("orgs").join(&org_name).join("repos.yaml");
+                if !repos_yaml.exists() {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("  ✗ Missing repos.yaml for org: {org_name}"),
+                    );
+                    total_issues += 1;
+                    continue;
+                }
+
+                // Load and count repos
+                let local_forge = state.get_local_forge(&org_name).await;
+                match local_forge.all_repos() {
+                    Ok(repos) => {
+                        let repo_count = repos.len();
+                        total_repos += repo_count;
+
+                        yield HyperforgeEvent::Info {
+                            message: format!("  ✓ Found {repo_count} repos in {org_name}"),
                         };
                     }
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("  ✗ Failed to load repos: {e}"),
+                        );
+                        total_issues += 1;
+                    }
+                }
+
+                // Check auth tokens for common forges
+                for forge in &["github", "codeberg", "gitlab"] {
+                    let _token_key = format!("{forge}/{org_name}/token");
+                    yield HyperforgeEvent::Info {
+                        message: format!("  ℹ Auth check for {forge}/{org_name} (use auth hub to verify)"),
+                    };
+                }
+            }
+
+            // Check SSH keys
+            let ssh_dir = dirs::home_dir()
+                .unwrap_or_else(|| std::path::PathBuf::from("."))
+                .join(".ssh");
+
+            if ssh_dir.exists() {
+                let ssh_keys = vec!["hyperforge_ed25519", "id_ed25519", "id_rsa"];
+                let mut found_keys = Vec::new();
+
+                for key_name in ssh_keys {
+                    let key_path = ssh_dir.join(key_name);
+                    if key_path.exists() {
+                        found_keys.push(key_name);
+                    }
+                }
+
+                if found_keys.is_empty() {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Auth,
+                        "✗ No SSH keys found in ~/.ssh/".to_string(),
+                    );
+                    total_issues += 1;
                 } else {
-                    yield HyperforgeEvent::RepoMove {
-                        repo_name: name.clone(),
-                        step: "registry".to_string(),
-                        success: true,
-                        message: format!("{dry_prefix}no source org, skipping registry"),
+                    yield HyperforgeEvent::Info {
+                        message: format!("✓ Found SSH keys: {}", found_keys.join(", ")),
                     };
                 }
+            } else {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Auth,
+                    "✗ ~/.ssh/ directory not found".to_string(),
+                );
+                total_issues += 1;
+            }
+
+            // Summary
+            yield HyperforgeEvent::Info {
+                message: "=== Verification Summary ===".to_string(),
+            };
+            yield HyperforgeEvent::Info {
+                message: format!("Total repositories: {total_repos}"),
+            };
+            yield HyperforgeEvent::Info {
+                message: format!("Issues found: {total_issues}"),
+            };
+
+            if total_issues == 0 {
+                yield HyperforgeEvent::Info {
+                    message: "✓ Workspace configuration verified successfully!".to_string(),
+                };
+            } else {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    format!("✗ Found {total_issues} issues that need attention"),
+                );
+            }
+        })))
+    }
+
+
+    /// Clone all repos for an org from `LocalForge` into a workspace directory
+    #[plexus_macros::method(
+        description = "Clone all repos for an org from LocalForge into a workspace directory. Skips repos already on disk.",
+        params(
+            org = "Organization name (must have repos in LocalForge)",
+            path = "Target workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            forge = "Preferred forge to clone from (optional, defaults to first in present_on)",
+            concurrency = "Max parallel clones (optional, default: 4)",
+            profile = "Only clone the repos of this [profiles] entry in <path>/.hyperforge/workspace.toml and make it the active profile (optional)",
+            depth = "Fetch only this many commits of history per repo (optional, default: full history; see unshallow)",
+            filter = "Partial clone: blobless or treeless, fetching contents on demand (optional)",
+            single_branch = "Fetch only each repo's default branch (optional, default: false)"
+        )
+    )]
+    pub async fn clone(
+        &self,
+        org: String,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        forge: Option<String>,
+        concurrency: Option<u32>,
+        profile: Option<String>,
+        depth: Option<u32>,
+        filter: Option<String>,
+        single_branch: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let state = self.state.clone();
+        let max_concurrent = concurrency.unwrap_or(4) as usize;
+        let clone_filter = filter;
+        let filter = RepoFilter::new(include, exclude);
+
+        output::structured(None, &self.state.config_dir, telemetry::instrument(&self.state.config_dir, "workspace.clone", rate_limit::with_notices(stream! {
+            let workspace_path = PathBuf::from(&path);
+            let options = match clone_options(depth, clone_filter.as_deref(), single_branch) {
+                Ok(options) => options,
+                Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Validation, e); return; }
+            };
+            let filter = match profile_filter(&workspace_path, filter, profile.as_deref(), true) {
+                Ok(f) => f,
+                Err(e) => { yield HyperforgeEvent::error(HyperforgeError::Config, e); return; }
+            };
+
+            // 1. Load LocalForge
+            let local = state.get_local_forge(&org).await;
+
+            // 2. Get all records
+            let records = match local.records() {
+                Ok(r) => r,
+                Err(e) => {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to load repos: {e}"),
+                    );
+                    return;
+                }
+            };
+
+            if records.is_empty() {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    format!("No repos found in LocalForge for org '{org}'"),
+                );
+                return;
+            }
+
+            // 3. Filter by glob
+            let filtered: Vec<_> = records.into_iter().filter(|r| filter.matches(&r.name)).collect();
+
+            // 4. Create workspace dir if needed
+            if let Err(e) = std::fs::create_dir_all(&workspace_path) {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    format!("Failed to create workspace directory: {e}"),
+                );
+                return;
+            }
+
+            // 5. Skip repos already on disk
+            let total_filtered = filtered.len();
+            let to_clone: Vec<_> = filtered.into_iter().filter(|r| {
+                !workspace_path.join(&r.name).exists()
+            }).collect();
+
+            let skipped_count = total_filtered - to_clone.len();
+
+            if to_clone.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: format!(
+                        "All {total_filtered} repos already exist on disk. Nothing to clone.",
+                    ),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "Cloning {} repos (skipping {} already on disk, concurrency: {}{})...",
+                    to_clone.len(), skipped_count, max_concurrent,
+                    if options.is_full() { String::new() } else { format!(", {}", options.args().join(" ")) },
+                ),
+            };
+
+            // Validate forge preference if provided
+            if let Some(ref f) = forge {
+                if HyperforgeConfig::parse_forge(f).is_none() {
+                    yield msg_error!(Msg::InvalidForge, forge = f);
+                    return;
+                }
+            }
+
+            // 6. Clone via delegation to repo hub
+            let mut success_count = 0usize;
+            let mut failed_count = 0usize;
+
+            let repo_hub = RepoHub::new(state);
+            let clone_inputs: Vec<_> = to_clone.into_iter()
+                .map(|r| {
+                    let hub = Clone::clone(&repo_hub);
+                    let target = workspace_path.join(&r.name).display().to_string();
+                    (hub, org.clone(), r.name.clone(), target, forge.clone(), clone_filter.clone())
+                })
+                .collect();
+
+            let clone_results = run_batch(
+                clone_inputs,
+                max_concurrent,
+                move |(hub, org, name, target_path, forge_pref, clone_filter): (RepoHub, String, String, String, Option<String>, Option<String>)| async move {
+                    let stream = RepoHub::clone(&hub, org, name.clone(), Some(target_path), forge_pref, depth, clone_filter, single_branch).await;
+                    tokio::pin!(stream);
+                    let events: Vec<HyperforgeEvent> = stream.collect().await;
+                    let has_error = events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. }));
+                    (name, events, has_error)
+                },
+            ).await;
+
+            for result in clone_results {
+                match result {
+                    Ok((_name, events, has_error)) => {
+                        for event in events {
+                            yield event;
+                        }
+                        if has_error {
+                            failed_count += 1;
+                        } else {
+                            success_count += 1;
+                        }
+                    }
+                    Err(e) => {
+                        failed_count += 1;
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Internal,
+                            format!("  Task error: {e}"),
+                        );
+                    }
+                }
+            }
+
+            // Summary
+            yield HyperforgeEvent::WorkspaceSummary {
+                total_repos: success_count + failed_count + skipped_count,
+                configured_repos: success_count,
+                unconfigured_repos: 0,
+                clean_repos: None,
+                dirty_repos: None,
+                wrong_branch_repos: None,
+                push_success: Some(success_count),
+                push_failed: Some(failed_count),
+                validation_passed: None,
+            };
+        })))
+    }
+
+
+    /// Fetch the full history of shallow, partial and single-branch clones
+    #[plexus_macros::method(
+        description = "Turn shallow, partial (blobless/treeless) and single-branch clones from `workspace clone` into full clones: track every branch of origin, drop the partial clone filter and fetch the whole history. Full clones are left alone.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            dry_run = "Only report which clones are incomplete (optional, default: false)"
+        )
+    )]
+    pub async fn unshallow(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+        let is_dry_run = dry_run.unwrap_or(false);
+
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let inputs: Vec<_> = ctx.repos.iter()
+                .filter(|r| filter.matches(&r.dir_name) && r.is_git_repo)
+                .map(|r| (r.dir_name.clone(), r.path.clone()))
+                .collect();
+
+            yield HyperforgeEvent::Info {
+                message: format!("{}Checking {} clones for missing history...", dry_prefix(is_dry_run), inputs.len()),
+            };
+
+            let results = run_batch_blocking(inputs, Scheduler::global().batch(None), move |(dir_name, path)| {
+                let result = if is_dry_run {
+                    Git::clone_completeness(&path, "origin")
+                } else {
+                    Git::unshallow(&path, "origin")
+                };
+                (dir_name, result)
+            }).await;
+
+            let (mut completed, mut full, mut failed) = (0usize, 0usize, 0usize);
+            for result in results {
+                let (dir_name, before) = match result {
+                    Ok((dir_name, Ok(before))) => (dir_name, before),
+                    Ok((dir_name, Err(e))) => {
+                        failed += 1;
+                        yield HyperforgeEvent::error(HyperforgeError::Git, format!("  {dir_name}: {e}"));
+                        continue;
+                    }
+                    Err(e) => {
+                        failed += 1;
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                if before.is_full() {
+                    full += 1;
+                    continue;
+                }
+                completed += 1;
+                let mut missing = Vec::new();
+                if before.shallow { missing.push("shallow".to_string()); }
+                if let Some(spec) = &before.filter { missing.push(format!("filter {spec}")); }
+                if before.single_branch { missing.push("single-branch".to_string()); }
+                yield HyperforgeEvent::Info {
+                    message: format!(
+                        "  {}{dir_name}: {} (was {})",
+                        dry_prefix(is_dry_run),
+                        if is_dry_run { "would fetch full history" } else { "fetched full history" },
+                        missing.join(", "),
+                    ),
+                };
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{}Unshallow: {completed} {}, {full} already full, {failed} failures",
+                    dry_prefix(is_dry_run),
+                    if is_dry_run { "incomplete" } else { "completed" },
+                ),
+            };
+        })
+    }
+
+    /// Convert legacy org-only registries into path-based workspaces
+    #[plexus_macros::method(
+        description = "Convert legacy org-based registries (~/.config/hyperforge/orgs/<org>/repos.yaml) into path-based workspaces: write .hyperforge/config.toml into existing checkouts and clone the rest",
+        params(
+            path = "Workspace directory to create (with several orgs, each gets <path>/<org>)",
+            org = "Migrate only this org (optional, default: every org with a legacy registry)",
+            clone = "Clone repos that aren't on disk yet (optional, default: true)",
+            dry_run = "Show what would happen without writing or cloning (optional, default: false)"
+        )
+    )]
+    pub async fn migrate_legacy(
+        &self,
+        path: String,
+        org: Option<String>,
+        clone: Option<bool>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let state = self.state.clone();
+        let do_clone = clone.unwrap_or(true);
+        let is_dry_run = dry_run.unwrap_or(false);
+
+        output::structured(None, &self.state.config_dir, stream! {
+            let root = PathBuf::from(&path);
+            let orgs = match org {
+                Some(org) => vec![org],
+                None => legacy::legacy_orgs(&state.config_dir),
+            };
+            if orgs.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No legacy org registries found. Nothing to migrate.".to_string(),
+                };
+                return;
+            }
+
+            let dry = dry_prefix(is_dry_run);
+            let repo_hub = RepoHub::new(state.clone());
+            for org in &orgs {
+                let workspace_dir = legacy::workspace_dir(&root, org, orgs.len());
+                let local = state.get_local_forge(org).await;
+                let records = match local.records() {
+                    Ok(records) => records,
+                    Err(e) => {
+                        yield HyperforgeEvent::error(HyperforgeError::Config, format!("Failed to load repos for {org}: {e}"));
+                        continue;
+                    }
+                };
+                yield HyperforgeEvent::Info {
+                    message: format!("{dry}Migrating {org} ({} repos) into {}", records.len(), workspace_dir.display()),
+                };
+                if !is_dry_run {
+                    if let Err(e) = std::fs::create_dir_all(&workspace_dir) {
+                        yield HyperforgeEvent::error(HyperforgeError::Config, format!("Failed to create workspace directory: {e}"));
+                        return;
+                    }
+                }
+
+                let (mut cloned, mut configured, mut unchanged, mut failed) = (0usize, 0usize, 0usize, 0usize);
+                for record in records {
+                    let repo_dir = workspace_dir.join(&record.name);
+                    match MigrationStep::for_dir(&repo_dir) {
+                        MigrationStep::Configured => unchanged += 1,
+                        MigrationStep::Clone if !do_clone => {
+                            yield HyperforgeEvent::Info { message: format!("  {}: not on disk, skipped (--clone false)", record.name) };
+                            unchanged += 1;
+                        }
+                        MigrationStep::Clone if is_dry_run => {
+                            yield HyperforgeEvent::Info { message: format!("  {dry}{}: would clone", record.name) };
+                            cloned += 1;
+                        }
+                        MigrationStep::Clone => {
+                            // RepoHub::clone writes the config and records the checkout
+                            let events: Vec<HyperforgeEvent> = RepoHub::clone(
+                                &repo_hub, org.clone(), record.name.clone(), Some(repo_dir.display().to_string()), None, None, None, None,
+                            ).await.collect().await;
+                            let has_error = events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. }));
+                            for event in events {
+                                yield event;
+                            }
+                            if has_error { failed += 1; } else { cloned += 1; }
+                        }
+                        MigrationStep::Materialize => {
+                            let mut updated = (*record).clone();
+                            updated.local_path = Some(repo_dir.clone());
+                            if updated.forges.is_empty() {
+                                updated.forges = updated.present_on.iter().map(ToString::to_string).collect();
+                            }
+                            let opts = crate::commands::materialize::MaterializeOpts {
+                                dry_run: is_dry_run,
+                                ..Default::default()
+                            };
+                            match crate::commands::materialize::materialize(org, &updated, &repo_dir, opts) {
+                                Ok(_) => {
+                                    yield HyperforgeEvent::Info {
+                                        message: format!("  {dry}{}: generated .hyperforge/config.toml", record.name),
+                                    };
+                                    configured += 1;
+                                    if !is_dry_run {
+                                        if let Err(e) = local.update_record(&updated) {
+                                            yield HyperforgeEvent::error(HyperforgeError::Config, format!("Failed to update LocalForge record: {e}"));
+                                        }
+                                    }
+                                }
+                                Err(e) => {
+                                    yield msg_error!(Msg::MaterializeFailed, error = e).with_context(record.name.clone());
+                                    failed += 1;
+                                }
+                            }
+                        }
+                    }
+                }
+                if !is_dry_run && configured > 0 {
+                    if let Err(e) = local.save_to_yaml().await {
+                        yield msg_error!(Msg::ReposYamlSaveFailed, error = e);
+                    }
+                }
+
+                yield HyperforgeEvent::Info {
+                    message: format!(
+                        "{dry}{org}: {cloned} cloned, {configured} configured, {unchanged} unchanged, {failed} failed"
+                    ),
+                };
+            }
+
+            if !is_dry_run {
+                yield HyperforgeEvent::Info {
+                    message: format!("Done. Use --path {} with workspace methods from now on.", root.display()),
+                };
+            }
+        })
+    }
+
+    /// Move repos from one workspace to another
+    #[plexus_macros::method(
+        description = "Move repos from one workspace to another, updating config, git remotes, and LocalForge registry",
+        params(
+            path = "Source workspace directory",
+            target_path = "Target workspace directory",
+            target_org = "Target organization name (optional — inferred from target workspace if unambiguous)",
+            repo = "Repo names to move (optional, repeat: --repo a --repo b)",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            dry_run = "Preview without making changes (optional, default: false)"
+        )
+    )]
+    pub async fn move_repos(
+        &self,
+        path: String,
+        target_path: String,
+        target_org: Option<String>,
+        repo: Option<Vec<String>>,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let state = self.state.clone();
+        let is_dry_run = dry_run.unwrap_or(false);
+        let filter = RepoFilter::new(include, exclude);
+
+        output::structured(None, &self.state.config_dir, stream! {
+            let dry_prefix = dry_prefix(is_dry_run);
+            let source_path = PathBuf::from(&path);
+            let dest_path = PathBuf::from(&target_path);
+
+            // ── Discover both workspaces ──
+            let ctx = match discover_or_bail(&source_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+            let target_ctx = match discover_or_bail(&dest_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            // ── Infer target_org if not provided ──
+            let target_org: String = match target_org {
+                Some(org) => org,
+                None => {
+                    match target_ctx.orgs.len() {
+                        0 => {
+                            yield HyperforgeEvent::error(
+                                HyperforgeError::Config,
+                                "Target workspace has no configured orgs, specify --target-org".to_string(),
+                            );
+                            return;
+                        }
+                        1 => {
+                            let org = target_ctx.orgs[0].clone();
+                            yield HyperforgeEvent::Info {
+                                message: format!("{dry_prefix}Inferred target org: {org}"),
+                            };
+                            org
+                        }
+                        _ => {
+                            yield HyperforgeEvent::error(
+                                HyperforgeError::Config,
+                                format!(
+                                    "Target workspace has multiple orgs ({}), specify --target-org",
+                                    target_ctx.orgs.join(", "),
+                                ),
+                            );
+                            return;
+                        }
+                    }
+                }
+            };
+
+            // ── Build repo list from filter and/or explicit names ──
+            if repo.is_none() && filter.is_empty() {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    "No repos specified. Use --repo <name> and/or --include <glob>.".to_string(),
+                );
+                return;
+            }
+
+            // Build a lookup of discovered repos by dir_name
+            let mut discovered_map: std::collections::HashMap<String, &crate::commands::workspace::DiscoveredRepo> =
+                std::collections::HashMap::new();
+            for dr in &ctx.repos {
+                discovered_map.insert(dr.dir_name.clone(), dr);
+            }
+            // Also check unconfigured repos (they have no DiscoveredRepo, just paths)
+            let unconfigured_names: HashSet<String> = ctx.unconfigured_repos.iter()
+                .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(std::string::ToString::to_string))
+                .collect();
+
+            // All known source names (configured + unconfigured)
+            let all_source_names: Vec<String> = discovered_map.keys()
+                .chain(unconfigured_names.iter())
+                .cloned()
+                .collect();
+
+            // Collect from filter
+            let mut selected: HashSet<String> = HashSet::new();
+            if !filter.is_empty() {
+                for name in &all_source_names {
+                    if filter.matches(name) {
+                        selected.insert(name.clone());
+                    }
+                }
+                if selected.is_empty() {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Validation,
+                        "Filter matched no repos in source workspace".to_string(),
+                    );
+                    return;
+                }
+            }
+
+            // Collect from explicit names
+            if let Some(ref names) = repo {
+                for name in names {
+                    selected.insert(name.clone());
+                }
+            }
+
+            // Stable sort for deterministic output
+            let repo: Vec<String> = {
+                let mut v: Vec<String> = selected.into_iter().collect();
+                v.sort();
+                v
+            };
+
+            // Build target lookup for collision detection
+            let target_repo_names: HashSet<String> = target_ctx.repos.iter()
+                .map(|r| r.dir_name.clone())
+                .chain(
+                    target_ctx.unconfigured_repos.iter()
+                        .filter_map(|p| p.file_name().and_then(|n| n.to_str()).map(std::string::ToString::to_string))
+                )
+                .collect();
+
+            // Validate all requested repos exist in source and don't collide in target
+            for name in &repo {
+                if !discovered_map.contains_key(name.as_str()) && !unconfigured_names.contains(name.as_str()) {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Validation,
+                        format!("Repo '{}' not found in source workspace {}", name, source_path.display()),
+                    );
+                    return;
+                }
+                // Check target doesn't already have it (discovered repos OR directory on disk)
+                if target_repo_names.contains(name.as_str()) {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Policy,
+                        format!("Target workspace already contains repo '{name}'"),
+                    );
+                    return;
+                }
+                let target_repo_path = dest_path.join(name);
+                if target_repo_path.exists() {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Policy,
+                        format!("Target already contains '{}': {}", name, target_repo_path.display()),
+                    );
+                    return;
+                }
+            }
+
+            let filter_info = if filter.is_empty() {
+                String::new()
+            } else {
+                " (filtered)".to_string()
+            };
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{}Moving {} repo(s) from {} to {} (target org: {}){}\n{}",
+                    dry_prefix, repo.len(), source_path.display(), dest_path.display(), target_org,
+                    filter_info,
+                    repo.iter().map(|n| format!("  - {n}")).collect::<Vec<_>>().join("\n"),
+                ),
+            };
+
+            let mut moved = 0usize;
+            let mut failed = 0usize;
+
+            for name in &repo {
+                let repo_path = source_path.join(name);
+                let target_repo_path = dest_path.join(name);
+                let discovered = discovered_map.get(name.as_str()).copied();
+                let source_org = discovered.and_then(|d| d.org().map(std::string::ToString::to_string));
+
+                yield HyperforgeEvent::Info {
+                    message: format!("{dry_prefix}── {name} ──"),
+                };
+
+                // ── Step 1+2: Materialize config + remotes ──
+                // Build a RepoRecord for the target org, then materialize
+                let mut record = if let Some(ref src_org) = source_org {
+                    let source_forge = state.get_local_forge(src_org).await;
+                    source_forge.get_record(name).unwrap_or_else(|_| {
+                        crate::types::RepoRecord::from_repo(
+                            &crate::types::Repo::new(name.clone(), crate::types::Forge::GitHub),
+                        )
+                    })
+                } else {
+                    crate::types::RepoRecord::from_repo(
+                        &crate::types::Repo::new(name.clone(), crate::types::Forge::GitHub),
+                    )
+                };
+
+                // Absorb per-repo config if available
+                if let Some(dr) = discovered {
+                    if let Some(ref config) = dr.config {
+                        record.merge_from_config(config);
+                    }
+                }
+                record.local_path = Some(repo_path.clone());
+
+                let materialize_opts = crate::commands::materialize::MaterializeOpts {
+                    config: true,
+                    remotes: true,
+                    hooks: false,
+                    ssh_wrapper: false,
+                    dry_run: is_dry_run,
+                    auto_commit: true,
+                    identity: true,
+                };
+
+                match crate::commands::materialize::materialize(&target_org, &record, &repo_path, materialize_opts) {
+                    Ok(report) => {
+                        let action = if report.config_written { "updated config" } else { "config unchanged" };
+                        yield HyperforgeEvent::RepoMove {
+                            repo_name: name.clone(),
+                            step: "config".to_string(),
+                            success: true,
+                            message: format!("{dry_prefix}{action}"),
+                        };
+                        for remote in &report.remotes_updated {
+                            yield HyperforgeEvent::RepoMove {
+                                repo_name: name.clone(),
+                                step: "remotes".to_string(),
+                                success: true,
+                                message: format!("{dry_prefix}updated remote: {remote}"),
+                            };
+                        }
+                        for remote in &report.remotes_added {
+                            yield HyperforgeEvent::RepoMove {
+                                repo_name: name.clone(),
+                                step: "remotes".to_string(),
+                                success: true,
+                                message: format!("{dry_prefix}added remote: {remote}"),
+                            };
+                        }
+                    }
+                    Err(e) => {
+                        yield HyperforgeEvent::RepoMove {
+                            repo_name: name.clone(),
+                            step: "config".to_string(),
+                            success: false,
+                            message: format!("Materialize failed: {e}"),
+                        };
+                        failed += 1;
+                        continue;
+                    }
+                }
+
+                // ── Step 3: Update LocalForge registry ──
+                if let Some(ref src_org) = source_org {
+                    let source_forge = state.get_local_forge(src_org).await;
+                    let target_forge = state.get_local_forge(&target_org).await;
+
+                    // Copy record to target, then remove from source
+                    let record_opt = source_forge.get_record(name).ok();
+
+                    if let Some(record) = record_opt {
+                        if is_dry_run {
+                            yield HyperforgeEvent::RepoMove {
+                                repo_name: name.clone(),
+                                step: "registry".to_string(),
+                                success: true,
+                                message: format!("{dry_prefix}would move {src_org} → {target_org} in LocalForge"),
+                            };
+                        } else if let Err(e) = target_forge.upsert_record(record) {
+                            yield HyperforgeEvent::RepoMove {
+                                repo_name: name.clone(),
+                                step: "registry".to_string(),
+                                success: false,
+                                message: format!("Failed to add to target LocalForge: {e}"),
+                            };
+                        } else if let Err(e) = source_forge.remove_repo(name) {
+                            yield HyperforgeEvent::RepoMove {
+                                repo_name: name.clone(),
+                                step: "registry".to_string(),
+                                success: false,
+                                message: format!("Added to target but failed to remove from source LocalForge: {e}"),
+                            };
+                        } else {
+                            yield HyperforgeEvent::RepoMove {
+                                repo_name: name.clone(),
+                                step: "registry".to_string(),
+                                success: true,
+                                message: format!("{dry_prefix}moved {src_org} → {target_org} in LocalForge"),
+                            };
+                        }
+                    } else {
+                        yield HyperforgeEvent::RepoMove {
+                            repo_name: name.clone(),
+                            step: "registry".to_string(),
+                            success: true,
+                            message: format!("{dry_prefix}not in source LocalForge, skipping registry"),
+                        };
+                    }
+                } else {
+                    yield HyperforgeEvent::RepoMove {
+                        repo_name: name.clone(),
+                        step: "registry".to_string(),
+                        success: true,
+                        message: format!("{dry_prefix}no source org, skipping registry"),
+                    };
+                }
+
+                // ── Step 4: Move directory ──
+                if is_dry_run {
+                    yield HyperforgeEvent::RepoMove {
+                        repo_name: name.clone(),
+                        step: "directory".to_string(),
+                        success: true,
+                        message: format!("{}would move to {}", dry_prefix, target_repo_path.display()),
+                    };
+                    moved += 1;
+                } else {
+                    match tokio::fs::rename(&repo_path, &target_repo_path).await {
+                        Ok(()) => {
+                            yield HyperforgeEvent::RepoMove {
+                                repo_name: name.clone(),
+                                step: "directory".to_string(),
+                                success: true,
+                                message: format!("moved to {}", target_repo_path.display()),
+                            };
+                            moved += 1;
+                        }
+                        Err(e) => {
+                            yield HyperforgeEvent::RepoMove {
+                                repo_name: name.clone(),
+                                step: "directory".to_string(),
+                                success: false,
+                                message: format!("Failed to move directory: {e}"),
+                            };
+                            failed += 1;
+                        }
+                    }
+                }
+            }
+
+            // ── Save LocalForge state ──
+            if !is_dry_run {
+                // Collect all source orgs that were affected
+                let source_orgs: HashSet<String> = repo.iter()
+                    .filter_map(|name| discovered_map.get(name.as_str()).and_then(|d| d.org().map(std::string::ToString::to_string)))
+                    .collect();
+
+                for src_org in &source_orgs {
+                    let forge = state.get_local_forge(src_org).await;
+                    if let Err(e) = forge.save_to_yaml().await {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("Failed to save source LocalForge for {src_org}: {e}"),
+                        );
+                    }
+                }
+                let target_forge = state.get_local_forge(&target_org).await;
+                if let Err(e) = target_forge.save_to_yaml().await {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("Failed to save target LocalForge for {target_org}: {e}"),
+                    );
+                }
+            }
+
+            // ── Summary ──
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{}Move complete: {} moved, {} failed (of {} requested)",
+                    dry_prefix, moved, failed, repo.len(),
+                ),
+            };
+
+            yield HyperforgeEvent::WorkspaceSummary {
+                total_repos: repo.len(),
+                configured_repos: moved,
+                unconfigured_repos: failed,
+                clean_repos: None,
+                dirty_repos: None,
+                wrong_branch_repos: None,
+                push_success: None,
+                push_failed: None,
+                validation_passed: None,
+            };
+        })
+    }
+
+    /// Check the linear-history policy across repos
+    #[plexus_macros::method(
+        description = "For repos under a `linear_history = true` policy (workspace [policy] or repo override), verify the default branch has no merge commits and every forge only allows squash merges. Run before enabling branch protection.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            skip_forges = "Only check local history, not forge merge settings (optional, default: false)"
+        )
+    )]
+    pub async fn linear_history(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        skip_forges: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+        let check_forges = !skip_forges.unwrap_or(false);
+
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let policy = match crate::config::WorkspaceConfig::load(&ctx.root) {
+                Ok(c) => c.policy,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    return;
+                }
+            };
+
+            let repos: Vec<_> = ctx
+                .repos
+                .iter()
+                .filter(|r| filter.matches(&r.dir_name))
+                .filter(|r| {
+                    r.config
+                        .as_ref()
+                        .and_then(|c| c.linear_history)
+                        .unwrap_or(policy.linear_history)
+                })
+                .cloned()
+                .collect();
+
+            if repos.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No repos under a linear_history policy.".to_string(),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("Checking linear history for {} repos...", repos.len()),
+            };
+
+            let mut violations = 0usize;
+
+            // ── Local: merge commits on the default branch ──
+            let local_inputs: Vec<(String, PathBuf, String)> = repos
+                .iter()
+                .filter(|r| r.is_git_repo)
+                .map(|r| {
+                    let branch = r
+                        .config
+                        .as_ref()
+                        .and_then(|c| c.default_branch.clone())
+                        .unwrap_or_else(|| "main".to_string());
+                    (r.dir_name.clone(), r.path.clone(), branch)
+                })
+                .collect();
+
+            let results = run_batch_blocking(local_inputs, Scheduler::global().batch(None), |(dir_name, path, branch)| {
+                let merges = Git::merge_commits(&path, &branch);
+                (dir_name, branch, merges)
+            }).await;
+
+            for result in results {
+                match result {
+                    Ok((_, _, Ok(merges))) if merges.is_empty() => {}
+                    Ok((dir_name, branch, Ok(merges))) => {
+                        violations += 1;
+                        yield HyperforgeEvent::LinearHistoryViolation {
+                            repo_name: dir_name,
+                            forge: None,
+                            check: "merge_commits".to_string(),
+                            detail: format!(
+                                "{} merge commits on {branch} (latest {})",
+                                merges.len(),
+                                &merges[0][..merges[0].len().min(8)]
+                            ),
+                        };
+                    }
+                    Ok((dir_name, branch, Err(e))) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Git,
+                            format!("  {dir_name}: cannot read {branch}: {e}"),
+                        );
+                    }
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                    }
+                }
+            }
+
+            // ── Forges: only squash merges allowed ──
+            if check_forges {
+                let policy_ctx = crate::commands::workspace::WorkspaceContext {
+                    repos,
+                    ..ctx.clone()
+                };
+                let work = repo_forge_targets(&policy_ctx, &filter, &config_dir);
+                let results = run_batch(work, Scheduler::global().batch(None), |t| async move {
+                    let settings = match make_adapter(&t.forge, &t.org, None, t.host.as_deref()) {
+                        Ok(adapter) => adapter.merge_settings(&t.org, &t.name).await.map_err(|e| e.to_string()),
+                        Err(e) => Err(e),
+                    };
+                    (t.dir_name, t.forge, settings)
+                }).await;
+
+                for result in results {
+                    match result {
+                        Ok((_, _, Ok(Some(settings)))) if settings.is_squash_only() => {}
+                        Ok((dir_name, forge, Ok(Some(settings)))) => {
+                            violations += 1;
+                            yield HyperforgeEvent::LinearHistoryViolation {
+                                repo_name: dir_name,
+                                forge: Some(forge),
+                                check: "merge_settings".to_string(),
+                                detail: format!("allows {settings}; expected squash only"),
+                            };
+                        }
+                        Ok((dir_name, forge, Ok(None))) => {
+                            yield HyperforgeEvent::Info {
+                                message: format!("  {dir_name} ({forge}): merge settings not visible with this token"),
+                            };
+                        }
+                        Ok((dir_name, forge, Err(e))) => {
+                            yield HyperforgeEvent::error(
+                                HyperforgeError::ForgeApi,
+                                format!("  {dir_name} ({forge}): {e}"),
+                            );
+                        }
+                        Err(e) => {
+                            yield msg_error!(Msg::TaskFailed, error = e);
+                        }
+                    }
+                }
+            }
+
+            yield HyperforgeEvent::Info {
+                message: if violations == 0 {
+                    "Linear history: no violations — safe to enable protection rules.".to_string()
+                } else {
+                    format!("Linear history: {violations} violations")
+                },
+            };
+        })
+    }
+
+    /// Compare forge-side visibility with config
+    #[plexus_macros::method(
+        description = "Fast visibility audit: list each org once per forge and compare every configured repo's actual visibility with its config ([forge.<name>] visibility overrides included). A repo configured private but public on a forge is a high-severity leak; the reverse is reported as low severity.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)"
+        )
+    )]
+    pub async fn visibility_audit(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let targets = repo_forge_targets(&ctx, &filter, &config_dir);
+            if targets.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No configured repos matched filter.".to_string(),
+                };
+                return;
+            }
+
+            // Expected visibility by (org, forge, remote name)
+            let mut expected: HashMap<(String, String, String), (String, Visibility)> = HashMap::new();
+            for repo in &ctx.repos {
+                let Some(config) = &repo.config else { continue };
+                let name = config.get_repo_name(&repo.path);
+                for t in targets.iter().filter(|t| t.dir_name == repo.dir_name) {
+                    expected.insert(
+                        (t.org.clone(), t.forge.clone(), name.clone()),
+                        (repo.dir_name.clone(), config.visibility_for(&t.forge)),
+                    );
+                }
+            }
+
+            let mut pairs: Vec<(String, String)> = targets.iter().map(|t| (t.org.clone(), t.forge.clone())).collect();
+            pairs.sort();
+            pairs.dedup();
+
+            yield HyperforgeEvent::Info {
+                message: format!("Auditing visibility of {} repos across {} org/forge pairs...", expected.len(), pairs.len()),
+            };
+
+            let results = run_batch(with_forge_hosts(pairs, &config_dir), Scheduler::global().batch(None), |(org, forge, host)| async move {
+                let listed = match make_adapter(&forge, &org, None, host.as_deref()) {
+                    Ok(adapter) => adapter.list_repos(&org).await.map_err(|e| e.to_string()),
+                    Err(e) => Err(e),
+                };
+                (org, forge, listed)
+            }).await;
+
+            let mut leaks = 0usize;
+            let mut mismatches = 0usize;
+            let mut checked = 0usize;
+
+            for result in results {
+                let (org, forge, listed) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                let remote_repos = match listed {
+                    Ok(r) => r,
+                    Err(e) => {
+                        yield HyperforgeEvent::error(HyperforgeError::ForgeApi, format!("  {forge}/{org}: listing failed: {e}"));
+                        continue;
+                    }
+                };
+
+                for remote in remote_repos {
+                    let Some((dir_name, want)) = expected.get(&(org.clone(), forge.clone(), remote.name.clone())) else {
+                        continue;
+                    };
+                    checked += 1;
+                    if remote.visibility == *want {
+                        continue;
+                    }
+                    let severity = if remote.visibility == Visibility::Public {
+                        leaks += 1;
+                        "high"
+                    } else {
+                        mismatches += 1;
+                        "low"
+                    };
+                    yield HyperforgeEvent::VisibilityFinding {
+                        repo_name: dir_name.clone(),
+                        org: org.clone(),
+                        forge: forge.clone(),
+                        expected: want.as_str().to_string(),
+                        actual: remote.visibility.as_str().to_string(),
+                        severity: severity.to_string(),
+                    };
+                }
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "Visibility audit: {checked} checked, {leaks} public leaks of private repos, {mismatches} other mismatches"
+                ),
+            };
+        })
+    }
+
+    /// Fleet health checks against the forges
+    #[plexus_macros::method(
+        description = "Check workspace repos for fleet health problems on their forges. Currently reports forks whose upstream has been archived or deleted, with a suggestion to adopt the fork as origin or archive it.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)"
+        )
+    )]
+    pub async fn health(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let work = repo_forge_targets(&ctx, &filter, &config_dir);
+
+            if work.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No configured repos matched filter.".to_string(),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("Checking fork upstreams for {} repo/forge pairs...", work.len()),
+            };
+
+            let results = run_batch(work, Scheduler::global().batch(None), |t| async move {
+                let info = match make_adapter(&t.forge, &t.org, None, t.host.as_deref()) {
+                    Ok(adapter) => adapter.fork_info(&t.org, &t.name).await.map_err(|e| e.to_string()),
+                    Err(e) => Err(e),
+                };
+                (t.dir_name, t.forge, info)
+            }).await;
+
+            let mut forks = 0usize;
+            let mut stale = 0usize;
+            let mut failed = 0usize;
+
+            for result in results {
+                match result {
+                    Ok((dir_name, forge, Ok(Some(info)))) => {
+                        forks += 1;
+                        let suggestion = match info.status {
+                            UpstreamStatus::Active => continue,
+                            UpstreamStatus::Archived => "upstream is archived — adopt this fork as origin (drop the upstream remote) or archive it",
+                            UpstreamStatus::Deleted => "upstream no longer exists — adopt this fork as origin or archive it",
+                        };
+                        stale += 1;
+                        yield HyperforgeEvent::ForkHealth {
+                            repo_name: dir_name,
+                            forge,
+                            upstream: info.upstream,
+                            upstream_status: info.status.as_str().to_string(),
+                            suggestion: suggestion.to_string(),
+                        };
+                    }
+                    Ok((_, _, Ok(None))) => {}
+                    Ok((dir_name, forge, Err(e))) => {
+                        failed += 1;
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  {dir_name} ({forge}): {e}"),
+                        );
+                    }
+                    Err(e) => {
+                        failed += 1;
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                    }
+                }
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("Health: {forks} forks, {stale} with archived/deleted upstream, {failed} checks failed"),
+            };
+        })
+    }
+
+    /// Storage usage per repo and per org/forge
+    #[plexus_macros::method(
+        description = "Report storage each forge attributes to the workspace's repos — git size, LFS, CI artifacts, packages (where the forge exposes them) — aggregated per org and forge, largest first.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            limit_mb = "Per-repo storage limit in MiB; repos at 80% or more are flagged (optional)"
+        )
+    )]
+    pub async fn usage(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        limit_mb: Option<u64>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+        // 80% of the limit; a limit too large to count in bytes never warns
+        let warn_bytes = limit_mb.map(|mb| mb.saturating_mul(1024 * 1024) / 10 * 8);
+
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let work = repo_forge_targets(&ctx, &filter, &config_dir);
+            if work.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No configured repos matched filter.".to_string(),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("Collecting storage usage for {} repo/forge pairs...", work.len()),
+            };
+
+            let results = run_batch(work, Scheduler::global().batch(None), |t| async move {
+                let usage = match make_adapter(&t.forge, &t.org, None, t.host.as_deref()) {
+                    Ok(adapter) => adapter.storage_usage(&t.org, &t.name).await.map_err(|e| e.to_string()),
+                    Err(e) => Err(e),
+                };
+                (t, usage)
+            }).await;
+
+            let mut rows: Vec<(RepoForgeTarget, crate::adapters::StorageUsage)> = Vec::new();
+            let mut unreported = 0usize;
+            for result in results {
+                match result {
+                    Ok((t, Ok(Some(usage)))) => rows.push((t, usage)),
+                    Ok((_, Ok(None))) => unreported += 1,
+                    Ok((t, Err(e))) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  {} ({}): {e}", t.dir_name, t.forge),
+                        );
+                    }
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                    }
+                }
+            }
+            rows.sort_by_key(|(_, u)| std::cmp::Reverse(u.total_bytes()));
+
+            let mut totals: std::collections::BTreeMap<(String, String), (usize, crate::adapters::StorageUsage)> =
+                std::collections::BTreeMap::new();
+            for (t, usage) in &rows {
+                let total = usage.total_bytes();
+                yield HyperforgeEvent::RepoUsage {
+                    repo_name: t.dir_name.clone(),
+                    org: t.org.clone(),
+                    forge: t.forge.clone(),
+                    repo_bytes: usage.repo_bytes,
+                    lfs_bytes: usage.lfs_bytes,
+                    artifact_bytes: usage.artifact_bytes,
+                    package_bytes: usage.package_bytes,
+                    total_bytes: total,
+                    near_limit: warn_bytes.is_some_and(|w| total >= w),
+                };
+
+                let (count, sum) = totals.entry((t.org.clone(), t.forge.clone())).or_default();
+                *count += 1;
+                sum.repo_bytes += usage.repo_bytes;
+                for (acc, v) in [
+                    (&mut sum.lfs_bytes, usage.lfs_bytes),
+                    (&mut sum.artifact_bytes, usage.artifact_bytes),
+                    (&mut sum.package_bytes, usage.package_bytes),
+                ] {
+                    if let Some(v) = v {
+                        *acc = Some(acc.unwrap_or(0) + v);
+                    }
+                }
+            }
+
+            for ((org, forge), (repos, sum)) in totals {
+                yield HyperforgeEvent::UsageSummary {
+                    org,
+                    forge,
+                    repos,
+                    repo_bytes: sum.repo_bytes,
+                    lfs_bytes: sum.lfs_bytes,
+                    artifact_bytes: sum.artifact_bytes,
+                    package_bytes: sum.package_bytes,
+                    total_bytes: sum.total_bytes(),
+                };
+            }
+
+            if unreported > 0 {
+                yield HyperforgeEvent::Info {
+                    message: format!("  {unreported} repo/forge pairs reported no usage (forge doesn't expose it or insufficient access)"),
+                };
+            }
+        })
+    }
+
+    /// Capture remote repo metadata for every org/forge pair into a snapshot file
+    #[plexus_macros::method(
+        description = "Capture the metadata every forge reports for the workspace's org repos (visibility, description, default branch) into a timestamped JSON snapshot. Reflects forge state only, not local state.",
+        params(
+            path = "Path to workspace directory (determines org/forge pairs)",
+            org = "Only snapshot this org (optional)",
+            output = "Snapshot file to write (optional, default: <path>/.hyperforge/snapshots/<UTC timestamp>.json)"
+        )
+    )]
+    pub async fn snapshot(
+        &self,
+        path: String,
+        org: Option<String>,
+        output: Option<String>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let pairs: Vec<(String, String)> = ctx
+                .org_forge_pairs()
+                .into_iter()
+                .filter(|(o, _)| org.as_ref().is_none_or(|wanted| wanted == o))
+                .collect();
+
+            if pairs.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No org/forge pairs found — nothing to snapshot.".to_string(),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("Snapshotting {} org/forge pairs...", pairs.len()),
+            };
+
+            let results = run_batch(with_forge_hosts(pairs, &config_dir), Scheduler::global().batch(None), |(org, forge, host)| async move {
+                let listed = match make_adapter(&forge, &org, None, host.as_deref()) {
+                    Ok(adapter) => adapter.list_repos(&org).await.map_err(|e| e.to_string()),
+                    Err(e) => Err(e),
+                };
+                let (repos, error) = match listed {
+                    Ok(repos) => (
+                        repos.iter().map(|r| (r.name.clone(), RepoMeta::from(r))).collect(),
+                        None,
+                    ),
+                    Err(e) => (std::collections::BTreeMap::new(), Some(e)),
+                };
+                SnapshotScope { org, forge, repos, error }
+            }).await;
+
+            let mut scopes = Vec::new();
+            for result in results {
+                match result {
+                    Ok(scope) => {
+                        if let Some(e) = &scope.error {
+                            yield HyperforgeEvent::error(
+                                HyperforgeError::ForgeApi,
+                                format!("  {}/{}: listing failed: {e}", scope.forge, scope.org),
+                            );
+                        }
+                        scopes.push(scope);
+                    }
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                    }
+                }
+            }
+
+            let snapshot = Snapshot::new(scopes);
+            let out_path = output
+                .map_or_else(|| Snapshot::default_path(&ctx.root, snapshot.taken_at), PathBuf::from);
+            if let Err(e) = snapshot.save(&out_path) {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                return;
+            }
+
+            yield HyperforgeEvent::SnapshotSaved {
+                path: out_path.display().to_string(),
+                taken_at: snapshot.taken_at.to_rfc3339(),
+                scopes: snapshot.scopes.len(),
+                repos: snapshot.repo_count(),
+                failed_scopes: snapshot.scopes.iter().filter(|s| s.error.is_some()).count(),
+            };
+        })
+    }
+
+    /// Compare two remote-state snapshots
+    #[plexus_macros::method(
+        description = "Compare two snapshots written by `workspace snapshot` and report repos added, removed, or changed (visibility, description, default branch) on each forge.",
+        params(
+            from = "Older snapshot file",
+            to = "Newer snapshot file"
+        )
+    )]
+    pub async fn snapshot_diff(
+        &self,
+        from: String,
+        to: String,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, stream! {
+            let (before, after) = match (
+                Snapshot::load(&PathBuf::from(&from)),
+                Snapshot::load(&PathBuf::from(&to)),
+            ) {
+                (Ok(b), Ok(a)) => (b, a),
+                (Err(e), _) | (_, Err(e)) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    return;
+                }
+            };
+
+            let changes = crate::services::snapshot::diff(&before, &after);
+
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "Snapshot diff {} → {}: {} changes",
+                    before.taken_at.to_rfc3339(),
+                    after.taken_at.to_rfc3339(),
+                    changes.len()
+                ),
+            };
+
+            for change in changes {
+                yield HyperforgeEvent::SnapshotChange {
+                    org: change.org,
+                    forge: change.forge,
+                    repo_name: change.repo_name,
+                    change: change.kind.as_str().to_string(),
+                    field: change.field,
+                    before: change.before,
+                    after: change.after,
+                };
+            }
+        })
+    }
+
+    /// Converge toolchain pin files to the versions declared in workspace config
+    #[plexus_macros::method(
+        description = "Report and converge toolchain pin files (rust-toolchain.toml, .ghcup, .nvmrc) to the versions declared under [toolchains] in .hyperforge/workspace.toml. Changed pins are committed per repo.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            dry_run = "Preview without writing files (optional, default: false)",
+            no_commit = "Write pin files but don't commit them (optional, default: false)"
+        )
+    )]
+    pub async fn toolchains(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        dry_run: Option<bool>,
+        no_commit: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        output::structured(None, &self.state.config_dir, crate::hubs::build::toolchains::toolchains(path, include, exclude, dry_run, no_commit))
+    }
+
+    /// Converge forge approval rules from the workspace ownership map
+    #[plexus_macros::method(
+        description = "Converge forge approval rules from the [owners] table in .hyperforge/workspace.toml so review routing matches the generated CODEOWNERS on every mirror. On GitLab each @user or @group owner gets a `codeowners: <owner>` project rule; managed rules for removed owners are deleted. Forges that route reviews from CODEOWNERS alone are skipped.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            approvals = "Approvals required per rule (optional, default: 1)",
+            dry_run = "Preview without changing forge settings (optional, default: false)"
+        )
+    )]
+    pub async fn approval_rules(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        approvals: Option<u32>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+        let is_dry_run = dry_run.unwrap_or(false);
+        let approvals = approvals.unwrap_or(1);
+
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+            let prefix = dry_prefix(is_dry_run);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let owners = match crate::config::WorkspaceConfig::load(&ctx.root) {
+                Ok(c) => c.owners,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    return;
+                }
+            };
+            let rules = crate::hubs::build::codeowners::approval_rules(&owners, approvals);
+            if rules.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No @user or @group owners declared under [owners] in workspace config."
+                        .to_string(),
+                };
+                return;
+            }
+
+            let targets = repo_forge_targets(&ctx, &filter, &config_dir);
+            if targets.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No configured repos matched filter.".to_string(),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{prefix}Converging {} approval rules across {} repo/forge targets...",
+                    rules.len(),
+                    targets.len()
+                ),
+            };
+
+            let results = run_batch(targets, Scheduler::global().batch(None), move |t| {
+                let rules = rules.clone();
+                async move {
+                    let changes = match make_adapter(&t.forge, &t.org, None, t.host.as_deref()) {
+                        Ok(adapter) => adapter
+                            .converge_approval_rules(&t.org, &t.name, &rules, is_dry_run)
+                            .await
+                            .map_err(|e| e.to_string()),
+                        Err(e) => Err(e),
+                    };
+                    (t, changes)
+                }
+            }).await;
+
+            let mut changed = 0usize;
+            let mut skipped = 0usize;
+            let mut failed = 0usize;
+
+            for result in results {
+                let (target, changes) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        failed += 1;
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                match changes {
+                    Ok(Some(changes)) => {
+                        for change in changes {
+                            if change.action != "unchanged" {
+                                changed += 1;
+                            }
+                            yield HyperforgeEvent::ApprovalRuleSync {
+                                repo_name: target.dir_name.clone(),
+                                forge: target.forge.clone(),
+                                rule: change.rule,
+                                action: change.action.to_string(),
+                            };
+                        }
+                    }
+                    Ok(None) => skipped += 1,
+                    Err(e) => {
+                        failed += 1;
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  {}@{}: {e}", target.dir_name, target.forge),
+                        );
+                    }
+                }
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{prefix}Approval rules: {changed} changed, {skipped} targets without approval rules, {failed} failed"
+                ),
+            };
+        })
+    }
+
+    /// Triage dependency-update PRs across forges
+    #[plexus_macros::method(
+        description = "List open Renovate/Dependabot pull requests for every repo on every forge with their CI state, and report per-repo update lag (open PRs and age of the oldest). Optionally approve and/or merge the ones whose checks pass.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            approve = "Approve update PRs whose checks pass (optional, default: false)",
+            merge = "Merge update PRs whose checks pass (optional, default: false)",
+            dry_run = "Report what would be approved/merged without doing it (optional, default: false)"
+        )
+    )]
+    pub async fn updates(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        approve: Option<bool>,
+        merge: Option<bool>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+        let approve = approve.unwrap_or(false);
+        let merge = merge.unwrap_or(false);
+        let is_dry_run = dry_run.unwrap_or(false);
+
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+            let prefix = dry_prefix(is_dry_run);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let targets = repo_forge_targets(&ctx, &filter, &config_dir);
+            if targets.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No configured repos matched filter.".to_string(),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("{}Checking update PRs across {} repo/forge targets...", prefix, targets.len()),
+            };
+
+            let results = run_batch(targets, Scheduler::global().batch(None), move |t| async move {
+                let triaged = triage_update_prs(&t, approve, merge, is_dry_run).await;
+                (t, triaged)
+            }).await;
+
+            let now = Utc::now();
+            let mut total = 0usize;
+            let mut passing = 0usize;
+            let mut merged = 0usize;
+
+            for result in results {
+                let (target, triaged) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                let prs = match triaged {
+                    Ok(Some(prs)) => prs,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  {}@{}: {e}", target.dir_name, target.forge),
+                        );
+                        continue;
+                    }
+                };
+
+                let oldest_days = prs
+                    .iter()
+                    .filter_map(|p| p.pr.created_at.map(|c| crate::services::updates::age_days(c, now)))
+                    .max();
+                yield HyperforgeEvent::UpdateLag {
+                    repo_name: target.dir_name.clone(),
+                    forge: target.forge.clone(),
+                    open_prs: prs.len(),
+                    oldest_days,
+                };
+
+                for p in prs {
+                    total += 1;
+                    if p.checks == crate::adapters::CheckState::Success {
+                        passing += 1;
+                    }
+                    if p.action.as_deref() == Some("merged") {
+                        merged += 1;
+                    }
+                    yield HyperforgeEvent::UpdatePr {
+                        repo_name: target.dir_name.clone(),
+                        forge: target.forge.clone(),
+                        number: p.pr.number,
+                        title: p.pr.title,
+                        bot: p.bot.as_str().to_string(),
+                        checks: p.checks.as_str().to_string(),
+                        age_days: p.pr.created_at.map(|c| crate::services::updates::age_days(c, now)),
+                        url: p.pr.url,
+                        action: p.action,
+                    };
+                }
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("{prefix}Update PRs: {total} open, {passing} passing checks, {merged} merged"),
+            };
+        })
+    }
+
+    /// List open pull requests across every repo and forge
+    #[plexus_macros::method(
+        description = "List open pull requests (merge requests on GitLab) for every workspace repo on every forge it declares, emitting one summary per repo/forge with each PR's author, branches, draft flag and age. Forges without pull request support are skipped.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            author = "Only PRs opened by this user (optional)",
+            drafts = "Include draft PRs (optional, default: true)"
+        )
+    )]
+    pub async fn prs(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        author: Option<String>,
+        drafts: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+        let include_drafts = drafts.unwrap_or(true);
+
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let targets = repo_forge_targets(&ctx, &filter, &config_dir);
+            if targets.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No configured repos matched filter.".to_string(),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("Listing pull requests across {} repo/forge targets...", targets.len()),
+            };
+
+            let results = run_batch(targets, Scheduler::global().batch(None), |t| async move {
+                let listed = match make_adapter(&t.forge, &t.org, None, t.host.as_deref()) {
+                    Ok(adapter) => adapter.list_pull_requests(&t.org, &t.name).await.map_err(|e| e.to_string()),
+                    Err(e) => Err(e),
+                };
+                (t, listed)
+            }).await;
+
+            let now = Utc::now();
+            let mut total = 0usize;
+            let mut with_prs = 0usize;
+            let mut unsupported = 0usize;
+
+            for result in results {
+                let (target, listed) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                let mut prs = match listed {
+                    Ok(Some(prs)) => prs,
+                    Ok(None) => {
+                        unsupported += 1;
+                        continue;
+                    }
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  {}@{}: {e}", target.dir_name, target.forge),
+                        );
+                        continue;
+                    }
+                };
+                prs.retain(|p| {
+                    (include_drafts || !p.draft)
+                        && author.as_ref().is_none_or(|a| p.author.eq_ignore_ascii_case(a))
+                });
+                prs.sort_by_key(|p| (p.created_at, p.number));
+
+                total += prs.len();
+                if !prs.is_empty() {
+                    with_prs += 1;
+                }
+                let pull_requests: Vec<PullRequestInfo> = prs
+                    .into_iter()
+                    .map(|p| PullRequestInfo {
+                        age_days: p.created_at.map(|c| crate::services::updates::age_days(c, now)),
+                        number: p.number,
+                        title: p.title,
+                        author: p.author,
+                        head_branch: p.head_branch,
+                        base_branch: p.base_branch,
+                        draft: p.draft,
+                        url: p.url,
+                    })
+                    .collect();
+                yield HyperforgeEvent::PullRequestSummary {
+                    repo_name: target.dir_name,
+                    org: target.org,
+                    forge: target.forge,
+                    open: pull_requests.len(),
+                    drafts: pull_requests.iter().filter(|p| p.draft).count(),
+                    oldest_days: pull_requests.iter().filter_map(|p| p.age_days).max(),
+                    pull_requests,
+                };
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "Pull requests: {total} open across {with_prs} repo/forge targets{}",
+                    if unsupported > 0 { format!(" ({unsupported} on forges without pull requests)") } else { String::new() },
+                ),
+            };
+        })
+    }
+
+    /// Propose managed files as pull requests instead of direct commits
+    #[plexus_macros::method(
+        description = "Sync the [managed] files from .hyperforge/workspace.toml onto a branch cut from each targeted repo's up-to-date default branch, push it to the repo's origin forge and open a pull request there with a shared title, body and labels — instead of committing to the default branch. Opened PRs are recorded in .hyperforge/changes/<branch>.toml; re-running re-cuts the branch, force-pushes it and reuses open PRs, updating their titles.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            branch = "Branch to push and open PRs from (optional, default: [managed].branch)",
+            title = "PR title (optional, default: the managed commit message)",
+            body = "PR description (optional)",
+            labels = "Labels to apply to every PR (optional, repeatable)",
+            dry_run = "Preview without writing, pushing or opening PRs (optional, default: false)",
+            force = "Overwrite files edited by hand since the last sync (optional, default: false)"
+        )
+    )]
+    pub async fn files_pr(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        branch: Option<String>,
+        title: Option<String>,
+        body: Option<String>,
+        labels: Option<Vec<String>>,
+        dry_run: Option<bool>,
+        force: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+        let is_dry_run = dry_run.unwrap_or(false);
+
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+            let prefix = dry_prefix(is_dry_run);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let managed = match crate::config::WorkspaceConfig::load(&ctx.root) {
+                Ok(c) => c.managed,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    return;
+                }
+            };
+            let Some(branch) = branch.or(managed.branch) else {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::Validation,
+                    "PRs need a branch — pass --branch or set [managed].branch.".to_string(),
+                );
+                return;
+            };
+            let message = managed.message.unwrap_or_else(|| managed_files::DEFAULT_MESSAGE.to_string());
+            let title = title.unwrap_or_else(|| message.clone());
+
+            let year = Utc::now().format("%Y").to_string();
+            let mut work: Vec<(ChangeTarget, RepoFiles)> = Vec::new();
+            for repo in ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)) {
+                let Some(config) = repo.config.as_ref() else { continue };
+                let Some(forge) = config.forges.first() else { continue };
+                let Some(org) = config.org_for_forge(forge) else { continue };
+                match managed_files::plan_repo(&ctx.root, repo, &managed.files, &year) {
+                    Ok(files) if files.is_empty() => {}
+                    Ok(files) => work.push((
+                        ChangeTarget {
+                            dir_name: repo.dir_name.clone(),
+                            path: repo.path.clone(),
+                            forge: forge.clone(),
+                            org: org.to_string(),
+                            name: repo.effective_name(),
+                            remote: config.remote_for_forge(forge),
+                            base: config.effective_default_branch().to_string(),
+                            host: forge_host(&config_dir, org, forge, config.forge_host(forge)),
+                        },
+                        files,
+                    )),
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Config,
+                            format!("  {}: {e}", repo.dir_name),
+                        );
+                    }
+                }
+            }
+
+            if work.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No configured repos targeted by managed files.".to_string(),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("{}Proposing managed files on '{}' across {} repos...", prefix, branch, work.len()),
+            };
+
+            // Phase 1: commit onto the branch and push it (blocking git)
+            let options = SyncOptions::new(message, Some(branch.clone()), dry_run, None, force);
+            let root = ctx.root.clone();
+            let push_branch = branch.clone();
+            let synced = run_batch_blocking(work, Scheduler::global().batch(None), move |(target, files)| {
+                let options = options.clone().cut_from(&target.remote, &target.base);
+                let result = managed_files::sync_repo(&root, &target.path, &files, &options);
+                let changed = result.as_ref().is_ok_and(|o| o.iter().any(managed_files::FileOutcome::is_change));
+                // The branch is re-cut from the base every run, so it may
+                // have diverged from what an earlier run pushed
+                let pushed = if changed && !is_dry_run {
+                    Git::push_with_env(&target.path, &target.remote, &push_branch, &["-u", "--force-with-lease"], &[])
+                        .map_err(|e| format!("git push {} {push_branch} failed: {e}", target.remote))
+                } else {
+                    Ok(())
+                };
+                (target, changed, pushed, result)
+            })
+            .await;
+
+            let mut outcomes = Vec::new();
+            let mut to_open = Vec::new();
+            for result in synced {
+                match result {
+                    Ok((target, changed, pushed, result)) => {
+                        match pushed {
+                            Ok(()) if changed => to_open.push(target.clone()),
+                            Ok(()) => {}
+                            Err(e) => {
+                                yield HyperforgeEvent::error(
+                                    HyperforgeError::Git,
+                                    format!("  {}: {e}", target.dir_name),
+                                );
+                            }
+                        }
+                        outcomes.push(Ok((target.dir_name, result)));
+                    }
+                    Err(e) => outcomes.push(Err(e)),
+                }
+            }
+            if let Err(e) = managed_files::record_manifest(&ctx.root, &outcomes) {
+                yield HyperforgeEvent::error(HyperforgeError::Config, e);
+            }
+            for event in managed_files::outcome_events(outcomes, prefix, "Managed files") {
+                yield event;
+            }
+
+            if to_open.is_empty() {
+                return;
+            }
+
+            // Phase 2: open (or reuse) a PR on each repo's origin forge
+            let new_pr = NewPullRequest {
+                title: title.clone(),
+                body: body.unwrap_or_default(),
+                head: branch.clone(),
+                head_owner: None,
+                base: String::new(),
+                labels: labels.unwrap_or_default(),
+            };
+            let opened = run_batch(to_open, Scheduler::global().batch(None), move |target| {
+                let pr = NewPullRequest { base: target.base.clone(), ..new_pr.clone() };
+                async move {
+                    if is_dry_run {
+                        return (target, Ok(None));
+                    }
+                    let result = match make_adapter(&target.forge, &target.org, None, target.host.as_deref()) {
+                        Ok(adapter) => adapter
+                            .create_pull_request(&target.org, &target.name, &pr)
+                            .await
+                            .map_err(|e| e.to_string()),
+                        Err(e) => Err(e),
+                    };
+                    (target, result)
+                }
+            }).await;
+
+            let mut manifest = match ChangeManifest::load(&ctx.root, &branch, &title) {
+                Ok(m) => m,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                    return;
+                }
+            };
+            manifest.title = title.clone();
+            let mut count = 0usize;
+            for result in opened {
+                let (target, created) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                let (url, action) = match created {
+                    Ok(Some(url)) => {
+                        count += 1;
+                        manifest.prs.insert(
+                            target.dir_name.clone(),
+                            ChangePr { forge: target.forge.clone(), url: url.clone() },
+                        );
+                        (url, "opened")
+                    }
+                    Ok(None) if is_dry_run => {
+                        count += 1;
+                        (String::new(), "would_open")
+                    }
+                    Ok(None) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  {}: {} doesn't support pull requests", target.dir_name, target.forge),
+                        );
+                        (String::new(), "failed")
+                    }
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  {}@{}: {e}", target.dir_name, target.forge),
+                        );
+                        (String::new(), "failed")
+                    }
+                };
+                yield HyperforgeEvent::ChangePr {
+                    repo_name: target.dir_name,
+                    forge: target.forge,
+                    branch: branch.clone(),
+                    url,
+                    action: action.to_string(),
+                };
+            }
+
+            if !is_dry_run {
+                if let Err(e) = manifest.save(&ctx.root) {
+                    yield HyperforgeEvent::error(HyperforgeError::Config, e);
+                }
+            }
+            yield HyperforgeEvent::Info {
+                message: format!(
+                    "{}{} PRs for '{}' — tracked in {}",
+                    prefix,
+                    count,
+                    branch,
+                    ChangeManifest::path(&ctx.root, &branch).display()
+                ),
+            };
+        })
+    }
+
+    /// Contribute to repos you can't push to, via forks
+    #[plexus_macros::method(
+        description = "Contribute to upstream repos you can't push to: fork the repo on its origin forge, rewire remotes so `origin` is the fork and `upstream` the source, push the branch to the fork and open a PR against upstream's default branch. Repos you can push to are skipped.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            branch = "Branch to contribute (optional, default: each repo's checked-out branch)",
+            title = "PR title (optional, default: the branch's last commit subject)",
+            body = "PR description (optional)",
+            dry_run = "Check permissions and report what would be forked without changing anything (optional, default: false)"
+        )
+    )]
+    pub async fn contribute(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        branch: Option<String>,
+        title: Option<String>,
+        body: Option<String>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+        let is_dry_run = dry_run.unwrap_or(false);
+
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+            let prefix = dry_prefix(is_dry_run);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let mut targets = Vec::new();
+            for repo in ctx.repos.iter().filter(|r| r.is_git_repo && filter.matches(&r.dir_name)) {
+                let Some(config) = repo.config.as_ref() else { continue };
+                let Some(forge) = config.forges.first() else { continue };
+                let Some(org) = config.org_for_forge(forge) else { continue };
+                let head = match branch.clone().map_or_else(|| Git::current_branch(&repo.path).map_err(|e| e.to_string()), Ok) {
+                    Ok(b) => b,
+                    Err(e) => {
+                        yield HyperforgeEvent::error(HyperforgeError::Git, format!("  {}: {e}", repo.dir_name));
+                        continue;
+                    }
+                };
+                let base = config.effective_default_branch().to_string();
+                if head == base {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Git,
+                        format!("  {}: on {base} — commit the change on a topic branch first", repo.dir_name),
+                    );
+                    continue;
+                }
+                targets.push((
+                    ChangeTarget {
+                        dir_name: repo.dir_name.clone(),
+                        path: repo.path.clone(),
+                        forge: forge.clone(),
+                        org: org.to_string(),
+                        name: repo.effective_name(),
+                        remote: "origin".to_string(),
+                        base,
+                        host: forge_host(&config_dir, org, forge, config.forge_host(forge)),
+                    },
+                    head,
+                ));
+            }
+
+            if targets.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No configured repos matched filter.".to_string(),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("{}Checking push rights for {} repos...", prefix, targets.len()),
+            };
+
+            let results = run_batch(targets, Scheduler::global().batch(None), move |(target, head)| {
+                let title = title.clone();
+                let body = body.clone().unwrap_or_default();
+                async move {
+                    let result = contribute_via_fork(&target, &head, title, body, is_dry_run).await;
+                    (target, head, result)
+                }
+            }).await;
+
+            let mut opened = 0usize;
+            let mut skipped = 0usize;
+            for result in results {
+                let (target, head, outcome) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                let (url, action) = match outcome {
+                    Ok(Contribution::CanPush) => {
+                        skipped += 1;
+                        yield HyperforgeEvent::Info {
+                            message: format!("  {}: you can push to {}/{} — open a PR directly", target.dir_name, target.org, target.name),
+                        };
+                        continue;
+                    }
+                    Ok(Contribution::Unsupported) => {
+                        skipped += 1;
+                        yield HyperforgeEvent::Info {
+                            message: format!("  {}: {} doesn't report permissions or support forks — skipped", target.dir_name, target.forge),
+                        };
+                        continue;
+                    }
+                    Ok(Contribution::WouldFork) => {
+                        opened += 1;
+                        (String::new(), "would_open")
+                    }
+                    Ok(Contribution::Opened(url)) => {
+                        opened += 1;
+                        (url, "opened")
+                    }
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  {}@{}: {e}", target.dir_name, target.forge),
+                        );
+                        (String::new(), "failed")
+                    }
+                };
+                yield HyperforgeEvent::ChangePr {
+                    repo_name: target.dir_name,
+                    forge: target.forge,
+                    branch: head,
+                    url,
+                    action: action.to_string(),
+                };
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("{prefix}Contributions: {opened} PRs from forks, {skipped} skipped"),
+            };
+        })
+    }
+
+    /// CI status matrix across forges
+    #[plexus_macros::method(
+        description = "Query the latest CI result for each repo's default branch on every forge it's mirrored to (GitHub checks and statuses, GitLab pipelines, Woodpecker/Forgejo Actions commit statuses) and report a repo × forge pass/fail matrix, calling out failures that only happen on mirrors.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            failing = "Only report repos failing on at least one forge (optional, default: false)"
+        )
+    )]
+    pub async fn ci_status(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        failing: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        let filter = RepoFilter::new(include, exclude);
+        let failing_only = failing.unwrap_or(false);
+
+        let config_dir = self.state.config_dir.clone();
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let branches: HashMap<String, String> = ctx.repos.iter()
+                .filter_map(|r| Some((r.dir_name.clone(), r.config.as_ref()?.effective_default_branch().to_string())))
+                .collect();
+            let origins: HashMap<String, String> = ctx.repos.iter()
+                .filter_map(|r| Some((r.dir_name.clone(), r.config.as_ref()?.forges.first()?.clone())))
+                .collect();
+
+            let targets: Vec<(RepoForgeTarget, String)> = repo_forge_targets(&ctx, &filter, &config_dir)
+                .into_iter()
+                .map(|t| {
+                    let branch = branches.get(&t.dir_name).cloned().unwrap_or_else(|| "main".to_string());
+                    (t, branch)
+                })
+                .collect();
+            if targets.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No configured repos matched filter.".to_string(),
+                };
+                return;
+            }
+
+            yield HyperforgeEvent::Info {
+                message: format!("Checking CI across {} repo/forge targets...", targets.len()),
+            };
+
+            let results = run_batch(targets, Scheduler::global().batch(None), |(t, branch)| async move {
+                let state = match make_adapter(&t.forge, &t.org, None, t.host.as_deref()) {
+                    Ok(adapter) => adapter
+                        .check_state(&t.org, &t.name, &branch)
+                        .await
+                        .map_err(|e| e.to_string()),
+                    Err(e) => Err(e),
+                };
+                (t, branch, state)
+            }).await;
+
+            // repo → (branch, forge → state)
+            let mut matrix: std::collections::BTreeMap<String, (String, std::collections::BTreeMap<String, String>)> =
+                std::collections::BTreeMap::new();
+            for result in results {
+                let (target, branch, state) = match result {
+                    Ok(v) => v,
+                    Err(e) => {
+                        yield msg_error!(Msg::TaskFailed, error = e);
+                        continue;
+                    }
+                };
+                let cell = match state {
+                    Ok(s) => s.unwrap_or(crate::adapters::CheckState::Unknown).as_str().to_string(),
+                    Err(e) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  {}@{}: {e}", target.dir_name, target.forge),
+                        );
+                        "error".to_string()
+                    }
+                };
+                matrix
+                    .entry(target.dir_name)
+                    .or_insert_with(|| (branch, std::collections::BTreeMap::new()))
+                    .1
+                    .insert(target.forge, cell);
+            }
+
+            let mut passing = 0usize;
+            let mut failing_repos = Vec::new();
+            let mut mirror_only = Vec::new();
+            for (repo_name, (branch, states)) in matrix {
+                let failed: Vec<&String> = states.iter().filter(|(_, s)| *s == "failure").map(|(f, _)| f).collect();
+                if failed.is_empty() {
+                    if states.values().all(|s| s == "success") {
+                        passing += 1;
+                    }
+                } else {
+                    let origin = origins.get(&repo_name);
+                    let origin_ok = origin.is_some_and(|o| states.get(o).is_some_and(|s| s == "success"));
+                    let cells = failed.iter().map(|f| format!("{repo_name}@{f}")).collect::<Vec<_>>();
+                    if origin_ok {
+                        mirror_only.extend(cells.iter().cloned());
+                    }
+                    failing_repos.extend(cells);
+                }
+                if failing_only && failed.is_empty() {
+                    continue;
+                }
+                yield HyperforgeEvent::CiMatrixRow { repo_name, branch, states };
+            }
+
+            if !mirror_only.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: format!("Failing only on mirrors (origin passes): {}", mirror_only.join(", ")),
+                };
+            }
+            yield HyperforgeEvent::Info {
+                message: if failing_repos.is_empty() {
+                    format!("CI: {passing} repos passing on every forge, no failures")
+                } else {
+                    format!("CI: {passing} repos passing on every forge, failing: {}", failing_repos.join(", "))
+                },
+            };
+        })
+    }
+
+    /// Build each repo's container image and push it to its registries
+    #[plexus_macros::method(
+        description = "Build the container image of every repo with a Dockerfile (or an [image] section in its config), tag it with the package version, and push it to each configured registry — ghcr.io, registry.gitlab.com, Codeberg, Docker Hub, or a custom host — using registry credentials from the auth store.",
+        params(
+            path = "Path to workspace directory",
+            include = "Glob patterns — repo must match at least one (optional, repeatable)",
+            exclude = "Glob patterns — repo matching any is excluded; exclude wins over include (optional, repeatable)",
+            registry = "Registries to push to, overriding repo config: ghcr, gitlab, codeberg, dockerhub, or a host (optional, repeatable)",
+            latest = "Also tag and push each image as latest (optional, default: false)",
+            dry_run = "Preview without building or pushing (optional, default: false)"
+        )
+    )]
+    pub async fn images(
+        &self,
+        path: String,
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+        registry: Option<Vec<String>>,
+        latest: Option<bool>,
+        dry_run: Option<bool>,
+    ) -> impl Stream<Item = HyperforgeEvent> + Send + 'static {
+        use crate::hubs::images::{detect_dockerfile, push_to_registry};
+        use crate::types::registry::{ContainerRegistry, ImageRef};
+
+        let filter = RepoFilter::new(include, exclude);
+        let is_dry_run = dry_run.unwrap_or(false);
+        let also_latest = latest.unwrap_or(false);
+
+        output::structured(None, &self.state.config_dir, stream! {
+            let workspace_path = PathBuf::from(&path);
+            let prefix = dry_prefix(is_dry_run);
+
+            let ctx = match discover_or_bail(&workspace_path) {
+                Ok(ctx) => ctx,
+                Err(event) => { yield event; return; }
+            };
+
+            let mut work: Vec<ImageTarget> = Vec::new();
+            for repo in ctx.repos.iter().filter(|r| filter.matches(&r.dir_name)) {
+                let Some(config) = repo.config.as_ref() else { continue };
+                let image = config.image.clone().unwrap_or_default();
+                if image.skip {
+                    continue;
+                }
+                let dockerfile = match image.dockerfile {
+                    Some(df) if repo.path.join(&df).exists() => df,
+                    Some(df) => {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Validation,
+                            format!("  {}: Dockerfile not found: {df}", repo.dir_name),
+                        );
+                        continue;
+                    }
+                    None => match detect_dockerfile(&repo.path) {
+                        Some(df) => df.to_string(),
+                        None => continue,
+                    },
+                };
+                let Some(org) = config.org.clone() else {
+                    yield HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("  {}: no org configured, can't name its image", repo.dir_name),
+                    );
+                    continue;
+                };
+
+                let names = registry.clone().filter(|r| !r.is_empty()).unwrap_or_else(|| {
+                    if image.registries.is_empty() { config.forges.clone() } else { image.registries.clone() }
+                });
+                let registries = names
+                    .iter()
+                    .map(|n| {
+                        let reg = ContainerRegistry::from_name(n);
+                        let reg_org = config.org_for_forge(reg.token_forge_name()).unwrap_or(&org).to_string();
+                        (reg, reg_org)
+                    })
+                    .collect();
+
+                let mut tags = vec![repo.package_version.clone().unwrap_or_else(|| "latest".to_string())];
+                if also_latest && tags[0] != "latest" {
+                    tags.push("latest".to_string());
+                }
 
-                // ── Step 4: Move directory ──
-                if is_dry_run {
-                    yield HyperforgeEvent::RepoMove {
-                        repo_name: name.clone(),
-                        step: "directory".to_string(),
-                        success: true,
-                        message: format!("{}would move to {}", dry_prefix, target_repo_path.display()),
-                    };
-                    moved += 1;
-                } else {
-                    match tokio::fs::rename(&repo_path, &target_repo_path).await {
-                        Ok(()) => {
-                            yield HyperforgeEvent::RepoMove {
-                                repo_name: name.clone(),
-                                step: "directory".to_string(),
-                                success: true,
-                                message: format!("moved to {}", target_repo_path.display()),
-                            };
-                            moved += 1;
-                        }
-                        Err(e) => {
-                            yield HyperforgeEvent::RepoMove {
-                                repo_name: name.clone(),
-                                step: "directory".to_string(),
-                                success: false,
-                                message: format!("Failed to move directory: {e}"),
-                            };
-                            failed += 1;
-                        }
+                work.push(ImageTarget {
+                    dir_name: repo.dir_name.clone(),
+                    path: repo.path.clone(),
+                    dockerfile,
+                    name: image.name.unwrap_or_else(|| config.get_repo_name(&repo.path)),
+                    tags,
+                    registries,
+                });
+            }
+
+            if work.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: "No repos with a Dockerfile matched filter.".to_string(),
+                };
+                return;
+            }
+
+            let docker = match crate::docker::connect() {
+                Ok(d) => d,
+                Err(e) => {
+                    yield msg_error!(Msg::DockerUnavailable, error = e);
+                    return;
+                }
+            };
+            if !is_dry_run {
+                match crate::docker::check_state(&docker).await {
+                    crate::docker::DockerState::Available { version } => {
+                        yield HyperforgeEvent::Info { message: format!("Docker {version} connected") };
+                    }
+                    crate::docker::DockerState::NotRunning => {
+                        yield msg_error!(Msg::DockerNotRunning);
+                        return;
+                    }
+                    crate::docker::DockerState::NotInstalled => {
+                        yield msg_error!(Msg::DockerNotInstalled);
+                        return;
                     }
                 }
             }
 
-            // ── Save LocalForge state ──
-            if !is_dry_run {
-                // Collect all source orgs that were affected
-                let source_orgs: HashSet<String> = repo.iter()
-                    .filter_map(|name| discovered_map.get(name.as_str()).and_then(|d| d.org().map(std::string::ToString::to_string)))
-                    .collect();
+            let auth = match YamlAuthProvider::new() {
+                Ok(a) => a,
+                Err(e) => {
+                    yield HyperforgeEvent::error(HyperforgeError::Auth, format!("Failed to create auth provider: {e}"));
+                    return;
+                }
+            };
 
-                for src_org in &source_orgs {
-                    let forge = state.get_local_forge(src_org).await;
-                    if let Err(e) = forge.save_to_yaml().await {
-                        yield HyperforgeEvent::Error {
-                            message: format!("Failed to save source LocalForge for {src_org}: {e}"),
-                        };
+            yield HyperforgeEvent::Info {
+                message: format!("{prefix}Building images for {} repos...", work.len()),
+            };
+
+            let mut pushed = 0usize;
+            let mut failed = Vec::new();
+            for target in work {
+                // Build once under the version tag; other tags reuse that image
+                let local_tag = format!("{}:{}", target.name, target.tags[0]);
+                yield HyperforgeEvent::Info {
+                    message: format!("{prefix}  {}: building {local_tag} from {}", target.dir_name, target.dockerfile),
+                };
+                if !is_dry_run {
+                    if let Err(e) = crate::docker::build_image(&docker, &target.path, &target.dockerfile, &local_tag).await {
+                        yield HyperforgeEvent::error(
+                            HyperforgeError::Validation,
+                            format!("  {}: build failed: {e}", target.dir_name),
+                        );
+                        failed.push(target.dir_name.clone());
+                        continue;
                     }
                 }
-                let target_forge = state.get_local_forge(&target_org).await;
-                if let Err(e) = target_forge.save_to_yaml().await {
-                    yield HyperforgeEvent::Error {
-                        message: format!("Failed to save target LocalForge for {target_org}: {e}"),
-                    };
+
+                for (reg, org) in &target.registries {
+                    for tag in &target.tags {
+                        let image_ref = ImageRef::new(reg.clone(), org, &target.name, tag);
+                        let event = push_to_registry(&docker, &auth, &image_ref, &local_tag, is_dry_run).await;
+                        if let HyperforgeEvent::ImagePush { success, .. } = &event {
+                            if *success {
+                                pushed += 1;
+                            } else {
+                                failed.push(image_ref.full_name());
+                            }
+                        }
+                        yield event;
+                    }
                 }
             }
 
-            // ── Summary ──
-            yield HyperforgeEvent::Info {
-                message: format!(
-                    "{}Move complete: {} moved, {} failed (of {} requested)",
-                    dry_prefix, moved, failed, repo.len(),
-                ),
-            };
+            if failed.is_empty() {
+                yield HyperforgeEvent::Info {
+                    message: format!("{prefix}Images: {pushed} pushed"),
+                };
+            } else {
+                yield HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("Images: {pushed} pushed, {} failed: {}", failed.len(), failed.join(", ")),
+                );
+            }
+        })
+    }
+}
 
-            yield HyperforgeEvent::WorkspaceSummary {
-                total_repos: repo.len(),
-                configured_repos: moved,
-                unconfigured_repos: failed,
-                clean_repos: None,
-                dirty_repos: None,
-                wrong_branch_repos: None,
-                push_success: None,
-                push_failed: None,
-                validation_passed: None,
-            };
+// ── Diff enrichment ──────────────────────────────────────────────────────
+
+/// Narrow `filter` to a workspace profile's repos: the one named, else the
+/// workspace's active profile. With `activate`, a named profile is recorded
+/// as the active one so later commands stay sparse.
+fn profile_filter(
+    workspace_root: &std::path::Path,
+    filter: RepoFilter,
+    profile: Option<&str>,
+    activate: bool,
+) -> Result<RepoFilter, String> {
+    let mut config = crate::config::WorkspaceConfig::load(workspace_root)?;
+    if let (Some(name), true) = (profile, activate) {
+        config.profile(name)?;
+        if config.profile.as_deref() != Some(name) {
+            config.profile = Some(name.to_string());
+            config.save(workspace_root)?;
         }
     }
+    let globs = match profile {
+        Some(name) => Some(config.profile(name)?),
+        None => config.active_profile()?,
+    };
+    Ok(match globs {
+        Some(globs) => filter.with_profile(globs),
+        None => filter,
+    })
 }
 
-// ── Diff enrichment ──────────────────────────────────────────────────────
+/// `[pins]` from workspace.toml. Pinned repos sit on a detached HEAD after
+/// `thaw`, so push and pull skip them instead of reporting them blocked.
+fn pinned_repos(workspace_root: &Path) -> Result<std::collections::BTreeMap<String, String>, String> {
+    crate::config::WorkspaceConfig::load(workspace_root)
+        .map(|c| c.pins)
+        .map_err(|e| format!("{e} — treating no repos as pinned"))
+}
+
+fn pinned_skip_event(repo_name: &str, pin: &str) -> HyperforgeEvent {
+    HyperforgeEvent::Info {
+        message: format!("  Skipping {repo_name} (pinned at {pin})"),
+    }
+}
+
+fn pin_event(repo_name: &str, report: PinReport) -> HyperforgeEvent {
+    HyperforgeEvent::RepoPin {
+        repo_name: repo_name.to_string(),
+        pin: report.pin,
+        commit: report.commit,
+        head: report.head,
+        status: report.status.as_str().to_string(),
+    }
+}
 
 /// Enrich a `SyncDiff` with git ahead/behind info from local repos.
 ///
 /// For each repo in the diff, looks up the `LocalForge` record to find the
 /// local clone path, then runs `git ahead_behind` against the appropriate
 /// remote. If commits are ahead/behind, adds details like "3 commits ahead".
+/// Rename suggestions for repos whose `LocalForge` and forge names differ
+/// only in case. The diff already treats them as one repo; the suggestion
+/// only keeps the spellings from drifting further.
+fn case_mismatch_events(diff: &crate::services::SyncDiff, forge_name: &str) -> Vec<HyperforgeEvent> {
+    diff.case_mismatches()
+        .into_iter()
+        .filter_map(|op| {
+            let remote_name = op.target_name.clone()?;
+            Some(HyperforgeEvent::NameCaseMismatch {
+                org: diff.org.clone(),
+                forge: forge_name.to_string(),
+                suggestion: format!(
+                    "repo rename --org {org} --old_name {remote_name} --new_name {local} --forges {forge_name}, \
+                     or adopt the forge's spelling with repo_name = \"{remote_name}\" in .hyperforge/config.toml",
+                    org = diff.org,
+                    local = op.repo.name,
+                ),
+                local_name: op.repo.name.clone(),
+                remote_name,
+            })
+        })
+        .collect()
+}
+
 /// Repos that were `InSync` on metadata but have unpushed commits are upgraded
 /// to Update.
 fn enrich_diff_with_git_state(
     diff: &mut crate::services::SyncDiff,
     forge_name: &str,
     state: &HyperforgeState,
-    records: &[crate::types::repo::RepoRecord],
+    records: &[std::sync::Arc<crate::types::repo::RepoRecord>],
 ) {
     use crate::services::SyncOp as SOp;
 
-    let record_map: std::collections::HashMap<&str, &crate::types::repo::RepoRecord> = records
+    let record_map: std::collections::HashMap<String, &crate::types::repo::RepoRecord> = records
         .iter()
-        .map(|r| (r.name.as_str(), r))
+        .map(|r| (name_key(&r.name), &**r))
         .collect();
     let _ = state;
 
     for repo_op in &mut diff.ops {
-        let record = match record_map.get(repo_op.repo.name.as_str()) {
+        let record = match record_map.get(&name_key(&repo_op.repo.name)) {
             Some(r) => r,
             None => continue,
         };
@@ -2219,9 +5440,14 @@ fn enrich_diff_with_git_state(
             _ => continue,
         };
 
-        // Determine remote name for this forge (same logic as HyperforgeConfig::remote_for_forge)
-        let remote_name = record.forge_config.get(forge_name)
-            .and_then(|fc| fc.remote.clone())
+        // Determine remote name for this forge: the repo's own config knows
+        // which entry is this org's account; otherwise the same logic as
+        // HyperforgeConfig::remote_for_forge on the record
+        let from_config = HyperforgeConfig::load(local_path).ok().and_then(|config| {
+            config.entry_for(forge_name, &diff.org).map(|entry| config.remote_for_forge(entry))
+        });
+        let remote_name = from_config
+            .or_else(|| record.forge_config.get(forge_name).and_then(|fc| fc.remote.clone()))
             .unwrap_or_else(|| {
                 if record.forges.first().map(std::string::String::as_str) == Some(forge_name) {
                     "origin".to_string()
@@ -2269,14 +5495,14 @@ async fn run_workspace_preflight(
 async fn run_sync_preflight(
     pairs: &[(String, String)],
 ) -> Vec<HyperforgeEvent> {
-    use std::collections::{HashMap, HashSet};
+    use std::collections::{BTreeMap, BTreeSet};
 
     if pairs.is_empty() {
         return Vec::new();
     }
 
     // Group forges by org
-    let mut org_forges: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut org_forges: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
     for (org, forge) in pairs {
         org_forges
             .entry(org.clone())
@@ -2287,9 +5513,10 @@ async fn run_sync_preflight(
     let auth = match YamlAuthProvider::new() {
         Ok(a) => std::sync::Arc::new(a),
         Err(e) => {
-            return vec![HyperforgeEvent::Error {
-                message: format!("Pre-flight: failed to create auth provider: {e}"),
-            }];
+            return vec![HyperforgeEvent::error(
+                HyperforgeError::Auth,
+                format!("Pre-flight: failed to create auth provider: {e}"),
+            )];
         }
     };
 
@@ -2304,6 +5531,251 @@ async fn run_sync_preflight(
     all_errors
 }
 
+/// A configured repo on one of its declared forges
+struct RepoForgeTarget {
+    dir_name: String,
+    /// Org on this forge (the entry's account, if it names one)
+    org: String,
+    /// The `forges` entry, e.g. `github` or `github:acme`
+    forge: String,
+    /// Repo name on the forge
+    name: String,
+    /// Self-hosted instance the forge lives on, if any
+    host: Option<String>,
+}
+
+/// Pair each org/forge with the self-hosted instance its org config names
+fn with_forge_hosts(pairs: Vec<(String, String)>, config_dir: &Path) -> Vec<(String, String, Option<String>)> {
+    pairs
+        .into_iter()
+        .map(|(org, forge)| {
+            let host = forge_host(config_dir, &org, &forge, None);
+            (org, forge, host)
+        })
+        .collect()
+}
+
+/// Expand filtered, configured repos into one target per declared forge.
+fn repo_forge_targets(
+    ctx: &crate::commands::workspace::WorkspaceContext,
+    filter: &RepoFilter,
+    config_dir: &Path,
+) -> Vec<RepoForgeTarget> {
+    ctx.repos
+        .iter()
+        .filter(|r| filter.matches(&r.dir_name))
+        .filter_map(|r| {
+            let config = r.config.as_ref()?;
+            config.org.as_ref()?;
+            let name = config.get_repo_name(&r.path);
+            Some(config.forges.iter().filter_map(move |f| {
+                let org = config.org_for_forge(f)?;
+                Some(RepoForgeTarget {
+                    dir_name: r.dir_name.clone(),
+                    host: forge_host(config_dir, org, f, config.forge_host(f)),
+                    org: org.to_string(),
+                    forge: f.clone(),
+                    name: name.clone(),
+                })
+            }))
+        })
+        .flatten()
+        .collect()
+}
+
+/// One repo's image for `workspace images`
+struct ImageTarget {
+    dir_name: String,
+    path: PathBuf,
+    dockerfile: String,
+    name: String,
+    /// Version tag first; the image is built under it
+    tags: Vec<String>,
+    /// Registry and the org/namespace the image goes under there
+    registries: Vec<(crate::types::registry::ContainerRegistry, String)>,
+}
+
+/// One repo a managed change is proposed to, on its origin forge
+#[derive(Clone)]
+struct ChangeTarget {
+    dir_name: String,
+    path: PathBuf,
+    forge: String,
+    org: String,
+    name: String,
+    remote: String,
+    base: String,
+    /// Self-hosted instance the forge lives on, if any
+    host: Option<String>,
+}
+
+/// What the fork flow did for one repo
+enum Contribution {
+    /// The user can push to the repo itself; no fork needed
+    CanPush,
+    /// The forge reports no permissions or can't fork
+    Unsupported,
+    WouldFork,
+    /// PR opened against upstream, with its URL
+    Opened(String),
+}
+
+/// How many times to check whether a new fork exists before giving up
+const FORK_READY_ATTEMPTS: u64 = 15;
+
+/// Seconds between fork readiness checks
+const FORK_READY_INTERVAL_SECS: u64 = 2;
+
+/// Fork `target` if the user can't push to it, wait for the fork to be
+/// ready, rewire `origin`/`upstream`, push `head` to the fork and open a PR
+/// against upstream, titled after `head`'s tip commit unless `title` is given.
+async fn contribute_via_fork(
+    target: &ChangeTarget,
+    head: &str,
+    title: Option<String>,
+    body: String,
+    dry_run: bool,
+) -> Result<Contribution, String> {
+    let adapter = make_adapter(&target.forge, &target.org, None, target.host.as_deref())?;
+    match adapter.can_push(&target.org, &target.name).await.map_err(|e| e.to_string())? {
+        Some(true) => return Ok(Contribution::CanPush),
+        None => return Ok(Contribution::Unsupported),
+        Some(false) if dry_run => return Ok(Contribution::WouldFork),
+        Some(false) => {}
+    }
+
+    let Some(fork_owner) = adapter
+        .create_fork(&target.org, &target.name)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(Contribution::Unsupported);
+    };
+
+    // Forking is asynchronous on the forges; pushing too early gets a 404
+    let mut attempts = 0;
+    while !adapter
+        .repo_exists(&fork_owner, &target.name)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        attempts += 1;
+        if attempts >= FORK_READY_ATTEMPTS {
+            return Err(format!(
+                "fork {fork_owner}/{} still not ready after {}s",
+                target.name,
+                FORK_READY_ATTEMPTS * FORK_READY_INTERVAL_SECS
+            ));
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(FORK_READY_INTERVAL_SECS)).await;
+    }
+
+    // Remote wiring and push are blocking git
+    let path = target.path.clone();
+    let fork_url = crate::git::build_remote_url_on(&target.forge, target.host.as_deref(), &fork_owner, &target.name);
+    let upstream_url = crate::git::build_remote_url_on(&target.forge, target.host.as_deref(), &target.org, &target.name);
+    let branch = head.to_string();
+    let subject = tokio::task::spawn_blocking(move || {
+        Git::wire_fork_remotes(&path, &fork_url, &upstream_url)
+            .map_err(|e| format!("failed to wire remotes: {e}"))?;
+        Git::push_set_upstream(&path, "origin", &branch)
+            .map_err(|e| format!("git push origin {branch} failed: {e}"))?;
+        Ok::<_, String>(Git::commit_subject(&path, &branch).unwrap_or_default())
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let pr = NewPullRequest {
+        title: title.unwrap_or(subject),
+        body,
+        head: head.to_string(),
+        head_owner: Some(fork_owner),
+        base: target.base.clone(),
+        labels: Vec::new(),
+    };
+    let url = adapter
+        .create_pull_request(&target.org, &target.name, &pr)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("{} doesn't support pull requests", target.forge))?;
+    Ok(Contribution::Opened(url))
+}
+
+/// An update-bot PR with its CI state and what triage did with it
+struct TriagedPr {
+    pr: crate::adapters::PullRequest,
+    bot: crate::config::workspace::UpdateBot,
+    checks: crate::adapters::CheckState,
+    action: Option<String>,
+}
+
+/// Fetch one target's open update-bot PRs, then approve/merge the passing
+/// ones as requested. Returns `None` when the forge doesn't expose PRs.
+async fn triage_update_prs(
+    target: &RepoForgeTarget,
+    approve: bool,
+    merge: bool,
+    dry_run: bool,
+) -> Result<Option<Vec<TriagedPr>>, String> {
+    let adapter = make_adapter(&target.forge, &target.org, None, target.host.as_deref())?;
+    let Some(prs) = adapter
+        .list_pull_requests(&target.org, &target.name)
+        .await
+        .map_err(|e| e.to_string())?
+    else {
+        return Ok(None);
+    };
+
+    let mut triaged = Vec::new();
+    for pr in prs {
+        let Some(bot) = crate::services::updates::detect_bot(&pr) else {
+            continue;
+        };
+        let checks = adapter
+            .check_state(&target.org, &target.name, &pr.head_sha)
+            .await
+            .map_err(|e| e.to_string())?
+            .unwrap_or(crate::adapters::CheckState::Unknown);
+
+        let action = if checks != crate::adapters::CheckState::Success || !(approve || merge) {
+            None
+        } else if dry_run {
+            Some(if merge { "would_merge" } else { "would_approve" }.to_string())
+        } else {
+            Some(match apply_triage(adapter.as_ref(), target, pr.number, approve, merge).await {
+                Ok(a) => a.to_string(),
+                Err(e) => format!("failed: {e}"),
+            })
+        };
+        triaged.push(TriagedPr { pr, bot, checks, action });
+    }
+    Ok(Some(triaged))
+}
+
+/// Approve and/or merge one passing update PR, stopping at the first failure
+async fn apply_triage(
+    adapter: &dyn ForgePort,
+    target: &RepoForgeTarget,
+    number: u64,
+    approve: bool,
+    merge: bool,
+) -> Result<&'static str, String> {
+    if approve {
+        adapter
+            .approve_pull_request(&target.org, &target.name, number)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    if merge {
+        adapter
+            .merge_pull_request(&target.org, &target.name, number)
+            .await
+            .map_err(|e| e.to_string())?;
+        return Ok("merged");
+    }
+    Ok("approved")
+}
+
 // ── Sync phase helpers (private) ──────────────────────────────────────────
 
 /// Phase 2: Initialize unconfigured repos.
@@ -2311,147 +5783,426 @@ fn sync_init_unconfigured(
     unconfigured_repos: &[PathBuf],
     inferred_org: &Option<String>,
     inferred_forges: &[String],
+    org_config: &crate::config::OrgConfig,
     is_dry_run: bool,
     dry_prefix: &str,
 ) -> (Vec<HyperforgeEvent>, usize) {
     let mut events = Vec::new();
     let mut inits_performed = 0usize;
 
-    for repo_path in unconfigured_repos {
-        let dir_name = repo_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("?");
+    for repo_path in unconfigured_repos {
+        let dir_name = repo_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("?");
+
+        let mut opts = InitOptions::new(inferred_forges.to_vec()).with_identity(org_config.identity.clone());
+        for (forge, fc) in &org_config.forge_config {
+            if let Some(host) = &fc.host {
+                opts = opts.with_forge_host(forge, host);
+            }
+        }
+        if let Some(ref o) = inferred_org {
+            opts = opts.with_org(o.as_str());
+        }
+        if is_dry_run {
+            opts = opts.dry_run();
+        }
+
+        match init(repo_path, opts) {
+            Ok(_) => {
+                inits_performed += 1;
+                events.push(HyperforgeEvent::Info {
+                    message: format!("  {dry_prefix}Initialized {dir_name}"),
+                });
+            }
+            Err(e) => {
+                events.push(HyperforgeEvent::error(
+                    HyperforgeError::Git,
+                    format!("  Failed to init {dir_name}: {e}"),
+                ));
+            }
+        }
+    }
+
+    (events, inits_performed)
+}
+
+/// Phase 4: Register configured repos in `LocalForge`, merging config-first fields.
+async fn sync_register_repos(
+    repos: &[DiscoveredRepo],
+    orgs: &[String],
+    state: &HyperforgeState,
+    is_reflect: bool,
+    is_dry_run: bool,
+    dry_prefix: &str,
+) -> (Vec<HyperforgeEvent>, usize, usize, usize) {
+    let mut events = Vec::new();
+    let mut registered = 0usize;
+    let mut already_registered = 0usize;
+    let mut unstaged = 0usize;
+
+    for discovered in repos {
+        if discovered.org().is_none() {
+            continue;
+        }
+        // Account entries (`github:acme`) register the repo in that org's
+        // LocalForge too, each record listing only its own org's forges
+        for repo_org in discovered.orgs() {
+            let Some(repo) = repo_for_org(discovered, repo_org) else { continue };
+            let repo_org = repo_org.to_string();
+            let org_config = discovered.config.clone().map(|mut c| {
+                c.forges = c.forges_for_org(&repo_org);
+                c
+            });
+
+            let local = state.get_local_forge(&repo_org).await;
+
+            match local.repo_exists(&repo_org, &repo.name).await {
+                Ok(true) => {
+                    if is_reflect {
+                        match local.get_repo(&repo_org, &repo.name).await {
+                            Ok(existing) if existing.staged_for_deletion => {
+                                let mut updated = existing.clone();
+                                updated.staged_for_deletion = false;
+                                if let Err(e) = local.update_repo(&repo_org, &updated).await {
+                                    events.push(HyperforgeEvent::error(
+                                        HyperforgeError::Config,
+                                        format!("  Failed to unstage {}: {}", repo.name, e),
+                                    ));
+                                } else {
+                                    unstaged += 1;
+                                    events.push(HyperforgeEvent::Info {
+                                        message: format!("  {}Unstaged {} (found locally)", dry_prefix, repo.name),
+                                    });
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    already_registered += 1;
+                    if let Ok(mut record) = local.get_record(&repo.name) {
+                        record.managed = true;
+                        record.local_path = Some(discovered.path.clone());
+                        if let Some(ref config) = org_config {
+                            record.merge_from_config(config);
+                        }
+                        let _ = local.update_record(&record);
+                    }
+                    continue;
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    events.push(HyperforgeEvent::error(
+                        HyperforgeError::Config,
+                        format!("  Failed to check {}: {}", repo.name, e),
+                    ));
+                    continue;
+                }
+            }
+
+            // Always populate in-memory state (even in dry-run) so Phase 6 diff is accurate
+            if let Err(e) = local.create_repo(&repo_org, &repo).await {
+                events.push(HyperforgeEvent::error(
+                    HyperforgeError::Config,
+                    format!("  Failed to register {}: {}", repo.name, e),
+                ));
+                continue;
+            }
+
+            if let Ok(mut record) = local.get_record(&repo.name) {
+                record.managed = true;
+                record.local_path = Some(discovered.path.clone());
+                if let Some(ref config) = org_config {
+                    record.merge_from_config(config);
+                }
+                let _ = local.update_record(&record);
+            }
+
+            registered += 1;
+            let account = if discovered.org() == Some(repo_org.as_str()) { String::new() } else { format!(" ({repo_org})") };
+            events.push(HyperforgeEvent::Info {
+                message: format!("  {}Registered {}{}", dry_prefix, repo.name, account),
+            });
+        }
+    }
+
+    // Persist to disk only on real runs
+    if !is_dry_run && (registered > 0 || unstaged > 0) {
+        for org_name in orgs {
+            let local = state.get_local_forge(org_name).await;
+            if let Err(e) = local.save_to_yaml().await {
+                events.push(HyperforgeEvent::error(
+                    Msg::LocalForgeSaveFailed.code(),
+                    format!("  {}", msg!(Msg::LocalForgeSaveFailed, org = org_name, error = e)),
+                ));
+            }
+        }
+    }
+
+    (events, registered, already_registered, unstaged)
+}
+
+/// Phase 6.5: Look for configured repos on forges their config doesn't declare.
+///
+/// Lists each org once per undeclared forge and reports name matches. Forges
+/// we can't list (no token, org absent) are skipped quietly — the probe is a
+/// best-effort warning, not a gate.
+async fn sync_probe_undeclared_forges(
+    repos: &[crate::commands::workspace::DiscoveredRepo],
+    config_dir: &Path,
+) -> Vec<HyperforgeEvent> {
+    use std::collections::{BTreeMap, HashSet};
+
+    let configs: Vec<&HyperforgeConfig> = repos.iter().filter_map(|r| r.config.as_ref()).collect();
+    let undeclared = undeclared_forges(&configs);
 
-        let mut opts = InitOptions::new(inferred_forges.to_vec());
-        if let Some(ref o) = inferred_org {
-            opts = opts.with_org(o.as_str());
-        }
-        if is_dry_run {
-            opts = opts.dry_run();
+    // (org, forge) → repos that don't declare that forge
+    let mut candidates: BTreeMap<(String, String), Vec<&crate::commands::workspace::DiscoveredRepo>> = BTreeMap::new();
+    for (repo, forges) in repos.iter().filter(|r| r.config.is_some()).zip(undeclared) {
+        let Some(org) = repo.config.as_ref().and_then(|c| c.org.clone()) else { continue };
+        for forge in forges {
+            candidates.entry((org.clone(), forge)).or_default().push(repo);
         }
+    }
 
-        match init(repo_path, opts) {
-            Ok(_) => {
-                inits_performed += 1;
-                events.push(HyperforgeEvent::Info {
-                    message: format!("  {dry_prefix}Initialized {dir_name}"),
-                });
-            }
-            Err(e) => {
-                events.push(HyperforgeEvent::Error {
-                    message: format!("  Failed to init {dir_name}: {e}"),
+    let pairs: Vec<(String, String)> = candidates.keys().cloned().collect();
+    let results = run_batch(with_forge_hosts(pairs, config_dir), Scheduler::global().batch(None), |(org, forge, host)| async move {
+        let listed = match make_adapter(&forge, &org, None, host.as_deref()) {
+            Ok(adapter) => adapter.list_repos(&org).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e),
+        };
+        let names: Option<HashSet<String>> = listed.ok().map(|repos| repos.into_iter().map(|r| name_key(&r.name)).collect());
+        (org, forge, names)
+    }).await;
+
+    let mut events = Vec::new();
+    let mut found = 0usize;
+    let mut unreachable = 0usize;
+    for result in results {
+        let Ok((org, forge, names)) = result else { continue };
+        let Some(names) = names else {
+            unreachable += 1;
+            continue;
+        };
+        let Some(candidates) = candidates.get(&(org.clone(), forge.clone())) else { continue };
+        for repo in candidates {
+            let Some(config) = &repo.config else { continue };
+            let name = config.get_repo_name(&repo.path);
+            if names.contains(&name_key(&name)) {
+                found += 1;
+                events.push(HyperforgeEvent::UndeclaredForgeRepo {
+                    org: org.clone(),
+                    repo_name: name,
+                    forge: forge.clone(),
+                    declared_forges: config.forges.clone(),
                 });
             }
         }
     }
 
-    (events, inits_performed)
+    events.push(HyperforgeEvent::Info {
+        message: format!(
+            "  {found} repos found on undeclared forges{}",
+            if unreachable > 0 { format!(" ({unreachable} org/forge pairs could not be listed)") } else { String::new() },
+        ),
+    });
+    events
 }
 
-/// Phase 4: Register configured repos in `LocalForge`, merging config-first fields.
-async fn sync_register_repos(
-    repos: &[DiscoveredRepo],
-    orgs: &[String],
+/// For each config, the forges some config in the workspace declares (self-
+/// hosted Gitea hosts included) that this one doesn't, in name order
+fn undeclared_forges(configs: &[&HyperforgeConfig]) -> Vec<Vec<String>> {
+    let declared = |config: &HyperforgeConfig| -> std::collections::BTreeSet<String> {
+        config
+            .forges
+            .iter()
+            .map(|entry| HyperforgeConfig::split_account(entry).0.to_lowercase())
+            .collect()
+    };
+    let workspace: std::collections::BTreeSet<String> = configs.iter().flat_map(|c| declared(c)).collect();
+    configs
+        .iter()
+        .map(|config| workspace.difference(&declared(config)).cloned().collect())
+        .collect()
+}
+
+/// The (org, forge) accounts a repo's forge entries resolve to
+fn forge_accounts(repo: &crate::commands::workspace::DiscoveredRepo) -> Vec<(String, String)> {
+    let Some(config) = &repo.config else { return Vec::new() };
+    config
+        .forges
+        .iter()
+        .filter_map(|f| {
+            let org = config.org_for_forge(f)?;
+            Some((org.to_string(), HyperforgeConfig::split_account(f).0.to_string()))
+        })
+        .collect()
+}
+
+/// Configured repos whose org/repo is in neither `LocalForge` nor any of the
+/// forges their config declares.
+///
+/// Only repos missing from `LocalForge` are looked up on the forges, each
+/// org/forge pair listed once. A repo is reported only when every declared
+/// forge could be listed; the rest are counted as unverified.
+async fn discover_orphans(
+    repos: &[&crate::commands::workspace::DiscoveredRepo],
     state: &HyperforgeState,
-    is_reflect: bool,
-    is_dry_run: bool,
-    dry_prefix: &str,
-) -> (Vec<HyperforgeEvent>, usize, usize, usize) {
-    let mut events = Vec::new();
-    let mut registered = 0usize;
-    let mut already_registered = 0usize;
-    let mut unstaged = 0usize;
+    workspace: &str,
+) -> Vec<HyperforgeEvent> {
+    use std::collections::BTreeMap;
+
+    // (repo, org, name) for configured repos LocalForge doesn't know
+    let mut missing = Vec::new();
+    for repo in repos {
+        let Some(config) = &repo.config else { continue };
+        let Some(org) = &config.org else { continue };
+        let name = config.get_repo_name(&repo.path);
+        if state.get_local_forge(org).await.get_record(&name).is_err() {
+            missing.push((*repo, org.clone(), name));
+        }
+    }
 
-    for discovered in repos {
-        let repo = match repo_from_config(discovered) {
-            Some(r) => r,
-            None => continue,
-        };
-        let repo_org = match discovered.org() {
-            Some(o) => o.to_string(),
-            None => continue,
+    let mut pairs: BTreeMap<(String, String), Option<HashSet<String>>> = BTreeMap::new();
+    for (repo, _, _) in &missing {
+        pairs.extend(forge_accounts(repo).into_iter().map(|pair| (pair, None)));
+    }
+    let results = run_batch(with_forge_hosts(pairs.keys().cloned().collect(), &state.config_dir), Scheduler::global().batch(None), |(org, forge, host)| async move {
+        let listed = match make_adapter(&forge, &org, None, host.as_deref()) {
+            Ok(adapter) => adapter.list_repos(&org).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e),
         };
+        let names: Option<HashSet<String>> = listed.ok().map(|repos| repos.into_iter().map(|r| name_key(&r.name)).collect());
+        (org, forge, names)
+    }).await;
+    for (org, forge, names) in results.into_iter().flatten() {
+        pairs.insert((org, forge), names);
+    }
 
-        let local = state.get_local_forge(&repo_org).await;
-
-        match local.repo_exists(&repo_org, &repo.name).await {
-            Ok(true) => {
-                if is_reflect {
-                    match local.get_repo(&repo_org, &repo.name).await {
-                        Ok(existing) if existing.staged_for_deletion => {
-                            let mut updated = existing.clone();
-                            updated.staged_for_deletion = false;
-                            if let Err(e) = local.update_repo(&repo_org, &updated).await {
-                                events.push(HyperforgeEvent::Error {
-                                    message: format!("  Failed to unstage {}: {}", repo.name, e),
-                                });
-                            } else {
-                                unstaged += 1;
-                                events.push(HyperforgeEvent::Info {
-                                    message: format!("  {}Unstaged {} (found locally)", dry_prefix, repo.name),
-                                });
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-                already_registered += 1;
-                if let Ok(mut record) = local.get_record(&repo.name) {
-                    record.managed = true;
-                    record.local_path = Some(discovered.path.clone());
-                    if let Some(ref config) = discovered.config {
-                        record.merge_from_config(config);
-                    }
-                    let _ = local.update_record(&record);
-                }
-                continue;
-            }
-            Ok(false) => {}
-            Err(e) => {
-                events.push(HyperforgeEvent::Error {
-                    message: format!("  Failed to check {}: {}", repo.name, e),
-                });
-                continue;
+    let mut events = Vec::new();
+    let mut unverified = 0usize;
+    for (repo, org, name) in missing {
+        let forges = repo.config.as_ref().map(|c| c.forges.clone()).unwrap_or_default();
+        let mut on_forge = false;
+        let mut listed_all = true;
+        for pair in forge_accounts(repo) {
+            match pairs.get(&pair) {
+                Some(Some(names)) => on_forge |= names.contains(&name_key(&name)),
+                _ => listed_all = false,
             }
         }
+        if on_forge {
+            continue;
+        }
+        if !listed_all {
+            unverified += 1;
+            continue;
+        }
+        events.push(HyperforgeEvent::OrphanedRepo {
+            repo_name: repo.dir_name.clone(),
+            path: repo.path.display().to_string(),
+            org: org.clone(),
+            name: name.clone(),
+            forges,
+            suggestions: vec![
+                format!("re-create the remote: workspace sync --path {workspace} --include {}", repo.dir_name),
+                format!("re-init against the right org: workspace init --path {workspace} --include {} --org <org> --force", repo.dir_name),
+                format!("retire it: remove {}/.hyperforge/config.toml", repo.path.display()),
+            ],
+        });
+    }
+
+    events.push(HyperforgeEvent::Info {
+        message: format!(
+            "Orphans: {} of {} configured repos{}",
+            events.len(),
+            repos.iter().filter(|r| r.config.as_ref().is_some_and(|c| c.org.is_some())).count(),
+            if unverified > 0 { format!(" ({unverified} not in LocalForge could not be checked on every forge)") } else { String::new() },
+        ),
+    });
+    events
+}
 
-        // Always populate in-memory state (even in dry-run) so Phase 6 diff is accurate
-        if let Err(e) = local.create_repo(&repo_org, &repo).await {
-            events.push(HyperforgeEvent::Error {
-                message: format!("  Failed to register {}: {}", repo.name, e),
+/// Write `checkpoint` unless this is a dry run, returning the error event if
+/// it can't be written
+fn save_checkpoint(checkpoint: &SyncCheckpoint, root: &Path, is_dry_run: bool) -> Option<HyperforgeEvent> {
+    if is_dry_run {
+        return None;
+    }
+    checkpoint.save(root).err().map(|e| HyperforgeEvent::error(HyperforgeError::Internal, e))
+}
+
+/// The etag to list `org_name` on `forge_name` with: the stored one if it was
+/// issued under `scope`, else none (noting a reset in `events`)
+fn usable_etag(
+    local: &LocalForge,
+    org_name: &str,
+    forge_name: &str,
+    forge: Option<&Forge>,
+    scope: &EtagScope,
+    events: &mut Vec<HyperforgeEvent>,
+) -> Option<String> {
+    match local.etag_for(forge?, scope) {
+        Ok(StoredEtag::Current(etag)) => Some(etag),
+        Ok(StoredEtag::Reset) => {
+            events.push(HyperforgeEvent::Info {
+                message: format!("  {org_name}/{forge_name}: token or owner type changed since the last listing — ETag reset"),
             });
-            continue;
+            None
         }
+        Ok(StoredEtag::Missing) | Err(_) => None,
+    }
+}
 
-        if let Ok(mut record) = local.get_record(&repo.name) {
-            record.managed = true;
-            record.local_path = Some(discovered.path.clone());
-            if let Some(ref config) = discovered.config {
-                record.merge_from_config(config);
+/// Drop `LocalForge` sync state for forges the workspace no longer declares
+/// for an org, so a forge re-added later starts from a full listing
+async fn prune_forge_states(
+    pairs: &[(String, String)],
+    orgs: &[String],
+    state: &HyperforgeState,
+    is_dry_run: bool,
+    dry_prefix: &str,
+    events: &mut Vec<HyperforgeEvent>,
+) {
+    for org_name in orgs {
+        let configured: Vec<Forge> = pairs.iter()
+            .filter(|(org, _)| org == org_name)
+            .filter_map(|(_, forge)| HyperforgeConfig::parse_forge(forge))
+            .collect();
+        if configured.is_empty() {
+            continue;
+        }
+        let local = state.get_local_forge(org_name).await;
+        let stale: Vec<String> = if is_dry_run {
+            let Ok(states) = local.forge_states() else { continue };
+            let mut stale: Vec<String> = states.keys()
+                .filter(|f| !configured.contains(f))
+                .map(ToString::to_string)
+                .collect();
+            stale.sort();
+            stale
+        } else {
+            match local.prune_forge_states(&configured) {
+                Ok(pruned) => pruned.iter().map(ToString::to_string).collect(),
+                Err(_) => continue,
             }
-            let _ = local.update_record(&record);
+        };
+        if stale.is_empty() {
+            continue;
         }
-
-        registered += 1;
         events.push(HyperforgeEvent::Info {
-            message: format!("  {}Registered {}", dry_prefix, repo.name),
+            message: format!("  {dry_prefix}{org_name}: dropped sync state for unconfigured forges: {}", stale.join(", ")),
         });
-    }
-
-    // Persist to disk only on real runs
-    if !is_dry_run && (registered > 0 || unstaged > 0) {
-        for org_name in orgs {
-            let local = state.get_local_forge(org_name).await;
+        if !is_dry_run {
             if let Err(e) = local.save_to_yaml().await {
-                events.push(HyperforgeEvent::Error {
-                    message: format!("  Failed to save LocalForge for {org_name}: {e}"),
-                });
+                events.push(HyperforgeEvent::error(
+                    Msg::LocalForgeSaveFailed.code(),
+                    format!("  {}", msg!(Msg::LocalForgeSaveFailed, org = org_name, error = e)),
+                ));
             }
         }
     }
-
-    (events, registered, already_registered, unstaged)
 }
 
 /// Phase 5: Import remote-only repos into `LocalForge` (ETag-based) + report unmanaged.
@@ -2465,33 +6216,32 @@ async fn sync_import_remote(
     let mut events = Vec::new();
     let mut imported = 0usize;
 
+    prune_forge_states(pairs, orgs, state, is_dry_run, dry_prefix, &mut events).await;
+
     for (org_name, forge_name) in pairs {
         let local = state.get_local_forge(org_name).await;
         let ot = local.owner_type();
+        let scope = etag_scope(forge_name, org_name, ot.clone()).await;
 
-        let adapter = match make_adapter(forge_name, org_name, ot) {
+        let host = forge_host(&state.config_dir, org_name, forge_name, None);
+        let adapter = match make_adapter(forge_name, org_name, ot, host.as_deref()) {
             Ok(a) => a,
             Err(e) => {
-                events.push(HyperforgeEvent::Error { message: e });
+                events.push(HyperforgeEvent::error(HyperforgeError::ForgeApi, e));
                 continue;
             }
         };
 
         let forge_enum = HyperforgeConfig::parse_forge(forge_name);
-        let stored_etag = if let Some(ref fe) = forge_enum {
-            local.forge_states().ok()
-                .and_then(|states| states.get(fe).map(|s| s.etag.clone()))
-                .flatten()
-        } else {
-            None
-        };
+        let stored_etag = usable_etag(&local, org_name, forge_name, forge_enum.as_ref(), &scope, &mut events);
 
         let list_result = match adapter.list_repos_incremental(org_name, stored_etag).await {
             Ok(lr) => lr,
             Err(e) => {
-                events.push(HyperforgeEvent::Error {
-                    message: format!("  Failed to list remote repos for {org_name}/{forge_name}: {e}"),
-                });
+                events.push(HyperforgeEvent::error(
+                    Msg::RemoteListFailed.code(),
+                    format!("  {}", msg!(Msg::RemoteListFailed, org = org_name, forge = forge_name, error = e)),
+                ));
                 continue;
             }
         };
@@ -2504,30 +6254,43 @@ async fn sync_import_remote(
                 let _ = local.set_forge_state(fe.clone(), ForgeSyncState {
                     last_synced: Utc::now(),
                     etag: list_result.etag.clone(),
+                    scope: scope.clone(),
                 });
             }
             continue;
         }
 
         let remote_repos = list_result.repos.as_deref().unwrap_or_default();
+        let mut permissions_changed = false;
 
         for remote_repo in remote_repos {
             match local.repo_exists(org_name, &remote_repo.name).await {
-                Ok(true) => continue,
+                Ok(true) => {
+                    // Keep read-only markers current so sync skips what the
+                    // token can't administer
+                    if let Some(ref fe) = forge_enum {
+                        permissions_changed |= local
+                            .set_read_only(&remote_repo.name, fe, remote_repo.read_only)
+                            .unwrap_or(false);
+                    }
+                    continue;
+                }
                 Ok(false) => {}
                 Err(e) => {
-                    events.push(HyperforgeEvent::Error {
-                        message: format!("  Failed to check {}: {}", remote_repo.name, e),
-                    });
+                    events.push(HyperforgeEvent::error(
+                        HyperforgeError::ForgeApi,
+                        format!("  Failed to check {}: {}", remote_repo.name, e),
+                    ));
                     continue;
                 }
             }
 
             // Always populate in-memory state (even in dry-run) so Phase 6 diff is accurate
             if let Err(e) = local.create_repo(org_name, remote_repo).await {
-                events.push(HyperforgeEvent::Error {
-                    message: format!("  Failed to import {}: {}", remote_repo.name, e),
-                });
+                events.push(HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("  Failed to import {}: {}", remote_repo.name, e),
+                ));
                 continue;
             }
 
@@ -2542,15 +6305,17 @@ async fn sync_import_remote(
                 let _ = local.set_forge_state(fe.clone(), ForgeSyncState {
                     last_synced: Utc::now(),
                     etag: list_result.etag.clone(),
+                    scope: scope.clone(),
                 });
             }
         }
 
-        if !is_dry_run && imported > 0 {
+        if !is_dry_run && (imported > 0 || permissions_changed) {
             if let Err(e) = local.save_to_yaml().await {
-                events.push(HyperforgeEvent::Error {
-                    message: format!("  Failed to save LocalForge for {org_name}: {e}"),
-                });
+                events.push(HyperforgeEvent::error(
+                    Msg::LocalForgeSaveFailed.code(),
+                    format!("  {}", msg!(Msg::LocalForgeSaveFailed, org = org_name, error = e)),
+                ));
             }
         }
     }
@@ -2562,7 +6327,7 @@ async fn sync_import_remote(
     // Phase 5.5: Report unmanaged repos
     for org_name in orgs {
         let local = state.get_local_forge(org_name).await;
-        if let Ok(records) = local.all_records() {
+        if let Ok(records) = local.records() {
             let unmanaged: Vec<_> = records.iter()
                 .filter(|r| !r.managed && !r.dismissed)
                 .collect();
@@ -2573,7 +6338,7 @@ async fn sync_import_remote(
                 });
                 for r in &unmanaged {
                     let forges: Vec<String> = r.present_on.iter()
-                        .map(|f| format!("{f:?}").to_lowercase())
+                        .map(ToString::to_string)
                         .collect();
                     events.push(HyperforgeEvent::Info {
                         message: format!("    {} [{}]", r.name, forges.join(", ")),
@@ -2586,6 +6351,340 @@ async fn sync_import_remote(
     events
 }
 
+/// Carry out a plan's changes: creates and updates through repo sync, deletes
+/// by privatizing, archives on their forge. `prefix` leads the closing
+/// summary line.
+async fn apply_plan_changes(state: &HyperforgeState, plan: &SyncPlan, prefix: &str) -> Vec<HyperforgeEvent> {
+    let mut events = Vec::new();
+
+    // Creates and updates: one repo sync per repo covers all its forges
+    let mut repos_to_sync: Vec<(String, String)> = Vec::new();
+    for change in plan.changes.iter().filter(|c| matches!(c.op, PlannedOp::Create | PlannedOp::Update)) {
+        let key = (change.org.clone(), change.repo.name.clone());
+        if !repos_to_sync.contains(&key) {
+            repos_to_sync.push(key);
+        }
+    }
+
+    let repo_hub = RepoHub::new(state.clone());
+    let sync_items: Vec<_> = repos_to_sync.into_iter()
+        .map(|(org, name)| (Clone::clone(&repo_hub), org, name))
+        .collect();
+    let sync_results = run_batch(sync_items, Scheduler::global().batch(None), |(hub, org, name): (RepoHub, String, String)| async move {
+        let stream = hub.sync(org, name, Some(false)).await;
+        tokio::pin!(stream);
+        stream.collect::<Vec<HyperforgeEvent>>().await
+    }).await;
+
+    let (mut synced, mut sync_errors) = (0usize, 0usize);
+    for result in sync_results {
+        match result {
+            Ok(repo_events) => {
+                if repo_events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. })) {
+                    sync_errors += 1;
+                } else {
+                    synced += 1;
+                }
+                events.extend(repo_events);
+            }
+            Err(e) => {
+                sync_errors += 1;
+                events.push(msg_error!(Msg::TaskJoinFailed, error = e));
+            }
+        }
+    }
+
+    let mut privatized = 0usize;
+    for change in plan.changes.iter().filter(|c| c.op == PlannedOp::Delete) {
+        let repo_events = privatize_remote(state, &change.org, &change.forge, &change.repo, false).await;
+        if repo_events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. })) {
+            sync_errors += 1;
+        } else {
+            privatized += 1;
+        }
+        events.extend(repo_events);
+    }
+
+    let mut archived = 0usize;
+    for change in plan.changes.iter().filter(|c| c.op == PlannedOp::Archive) {
+        let repo_events = archive_remote(state, &change.org, &change.forge, &change.repo, false).await;
+        if repo_events.iter().any(|e| matches!(e, HyperforgeEvent::Error { .. })) {
+            sync_errors += 1;
+        } else {
+            archived += 1;
+        }
+        events.extend(repo_events);
+    }
+
+    events.push(HyperforgeEvent::Info {
+        message: format!("{prefix}Plan applied: {synced} repos synced, {privatized} privatization ops, {archived} archive ops, {sync_errors} errors"),
+    });
+    events
+}
+
+/// Make `repo` private on `forge_name`, which is how sync answers a delete
+/// op. Protected and already-privatized repos are reported and left alone;
+/// a failure is reported as an error event.
+async fn privatize_remote(
+    state: &HyperforgeState,
+    org_name: &str,
+    forge_name: &str,
+    repo: &crate::types::Repo,
+    is_dry_run: bool,
+) -> Vec<HyperforgeEvent> {
+    let sync_op = |operation: &str| HyperforgeEvent::SyncOp {
+        repo_name: repo.name.clone(),
+        operation: operation.to_string(),
+        forge: forge_name.to_string(),
+        details: vec![],
+    };
+    let local = state.get_local_forge(org_name).await;
+    let record_info = local.get_record(&repo.name).ok();
+
+    if record_info.as_ref().is_some_and(|r| r.protected || r.archived) {
+        return vec![sync_op("skip_protected")];
+    }
+
+    let already_privatized = record_info.as_ref()
+        .and_then(|rec| {
+            HyperforgeConfig::parse_forge(forge_name)
+                .map(|fe| rec.privatized_on.contains(&fe))
+        })
+        .unwrap_or(false);
+    if already_privatized {
+        return vec![sync_op("already_privatized")];
+    }
+
+    let mut events = Vec::new();
+    if !is_dry_run {
+        let private_repo = crate::types::Repo::new(
+            &repo.name,
+            repo.origin.clone(),
+        ).with_visibility(crate::types::Visibility::Private);
+
+        match make_adapter(forge_name, org_name, local.owner_type(), forge_host(&state.config_dir, org_name, forge_name, None).as_deref()) {
+            Ok(adapter) => match adapter.update_repo(org_name, &private_repo).await {
+                // A simulated write changed nothing to record
+                Ok(()) if simulation::is_recording() => {}
+                Ok(()) => {
+                    if let Some(forge_enum) = HyperforgeConfig::parse_forge(forge_name) {
+                        if let Ok(mut rec) = local.get_record(&repo.name) {
+                            rec.privatized_on.insert(forge_enum);
+                            let _ = local.update_record(&rec);
+                            let _ = local.save_to_yaml().await;
+                        }
+                    }
+                }
+                Err(e) => events.push(HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("  Failed to privatize {} on {}: {}", repo.name, forge_name, e),
+                )),
+            },
+            Err(e) => events.push(HyperforgeEvent::error(HyperforgeError::ForgeApi, e)),
+        }
+    }
+    events.push(sync_op("privatize"));
+    events
+}
+
+/// Archive `repo` on `forge_name`, which is how sync answers an archive op
+async fn archive_remote(
+    state: &HyperforgeState,
+    org_name: &str,
+    forge_name: &str,
+    repo: &crate::types::Repo,
+    is_dry_run: bool,
+) -> Vec<HyperforgeEvent> {
+    let mut events = Vec::new();
+    if !is_dry_run {
+        let local = state.get_local_forge(org_name).await;
+        let archived = match make_adapter(forge_name, org_name, local.owner_type(), forge_host(&state.config_dir, org_name, forge_name, None).as_deref()) {
+            Ok(adapter) => adapter.archive_repo(org_name, &repo.name).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = archived {
+            events.push(HyperforgeEvent::error(
+                HyperforgeError::ForgeApi,
+                format!("  Failed to archive {} on {}: {}", repo.name, forge_name, e),
+            ));
+            return events;
+        }
+    }
+    events.push(HyperforgeEvent::SyncOp {
+        repo_name: repo.name.clone(),
+        operation: "archive".to_string(),
+        forge: forge_name.to_string(),
+        details: vec![],
+    });
+    events
+}
+
+/// Apply each repo's `[branch_protection]` on every forge it declares.
+/// Returns the events, the number of rules updated and the number of
+/// failed targets.
+async fn sync_branch_protection(
+    repos: &[DiscoveredRepo],
+    config_dir: &Path,
+    sync_service: &std::sync::Arc<crate::services::SymmetricSyncService>,
+    is_dry_run: bool,
+) -> (Vec<HyperforgeEvent>, usize, usize) {
+    let targets: Vec<_> = repos
+        .iter()
+        .filter_map(|r| {
+            let config = r.config.as_ref()?;
+            let protection = config.branch_protection.as_ref()?;
+            let branch = protection.branch.clone().unwrap_or_else(|| config.effective_default_branch().to_string());
+            let rule = BranchProtection::from(protection);
+            let name = config.get_repo_name(&r.path);
+            Some(config.forges.iter().filter_map(move |f| {
+                let org = config.org_for_forge(f)?;
+                Some((
+                    r.dir_name.clone(),
+                    f.clone(),
+                    org.to_string(),
+                    name.clone(),
+                    branch.clone(),
+                    rule.clone(),
+                    forge_host(config_dir, org, f, config.forge_host(f)),
+                ))
+            }))
+        })
+        .flatten()
+        .collect();
+
+    let results = run_batch(targets, Scheduler::global().batch(None), {
+        let sync_service = sync_service.clone();
+        move |(dir_name, forge, org, name, branch, rule, host)| {
+            let sync_service = sync_service.clone();
+            async move {
+                let diff = match make_adapter(&forge, &org, None, host.as_deref()) {
+                    Ok(adapter) => sync_service
+                        .sync_branch_protection(adapter.as_ref(), &org, &name, &branch, &rule, is_dry_run)
+                        .await
+                        .map_err(|e| e.to_string()),
+                    Err(e) => Err(e),
+                };
+                (dir_name, forge, diff)
+            }
+        }
+    }).await;
+
+    let mut events = Vec::new();
+    let (mut updated, mut failed) = (0usize, 0usize);
+    for result in results {
+        let (dir_name, forge, diff) = match result {
+            Ok(v) => v,
+            Err(e) => {
+                failed += 1;
+                events.push(msg_error!(Msg::TaskFailed, error = e));
+                continue;
+            }
+        };
+        match diff {
+            Ok(Some(diff)) => {
+                let action = if diff.details.is_empty() { "unchanged" } else { "updated" };
+                if !diff.details.is_empty() {
+                    updated += 1;
+                }
+                events.push(HyperforgeEvent::BranchProtectionSync {
+                    repo_name: dir_name,
+                    forge,
+                    branch: diff.branch,
+                    action: action.to_string(),
+                    details: diff.details,
+                });
+            }
+            Ok(None) => {}
+            Err(e) => {
+                failed += 1;
+                events.push(HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("  {dir_name}@{forge}: branch protection: {e}"),
+                ).with_context(forge.clone()));
+            }
+        }
+    }
+
+    (events, updated, failed)
+}
+
+/// Converge each repo's `[webhooks]` on every forge it declares. Returns
+/// the events, the number of hooks changed and the number of failed targets.
+async fn sync_converge_webhooks(
+    repos: &[DiscoveredRepo],
+    config_dir: &Path,
+    is_dry_run: bool,
+) -> (Vec<HyperforgeEvent>, usize, usize) {
+    let targets: Vec<_> = repos
+        .iter()
+        .filter_map(|r| {
+            let config = r.config.as_ref().filter(|c| !c.webhooks.is_empty())?;
+            let name = config.get_repo_name(&r.path);
+            Some(config.forges.iter().filter_map(move |f| {
+                let org = config.org_for_forge(f)?;
+                Some((
+                    r.dir_name.clone(),
+                    f.clone(),
+                    org.to_string(),
+                    name.clone(),
+                    config.webhooks.clone(),
+                    forge_host(config_dir, org, f, config.forge_host(f)),
+                ))
+            }))
+        })
+        .flatten()
+        .collect();
+
+    let results = run_batch(targets, Scheduler::global().batch(None), move |(dir_name, forge, org, name, hooks, host)| async move {
+        let changes = match make_adapter(&forge, &org, None, host.as_deref()) {
+            Ok(adapter) => WebhookService::converge(adapter.as_ref(), &org, &name, &forge, &hooks, is_dry_run)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e),
+        };
+        (dir_name, forge, changes)
+    }).await;
+
+    let mut events = Vec::new();
+    let (mut changed, mut failed) = (0usize, 0usize);
+    for result in results {
+        let (dir_name, forge, changes) = match result {
+            Ok(v) => v,
+            Err(e) => {
+                failed += 1;
+                events.push(msg_error!(Msg::TaskFailed, error = e));
+                continue;
+            }
+        };
+        match changes {
+            Ok(Some(changes)) => {
+                for change in changes {
+                    if change.action != WebhookAction::Unchanged {
+                        changed += 1;
+                    }
+                    events.push(HyperforgeEvent::WebhookSync {
+                        repo_name: dir_name.clone(),
+                        forge: forge.clone(),
+                        hook: change.hook,
+                        url: change.url,
+                        action: change.action.as_str().to_string(),
+                    });
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                failed += 1;
+                events.push(HyperforgeEvent::error(
+                    HyperforgeError::ForgeApi,
+                    format!("  {dir_name}@{forge}: webhooks: {e}"),
+                ).with_context(forge.clone()));
+            }
+        }
+    }
+
+    (events, changed, failed)
+}
+
 /// Phase 7.5: Retire remote-only repos (reflect/purge mode).
 async fn sync_retire_remote_only(
     pairs: &[(String, String)],
@@ -2599,38 +6698,46 @@ async fn sync_retire_remote_only(
     let mut purged_count = 0usize;
     let mut protected_skipped = 0usize;
 
-    // Build set of local repo names from workspace discovery
+    // Build set of local repo names from workspace discovery, keyed
+    // case-insensitively so `MyRepo` on a forge is never retired for `myrepo`
     let local_names: HashSet<String> = ctx.repos.iter()
-        .filter_map(|r| repo_from_config(r).map(|repo| repo.name))
+        .filter_map(|r| repo_from_config(r).map(|repo| name_key(&repo.name)))
         .collect();
 
+    // In a sparse workspace, repos outside the active profile are absent on
+    // purpose and must never be treated as deleted locally
+    let sparse = match profile_filter(&ctx.root, RepoFilter::new(None, None), None, false) {
+        Ok(filter) => filter,
+        Err(e) => {
+            events.push(HyperforgeEvent::error(HyperforgeError::Config, e));
+            return (events, 0, 0, 0);
+        }
+    };
+
     for (org_name, forge_name) in pairs {
         let local = state.get_local_forge(org_name).await;
         let ot = local.owner_type();
+        let scope = etag_scope(forge_name, org_name, ot.clone()).await;
 
-        let adapter = match make_adapter(forge_name, org_name, ot) {
+        let host = forge_host(&state.config_dir, org_name, forge_name, None);
+        let adapter = match make_adapter(forge_name, org_name, ot, host.as_deref()) {
             Ok(a) => a,
             Err(e) => {
-                events.push(HyperforgeEvent::Error { message: e });
+                events.push(HyperforgeEvent::error(HyperforgeError::ForgeApi, e));
                 continue;
             }
         };
 
         let forge_enum = HyperforgeConfig::parse_forge(forge_name);
-        let stored_etag = if let Some(ref fe) = forge_enum {
-            local.forge_states().ok()
-                .and_then(|states| states.get(fe).map(|s| s.etag.clone()))
-                .flatten()
-        } else {
-            None
-        };
+        let stored_etag = usable_etag(&local, org_name, forge_name, forge_enum.as_ref(), &scope, &mut events);
 
         let list_result = match adapter.list_repos_incremental(org_name, stored_etag).await {
             Ok(lr) => lr,
             Err(e) => {
-                events.push(HyperforgeEvent::Error {
-                    message: format!("  Failed to list remote repos for {org_name}/{forge_name}: {e}"),
-                });
+                events.push(HyperforgeEvent::error(
+                    Msg::RemoteListFailed.code(),
+                    format!("  {}", msg!(Msg::RemoteListFailed, org = org_name, forge = forge_name, error = e)),
+                ));
                 continue;
             }
         };
@@ -2643,6 +6750,7 @@ async fn sync_retire_remote_only(
                 let _ = local.set_forge_state(fe.clone(), ForgeSyncState {
                     last_synced: Utc::now(),
                     etag: list_result.etag.clone(),
+                    scope: scope.clone(),
                 });
             }
             continue;
@@ -2651,7 +6759,7 @@ async fn sync_retire_remote_only(
         let remote_repos = list_result.repos.as_deref().unwrap_or_default();
 
         for remote_repo in remote_repos {
-            if local_names.contains(&remote_repo.name) {
+            if local_names.contains(&name_key(&remote_repo.name)) || !sparse.matches(&remote_repo.name) {
                 continue;
             }
 
@@ -2666,6 +6774,16 @@ async fn sync_retire_remote_only(
                 continue;
             }
 
+            if remote_repo.read_only {
+                events.push(HyperforgeEvent::InsufficientPermissions {
+                    org: org_name.clone(),
+                    forge: forge_name.clone(),
+                    repo_name: remote_repo.name.clone(),
+                    operation: if is_purge { "delete" } else { "stage" }.to_string(),
+                });
+                continue;
+            }
+
             if is_purge {
                 // Purge mode: only delete repos that were previously staged
                 let is_staged = match local.get_repo(org_name, &remote_repo.name).await {
@@ -2717,9 +6835,10 @@ async fn sync_retire_remote_only(
                 // Actually delete from remote
                 if !is_dry_run {
                     if let Err(e) = adapter.delete_repo(org_name, &remote_repo.name).await {
-                        events.push(HyperforgeEvent::Error {
-                            message: format!("  Failed to delete {} on {}: {}", remote_repo.name, forge_name, e),
-                        });
+                        events.push(HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  Failed to delete {} on {}: {}", remote_repo.name, forge_name, e),
+                        ));
                         continue;
                     }
                     let _ = local.delete_repo(org_name, &remote_repo.name).await;
@@ -2741,9 +6860,10 @@ async fn sync_retire_remote_only(
 
                 if !is_dry_run {
                     if let Err(e) = adapter.update_repo(org_name, &privatized).await {
-                        events.push(HyperforgeEvent::Error {
-                            message: format!("  Failed to make {} private on {}: {}", remote_repo.name, forge_name, e),
-                        });
+                        events.push(HyperforgeEvent::error(
+                            HyperforgeError::ForgeApi,
+                            format!("  Failed to make {} private on {}: {}", remote_repo.name, forge_name, e),
+                        ));
                         continue;
                     }
                 }
@@ -2769,18 +6889,43 @@ async fn sync_retire_remote_only(
                 let _ = local.set_forge_state(fe.clone(), ForgeSyncState {
                     last_synced: Utc::now(),
                     etag: list_result.etag.clone(),
+                    scope: scope.clone(),
                 });
             }
         }
 
         if !is_dry_run {
             if let Err(e) = local.save_to_yaml().await {
-                events.push(HyperforgeEvent::Error {
-                    message: format!("  Failed to save LocalForge for {org_name}: {e}"),
-                });
+                events.push(HyperforgeEvent::error(
+                    Msg::LocalForgeSaveFailed.code(),
+                    format!("  {}", msg!(Msg::LocalForgeSaveFailed, org = org_name, error = e)),
+                ));
             }
         }
     }
 
     (events, staged_count, purged_count, protected_skipped)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undeclared_forges_come_from_workspace_config() {
+        let config = |forges: &[&str]| HyperforgeConfig {
+            org: Some("acme".to_string()),
+            forges: forges.iter().map(ToString::to_string).collect(),
+            ..HyperforgeConfig::default()
+        };
+        let web = config(&["github", "codeberg"]);
+        let api = config(&["github:acme-bot", "git.example.org"]);
+
+        assert_eq!(
+            undeclared_forges(&[&web, &api]),
+            vec![vec!["git.example.org".to_string()], vec!["codeberg".to_string()]]
+        );
+        // GitLab is probed only once something in the workspace declares it
+        assert_eq!(undeclared_forges(&[&web]), vec![Vec::<String>::new()]);
+    }
+}
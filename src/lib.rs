@@ -11,12 +11,18 @@ pub mod commands;
 pub mod config;
 pub mod docker;
 pub mod git;
+pub mod help;
 pub mod hub;
 pub mod hubs;
+pub mod messages;
+pub mod output;
 pub mod package;
 pub mod registry;
 pub mod remote;
+pub mod render;
+pub mod scheduler;
 pub mod services;
+pub mod shell;
 pub mod types;
 
 // Re-export serde_helpers from plexus_core (required by plexus_macros generated code)
@@ -26,6 +32,6 @@ pub use plexus_core::serde_helpers;
 pub use adapters::{ForgePort, LocalForge};
 pub use auth_hub::{AuthEvent, AuthHub};
 pub use config::HyperforgeConfig;
-pub use hub::{HyperforgeEvent, HyperforgeHub};
+pub use hub::{HyperforgeError, HyperforgeEvent, HyperforgeHub};
 pub use services::{SymmetricSyncService, SyncDiff, SyncOp};
 pub use types::*;
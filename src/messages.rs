@@ -0,0 +1,337 @@
+//! Catalog of user-facing message templates
+//!
+//! Messages that recur across hub methods are keyed by [`Msg`], whose key is
+//! prefixed with the message's [`HyperforgeError`] code (`config.repos_yaml_save_failed`).
+//! English templates are built in; another locale is selected with `locale`
+//! in `~/.config/hyperforge/config.toml` (or `HYPERFORGE_LOCALE`, which wins)
+//! and read from `~/.config/hyperforge/locales/{locale}.toml`:
+//!
+//! ```toml
+//! [validation]
+//! invalid_forge = "Ungültige Forge: {forge}. Erlaubt: github, codeberg, gitlab"
+//! ```
+//!
+//! Keys missing from a locale file fall back to English, so a catalog can be
+//! translated incrementally.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::config::UserConfig;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+use crate::hubs::HyperforgeState;
+
+/// Directory under the hyperforge config dir holding locale catalogs
+pub const LOCALES_DIR: &str = "locales";
+
+/// Locale used when none is configured; its templates are built in
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// A user-facing message template
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Msg {
+    DiscoveryFailed,
+    TaskFailed,
+    TaskJoinFailed,
+    InvalidForge,
+    InvalidRegex,
+    DependencyCycle,
+    ReposYamlSaveFailed,
+    LocalForgeSaveFailed,
+    RepoNotInLocalForge,
+    RepoNotInLocalConfig,
+    RepoNoLocalPath,
+    RepoPathMissing,
+    MaterializeFailed,
+    RunHistoryFailed,
+    RemoteListFailed,
+    GitLsFilesFailed,
+    DockerUnavailable,
+    DockerNotRunning,
+    DockerNotInstalled,
+}
+
+impl Msg {
+    /// Every message, for catalog tooling and tests
+    pub const ALL: &'static [Self] = &[
+        Self::DiscoveryFailed,
+        Self::TaskFailed,
+        Self::TaskJoinFailed,
+        Self::InvalidForge,
+        Self::InvalidRegex,
+        Self::DependencyCycle,
+        Self::ReposYamlSaveFailed,
+        Self::LocalForgeSaveFailed,
+        Self::RepoNotInLocalForge,
+        Self::RepoNotInLocalConfig,
+        Self::RepoNoLocalPath,
+        Self::RepoPathMissing,
+        Self::MaterializeFailed,
+        Self::RunHistoryFailed,
+        Self::RemoteListFailed,
+        Self::GitLsFilesFailed,
+        Self::DockerUnavailable,
+        Self::DockerNotRunning,
+        Self::DockerNotInstalled,
+    ];
+
+    /// Error category the message reports
+    pub const fn code(self) -> HyperforgeError {
+        match self {
+            Self::DiscoveryFailed
+            | Self::ReposYamlSaveFailed
+            | Self::LocalForgeSaveFailed
+            | Self::RepoNotInLocalForge
+            | Self::RepoNotInLocalConfig
+            | Self::RepoNoLocalPath
+            | Self::RepoPathMissing
+            | Self::MaterializeFailed
+            | Self::RunHistoryFailed => HyperforgeError::Config,
+            Self::InvalidForge | Self::InvalidRegex | Self::DependencyCycle => {
+                HyperforgeError::Validation
+            }
+            Self::RemoteListFailed => HyperforgeError::ForgeApi,
+            Self::GitLsFilesFailed => HyperforgeError::Git,
+            Self::TaskFailed
+            | Self::TaskJoinFailed
+            | Self::DockerUnavailable
+            | Self::DockerNotRunning
+            | Self::DockerNotInstalled => HyperforgeError::Internal,
+        }
+    }
+
+    /// Name within the code's table in a locale file
+    const fn name(self) -> &'static str {
+        match self {
+            Self::DiscoveryFailed => "discovery_failed",
+            Self::TaskFailed => "task_failed",
+            Self::TaskJoinFailed => "task_join_failed",
+            Self::InvalidForge => "invalid_forge",
+            Self::InvalidRegex => "invalid_regex",
+            Self::DependencyCycle => "dependency_cycle",
+            Self::ReposYamlSaveFailed => "repos_yaml_save_failed",
+            Self::LocalForgeSaveFailed => "local_forge_save_failed",
+            Self::RepoNotInLocalForge => "repo_not_in_local_forge",
+            Self::RepoNotInLocalConfig => "repo_not_in_local_config",
+            Self::RepoNoLocalPath => "repo_no_local_path",
+            Self::RepoPathMissing => "repo_path_missing",
+            Self::MaterializeFailed => "materialize_failed",
+            Self::RunHistoryFailed => "run_history_failed",
+            Self::RemoteListFailed => "remote_list_failed",
+            Self::GitLsFilesFailed => "git_ls_files_failed",
+            Self::DockerUnavailable => "docker_unavailable",
+            Self::DockerNotRunning => "docker_not_running",
+            Self::DockerNotInstalled => "docker_not_installed",
+        }
+    }
+
+    /// Catalog key: `{code}.{name}`
+    pub fn key(self) -> String {
+        format!("{}.{}", self.code(), self.name())
+    }
+
+    /// Built-in English template
+    pub const fn english(self) -> &'static str {
+        match self {
+            Self::DiscoveryFailed => "Discovery failed: {error}",
+            Self::TaskFailed => "Task error: {error}",
+            Self::TaskJoinFailed => "Task join error: {error}",
+            Self::InvalidForge => "Invalid forge: {forge}. Must be github, codeberg, or gitlab",
+            Self::InvalidRegex => "Invalid regex '{pattern}': {error}",
+            Self::DependencyCycle => "Dependency cycle detected: {error}",
+            Self::ReposYamlSaveFailed => "Failed to save repos.yaml: {error}",
+            Self::LocalForgeSaveFailed => "Failed to save LocalForge for {org}: {error}",
+            Self::RepoNotInLocalForge => "Repo '{name}' not found in LocalForge: {error}",
+            Self::RepoNotInLocalConfig => "Repository not found in local config: {error}",
+            Self::RepoNoLocalPath => "Repo '{name}' has no local_path set in LocalForge",
+            Self::RepoPathMissing => "Repo path does not exist: {path}",
+            Self::MaterializeFailed => "Failed to materialize config: {error}",
+            Self::RunHistoryFailed => "Failed to record run history: {error}",
+            Self::RemoteListFailed => "Failed to list remote repos for {org}/{forge}: {error}",
+            Self::GitLsFilesFailed => "Failed to run git ls-files: {error}",
+            Self::DockerUnavailable => "Docker unavailable: {error}. Is Docker/Colima running?",
+            Self::DockerNotRunning => "Docker daemon is not running. Start Docker or Colima first.",
+            Self::DockerNotInstalled => "Docker is not installed.",
+        }
+    }
+
+    /// Render in the configured locale
+    pub fn render(self, args: &[(&str, &dyn Display)]) -> String {
+        Catalog::global().render(self, args)
+    }
+
+    /// Render in the configured locale as an `Error` event carrying the
+    /// message's code
+    pub fn error(self, args: &[(&str, &dyn Display)]) -> HyperforgeEvent {
+        HyperforgeEvent::error(self.code(), self.render(args))
+    }
+}
+
+/// Render a [`Msg`] in the configured locale: `msg!(Msg::InvalidForge, forge = name)`
+///
+/// Arguments are borrowed only while rendering, so the macro can be used
+/// directly in a `yield` inside `stream!` without holding non-`Send`
+/// references across the yield point.
+#[macro_export]
+macro_rules! msg {
+    ($msg:expr $(, $name:ident = $value:expr)* $(,)?) => {{
+        let rendered = $msg.render(&[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),*]);
+        rendered
+    }};
+}
+
+/// Like [`msg!`], producing an `Error` event with the message's code
+#[macro_export]
+macro_rules! msg_error {
+    ($msg:expr $(, $name:ident = $value:expr)* $(,)?) => {{
+        let event = $msg.error(&[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),*]);
+        event
+    }};
+}
+
+/// Message templates for one locale, falling back to English
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    locale: Option<String>,
+    templates: HashMap<String, String>,
+}
+
+static GLOBAL_CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+impl Catalog {
+    /// The built-in English catalog
+    pub fn english() -> Self {
+        Self::default()
+    }
+
+    /// Catalog for `locale` from its TOML source; `[code]` tables of
+    /// `name = "template"`
+    pub fn from_toml(locale: &str, content: &str) -> Result<Self, String> {
+        let table: toml::Table = toml::from_str(content)
+            .map_err(|e| format!("Failed to parse {locale} message catalog: {e}"))?;
+        let mut templates = HashMap::new();
+        for (code, entries) in table {
+            let Some(entries) = entries.as_table() else {
+                continue;
+            };
+            for (name, template) in entries {
+                if let Some(template) = template.as_str() {
+                    templates.insert(format!("{code}.{name}"), template.to_string());
+                }
+            }
+        }
+        Ok(Self {
+            locale: Some(locale.to_string()),
+            templates,
+        })
+    }
+
+    /// Path to a locale's catalog: ~/.config/hyperforge/locales/{locale}.toml
+    pub fn path(config_dir: &Path, locale: &str) -> PathBuf {
+        config_dir.join(LOCALES_DIR).join(format!("{locale}.toml"))
+    }
+
+    /// Catalog for the locale configured under `config_dir`, with
+    /// `env_locale` taking precedence. A missing or unreadable catalog is
+    /// English.
+    pub fn load(config_dir: &Path, env_locale: Option<String>) -> Self {
+        let locale = env_locale
+            .filter(|l| !l.trim().is_empty())
+            .or_else(|| UserConfig::load(config_dir).locale);
+        match locale {
+            Some(locale) if locale != DEFAULT_LOCALE => {
+                std::fs::read_to_string(Self::path(config_dir, &locale))
+                    .ok()
+                    .and_then(|content| Self::from_toml(&locale, &content).ok())
+                    .unwrap_or_default()
+            }
+            _ => Self::english(),
+        }
+    }
+
+    /// Load the process-wide catalog from `config_dir`. The first call (or
+    /// the first [`Catalog::global`]) wins; later calls are no-ops.
+    pub fn init(config_dir: &Path) -> &'static Self {
+        GLOBAL_CATALOG.get_or_init(|| Self::load(config_dir, std::env::var("HYPERFORGE_LOCALE").ok()))
+    }
+
+    /// The process-wide catalog; loaded from ~/.config/hyperforge when
+    /// nothing called [`Catalog::init`] first
+    pub fn global() -> &'static Self {
+        Self::init(&HyperforgeState::default_config_dir())
+    }
+
+    /// Locale the catalog renders in
+    pub fn locale(&self) -> &str {
+        self.locale.as_deref().unwrap_or(DEFAULT_LOCALE)
+    }
+
+    /// Template for `msg`, falling back to English
+    pub fn template(&self, msg: Msg) -> &str {
+        self.templates
+            .get(&msg.key())
+            .map_or_else(|| msg.english(), String::as_str)
+    }
+
+    /// Fill `{name}` placeholders in `msg`'s template; unknown placeholders
+    /// are left as written
+    pub fn render(&self, msg: Msg, args: &[(&str, &dyn Display)]) -> String {
+        let mut out = self.template(msg).to_string();
+        for (name, value) in args {
+            out = out.replace(&format!("{{{name}}}"), &value.to_string());
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_english_render_matches_builtin() {
+        let catalog = Catalog::english();
+        assert_eq!(catalog.locale(), "en");
+        assert_eq!(
+            catalog.render(Msg::InvalidForge, &[("forge", &"sourcehut")]),
+            "Invalid forge: sourcehut. Must be github, codeberg, or gitlab"
+        );
+        assert_eq!(Msg::ReposYamlSaveFailed.key(), "config.repos_yaml_save_failed");
+    }
+
+    #[test]
+    fn test_keys_are_unique() {
+        let keys: std::collections::HashSet<String> = Msg::ALL.iter().map(|m| m.key()).collect();
+        assert_eq!(keys.len(), Msg::ALL.len());
+    }
+
+    #[test]
+    fn test_locale_from_user_config_with_english_fallback() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(UserConfig::config_path(tmp.path()), "locale = \"de\"\n").unwrap();
+        std::fs::create_dir_all(tmp.path().join(LOCALES_DIR)).unwrap();
+        std::fs::write(
+            Catalog::path(tmp.path(), "de"),
+            "[validation]\ninvalid_forge = \"Ungültige Forge: {forge}\"\n",
+        )
+        .unwrap();
+
+        let catalog = Catalog::load(tmp.path(), None);
+        assert_eq!(catalog.locale(), "de");
+        assert_eq!(
+            catalog.render(Msg::InvalidForge, &[("forge", &"x")]),
+            "Ungültige Forge: x"
+        );
+        // Untranslated keys stay English
+        assert_eq!(
+            catalog.render(Msg::RepoPathMissing, &[("path", &"/tmp/r")]),
+            "Repo path does not exist: /tmp/r"
+        );
+
+        // The environment wins over the config file
+        assert_eq!(Catalog::load(tmp.path(), Some("en".to_string())).locale(), "en");
+    }
+}
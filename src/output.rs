@@ -0,0 +1,148 @@
+//! Structured (NDJSON) output for CI
+//!
+//! Hub methods narrate with `info` events alongside typed ones
+//! (`workspace_summary`, `repo_push`, `publish_summary`, `error`, ...). In
+//! `json` format the narration is dropped and only typed events remain, one
+//! JSON object per line, so pipelines can parse results without scraping text.
+//!
+//! Every hyperforge hub method passes its stream through [`structured`], so
+//! `[output] format = "json"` in the hub's config.toml applies to all of
+//! them. `workspace sync` and `build publish` also take `--format` to pick
+//! the format per call. Streams from elsewhere (e.g. the auth hub) can be
+//! filtered the same way by piping them through `hyperforge --render
+//! --format json`.
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::config::UserConfig;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+
+/// How a stream's events are meant to be consumed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Every event, narration included
+    #[default]
+    Text,
+    /// Typed events only, as NDJSON
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" | "ndjson" => Ok(Self::Json),
+            other => Err(format!("Invalid output format '{other}'. Must be text or json")),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// The format a call asked for, else `[output] format` from
+    /// `config_dir`'s config.toml, else text
+    pub fn resolve(requested: Option<&str>, config_dir: &Path) -> Result<Self, String> {
+        match requested {
+            Some(format) => format.parse(),
+            None => UserConfig::load(config_dir).output.format.as_deref().map_or(Ok(Self::Text), str::parse),
+        }
+    }
+
+    /// Whether `event` belongs in this format's output
+    pub const fn keeps(self, event: &HyperforgeEvent) -> bool {
+        match self {
+            Self::Text => true,
+            Self::Json => !matches!(event, HyperforgeEvent::Info { .. }),
+        }
+    }
+}
+
+/// `events` in the format `requested` (or configured) for `config_dir`. An
+/// invalid format ends the stream with a validation error before any work
+/// starts.
+pub fn structured<S>(requested: Option<&str>, config_dir: &Path, events: S) -> impl Stream<Item = HyperforgeEvent> + Send + 'static
+where
+    S: Stream<Item = HyperforgeEvent> + Send + 'static,
+{
+    let format = OutputFormat::resolve(requested, config_dir);
+    // Boxed up front so wrapping a method's stream doesn't grow its future
+    let mut events = Box::pin(events);
+    stream! {
+        let format = match format {
+            Ok(format) => format,
+            Err(e) => {
+                yield HyperforgeEvent::error(HyperforgeError::Validation, e);
+                return;
+            }
+        };
+        while let Some(event) = events.next().await {
+            if format.keeps(&event) {
+                yield event;
+            }
+        }
+    }
+}
+
+/// Copy an NDJSON event stream from `input` to `output`, keeping only the
+/// typed events and writing each as one compact line. Lines that aren't
+/// events are dropped.
+pub fn write_ndjson(input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        let Ok(event) = serde_json::from_str::<HyperforgeEvent>(&line) else { continue };
+        if OutputFormat::Json.keeps(&event) {
+            writeln!(output, "{}", serde_json::to_string(&event)?)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefers_the_call_then_config() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert_eq!(OutputFormat::resolve(None, temp.path()), Ok(OutputFormat::Text));
+        std::fs::write(temp.path().join("config.toml"), "[output]\nformat = \"json\"\n").unwrap();
+        assert_eq!(OutputFormat::resolve(None, temp.path()), Ok(OutputFormat::Json));
+        assert_eq!(OutputFormat::resolve(Some("text"), temp.path()), Ok(OutputFormat::Text));
+        assert!(OutputFormat::resolve(Some("yaml"), temp.path()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_json_drops_narration() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let events = futures::stream::iter(vec![
+            HyperforgeEvent::Info { message: "Phase 1: discover".to_string() },
+            HyperforgeEvent::error(HyperforgeError::Git, "push rejected"),
+        ]);
+        let kept: Vec<_> = structured(Some("json"), temp.path(), events).collect().await;
+        assert_eq!(kept.len(), 1);
+        assert!(matches!(kept[0], HyperforgeEvent::Error { .. }));
+
+        let rejected: Vec<_> = structured(Some("xml"), temp.path(), futures::stream::empty()).collect().await;
+        assert!(matches!(rejected[0], HyperforgeEvent::Error { code: HyperforgeError::Validation, .. }));
+    }
+
+    #[test]
+    fn test_write_ndjson() {
+        let input = concat!(
+            "{\"type\":\"info\",\"message\":\"Scanning\"}\n",
+            "not json\n",
+            "{\n",
+            "{\"type\":\"error\",\"code\":\"git\",\"message\":\"push rejected\"}\n",
+        );
+        let mut output = Vec::new();
+        write_ndjson(input.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.starts_with("{\"type\":\"error\""));
+    }
+}
@@ -0,0 +1,290 @@
+//! Forge-native package registries.
+//!
+//! Publishes to GitHub Packages, the GitLab Package Registry, and
+//! Codeberg's (Gitea) package registry alongside the public registries.
+//! npm packages go to all three; crates only to Codeberg, the one forge
+//! with a Cargo registry. Container images are published by `images push`.
+//!
+//! Tokens come from `{forge}/{org}/packages_token`, falling back to
+//! `{forge}/{org}/token`, and only ever reach the publishing tool through
+//! its environment.
+
+use super::{PublishResult, PublishedVersion, RegistryClient};
+use crate::build_system::BuildSystemKind;
+use crate::hub::PackageRegistry;
+use crate::types::Forge;
+use async_trait::async_trait;
+use std::path::Path;
+
+/// Cargo registry name the Codeberg index is registered under via env
+const CARGO_REGISTRY_NAME: &str = "hyperforge-codeberg";
+
+/// Env var carrying the token into the generated `.npmrc`
+const NPM_TOKEN_ENV: &str = "HYPERFORGE_PACKAGES_TOKEN";
+
+/// Whether `forge` has a package registry for `kind`
+pub const fn supports(forge: &Forge, kind: &BuildSystemKind) -> bool {
+    matches!(
+        (forge, kind),
+        (_, BuildSystemKind::Node) | (Forge::Codeberg, BuildSystemKind::Cargo)
+    )
+}
+
+/// Registry identifier for a forge's package registry
+pub const fn registry_kind(forge: &Forge) -> PackageRegistry {
+    match forge {
+        Forge::GitHub => PackageRegistry::GitHubPackages,
+        // Forgejo/Gitea package registries share Codeberg's API
+        Forge::Codeberg | Forge::Gitea { .. } => PackageRegistry::CodebergPackages,
+        Forge::GitLab => PackageRegistry::GitLabPackages,
+    }
+}
+
+/// npm registry URL for publishing `name` under `org`.
+///
+/// GitLab publishes per project, addressed by its URL-encoded path.
+pub fn npm_registry_url(forge: &Forge, org: &str, repo: &str) -> String {
+    match forge {
+        Forge::GitHub => "https://npm.pkg.github.com/".to_string(),
+        Forge::Codeberg => format!("https://codeberg.org/api/packages/{org}/npm/"),
+        Forge::Gitea { host } => format!("https://{host}/api/packages/{org}/npm/"),
+        Forge::GitLab => format!("https://gitlab.com/api/v4/projects/{org}%2F{repo}/packages/npm/"),
+    }
+}
+
+/// Sparse index URL of an org's Codeberg Cargo registry
+pub fn cargo_index_url(org: &str) -> String {
+    format!("sparse+https://codeberg.org/api/packages/{org}/cargo/")
+}
+
+/// Path of a crate's file within a Cargo index (`se/rd/serde`, `3/f/foo`)
+pub fn cargo_index_path(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{name}"),
+        2 => format!("2/{name}"),
+        3 => format!("3/{}/{name}", &name[..1]),
+        _ => format!("{}/{}/{name}", &name[..2], &name[2..4]),
+    }
+}
+
+/// Highest non-yanked version listed in a Cargo index file
+fn latest_index_version(index_file: &str) -> Option<String> {
+    index_file
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|entry| !entry.get("yanked").and_then(serde_json::Value::as_bool).unwrap_or(false))
+        .filter_map(|entry| entry.get("vers")?.as_str().map(String::from))
+        .max_by(|a, b| crate::build_system::version::compare_versions(a, b).unwrap_or_else(|| a.cmp(b)))
+}
+
+/// Client for one forge's package registry, for one org
+pub struct ForgeRegistryClient {
+    forge: Forge,
+    org: String,
+    repo: String,
+    kind: BuildSystemKind,
+    token: String,
+    http: reqwest::Client,
+}
+
+impl ForgeRegistryClient {
+    pub fn new(forge: Forge, org: &str, repo: &str, kind: BuildSystemKind, token: String) -> Self {
+        let http = reqwest::Client::builder()
+            .user_agent("hyperforge/4.0 (https://github.com/juggernautlabs/hyperforge)")
+            .build()
+            .expect("failed to build HTTP client");
+        Self {
+            forge,
+            org: org.to_string(),
+            repo: repo.to_string(),
+            kind,
+            token,
+            http,
+        }
+    }
+
+    /// Resolve the org's packages token and build a client
+    pub async fn connect(
+        forge: Forge,
+        org: &str,
+        repo: &str,
+        kind: BuildSystemKind,
+    ) -> Result<Self, String> {
+        let auth = crate::auth::YamlAuthProvider::new()
+            .map_err(|e| format!("Failed to create auth provider: {e}"))?;
+        let registry = crate::types::ContainerRegistry::from(&forge);
+        match crate::types::RegistryAuth::resolve(&registry, org, &auth).await? {
+            crate::types::RegistryAuth::Token(token)
+            | crate::types::RegistryAuth::Basic { password: token, .. } => {
+                Ok(Self::new(forge, org, repo, kind, token))
+            }
+            crate::types::RegistryAuth::Anonymous => {
+                Err(format!("No packages token for {}/{org}", forge.as_str()))
+            }
+        }
+    }
+
+    async fn run_publish(&self, path: &Path, dry_run: bool) -> anyhow::Result<std::process::Output> {
+        if self.kind == BuildSystemKind::Cargo {
+            let env_name = CARGO_REGISTRY_NAME.to_uppercase().replace('-', "_");
+            let mut args = vec!["publish", "--no-verify", "--registry", CARGO_REGISTRY_NAME];
+            if dry_run {
+                args.push("--dry-run");
+            }
+            return Ok(tokio::process::Command::new("cargo")
+                .args(&args)
+                .env(format!("CARGO_REGISTRIES_{env_name}_INDEX"), cargo_index_url(&self.org))
+                .env(format!("CARGO_REGISTRIES_{env_name}_TOKEN"), format!("Bearer {}", self.token))
+                .current_dir(path)
+                .output()
+                .await?);
+        }
+
+        // npm reads the token from the environment via `${VAR}` in a
+        // throwaway userconfig, so it never lands on disk or in argv
+        let registry = npm_registry_url(&self.forge, &self.org, &self.repo);
+        let auth_key = registry.trim_start_matches("https:");
+        let npmrc = std::env::temp_dir().join(format!("hyperforge-npmrc-{}", uuid::Uuid::new_v4()));
+        tokio::fs::write(&npmrc, format!("{auth_key}:_authToken=${{{NPM_TOKEN_ENV}}}\n")).await?;
+        let mut args = vec![
+            "publish".to_string(),
+            "--registry".to_string(),
+            registry,
+            "--userconfig".to_string(),
+            npmrc.display().to_string(),
+        ];
+        if dry_run {
+            args.push("--dry-run".to_string());
+        }
+        let output = tokio::process::Command::new("npm")
+            .args(&args)
+            .env(NPM_TOKEN_ENV, &self.token)
+            .current_dir(path)
+            .output()
+            .await;
+        let _ = tokio::fs::remove_file(&npmrc).await;
+        Ok(output?)
+    }
+}
+
+#[async_trait]
+impl RegistryClient for ForgeRegistryClient {
+    fn build_system(&self) -> BuildSystemKind {
+        self.kind.clone()
+    }
+
+    fn registry_kind(&self) -> PackageRegistry {
+        registry_kind(&self.forge)
+    }
+
+    async fn published_version(&self, name: &str) -> anyhow::Result<Option<PublishedVersion>> {
+        let (url, auth) = if self.kind == BuildSystemKind::Cargo {
+            let index = cargo_index_url(&self.org);
+            let base = index.trim_start_matches("sparse+");
+            (format!("{base}{}", cargo_index_path(name)), format!("Bearer {}", self.token))
+        } else {
+            let registry = npm_registry_url(&self.forge, &self.org, &self.repo);
+            (format!("{registry}{}", name.replace('/', "%2F")), format!("Bearer {}", self.token))
+        };
+
+        let resp = self.http.get(&url).header(reqwest::header::AUTHORIZATION, auth).send().await?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let resp = resp.error_for_status()?;
+
+        let version = if self.kind == BuildSystemKind::Cargo {
+            latest_index_version(&resp.text().await?)
+        } else {
+            let body: serde_json::Value = resp.json().await?;
+            body.get("dist-tags")
+                .and_then(|t| t.get("latest"))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        };
+
+        Ok(version.map(|version| PublishedVersion {
+            name: name.to_string(),
+            version,
+        }))
+    }
+
+    async fn publish(
+        &self,
+        path: &Path,
+        name: &str,
+        dry_run: bool,
+    ) -> anyhow::Result<PublishResult> {
+        // GitHub Packages only hosts scoped npm packages under the owner
+        if self.forge == Forge::GitHub && !name.starts_with(&format!("@{}/", self.org.to_lowercase())) {
+            return Ok(PublishResult {
+                package_name: name.to_string(),
+                version: "unknown".to_string(),
+                success: false,
+                error: Some(format!(
+                    "GitHub Packages needs the npm package scoped to its owner (@{}/...)",
+                    self.org.to_lowercase()
+                )),
+            });
+        }
+
+        let output = self.run_publish(path, dry_run).await?;
+        let success = output.status.success();
+        let error = if success {
+            None
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            Some(format!("{}\n{}", stderr.trim(), stdout.trim()).trim().replace(&self.token, "***"))
+        };
+
+        let version = if self.kind == BuildSystemKind::Cargo {
+            crate::build_system::cargo::cargo_package_version(path)
+        } else {
+            crate::build_system::node::node_package_version(path)
+        }
+        .unwrap_or_else(|| "unknown".to_string());
+
+        Ok(PublishResult {
+            package_name: name.to_string(),
+            version,
+            success,
+            error,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cargo_index_path() {
+        assert_eq!(cargo_index_path("a"), "1/a");
+        assert_eq!(cargo_index_path("ab"), "2/ab");
+        assert_eq!(cargo_index_path("abc"), "3/a/abc");
+        assert_eq!(cargo_index_path("Serde"), "se/rd/serde");
+    }
+
+    #[test]
+    fn test_latest_index_version_skips_yanked() {
+        let index = r#"{"name":"foo","vers":"0.9.0","yanked":false}
+{"name":"foo","vers":"0.10.0","yanked":false}
+{"name":"foo","vers":"0.11.0","yanked":true}"#;
+        assert_eq!(latest_index_version(index), Some("0.10.0".to_string()));
+        assert_eq!(latest_index_version(""), None);
+    }
+
+    #[test]
+    fn test_supports_and_urls() {
+        assert!(supports(&Forge::GitHub, &BuildSystemKind::Node));
+        assert!(supports(&Forge::Codeberg, &BuildSystemKind::Cargo));
+        assert!(!supports(&Forge::GitHub, &BuildSystemKind::Cargo));
+        assert!(!supports(&Forge::GitLab, &BuildSystemKind::Cabal));
+        assert_eq!(
+            npm_registry_url(&Forge::GitLab, "acme", "web"),
+            "https://gitlab.com/api/v4/projects/acme%2Fweb/packages/npm/"
+        );
+    }
+}
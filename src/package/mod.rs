@@ -1,9 +1,11 @@
 //! Package registry clients for version querying and publishing.
 //!
 //! Provides a unified trait for interacting with package registries
-//! (crates.io, Hackage, npm) and concrete implementations.
+//! (crates.io, Hackage, npm, forge package registries) and concrete
+//! implementations.
 
 pub mod crates_io;
+pub mod forge_registry;
 pub mod hackage;
 
 use crate::build_system::BuildSystemKind;
@@ -91,3 +93,32 @@ pub fn registry_for(kind: &BuildSystemKind) -> Option<Box<dyn RegistryClient>> {
         BuildSystemKind::Node | BuildSystemKind::Unknown => None,
     }
 }
+
+/// Forge package registries a package is also published to, from the repo's
+/// `dist.forge_packages`.
+///
+/// Forges without a registry for `kind` are left out; a forge whose token
+/// can't be resolved is returned as an error.
+pub async fn forge_registries_for(
+    repo_path: &Path,
+    kind: &BuildSystemKind,
+) -> Vec<(PackageRegistry, Result<forge_registry::ForgeRegistryClient, String>)> {
+    let Ok(config) = crate::config::HyperforgeConfig::load(repo_path) else {
+        return Vec::new();
+    };
+    let forges = config.dist.as_ref().map(|d| d.forge_packages.clone()).unwrap_or_default();
+    let repo = config.repo_name.clone().unwrap_or_else(|| {
+        repo_path.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_string()
+    });
+
+    let mut registries = Vec::new();
+    for forge in forges.into_iter().filter(|f| forge_registry::supports(f, kind)) {
+        let registry_kind = forge_registry::registry_kind(&forge);
+        let client = match config.org_for_forge(forge.as_str()).map(str::to_string) {
+            Some(org) => forge_registry::ForgeRegistryClient::connect(forge, &org, &repo, kind.clone()).await,
+            None => Err(format!("no org configured for {}", forge.as_str())),
+        };
+        registries.push((registry_kind, client));
+    }
+    registries
+}
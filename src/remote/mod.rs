@@ -6,10 +6,12 @@ use std::sync::Arc;
 use std::path::Path;
 use anyhow::{Context, Result};
 
-use crate::adapters::{ForgePort, GitHubAdapter, CodebergAdapter, GitLabAdapter};
+use crate::adapters::{ForgePort, GitHubAdapter, CodebergAdapter, GiteaAdapter};
 use crate::auth::YamlAuthProvider;
+use crate::config::OrgConfig;
+use crate::hubs::utils::gitlab_adapter;
 use crate::types::{Forge, Repo};
-use crate::git::Git;
+use crate::git::{CloneOptions, Git};
 
 /// Get forge adapter for a given forge type
 fn get_forge_adapter(forge: &Forge, org: &str) -> Result<Arc<dyn ForgePort>> {
@@ -18,7 +20,8 @@ fn get_forge_adapter(forge: &Forge, org: &str) -> Result<Arc<dyn ForgePort>> {
     let adapter: Arc<dyn ForgePort> = match forge {
         Forge::GitHub => Arc::new(GitHubAdapter::new(auth, org)?),
         Forge::Codeberg => Arc::new(CodebergAdapter::new(auth, org)?),
-        Forge::GitLab => Arc::new(GitLabAdapter::new(auth, org)?),
+        Forge::GitLab => Arc::new(gitlab_adapter(auth, org, OrgConfig::load_default(org).forge_host("gitlab"))?),
+        Forge::Gitea { host } => Arc::new(GiteaAdapter::gitea(auth, org, host)?),
     };
 
     Ok(adapter)
@@ -44,6 +47,7 @@ pub async fn import_repos(forge: &Forge, org: &str, target_dir: &str) -> Result<
 
     println!("Found {} repositories to import", repos.len());
 
+    let org_config = OrgConfig::load_default(org);
     let target_path = Path::new(target_dir);
     tokio::fs::create_dir_all(target_path).await
         .context("Failed to create target directory")?;
@@ -56,10 +60,10 @@ pub async fn import_repos(forge: &Forge, org: &str, target_dir: &str) -> Result<
             continue;
         }
 
-        let clone_url = format_clone_url(forge, org, &repo.name);
+        let clone_url = format_clone_url(forge, org_config.forge_host(forge.as_str()), org, &repo.name);
         println!("  {} - cloning from {}", repo.name, clone_url);
 
-        Git::clone(&clone_url, repo_path.to_str().unwrap())
+        Git::clone(&clone_url, repo_path.to_str().unwrap(), &CloneOptions::default())
             .context(format!("Failed to clone {}", repo.name))?;
     }
 
@@ -67,12 +71,13 @@ pub async fn import_repos(forge: &Forge, org: &str, target_dir: &str) -> Result<
     Ok(())
 }
 
-/// Format clone URL for a repository
-fn format_clone_url(forge: &Forge, org: &str, repo_name: &str) -> String {
+/// Format clone URL for a repository; `host` names a self-hosted GitLab
+fn format_clone_url(forge: &Forge, host: Option<&str>, org: &str, repo_name: &str) -> String {
     match forge {
         Forge::GitHub => format!("https://github.com/{org}/{repo_name}.git"),
         Forge::Codeberg => format!("https://codeberg.org/{org}/{repo_name}.git"),
-        Forge::GitLab => format!("https://gitlab.com/{org}/{repo_name}.git"),
+        Forge::GitLab => format!("https://{}/{org}/{repo_name}.git", host.unwrap_or("gitlab.com")),
+        Forge::Gitea { host } => format!("https://{host}/{org}/{repo_name}.git"),
     }
 }
 
@@ -82,19 +87,22 @@ mod tests {
 
     #[test]
     fn test_format_clone_url_github() {
-        let url = format_clone_url(&Forge::GitHub, "myorg", "myrepo");
+        let url = format_clone_url(&Forge::GitHub, None, "myorg", "myrepo");
         assert_eq!(url, "https://github.com/myorg/myrepo.git");
     }
 
     #[test]
     fn test_format_clone_url_codeberg() {
-        let url = format_clone_url(&Forge::Codeberg, "myorg", "myrepo");
+        let url = format_clone_url(&Forge::Codeberg, None, "myorg", "myrepo");
         assert_eq!(url, "https://codeberg.org/myorg/myrepo.git");
     }
 
     #[test]
     fn test_format_clone_url_gitlab() {
-        let url = format_clone_url(&Forge::GitLab, "myorg", "myrepo");
+        let url = format_clone_url(&Forge::GitLab, None, "myorg", "myrepo");
         assert_eq!(url, "https://gitlab.com/myorg/myrepo.git");
+
+        let url = format_clone_url(&Forge::GitLab, Some("gitlab.mycompany.com"), "myorg", "myrepo");
+        assert_eq!(url, "https://gitlab.mycompany.com/myorg/myrepo.git");
     }
 }
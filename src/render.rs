@@ -0,0 +1,272 @@
+//! Terminal rendering for event streams
+//!
+//! Hub methods only produce [`HyperforgeEvent`]s; this module turns them into
+//! human-readable lines with colors and symbols, grouping consecutive events
+//! about the same repo under one section header. Event generation never
+//! depends on it, so JSON consumers see the same stream as before.
+//!
+//! Color follows `--color auto|always|never`; `auto` colors only a terminal
+//! and honors `NO_COLOR` (<https://no-color.org>).
+
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::hub::HyperforgeEvent;
+
+/// When to emit ANSI colors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            other => Err(format!("Invalid color choice '{other}'. Must be auto, always, or never")),
+        }
+    }
+}
+
+impl ColorChoice {
+    /// Whether to color output going to a terminal (`is_tty`) given the
+    /// `NO_COLOR` environment variable's value
+    pub fn enabled(self, is_tty: bool, no_color: Option<&str>) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => is_tty && no_color.is_none_or(str::is_empty),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Style {
+    Red,
+    Green,
+    Yellow,
+    Cyan,
+    Dim,
+}
+
+impl Style {
+    const fn ansi(self) -> &'static str {
+        match self {
+            Self::Red => "\x1b[31m",
+            Self::Green => "\x1b[32m",
+            Self::Yellow => "\x1b[33m",
+            Self::Cyan => "\x1b[1;36m",
+            Self::Dim => "\x1b[2m",
+        }
+    }
+}
+
+/// Fields naming the repo an event is about, in lookup order
+const REPO_FIELDS: &[&str] = &["repo_name", "repo", "dir_name"];
+
+/// Stateful renderer: remembers the current repo section between events
+#[derive(Debug, Default)]
+pub struct Renderer {
+    color: bool,
+    section: Option<String>,
+}
+
+impl Renderer {
+    pub const fn new(color: bool) -> Self {
+        Self {
+            color,
+            section: None,
+        }
+    }
+
+    fn paint(&self, style: Style, text: &str) -> String {
+        if self.color {
+            format!("{}{text}\x1b[0m", style.ansi())
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Render one event as zero or more lines (without trailing newline)
+    pub fn render(&mut self, event: &HyperforgeEvent) -> Vec<String> {
+        let Ok(Value::Object(mut fields)) = serde_json::to_value(event) else {
+            return Vec::new();
+        };
+        let kind = fields
+            .remove("type")
+            .and_then(|t| t.as_str().map(str::to_string))
+            .unwrap_or_default();
+
+        let mut lines = Vec::new();
+        let repo = REPO_FIELDS
+            .iter()
+            .find_map(|k| fields.get(*k).and_then(Value::as_str).map(str::to_string));
+        match repo {
+            Some(repo) => {
+                if self.section.as_deref() != Some(repo.as_str()) {
+                    lines.push(self.paint(Style::Cyan, &format!("▸ {repo}")));
+                    self.section = Some(repo);
+                }
+                for key in REPO_FIELDS {
+                    fields.remove(*key);
+                }
+            }
+            None => self.section = None,
+        }
+        let indent = if self.section.is_some() { "  " } else { "" };
+
+        if let HyperforgeEvent::MethodHelp { method, description, params, example, .. } = event {
+            lines.push(format!("{} — {description}", self.paint(Style::Cyan, method)));
+            for param in params {
+                let required = if param.required { " (required)" } else { "" };
+                lines.push(format!("  --{} <{}>{required}  {}", param.name, param.ty, self.paint(Style::Dim, &param.description)));
+            }
+            lines.push(format!("  e.g. {example}"));
+            return lines;
+        }
+
+        let line = match event {
+            HyperforgeEvent::Info { message } => message.clone(),
+            HyperforgeEvent::Error {
+                code,
+                message,
+                context,
+            } => {
+                let mut line = format!("{} {message}", self.paint(Style::Red, "✗"));
+                let detail = context
+                    .as_ref()
+                    .map_or_else(|| format!("[{code}]"), |c| format!("[{code}: {c}]"));
+                line.push(' ');
+                line.push_str(&self.paint(Style::Dim, &detail));
+                line
+            }
+            HyperforgeEvent::Status {
+                version,
+                description,
+            } => format!("hyperforge {version} — {description}"),
+            _ => {
+                let symbol = match fields.remove("success").and_then(|v| v.as_bool()) {
+                    Some(true) => self.paint(Style::Green, "✓"),
+                    Some(false) => self.paint(Style::Red, "✗"),
+                    None if fields.get("passed").and_then(Value::as_bool) == Some(false) => {
+                        self.paint(Style::Yellow, "⚠")
+                    }
+                    None => self.paint(Style::Dim, "•"),
+                };
+                let details: Vec<String> = fields
+                    .iter()
+                    .filter_map(|(k, v)| format_value(v).map(|v| format!("{k}={v}")))
+                    .collect();
+                if details.is_empty() {
+                    format!("{symbol} {kind}")
+                } else {
+                    format!("{symbol} {kind} {}", self.paint(Style::Dim, &details.join(" ")))
+                }
+            }
+        };
+        lines.push(format!("{indent}{line}"));
+        lines
+    }
+}
+
+/// Compact display of a field value; `None` for values not worth showing
+fn format_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        Value::Array(items) if items.is_empty() => None,
+        Value::Array(items) => Some(
+            items
+                .iter()
+                .filter_map(format_value)
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Render newline-delimited JSON events from `input` to `output`. Lines that
+/// aren't events are passed through unchanged.
+pub fn render_lines(
+    input: impl BufRead,
+    mut output: impl Write,
+    renderer: &mut Renderer,
+) -> std::io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        match serde_json::from_str::<HyperforgeEvent>(&line) {
+            Ok(event) => {
+                for rendered in renderer.render(&event) {
+                    writeln!(output, "{rendered}")?;
+                }
+            }
+            Err(_) => writeln!(output, "{line}")?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hub::HyperforgeError;
+
+    #[test]
+    fn test_color_choice_resolution() {
+        assert!(ColorChoice::Auto.enabled(true, None));
+        assert!(!ColorChoice::Auto.enabled(false, None));
+        assert!(!ColorChoice::Auto.enabled(true, Some("1")));
+        assert!(ColorChoice::Auto.enabled(true, Some("")));
+        assert!(ColorChoice::Always.enabled(false, Some("1")));
+        assert!(!ColorChoice::Never.enabled(true, None));
+        assert_eq!("always".parse::<ColorChoice>(), Ok(ColorChoice::Always));
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+
+    #[test]
+    fn test_groups_consecutive_repo_events() {
+        let mut renderer = Renderer::new(false);
+        let push = |forge: &str, success: bool| HyperforgeEvent::RepoPush {
+            repo_name: "alpha".to_string(),
+            path: "/ws/alpha".to_string(),
+            forge: forge.to_string(),
+            success,
+            error: None,
+        };
+
+        assert_eq!(
+            renderer.render(&push("github", true)),
+            vec!["▸ alpha", "  ✓ repo_push forge=github path=/ws/alpha"]
+        );
+        assert_eq!(
+            renderer.render(&push("codeberg", false)),
+            vec!["  ✗ repo_push forge=codeberg path=/ws/alpha"]
+        );
+        assert_eq!(
+            renderer.render(&HyperforgeEvent::error(HyperforgeError::Git, "boom").with_context("alpha")),
+            vec!["✗ boom [git: alpha]"]
+        );
+    }
+
+    #[test]
+    fn test_color_wraps_symbols_and_passes_through_non_events() {
+        let mut renderer = Renderer::new(true);
+        let lines = renderer.render(&HyperforgeEvent::error(HyperforgeError::Auth, "no token"));
+        assert!(lines[0].starts_with("\x1b[31m✗\x1b[0m no token"));
+
+        let input = "{\"type\":\"info\",\"message\":\"hello\"}\nnot json\n";
+        let mut out = Vec::new();
+        render_lines(input.as_bytes(), &mut out, &mut Renderer::new(false)).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "hello\nnot json\n");
+    }
+}
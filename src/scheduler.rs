@@ -0,0 +1,308 @@
+//! Process-wide scheduler bounding git processes, forge API calls and
+//! containers across every concurrently running method and phase.
+//!
+//! Per-call `concurrency` arguments still bound a single batch; the
+//! scheduler bounds the sum, so two workspace methods running against a
+//! 500-repo workspace share one budget instead of doubling the load.
+//!
+//! Limits come from `[concurrency]` in the hub's config.toml
+//! (~/.config/hyperforge by default), then the environment, read once by
+//! [`Scheduler::init`] or on first use:
+//!   - `HYPERFORGE_MAX_GIT` / `git` — concurrent git processes (default 16)
+//!   - `HYPERFORGE_MAX_API` / `api` — concurrent API requests per forge
+//!     (default 8); `[concurrency.forges]` sets it for single forges, e.g.
+//!     `codeberg = 2` to stay under Codeberg's throttling
+//!   - `HYPERFORGE_MAX_CONTAINERS` / `containers` — concurrent docker
+//!     builds/runs (default 2)
+//!   - `HYPERFORGE_MAX_BATCH` / `batch` — repos a workspace method works on
+//!     at once when the call doesn't pass `concurrency` (default 8)
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::adapters::http_cache::ResponseCache;
+use crate::adapters::rate_limit::{self, RateLimiter};
+use crate::adapters::simulation;
+use crate::config::UserConfig;
+use crate::hubs::HyperforgeState;
+use crate::types::Forge;
+
+/// A resource class with a global concurrency limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resource {
+    /// A git subprocess (or other local blocking repo work)
+    Git,
+    /// An HTTP request to one forge's API
+    ForgeApi(Forge),
+    /// A docker build or container run
+    Container,
+}
+
+/// Global limits per resource class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchedulerLimits {
+    pub git: usize,
+    pub api_per_forge: usize,
+    /// `api_per_forge` overrides by forge name (hostname when self-hosted)
+    pub forges: BTreeMap<String, usize>,
+    pub containers: usize,
+    /// Default window for one batch of repos
+    pub batch: usize,
+}
+
+impl Default for SchedulerLimits {
+    fn default() -> Self {
+        Self {
+            git: 16,
+            api_per_forge: 8,
+            forges: BTreeMap::new(),
+            containers: 2,
+            batch: 8,
+        }
+    }
+}
+
+impl SchedulerLimits {
+    /// Defaults overridden by `[concurrency]` in `config_dir`'s config.toml,
+    /// then by `HYPERFORGE_MAX_*` env vars; unparsable or zero values are
+    /// ignored
+    pub fn load(config_dir: &Path) -> Self {
+        Self::from_lookup(&UserConfig::load(config_dir).concurrency, |key| std::env::var(key).ok())
+    }
+
+    fn from_lookup(config: &crate::config::ConcurrencyConfig, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let defaults = Self::default();
+        let read = |key: &str, configured: Option<usize>, default: usize| {
+            lookup(key)
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .filter(|n| *n > 0)
+                .or_else(|| configured.filter(|n| *n > 0))
+                .unwrap_or(default)
+        };
+        Self {
+            git: read("HYPERFORGE_MAX_GIT", config.git, defaults.git),
+            api_per_forge: read("HYPERFORGE_MAX_API", config.api, defaults.api_per_forge),
+            forges: config
+                .forges
+                .iter()
+                .filter(|(_, n)| **n > 0)
+                .map(|(forge, n)| (forge.to_lowercase(), *n))
+                .collect(),
+            containers: read("HYPERFORGE_MAX_CONTAINERS", config.containers, defaults.containers),
+            batch: read("HYPERFORGE_MAX_BATCH", config.batch, defaults.batch),
+        }
+    }
+
+    /// Concurrent API requests allowed to `forge`
+    pub fn api_for(&self, forge: &Forge) -> usize {
+        self.forges.get(forge.as_str()).copied().unwrap_or(self.api_per_forge)
+    }
+}
+
+/// Holds one slot of a resource until dropped
+#[derive(Debug)]
+pub struct Permit {
+    _slot: OwnedSemaphorePermit,
+}
+
+/// Semaphore-per-resource scheduler
+#[derive(Debug)]
+pub struct Scheduler {
+    limits: SchedulerLimits,
+    git: Arc<Semaphore>,
+    /// One per forge (per host for self-hosted ones), created on first use
+    forges: Mutex<HashMap<Forge, Arc<Semaphore>>>,
+    containers: Arc<Semaphore>,
+}
+
+static GLOBAL_SCHEDULER: OnceLock<Scheduler> = OnceLock::new();
+
+impl Scheduler {
+    pub fn new(limits: &SchedulerLimits) -> Self {
+        Self {
+            limits: limits.clone(),
+            git: Arc::new(Semaphore::new(limits.git)),
+            forges: Mutex::new(HashMap::new()),
+            containers: Arc::new(Semaphore::new(limits.containers)),
+        }
+    }
+
+    /// Configure the process-wide scheduler from `config_dir` and the
+    /// environment. The first call (or the first [`Scheduler::global`])
+    /// wins; later calls are no-ops.
+    pub fn init(config_dir: &Path) -> &'static Self {
+        GLOBAL_SCHEDULER.get_or_init(|| Self::new(&SchedulerLimits::load(config_dir)))
+    }
+
+    /// The process-wide scheduler; configured from ~/.config/hyperforge when
+    /// nothing called [`Scheduler::init`] first
+    pub fn global() -> &'static Self {
+        Self::init(&HyperforgeState::default_config_dir())
+    }
+
+    pub const fn limits(&self) -> &SchedulerLimits {
+        &self.limits
+    }
+
+    /// The batch window for a call that asked for `requested` (`0` =
+    /// unbounded), else the configured default
+    pub fn batch(&self, requested: Option<usize>) -> usize {
+        requested.unwrap_or(self.limits.batch)
+    }
+
+    fn semaphore(&self, resource: &Resource) -> Arc<Semaphore> {
+        match resource {
+            Resource::Git => self.git.clone(),
+            Resource::ForgeApi(forge) => self
+                .forges
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .entry(forge.clone())
+                .or_insert_with(|| Arc::new(Semaphore::new(self.limits.api_for(forge))))
+                .clone(),
+            Resource::Container => self.containers.clone(),
+        }
+    }
+
+    /// Wait for a slot of `resource`
+    pub async fn acquire(&self, resource: Resource) -> Permit {
+        let permit = self
+            .semaphore(&resource)
+            .acquire_owned()
+            .await
+            .expect("scheduler semaphores are never closed");
+        Permit { _slot: permit }
+    }
+
+    /// Blocking variant of [`Self::acquire`] for synchronous code (validation
+    /// steps, `spawn_blocking` closures); blocks the calling thread
+    pub fn acquire_blocking(&self, resource: Resource) -> Permit {
+        futures::executor::block_on(self.acquire(resource))
+    }
+
+    /// Free slots of `resource` right now
+    pub fn available(&self, resource: &Resource) -> usize {
+        self.semaphore(resource).available_permits()
+    }
+}
+
+/// Send a forge API request through the global per-forge limit. The slot is
+/// held until the response headers arrive; reading the body doesn't count.
+///
+/// Rate-limited requests are retried after the forge's pause (see
+/// [`crate::adapters::rate_limit`]), and GETs are revalidated against the
+/// response cache (see [`crate::adapters::http_cache`]). Under a simulation
+/// writes are recorded and never sent (see [`crate::adapters::simulation`]).
+pub trait LimitedSend {
+    fn send_limited(
+        self,
+        forge: Forge,
+    ) -> impl Future<Output = reqwest::Result<reqwest::Response>> + Send;
+}
+
+impl LimitedSend for reqwest::RequestBuilder {
+    async fn send_limited(self, forge: Forge) -> reqwest::Result<reqwest::Response> {
+        let limiter = RateLimiter::global();
+        let cache = ResponseCache::global();
+        let (client, built) = self.build_split();
+        let built = built?;
+        if let Some(response) = simulation::intercept(&built, &forge) {
+            return Ok(response);
+        }
+        let mut request = reqwest::RequestBuilder::from_parts(client, built);
+        let cache_key = request.try_clone().and_then(|r| r.build().ok()).and_then(|r| cache.key(&r));
+        let cached = cache_key.as_deref().and_then(|key| cache.load(key));
+        if let Some(entry) = &cached {
+            request = entry.condition(request);
+        }
+        let mut attempt = 0;
+        loop {
+            limiter.wait(&forge).await;
+            // Streaming bodies can't be cloned; those requests aren't retried
+            let retry = request.try_clone();
+            let response = {
+                let _permit = Scheduler::global().acquire(Resource::ForgeApi(forge.clone())).await;
+                request.send().await?
+            };
+
+            let now = chrono::Utc::now().timestamp();
+            let limits = rate_limit::headers_for(&forge, response.headers(), now);
+            if limits.is_limited(response.status()) {
+                if let Some(next) = retry.filter(|_| attempt < rate_limit::MAX_RETRIES) {
+                    if limiter.pause(&forge, limits.backoff(attempt, now)) {
+                        request = next;
+                        attempt += 1;
+                        continue;
+                    }
+                }
+            } else if let Some(wait) = limits.exhausted_for(now) {
+                limiter.pause(&forge, wait);
+            }
+            return match (cache_key, cached) {
+                (Some(_), Some(entry)) if response.status() == reqwest::StatusCode::NOT_MODIFIED => Ok(entry.to_response()),
+                (Some(key), _) => cache.record(&key, response).await,
+                (None, _) => Ok(response),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_from_env_lookup() {
+        let limits = SchedulerLimits::from_lookup(&crate::config::ConcurrencyConfig::default(), |key| match key {
+            "HYPERFORGE_MAX_GIT" => Some("4".to_string()),
+            "HYPERFORGE_MAX_API" => Some("0".to_string()),
+            "HYPERFORGE_MAX_CONTAINERS" => Some("lots".to_string()),
+            _ => None,
+        });
+        assert_eq!(limits.git, 4);
+        assert_eq!(limits.api_per_forge, SchedulerLimits::default().api_per_forge);
+        assert_eq!(limits.containers, SchedulerLimits::default().containers);
+    }
+
+    #[test]
+    fn test_limits_from_config() {
+        let config: crate::config::ConcurrencyConfig =
+            toml::from_str("api = 12\nbatch = 4\ngit = 0\n\n[forges]\ncodeberg = 2\n\"git.acme.dev\" = 3\n").unwrap();
+        let limits = SchedulerLimits::from_lookup(&config, |key| {
+            (key == "HYPERFORGE_MAX_BATCH").then(|| "6".to_string())
+        });
+        assert_eq!(limits.batch, 6, "env wins over config");
+        assert_eq!(limits.git, SchedulerLimits::default().git);
+        assert_eq!(limits.api_for(&Forge::GitHub), 12);
+        assert_eq!(limits.api_for(&Forge::Codeberg), 2);
+        assert_eq!(limits.api_for(&Forge::Gitea { host: "git.acme.dev".to_string() }), 3);
+        assert_eq!(limits.api_for(&Forge::Gitea { host: "git.other.dev".to_string() }), 12);
+    }
+
+    #[tokio::test]
+    async fn test_permits_are_per_resource_and_released_on_drop() {
+        let scheduler = Scheduler::new(&SchedulerLimits {
+            git: 2,
+            api_per_forge: 1,
+            forges: BTreeMap::from([("codeberg".to_string(), 2)]),
+            containers: 1,
+            batch: 8,
+        });
+
+        let first = scheduler.acquire(Resource::Git).await;
+        let _second = scheduler.acquire(Resource::Git).await;
+        assert_eq!(scheduler.available(&Resource::Git), 0);
+
+        // Forges don't share a budget
+        let _github = scheduler.acquire(Resource::ForgeApi(Forge::GitHub)).await;
+        assert_eq!(scheduler.available(&Resource::ForgeApi(Forge::GitHub)), 0);
+        assert_eq!(scheduler.available(&Resource::ForgeApi(Forge::Codeberg)), 2);
+        assert_eq!(scheduler.available(&Resource::ForgeApi(Forge::GitLab)), 1);
+
+        drop(first);
+        assert_eq!(scheduler.available(&Resource::Git), 1);
+    }
+}
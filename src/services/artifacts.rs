@@ -0,0 +1,156 @@
+//! At-rest protection for run artifacts: the registry journal and the build
+//! run history
+//!
+//! Both can carry repo names and error strings worth keeping private.
+//! `[artifacts]` in ~/.config/hyperforge/config.toml sets:
+//!
+//! ```toml
+//! [artifacts]
+//! encrypt = true        # encrypt each line with a key held in the OS keychain
+//! retention_days = 90   # drop older entries whenever new ones are written
+//! ```
+//!
+//! The key is 32 random bytes made on first use and kept in the keychain as
+//! `hyperforge:artifacts/key`. It is never written to disk, so the files only
+//! decrypt on this device. Encrypted lines read `enc1:<base64>` (nonce,
+//! ciphertext, Poly1305 tag); plaintext lines from before encryption was
+//! turned on stay readable and are encrypted the next time the file is
+//! rewritten.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+use crate::auth::KeychainBridge;
+use crate::config::UserConfig;
+
+const LINE_PREFIX: &str = "enc1:";
+const KEY_PATH: &str = "artifacts/key";
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// How artifacts are written and how long entries live
+#[derive(Default)]
+pub struct ArtifactPolicy {
+    key: Option<Zeroizing<[u8; 32]>>,
+    retention: Option<TimeDelta>,
+}
+
+impl ArtifactPolicy {
+    /// Plaintext, kept forever
+    pub fn plaintext() -> Self {
+        Self::default()
+    }
+
+    pub fn new(key: Option<[u8; 32]>, retention_days: Option<u32>) -> Self {
+        Self {
+            key: key.map(Zeroizing::new),
+            retention: retention_days.map(|days| TimeDelta::days(i64::from(days))),
+        }
+    }
+
+    /// The policy `[artifacts]` in `config_dir`'s config.toml asks for,
+    /// creating the keychain key on first use. Fails rather than fall back
+    /// to plaintext when encryption is on but the keychain is unavailable.
+    pub async fn load(config_dir: &Path) -> Result<Self, String> {
+        let config = UserConfig::load(config_dir).artifacts;
+        let key = if config.encrypt { Some(device_key().await?) } else { None };
+        Ok(Self::new(key, config.retention_days))
+    }
+
+    /// [`load`](Self::load) from ~/.config/hyperforge
+    pub async fn load_default() -> Result<Self, String> {
+        let config_dir = dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".config")
+            .join("hyperforge");
+        Self::load(&config_dir).await
+    }
+
+    /// A line as it should be written
+    pub fn seal_line(&self, line: &str) -> Result<String, String> {
+        let Some(key) = &self.key else {
+            return Ok(line.to_string());
+        };
+        let mut nonce = [0u8; NONCE_LEN];
+        openssl::rand::rand_bytes(&mut nonce).map_err(|e| e.to_string())?;
+        let mut tag = [0u8; TAG_LEN];
+        let ciphertext = encrypt_aead(Cipher::chacha20_poly1305(), key.as_ref(), Some(&nonce), &[], line.as_bytes(), &mut tag)
+            .map_err(|e| format!("Failed to encrypt artifact: {e}"))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        sealed.extend_from_slice(&tag);
+        Ok(format!("{LINE_PREFIX}{}", openssl::base64::encode_block(&sealed)))
+    }
+
+    /// A line as it was before sealing; plaintext lines pass through
+    pub fn open_line(&self, line: &str) -> Result<String, String> {
+        let Some(encoded) = line.strip_prefix(LINE_PREFIX) else {
+            return Ok(line.to_string());
+        };
+        let key = self.key.as_ref().ok_or("entry is encrypted but [artifacts] encrypt is off")?;
+        let sealed = openssl::base64::decode_block(encoded).map_err(|e| format!("invalid encrypted entry: {e}"))?;
+        if sealed.len() < NONCE_LEN + TAG_LEN {
+            return Err("encrypted entry is truncated".to_string());
+        }
+        let (nonce, rest) = sealed.split_at(NONCE_LEN);
+        let (ciphertext, tag) = rest.split_at(rest.len() - TAG_LEN);
+        let plaintext = decrypt_aead(Cipher::chacha20_poly1305(), key.as_ref(), Some(nonce), &[], ciphertext, tag)
+            .map_err(|_| "encrypted entry doesn't decrypt with this device's key".to_string())?;
+        String::from_utf8(plaintext).map_err(|e| e.to_string())
+    }
+
+    /// Whether an entry recorded at `at` is past the retention period
+    pub fn is_expired(&self, at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        self.retention.is_some_and(|retention| now - at > retention)
+    }
+}
+
+/// This device's artifact key from the keychain, generated if missing
+async fn device_key() -> Result<[u8; 32], String> {
+    let keychain = KeychainBridge::global();
+    if let Some(encoded) = keychain.get(KEY_PATH).await? {
+        let decoded = Zeroizing::new(openssl::base64::decode_block(&encoded).map_err(|e| format!("invalid artifact key: {e}"))?);
+        return <[u8; 32]>::try_from(decoded.as_slice()).map_err(|_| "artifact key has the wrong length".to_string());
+    }
+
+    let mut key = [0u8; 32];
+    openssl::rand::rand_bytes(&mut key).map_err(|e| e.to_string())?;
+    keychain
+        .set(KEY_PATH, &openssl::base64::encode_block(&key))
+        .await
+        .map_err(|e| format!("Failed to store the artifact key in the keychain: {e}"))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open_lines() {
+        let policy = ArtifactPolicy::new(Some([7u8; 32]), None);
+        let line = r#"{"repo":"secret-project","field":"visibility"}"#;
+        let sealed = policy.seal_line(line).unwrap();
+        assert!(sealed.starts_with(LINE_PREFIX));
+        assert!(!sealed.contains("secret-project"));
+        assert_eq!(policy.open_line(&sealed).unwrap(), line);
+        // Plaintext lines from before encryption still read
+        assert_eq!(policy.open_line(line).unwrap(), line);
+
+        assert!(ArtifactPolicy::new(Some([8u8; 32]), None).open_line(&sealed).is_err());
+        assert!(ArtifactPolicy::plaintext().open_line(&sealed).is_err());
+        assert_eq!(ArtifactPolicy::plaintext().seal_line(line).unwrap(), line);
+    }
+
+    #[test]
+    fn test_retention() {
+        let now = Utc::now();
+        let policy = ArtifactPolicy::new(None, Some(30));
+        assert!(policy.is_expired(now - TimeDelta::days(31), now));
+        assert!(!policy.is_expired(now - TimeDelta::days(29), now));
+        assert!(!ArtifactPolicy::plaintext().is_expired(now - TimeDelta::days(3650), now));
+    }
+}
@@ -0,0 +1,129 @@
+//! Field assignments for bulk edits of `LocalForge` records
+//!
+//! ```text
+//! protected=true
+//! visibility=private
+//! forges+=codeberg      # add a mirror
+//! forges-=gitlab        # drop one
+//! default_branch=main
+//! ```
+
+use crate::types::{Forge, RepoRecord, Visibility};
+
+/// One field assignment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    Protected(bool),
+    Managed(bool),
+    Visibility(Visibility),
+    DefaultBranch(String),
+    AddForge(Forge),
+    RemoveForge(Forge),
+}
+
+/// A field change an edit made (or would make) to one record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub before: String,
+    pub after: String,
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    value.parse().map_err(|_| format!("Expected true or false, got '{value}'"))
+}
+
+impl Edit {
+    pub fn parse(assignment: &str) -> Result<Self, String> {
+        if let Some((key, value)) = assignment.split_once("+=") {
+            return match key.trim() {
+                "forges" => Ok(Self::AddForge(value.trim().parse()?)),
+                other => Err(format!("'+=' only applies to forges, not '{other}'")),
+            };
+        }
+        if let Some((key, value)) = assignment.split_once("-=") {
+            return match key.trim() {
+                "forges" => Ok(Self::RemoveForge(value.trim().parse()?)),
+                other => Err(format!("'-=' only applies to forges, not '{other}'")),
+            };
+        }
+        let (key, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid assignment '{assignment}', expected field=value"))?;
+        let value = value.trim();
+        match key.trim() {
+            "protected" => Ok(Self::Protected(parse_bool(value)?)),
+            "managed" => Ok(Self::Managed(parse_bool(value)?)),
+            "visibility" => Ok(Self::Visibility(Visibility::parse(value)?)),
+            "default_branch" if !value.is_empty() => Ok(Self::DefaultBranch(value.to_string())),
+            other => Err(format!(
+                "Unknown field '{other}'. Settable: protected, managed, visibility, default_branch, forges+=, forges-="
+            )),
+        }
+    }
+
+    /// Apply to `record`, returning the change or `None` if it already holds
+    pub fn apply(&self, record: &mut RepoRecord) -> Option<FieldChange> {
+        let change = |field, before: String, after: String| (before != after).then_some(FieldChange { field, before, after });
+        match self {
+            Self::Protected(value) => {
+                let before = std::mem::replace(&mut record.protected, *value);
+                change("protected", before.to_string(), value.to_string())
+            }
+            Self::Managed(value) => {
+                let before = std::mem::replace(&mut record.managed, *value);
+                change("managed", before.to_string(), value.to_string())
+            }
+            Self::Visibility(value) => {
+                let before = std::mem::replace(&mut record.visibility, value.clone());
+                change("visibility", before.as_str().to_string(), value.as_str().to_string())
+            }
+            Self::DefaultBranch(value) => {
+                let before = std::mem::replace(&mut record.default_branch, value.clone());
+                change("default_branch", before, value.clone())
+            }
+            Self::AddForge(forge) => {
+                if record.forges.iter().any(|f| f.eq_ignore_ascii_case(forge.as_str())) {
+                    return None;
+                }
+                let before = record.forges.join(",");
+                record.forges.push(forge.to_string());
+                change("forges", before, record.forges.join(","))
+            }
+            Self::RemoveForge(forge) => {
+                let before = record.forges.join(",");
+                record.forges.retain(|f| !f.eq_ignore_ascii_case(forge.as_str()));
+                change("forges", before, record.forges.join(","))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Repo;
+
+    #[test]
+    fn test_parse_and_apply() {
+        let mut record = RepoRecord::from_repo(&Repo::new("tool", Forge::GitHub));
+        record.forges = vec!["github".to_string()];
+
+        let add = Edit::parse("forges+=codeberg").unwrap();
+        let change = add.apply(&mut record).unwrap();
+        assert_eq!((change.before.as_str(), change.after.as_str()), ("github", "github,codeberg"));
+        assert_eq!(add.apply(&mut record), None);
+
+        let protect = Edit::parse("protected = true").unwrap();
+        assert_eq!(protect.apply(&mut record).unwrap().after, "true");
+        assert_eq!(protect.apply(&mut record), None);
+
+        Edit::parse("forges-=github").unwrap().apply(&mut record).unwrap();
+        assert_eq!(record.forges, vec!["codeberg".to_string()]);
+
+        assert!(Edit::parse("visibility=secret").is_err());
+        assert!(Edit::parse("name=other").is_err());
+        assert!(Edit::parse("protected+=true").is_err());
+        assert!(Edit::parse("protected").is_err());
+    }
+}
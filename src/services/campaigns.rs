@@ -0,0 +1,233 @@
+//! Change campaigns: multi-repo migrations tracked to completion
+//!
+//! A campaign is a script run in every targeted repo, proposed as one pull
+//! request per repo from a shared branch. Its definition and per-repo
+//! progress live in `<workspace>/.hyperforge/campaigns/<name>.toml`, so a
+//! migration can be run, re-run for failures, and reported on over days.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const CAMPAIGNS_DIR: &str = "campaigns";
+
+/// Where one repo is in a campaign
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CampaignStatus {
+    /// Script not run yet (or reset for a retry)
+    #[default]
+    Pending,
+    /// Script ran and made no changes; nothing to propose
+    Unchanged,
+    /// Branch pushed but the PR isn't open yet; a re-run only opens the PR
+    Pushed,
+    PrOpen,
+    Merged,
+    Failed,
+}
+
+impl CampaignStatus {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Unchanged => "unchanged",
+            Self::Pushed => "pushed",
+            Self::PrOpen => "pr_open",
+            Self::Merged => "merged",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// Whether `run` should (re)process a repo in this state
+    pub const fn is_runnable(self) -> bool {
+        matches!(self, Self::Pending | Self::Pushed | Self::Failed)
+    }
+}
+
+/// Progress of one repo
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoProgress {
+    pub status: CampaignStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forge: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A campaign definition plus per-repo progress
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Campaign {
+    pub name: String,
+    /// Used as commit message and PR title; the first line is the subject
+    pub description: String,
+    /// Shell command run in each repo's root
+    pub script: String,
+    pub branch: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Progress keyed by repo directory name
+    #[serde(default)]
+    pub repos: BTreeMap<String, RepoProgress>,
+}
+
+impl Campaign {
+    pub fn path(workspace_root: &Path, name: &str) -> PathBuf {
+        workspace_root
+            .join(crate::config::CONFIG_DIR)
+            .join(CAMPAIGNS_DIR)
+            .join(format!("{name}.toml"))
+    }
+
+    pub fn exists(workspace_root: &Path, name: &str) -> bool {
+        Self::path(workspace_root, name).exists()
+    }
+
+    pub fn load(workspace_root: &Path, name: &str) -> Result<Self, String> {
+        let path = Self::path(workspace_root, name);
+        let content = std::fs::read_to_string(&path)
+            .map_err(|_| format!("No campaign '{name}' — create it first ({} not found)", path.display()))?;
+        toml::from_str(&content).map_err(|e| format!("Failed to parse {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, workspace_root: &Path) -> Result<(), String> {
+        let path = Self::path(workspace_root, &self.name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize campaign: {e}"))?;
+        std::fs::write(&path, content)
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    /// Names of every campaign in the workspace
+    pub fn list(workspace_root: &Path) -> Vec<String> {
+        let dir = workspace_root.join(crate::config::CONFIG_DIR).join(CAMPAIGNS_DIR);
+        let mut names: Vec<String> = std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|e| {
+                let path = e.path();
+                (path.extension()? == "toml").then(|| path.file_stem()?.to_str().map(String::from))?
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// First line of the description, for PR titles
+    pub fn title(&self) -> &str {
+        self.description.lines().next().unwrap_or(&self.name)
+    }
+
+    /// Repo count per status, in status order
+    pub fn tally(&self) -> BTreeMap<CampaignStatus, usize> {
+        let mut counts = BTreeMap::new();
+        for progress in self.repos.values() {
+            *counts.entry(progress.status).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// One-line progress report, e.g. `3/10 done (2 merged, 1 unchanged), 5 pr_open, 2 pending`
+    pub fn summary(&self) -> String {
+        let tally = self.tally();
+        let get = |s| tally.get(&s).copied().unwrap_or(0);
+        let done = get(CampaignStatus::Merged) + get(CampaignStatus::Unchanged);
+        let mut parts = vec![format!(
+            "{done}/{} done ({} merged, {} unchanged)",
+            self.repos.len(),
+            get(CampaignStatus::Merged),
+            get(CampaignStatus::Unchanged)
+        )];
+        for status in [
+            CampaignStatus::PrOpen,
+            CampaignStatus::Pushed,
+            CampaignStatus::Failed,
+            CampaignStatus::Pending,
+        ] {
+            if get(status) > 0 {
+                parts.push(format!("{} {}", get(status), status.as_str()));
+            }
+        }
+        parts.join(", ")
+    }
+}
+
+/// Campaign names become file names and branch suffixes
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "Invalid campaign name '{name}' — use letters, digits, '-' and '_'"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn campaign() -> Campaign {
+        Campaign {
+            name: "edition-2021".to_string(),
+            description: "Move to edition 2021\n\nBumps every crate.".to_string(),
+            script: "sed -i 's/2018/2021/' Cargo.toml".to_string(),
+            branch: "campaign/edition-2021".to_string(),
+            ..Campaign::default()
+        }
+    }
+
+    #[test]
+    fn test_campaign_roundtrip_and_list() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut c = campaign();
+        c.repos.insert("api".to_string(), RepoProgress::default());
+        c.repos.insert(
+            "web".to_string(),
+            RepoProgress {
+                status: CampaignStatus::PrOpen,
+                forge: Some("github".to_string()),
+                url: Some("https://github.com/acme/web/pull/3".to_string()),
+                error: None,
+            },
+        );
+        c.save(temp.path()).unwrap();
+
+        assert_eq!(Campaign::load(temp.path(), "edition-2021").unwrap(), c);
+        assert_eq!(Campaign::list(temp.path()), vec!["edition-2021".to_string()]);
+        assert!(Campaign::load(temp.path(), "nope").is_err());
+    }
+
+    #[test]
+    fn test_summary_and_title() {
+        let mut c = campaign();
+        for (repo, status) in [
+            ("a", CampaignStatus::Merged),
+            ("b", CampaignStatus::Unchanged),
+            ("c", CampaignStatus::PrOpen),
+            ("d", CampaignStatus::Failed),
+            ("e", CampaignStatus::Pushed),
+        ] {
+            c.repos.insert(repo.to_string(), RepoProgress { status, ..RepoProgress::default() });
+        }
+        assert_eq!(c.summary(), "2/5 done (1 merged, 1 unchanged), 1 pr_open, 1 pushed, 1 failed");
+        assert!(CampaignStatus::Pushed.is_runnable());
+        assert_eq!(c.title(), "Move to edition 2021");
+        assert!(validate_name("edition-2021").is_ok());
+        assert!(validate_name("../x").is_err());
+    }
+}
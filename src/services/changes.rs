@@ -0,0 +1,89 @@
+//! Change manifests: the PRs opened for one managed change
+//!
+//! When a managed change is proposed as pull requests instead of committed
+//! to default branches, every opened PR is recorded in
+//! `<workspace>/.hyperforge/changes/<branch>.toml`, keyed by repo. Re-running
+//! the same change updates the manifest in place.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const CHANGES_DIR: &str = "changes";
+
+/// A PR opened for one repo
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangePr {
+    pub forge: String,
+    pub url: String,
+}
+
+/// Every PR opened for one change branch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeManifest {
+    pub branch: String,
+    pub title: String,
+    /// PRs keyed by repo directory name
+    #[serde(default)]
+    pub prs: BTreeMap<String, ChangePr>,
+}
+
+impl ChangeManifest {
+    /// Manifest location for `branch`; `/` in branch names becomes `-`
+    pub fn path(workspace_root: &Path, branch: &str) -> PathBuf {
+        workspace_root
+            .join(crate::config::CONFIG_DIR)
+            .join(CHANGES_DIR)
+            .join(format!("{}.toml", branch.replace('/', "-")))
+    }
+
+    /// Load the manifest for `branch`, or start a new one
+    pub fn load(workspace_root: &Path, branch: &str, title: &str) -> Result<Self, String> {
+        let path = Self::path(workspace_root, branch);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .map_err(|e| format!("Failed to parse {}: {e}", path.display())),
+            Err(_) => Ok(Self {
+                branch: branch.to_string(),
+                title: title.to_string(),
+                prs: BTreeMap::new(),
+            }),
+        }
+    }
+
+    pub fn save(&self, workspace_root: &Path) -> Result<(), String> {
+        let path = Self::path(workspace_root, &self.branch);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize change manifest: {e}"))?;
+        std::fs::write(&path, content)
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_roundtrip_and_path() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut manifest = ChangeManifest::load(temp.path(), "chore/editorconfig", "Sync editorconfig").unwrap();
+        assert!(manifest.prs.is_empty());
+        manifest.prs.insert(
+            "api".to_string(),
+            ChangePr {
+                forge: "github".to_string(),
+                url: "https://github.com/acme/api/pull/7".to_string(),
+            },
+        );
+        manifest.save(temp.path()).unwrap();
+
+        assert!(temp.path().join(".hyperforge/changes/chore-editorconfig.toml").exists());
+        let loaded = ChangeManifest::load(temp.path(), "chore/editorconfig", "ignored").unwrap();
+        assert_eq!(loaded, manifest);
+    }
+}
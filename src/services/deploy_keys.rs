@@ -0,0 +1,225 @@
+//! `DeployKeyService` - Keep a repo's configured deploy keys on its forges
+//!
+//! `[ssh_deploy_keys.<name>]` entries identify keys by title, so keys added
+//! by hand or by CI integrations are never touched. Converging one forge:
+//! - A key titled `<name>` with the configured public key and access is
+//!   left alone
+//! - One with another key or access is deleted and added again; forges
+//!   don't edit deploy keys in place
+//! - Missing keys are added
+//! - `remove = true` entries, and entries limited to other forges, are
+//!   deleted wherever they are found
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::adapters::{DeployKey, ForgeError, ForgePort, ForgeResult, NewDeployKey};
+use crate::types::DeployKeyConfig;
+
+/// How one configured key changes on a forge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeployKeyAction {
+    Add,
+    /// Present with another key or access: deleted and added again
+    Replace,
+    Remove,
+    Unchanged,
+}
+
+impl DeployKeyAction {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Add => "added",
+            Self::Replace => "replaced",
+            Self::Remove => "removed",
+            Self::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// What converging one configured key did (or would do)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeployKeyChange {
+    /// Name under `[ssh_deploy_keys]`, also the key's title on the forge
+    pub key: String,
+    pub action: DeployKeyAction,
+    /// Forge keys with the title that get deleted
+    pub stale_ids: Vec<u64>,
+}
+
+/// A public key as `<type> <base64>`, dropping any trailing comment
+fn key_material(key: &str) -> String {
+    key.split_whitespace().take(2).collect::<Vec<_>>().join(" ")
+}
+
+/// Service for converging deploy keys
+pub struct DeployKeyService;
+
+impl DeployKeyService {
+    /// Changes that make `existing` (the keys on the `forges` entry `forge`)
+    /// match `desired`. `public_keys` holds the key file contents of every
+    /// entry that should be on the forge.
+    pub fn plan(
+        desired: &BTreeMap<String, DeployKeyConfig>,
+        public_keys: &BTreeMap<String, String>,
+        forge: &str,
+        existing: &[DeployKey],
+    ) -> Vec<DeployKeyChange> {
+        let mut changes = Vec::new();
+        for (title, config) in desired {
+            let matching: Vec<&DeployKey> = existing.iter().filter(|k| k.title == *title).collect();
+            let stale_ids: Vec<u64> = matching.iter().map(|k| k.id).collect();
+
+            let action = match public_keys.get(title) {
+                Some(public_key) if !config.remove && config.applies_to(forge) => match matching.as_slice() {
+                    [] => DeployKeyAction::Add,
+                    [only] if key_material(&only.key) == key_material(public_key) && only.read_only == config.read_only => {
+                        DeployKeyAction::Unchanged
+                    }
+                    // Duplicates under the title collapse into one
+                    _ => DeployKeyAction::Replace,
+                },
+                _ => {
+                    if matching.is_empty() {
+                        continue;
+                    }
+                    DeployKeyAction::Remove
+                }
+            };
+
+            changes.push(DeployKeyChange {
+                key: title.clone(),
+                stale_ids: if action == DeployKeyAction::Unchanged { Vec::new() } else { stale_ids },
+                action,
+            });
+        }
+        changes
+    }
+
+    /// Read the public key of every entry that belongs on `forge`; a
+    /// missing or empty file is an error, so nothing is half-converged
+    pub fn read_public_keys(
+        desired: &BTreeMap<String, DeployKeyConfig>,
+        repo_path: &Path,
+        forge: &str,
+    ) -> ForgeResult<BTreeMap<String, String>> {
+        let mut keys = BTreeMap::new();
+        for (title, config) in desired.iter().filter(|(_, c)| !c.remove && c.applies_to(forge)) {
+            let path = config.key_path(repo_path);
+            let key = std::fs::read_to_string(&path)
+                .ok()
+                .map(|k| key_material(&k))
+                .filter(|k| !k.is_empty())
+                .ok_or_else(|| ForgeError::ApiError(format!("deploy key '{title}': cannot read public key {}", path.display())))?;
+            keys.insert(title.clone(), key);
+        }
+        Ok(keys)
+    }
+
+    /// Converge `org/name`'s deploy keys on the `forges` entry `forge`:
+    /// `None` if the forge doesn't expose deploy keys. Dry runs list but
+    /// never change.
+    pub async fn converge(
+        port: &dyn ForgePort,
+        org: &str,
+        name: &str,
+        forge: &str,
+        desired: &BTreeMap<String, DeployKeyConfig>,
+        public_keys: &BTreeMap<String, String>,
+        dry_run: bool,
+    ) -> ForgeResult<Option<Vec<DeployKeyChange>>> {
+        let Some(existing) = port.list_deploy_keys(org, name).await? else {
+            return Ok(None);
+        };
+        let changes = Self::plan(desired, public_keys, forge, &existing);
+        if dry_run {
+            return Ok(Some(changes));
+        }
+
+        for change in &changes {
+            if change.action == DeployKeyAction::Unchanged {
+                continue;
+            }
+            for id in &change.stale_ids {
+                port.remove_deploy_key(org, name, *id).await?;
+            }
+            if matches!(change.action, DeployKeyAction::Add | DeployKeyAction::Replace) {
+                let key = NewDeployKey {
+                    title: change.key.clone(),
+                    key: public_keys[&change.key].clone(),
+                    read_only: desired[&change.key].read_only,
+                };
+                port.add_deploy_key(org, name, &key).await?;
+            }
+        }
+
+        Ok(Some(changes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(read_only: bool) -> DeployKeyConfig {
+        DeployKeyConfig { key: "keys/ci.pub".to_string(), read_only, forges: Vec::new(), remove: false }
+    }
+
+    fn key(id: u64, title: &str, key: &str, read_only: bool) -> DeployKey {
+        DeployKey { id, title: title.to_string(), key: key.to_string(), read_only }
+    }
+
+    #[test]
+    fn test_plan_adds_replaces_and_removes() {
+        let mut desired = BTreeMap::new();
+        desired.insert("ci".to_string(), config(true));
+        desired.insert("deploy".to_string(), config(false));
+        desired.insert("docs".to_string(), config(true));
+        desired.insert("old".to_string(), DeployKeyConfig { remove: true, ..config(true) });
+        desired.insert("gitlab_only".to_string(), DeployKeyConfig { forges: vec!["gitlab".to_string()], ..config(true) });
+
+        let public_keys: BTreeMap<String, String> = [("ci", "ssh-ed25519 AAAA1"), ("deploy", "ssh-ed25519 AAAA2"), ("docs", "ssh-ed25519 AAAA3")]
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+
+        let existing = vec![
+            // Forges may echo the comment back; it doesn't count as a change
+            key(1, "ci", "ssh-ed25519 AAAA1 ci@example", true),
+            key(2, "deploy", "ssh-ed25519 AAAA2", true),
+            key(3, "old", "ssh-ed25519 AAAA9", true),
+            key(4, "gitlab_only", "ssh-ed25519 AAAA4", true),
+            key(5, "manual", "ssh-ed25519 AAAA5", false),
+        ];
+
+        let changes = DeployKeyService::plan(&desired, &public_keys, "github", &existing);
+        let actions: Vec<_> = changes.iter().map(|c| (c.key.as_str(), c.action, c.stale_ids.clone())).collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("ci", DeployKeyAction::Unchanged, vec![]),
+                ("deploy", DeployKeyAction::Replace, vec![2]),
+                ("docs", DeployKeyAction::Add, vec![]),
+                ("gitlab_only", DeployKeyAction::Remove, vec![4]),
+                ("old", DeployKeyAction::Remove, vec![3]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_public_keys_skips_removed_and_fails_on_missing() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join("keys")).unwrap();
+        std::fs::write(temp.path().join("keys/ci.pub"), "ssh-ed25519 AAAA1 ci@example\n").unwrap();
+
+        let mut desired = BTreeMap::new();
+        desired.insert("ci".to_string(), config(true));
+        desired.insert("old".to_string(), DeployKeyConfig { key: "keys/gone.pub".to_string(), remove: true, ..config(true) });
+        let keys = DeployKeyService::read_public_keys(&desired, temp.path(), "github").unwrap();
+        assert_eq!(keys["ci"], "ssh-ed25519 AAAA1");
+        assert!(!keys.contains_key("old"));
+
+        desired.insert("missing".to_string(), DeployKeyConfig { key: "keys/missing.pub".to_string(), ..config(true) });
+        assert!(DeployKeyService::read_public_keys(&desired, temp.path(), "github").is_err());
+    }
+}
@@ -0,0 +1,201 @@
+//! `IssueMigrationService` - Copy issues from one forge to another
+//!
+//! Code sync makes a new mirror a full copy of the repo's git content, but
+//! the issue tracker stays behind on the origin. Migration copies issues
+//! across so moving a repo between forges keeps its open work:
+//! - Issues are copied oldest first with title, body, labels and state
+//! - Each copy's body ends with a footer naming the source issue's URL
+//! - Issues whose footer is already on the target are skipped, so a
+//!   migration can be re-run after a partial failure
+
+use std::collections::HashSet;
+
+use crate::adapters::{ForgeResult, Issue, IssuePort, IssueState, NewIssue};
+
+/// What happened to one source issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueOutcome {
+    /// Copied to the target (or would be, on a dry run)
+    Created,
+    /// A copy with this issue's footer is already on the target
+    AlreadyMigrated,
+    /// Creating the copy failed; see `error`
+    Failed,
+}
+
+impl IssueOutcome {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::AlreadyMigrated => "already_migrated",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Result of migrating one issue
+#[derive(Debug, Clone)]
+pub struct MigratedIssue {
+    pub source: Issue,
+    pub outcome: IssueOutcome,
+    /// URL of the copy on the target, once created
+    pub target_url: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Service for migrating issues between forges
+pub struct IssueMigrationService;
+
+impl IssueMigrationService {
+    /// Footer appended to every migrated issue. Later runs look for it to
+    /// recognise issues they already copied.
+    pub fn footer(issue: &Issue) -> String {
+        format!("_Migrated from {}_", issue.url)
+    }
+
+    /// Body of the copy of `issue`
+    fn migrated_body(issue: &Issue) -> String {
+        let body = issue.body.trim_end();
+        if body.is_empty() {
+            Self::footer(issue)
+        } else {
+            format!("{body}\n\n---\n{}", Self::footer(issue))
+        }
+    }
+
+    /// Copy `org/name`'s issues from `source` to `target`: open ones only
+    /// unless `include_closed`. Dry runs list both sides but create nothing.
+    ///
+    /// Listing errors fail the migration; a failing create only fails its
+    /// own issue.
+    pub async fn migrate(
+        source: &dyn IssuePort,
+        target: &dyn IssuePort,
+        org: &str,
+        name: &str,
+        include_closed: bool,
+        dry_run: bool,
+    ) -> ForgeResult<Vec<MigratedIssue>> {
+        let state = if include_closed { None } else { Some(IssueState::Open) };
+        let mut issues = source.list_issues(org, name, state).await?;
+        // Oldest first, so the target's numbering follows the source's order
+        issues.sort_by_key(|issue| issue.number);
+
+        let existing = target.list_issues(org, name, None).await?;
+        let migrated: HashSet<String> = issues
+            .iter()
+            .map(Self::footer)
+            .filter(|footer| existing.iter().any(|e| e.body.contains(footer.as_str())))
+            .collect();
+
+        let mut results = Vec::new();
+        for issue in issues {
+            let mut result = MigratedIssue {
+                outcome: IssueOutcome::Created,
+                target_url: None,
+                error: None,
+                source: issue,
+            };
+            if migrated.contains(&Self::footer(&result.source)) {
+                result.outcome = IssueOutcome::AlreadyMigrated;
+            } else if !dry_run {
+                let copy = NewIssue {
+                    title: result.source.title.clone(),
+                    body: Self::migrated_body(&result.source),
+                    labels: result.source.labels.clone(),
+                    state: result.source.state,
+                };
+                match target.create_issue(org, name, &copy).await {
+                    Ok(created) => result.target_url = Some(created.url),
+                    Err(e) => {
+                        result.outcome = IssueOutcome::Failed;
+                        result.error = Some(e.to_string());
+                    }
+                }
+            }
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// In-memory issue tracker
+    struct Tracker {
+        url: &'static str,
+        issues: Mutex<Vec<Issue>>,
+    }
+
+    impl Tracker {
+        fn new(url: &'static str, issues: Vec<Issue>) -> Self {
+            Self { url, issues: Mutex::new(issues) }
+        }
+    }
+
+    #[async_trait]
+    impl IssuePort for Tracker {
+        async fn list_issues(&self, _org: &str, _name: &str, state: Option<IssueState>) -> ForgeResult<Vec<Issue>> {
+            let issues = self.issues.lock().unwrap();
+            Ok(issues.iter().filter(|i| state.is_none_or(|s| i.state == s)).cloned().collect())
+        }
+
+        async fn create_issue(&self, _org: &str, _name: &str, issue: &NewIssue) -> ForgeResult<Issue> {
+            let mut issues = self.issues.lock().unwrap();
+            let number = issues.len() as u64 + 1;
+            let created = Issue {
+                number,
+                title: issue.title.clone(),
+                body: issue.body.clone(),
+                labels: issue.labels.clone(),
+                state: issue.state,
+                url: format!("{}/{number}", self.url),
+            };
+            issues.push(created.clone());
+            Ok(created)
+        }
+    }
+
+    fn issue(number: u64, state: IssueState) -> Issue {
+        Issue {
+            number,
+            title: format!("Issue {number}"),
+            body: format!("Body {number}"),
+            labels: vec!["bug".to_string()],
+            state,
+            url: format!("https://github.com/alice/tool/issues/{number}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_copies_open_issues_once() {
+        let source = Tracker::new("gh", vec![issue(2, IssueState::Open), issue(1, IssueState::Open), issue(3, IssueState::Closed)]);
+        let target = Tracker::new("cb", Vec::new());
+
+        let dry = IssueMigrationService::migrate(&source, &target, "alice", "tool", false, true).await.unwrap();
+        assert_eq!(dry.len(), 2);
+        assert!(target.issues.lock().unwrap().is_empty());
+
+        let results = IssueMigrationService::migrate(&source, &target, "alice", "tool", false, false).await.unwrap();
+        let numbers: Vec<_> = results.iter().map(|r| (r.source.number, r.outcome)).collect();
+        assert_eq!(numbers, vec![(1, IssueOutcome::Created), (2, IssueOutcome::Created)]);
+        {
+            let copied = target.issues.lock().unwrap();
+            assert_eq!(copied[0].title, "Issue 1");
+            assert_eq!(copied[0].labels, vec!["bug"]);
+            assert!(copied[0].body.starts_with("Body 1\n"));
+            assert!(copied[0].body.ends_with("_Migrated from https://github.com/alice/tool/issues/1_"));
+        }
+
+        // Re-running with closed issues only copies the closed one
+        let rerun = IssueMigrationService::migrate(&source, &target, "alice", "tool", true, false).await.unwrap();
+        let outcomes: Vec<_> = rerun.iter().map(|r| r.outcome).collect();
+        assert_eq!(outcomes, vec![IssueOutcome::AlreadyMigrated, IssueOutcome::AlreadyMigrated, IssueOutcome::Created]);
+        assert_eq!(target.issues.lock().unwrap()[2].state, IssueState::Closed);
+    }
+}
@@ -0,0 +1,131 @@
+//! Registry journal: an append-only log of edits to `LocalForge` records
+//!
+//! Each line of `~/.config/hyperforge/orgs/{org}/journal.jsonl` is one
+//! field change, so bulk edits can be audited (and reverted by hand) without
+//! diffing `repos.yaml` history. `[artifacts]` can encrypt the entries and
+//! prune old ones (see [`super::artifacts`]).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::artifacts::ArtifactPolicy;
+
+/// One recorded field change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub at: DateTime<Utc>,
+    /// What made the change, e.g. `"registry.bulk_set"`
+    pub action: String,
+    pub repo: String,
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Path to an org's journal
+pub fn journal_path(config_dir: &Path, org: &str) -> PathBuf {
+    config_dir.join("orgs").join(org).join("journal.jsonl")
+}
+
+/// Append entries to an org's journal, sealed per `policy`. Entries past
+/// the retention period are pruned on the way, rewriting the file.
+pub fn append(config_dir: &Path, org: &str, entries: &[JournalEntry], policy: &ArtifactPolicy) -> Result<(), String> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let path = journal_path(config_dir, org);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+
+    let now = Utc::now();
+    let existing = read(config_dir, org, policy)?;
+    if existing.iter().any(|e| policy.is_expired(e.at, now)) {
+        let kept: Vec<&JournalEntry> = existing.iter().chain(entries).filter(|e| !policy.is_expired(e.at, now)).collect();
+        let mut content = String::new();
+        for entry in kept {
+            content.push_str(&seal(entry, policy)?);
+            content.push('\n');
+        }
+        return std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()));
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+    for entry in entries {
+        writeln!(file, "{}", seal(entry, policy)?).map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn seal(entry: &JournalEntry, policy: &ArtifactPolicy) -> Result<String, String> {
+    let line = serde_json::to_string(entry).map_err(|e| format!("Failed to serialize journal entry: {e}"))?;
+    policy.seal_line(&line)
+}
+
+/// Read an org's journal, oldest first; empty if there is none
+pub fn read(config_dir: &Path, org: &str, policy: &ArtifactPolicy) -> Result<Vec<JournalEntry>, String> {
+    let path = journal_path(config_dir, org);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Ok(Vec::new());
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let line = policy.open_line(line).map_err(|e| format!("Invalid entry in {}: {e}", path.display()))?;
+            serde_json::from_str(&line).map_err(|e| format!("Invalid entry in {}: {e}", path.display()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_and_read_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let policy = ArtifactPolicy::plaintext();
+        assert!(read(tmp.path(), "acme", &policy).unwrap().is_empty());
+
+        append(tmp.path(), "acme", &[entry("protected", Utc::now())], &policy).unwrap();
+        append(tmp.path(), "acme", &[entry("managed", Utc::now())], &policy).unwrap();
+
+        let entries = read(tmp.path(), "acme", &policy).unwrap();
+        assert_eq!(entries.iter().map(|e| e.field.as_str()).collect::<Vec<_>>(), vec!["protected", "managed"]);
+    }
+
+    #[test]
+    fn test_encrypted_journal_prunes_expired_entries() {
+        let tmp = TempDir::new().unwrap();
+        let plaintext = ArtifactPolicy::plaintext();
+        append(tmp.path(), "acme", &[entry("old", Utc::now() - chrono::TimeDelta::days(100))], &plaintext).unwrap();
+
+        let policy = ArtifactPolicy::new(Some([3u8; 32]), Some(30));
+        append(tmp.path(), "acme", &[entry("new", Utc::now())], &policy).unwrap();
+
+        let content = std::fs::read_to_string(journal_path(tmp.path(), "acme")).unwrap();
+        assert!(!content.contains("tool"));
+        let entries = read(tmp.path(), "acme", &policy).unwrap();
+        assert_eq!(entries.iter().map(|e| e.field.as_str()).collect::<Vec<_>>(), vec!["new"]);
+        assert!(read(tmp.path(), "acme", &plaintext).is_err());
+    }
+
+    fn entry(field: &str, at: DateTime<Utc>) -> JournalEntry {
+        JournalEntry {
+            at,
+            action: "registry.bulk_set".to_string(),
+            repo: "tool".to_string(),
+            field: field.to_string(),
+            before: "false".to_string(),
+            after: "true".to_string(),
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! `MirrorService` - Copy branches and tags from the origin forge to mirrors
+//!
+//! `workspace sync` only makes sure repos exist on every forge, and `push`
+//! only sends the local branch. Mirroring makes each mirror forge's branches
+//! and tags match the origin's exactly:
+//! - Origin refs are fetched into a private namespace
+//!   (`refs/hyperforge/mirror/`), so local branches and tags are untouched
+//! - Each mirror's refs are listed and compared ref by ref
+//! - Differing refs are force-pushed in one `git push` per mirror
+//! - With `prune`, refs only the mirror has are deleted from it
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::config::{HyperforgeConfig, RemoteLayout};
+use crate::git::Git;
+
+/// Namespace origin refs are fetched into
+const MIRROR_NAMESPACE: &str = "refs/hyperforge/mirror/";
+
+/// How one ref on a mirror changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefChange {
+    /// Only the origin has it
+    Create,
+    /// Both have it at different commits
+    Update,
+    /// Only the mirror has it (applied only with `prune`)
+    Delete,
+}
+
+impl RefChange {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// One ref that differs between origin and a mirror
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefUpdate {
+    /// Full ref name (`refs/heads/main`, `refs/tags/v1.0.0`)
+    pub name: String,
+    pub change: RefChange,
+    /// SHA on the mirror before the push
+    pub old: Option<String>,
+    /// SHA on the origin
+    pub new: Option<String>,
+}
+
+impl RefUpdate {
+    /// The `git push` refspec that applies this update
+    fn refspec(&self) -> String {
+        match self.change {
+            RefChange::Delete => format!(":{}", self.name),
+            RefChange::Create | RefChange::Update => {
+                let local = self.name.strip_prefix("refs/").unwrap_or(&self.name);
+                format!("+{MIRROR_NAMESPACE}{local}:{}", self.name)
+            }
+        }
+    }
+}
+
+/// Outcome of mirroring to one forge
+#[derive(Debug, Clone)]
+pub struct MirrorResult {
+    pub forge: String,
+    pub remote_name: String,
+    /// Refs that differ (deletes only appear with `prune`)
+    pub updates: Vec<RefUpdate>,
+    /// Refs only the mirror has, left alone because `prune` was off
+    pub extra_refs: usize,
+    /// Refs that already matched
+    pub in_sync: usize,
+    /// Whether `updates` were pushed
+    pub applied: bool,
+    pub error: Option<String>,
+}
+
+/// Service for mirroring refs between forges
+pub struct MirrorService;
+
+impl MirrorService {
+    /// Refs to push so `mirror` matches `origin`. Deletes are only planned
+    /// with `prune`. Returns the updates and the number of refs in sync.
+    pub fn plan(
+        origin: &BTreeMap<String, String>,
+        mirror: &BTreeMap<String, String>,
+        prune: bool,
+    ) -> (Vec<RefUpdate>, usize) {
+        let mut updates = Vec::new();
+        let mut in_sync = 0usize;
+
+        for (name, oid) in origin {
+            match mirror.get(name) {
+                Some(old) if old == oid => in_sync += 1,
+                old => updates.push(RefUpdate {
+                    name: name.clone(),
+                    change: if old.is_some() { RefChange::Update } else { RefChange::Create },
+                    old: old.cloned(),
+                    new: Some(oid.clone()),
+                }),
+            }
+        }
+
+        if prune {
+            for (name, oid) in mirror {
+                if !origin.contains_key(name) {
+                    updates.push(RefUpdate {
+                        name: name.clone(),
+                        change: RefChange::Delete,
+                        old: Some(oid.clone()),
+                        new: None,
+                    });
+                }
+            }
+        }
+
+        (updates, in_sync)
+    }
+
+    /// Mirror the origin forge's branches and tags to every other forge in
+    /// the repo's config. Dry runs list and compare but never push.
+    ///
+    /// Errors talking to the origin fail the whole repo; a failing mirror
+    /// only fails its own result.
+    pub fn mirror(path: &Path, prune: bool, dry_run: bool) -> Result<Vec<MirrorResult>, String> {
+        let config = HyperforgeConfig::load(path).map_err(|e| format!("Failed to load config: {e}"))?;
+        let Some(origin_forge) = config.forges.first() else {
+            return Err("No forges configured".to_string());
+        };
+        if config.remote_layout == RemoteLayout::MultiPush {
+            return Err("multi_push remote layout: every push already reaches all forges".to_string());
+        }
+        let origin_remote = config.remote_for_forge(origin_forge);
+
+        let origin = Git::ls_remote_refs(path, &origin_remote)
+            .map_err(|e| format!("Failed to list {origin_forge} ({origin_remote}) refs: {e}"))?;
+        // An empty listing is far more likely a broken origin than an
+        // intentionally empty repo; never prune every mirror ref off it
+        if origin.is_empty() && prune {
+            return Err(format!("{origin_forge} ({origin_remote}) has no branches or tags; refusing to prune mirrors"));
+        }
+        if !dry_run {
+            let heads = format!("+refs/heads/*:{MIRROR_NAMESPACE}heads/*");
+            let tags = format!("+refs/tags/*:{MIRROR_NAMESPACE}tags/*");
+            Git::fetch_refspecs(path, &origin_remote, &[&heads, &tags])
+                .map_err(|e| format!("Failed to fetch {origin_forge} ({origin_remote}): {e}"))?;
+        }
+
+        let mut results = Vec::new();
+        for forge in config.forges.iter().skip(1) {
+            let remote_name = config.remote_for_forge(forge);
+            let mut result = MirrorResult {
+                forge: forge.clone(),
+                remote_name: remote_name.clone(),
+                updates: Vec::new(),
+                extra_refs: 0,
+                in_sync: 0,
+                applied: false,
+                error: None,
+            };
+
+            let mirror = match Git::ls_remote_refs(path, &remote_name) {
+                Ok(refs) => refs,
+                Err(e) => {
+                    result.error = Some(format!("Failed to list refs: {e}"));
+                    results.push(result);
+                    continue;
+                }
+            };
+
+            let (updates, in_sync) = Self::plan(&origin, &mirror, prune);
+            if !prune {
+                result.extra_refs = mirror.keys().filter(|name| !origin.contains_key(*name)).count();
+            }
+            result.updates = updates;
+            result.in_sync = in_sync;
+
+            if !dry_run && !result.updates.is_empty() {
+                let refspecs: Vec<String> = result.updates.iter().map(RefUpdate::refspec).collect();
+                match Git::push_refspecs(path, &remote_name, &refspecs) {
+                    Ok(()) => result.applied = true,
+                    Err(e) => result.error = Some(e.to_string()),
+                }
+            }
+
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refs(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries.iter().map(|(n, o)| ((*n).to_string(), (*o).to_string())).collect()
+    }
+
+    #[test]
+    fn test_plan_creates_updates_and_prunes() {
+        let origin = refs(&[("refs/heads/main", "a1"), ("refs/heads/dev", "b1"), ("refs/tags/v1", "c1")]);
+        let mirror = refs(&[("refs/heads/main", "a0"), ("refs/tags/v1", "c1"), ("refs/heads/old", "d0")]);
+
+        let (updates, in_sync) = MirrorService::plan(&origin, &mirror, false);
+        assert_eq!(in_sync, 1);
+        let changes: Vec<_> = updates.iter().map(|u| (u.name.as_str(), u.change)).collect();
+        assert_eq!(changes, vec![("refs/heads/dev", RefChange::Create), ("refs/heads/main", RefChange::Update)]);
+
+        let (updates, _) = MirrorService::plan(&origin, &mirror, true);
+        let delete = updates.iter().find(|u| u.change == RefChange::Delete).unwrap();
+        assert_eq!((delete.name.as_str(), delete.old.as_deref()), ("refs/heads/old", Some("d0")));
+    }
+
+    #[test]
+    fn test_mirror_pushes_and_prunes_refs() {
+        use crate::commands::init::{init, InitOptions};
+        use std::process::Command;
+
+        let git = |dir: &Path, args: &[&str]| {
+            let out = Command::new("git").args(args).current_dir(dir).output().unwrap();
+            assert!(out.status.success(), "git {args:?}: {}", String::from_utf8_lossy(&out.stderr));
+        };
+        let temp = tempfile::TempDir::new().unwrap();
+        let (origin, mirror, repo) = (temp.path().join("origin.git"), temp.path().join("mirror.git"), temp.path().join("repo"));
+        for bare in [&origin, &mirror] {
+            std::fs::create_dir_all(bare).unwrap();
+            git(bare, &["init", "--bare", "-q"]);
+        }
+        std::fs::create_dir_all(&repo).unwrap();
+        init(&repo, InitOptions::new(vec!["github".to_string(), "codeberg".to_string()]).with_org("alice")).unwrap();
+        git(&repo, &["config", "user.email", "test@test.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        git(&repo, &["remote", "set-url", "origin", origin.to_str().unwrap()]);
+        git(&repo, &["remote", "set-url", "codeberg", mirror.to_str().unwrap()]);
+        std::fs::write(repo.join("a.txt"), "a").unwrap();
+        git(&repo, &["add", "a.txt"]);
+        git(&repo, &["commit", "-qm", "a"]);
+        git(&repo, &["tag", "v1"]);
+        git(&repo, &["push", "-q", "origin", "HEAD:refs/heads/main", "v1"]);
+        git(&repo, &["push", "-q", "codeberg", "HEAD:refs/heads/stale"]);
+
+        let dry = MirrorService::mirror(&repo, true, true).unwrap();
+        assert!(!dry[0].applied);
+        assert_eq!(dry[0].updates.len(), 3);
+
+        let results = MirrorService::mirror(&repo, true, false).unwrap();
+        assert!(results[0].applied, "{:?}", results[0].error);
+        let mirrored = Git::ls_remote_refs(&repo, "codeberg").unwrap();
+        assert_eq!(mirrored, Git::ls_remote_refs(&repo, "origin").unwrap());
+        assert!(!mirrored.contains_key("refs/heads/stale"));
+        // Local tags and branches are left alone
+        assert!(Git::resolve_commit(&repo, "refs/hyperforge/mirror/heads/main").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_refspecs() {
+        let update = RefUpdate {
+            name: "refs/tags/v1".to_string(),
+            change: RefChange::Create,
+            old: None,
+            new: Some("c1".to_string()),
+        };
+        assert_eq!(update.refspec(), "+refs/hyperforge/mirror/tags/v1:refs/tags/v1");
+        let delete = RefUpdate { change: RefChange::Delete, ..update };
+        assert_eq!(delete.refspec(), ":refs/tags/v1");
+    }
+}
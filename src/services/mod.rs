@@ -1,5 +1,26 @@
 //! Services for repository synchronization and management
 
+pub mod artifacts;
+pub mod bulk_edit;
+pub mod campaigns;
+pub mod changes;
+pub mod deploy_keys;
+pub mod issue_migration;
+pub mod journal;
+pub mod mirror;
+pub mod query;
+pub mod snapshot;
 pub mod symmetric_sync;
+pub mod sync_checkpoint;
+pub mod sync_plan;
+pub mod telemetry;
+pub mod updates;
+pub mod upgrade;
+pub mod webhooks;
 
-pub use symmetric_sync::{SymmetricSyncService, SyncDiff, SyncOp};
+pub use artifacts::ArtifactPolicy;
+pub use deploy_keys::{DeployKeyAction, DeployKeyChange, DeployKeyService};
+pub use issue_migration::{IssueMigrationService, IssueOutcome, MigratedIssue};
+pub use mirror::{MirrorResult, MirrorService, RefChange, RefUpdate};
+pub use symmetric_sync::{ProtectionDiff, SymmetricSyncService, SyncDiff, SyncOp};
+pub use webhooks::{WebhookAction, WebhookChange, WebhookService};
@@ -0,0 +1,252 @@
+//! Registry queries: a small filter language over `LocalForge` records
+//!
+//! ```text
+//! on:github and not on:codeberg        # present on GitHub, missing from Codeberg
+//! private managed                      # adjacent terms are ANDed
+//! (staged or dismissed) name:old-*
+//! forge:gitlab visibility:public
+//! ```
+//!
+//! Terms:
+//! - `on:<forge>` — the repo exists on the forge (`present_on`)
+//! - `forge:<forge>` — the repo is configured to sync to the forge
+//! - `visibility:<public|private>`, or bare `public` / `private`
+//! - `managed`, `dismissed`, `protected`, `staged` (privatized somewhere
+//!   pending deletion, or dismissed)
+//! - `name:<glob>`, `branch:<name>`
+//! - `has:<ci|dist|local_path|description>`
+//!
+//! Combine with `and` (or juxtaposition), `or`, `not` and parentheses; `and`
+//! binds tighter than `or`.
+
+use crate::hubs::utils::glob_match;
+use crate::types::{Forge, RepoRecord, Visibility};
+
+/// A parsed registry query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// Matches every record (the empty query)
+    All,
+    Term(Term),
+    Not(Box<Self>),
+    And(Box<Self>, Box<Self>),
+    Or(Box<Self>, Box<Self>),
+}
+
+/// A single predicate over a record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    On(Forge),
+    ConfiguredFor(Forge),
+    Visibility(Visibility),
+    Managed,
+    Dismissed,
+    Protected,
+    Staged,
+    Name(String),
+    Branch(String),
+    Has(Field),
+}
+
+/// Optional record fields `has:` tests for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Ci,
+    Dist,
+    LocalPath,
+    Description,
+}
+
+impl Term {
+    fn parse(token: &str) -> Result<Self, String> {
+        let term = match token.split_once(':') {
+            None => match token {
+                "managed" => Self::Managed,
+                "dismissed" => Self::Dismissed,
+                "protected" => Self::Protected,
+                "staged" => Self::Staged,
+                "public" => Self::Visibility(Visibility::Public),
+                "private" => Self::Visibility(Visibility::Private),
+                other => return Err(format!("Unknown query term '{other}'")),
+            },
+            Some((key, value)) => match key {
+                "on" => Self::On(value.parse()?),
+                "forge" => Self::ConfiguredFor(value.parse()?),
+                "visibility" => Self::Visibility(Visibility::parse(value)?),
+                "name" => Self::Name(value.to_string()),
+                "branch" => Self::Branch(value.to_string()),
+                "has" => Self::Has(match value {
+                    "ci" => Field::Ci,
+                    "dist" => Field::Dist,
+                    "local_path" => Field::LocalPath,
+                    "description" => Field::Description,
+                    other => {
+                        return Err(format!(
+                            "Unknown field '{other}' in has:. Must be ci, dist, local_path, or description"
+                        ))
+                    }
+                }),
+                other => return Err(format!("Unknown query key '{other}:'")),
+            },
+        };
+        Ok(term)
+    }
+
+    fn matches(&self, record: &RepoRecord) -> bool {
+        match self {
+            Self::On(forge) => record.present_on.contains(forge),
+            Self::ConfiguredFor(forge) => record.forges.iter().any(|f| f.eq_ignore_ascii_case(forge.as_str())),
+            Self::Visibility(v) => record.visibility == *v,
+            Self::Managed => record.managed,
+            Self::Dismissed => record.dismissed,
+            Self::Protected => record.protected,
+            Self::Staged => record.dismissed || !record.privatized_on.is_empty(),
+            Self::Name(pattern) => glob_match(pattern, &record.name),
+            Self::Branch(branch) => record.default_branch == *branch,
+            Self::Has(Field::Ci) => record.ci.is_some(),
+            Self::Has(Field::Dist) => record.dist.is_some(),
+            Self::Has(Field::LocalPath) => record.local_path.is_some(),
+            Self::Has(Field::Description) => record.description.as_deref().is_some_and(|d| !d.is_empty()),
+        }
+    }
+}
+
+impl Query {
+    /// Parse a query expression; blank input matches everything
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let tokens = tokenize(input);
+        if tokens.is_empty() {
+            return Ok(Self::All);
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let query = parser.or()?;
+        match parser.peek() {
+            None => Ok(query),
+            Some(token) => Err(format!("Unexpected '{token}' in query")),
+        }
+    }
+
+    pub fn matches(&self, record: &RepoRecord) -> bool {
+        match self {
+            Self::All => true,
+            Self::Term(term) => term.matches(record),
+            Self::Not(inner) => !inner.matches(record),
+            Self::And(a, b) => a.matches(record) && b.matches(record),
+            Self::Or(a, b) => a.matches(record) || b.matches(record),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Vec<String> {
+    input
+        .replace('(', " ( ")
+        .replace(')', " ) ")
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Query, String> {
+        let mut query = self.and()?;
+        while self.peek() == Some("or") {
+            self.pos += 1;
+            query = Query::Or(Box::new(query), Box::new(self.and()?));
+        }
+        Ok(query)
+    }
+
+    fn and(&mut self) -> Result<Query, String> {
+        let mut query = self.unary()?;
+        loop {
+            match self.peek() {
+                Some("and") => self.pos += 1,
+                None | Some("or" | ")") => return Ok(query),
+                Some(_) => {}
+            }
+            query = Query::And(Box::new(query), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Query, String> {
+        match self.next().as_deref() {
+            None => Err("Query ends unexpectedly".to_string()),
+            Some("not") => Ok(Query::Not(Box::new(self.unary()?))),
+            Some("(") => {
+                let query = self.or()?;
+                match self.next().as_deref() {
+                    Some(")") => Ok(query),
+                    _ => Err("Missing ')' in query".to_string()),
+                }
+            }
+            Some(token @ (")" | "and" | "or")) => Err(format!("Unexpected '{token}' in query")),
+            Some(token) => Term::parse(token).map(Query::Term),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, on: &[Forge]) -> RepoRecord {
+        let mut record = RepoRecord::from_repo(&crate::types::Repo::new(name, on[0].clone()));
+        record.present_on = on.iter().cloned().collect();
+        record
+    }
+
+    #[test]
+    fn test_parse_precedence_and_errors() {
+        let q = Query::parse("managed or private on:github").unwrap();
+        assert_eq!(
+            q,
+            Query::Or(
+                Box::new(Query::Term(Term::Managed)),
+                Box::new(Query::And(
+                    Box::new(Query::Term(Term::Visibility(Visibility::Private))),
+                    Box::new(Query::Term(Term::On(Forge::GitHub))),
+                )),
+            )
+        );
+        assert_eq!(Query::parse("  ").unwrap(), Query::All);
+        assert!(Query::parse("on:sourcehut").is_err());
+        assert!(Query::parse("(managed").is_err());
+        assert!(Query::parse("managed or").is_err());
+        assert!(Query::parse("labels:x").is_err());
+    }
+
+    #[test]
+    fn test_matches_records() {
+        let mut mirrored = record("tool", &[Forge::GitHub, Forge::Codeberg]);
+        mirrored.managed = true;
+        let mut old = record("old-api", &[Forge::GitHub]);
+        old.visibility = Visibility::Private;
+        old.privatized_on.insert(Forge::GitHub);
+
+        let names = |query: &str| -> Vec<String> {
+            let q = Query::parse(query).unwrap();
+            [&mirrored, &old].iter().filter(|r| q.matches(r)).map(|r| r.name.clone()).collect()
+        };
+
+        assert_eq!(names("on:github and not on:codeberg"), vec!["old-api"]);
+        assert_eq!(names("staged or managed"), vec!["tool", "old-api"]);
+        assert_eq!(names("not (private or name:t*)"), Vec::<String>::new());
+        assert_eq!(names("visibility:public managed"), vec!["tool"]);
+        assert_eq!(names(""), vec!["tool", "old-api"]);
+    }
+}
@@ -0,0 +1,326 @@
+//! Remote-state snapshots
+//!
+//! Captures the metadata each forge reports for an org's repos into a
+//! timestamped JSON file, and diffs two snapshots. This tracks what changed
+//! on the forges themselves, independent of any local workspace state.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::types::{Repo, Visibility};
+
+/// Directory (under the workspace `.hyperforge/`) holding snapshot files
+pub const SNAPSHOT_DIR: &str = "snapshots";
+
+/// Remote metadata recorded for a single repo
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub visibility: Visibility,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+}
+
+impl From<&Repo> for RepoMeta {
+    fn from(repo: &Repo) -> Self {
+        Self {
+            description: repo.description.clone(),
+            visibility: repo.visibility.clone(),
+            default_branch: repo.default_branch.clone(),
+            topics: repo.topics.clone(),
+            archived: repo.archived,
+        }
+    }
+}
+
+/// All repos one forge reports for one org
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotScope {
+    pub org: String,
+    pub forge: String,
+    #[serde(default)]
+    pub repos: BTreeMap<String, RepoMeta>,
+    /// Set when listing failed; `repos` is empty and shouldn't be diffed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A point-in-time capture of remote repo metadata
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub taken_at: DateTime<Utc>,
+    pub scopes: Vec<SnapshotScope>,
+}
+
+/// What happened to a repo between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Changed => "changed",
+        }
+    }
+}
+
+/// A single difference between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotChange {
+    pub org: String,
+    pub forge: String,
+    pub repo_name: String,
+    pub kind: ChangeKind,
+    /// Field that changed (only for `Changed`)
+    pub field: Option<String>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl Snapshot {
+    pub fn new(scopes: Vec<SnapshotScope>) -> Self {
+        let mut scopes = scopes;
+        scopes.sort_by(|a, b| a.org.cmp(&b.org).then(a.forge.cmp(&b.forge)));
+        Self {
+            taken_at: Utc::now(),
+            scopes,
+        }
+    }
+
+    /// Default location for a new snapshot:
+    /// `<workspace>/.hyperforge/snapshots/<UTC timestamp>.json`
+    pub fn default_path(workspace_root: &Path, taken_at: DateTime<Utc>) -> PathBuf {
+        workspace_root
+            .join(crate::config::CONFIG_DIR)
+            .join(SNAPSHOT_DIR)
+            .join(format!("{}.json", taken_at.format("%Y%m%dT%H%M%SZ")))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read snapshot {}: {e}", path.display()))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse snapshot {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create snapshot dir: {e}"))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize snapshot: {e}"))?;
+        std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write snapshot {}: {e}", path.display()))
+    }
+
+    /// Total repos across all scopes
+    pub fn repo_count(&self) -> usize {
+        self.scopes.iter().map(|s| s.repos.len()).sum()
+    }
+
+    fn scope(&self, org: &str, forge: &str) -> Option<&SnapshotScope> {
+        self.scopes.iter().find(|s| s.org == org && s.forge == forge)
+    }
+}
+
+/// Compare two snapshots. Scopes that failed to list in either snapshot are
+/// skipped rather than reported as mass additions/removals.
+pub fn diff(before: &Snapshot, after: &Snapshot) -> Vec<SnapshotChange> {
+    let mut keys: Vec<(&str, &str)> = before
+        .scopes
+        .iter()
+        .chain(&after.scopes)
+        .map(|s| (s.org.as_str(), s.forge.as_str()))
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    let empty = BTreeMap::new();
+    let mut changes = Vec::new();
+
+    for (org, forge) in keys {
+        let old = before.scope(org, forge);
+        let new = after.scope(org, forge);
+        if old.is_some_and(|s| s.error.is_some()) || new.is_some_and(|s| s.error.is_some()) {
+            continue;
+        }
+        let old_repos = old.map_or(&empty, |s| &s.repos);
+        let new_repos = new.map_or(&empty, |s| &s.repos);
+
+        let change = |repo: &str, kind, field: Option<&str>, b: Option<String>, a: Option<String>| {
+            SnapshotChange {
+                org: org.to_string(),
+                forge: forge.to_string(),
+                repo_name: repo.to_string(),
+                kind,
+                field: field.map(str::to_string),
+                before: b,
+                after: a,
+            }
+        };
+
+        for (name, old_meta) in old_repos {
+            let Some(new_meta) = new_repos.get(name) else {
+                changes.push(change(name, ChangeKind::Removed, None, None, None));
+                continue;
+            };
+            if old_meta.visibility != new_meta.visibility {
+                changes.push(change(
+                    name,
+                    ChangeKind::Changed,
+                    Some("visibility"),
+                    Some(old_meta.visibility.as_str().to_string()),
+                    Some(new_meta.visibility.as_str().to_string()),
+                ));
+            }
+            if old_meta.description != new_meta.description {
+                changes.push(change(
+                    name,
+                    ChangeKind::Changed,
+                    Some("description"),
+                    old_meta.description.clone(),
+                    new_meta.description.clone(),
+                ));
+            }
+            if old_meta.default_branch != new_meta.default_branch {
+                changes.push(change(
+                    name,
+                    ChangeKind::Changed,
+                    Some("default_branch"),
+                    old_meta.default_branch.clone(),
+                    new_meta.default_branch.clone(),
+                ));
+            }
+            if old_meta.topics != new_meta.topics {
+                changes.push(change(
+                    name,
+                    ChangeKind::Changed,
+                    Some("topics"),
+                    Some(old_meta.topics.join(",")),
+                    Some(new_meta.topics.join(",")),
+                ));
+            }
+            if old_meta.archived != new_meta.archived {
+                changes.push(change(
+                    name,
+                    ChangeKind::Changed,
+                    Some("archived"),
+                    Some(old_meta.archived.to_string()),
+                    Some(new_meta.archived.to_string()),
+                ));
+            }
+        }
+        for name in new_repos.keys() {
+            if !old_repos.contains_key(name) {
+                changes.push(change(name, ChangeKind::Added, None, None, None));
+            }
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(visibility: Visibility, description: Option<&str>) -> RepoMeta {
+        RepoMeta {
+            description: description.map(str::to_string),
+            visibility,
+            default_branch: Some("main".to_string()),
+            topics: vec![],
+            archived: false,
+        }
+    }
+
+    fn scope(forge: &str, repos: &[(&str, RepoMeta)]) -> SnapshotScope {
+        SnapshotScope {
+            org: "acme".to_string(),
+            forge: forge.to_string(),
+            repos: repos
+                .iter()
+                .map(|(n, m)| ((*n).to_string(), m.clone()))
+                .collect(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_added_removed_changed() {
+        let before = Snapshot::new(vec![scope(
+            "github",
+            &[
+                ("api", meta(Visibility::Private, Some("API"))),
+                ("old", meta(Visibility::Public, None)),
+            ],
+        )]);
+        let after = Snapshot::new(vec![scope(
+            "github",
+            &[
+                ("api", meta(Visibility::Public, Some("API"))),
+                ("new", meta(Visibility::Public, None)),
+            ],
+        )]);
+
+        let changes = diff(&before, &after);
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].repo_name, "api");
+        assert_eq!(changes[0].kind, ChangeKind::Changed);
+        assert_eq!(changes[0].field.as_deref(), Some("visibility"));
+        assert_eq!(changes[0].before.as_deref(), Some("private"));
+        assert_eq!(changes[0].after.as_deref(), Some("public"));
+        assert_eq!(changes[1].repo_name, "old");
+        assert_eq!(changes[1].kind, ChangeKind::Removed);
+        assert_eq!(changes[2].repo_name, "new");
+        assert_eq!(changes[2].kind, ChangeKind::Added);
+    }
+
+    #[test]
+    fn test_diff_topics_and_archived() {
+        let before = Snapshot::new(vec![scope("github", &[("api", meta(Visibility::Public, None))])]);
+        let mut retired = meta(Visibility::Public, None);
+        retired.topics = vec!["cli".to_string(), "rust".to_string()];
+        retired.archived = true;
+        let after = Snapshot::new(vec![scope("github", &[("api", retired)])]);
+
+        let changes = diff(&before, &after);
+        let fields: Vec<_> = changes.iter().map(|c| (c.field.as_deref(), c.after.as_deref())).collect();
+        assert_eq!(fields, vec![(Some("topics"), Some("cli,rust")), (Some("archived"), Some("true"))]);
+    }
+
+    #[test]
+    fn test_diff_skips_failed_scopes() {
+        let before = Snapshot::new(vec![scope("github", &[("api", meta(Visibility::Public, None))])]);
+        let mut failed = scope("github", &[]);
+        failed.error = Some("401".to_string());
+        let after = Snapshot::new(vec![failed]);
+
+        assert!(diff(&before, &after).is_empty());
+    }
+
+    #[test]
+    fn test_save_load_roundtrip() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let snapshot = Snapshot::new(vec![scope("codeberg", &[("api", meta(Visibility::Public, None))])]);
+        let path = Snapshot::default_path(tmp.path(), snapshot.taken_at);
+        snapshot.save(&path).unwrap();
+
+        assert!(path.starts_with(tmp.path().join(".hyperforge/snapshots")));
+        assert_eq!(Snapshot::load(&path).unwrap(), snapshot);
+    }
+}
@@ -8,11 +8,15 @@
 //! Origin-based logic:
 //! - Each repo has one origin forge (source of truth)
 //! - Repos are synced to origin first, then mirrored to other forges
+//!
+//! Branch protection is diffed per repo rather than per org: forges only
+//! report it one branch at a time.
 
 use std::sync::Arc;
 
-use crate::adapters::{ForgePort, ForgeResult};
-use crate::types::Repo;
+use crate::adapters::{BranchProtection, ForgePort, ForgeResult};
+use crate::types::repo::{name_key, normalize_topics};
+use crate::types::{fit_description, fit_repo, Forge, Repo};
 
 /// Sync operation type
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,8 +27,13 @@ pub enum SyncOp {
     Update,
     /// Delete repo on target (exists but marked for deletion)
     Delete,
+    /// Archive repo on target (retired in the source, still active there)
+    Archive,
     /// No action needed (in sync)
     InSync,
+    /// Update or delete needed, but the token can only read the target's
+    /// copy; never applied
+    ReadOnly,
 }
 
 /// Repo with its sync operation
@@ -34,6 +43,10 @@ pub struct RepoOp {
     pub op: SyncOp,
     /// What specifically differs (e.g. "description", "visibility", "3 commits ahead")
     pub details: Vec<String>,
+    /// The target's name for the repo when it differs from the source's only
+    /// in case. Forges match names case-insensitively, so this is the same
+    /// repo; renaming one side is a suggestion, never applied by sync.
+    pub target_name: Option<String>,
 }
 
 /// Diff between source and target forges
@@ -73,6 +86,15 @@ impl SyncDiff {
             .collect()
     }
 
+    /// Get repos that need to be archived
+    pub fn to_archive(&self) -> Vec<&Repo> {
+        self.ops
+            .iter()
+            .filter(|op| op.op == SyncOp::Archive)
+            .map(|op| &op.repo)
+            .collect()
+    }
+
     /// Get repos that are already in sync
     pub fn in_sync(&self) -> Vec<&Repo> {
         self.ops
@@ -82,12 +104,37 @@ impl SyncDiff {
             .collect()
     }
 
-    /// Check if any changes are needed
+    /// Get repos whose target copy needs changes the token can't make
+    pub fn read_only(&self) -> Vec<&Repo> {
+        self.ops
+            .iter()
+            .filter(|op| op.op == SyncOp::ReadOnly)
+            .map(|op| &op.repo)
+            .collect()
+    }
+
+    /// Ops whose source and target names differ only in case
+    pub fn case_mismatches(&self) -> Vec<&RepoOp> {
+        self.ops.iter().filter(|op| op.target_name.is_some()).collect()
+    }
+
+    /// Check if any applicable changes are needed
     pub fn has_changes(&self) -> bool {
-        self.ops.iter().any(|op| op.op != SyncOp::InSync)
+        self.ops.iter().any(|op| !matches!(op.op, SyncOp::InSync | SyncOp::ReadOnly))
     }
 }
 
+/// A branch's protection on one forge against the configured rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtectionDiff {
+    pub branch: String,
+    pub current: BranchProtection,
+    pub desired: BranchProtection,
+    /// What differs (`required_reviews`, `status_checks`, `force_push`);
+    /// empty when in sync
+    pub details: Vec<String>,
+}
+
 /// Service for symmetric forge synchronization
 pub struct SymmetricSyncService;
 
@@ -112,14 +159,26 @@ impl SymmetricSyncService {
         target: Arc<dyn ForgePort>,
         org: &str,
     ) -> ForgeResult<SyncDiff> {
-        // Get repos from both forges
         let source_repos = source.list_repos(org).await?;
+        self.diff_repos(source_repos, target.as_ref(), org).await
+    }
+
+    /// Diff already-listed source repos against `target`. Source repos are
+    /// moved into the resulting ops and target repos are dropped as they are
+    /// matched, so at most one copy of each side is alive at a time.
+    async fn diff_repos(
+        &self,
+        source_repos: impl IntoIterator<Item = Repo> + Send,
+        target: &dyn ForgePort,
+        org: &str,
+    ) -> ForgeResult<SyncDiff> {
         let target_repos = target.list_repos(org).await?;
+        let target_forge = target.forge();
 
-        // Build map for quick lookup
+        // Build map for quick lookup, keyed case-insensitively like the forges
         let mut target_map: std::collections::HashMap<String, Repo> = target_repos
             .into_iter()
-            .map(|r| (r.name.clone(), r))
+            .map(|r| (name_key(&r.name), r))
             .collect();
 
         let mut ops = Vec::new();
@@ -128,38 +187,57 @@ impl SymmetricSyncService {
         for source_repo in source_repos {
             // Staged for deletion: delete from target if present, otherwise skip
             if source_repo.staged_for_deletion {
-                if target_map.remove(&source_repo.name).is_some() {
+                if let Some(target_repo) = target_map.remove(&name_key(&source_repo.name)) {
                     ops.push(RepoOp {
                         repo: source_repo,
-                        op: SyncOp::Delete,
+                        op: if target_repo.read_only { SyncOp::ReadOnly } else { SyncOp::Delete },
                         details: vec![],
+                        target_name: None,
                     });
                 }
                 continue;
             }
 
-            if let Some(target_repo) = target_map.remove(&source_repo.name) {
+            // Archived: archive the target's copy; nothing else changes on an
+            // archived repo, and a retired repo isn't created anywhere new
+            if source_repo.archived {
+                if let Some(target_repo) = target_map.remove(&name_key(&source_repo.name)) {
+                    let op = match (target_repo.archived, target_repo.read_only) {
+                        (true, _) => SyncOp::InSync,
+                        (false, true) => SyncOp::ReadOnly,
+                        (false, false) => SyncOp::Archive,
+                    };
+                    let details = if op == SyncOp::InSync { vec![] } else { vec!["archived".to_string()] };
+                    let target_name = (target_repo.name != source_repo.name).then_some(target_repo.name);
+                    ops.push(RepoOp { repo: source_repo, op, details, target_name });
+                }
+                continue;
+            }
+
+            if let Some(target_repo) = target_map.remove(&name_key(&source_repo.name)) {
                 // Repo exists on both - check if update needed
-                let details = repo_diff_details(&source_repo, &target_repo);
-                if details.is_empty() {
-                    ops.push(RepoOp {
-                        repo: source_repo,
-                        op: SyncOp::InSync,
-                        details: vec![],
-                    });
+                let details = repo_diff_details(&source_repo, &target_repo, target_forge.as_ref());
+                let target_name = (target_repo.name != source_repo.name).then_some(target_repo.name);
+                let op = if details.is_empty() {
+                    SyncOp::InSync
+                } else if target_repo.read_only {
+                    SyncOp::ReadOnly
                 } else {
-                    ops.push(RepoOp {
-                        repo: source_repo,
-                        op: SyncOp::Update,
-                        details,
-                    });
-                }
+                    SyncOp::Update
+                };
+                ops.push(RepoOp {
+                    repo: source_repo,
+                    op,
+                    details,
+                    target_name,
+                });
             } else {
                 // Repo only in source - needs creation on target
                 ops.push(RepoOp {
                     repo: source_repo,
                     op: SyncOp::Create,
                     details: vec![],
+                    target_name: None,
                 });
             }
         }
@@ -167,12 +245,17 @@ impl SymmetricSyncService {
         // Remaining target repos not in source - mark for deletion
         for (_, target_repo) in target_map {
             ops.push(RepoOp {
+                op: if target_repo.read_only { SyncOp::ReadOnly } else { SyncOp::Delete },
                 repo: target_repo,
-                op: SyncOp::Delete,
                 details: vec![],
+                target_name: None,
             });
         }
 
+        // Listings arrive in page-completion order and the leftovers in hash
+        // order; reports list repos by name
+        ops.sort_by(|a, b| name_key(&a.repo.name).cmp(&name_key(&b.repo.name)).then_with(|| a.repo.name.cmp(&b.repo.name)));
+
         Ok(SyncDiff {
             org: org.to_string(),
             ops,
@@ -196,26 +279,43 @@ impl SymmetricSyncService {
         org: &str,
         dry_run: bool,
     ) -> ForgeResult<SyncDiff> {
-        let diff = self.diff(source.clone(), target.clone(), org).await?;
+        let diff = self.diff(source, target.clone(), org).await?;
+        Self::apply(diff, target.as_ref(), org, dry_run).await
+    }
 
+    /// Execute a diff's operations on `target` (no-op when `dry_run`)
+    async fn apply(
+        diff: SyncDiff,
+        target: &dyn ForgePort,
+        org: &str,
+        dry_run: bool,
+    ) -> ForgeResult<SyncDiff> {
         if dry_run {
             return Ok(diff);
         }
 
-        // Execute operations
+        // Execute operations, with metadata fitted to the target's limits
+        let target_forge = target.forge();
+        let fitted = |repo: &Repo| match &target_forge {
+            Some(forge) => fit_repo(forge, repo).0,
+            None => repo.clone(),
+        };
         for op in &diff.ops {
             match op.op {
                 SyncOp::Create => {
-                    target.create_repo(org, &op.repo).await?;
+                    target.create_repo(org, &fitted(&op.repo)).await?;
                 }
                 SyncOp::Update => {
-                    target.update_repo(org, &op.repo).await?;
+                    target.update_repo(org, &fitted(&op.repo)).await?;
                 }
                 SyncOp::Delete => {
                     target.delete_repo(org, &op.repo.name).await?;
                 }
-                SyncOp::InSync => {
-                    // No action needed
+                SyncOp::Archive => {
+                    target.archive_repo(org, &op.repo.name).await?;
+                }
+                SyncOp::InSync | SyncOp::ReadOnly => {
+                    // No action needed, or none possible
                 }
             }
         }
@@ -238,53 +338,81 @@ impl SymmetricSyncService {
         org: &str,
         dry_run: bool,
     ) -> ForgeResult<Vec<SyncDiff>> {
-        use crate::adapters::LocalForge;
         use crate::types::Forge;
 
         let all_repos = source.list_repos(org).await?;
         let mut diffs = Vec::new();
 
-        // For each forge, filter repos that belong on it
+        // For each forge (by name, so the diffs come back in a stable order),
+        // filter repos that belong on it
+        let mut forges: Vec<_> = forges.into_iter().collect();
+        forges.sort_by(|a, b| a.0.cmp(&b.0));
         for (forge_name, forge_adapter) in forges {
-            let forge_type = match forge_name.to_lowercase().as_str() {
-                "github" => Forge::GitHub,
-                "codeberg" => Forge::Codeberg,
-                "gitlab" => Forge::GitLab,
-                _ => continue, // Skip unknown forges
+            let Ok(forge_type) = forge_name.parse::<Forge>() else {
+                continue; // Skip unknown forges
             };
 
             // Filter repos that should be on this forge (exclude staged for deletion)
-            let repos_for_forge: Vec<_> = all_repos
-                .iter()
-                .filter(|r| {
-                    !r.staged_for_deletion
-                        && (r.origin == forge_type || r.mirrors.contains(&forge_type))
-                })
-                .cloned()
-                .collect();
-
-            if repos_for_forge.is_empty() {
+            let belongs = |r: &&Repo| {
+                !r.staged_for_deletion
+                    && (r.origin == forge_type || r.mirrors.contains(&forge_type))
+            };
+            if !all_repos.iter().any(|r| belongs(&r)) {
                 continue; // No repos for this forge
             }
 
-            // Create temporary LocalForge with only these repos
-            let filtered_local = Arc::new(LocalForge::new(org));
-            for repo in repos_for_forge {
-                if let Err(e) = filtered_local.create_repo(org, &repo).await {
-                    // Log error but continue with other repos
-                    eprintln!("Failed to add repo {} to filtered forge: {}", repo.name, e);
-                }
-            }
-
-            // Sync filtered repos to this forge
+            // Only the repos that end up in this forge's ops are copied
+            let repos_for_forge: Vec<Repo> = all_repos.iter().filter(belongs).cloned().collect();
             let diff = self
-                .sync(filtered_local, forge_adapter, org, dry_run)
+                .diff_repos(repos_for_forge, forge_adapter.as_ref(), org)
                 .await?;
-            diffs.push(diff);
+            diffs.push(Self::apply(diff, forge_adapter.as_ref(), org, dry_run).await?);
         }
 
         Ok(diffs)
     }
+
+    /// Compare `branch`'s protection on `target` with `desired`: `None` if
+    /// the target doesn't support branch protection.
+    pub async fn diff_branch_protection(
+        &self,
+        target: &dyn ForgePort,
+        org: &str,
+        name: &str,
+        branch: &str,
+        desired: &BranchProtection,
+    ) -> ForgeResult<Option<ProtectionDiff>> {
+        let Some(current) = target.branch_protection(org, name, branch).await? else {
+            return Ok(None);
+        };
+        Ok(Some(ProtectionDiff {
+            branch: branch.to_string(),
+            details: protection_diff_details(desired, &current),
+            desired: desired.clone(),
+            current,
+        }))
+    }
+
+    /// Make `branch`'s protection on `target` match `desired`, returning the
+    /// diff that was (or would be) applied: `None` if the target doesn't
+    /// support branch protection.
+    pub async fn sync_branch_protection(
+        &self,
+        target: &dyn ForgePort,
+        org: &str,
+        name: &str,
+        branch: &str,
+        desired: &BranchProtection,
+        dry_run: bool,
+    ) -> ForgeResult<Option<ProtectionDiff>> {
+        let Some(diff) = self.diff_branch_protection(target, org, name, branch, desired).await? else {
+            return Ok(None);
+        };
+        if !dry_run && !diff.details.is_empty() {
+            target.set_branch_protection(org, name, branch, desired).await?;
+        }
+        Ok(Some(diff))
+    }
 }
 
 impl Default for SymmetricSyncService {
@@ -301,15 +429,47 @@ fn norm_desc(d: &Option<String>) -> Option<&str> {
     }
 }
 
-/// Return list of fields that differ between two repos (empty = in sync)
-fn repo_diff_details(a: &Repo, b: &Repo) -> Vec<String> {
+/// Return list of fields that differ between two repos (empty = in sync).
+/// When `b` lives on a forge, `a`'s description is compared as that forge
+/// would store it, so a truncated copy doesn't drift forever.
+fn repo_diff_details(a: &Repo, b: &Repo, b_forge: Option<&Forge>) -> Vec<String> {
     let mut details = Vec::new();
-    if norm_desc(&a.description) != norm_desc(&b.description) {
+    let fitted = b_forge
+        .zip(a.description.as_deref())
+        .and_then(|(forge, d)| fit_description(forge, d))
+        .map(|fit| fit.text);
+    let a_desc = if fitted.is_some() { &fitted } else { &a.description };
+    if norm_desc(a_desc) != norm_desc(&b.description) {
         details.push("description".to_string());
     }
-    if a.visibility != b.visibility {
+    let a_visibility = b_forge.map_or(&a.visibility, |forge| a.visibility_for(forge));
+    if *a_visibility != b.visibility {
         details.push("visibility".to_string());
     }
+    // No topics on `a` means topics aren't managed for the repo
+    if !a.topics.is_empty() && normalize_topics(&a.topics) != normalize_topics(&b.topics) {
+        details.push("topics".to_string());
+    }
+    details
+}
+
+/// Return list of protection settings that differ (empty = in sync)
+fn protection_diff_details(a: &BranchProtection, b: &BranchProtection) -> Vec<String> {
+    let mut details = Vec::new();
+    if a.required_reviews != b.required_reviews {
+        details.push("required_reviews".to_string());
+    }
+    let sorted = |checks: &[String]| {
+        let mut checks = checks.to_vec();
+        checks.sort();
+        checks
+    };
+    if sorted(&a.status_checks) != sorted(&b.status_checks) {
+        details.push("status_checks".to_string());
+    }
+    if a.block_force_push != b.block_force_push {
+        details.push("force_push".to_string());
+    }
     details
 }
 
@@ -402,6 +562,68 @@ mod tests {
         assert!(!diff.has_changes());
     }
 
+    #[tokio::test]
+    async fn test_diff_case_only_name_difference() {
+        let service = SymmetricSyncService::new();
+        let source = Arc::new(LocalForge::new("testorg"));
+        let target = Arc::new(LocalForge::new("TestOrg"));
+
+        source.create_repo("testorg", &Repo::new("myrepo", Forge::GitHub)).await.unwrap();
+        target.create_repo("TestOrg", &Repo::new("MyRepo", Forge::GitHub)).await.unwrap();
+
+        let diff = service.diff(source, target, "testorg").await.unwrap();
+        assert_eq!(diff.ops.len(), 1, "no phantom create+delete pair");
+        assert_eq!(diff.in_sync().len(), 1);
+        assert!(!diff.has_changes());
+        let mismatches = diff.case_mismatches();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].repo.name, "myrepo");
+        assert_eq!(mismatches[0].target_name.as_deref(), Some("MyRepo"));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_target_is_never_mutated() {
+        let service = SymmetricSyncService::new();
+        let source = Arc::new(LocalForge::new("testorg"));
+        let target = Arc::new(LocalForge::new("testorg"));
+
+        source.create_repo("testorg", &Repo::new("shared", Forge::GitHub).with_description("New")).await.unwrap();
+        let mut shared = Repo::new("shared", Forge::GitHub).with_description("Old");
+        shared.read_only = true;
+        let mut foreign = Repo::new("foreign", Forge::GitHub);
+        foreign.read_only = true;
+        target.create_repo("testorg", &shared).await.unwrap();
+        target.create_repo("testorg", &foreign).await.unwrap();
+
+        let diff = service.sync(source, target.clone(), "testorg", false).await.unwrap();
+        assert_eq!(diff.read_only().len(), 2);
+        assert!(diff.to_update().is_empty() && diff.to_delete().is_empty());
+        assert!(!diff.has_changes());
+        assert_eq!(target.get_repo("testorg", "shared").await.unwrap().description.as_deref(), Some("Old"));
+        assert!(!target.get_repo("testorg", "foreign").await.unwrap().staged_for_deletion);
+    }
+
+    #[tokio::test]
+    async fn test_sync_archives_retired_repos() {
+        let service = SymmetricSyncService::new();
+        let source = Arc::new(LocalForge::new("testorg"));
+        let target = Arc::new(LocalForge::new("testorg"));
+
+        // Drifted description is left alone: archived repos take no updates
+        source.create_repo("testorg", &Repo::new("retired", Forge::GitHub).with_description("New").with_archived(true)).await.unwrap();
+        source.create_repo("testorg", &Repo::new("never-pushed", Forge::GitHub).with_archived(true)).await.unwrap();
+        target.create_repo("testorg", &Repo::new("retired", Forge::GitHub).with_description("Old")).await.unwrap();
+
+        let diff = service.sync(source.clone(), target.clone(), "testorg", false).await.unwrap();
+        assert_eq!(diff.to_archive().len(), 1);
+        assert_eq!(diff.ops[0].details, vec!["archived".to_string()]);
+        assert!(diff.to_create().is_empty() && diff.to_update().is_empty());
+        assert!(target.get_repo("testorg", "retired").await.unwrap().archived);
+
+        let again = service.diff(source, target, "testorg").await.unwrap();
+        assert!(!again.has_changes());
+    }
+
     #[tokio::test]
     async fn test_sync_creates_repos() {
         let service = SymmetricSyncService::new();
@@ -452,7 +674,7 @@ mod tests {
     async fn test_repo_diff_details_description() {
         let repo1 = Repo::new("test", Forge::GitHub).with_description("Desc 1");
         let repo2 = Repo::new("test", Forge::GitHub).with_description("Desc 2");
-        let details = repo_diff_details(&repo1, &repo2);
+        let details = repo_diff_details(&repo1, &repo2, None);
         assert_eq!(details, vec!["description"]);
     }
 
@@ -460,7 +682,7 @@ mod tests {
     async fn test_repo_diff_details_visibility() {
         let repo1 = Repo::new("test", Forge::GitHub).with_visibility(Visibility::Public);
         let repo2 = Repo::new("test", Forge::GitHub).with_visibility(Visibility::Private);
-        let details = repo_diff_details(&repo1, &repo2);
+        let details = repo_diff_details(&repo1, &repo2, None);
         assert_eq!(details, vec!["visibility"]);
     }
 
@@ -472,18 +694,76 @@ mod tests {
         let repo2 = Repo::new("test", Forge::GitHub)
             .with_description("B")
             .with_visibility(Visibility::Private);
-        let details = repo_diff_details(&repo1, &repo2);
+        let details = repo_diff_details(&repo1, &repo2, None);
         assert_eq!(details, vec!["description", "visibility"]);
     }
 
+    #[tokio::test]
+    async fn test_repo_diff_details_compares_fitted_description() {
+        let long = "x".repeat(400);
+        let local = Repo::new("r", Forge::GitHub).with_description(long.clone());
+        let fitted = fit_description(&Forge::GitHub, &long).unwrap().text;
+        let remote = Repo::new("r", Forge::GitHub).with_description(fitted);
+        assert!(repo_diff_details(&local, &remote, Some(&Forge::GitHub)).is_empty());
+        assert_eq!(repo_diff_details(&local, &remote, None), vec!["description"]);
+    }
+
+    #[tokio::test]
+    async fn test_repo_diff_details_per_forge_visibility() {
+        let mut local = Repo::new("test", Forge::GitHub).with_visibility(Visibility::Private);
+        local.visibility_on.insert("codeberg".to_string(), Visibility::Public);
+        let public = Repo::new("test", Forge::Codeberg).with_visibility(Visibility::Public);
+
+        assert!(repo_diff_details(&local, &public, Some(&Forge::Codeberg)).is_empty());
+        assert_eq!(repo_diff_details(&local, &public, Some(&Forge::GitHub)), vec!["visibility"]);
+        assert_eq!(fit_repo(&Forge::Codeberg, &local).0.visibility, Visibility::Public);
+    }
+
+    #[tokio::test]
+    async fn test_repo_diff_details_topics() {
+        let local = Repo::new("test", Forge::GitHub).with_topics(&["Rust", "cli"]);
+        let remote = Repo::new("test", Forge::GitHub).with_topics(&["cli"]);
+        assert_eq!(repo_diff_details(&local, &remote, None), vec!["topics"]);
+
+        let remote = remote.with_topics(&["cli", "rust"]);
+        assert!(repo_diff_details(&local, &remote, None).is_empty());
+
+        // Unmanaged locally: the forge's topics are left alone
+        let unmanaged = Repo::new("test", Forge::GitHub);
+        assert!(repo_diff_details(&unmanaged, &remote, None).is_empty());
+    }
+
     #[tokio::test]
     async fn test_repo_diff_details_same() {
         let repo1 = Repo::new("test", Forge::GitHub).with_description("Same");
         let repo2 = Repo::new("test", Forge::GitHub).with_description("Same");
-        let details = repo_diff_details(&repo1, &repo2);
+        let details = repo_diff_details(&repo1, &repo2, None);
         assert!(details.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_protection_diff_details() {
+        let desired = BranchProtection {
+            required_reviews: 1,
+            status_checks: vec!["ci/test".to_string(), "ci/build".to_string()],
+            block_force_push: true,
+        };
+        let current = BranchProtection {
+            status_checks: vec!["ci/build".to_string(), "ci/test".to_string()],
+            ..BranchProtection::default()
+        };
+        assert_eq!(protection_diff_details(&desired, &current), vec!["required_reviews", "force_push"]);
+        assert!(protection_diff_details(&desired, &desired).is_empty());
+
+        // LocalForge has no branch protection
+        let local = LocalForge::new("testorg");
+        let diff = SymmetricSyncService::new()
+            .sync_branch_protection(&local, "testorg", "repo", "main", &desired, false)
+            .await
+            .unwrap();
+        assert!(diff.is_none());
+    }
+
     #[tokio::test]
     async fn test_diff_staged_for_deletion_on_target() {
         let service = SymmetricSyncService::new();
@@ -0,0 +1,184 @@
+//! Resumable `workspace sync`
+//!
+//! A sync over hundreds of repos can fail late: a forge outage while
+//! applying, an expired token while pushing. Each run records what it has
+//! finished in `<workspace>/.hyperforge/sync-checkpoint.toml` (every repo
+//! applied, privatized or pushed, and each converge phase that completed
+//! cleanly) and `workspace sync --resume` skips those. Discovery through
+//! diffing always runs again: it only reads, and the later phases need its
+//! results. The checkpoint is removed once a run finishes without failures.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+const CHECKPOINT_FILE: &str = "sync-checkpoint.toml";
+
+/// `[webhooks]` converged on every forge
+pub const PHASE_WEBHOOKS: &str = "webhooks";
+/// `[branch_protection]` converged on every forge
+pub const PHASE_BRANCH_PROTECTION: &str = "branch_protection";
+/// Remote-only repos staged or purged (reflect mode)
+pub const PHASE_RETIRE: &str = "retire";
+
+/// What a sync run has finished so far
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncCheckpoint {
+    pub started_at: DateTime<Utc>,
+    /// The run's options, as [`options_key`] renders them; a resume with
+    /// different ones would skip work the new run asks for
+    pub options: String,
+    #[serde(default)]
+    pub phases: BTreeSet<String>,
+    /// Finished per-repo operations, as the `*_key` functions name them
+    #[serde(default)]
+    pub done: BTreeSet<String>,
+}
+
+impl SyncCheckpoint {
+    pub fn new(options: impl Into<String>) -> Self {
+        Self {
+            started_at: Utc::now(),
+            options: options.into(),
+            phases: BTreeSet::new(),
+            done: BTreeSet::new(),
+        }
+    }
+
+    pub fn path(workspace_root: &Path) -> PathBuf {
+        workspace_root.join(crate::config::CONFIG_DIR).join(CHECKPOINT_FILE)
+    }
+
+    /// The checkpoint left by an unfinished run, if any
+    pub fn load(workspace_root: &Path) -> Result<Option<Self>, String> {
+        let path = Self::path(workspace_root);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Ok(None);
+        };
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, workspace_root: &Path) -> Result<(), String> {
+        let path = Self::path(workspace_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize sync checkpoint: {e}"))?;
+        std::fs::write(&path, content)
+            .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    /// Delete the checkpoint after a clean run
+    pub fn remove(workspace_root: &Path) -> Result<(), String> {
+        let path = Self::path(workspace_root);
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to remove {}: {e}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn is_done(&self, key: &str) -> bool {
+        self.done.contains(key)
+    }
+
+    pub fn mark_done(&mut self, key: String) {
+        self.done.insert(key);
+    }
+
+    pub fn phase_done(&self, phase: &str) -> bool {
+        self.phases.contains(phase)
+    }
+
+    pub fn mark_phase(&mut self, phase: &str) {
+        self.phases.insert(phase.to_string());
+    }
+}
+
+/// A repo created or updated on its forges
+pub fn apply_key(org: &str, repo: &str) -> String {
+    format!("apply:{org}/{repo}")
+}
+
+/// A repo privatized on one forge
+pub fn privatize_key(org: &str, forge: &str, repo: &str) -> String {
+    format!("privatize:{org}/{forge}/{repo}")
+}
+
+/// A workspace directory pushed to every remote
+pub fn push_key(dir_name: &str) -> String {
+    format!("push:{dir_name}")
+}
+
+/// The options that decide which operations a sync performs, rendered for
+/// comparison. Flags that only change how much is checked (auth preflight,
+/// forge probing) or what is printed are left out.
+#[allow(clippy::fn_params_excessive_bools)]
+pub fn options_key(
+    org: Option<&str>,
+    forges: Option<&[String]>,
+    include: Option<&[String]>,
+    exclude: Option<&[String]>,
+    no_push: bool,
+    no_init: bool,
+    validate: bool,
+    reflect: bool,
+    purge: bool,
+    branch: Option<&str>,
+) -> String {
+    let list = |items: Option<&[String]>| items.map(|i| i.join(",")).unwrap_or_default();
+    format!(
+        "org={} forges={} include={} exclude={} no_push={no_push} no_init={no_init} validate={validate} reflect={reflect} purge={purge} branch={}",
+        org.unwrap_or_default(),
+        list(forges),
+        list(include),
+        list(exclude),
+        branch.unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert_eq!(SyncCheckpoint::load(temp.path()).unwrap(), None);
+
+        let options = options_key(Some("acme"), None, Some(&["api*".to_string()]), None, false, true, false, false, false, None);
+        let mut checkpoint = SyncCheckpoint::new(options.clone());
+        checkpoint.mark_done(apply_key("acme", "api"));
+        checkpoint.mark_done(push_key("api"));
+        checkpoint.mark_phase(PHASE_WEBHOOKS);
+        checkpoint.save(temp.path()).unwrap();
+
+        let loaded = SyncCheckpoint::load(temp.path()).unwrap().unwrap();
+        assert_eq!(loaded, checkpoint);
+        assert_eq!(loaded.options, options);
+        assert!(loaded.is_done("apply:acme/api"));
+        assert!(!loaded.is_done(&privatize_key("acme", "github", "api")));
+        assert!(loaded.phase_done(PHASE_WEBHOOKS));
+        assert!(!loaded.phase_done(PHASE_RETIRE));
+
+        SyncCheckpoint::remove(temp.path()).unwrap();
+        assert_eq!(SyncCheckpoint::load(temp.path()).unwrap(), None);
+        SyncCheckpoint::remove(temp.path()).unwrap();
+    }
+
+    #[test]
+    fn test_options_key_distinguishes_runs() {
+        let base = options_key(Some("acme"), None, None, None, false, true, false, false, false, None);
+        let no_push = options_key(Some("acme"), None, None, None, true, true, false, false, false, None);
+        let branch = options_key(Some("acme"), None, None, None, false, true, false, false, false, Some("main"));
+        assert_ne!(base, no_push);
+        assert_ne!(base, branch);
+        assert_eq!(base, options_key(Some("acme"), None, None, None, false, true, false, false, false, None));
+    }
+}
@@ -0,0 +1,218 @@
+//! Saved sync plans
+//!
+//! `workspace plan` computes the diffs `workspace sync` would act on and
+//! writes the creates, updates, deletes and archives to a JSON file for review.
+//! `workspace apply --plan_file` recomputes the diffs for the plan's
+//! org/forge pairs and carries the plan out only if they still match it: a
+//! config edit or a forge change made after review makes it refuse rather
+//! than apply something nobody looked at.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::services::{SyncDiff, SyncOp};
+use crate::types::Repo;
+
+const PLAN_FILE: &str = "sync-plan.json";
+
+/// What applying a change does on the target forge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlannedOp {
+    Create,
+    Update,
+    /// Privatized, as `workspace sync` does; never deleted
+    Delete,
+    Archive,
+}
+
+impl PlannedOp {
+    /// The planned counterpart of a diff op; `None` for ops nothing is done about
+    pub const fn from_sync_op(op: &SyncOp) -> Option<Self> {
+        match op {
+            SyncOp::Create => Some(Self::Create),
+            SyncOp::Update => Some(Self::Update),
+            SyncOp::Delete => Some(Self::Delete),
+            SyncOp::Archive => Some(Self::Archive),
+            SyncOp::InSync | SyncOp::ReadOnly => None,
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Delete => "delete",
+            Self::Archive => "archive",
+        }
+    }
+}
+
+/// An org/forge pair the plan was computed for
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PlanScope {
+    pub org: String,
+    pub forge: String,
+}
+
+/// One repo change on one forge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedChange {
+    pub org: String,
+    pub forge: String,
+    pub op: PlannedOp,
+    /// The repo as the source (`LocalForge`) describes it
+    pub repo: Repo,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+}
+
+/// Reviewed changes, to be applied later by `workspace apply`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncPlan {
+    pub created_at: DateTime<Utc>,
+    /// Every pair that was diffed, including those with nothing to change
+    pub scopes: Vec<PlanScope>,
+    pub changes: Vec<PlannedChange>,
+}
+
+impl SyncPlan {
+    /// A plan from `(org, forge, diff)` triples, in org, forge, repo order
+    pub fn new(diffs: &[(String, String, SyncDiff)]) -> Self {
+        let mut scopes: Vec<PlanScope> = diffs
+            .iter()
+            .map(|(org, forge, _)| PlanScope { org: org.clone(), forge: forge.clone() })
+            .collect();
+        scopes.sort();
+        let mut changes: Vec<PlannedChange> = diffs
+            .iter()
+            .flat_map(|(org, forge, diff)| {
+                diff.ops.iter().filter_map(move |op| {
+                    Some(PlannedChange {
+                        org: org.clone(),
+                        forge: forge.clone(),
+                        op: PlannedOp::from_sync_op(&op.op)?,
+                        repo: op.repo.clone(),
+                        details: op.details.clone(),
+                    })
+                })
+            })
+            .collect();
+        changes.sort_by(|a, b| (&a.org, &a.forge, &a.repo.name).cmp(&(&b.org, &b.forge, &b.repo.name)));
+        Self { created_at: Utc::now(), scopes, changes }
+    }
+
+    /// Default location: `<workspace>/.hyperforge/sync-plan.json`
+    pub fn default_path(workspace_root: &Path) -> PathBuf {
+        workspace_root.join(crate::config::CONFIG_DIR).join(PLAN_FILE)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read plan {}: {e}", path.display()))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse plan {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize plan: {e}"))?;
+        std::fs::write(path, content)
+            .map_err(|e| format!("Failed to write plan {}: {e}", path.display()))
+    }
+
+    /// How `current` (a plan recomputed now) departs from this one, one line
+    /// per repo. Empty when this plan can still be applied as reviewed.
+    pub fn drift(&self, current: &Self) -> Vec<String> {
+        let key = |c: &PlannedChange| format!("{}/{}/{}", c.org, c.forge, c.repo.name);
+        let planned: BTreeMap<String, &PlannedChange> = self.changes.iter().map(|c| (key(c), c)).collect();
+        let now: BTreeMap<String, &PlannedChange> = current.changes.iter().map(|c| (key(c), c)).collect();
+
+        let mut drift = Vec::new();
+        for (name, change) in &planned {
+            match now.get(name) {
+                None => drift.push(format!("{name}: planned {} is no longer needed", change.op.as_str())),
+                Some(found) if found.op != change.op => drift.push(format!(
+                    "{name}: planned {}, now {}",
+                    change.op.as_str(),
+                    found.op.as_str(),
+                )),
+                Some(found) if found != change => {
+                    drift.push(format!("{name}: {} differs from the plan", change.op.as_str()));
+                }
+                Some(_) => {}
+            }
+        }
+        for (name, change) in &now {
+            if !planned.contains_key(name) {
+                drift.push(format!("{name}: {} is not in the plan", change.op.as_str()));
+            }
+        }
+        drift
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::symmetric_sync::RepoOp;
+
+    fn op(name: &str, op: SyncOp, description: &str) -> RepoOp {
+        RepoOp {
+            repo: Repo::new(name, crate::types::Forge::GitHub).with_description(description),
+            op,
+            details: vec![],
+            target_name: None,
+        }
+    }
+
+    fn diff(ops: Vec<RepoOp>) -> SyncDiff {
+        SyncDiff { org: "acme".to_string(), ops }
+    }
+
+    #[test]
+    fn test_plan_roundtrip_and_drift() {
+        let reviewed = SyncPlan::new(&[(
+            "acme".to_string(),
+            "github".to_string(),
+            diff(vec![
+                op("web", SyncOp::Update, "Site"),
+                op("api", SyncOp::Create, "API"),
+                op("tool", SyncOp::InSync, ""),
+            ]),
+        )]);
+        let names: Vec<&str> = reviewed.changes.iter().map(|c| c.repo.name.as_str()).collect();
+        assert_eq!(names, vec!["api", "web"]);
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = SyncPlan::default_path(temp.path());
+        reviewed.save(&path).unwrap();
+        let loaded = SyncPlan::load(&path).unwrap();
+        assert_eq!(loaded, reviewed);
+        assert!(loaded.drift(&reviewed).is_empty());
+
+        // The description was edited and a new repo appeared after review
+        let current = SyncPlan::new(&[(
+            "acme".to_string(),
+            "github".to_string(),
+            diff(vec![
+                op("web", SyncOp::Update, "New site"),
+                op("api", SyncOp::Create, "API"),
+                op("cli", SyncOp::Create, "CLI"),
+            ]),
+        )]);
+        assert_eq!(
+            loaded.drift(&current),
+            vec![
+                "acme/github/web: update differs from the plan".to_string(),
+                "acme/github/cli: create is not in the plan".to_string(),
+            ]
+        );
+    }
+}
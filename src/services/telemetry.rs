@@ -0,0 +1,248 @@
+//! Opt-in anonymous usage telemetry
+//!
+//! Off by default. Once turned on with `hyperforge telemetry_enable
+//! --endpoint <url>` (or `[telemetry]` in ~/.config/hyperforge/config.toml),
+//! each instrumented run POSTs one small JSON report when it finishes:
+//!
+//! ```json
+//! {"method":"workspace.sync","version":"2.0.0","repos":"11-50","duration":"10-60s","error":"forge_api"}
+//! ```
+//!
+//! Counts and timings are bucketed, `error` is the stable code of the first
+//! failure (if any), and nothing names a repo, org, path or user. Reports
+//! are sent in the background and dropped on failure; runs cancelled
+//! mid-stream send nothing. `DO_NOT_TRACK=1` or `HYPERFORGE_TELEMETRY=0`
+//! turns reporting off regardless of the config.
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::config::UserConfig;
+use crate::hub::{HyperforgeError, HyperforgeEvent};
+
+/// Set to anything but `0` to veto reporting (<https://consoledonottrack.com>)
+const DO_NOT_TRACK_ENV: &str = "DO_NOT_TRACK";
+/// Set to `0` to veto reporting
+const TELEMETRY_ENV: &str = "HYPERFORGE_TELEMETRY";
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The fields a report carries, for `telemetry_status`
+pub const REPORT_FIELDS: [&str; 5] = ["method", "version", "repos", "duration", "error"];
+
+/// Whether telemetry may send, and where
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelemetryState {
+    /// Never enabled in the config
+    Disabled,
+    /// Enabled, but vetoed by an env var
+    Vetoed(&'static str),
+    /// Enabled without an endpoint
+    NoEndpoint,
+    Enabled(String),
+}
+
+impl TelemetryState {
+    /// The state for `config_dir`'s config and the process environment
+    pub fn load(config_dir: &Path) -> Self {
+        let config = UserConfig::load(config_dir).telemetry;
+        let do_not_track = std::env::var(DO_NOT_TRACK_ENV).ok();
+        let telemetry = std::env::var(TELEMETRY_ENV).ok();
+        Self::resolve(config.enabled, config.endpoint, do_not_track.as_deref(), telemetry.as_deref())
+    }
+
+    fn resolve(enabled: bool, endpoint: Option<String>, do_not_track: Option<&str>, telemetry: Option<&str>) -> Self {
+        if !enabled {
+            return Self::Disabled;
+        }
+        if do_not_track.is_some_and(|v| !v.is_empty() && v != "0") {
+            return Self::Vetoed(DO_NOT_TRACK_ENV);
+        }
+        if telemetry == Some("0") {
+            return Self::Vetoed(TELEMETRY_ENV);
+        }
+        match endpoint.filter(|e| !e.is_empty()) {
+            Some(endpoint) => Self::Enabled(endpoint),
+            None => Self::NoEndpoint,
+        }
+    }
+
+    /// The endpoint to report to, if reporting is on
+    pub fn endpoint(&self) -> Option<&str> {
+        match self {
+            Self::Enabled(endpoint) => Some(endpoint),
+            _ => None,
+        }
+    }
+
+    /// One-line explanation for `telemetry_status`
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Disabled => "Telemetry is off (the default). Nothing is recorded or sent.".to_string(),
+            Self::Vetoed(var) => format!("Telemetry is enabled in config.toml but {var} turns it off for this process. Nothing is sent."),
+            Self::NoEndpoint => "Telemetry is enabled but no endpoint is set. Nothing is sent.".to_string(),
+            Self::Enabled(endpoint) => format!("Telemetry is on: one anonymous report per run is sent to {endpoint}"),
+        }
+    }
+}
+
+/// One run's report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UsageReport {
+    pub method: &'static str,
+    pub version: &'static str,
+    pub repos: &'static str,
+    pub duration: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<HyperforgeError>,
+}
+
+/// Coarse bucket for a repo count; `unknown` when the run never said
+pub const fn repo_bucket(repos: Option<usize>) -> &'static str {
+    match repos {
+        None => "unknown",
+        Some(0) => "0",
+        Some(1..=10) => "1-10",
+        Some(11..=50) => "11-50",
+        Some(51..=200) => "51-200",
+        Some(_) => "200+",
+    }
+}
+
+/// Coarse bucket for a run's wall time
+pub const fn duration_bucket(duration: Duration) -> &'static str {
+    match duration.as_secs() {
+        0 => "<1s",
+        1..=9 => "1-10s",
+        10..=59 => "10-60s",
+        60..=299 => "1-5m",
+        _ => "5m+",
+    }
+}
+
+/// What a run's events tell us about it
+#[derive(Debug, Default)]
+struct Observed {
+    repos: Option<usize>,
+    error: Option<HyperforgeError>,
+}
+
+impl Observed {
+    fn observe(&mut self, event: &HyperforgeEvent) {
+        match event {
+            HyperforgeEvent::Error { code, .. } => {
+                self.error.get_or_insert(*code);
+            }
+            HyperforgeEvent::WorkspaceSummary { total_repos: total, .. } | HyperforgeEvent::SyncSummary { total, .. } => {
+                self.repos = Some(self.repos.map_or(*total, |seen| seen.max(*total)));
+            }
+            _ => {}
+        }
+    }
+
+    const fn report(self, method: &'static str, elapsed: Duration) -> UsageReport {
+        UsageReport {
+            method,
+            version: env!("CARGO_PKG_VERSION"),
+            repos: repo_bucket(self.repos),
+            duration: duration_bucket(elapsed),
+            error: self.error,
+        }
+    }
+}
+
+/// Pass `events` through unchanged, reporting the run as `method` once the
+/// stream ends if telemetry is on for `config_dir`
+pub fn instrument<S>(config_dir: &Path, method: &'static str, events: S) -> impl Stream<Item = HyperforgeEvent> + Send + 'static
+where
+    S: Stream<Item = HyperforgeEvent> + Send + 'static,
+{
+    let state = TelemetryState::load(config_dir);
+    stream! {
+        let started = Instant::now();
+        let mut observed = Observed::default();
+        let mut events = Box::pin(events);
+        while let Some(event) = events.next().await {
+            observed.observe(&event);
+            yield event;
+        }
+        if let Some(endpoint) = state.endpoint() {
+            send(endpoint.to_string(), observed.report(method, started.elapsed()));
+        }
+    }
+}
+
+/// POST `report` in the background; failures are only logged
+fn send(endpoint: String, report: UsageReport) {
+    tokio::spawn(async move {
+        let result = async {
+            let client = reqwest::Client::builder()
+                .user_agent(concat!("hyperforge/", env!("CARGO_PKG_VERSION")))
+                .timeout(SEND_TIMEOUT)
+                .build()?;
+            client.post(&endpoint).json(&report).send().await?.error_for_status()
+        }
+        .await;
+        if let Err(e) = result {
+            tracing::debug!("telemetry report to {endpoint} failed: {e}");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buckets() {
+        assert_eq!(repo_bucket(None), "unknown");
+        assert_eq!(repo_bucket(Some(10)), "1-10");
+        assert_eq!(repo_bucket(Some(11)), "11-50");
+        assert_eq!(repo_bucket(Some(500)), "200+");
+        assert_eq!(duration_bucket(Duration::from_millis(900)), "<1s");
+        assert_eq!(duration_bucket(Duration::from_secs(45)), "10-60s");
+        assert_eq!(duration_bucket(Duration::from_secs(301)), "5m+");
+    }
+
+    #[test]
+    fn test_state_is_opt_in_and_vetoable() {
+        let endpoint = || Some("https://telemetry.example/v1".to_string());
+        assert_eq!(TelemetryState::resolve(false, endpoint(), None, None), TelemetryState::Disabled);
+        assert_eq!(TelemetryState::resolve(true, None, None, None), TelemetryState::NoEndpoint);
+        assert_eq!(TelemetryState::resolve(true, endpoint(), Some("1"), None), TelemetryState::Vetoed(DO_NOT_TRACK_ENV));
+        assert_eq!(TelemetryState::resolve(true, endpoint(), None, Some("0")), TelemetryState::Vetoed(TELEMETRY_ENV));
+        assert_eq!(
+            TelemetryState::resolve(true, endpoint(), Some("0"), None).endpoint(),
+            Some("https://telemetry.example/v1")
+        );
+    }
+
+    #[test]
+    fn test_report_is_coarse() {
+        let mut observed = Observed::default();
+        observed.observe(&HyperforgeEvent::Info { message: "acme/secret-repo".to_string() });
+        observed.observe(&HyperforgeEvent::error(HyperforgeError::ForgeApi, "502 from github").with_context("secret-repo"));
+        observed.observe(&HyperforgeEvent::error(HyperforgeError::Git, "push rejected"));
+        observed.observe(&HyperforgeEvent::SyncSummary {
+            forge: "github".to_string(),
+            total: 37,
+            to_create: 0,
+            to_update: 1,
+            to_delete: 0,
+            to_archive: 0,
+            in_sync: 36,
+        });
+
+        let report = observed.report("workspace.sync", Duration::from_secs(12));
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["repos"], "11-50");
+        assert_eq!(json["duration"], "10-60s");
+        assert_eq!(json["error"], "forge_api");
+        let keys: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+        assert!(keys.iter().all(|k| REPORT_FIELDS.contains(k)));
+        assert!(!json.to_string().contains("secret-repo"));
+    }
+}
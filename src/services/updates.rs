@@ -0,0 +1,171 @@
+//! Dependency-update bots (Renovate, Dependabot)
+//!
+//! Renders per-repo bot configuration from the workspace `[updates]` table and
+//! recognizes the pull requests those bots open, so they can be triaged across
+//! every forge at once.
+
+use chrono::{DateTime, Utc};
+
+use crate::adapters::PullRequest;
+use crate::build_system::BuildSystemKind;
+use crate::config::workspace::{UpdateBot, UpdateBots};
+
+/// Which bot opened `pr`, judged by author and branch naming
+pub fn detect_bot(pr: &PullRequest) -> Option<UpdateBot> {
+    let author = pr.author.to_lowercase();
+    if author.contains("renovate") || pr.head_branch.starts_with("renovate/") {
+        Some(UpdateBot::Renovate)
+    } else if author.contains("dependabot") || pr.head_branch.starts_with("dependabot/") {
+        Some(UpdateBot::Dependabot)
+    } else {
+        None
+    }
+}
+
+/// Whole days between `created_at` and `now`
+pub fn age_days(created_at: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    (now - created_at).num_days().max(0)
+}
+
+/// Bot config file for a repo, as `(path relative to repo, content)`.
+///
+/// Returns `None` for Dependabot when none of the repo's ecosystems are
+/// supported (Dependabot has no Cabal support; Renovate detects managers
+/// itself, so it always gets a config).
+pub fn config_file(
+    settings: &UpdateBots,
+    bot: UpdateBot,
+    build_systems: &[BuildSystemKind],
+    has_workflows: bool,
+) -> Result<Option<(&'static str, String)>, String> {
+    let schedule = settings.schedule.as_deref().unwrap_or("weekly");
+    if !matches!(schedule, "daily" | "weekly" | "monthly") {
+        return Err(format!(
+            "Unknown [updates].schedule '{schedule}' — use daily, weekly or monthly"
+        ));
+    }
+
+    match bot {
+        UpdateBot::Renovate => {
+            let when = match schedule {
+                "daily" => "before 4am",
+                "weekly" => "before 4am on monday",
+                _ => "before 4am on the first day of the month",
+            };
+            let mut doc = serde_json::json!({
+                "$schema": "https://docs.renovatebot.com/renovate-schema.json",
+                "extends": ["config:recommended"],
+                "schedule": [when],
+            });
+            if settings.automerge {
+                doc["packageRules"] = serde_json::json!([{
+                    "matchUpdateTypes": ["minor", "patch"],
+                    "automerge": true,
+                }]);
+            }
+            let mut content = serde_json::to_string_pretty(&doc)
+                .map_err(|e| format!("Failed to render renovate.json: {e}"))?;
+            content.push('\n');
+            Ok(Some(("renovate.json", content)))
+        }
+        UpdateBot::Dependabot => {
+            let mut ecosystems: Vec<&str> = build_systems
+                .iter()
+                .filter_map(|bs| match bs {
+                    BuildSystemKind::Cargo => Some("cargo"),
+                    BuildSystemKind::Node => Some("npm"),
+                    BuildSystemKind::Cabal | BuildSystemKind::Unknown => None,
+                })
+                .collect();
+            ecosystems.dedup();
+            if has_workflows {
+                ecosystems.push("github-actions");
+            }
+            if ecosystems.is_empty() {
+                return Ok(None);
+            }
+
+            let mut content = String::from("version: 2\nupdates:\n");
+            for ecosystem in ecosystems {
+                content.push_str(&format!(
+                    "  - package-ecosystem: \"{ecosystem}\"\n    directory: \"/\"\n    schedule:\n      interval: \"{schedule}\"\n"
+                ));
+            }
+            Ok(Some((".github/dependabot.yml", content)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(author: &str, branch: &str) -> PullRequest {
+        PullRequest {
+            number: 1,
+            title: "Update serde to v1.0.200".to_string(),
+            author: author.to_string(),
+            head_branch: branch.to_string(),
+            head_sha: "abc".to_string(),
+            base_branch: "main".to_string(),
+            draft: false,
+            url: String::new(),
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_detect_bot() {
+        assert_eq!(detect_bot(&pr("renovate[bot]", "renovate/serde")), Some(UpdateBot::Renovate));
+        assert_eq!(detect_bot(&pr("dependabot[bot]", "x")), Some(UpdateBot::Dependabot));
+        assert_eq!(detect_bot(&pr("ci-user", "dependabot/cargo/serde-1.0")), Some(UpdateBot::Dependabot));
+        assert_eq!(detect_bot(&pr("alice", "feature/x")), None);
+    }
+
+    #[test]
+    fn test_dependabot_config_per_ecosystem() {
+        let settings = UpdateBots {
+            bot: Some(UpdateBot::Dependabot),
+            schedule: Some("daily".to_string()),
+            automerge: false,
+        };
+        let (path, content) = config_file(
+            &settings,
+            UpdateBot::Dependabot,
+            &[BuildSystemKind::Cargo, BuildSystemKind::Cabal],
+            true,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(path, ".github/dependabot.yml");
+        let doc: serde_yaml::Value = serde_yaml::from_str(&content).unwrap();
+        assert_eq!(doc["updates"][0]["package-ecosystem"], "cargo");
+        assert_eq!(doc["updates"][1]["package-ecosystem"], "github-actions");
+        assert_eq!(doc["updates"][0]["schedule"]["interval"], "daily");
+
+        let none = config_file(&settings, UpdateBot::Dependabot, &[BuildSystemKind::Cabal], false);
+        assert!(none.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_renovate_config_automerge() {
+        let settings = UpdateBots {
+            bot: Some(UpdateBot::Renovate),
+            schedule: None,
+            automerge: true,
+        };
+        let (path, content) = config_file(&settings, UpdateBot::Renovate, &[], false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(path, "renovate.json");
+        let doc: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(doc["schedule"][0], "before 4am on monday");
+        assert_eq!(doc["packageRules"][0]["automerge"], true);
+
+        let bad = UpdateBots {
+            schedule: Some("hourly".to_string()),
+            ..settings
+        };
+        assert!(config_file(&bad, UpdateBot::Renovate, &[], false).is_err());
+    }
+}
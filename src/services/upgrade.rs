@@ -0,0 +1,266 @@
+//! Self-update: find, verify and install newer hyperforge releases
+//!
+//! Releases come from hyperforge's own repo through the release adapters
+//! (`[upgrade] forge` and `repo` in ~/.config/hyperforge/config.toml,
+//! `github` and `hypermemetic/hyperforge` by default). The host's archive
+//! (`hyperforge-<target>-v<version>.tar.gz`) is installed only if its cosign
+//! or minisign signature, as `build release` attaches with `[dist.signing]`,
+//! verifies under the public key in `[upgrade] public_key`, and it matches
+//! its line in `SHA256SUMS` when the release has one.
+//!
+//! The new binary is written next to the running one and renamed over it,
+//! so a failed upgrade leaves the old binary in place. The running process
+//! keeps executing the old code until it is restarted.
+//!
+//! The server also checks for a newer release once at startup (unless
+//! `[upgrade] check_on_startup = false`); `status` then reports it.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use crate::adapters::releases::codeberg::CodebergReleaseAdapter;
+use crate::adapters::releases::github::GitHubReleaseAdapter;
+use crate::adapters::releases::{ReleaseInfo, ReleasePort};
+use crate::auth::YamlAuthProvider;
+use crate::build_system::cross_compile::{host_triple, sha256_hex, CHECKSUMS_FILE};
+use crate::build_system::signing::verify_signature;
+use crate::build_system::version::compare_versions;
+use crate::config::{UpgradeConfig, UserConfig};
+use crate::types::config::SigningTool;
+
+/// The version this binary was built as
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A release newer than the running binary
+#[derive(Debug, Clone)]
+pub struct AvailableRelease {
+    pub version: String,
+    pub release: ReleaseInfo,
+}
+
+/// The newest stable release in `releases` above `current`, or the one
+/// tagged `wanted` (with or without a leading `v`) if given
+pub fn select_release(releases: &[ReleaseInfo], current: &str, wanted: Option<&str>) -> Option<AvailableRelease> {
+    let version_of = |release: &ReleaseInfo| release.tag_name.trim_start_matches('v').to_string();
+    if let Some(wanted) = wanted {
+        let wanted = wanted.trim_start_matches('v');
+        return releases
+            .iter()
+            .find(|r| !r.draft && version_of(r) == wanted)
+            .map(|r| AvailableRelease { version: version_of(r), release: r.clone() });
+    }
+    releases
+        .iter()
+        .filter(|r| !r.draft && !r.prerelease)
+        .filter(|r| compare_versions(&version_of(r), current) == Some(std::cmp::Ordering::Greater))
+        .max_by(|a, b| compare_versions(&version_of(a), &version_of(b)).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|r| AvailableRelease { version: version_of(r), release: r.clone() })
+}
+
+/// Releases from the configured feed
+pub async fn list_releases(config: &UpgradeConfig) -> Result<Vec<ReleaseInfo>, String> {
+    let (org, repo) = config
+        .repo
+        .split_once('/')
+        .ok_or_else(|| format!("[upgrade] repo must be <org>/<repo>, got '{}'", config.repo))?;
+    let auth = Arc::new(YamlAuthProvider::new().map_err(|e| e.to_string())?);
+    let port: Box<dyn ReleasePort> = match config.forge.as_str() {
+        "github" => Box::new(GitHubReleaseAdapter::new(auth, org).map_err(|e| e.to_string())?),
+        "codeberg" => Box::new(CodebergReleaseAdapter::new(auth, org).map_err(|e| e.to_string())?),
+        other => return Err(format!("[upgrade] forge must be github or codeberg, got '{other}'")),
+    };
+    port.list_releases(org, repo).await.map_err(|e| e.to_string())
+}
+
+/// The checksum `sums` (sha256sum format) lists for `file`
+pub fn expected_checksum<'a>(sums: &'a str, file: &str) -> Option<&'a str> {
+    sums.lines().find_map(|line| {
+        let (hash, name) = line.split_once(char::is_whitespace)?;
+        (name.trim_start().trim_start_matches('*') == file).then_some(hash)
+    })
+}
+
+/// The `binary` entry of a release `.tar.gz`
+pub fn extract_binary(archive: &[u8], binary: &str) -> Result<Vec<u8>, String> {
+    let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+    for entry in tar.entries().map_err(|e| format!("invalid archive: {e}"))? {
+        let mut entry = entry.map_err(|e| format!("invalid archive: {e}"))?;
+        let path = entry.path().map_err(|e| e.to_string())?.into_owned();
+        if path.file_name().and_then(|n| n.to_str()) == Some(binary) {
+            let mut bytes = Vec::new();
+            entry.read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+            return Ok(bytes);
+        }
+    }
+    Err(format!("archive has no {binary}"))
+}
+
+/// Put `binary` in place of the executable at `exe`
+pub fn replace_executable(exe: &Path, binary: &[u8]) -> Result<(), String> {
+    let name = exe.file_name().and_then(|n| n.to_str()).ok_or("invalid executable path")?;
+    let staged = exe.with_file_name(format!(".{name}.upgrade"));
+    std::fs::write(&staged, binary).map_err(|e| format!("failed to write {}: {e}", staged.display()))?;
+    let permissions = std::fs::metadata(exe).map_err(|e| e.to_string())?.permissions();
+    std::fs::set_permissions(&staged, permissions).map_err(|e| e.to_string())?;
+
+    // Windows can't replace a running executable, but can rename it
+    if cfg!(windows) {
+        let old = exe.with_file_name(format!("{name}.old"));
+        let _ = std::fs::remove_file(&old);
+        std::fs::rename(exe, &old).map_err(|e| format!("failed to move {} aside: {e}", exe.display()))?;
+    }
+    std::fs::rename(&staged, exe).map_err(|e| {
+        let _ = std::fs::remove_file(&staged);
+        format!("failed to replace {}: {e}", exe.display())
+    })
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, String> {
+    let response = client.get(url).send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("download of {url} failed ({})", response.status()));
+    }
+    Ok(response.bytes().await.map_err(|e| e.to_string())?.to_vec())
+}
+
+/// Download, verify and install `available` over the executable at `exe`
+pub async fn install(config: &UpgradeConfig, available: &AvailableRelease, exe: &Path) -> Result<(), String> {
+    let public_key = config
+        .public_key
+        .as_ref()
+        .ok_or("no [upgrade] public_key configured: refusing to install an unverified binary")?;
+    let public_key = match (public_key.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rel), Some(home)) => home.join(rel),
+        _ => public_key.clone(),
+    };
+
+    let release = &available.release;
+    let archive_name = format!("hyperforge-{}-v{}.tar.gz", host_triple(), available.version);
+    let asset_url = |name: &str| release.assets.iter().find(|a| a.name == name).map(|a| a.download_url.clone());
+    let archive_url = asset_url(&archive_name).ok_or_else(|| format!("release {} has no {archive_name}", release.tag_name))?;
+    let (tool, signature_url) = [SigningTool::Cosign, SigningTool::Minisign]
+        .into_iter()
+        .find_map(|tool| Some((tool, asset_url(&format!("{archive_name}{}", tool.signature_extension()))?)))
+        .ok_or_else(|| format!("release {} has no signature for {archive_name}", release.tag_name))?;
+
+    let client = reqwest::Client::builder().user_agent(format!("hyperforge/{CURRENT_VERSION}")).build().map_err(|e| e.to_string())?;
+    let archive = download(&client, &archive_url).await?;
+    if let Some(sums_url) = asset_url(CHECKSUMS_FILE) {
+        let sums = String::from_utf8(download(&client, &sums_url).await?).map_err(|_| format!("{CHECKSUMS_FILE} is not text"))?;
+        if expected_checksum(&sums, &archive_name) != Some(sha256_hex(&archive).as_str()) {
+            return Err(format!("{archive_name} doesn't match {CHECKSUMS_FILE}"));
+        }
+    }
+
+    // The signing tools verify files
+    let scratch = std::env::temp_dir().join(format!("hyperforge-upgrade-{}", uuid::Uuid::new_v4()));
+    let verified = async {
+        std::fs::create_dir_all(&scratch).map_err(|e| e.to_string())?;
+        let archive_path = scratch.join(&archive_name);
+        let signature_path = scratch.join(format!("{archive_name}{}", tool.signature_extension()));
+        std::fs::write(&archive_path, &archive).map_err(|e| e.to_string())?;
+        std::fs::write(&signature_path, download(&client, &signature_url).await?).map_err(|e| e.to_string())?;
+        verify_signature(tool, &public_key, &archive_path, &signature_path)
+            .await
+            .map_err(|e| format!("{tool} signature of {archive_name} doesn't verify: {e}"))
+    }
+    .await;
+    let _ = std::fs::remove_dir_all(&scratch);
+    verified?;
+
+    let binary_name = exe.file_name().and_then(|n| n.to_str()).ok_or("invalid executable path")?;
+    replace_executable(exe, &extract_binary(&archive, binary_name)?)
+}
+
+/// The newer version found by the startup check, if any
+pub fn known_update() -> Option<&'static AvailableRelease> {
+    startup_result().get().and_then(Option::as_ref)
+}
+
+fn startup_result() -> &'static OnceLock<Option<AvailableRelease>> {
+    static RESULT: OnceLock<Option<AvailableRelease>> = OnceLock::new();
+    &RESULT
+}
+
+/// Check for a newer release in the background, unless disabled
+pub fn spawn_startup_check(config_dir: PathBuf) {
+    let config = UserConfig::load(&config_dir).upgrade;
+    if !config.check_on_startup {
+        return;
+    }
+    tokio::spawn(async move {
+        match list_releases(&config).await {
+            Ok(releases) => {
+                let available = select_release(&releases, CURRENT_VERSION, None);
+                if let Some(available) = &available {
+                    tracing::info!(
+                        "hyperforge {} is available (running {CURRENT_VERSION}); run `hyperforge upgrade` to install it",
+                        available.version
+                    );
+                }
+                let _ = startup_result().set(available);
+            }
+            Err(e) => tracing::debug!("startup version check failed: {e}"),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::releases::AssetInfo;
+    use chrono::Utc;
+
+    fn release(tag: &str, prerelease: bool) -> ReleaseInfo {
+        ReleaseInfo {
+            id: 1,
+            tag_name: tag.to_string(),
+            name: tag.to_string(),
+            body: String::new(),
+            draft: false,
+            prerelease,
+            created_at: Utc::now(),
+            assets: Vec::<AssetInfo>::new(),
+        }
+    }
+
+    #[test]
+    fn test_select_release() {
+        let releases = [release("v4.1.3", false), release("v4.2.0", false), release("v5.0.0-rc1", true), release("v4.10.0", false)];
+        assert_eq!(select_release(&releases, "4.1.3", None).unwrap().version, "4.10.0");
+        assert!(select_release(&releases, "4.10.0", None).is_none());
+        assert_eq!(select_release(&releases, "4.10.0", Some("4.2.0")).unwrap().version, "4.2.0");
+        assert!(select_release(&releases, "4.1.3", Some("v9.9.9")).is_none());
+    }
+
+    #[test]
+    fn test_expected_checksum() {
+        let sums = "abc123  hyperforge-x86_64-unknown-linux-gnu-v4.2.0.tar.gz\ndef456 *hyperforge-aarch64-apple-darwin-v4.2.0.tar.gz\n";
+        assert_eq!(expected_checksum(sums, "hyperforge-x86_64-unknown-linux-gnu-v4.2.0.tar.gz"), Some("abc123"));
+        assert_eq!(expected_checksum(sums, "hyperforge-aarch64-apple-darwin-v4.2.0.tar.gz"), Some("def456"));
+        assert_eq!(expected_checksum(sums, "hyperforge-x86_64-pc-windows-msvc-v4.2.0.tar.gz"), None);
+    }
+
+    #[test]
+    fn test_extract_and_replace() {
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(4);
+        header.set_mode(0o755);
+        header.set_cksum();
+        tar.append_data(&mut header, "hyperforge-x-v4.2.0/hyperforge", &b"new!"[..]).unwrap();
+        let archive = tar.into_inner().unwrap().finish().unwrap();
+
+        let binary = extract_binary(&archive, "hyperforge").unwrap();
+        assert_eq!(binary, b"new!");
+        assert!(extract_binary(&archive, "hyperforge-auth").is_err());
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let exe = temp.path().join("hyperforge");
+        std::fs::write(&exe, b"old").unwrap();
+        replace_executable(&exe, &binary).unwrap();
+        assert_eq!(std::fs::read(&exe).unwrap(), b"new!");
+        assert!(!temp.path().join(".hyperforge.upgrade").exists());
+    }
+}
@@ -0,0 +1,191 @@
+//! `WebhookService` - Keep a repo's configured webhooks on its forges
+//!
+//! `[webhooks.<name>]` entries identify hooks by payload URL, so hooks
+//! added by hand or by forge integrations are never touched. Converging one
+//! forge:
+//! - A hook at a configured URL with the configured events is left alone
+//! - One with other events (or disabled) is deleted and created again;
+//!   forges don't report secrets, so recreating is the only way to be sure
+//! - Missing hooks are created
+//! - `remove = true` entries, and entries limited to other forges, are
+//!   deleted wherever they are found
+
+use std::collections::BTreeMap;
+
+use crate::adapters::{ForgeError, ForgePort, ForgeResult, NewWebhook, Webhook};
+use crate::types::{WebhookConfig, WebhookEvent};
+
+/// How one configured hook changes on a forge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookAction {
+    Create,
+    /// Present with the wrong events: deleted and created again
+    Replace,
+    Delete,
+    Unchanged,
+}
+
+impl WebhookAction {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Create => "created",
+            Self::Replace => "replaced",
+            Self::Delete => "deleted",
+            Self::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// What converging one configured hook did (or would do)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookChange {
+    /// Name under `[webhooks]`
+    pub hook: String,
+    pub url: String,
+    pub action: WebhookAction,
+    /// Forge hooks at the URL that get deleted
+    pub stale_ids: Vec<u64>,
+}
+
+/// Service for converging webhooks
+pub struct WebhookService;
+
+impl WebhookService {
+    /// Changes that make `existing` (the hooks on the `forges` entry
+    /// `forge`) match `desired`
+    pub fn plan(desired: &BTreeMap<String, WebhookConfig>, forge: &str, existing: &[Webhook]) -> Vec<WebhookChange> {
+        let sorted = |events: &[WebhookEvent]| {
+            let mut events = events.to_vec();
+            events.sort();
+            events.dedup();
+            events
+        };
+
+        let mut changes = Vec::new();
+        for (hook, config) in desired {
+            let matching: Vec<&Webhook> = existing.iter().filter(|h| h.url == config.url).collect();
+            let stale_ids: Vec<u64> = matching.iter().map(|h| h.id).collect();
+
+            let action = if config.remove || !config.applies_to(forge) {
+                if matching.is_empty() {
+                    continue;
+                }
+                WebhookAction::Delete
+            } else {
+                match matching.as_slice() {
+                    [] => WebhookAction::Create,
+                    [only] if only.active && sorted(&only.events) == sorted(&config.events) => WebhookAction::Unchanged,
+                    // Duplicates at the URL collapse into one
+                    _ => WebhookAction::Replace,
+                }
+            };
+
+            changes.push(WebhookChange {
+                hook: hook.clone(),
+                url: config.url.clone(),
+                stale_ids: if action == WebhookAction::Unchanged { Vec::new() } else { stale_ids },
+                action,
+            });
+        }
+        changes
+    }
+
+    /// Converge `org/name`'s hooks on the `forges` entry `forge`: `None` if
+    /// the forge doesn't expose webhooks. Dry runs list but never change.
+    ///
+    /// Secrets are read from each hook's `secret_env` just before it is
+    /// created; a missing variable fails before the old hook is deleted.
+    pub async fn converge(
+        port: &dyn ForgePort,
+        org: &str,
+        name: &str,
+        forge: &str,
+        desired: &BTreeMap<String, WebhookConfig>,
+        dry_run: bool,
+    ) -> ForgeResult<Option<Vec<WebhookChange>>> {
+        let Some(existing) = port.list_webhooks(org, name).await? else {
+            return Ok(None);
+        };
+        let changes = Self::plan(desired, forge, &existing);
+        if dry_run {
+            return Ok(Some(changes));
+        }
+
+        for change in &changes {
+            let create = match change.action {
+                WebhookAction::Unchanged => continue,
+                WebhookAction::Delete => None,
+                WebhookAction::Create | WebhookAction::Replace => {
+                    let config = &desired[&change.hook];
+                    let secret = match &config.secret_env {
+                        Some(var) => Some(std::env::var(var).map_err(|_| ForgeError::AuthenticationFailed {
+                            message: format!("webhook '{}' needs ${var} for its secret, which is not set", change.hook),
+                        })?),
+                        None => None,
+                    };
+                    Some(NewWebhook { url: config.url.clone(), events: config.events.clone(), secret })
+                }
+            };
+
+            for id in &change.stale_ids {
+                port.delete_webhook(org, name, *id).await?;
+            }
+            if let Some(hook) = create {
+                port.create_webhook(org, name, &hook).await?;
+            }
+        }
+
+        Ok(Some(changes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(url: &str, events: Vec<WebhookEvent>) -> WebhookConfig {
+        WebhookConfig { url: url.to_string(), events, secret_env: None, forges: Vec::new(), remove: false }
+    }
+
+    fn hook(id: u64, url: &str, events: Vec<WebhookEvent>) -> Webhook {
+        Webhook { id, url: url.to_string(), events, active: true }
+    }
+
+    #[test]
+    fn test_plan_creates_replaces_and_deletes() {
+        let mut desired = BTreeMap::new();
+        desired.insert("ci".to_string(), config("https://ci/hook", vec![WebhookEvent::PullRequest, WebhookEvent::Push]));
+        desired.insert("chat".to_string(), config("https://chat/hook", vec![WebhookEvent::Push]));
+        desired.insert("docs".to_string(), config("https://docs/hook", vec![WebhookEvent::Release]));
+        desired.insert("old".to_string(), WebhookConfig { remove: true, ..config("https://old/hook", vec![]) });
+        desired.insert(
+            "gitlab_only".to_string(),
+            WebhookConfig { forges: vec!["gitlab".to_string()], ..config("https://gl/hook", vec![WebhookEvent::Push]) },
+        );
+
+        let existing = vec![
+            hook(1, "https://ci/hook", vec![WebhookEvent::Push, WebhookEvent::PullRequest]),
+            hook(2, "https://chat/hook", vec![WebhookEvent::Push, WebhookEvent::Issues]),
+            hook(3, "https://old/hook", vec![WebhookEvent::Push]),
+            hook(4, "https://gl/hook", vec![WebhookEvent::Push]),
+            hook(5, "https://manual/hook", vec![WebhookEvent::Push]),
+        ];
+
+        let changes = WebhookService::plan(&desired, "github", &existing);
+        let actions: Vec<_> = changes.iter().map(|c| (c.hook.as_str(), c.action, c.stale_ids.clone())).collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("chat", WebhookAction::Replace, vec![2]),
+                ("ci", WebhookAction::Unchanged, vec![]),
+                ("docs", WebhookAction::Create, vec![]),
+                ("gitlab_only", WebhookAction::Delete, vec![4]),
+                ("old", WebhookAction::Delete, vec![3]),
+            ]
+        );
+
+        // On the forge it's limited to, the restricted hook is kept
+        let gitlab = WebhookService::plan(&desired, "gitlab", &existing);
+        assert!(gitlab.iter().any(|c| c.hook == "gitlab_only" && c.action == WebhookAction::Unchanged));
+    }
+}
@@ -0,0 +1,199 @@
+//! Line input for the shell
+//!
+//! On a terminal the line is edited in raw mode (set with `stty`, restored
+//! on drop) so Tab and the arrow keys reach us: Tab completes, Up/Down walk
+//! the history, Ctrl-U clears the line, Ctrl-C abandons it and Ctrl-D on an
+//! empty line ends the session. Piped input is read line by line.
+
+use std::io::{BufRead, Read, Write};
+use std::process::{Command, Stdio};
+
+/// Completes the word ending at the cursor: where that word starts in the
+/// line, and the candidates that could replace it
+pub type Completer<'a> = dyn Fn(&str) -> (usize, Vec<String>) + 'a;
+
+/// Reads lines from stdin
+pub struct Editor {
+    history: Vec<String>,
+    raw: bool,
+}
+
+impl Editor {
+    /// An editor recalling `history` (oldest first); raw mode only when
+    /// stdin is a terminal `stty` can drive
+    pub fn new(history: Vec<String>) -> Self {
+        let raw = std::io::IsTerminal::is_terminal(&std::io::stdin()) && stty(&["-g"]).is_some();
+        Self { history, raw }
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Remember `line`, skipping a repeat of the previous one
+    pub fn add_history(&mut self, line: &str) {
+        if self.history.last().is_none_or(|last| last != line) {
+            self.history.push(line.to_string());
+        }
+    }
+
+    /// The next line, or `None` at end of input
+    pub fn read_line(&mut self, prompt: &str, complete: &Completer<'_>) -> std::io::Result<Option<String>> {
+        if !self.raw {
+            print!("{prompt}");
+            std::io::stdout().flush()?;
+            let mut line = String::new();
+            return Ok((std::io::stdin().lock().read_line(&mut line)? > 0).then(|| line.trim_end().to_string()));
+        }
+        let Some(_raw) = RawMode::enter() else {
+            self.raw = false;
+            return self.read_line(prompt, complete);
+        };
+        self.edit(prompt, complete)
+    }
+
+    fn edit(&self, prompt: &str, complete: &Completer<'_>) -> std::io::Result<Option<String>> {
+        let mut out = std::io::stdout();
+        let mut input = std::io::stdin().lock();
+        let mut line = String::new();
+        let mut pending = Vec::new();
+        // Position in history while browsing; `history.len()` is the new line
+        let mut recalled = self.history.len();
+        let redraw = |out: &mut std::io::Stdout, line: &str| {
+            write!(out, "\r\x1b[K{prompt}{line}")?;
+            out.flush()
+        };
+        redraw(&mut out, &line)?;
+
+        loop {
+            let mut byte = [0u8; 1];
+            if input.read(&mut byte)? == 0 {
+                return Ok(None);
+            }
+            match byte[0] {
+                b'\r' | b'\n' => {
+                    write!(out, "\r\n")?;
+                    return Ok(Some(line));
+                }
+                // Ctrl-D
+                0x04 if line.is_empty() => {
+                    write!(out, "\r\n")?;
+                    return Ok(None);
+                }
+                // Ctrl-C
+                0x03 => {
+                    write!(out, "^C\r\n")?;
+                    line.clear();
+                    recalled = self.history.len();
+                }
+                // Ctrl-U
+                0x15 => line.clear(),
+                0x7f | 0x08 => {
+                    line.pop();
+                }
+                b'\t' => {
+                    let (start, candidates) = complete(&line);
+                    match candidates.as_slice() {
+                        [] => {}
+                        [only] => {
+                            line.truncate(start);
+                            line.push_str(only);
+                            line.push(' ');
+                        }
+                        many => {
+                            let prefix = common_prefix(many);
+                            if prefix.len() > line.len() - start {
+                                line.truncate(start);
+                                line.push_str(&prefix);
+                            } else {
+                                write!(out, "\r\n{}\r\n", many.join("  "))?;
+                            }
+                        }
+                    }
+                }
+                // Escape sequences: only Up and Down are handled
+                0x1b => {
+                    let mut seq = [0u8; 2];
+                    input.read_exact(&mut seq)?;
+                    match seq {
+                        [b'[', b'A'] if recalled > 0 => {
+                            recalled -= 1;
+                            line.clone_from(&self.history[recalled]);
+                        }
+                        [b'[', b'B'] if recalled < self.history.len() => {
+                            recalled += 1;
+                            line = self.history.get(recalled).cloned().unwrap_or_default();
+                        }
+                        _ => {}
+                    }
+                }
+                byte if byte >= 0x20 => {
+                    pending.push(byte);
+                    if let Ok(text) = std::str::from_utf8(&pending) {
+                        line.push_str(text);
+                        pending.clear();
+                    } else if pending.len() >= 4 {
+                        pending.clear();
+                    }
+                }
+                _ => {}
+            }
+            redraw(&mut out, &line)?;
+        }
+    }
+}
+
+/// The longest prefix all `words` share
+fn common_prefix(words: &[String]) -> String {
+    let Some((first, rest)) = words.split_first() else {
+        return String::new();
+    };
+    let mut prefix = first.as_str();
+    for word in rest {
+        while !word.starts_with(prefix) {
+            let mut chars = prefix.chars();
+            chars.next_back();
+            prefix = chars.as_str();
+        }
+    }
+    prefix.to_string()
+}
+
+/// Run `stty` on the controlling terminal, returning its output
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty").args(args).stdin(Stdio::inherit()).stderr(Stdio::null()).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The terminal in raw mode until dropped
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enter() -> Option<Self> {
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+        Some(Self { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_prefix() {
+        let words = |w: &[&str]| w.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(common_prefix(&words(&["push_all", "pull_all"])), "pu");
+        assert_eq!(common_prefix(&words(&["snapshot", "snapshot_diff"])), "snapshot");
+        assert_eq!(common_prefix(&words(&["sync", "diff"])), "");
+        assert_eq!(common_prefix(&[]), "");
+    }
+}
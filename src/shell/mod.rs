@@ -0,0 +1,509 @@
+//! `hyperforge shell`: an interactive prompt over one workspace
+//!
+//! For operators running many small commands against the same workspace.
+//! A line is `[hub] method [value ...] [--param value ...]`, routed to the
+//! hubs in-process and rendered like `hyperforge --render`; the hub defaults
+//! to `workspace` (`hyperforge` names the root hub). Bare values fill the
+//! method's required parameters in order, so `repo sync acme my-tool` works.
+//!
+//! The session supplies what would otherwise be repeated on every line:
+//! `--path` is always the workspace, and the org and include/exclude
+//! filters chosen with `use org` and `filter` are passed to every method
+//! that takes them. The session and the command history are kept in the
+//! workspace's `.hyperforge/` and restored next time.
+
+mod editor;
+
+use futures::StreamExt;
+use plexus_core::plexus::{Activation, DynamicHub, PlexusStreamItem, PluginSchema};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::commands::workspace::discover_workspace;
+use crate::help::{self, MethodHelp};
+use crate::hub::{HyperforgeEvent, HyperforgeHub};
+use crate::hubs::{BuildHub, RegistryHub, RepoHub, WorkspaceHub};
+use crate::render::Renderer;
+
+pub use editor::{Completer, Editor};
+
+const SESSION_FILE: &str = "shell-session.toml";
+const HISTORY_FILE: &str = "shell-history";
+/// History lines kept between sessions
+const HISTORY_LIMIT: usize = 1000;
+
+/// The hub a line without one goes to
+const DEFAULT_HUB: &str = "workspace";
+/// What the root hub is called at the prompt
+const ROOT_HUB: &str = "hyperforge";
+
+/// Shell commands that aren't hub methods
+const BUILTINS: &[&str] = &["use", "filter", "session", "repos", "history", "exit", "quit"];
+
+/// Parameters naming a repo, completed from the workspace's repos
+const REPO_PARAMS: &[&str] = &["name", "repo", "repo_name"];
+
+/// State carried from one command to the next, and across sessions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    /// Passed as `--org` to methods that take one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org: Option<String>,
+    /// Passed as `--include` to methods that take one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Passed as `--exclude` to methods that take one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+impl Session {
+    fn path(workspace_root: &Path) -> PathBuf {
+        workspace_root.join(crate::config::CONFIG_DIR).join(SESSION_FILE)
+    }
+
+    /// The session saved for `workspace_root`, or an empty one
+    pub fn load(workspace_root: &Path) -> Self {
+        std::fs::read_to_string(Self::path(workspace_root))
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, workspace_root: &Path) -> Result<(), String> {
+        let path = Self::path(workspace_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self).map_err(|e| format!("Failed to serialize shell session: {e}"))?;
+        std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    fn describe(&self) -> String {
+        let list = |globs: &[String]| if globs.is_empty() { "-".to_string() } else { globs.join(" ") };
+        format!(
+            "org: {}  include: {}  exclude: {}",
+            self.org.as_deref().unwrap_or("-"),
+            list(&self.include),
+            list(&self.exclude),
+        )
+    }
+}
+
+/// Every hub's methods, as the prompt addresses them
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    hubs: Vec<(String, Vec<MethodHelp>)>,
+}
+
+impl Catalog {
+    /// The methods of `hub` and all its children
+    pub fn new(hub: &HyperforgeHub) -> Self {
+        let state = hub.state.clone();
+        let repo = RepoHub::new(state.clone());
+        let workspace = WorkspaceHub::new(state.clone());
+        let schemas: Vec<(&str, PluginSchema)> = vec![
+            (ROOT_HUB, hub.plugin_schema()),
+            ("repo", repo.plugin_schema()),
+            ("repo.images", crate::hubs::images::ImagesHub::new(state.clone()).plugin_schema()),
+            ("repo.releases", crate::hubs::releases::ReleasesHub::new(state.clone()).plugin_schema()),
+            ("workspace", workspace.plugin_schema()),
+            ("workspace.campaigns", crate::hubs::campaigns::CampaignsHub::new(state.clone()).plugin_schema()),
+            ("registry", RegistryHub::new(state.clone()).plugin_schema()),
+            ("build", BuildHub::new(state).plugin_schema()),
+        ];
+        Self::from_schemas(schemas)
+    }
+
+    fn from_schemas<'a>(schemas: impl IntoIterator<Item = (&'a str, PluginSchema)>) -> Self {
+        let hubs = schemas
+            .into_iter()
+            .map(|(name, schema)| (name.to_string(), help::catalog(&schema, "", None).unwrap_or_default()))
+            .collect();
+        Self { hubs }
+    }
+
+    fn hub(&self, name: &str) -> Option<&[MethodHelp]> {
+        self.hubs.iter().find(|(hub, _)| hub == name).map(|(_, methods)| methods.as_slice())
+    }
+
+    fn method(&self, hub: &str, method: &str) -> Option<&MethodHelp> {
+        self.hub(hub)?.iter().find(|m| m.method == method)
+    }
+
+    fn hub_names(&self) -> impl Iterator<Item = &str> {
+        self.hubs.iter().map(|(hub, _)| hub.as_str())
+    }
+}
+
+/// What the workspace offers for completion
+#[derive(Debug, Clone, Default)]
+pub struct Names {
+    pub repos: Vec<String>,
+    pub orgs: Vec<String>,
+}
+
+/// A parsed line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    Empty,
+    /// `use org <name>`; `None` clears it
+    UseOrg(Option<String>),
+    /// `filter include|exclude <glob>...`, or `filter clear`
+    Filter { include: Vec<String>, exclude: Vec<String> },
+    Session,
+    Repos,
+    History,
+    Exit,
+    /// A hub method: its route below the root (`hyperforge.workspace.status`) and params
+    Call { route: String, params: Value },
+}
+
+/// Parse `line` into a builtin or a hub call, filling in what `session`
+/// and `workspace` supply
+pub fn parse(line: &str, catalog: &Catalog, session: &Session, workspace: &Path) -> Result<Line, String> {
+    let words = split_words(line)?;
+    let Some((first, rest)) = words.split_first() else {
+        return Ok(Line::Empty);
+    };
+    match (first.as_str(), rest) {
+        ("exit" | "quit", _) => return Ok(Line::Exit),
+        ("session", _) => return Ok(Line::Session),
+        ("repos", _) => return Ok(Line::Repos),
+        ("history", _) => return Ok(Line::History),
+        ("use", [what, name]) if what == "org" => return Ok(Line::UseOrg(Some(name.clone()))),
+        ("use", [what]) if what == "org" => return Ok(Line::UseOrg(None)),
+        ("use", _) => return Err("Usage: use org [<name>]".to_string()),
+        ("filter", [what]) if what == "clear" => {
+            return Ok(Line::Filter { include: Vec::new(), exclude: Vec::new() });
+        }
+        ("filter", [what, globs @ ..]) if what == "include" => {
+            return Ok(Line::Filter { include: globs.to_vec(), exclude: session.exclude.clone() });
+        }
+        ("filter", [what, globs @ ..]) if what == "exclude" => {
+            return Ok(Line::Filter { include: session.include.clone(), exclude: globs.to_vec() });
+        }
+        ("filter", _) => return Err("Usage: filter include|exclude <glob>... | filter clear".to_string()),
+        _ => {}
+    }
+
+    let (hub, method, args) = match (catalog.hub(first), rest.split_first()) {
+        (Some(_), Some((method, args))) => (first.as_str(), method.as_str(), args),
+        (Some(_), None) => (first.as_str(), "help", rest),
+        (None, _) => (DEFAULT_HUB, first.as_str(), rest),
+    };
+    let Some(info) = catalog.method(hub, method) else {
+        return Err(format!("No method '{method}' on {hub}; `{hub} help` lists them"));
+    };
+
+    let mut params = Map::new();
+    let mut positional = Vec::new();
+    let mut args = args.iter().peekable();
+    while let Some(arg) = args.next() {
+        let Some(name) = arg.strip_prefix("--") else {
+            positional.push(arg.clone());
+            continue;
+        };
+        let Some(param) = info.params.iter().find(|p| p.name == name) else {
+            return Err(format!("{hub} {method} has no --{name}"));
+        };
+        let value = match args.next_if(|next| !next.starts_with("--")) {
+            Some(value) => value_of(value),
+            None => Value::Bool(true),
+        };
+        let is_list = param.ty.ends_with("[]");
+        match params.get_mut(name) {
+            Some(Value::Array(values)) if is_list => values.push(value),
+            _ if is_list => {
+                params.insert(name.to_string(), Value::Array(vec![value]));
+            }
+            _ => {
+                params.insert(name.to_string(), value);
+            }
+        }
+    }
+
+    let session_values = [
+        ("path", Some(Value::String(workspace.display().to_string()))),
+        ("org", session.org.clone().map(Value::String)),
+        ("include", (!session.include.is_empty()).then(|| Value::from(session.include.clone()))),
+        ("exclude", (!session.exclude.is_empty()).then(|| Value::from(session.exclude.clone()))),
+    ];
+    for (name, value) in session_values {
+        if let (Some(value), false) = (value, params.contains_key(name)) {
+            if info.params.iter().any(|p| p.name == name) {
+                params.insert(name.to_string(), value);
+            }
+        }
+    }
+
+    let unfilled: Vec<String> = info
+        .params
+        .iter()
+        .filter(|p| p.required && !params.contains_key(&p.name))
+        .map(|p| p.name.clone())
+        .collect();
+    if let Some(extra) = positional.get(unfilled.len()) {
+        return Err(format!("Unexpected '{extra}'; {hub} {method} takes no more values"));
+    }
+    for (name, value) in unfilled.into_iter().zip(&positional) {
+        params.insert(name, value_of(value));
+    }
+
+    let route = if hub == ROOT_HUB { format!("{ROOT_HUB}.{method}") } else { format!("{ROOT_HUB}.{hub}.{method}") };
+    Ok(Line::Call { route, params: Value::Object(params) })
+}
+
+/// `true`, numbers, JSON arrays and the like as JSON; anything else as a string
+fn value_of(word: &str) -> Value {
+    match serde_json::from_str::<Value>(word) {
+        Ok(value @ (Value::Bool(_) | Value::Number(_) | Value::Array(_) | Value::Object(_))) => value,
+        _ => Value::String(word.to_string()),
+    }
+}
+
+/// Split `line` at whitespace, keeping quoted runs together
+fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err("Unterminated quote".to_string());
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Candidates for the word being typed at the end of `line`: builtins,
+/// hubs and methods first, then `--params`, then repo and org names
+pub fn complete(line: &str, catalog: &Catalog, names: &Names) -> (usize, Vec<String>) {
+    let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+    let word = &line[start..];
+    let before: Vec<&str> = line[..start].split_whitespace().collect();
+
+    let candidates: Vec<String> = match before.as_slice() {
+        [] => BUILTINS
+            .iter()
+            .copied()
+            .chain(catalog.hub_names())
+            .chain(catalog.hub(DEFAULT_HUB).unwrap_or_default().iter().map(|m| m.method.as_str()))
+            .map(str::to_string)
+            .collect(),
+        ["use"] => vec!["org".to_string()],
+        ["use", "org"] => names.orgs.clone(),
+        ["filter"] => vec!["include".to_string(), "exclude".to_string(), "clear".to_string()],
+        ["filter", "include" | "exclude", ..] => names.repos.clone(),
+        [hub] if catalog.hub(hub).is_some() => {
+            catalog.hub(hub).unwrap_or_default().iter().map(|m| m.method.clone()).collect()
+        }
+        [first, rest @ ..] => {
+            let (hub, method, args) = match (catalog.hub(first), rest.split_first()) {
+                (Some(_), Some((method, args))) => (*first, *method, args),
+                _ => (DEFAULT_HUB, *first, rest),
+            };
+            let Some(info) = catalog.method(hub, method) else {
+                return (start, Vec::new());
+            };
+            if word.starts_with("--") {
+                info.params.iter().map(|p| format!("--{}", p.name)).collect()
+            } else {
+                // The parameter this value is for: the preceding --flag, or
+                // the next required one for a bare value
+                let param = args.last().and_then(|a| a.strip_prefix("--")).or_else(|| {
+                    // Values after a --flag belong to it, as in `parse`
+                    let given = args
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, a)| !a.starts_with("--") && (*i == 0 || !args[i - 1].starts_with("--")))
+                        .count();
+                    info.params.iter().filter(|p| p.required && p.name != "path").nth(given).map(|p| p.name.as_str())
+                });
+                match param {
+                    Some("org") => names.orgs.clone(),
+                    Some(param) if REPO_PARAMS.contains(&param) => names.repos.clone(),
+                    _ => Vec::new(),
+                }
+            }
+        }
+    };
+
+    let mut matching: Vec<String> = candidates.into_iter().filter(|c| c.starts_with(word)).collect();
+    matching.sort();
+    matching.dedup();
+    (start, matching)
+}
+
+fn history_path(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(crate::config::CONFIG_DIR).join(HISTORY_FILE)
+}
+
+fn load_history(workspace_root: &Path) -> Vec<String> {
+    std::fs::read_to_string(history_path(workspace_root))
+        .map(|content| content.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn save_history(workspace_root: &Path, history: &[String]) {
+    let kept = &history[history.len().saturating_sub(HISTORY_LIMIT)..];
+    let _ = std::fs::write(history_path(workspace_root), kept.join("\n") + "\n");
+}
+
+/// Run the shell over the workspace at `path` until `exit` or end of input
+pub async fn run(path: &Path, color: bool) -> Result<(), String> {
+    let ctx = discover_workspace(path).map_err(|e| format!("Discovery failed: {e}"))?;
+    let root = ctx.root.clone();
+    let names = Names {
+        repos: ctx.repos.iter().map(|r| r.dir_name.clone()).collect(),
+        orgs: ctx.orgs.clone(),
+    };
+
+    let hub = HyperforgeHub::new();
+    let catalog = Catalog::new(&hub);
+    let router = Arc::new(DynamicHub::new("shell").register(hub));
+    let mut session = Session::load(&root);
+    let mut editor = Editor::new(load_history(&root));
+
+    println!("hyperforge shell on {} ({} repos). `help` lists methods, Tab completes, `exit` leaves.", root.display(), names.repos.len());
+    if session != Session::default() {
+        println!("Restored session: {}", session.describe());
+    }
+
+    loop {
+        let prompt = format!("hyperforge:{}> ", session.org.as_deref().unwrap_or_else(|| root.file_name().and_then(|n| n.to_str()).unwrap_or("")));
+        let completer = |line: &str| complete(line, &catalog, &names);
+        let Some(line) = tokio::task::block_in_place(|| editor.read_line(&prompt, &completer)).map_err(|e| e.to_string())? else {
+            break;
+        };
+        if !line.trim().is_empty() {
+            editor.add_history(line.trim());
+        }
+
+        match parse(&line, &catalog, &session, &root) {
+            Ok(Line::Empty) => {}
+            Ok(Line::Exit) => break,
+            Ok(Line::Session) => println!("{}", session.describe()),
+            Ok(Line::Repos) => println!("{}", names.repos.join("  ")),
+            Ok(Line::History) => {
+                for (i, entry) in editor.history().iter().enumerate() {
+                    println!("{:>4}  {entry}", i + 1);
+                }
+            }
+            Ok(Line::UseOrg(org)) => {
+                session.org = org;
+                if let Err(e) = session.save(&root) {
+                    eprintln!("{e}");
+                }
+            }
+            Ok(Line::Filter { include, exclude }) => {
+                session.include = include;
+                session.exclude = exclude;
+                if let Err(e) = session.save(&root) {
+                    eprintln!("{e}");
+                }
+                println!("{}", session.describe());
+            }
+            Ok(Line::Call { route, params }) => match router.route(&route, params, None).await {
+                Ok(mut stream) => {
+                    let mut renderer = Renderer::new(color);
+                    while let Some(item) = stream.next().await {
+                        match item {
+                            PlexusStreamItem::Data { content, .. } => match serde_json::from_value::<HyperforgeEvent>(content.clone()) {
+                                Ok(event) => {
+                                    for rendered in renderer.render(&event) {
+                                        println!("{rendered}");
+                                    }
+                                }
+                                Err(_) => println!("{content}"),
+                            },
+                            PlexusStreamItem::Error { message, .. } => eprintln!("error: {message}"),
+                            _ => {}
+                        }
+                    }
+                }
+                Err(e) => eprintln!("error: {e}"),
+            },
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+
+    save_history(&root, editor.history());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn catalog() -> Catalog {
+        Catalog::new(&HyperforgeHub::new())
+    }
+
+    #[test]
+    fn test_parse_fills_session_and_positionals() {
+        let catalog = catalog();
+        let workspace = Path::new("/ws");
+        let session = Session { org: Some("acme".to_string()), include: vec!["api*".to_string()], exclude: Vec::new() };
+
+        let Ok(Line::Call { route, params }) = parse("status --fetch", &catalog, &session, workspace) else {
+            panic!("expected a call");
+        };
+        assert_eq!(route, "hyperforge.workspace.status");
+        assert_eq!(params, json!({ "path": "/ws", "fetch": true, "include": ["api*"] }));
+
+        let Ok(Line::Call { route, params }) = parse("sync --exclude old --exclude 'legacy tools' --dry_run true", &catalog, &session, workspace) else {
+            panic!("expected a call");
+        };
+        assert_eq!(route, "hyperforge.workspace.sync");
+        assert_eq!(params["org"], "acme");
+        assert_eq!(params["include"], json!(["api*"]));
+        assert_eq!(params["exclude"], json!(["old", "legacy tools"]));
+
+        let Ok(Line::Call { route, params }) = parse("repo sync my-tool", &catalog, &session, workspace) else {
+            panic!("expected a call");
+        };
+        assert_eq!(route, "hyperforge.repo.sync");
+        assert_eq!(params, json!({ "org": "acme", "name": "my-tool" }));
+
+        assert_eq!(parse("use org acme", &catalog, &session, workspace), Ok(Line::UseOrg(Some("acme".to_string()))));
+        assert!(parse("status --nope", &catalog, &session, workspace).is_err());
+        assert!(parse("frobnicate", &catalog, &session, workspace).is_err());
+    }
+
+    #[test]
+    fn test_complete() {
+        let catalog = catalog();
+        let names = Names { repos: vec!["api".to_string(), "web".to_string()], orgs: vec!["acme".to_string()] };
+
+        assert_eq!(complete("snap", &catalog, &names), (0, vec!["snapshot".to_string(), "snapshot_diff".to_string()]));
+        assert_eq!(complete("status --fe", &catalog, &names), (7, vec!["--fetch".to_string()]));
+        assert_eq!(complete("repo sync ", &catalog, &names), (10, vec!["acme".to_string()]));
+        assert_eq!(complete("repo sync acme ", &catalog, &names), (15, vec!["api".to_string(), "web".to_string()]));
+        assert_eq!(complete("repo sync --dry_run true acme w", &catalog, &names), (30, vec!["web".to_string()]));
+        assert_eq!(complete("use org a", &catalog, &names), (8, vec!["acme".to_string()]));
+        assert!(complete("repo.images ", &catalog, &names).1.contains(&"help".to_string()));
+    }
+
+    #[test]
+    fn test_session_roundtrip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert_eq!(Session::load(temp.path()), Session::default());
+        let session = Session { org: Some("acme".to_string()), include: Vec::new(), exclude: vec!["old*".to_string()] };
+        session.save(temp.path()).unwrap();
+        assert_eq!(Session::load(temp.path()), session);
+    }
+}
@@ -15,7 +15,7 @@ pub enum RunnerType {
 
 /// A single runner in the layered CI pipeline.
 /// Runners are ordered by rigor: index 0 = quickest, higher = more thorough.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RunnerConfig {
     /// Runner type: local (direct execution) or docker (containerized)
     #[serde(rename = "type")]
@@ -47,7 +47,7 @@ const fn default_timeout() -> u64 {
 }
 
 /// CI/validation configuration for a repo
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[derive(Default)]
 pub struct CiConfig {
     /// Skip all CI for this repo
@@ -57,6 +57,50 @@ pub struct CiConfig {
     /// Ordered list of runners (escalating rigor)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub runners: Vec<RunnerConfig>,
+
+    /// Dockerfile (relative to the repo root) to build the image `build validate`
+    /// runs in, instead of the default image
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dockerfile: Option<String>,
+
+    /// Split `build validate` tests across this many parallel containers.
+    /// Applies to test commands that support partitioning (cargo nextest, jest).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_shards: Option<u32>,
+
+    /// Coverage collection for `build validate --coverage`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<CoverageConfig>,
+
+    /// Benchmarks for `workspace bench`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bench: Option<BenchConfig>,
+}
+
+/// Benchmark settings under `[ci.bench]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BenchConfig {
+    /// Benchmark command, run from the repo root
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub command: Vec<String>,
+
+    /// Slowdown versus the baseline, in percent, that counts as a regression
+    /// (overrides the `workspace bench` threshold)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_regression_pct: Option<f64>,
+}
+
+/// Coverage settings under `[ci.coverage]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CoverageConfig {
+    /// Command that writes an LCOV report to `/coverage/lcov.info` in the
+    /// validation container (default: cargo-llvm-cov or jest, by build system)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub command: Vec<String>,
+
+    /// Fail validation when this repo's line coverage is below this percentage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_line_pct: Option<f64>,
 }
 
 
@@ -88,6 +132,10 @@ pub fn default_ci_config(build_systems: &[BuildSystemKind]) -> CiConfig {
     match primary {
         BuildSystemKind::Cargo => CiConfig {
             skip_validate: false,
+            dockerfile: None,
+            test_shards: None,
+            coverage: None,
+            bench: None,
             runners: vec![
                 RunnerConfig {
                     runner_type: RunnerType::Local,
@@ -117,6 +165,10 @@ pub fn default_ci_config(build_systems: &[BuildSystemKind]) -> CiConfig {
         },
         BuildSystemKind::Cabal => CiConfig {
             skip_validate: false,
+            dockerfile: None,
+            test_shards: None,
+            coverage: None,
+            bench: None,
             runners: vec![
                 RunnerConfig {
                     runner_type: RunnerType::Local,
@@ -138,6 +190,10 @@ pub fn default_ci_config(build_systems: &[BuildSystemKind]) -> CiConfig {
         },
         BuildSystemKind::Node => CiConfig {
             skip_validate: false,
+            dockerfile: None,
+            test_shards: None,
+            coverage: None,
+            bench: None,
             runners: vec![
                 RunnerConfig {
                     runner_type: RunnerType::Local,
@@ -159,6 +215,10 @@ pub fn default_ci_config(build_systems: &[BuildSystemKind]) -> CiConfig {
         },
         BuildSystemKind::Unknown => CiConfig {
             skip_validate: true,
+            dockerfile: None,
+            test_shards: None,
+            coverage: None,
+            bench: None,
             runners: Vec::new(),
         },
     }
@@ -174,6 +234,7 @@ pub enum DistChannel {
     Brew,
     Ghcr,
     Binstall,
+    Aur,
 }
 
 impl std::fmt::Display for DistChannel {
@@ -185,6 +246,7 @@ impl std::fmt::Display for DistChannel {
             Self::Brew => write!(f, "brew"),
             Self::Ghcr => write!(f, "ghcr"),
             Self::Binstall => write!(f, "binstall"),
+            Self::Aur => write!(f, "aur"),
         }
     }
 }
@@ -200,10 +262,82 @@ pub struct DistConfig {
     pub brew_tap: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub brew_tap_path: Option<String>,
+    /// AUR package updated after each release (default: `{name}-bin`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aur_package: Option<String>,
+    /// Local clone of the AUR package repo (default: a fresh clone per release)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aur_repo_path: Option<String>,
+    /// Forges whose package registry also receives each published package
+    /// (npm on any forge, crates on codeberg)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forge_packages: Vec<crate::types::Forge>,
+    /// Sign release assets and attach SLSA provenance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing: Option<SigningConfig>,
 }
 
-/// Per-forge configuration overrides
+/// Signing tool for release artifacts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SigningTool {
+    #[default]
+    Cosign,
+    Minisign,
+}
+
+impl std::fmt::Display for SigningTool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cosign => write!(f, "cosign"),
+            Self::Minisign => write!(f, "minisign"),
+        }
+    }
+}
+
+/// `[dist.signing]`: how release artifacts are signed
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SigningConfig {
+    #[serde(default)]
+    pub tool: SigningTool,
+    /// Secrets store path of the private key (e.g. `signing/acme/cosign_key`)
+    pub key: String,
+    /// Secrets store path of the key's password, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Generate and attach SLSA provenance (default: true)
+    #[serde(default = "default_provenance")]
+    pub provenance: bool,
+}
+
+const fn default_provenance() -> bool {
+    true
+}
+
+/// Container image settings under `[image]`, for `workspace images`
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImageConfig {
+    /// Dockerfile relative to the repo root (default: Dockerfile,
+    /// Containerfile or docker/Dockerfile, whichever exists)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dockerfile: Option<String>,
+
+    /// Image name (default: the repo name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Registries to push to: ghcr, gitlab, codeberg, dockerhub, or a
+    /// registry host (default: the registries of the repo's forges)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub registries: Vec<String>,
+
+    /// Leave this repo out of `workspace images`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip: bool,
+}
+
+/// Per-forge configuration overrides
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ForgeConfig {
     /// Override organization for this forge
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -212,4 +346,172 @@ pub struct ForgeConfig {
     /// Git remote name for this forge
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remote: Option<String>,
+
+    /// Hostname of a self-hosted instance, e.g. `gitlab.mycompany.com`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+
+    /// Transport for fetches when it should differ from pushes, e.g.
+    /// `fetch = "https"` fetches over HTTPS and keeps pushing over SSH
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetch: Option<crate::git::Transport>,
+
+    /// Visibility on this forge when it differs from the repo's, e.g. a
+    /// private repo with a public Codeberg mirror
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<super::Visibility>,
+}
+
+/// How a repo's forges map onto git remotes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteLayout {
+    /// One remote per forge (`origin`, `codeberg`, ...)
+    #[default]
+    PerForge,
+    /// A single `origin` fetching from the first forge with one push URL per
+    /// forge, so a plain `git push` reaches every forge
+    MultiPush,
+}
+
+impl RemoteLayout {
+    pub const fn is_per_forge(&self) -> bool {
+        matches!(self, Self::PerForge)
+    }
+}
+
+/// Repository event a webhook fires on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// Branch and tag pushes
+    Push,
+    /// Pull requests (merge requests on GitLab)
+    PullRequest,
+    Issues,
+    Release,
+}
+
+impl WebhookEvent {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Push => "push",
+            Self::PullRequest => "pull_request",
+            Self::Issues => "issues",
+            Self::Release => "release",
+        }
+    }
+
+    /// Parse a GitHub/Gitea event name. Events hyperforge doesn't model
+    /// return `None`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "push" => Some(Self::Push),
+            "pull_request" => Some(Self::PullRequest),
+            "issues" => Some(Self::Issues),
+            "release" => Some(Self::Release),
+            _ => None,
+        }
+    }
+}
+
+/// One webhook under `[webhooks.<name>]`, kept on every forge the repo is on
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebhookConfig {
+    /// Payload URL; a forge hook with this URL is the one being managed
+    pub url: String,
+
+    /// Events that trigger it (default: push)
+    #[serde(default = "default_webhook_events")]
+    pub events: Vec<WebhookEvent>,
+
+    /// Environment variable holding the signing secret, read at sync time
+    /// so the secret never lands in the repo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_env: Option<String>,
+
+    /// Only keep the hook on these forges (default: every forge)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forges: Vec<String>,
+
+    /// Delete the hook from every forge instead of keeping it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub remove: bool,
+}
+
+fn default_webhook_events() -> Vec<WebhookEvent> {
+    vec![WebhookEvent::Push]
+}
+
+impl WebhookConfig {
+    /// Whether the hook belongs on the `forges` entry `forge`
+    pub fn applies_to(&self, forge: &str) -> bool {
+        let (base, _) = crate::config::HyperforgeConfig::split_account(forge);
+        self.forges.is_empty() || self.forges.iter().any(|f| f == forge || f == base)
+    }
+}
+
+/// One deploy key under `[ssh_deploy_keys.<name>]`, kept on every forge the
+/// repo is on under the title `<name>`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeployKeyConfig {
+    /// Path to the public key (`~/` and repo-relative paths allowed)
+    pub key: String,
+
+    /// Pull-only access (default: true)
+    #[serde(default = "default_read_only")]
+    pub read_only: bool,
+
+    /// Only keep the key on these forges (default: every forge)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forges: Vec<String>,
+
+    /// Remove the key from every forge instead of keeping it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub remove: bool,
+}
+
+const fn default_read_only() -> bool {
+    true
+}
+
+impl DeployKeyConfig {
+    /// Whether the key belongs on the `forges` entry `forge`
+    pub fn applies_to(&self, forge: &str) -> bool {
+        let (base, _) = crate::config::HyperforgeConfig::split_account(forge);
+        self.forges.is_empty() || self.forges.iter().any(|f| f == forge || f == base)
+    }
+
+    /// The public key file, resolved against the repo at `repo_path`
+    pub fn key_path(&self, repo_path: &std::path::Path) -> std::path::PathBuf {
+        match (self.key.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rel), Some(home)) => home.join(rel),
+            _ => repo_path.join(&self.key),
+        }
+    }
+}
+
+/// Protection for the default branch under `[branch_protection]`, applied
+/// by `workspace sync`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BranchProtectionConfig {
+    /// Branch to protect (default: the repo's default branch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+
+    /// Approving reviews a pull request needs before merging
+    #[serde(default)]
+    pub required_reviews: u32,
+
+    /// CI contexts that must pass before merging, e.g. `ci/build`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status_checks: Vec<String>,
+
+    /// Refuse force-pushes to the branch (default: true)
+    #[serde(default = "default_block_force_push")]
+    pub block_force_push: bool,
+}
+
+const fn default_block_force_push() -> bool {
+    true
 }
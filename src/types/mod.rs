@@ -1,6 +1,7 @@
 //! Core types for hyperforge
 
 pub mod config;
+pub mod policy;
 pub mod registry;
 pub mod repo;
 
@@ -10,30 +11,97 @@ use serde::{Deserialize, Serialize};
 pub use repo::Repo;
 pub use repo::RepoRecord;
 
+// Re-export metadata policy
+pub use policy::{fit_description, fit_repo, FittedDescription};
+
 // Re-export config types
-pub use config::{CiConfig, DistChannel, DistConfig, ForgeConfig};
+pub use config::{BranchProtectionConfig, CiConfig, DeployKeyConfig, DistChannel, DistConfig, ForgeConfig, RemoteLayout, WebhookConfig, WebhookEvent};
 
 // Re-export registry types
 pub use registry::{ContainerRegistry, ImageRef, RegistryAuth};
 
 /// Supported git forges
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
-#[serde(rename_all = "lowercase")]
+///
+/// Self-hosted Gitea/Forgejo instances are named by hostname in config files
+/// (`forges = ["github", "git.acme.dev"]`): any forge name containing a dot
+/// is a [`Forge::Gitea`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
 pub enum Forge {
     GitHub,
     Codeberg,
     GitLab,
+    /// A self-hosted Gitea or Forgejo instance
+    Gitea { host: String },
 }
 
 impl Forge {
-    /// Return the lowercase string representation used in config files and adapters.
-    pub const fn as_str(&self) -> &'static str {
+    /// Return the lowercase string representation used in config files and
+    /// adapters; the hostname for self-hosted forges.
+    pub fn as_str(&self) -> &str {
         match self {
             Self::GitHub => "github",
             Self::Codeberg => "codeberg",
             Self::GitLab => "gitlab",
+            Self::Gitea { host } => host,
         }
     }
+
+    pub const fn is_self_hosted(&self) -> bool {
+        matches!(self, Self::Gitea { .. })
+    }
+}
+
+impl std::fmt::Display for Forge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Forge {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        match name.as_str() {
+            "github" => Ok(Self::GitHub),
+            "codeberg" => Ok(Self::Codeberg),
+            "gitlab" => Ok(Self::GitLab),
+            host if host.contains('.') && !host.contains(['/', ':', ' ']) => Ok(Self::Gitea {
+                host: host.trim_end_matches('.').to_string(),
+            }),
+            _ => Err(format!(
+                "Invalid forge: {s}. Must be github, codeberg, gitlab, or a Gitea/Forgejo hostname"
+            )),
+        }
+    }
+}
+
+impl From<Forge> for String {
+    fn from(forge: Forge) -> Self {
+        forge.as_str().to_string()
+    }
+}
+
+impl TryFrom<String> for Forge {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl schemars::JsonSchema for Forge {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Forge".into()
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "description": "github, codeberg, gitlab, or the hostname of a Gitea/Forgejo instance"
+        })
+    }
 }
 
 /// Repository visibility
@@ -46,6 +114,14 @@ pub enum Visibility {
 }
 
 impl Visibility {
+    /// Return the lowercase string representation used in config files and adapters.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Private => "private",
+        }
+    }
+
     /// Parse a visibility string ("public" or "private"), case-insensitive.
     pub fn parse(s: &str) -> Result<Self, String> {
         match s.to_lowercase().as_str() {
@@ -0,0 +1,108 @@
+//! Per-forge constraints on repository metadata
+//!
+//! Forges reject a description that is too long (GitHub stops at 350
+//! characters) or that contains control characters. Descriptions are fitted
+//! to the target forge before create/update, so an over-long description
+//! becomes a warning instead of an API error halfway through a sync.
+
+use super::{Forge, Repo};
+
+impl Forge {
+    /// Longest repository description the forge accepts, in characters
+    pub const fn description_limit(&self) -> usize {
+        match self {
+            Self::GitHub => 350,
+            Self::GitLab => 2000,
+            Self::Codeberg | Self::Gitea { .. } => 2048,
+        }
+    }
+}
+
+/// A description rewritten to fit a forge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FittedDescription {
+    pub text: String,
+    /// What was changed: "control characters", "truncated"
+    pub changes: Vec<&'static str>,
+}
+
+/// Fit `description` to `forge`; `None` when the forge accepts it as is.
+///
+/// Control characters and line breaks become single spaces, and text past
+/// the forge's limit is cut at a word boundary with an ellipsis.
+pub fn fit_description(forge: &Forge, description: &str) -> Option<FittedDescription> {
+    let mut changes = Vec::new();
+
+    let mut text = description.to_string();
+    if description.chars().any(char::is_control) {
+        text = description
+            .split(|c: char| c.is_control())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        changes.push("control characters");
+    }
+
+    let limit = forge.description_limit();
+    if text.chars().count() > limit {
+        // Leave room for the ellipsis
+        let cut: String = text.chars().take(limit - 1).collect();
+        let cut = match cut.rfind(' ') {
+            Some(i) if i > cut.len() / 2 => &cut[..i],
+            _ => cut.as_str(),
+        };
+        text = format!("{}…", cut.trim_end());
+        changes.push("truncated");
+    }
+
+    (!changes.is_empty()).then_some(FittedDescription { text, changes })
+}
+
+/// `repo` as it should be on `forge`: its description fitted and its
+/// per-forge visibility applied, plus the fit when one was needed
+pub fn fit_repo(forge: &Forge, repo: &Repo) -> (Repo, Option<FittedDescription>) {
+    let fitted = repo.description.as_deref().and_then(|d| fit_description(forge, d));
+    let mut repo = repo.clone();
+    repo.visibility = repo.visibility_for(forge).clone();
+    if let Some(fit) = &fitted {
+        repo.description = Some(fit.text.clone());
+    }
+    (repo, fitted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_description_accepts_short_text() {
+        assert_eq!(fit_description(&Forge::GitHub, "A small tool"), None);
+    }
+
+    #[test]
+    fn test_fit_description_truncates_per_forge() {
+        let long = "word ".repeat(100);
+        let fit = fit_description(&Forge::GitHub, &long).unwrap();
+        assert_eq!(fit.changes, vec!["truncated"]);
+        assert!(fit.text.chars().count() <= 350);
+        assert!(fit.text.ends_with("word…"));
+        // Codeberg allows the same text
+        assert_eq!(fit_description(&Forge::Codeberg, &long), None);
+    }
+
+    #[test]
+    fn test_fit_description_strips_control_characters() {
+        let fit = fit_description(&Forge::Codeberg, "First line\nsecond\tline\r\n").unwrap();
+        assert_eq!(fit.text, "First line second line");
+        assert_eq!(fit.changes, vec!["control characters"]);
+    }
+
+    #[test]
+    fn test_fit_description_counts_characters_not_bytes() {
+        let text = "é".repeat(350);
+        assert_eq!(fit_description(&Forge::GitHub, &text), None);
+        let fit = fit_description(&Forge::GitHub, &"é".repeat(351)).unwrap();
+        assert_eq!(fit.text.chars().count(), 350);
+    }
+}
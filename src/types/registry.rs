@@ -14,6 +14,8 @@ pub enum ContainerRegistry {
     Codeberg,
     /// GitLab Container Registry (registry.gitlab.com)
     GitLab,
+    /// Docker Hub (docker.io)
+    DockerHub,
     /// Arbitrary OCI-compliant registry
     Custom(String),
 }
@@ -25,16 +27,38 @@ impl ContainerRegistry {
             Self::Ghcr => "ghcr.io",
             Self::Codeberg => "codeberg.org",
             Self::GitLab => "registry.gitlab.com",
+            Self::DockerHub => "docker.io",
             Self::Custom(host) => host.as_str(),
         }
     }
 
+    /// Server address for registry login; Docker Hub logs in via its index
+    pub fn server_address(&self) -> String {
+        match self {
+            Self::DockerHub => "https://index.docker.io/v1/".to_string(),
+            other => format!("https://{}", other.host()),
+        }
+    }
+
+    /// Parse a registry from config: `ghcr`, `gitlab`, `codeberg`,
+    /// `dockerhub`, a forge name, or any other registry host.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "ghcr" | "ghcr.io" | "github" => Self::Ghcr,
+            "gitlab" | "registry.gitlab.com" => Self::GitLab,
+            "codeberg" | "codeberg.org" => Self::Codeberg,
+            "dockerhub" | "docker.io" => Self::DockerHub,
+            host => Self::Custom(host.to_string()),
+        }
+    }
+
     /// The forge name used for token lookup (e.g. "github" -> "`github/{org}/packages_token`")
     pub const fn token_forge_name(&self) -> &str {
         match self {
             Self::Ghcr => "github",
             Self::Codeberg => "codeberg",
             Self::GitLab => "gitlab",
+            Self::DockerHub => "dockerhub",
             Self::Custom(_) => "custom",
         }
     }
@@ -46,6 +70,7 @@ impl From<&Forge> for ContainerRegistry {
             Forge::GitHub => Self::Ghcr,
             Forge::Codeberg => Self::Codeberg,
             Forge::GitLab => Self::GitLab,
+            Forge::Gitea { host } => Self::Custom(host.clone()),
         }
     }
 }
@@ -156,6 +181,17 @@ mod tests {
         assert_eq!(ContainerRegistry::Custom("my.reg.io".into()).host(), "my.reg.io");
     }
 
+    #[test]
+    fn test_container_registry_from_name() {
+        assert_eq!(ContainerRegistry::from_name("github"), ContainerRegistry::Ghcr);
+        assert_eq!(ContainerRegistry::from_name("dockerhub"), ContainerRegistry::DockerHub);
+        assert_eq!(ContainerRegistry::DockerHub.server_address(), "https://index.docker.io/v1/");
+        assert_eq!(
+            ContainerRegistry::from_name("harbor.internal"),
+            ContainerRegistry::Custom("harbor.internal".into())
+        );
+    }
+
     #[test]
     fn test_image_ref_full_name() {
         let img = ImageRef::new(ContainerRegistry::Ghcr, "hypermemetic", "substrate", "v1.0");
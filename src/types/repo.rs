@@ -1,6 +1,6 @@
 //! Repository types for LFORGE2
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -14,6 +14,29 @@ pub(crate) const fn is_false(b: &bool) -> bool {
     !*b
 }
 
+/// Whether two repo or org names name the same thing. Forges compare names
+/// case-insensitively but preserve case, so `MyRepo` and `myrepo` match.
+pub const fn same_name(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+/// Map key for a repo or org name under [`same_name`] rules
+pub fn name_key(name: &str) -> String {
+    name.to_ascii_lowercase()
+}
+
+/// Topics as forges store them: trimmed, lowercased, sorted and deduplicated
+pub fn normalize_topics<S: AsRef<str>>(topics: &[S]) -> Vec<String> {
+    let mut topics: Vec<String> = topics
+        .iter()
+        .map(|t| t.as_ref().trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    topics.sort();
+    topics.dedup();
+    topics
+}
+
 /// Repository configuration with origin and mirrors
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Repo {
@@ -41,9 +64,27 @@ pub struct Repo {
     #[serde(default, skip_serializing_if = "is_false")]
     pub staged_for_deletion: bool,
 
+    /// Whether this repo is retired: archived (read-only) on every forge
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub archived: bool,
+
     /// Default branch as reported by the forge (e.g. "main", "master")
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_branch: Option<String>,
+
+    /// Whether the token can read but not administer this repo on the
+    /// forge it was listed from
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub read_only: bool,
+
+    /// Topics (tags), normalized; empty leaves a forge's topics alone
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
+
+    /// Per-forge visibility where it differs from `visibility`, keyed by
+    /// forge name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub visibility_on: BTreeMap<String, Visibility>,
 }
 
 impl Repo {
@@ -57,16 +98,31 @@ impl Repo {
             mirrors: Vec::new(),
             protected: false,
             staged_for_deletion: false,
+            archived: false,
             default_branch: None,
+            read_only: false,
+            topics: Vec::new(),
+            visibility_on: BTreeMap::new(),
         }
     }
 
+    /// Visibility this repo should have on `forge`
+    pub fn visibility_for(&self, forge: &Forge) -> &Visibility {
+        self.visibility_on.get(forge.as_str()).unwrap_or(&self.visibility)
+    }
+
     /// Set repository description
     pub fn with_description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
         self
     }
 
+    /// Set repository topics
+    pub fn with_topics<S: AsRef<str>>(mut self, topics: &[S]) -> Self {
+        self.topics = normalize_topics(topics);
+        self
+    }
+
     /// Set repository visibility
     pub const fn with_visibility(mut self, visibility: Visibility) -> Self {
         self.visibility = visibility;
@@ -101,6 +157,12 @@ impl Repo {
         self
     }
 
+    /// Mark as archived
+    pub const fn with_archived(mut self, archived: bool) -> Self {
+        self.archived = archived;
+        self
+    }
+
     /// Get all forges (origin + mirrors)
     pub fn all_forges(&self) -> Vec<Forge> {
         let mut forges = vec![self.origin.clone()];
@@ -125,6 +187,10 @@ pub struct RepoRecord {
     /// Whether this repo is protected from deletion (soft-delete, privatize, purge)
     #[serde(default)]
     pub protected: bool,
+    /// Whether this repo is retired: sync archives it on every forge and
+    /// never privatizes or deletes it
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub archived: bool,
     #[serde(default)]
     pub managed: bool,
     #[serde(default)]
@@ -137,8 +203,15 @@ pub struct RepoRecord {
     /// Populated by `repo delete` and `workspace sync` so we don't re-privatize.
     #[serde(default, skip_serializing_if = "HashSet::is_empty")]
     pub privatized_on: HashSet<Forge>,
+    /// Forges where the token can read but not administer the repo.
+    /// Refreshed from each listing; sync skips mutations there.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub read_only_on: HashSet<Forge>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub previous_names: Vec<String>,
+    /// Topics (tags) synced to every forge
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
 
     // ── Config-first fields (absorbed from HyperforgeConfig) ──
 
@@ -179,6 +252,10 @@ impl RepoRecord {
         for m in &repo.mirrors {
             present_on.insert(m.clone());
         }
+        let mut read_only_on = HashSet::new();
+        if repo.read_only {
+            read_only_on.insert(repo.origin.clone());
+        }
         Self {
             name: repo.name.clone(),
             description: repo.description.clone(),
@@ -186,12 +263,15 @@ impl RepoRecord {
             default_branch: "main".to_string(),
             present_on,
             protected: repo.protected,
+            archived: repo.archived,
             managed: false,
             dismissed: false,
             deleted_from: Vec::new(),
             deleted_at: None,
             privatized_on: HashSet::new(),
+            read_only_on,
             previous_names: Vec::new(),
+            topics: repo.topics.clone(),
             local_path: None,
             forges: Vec::new(),
             ssh: HashMap::new(),
@@ -229,6 +309,29 @@ impl RepoRecord {
         if self.description.is_none() {
             self.description = config.description.clone();
         }
+        if self.topics.is_empty() {
+            self.topics = normalize_topics(&config.topics);
+        }
+        // Archiving is one-way here; `repo archive --unarchive` reverses it
+        self.archived |= config.archived;
+    }
+
+    /// Org a `forges` entry pins this repo to, from its forge override or
+    /// account (`github:acme`). `None` means the record's own org.
+    pub fn forge_org<'a>(&'a self, entry: &'a str) -> Option<&'a str> {
+        self.forge_config
+            .get(entry)
+            .and_then(|fc| fc.org.as_deref())
+            .or_else(|| crate::config::HyperforgeConfig::split_account(entry).1)
+    }
+
+    /// Self-hosted host a `forges` entry (or its base forge) is pinned to
+    /// by this record's forge override
+    pub fn forge_host(&self, entry: &str) -> Option<&str> {
+        let (base, _) = crate::config::HyperforgeConfig::split_account(entry);
+        [entry, base]
+            .iter()
+            .find_map(|f| self.forge_config.get(*f).and_then(|fc| fc.host.as_deref()))
     }
 
     /// Convert back to Repo for `ForgePort` compatibility
@@ -243,11 +346,21 @@ impl RepoRecord {
         let mut repo = Repo::new(self.name.clone(), origin)
             .with_visibility(if self.dismissed { Visibility::Private } else { self.visibility.clone() })
             .with_mirrors(mirrors)
-            .with_protected(self.protected);
+            .with_protected(self.protected)
+            .with_archived(self.archived);
         if let Some(ref desc) = self.description {
             repo = repo.with_description(desc);
         }
         repo.default_branch = Some(self.default_branch.clone());
+        repo.topics.clone_from(&self.topics);
+        if !self.dismissed {
+            repo.visibility_on = self
+                .forge_config
+                .iter()
+                .filter_map(|(forge, fc)| Some((forge.clone(), fc.visibility.clone()?)))
+                .collect();
+        }
+        repo.read_only = self.read_only_on.contains(&repo.origin);
         if self.dismissed {
             repo.staged_for_deletion = true;
         }
@@ -88,7 +88,7 @@ repo_name = "{name}"
 
 #[test]
 fn build_hub_has_correct_methods() {
-    let hub = BuildHub::new();
+    let hub = BuildHub::new(HyperforgeState::new());
     let methods: HashSet<&str> = hub.methods().into_iter().collect();
 
     let expected: HashSet<&str> = [
@@ -103,6 +103,13 @@ fn build_hub_has_correct_methods() {
         "run",
         "init_configs",
         "gitignore_sync",
+        "codeowners",
+        "templates_sync",
+        "files_sync",
+        "updates_config",
+        "validate_report",
+        "bench",
+        "verify_artifacts",
         "large_files",
         "repo_sizes",
         "dirty",
@@ -114,6 +121,7 @@ fn build_hub_has_correct_methods() {
         "dist_show",
         "dist_init",
         "schema",
+        "help",
     ]
     .into_iter()
     .collect();
@@ -130,15 +138,40 @@ fn workspace_hub_has_correct_methods() {
         "discover",
         "init",
         "check",
+        "status",
+        "thaw",
         "push_all",
+        "pull_all",
+        "mirror",
+        "remotes_converge",
+        "upstreams",
         "diff",
         "sync",
+        "plan",
+        "apply",
         "set_default_branch",
         "check_default_branch",
         "verify",
         "clone",
+        "unshallow",
+        "migrate_legacy",
         "move_repos",
+        "linear_history",
+        "visibility_audit",
+        "health",
+        "usage",
+        "snapshot",
+        "snapshot_diff",
+        "toolchains",
+        "approval_rules",
+        "updates",
+        "prs",
+        "files_pr",
+        "contribute",
+        "ci_status",
+        "images",
         "schema",
+        "help",
     ]
     .into_iter()
     .collect();
@@ -148,7 +181,7 @@ fn workspace_hub_has_correct_methods() {
 
 #[test]
 fn no_method_overlap_between_hubs() {
-    let build = BuildHub::new();
+    let build = BuildHub::new(HyperforgeState::new());
     let workspace = WorkspaceHub::new(HyperforgeState::new());
 
     let build_methods: HashSet<&str> = build.methods().into_iter().collect();
@@ -158,8 +191,8 @@ fn no_method_overlap_between_hubs() {
         .intersection(&workspace_methods)
         .collect();
 
-    // "schema" is expected on both — it's auto-generated
-    let non_schema_overlap: Vec<&&&str> = overlap.iter().filter(|m| ***m != "schema").collect();
+    // "schema" is expected on both — it's auto-generated; every hub has its own "help"
+    let non_schema_overlap: Vec<&&&str> = overlap.iter().filter(|m| !matches!(***m, "schema" | "help")).collect();
 
     assert!(
         non_schema_overlap.is_empty(),
@@ -169,7 +202,7 @@ fn no_method_overlap_between_hubs() {
 
 #[test]
 fn build_hub_schema_metadata() {
-    let hub = BuildHub::new();
+    let hub = BuildHub::new(HyperforgeState::new());
     let schema = hub.plugin_schema();
 
     assert_eq!(schema.namespace, "build");
@@ -199,7 +232,11 @@ fn root_hub_lists_build_as_child() {
         child_namespaces.contains(&"workspace"),
         "root hub should list 'workspace' as child"
     );
-    assert_eq!(child_namespaces.len(), 3);
+    assert!(
+        child_namespaces.contains(&"registry"),
+        "root hub should list 'registry' as child"
+    );
+    assert_eq!(child_namespaces.len(), 4);
 }
 
 // ============================================================================
@@ -224,6 +261,15 @@ async fn root_hub_routes_to_workspace_child() {
     assert_eq!(child.unwrap().router_namespace(), "workspace");
 }
 
+#[tokio::test]
+async fn workspace_hub_routes_to_campaigns_child() {
+    let hub = WorkspaceHub::new(HyperforgeState::new());
+
+    let child = hub.get_child("campaigns").await;
+    assert!(child.is_some(), "get_child('campaigns') should return Some");
+    assert_eq!(child.unwrap().router_namespace(), "campaigns");
+}
+
 #[tokio::test]
 async fn root_hub_rejects_unknown_child() {
     let hub = HyperforgeHub::new();
@@ -261,6 +307,37 @@ async fn route_build_analyze_on_test_workspace() {
     assert!(has_info, "analyze should produce Info events");
 }
 
+#[tokio::test]
+async fn route_build_analyze_json_export() {
+    let tmp = make_test_workspace();
+    let hub = Arc::new(DynamicHub::new("test").register(HyperforgeHub::new()));
+
+    let stream = hub
+        .route(
+            "hyperforge.build.analyze",
+            serde_json::json!({ "path": tmp.path().to_str().unwrap(), "format": "json" }),
+            None,
+        )
+        .await
+        .expect("route should succeed");
+
+    let events = collect_events(stream).await;
+    let content = events
+        .iter()
+        .find_map(|e| match e {
+            HyperforgeEvent::DepGraphExport { format, content } if format == "json" => {
+                Some(content.clone())
+            }
+            _ => None,
+        })
+        .expect("analyze --format json should emit a DepGraphExport");
+
+    let graph: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(graph["edges"][0]["from"], "beta");
+    assert_eq!(graph["edges"][0]["to"], "alpha");
+    assert_eq!(graph["tiers"], serde_json::json!([["alpha"], ["beta"]]));
+}
+
 #[tokio::test]
 async fn route_build_detect_name_mismatches() {
     let tmp = make_test_workspace();
@@ -456,6 +533,59 @@ async fn route_build_bump_dry_run() {
     );
 }
 
+#[tokio::test]
+async fn route_workspace_toolchains_dry_run() {
+    let tmp = make_test_workspace();
+    std::fs::create_dir_all(tmp.path().join(".hyperforge")).unwrap();
+    std::fs::write(
+        tmp.path().join(".hyperforge/workspace.toml"),
+        "[toolchains]\nrust = \"1.85.0\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        tmp.path().join("alpha/rust-toolchain.toml"),
+        "[toolchain]\nchannel = \"1.85.0\"\n",
+    )
+    .unwrap();
+    let hub = Arc::new(DynamicHub::new("test").register(HyperforgeHub::new()));
+
+    let stream = hub
+        .route(
+            "hyperforge.workspace.toolchains",
+            serde_json::json!({
+                "path": tmp.path().to_str().unwrap(),
+                "dry_run": true,
+            }),
+            None,
+        )
+        .await
+        .expect("route should succeed");
+
+    let events = collect_events(stream).await;
+
+    let pins: Vec<_> = events
+        .iter()
+        .filter_map(|e| match e {
+            HyperforgeEvent::ToolchainPin {
+                repo_name, action, ..
+            } => Some((repo_name.clone(), action.clone())),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(
+        pins,
+        vec![
+            ("alpha".to_string(), "unchanged".to_string()),
+            ("beta".to_string(), "created".to_string()),
+        ]
+    );
+    assert!(
+        !tmp.path().join("beta/rust-toolchain.toml").exists(),
+        "dry_run should not write files"
+    );
+}
+
 // ============================================================================
 // Workspace methods still work
 // ============================================================================
@@ -501,6 +631,63 @@ async fn route_workspace_discover_still_works() {
     assert!(has_summary, "discover should produce WorkspaceSummary");
 }
 
+#[tokio::test]
+async fn configured_json_output_drops_narration_from_any_method() {
+    let tmp = make_test_workspace();
+    let config = tempfile::TempDir::new().unwrap();
+    std::fs::write(config.path().join("config.toml"), "[output]\nformat = \"json\"\n").unwrap();
+    let state = HyperforgeState {
+        config_dir: config.path().to_path_buf(),
+        ..HyperforgeState::new()
+    };
+
+    let events: Vec<HyperforgeEvent> = WorkspaceHub::new(state)
+        .discover(tmp.path().to_str().unwrap().to_string(), None, None, None, None)
+        .await
+        .collect()
+        .await;
+
+    assert!(events.iter().any(|e| matches!(e, HyperforgeEvent::WorkspaceSummary { .. })));
+    assert!(
+        !events.iter().any(|e| matches!(e, HyperforgeEvent::Info { .. })),
+        "[output] format = \"json\" should drop info events"
+    );
+}
+
+#[tokio::test]
+async fn route_workspace_help_describes_methods() {
+    let hub = Arc::new(DynamicHub::new("test").register(HyperforgeHub::new()));
+
+    let stream = hub
+        .route("hyperforge.workspace.help", serde_json::json!({ "method": "status" }), None)
+        .await
+        .expect("route should succeed");
+    let events = collect_events(stream).await;
+
+    assert_eq!(events.len(), 1, "one entry for one method, got: {events:?}");
+    let HyperforgeEvent::MethodHelp { hub: namespace, method, params, example, .. } = &events[0] else {
+        panic!("expected method_help, got: {:?}", events[0]);
+    };
+    assert_eq!((namespace.as_str(), method.as_str()), ("workspace", "status"));
+    assert!(params[0].required && params[0].name == "path");
+    assert!(params.iter().any(|p| p.name == "fetch" && !p.required && p.ty == "boolean"));
+    assert_eq!(example, "synapse substrate hyperforge workspace status --path <path>");
+
+    let stream = hub
+        .route("hyperforge.build.help", serde_json::json!({}), None)
+        .await
+        .expect("route should succeed");
+    let events = collect_events(stream).await;
+    assert_eq!(events.len(), BuildHub::new(HyperforgeState::new()).methods().len());
+
+    let stream = hub
+        .route("hyperforge.workspace.help", serde_json::json!({ "method": "nope" }), None)
+        .await
+        .expect("route should succeed");
+    let events = collect_events(stream).await;
+    assert!(matches!(events[..], [HyperforgeEvent::Error { .. }]));
+}
+
 // ============================================================================
 // Utils unit tests
 // ============================================================================
@@ -0,0 +1,129 @@
+//! Scale benchmarks for `LocalForge` and sync diffing on large orgs
+//!
+//! Ignored by default; run with:
+//!   `cargo test --release --test scale_test -- --ignored --nocapture`
+//!
+//! Each benchmark prints its timings and, on Linux, the process's peak RSS,
+//! and fails if sync of a 5k-repo org exceeds the time or memory budget.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use hyperforge::adapters::{ForgePort, LocalForge};
+use hyperforge::services::SymmetricSyncService;
+use hyperforge::types::{Forge, Repo, Visibility};
+
+const ORG: &str = "bigorg";
+const REPO_COUNT: usize = 5_000;
+const TIME_BUDGET: Duration = Duration::from_secs(30);
+const PEAK_RSS_BUDGET_KB: u64 = 256 * 1024;
+
+/// Peak resident set size of this process in KiB (`VmHWM`), Linux only
+fn peak_rss_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .and_then(|v| v.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+fn synthetic_repo(i: usize) -> Repo {
+    let mirrors = match i % 3 {
+        0 => vec![Forge::Codeberg, Forge::GitLab],
+        1 => vec![Forge::Codeberg],
+        _ => vec![],
+    };
+    Repo::new(format!("repo-{i:05}"), Forge::GitHub)
+        .with_description(format!("Synthetic repository number {i} used for scale testing"))
+        .with_visibility(if i.is_multiple_of(5) { Visibility::Private } else { Visibility::Public })
+        .with_mirrors(mirrors)
+}
+
+async fn populated_forge(count: usize, describe: impl Fn(usize) -> Repo) -> Arc<LocalForge> {
+    let forge = Arc::new(LocalForge::new(ORG));
+    for i in 0..count {
+        forge.create_repo(ORG, &describe(i)).await.unwrap();
+    }
+    forge
+}
+
+#[tokio::test]
+#[ignore = "benchmark; run explicitly with --ignored"]
+async fn bench_sync_5k_repo_org() {
+    let started = Instant::now();
+    let local = populated_forge(REPO_COUNT, synthetic_repo).await;
+
+    // A forge that's mostly in sync: every 10th repo is missing, every 7th differs
+    let remote = populated_forge(REPO_COUNT, |i| {
+        let repo = synthetic_repo(i);
+        if i.is_multiple_of(7) { repo.with_description("stale") } else { repo }
+    })
+    .await;
+    for i in (0..REPO_COUNT).step_by(10) {
+        remote.remove_repo(&format!("repo-{i:05}")).unwrap();
+    }
+    let populated = started.elapsed();
+
+    let service = SymmetricSyncService::new();
+    let diff_started = Instant::now();
+    let diff = service.diff(local.clone(), remote.clone(), ORG).await.unwrap();
+    let diffed = diff_started.elapsed();
+    assert_eq!(diff.ops.len(), REPO_COUNT);
+    assert_eq!(diff.to_create().len(), REPO_COUNT / 10);
+    drop(diff);
+
+    // Origin-first sync to three forges, actually applying the operations
+    let mut targets: HashMap<String, Arc<dyn ForgePort>> = HashMap::new();
+    targets.insert("github".to_string(), remote.clone());
+    targets.insert("codeberg".to_string(), Arc::new(LocalForge::new(ORG)));
+    targets.insert("gitlab".to_string(), Arc::new(LocalForge::new(ORG)));
+    let sync_started = Instant::now();
+    let diffs = service
+        .sync_with_origins(local.clone(), targets, ORG, false)
+        .await
+        .unwrap();
+    let synced = sync_started.elapsed();
+    assert_eq!(diffs.len(), 3);
+    assert!(remote.list_repos(ORG).await.unwrap().len() >= REPO_COUNT);
+
+    let records_started = Instant::now();
+    let unmanaged = local.records().unwrap().iter().filter(|r| !r.managed).count();
+    let scanned = records_started.elapsed();
+    assert!(unmanaged <= REPO_COUNT);
+
+    let total = started.elapsed();
+    println!(
+        "{REPO_COUNT} repos: populate {populated:?}, diff {diffed:?}, sync_with_origins {synced:?}, record scan {scanned:?}, total {total:?}"
+    );
+    assert!(total < TIME_BUDGET, "5k-repo sync took {total:?}");
+
+    if let Some(peak) = peak_rss_kb() {
+        println!("peak RSS: {} MiB", peak / 1024);
+        assert!(peak < PEAK_RSS_BUDGET_KB, "peak RSS {peak} KiB over budget");
+    }
+}
+
+#[tokio::test]
+#[ignore = "benchmark; run explicitly with --ignored"]
+async fn bench_local_forge_yaml_roundtrip_5k() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let path = tmp.path().join("repos.yaml");
+    let forge = LocalForge::with_config_path(ORG, path.clone());
+    for i in 0..REPO_COUNT {
+        forge.create_repo(ORG, &synthetic_repo(i)).await.unwrap();
+    }
+
+    let started = Instant::now();
+    forge.save_to_yaml().await.unwrap();
+    let saved = started.elapsed();
+
+    let reloaded = LocalForge::with_config_path(ORG, path);
+    let started = Instant::now();
+    reloaded.load_from_yaml().await.unwrap();
+    let loaded = started.elapsed();
+
+    assert_eq!(reloaded.record_count().unwrap(), REPO_COUNT);
+    println!("{REPO_COUNT} repos: save {saved:?}, load {loaded:?}");
+    assert!(saved + loaded < TIME_BUDGET);
+}